@@ -34,6 +34,7 @@ pub(super) async fn init_core(
     authz_hook_override: Option<SharedAuthzHook>,
 ) -> RuntimeResult<CoreLayer> {
     let profile = ProfileBootstrap::get()?;
+    systemprompt_ai::discover_local_models(&profile.providers).await;
     let app_paths = Arc::new(AppPaths::from_profile(
         &profile.paths,
         profile.path_resolution(),
@@ -60,6 +61,9 @@ pub(super) async fn init_core(
     validate_write_pool_is_primary(&database).await?;
 
     let authz_audit_pool = database.write_pool_arc().ok();
+    if let Some(pool) = authz_audit_pool.clone() {
+        systemprompt_security::policy::install_shared_rate_limiter(pool);
+    }
     let authz_hook = systemprompt_security::authz::build_authz_hook(
         profile.governance.as_ref(),
         authz_audit_pool,
@@ -1,11 +1,13 @@
 //! Periodic database-cleanup job: orphan logs, old logs, expired OAuth
-//! artifacts.
+//! artifacts, expired gateway response-cache entries.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 use async_trait::async_trait;
+use systemprompt_ai::repository::AiResponseCacheRepository;
 use systemprompt_database::{CleanupRepository, DbPool};
+use systemprompt_security::policy::GovernanceRateLimitRepository;
 use systemprompt_traits::{Job, JobContext, JobResult, ProviderError, ProviderResult};
 use tracing::{debug, info};
 
@@ -23,7 +25,7 @@ impl Job for DatabaseCleanupJob {
     }
 
     fn description(&self) -> &'static str {
-        "Cleans up orphaned logs, old logs (parameter log_retention_days, default 30), and expired OAuth tokens; log deletion requires enforce"
+        "Cleans up orphaned logs, old logs (parameter log_retention_days, default 30), expired OAuth tokens, device codes and federated sign-ins, expired gateway response-cache entries, and expired shared rate-limit charges; log deletion requires enforce"
     }
 
     fn schedule(&self) -> &'static str {
@@ -80,6 +82,20 @@ impl Job for DatabaseCleanupJob {
         let oauth = Self::delete_expired_oauth(&cleanup_repo).await?;
         total_deleted += oauth.total();
 
+        let response_cache = AiResponseCacheRepository::new(&db_pool)
+            .map_err(|e| SchedulerError::Internal(e.to_string()))?
+            .delete_expired()
+            .await
+            .map_err(|e| ProviderError::from(SchedulerError::Internal(e.to_string())))?;
+        total_deleted += response_cache;
+
+        let rate_limit_charges =
+            GovernanceRateLimitRepository::from_pool(std::sync::Arc::clone(&write_pool))
+                .delete_expired()
+                .await
+                .map_err(|e| ProviderError::from(SchedulerError::Internal(e.to_string())))?;
+        total_deleted += rate_limit_charges;
+
         let duration_ms = start_time.elapsed().as_millis() as u64;
 
         debug!(
@@ -90,7 +106,11 @@ impl Job for DatabaseCleanupJob {
             oauth_tokens = oauth.tokens,
             oauth_state_bindings = oauth.state_bindings,
             oauth_jti_revocations = oauth.jti_revocations,
+            oauth_device_codes = oauth.device_codes,
+            oauth_federated_logins = oauth.federated_logins,
             id_jag_replays = oauth.id_jag_replays,
+            response_cache = response_cache,
+            rate_limit_charges = rate_limit_charges,
             duration_ms = duration_ms,
             "Job completed"
         );
@@ -106,12 +126,20 @@ struct OauthCleanupCounts {
     tokens: u64,
     state_bindings: u64,
     jti_revocations: u64,
+    device_codes: u64,
+    federated_logins: u64,
     id_jag_replays: u64,
 }
 
 impl OauthCleanupCounts {
     const fn total(&self) -> u64 {
-        self.codes + self.tokens + self.state_bindings + self.jti_revocations + self.id_jag_replays
+        self.codes
+            + self.tokens
+            + self.state_bindings
+            + self.jti_revocations
+            + self.device_codes
+            + self.federated_logins
+            + self.id_jag_replays
     }
 }
 
@@ -135,6 +163,14 @@ impl DatabaseCleanupJob {
             .delete_expired_oauth_jti_revocations()
             .await
             .map_err(|e| ProviderError::from(SchedulerError::from(e)))?;
+        let device_codes = cleanup_repo
+            .delete_expired_oauth_device_codes()
+            .await
+            .map_err(|e| ProviderError::from(SchedulerError::from(e)))?;
+        let federated_logins = cleanup_repo
+            .delete_expired_oauth_federated_logins()
+            .await
+            .map_err(|e| ProviderError::from(SchedulerError::from(e)))?;
         let id_jag_replays = cleanup_repo
             .delete_expired_id_jag_replays()
             .await
@@ -145,6 +181,8 @@ impl DatabaseCleanupJob {
             tokens,
             state_bindings,
             jti_revocations,
+            device_codes,
+            federated_logins,
             id_jag_replays,
         })
     }
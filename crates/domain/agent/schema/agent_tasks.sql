@@ -27,6 +27,8 @@ CREATE TABLE IF NOT EXISTS agent_tasks (
 
     version BIGINT NOT NULL DEFAULT 0,
 
+    parent_task_id TEXT REFERENCES agent_tasks(task_id) ON DELETE SET NULL,
+
     created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
     updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
 
@@ -46,6 +48,7 @@ CREATE INDEX IF NOT EXISTS idx_agent_tasks_session_id ON agent_tasks(session_id)
 CREATE INDEX IF NOT EXISTS idx_agent_tasks_trace_id ON agent_tasks(trace_id);
 CREATE INDEX IF NOT EXISTS idx_agent_tasks_user_created ON agent_tasks(user_id, created_at);
 CREATE INDEX IF NOT EXISTS idx_agent_tasks_agent_name ON agent_tasks(agent_name);
+CREATE INDEX IF NOT EXISTS idx_agent_tasks_parent_task_id ON agent_tasks(parent_task_id) WHERE parent_task_id IS NOT NULL;
 
 CREATE INDEX IF NOT EXISTS idx_agent_tasks_started_at ON agent_tasks(started_at DESC);
 CREATE INDEX IF NOT EXISTS idx_agent_tasks_completed_at ON agent_tasks(completed_at DESC);
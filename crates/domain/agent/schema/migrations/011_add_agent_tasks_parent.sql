@@ -0,0 +1,7 @@
+-- Links a task started by agent-to-agent delegation to the task that
+-- delegated it.
+ALTER TABLE agent_tasks ADD COLUMN IF NOT EXISTS parent_task_id TEXT
+    REFERENCES agent_tasks(task_id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_agent_tasks_parent_task_id
+    ON agent_tasks(parent_task_id) WHERE parent_task_id IS NOT NULL;
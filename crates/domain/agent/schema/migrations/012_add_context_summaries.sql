@@ -0,0 +1,14 @@
+-- Rolling summaries of a context's older messages, written by agents whose
+-- compaction strategy is summarize.
+CREATE TABLE IF NOT EXISTS context_summaries (
+    id SERIAL PRIMARY KEY,
+    context_id TEXT NOT NULL REFERENCES user_contexts(context_id) ON DELETE CASCADE,
+    agent_name TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    covered_messages INTEGER NOT NULL CHECK (covered_messages > 0),
+    token_estimate INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_context_summaries_context_agent
+    ON context_summaries(context_id, agent_name, created_at DESC);
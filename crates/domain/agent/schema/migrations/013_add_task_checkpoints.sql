@@ -0,0 +1,15 @@
+-- The message that started each in-flight task run, kept until the run ends
+-- so a restarted agent can resume or fail the tasks it left working.
+CREATE TABLE IF NOT EXISTS task_checkpoints (
+    task_id TEXT PRIMARY KEY REFERENCES agent_tasks(task_id) ON DELETE CASCADE,
+    message JSONB NOT NULL,
+    user_type TEXT NOT NULL,
+    resume_attempts INTEGER NOT NULL DEFAULT 0 CHECK (resume_attempts >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE OR REPLACE TRIGGER update_task_checkpoints_updated_at
+    BEFORE UPDATE ON task_checkpoints
+    FOR EACH ROW
+    EXECUTE FUNCTION update_timestamp_trigger();
@@ -0,0 +1,4 @@
+-- How far an iterative run had got when it suspended for approval, so the
+-- resumed loop keeps counting toward its iteration and repeated-call limits.
+ALTER TABLE task_tool_approvals ADD COLUMN IF NOT EXISTS iteration INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE task_tool_approvals ADD COLUMN IF NOT EXISTS call_counts JSONB NOT NULL DEFAULT '{}';
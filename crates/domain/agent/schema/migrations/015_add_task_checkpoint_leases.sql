@@ -0,0 +1,5 @@
+-- The recovery lease on a run checkpoint: which agent process claimed the
+-- task and until when, so of several replicas restarting together only one
+-- resumes or fails each orphaned task.
+ALTER TABLE task_checkpoints ADD COLUMN IF NOT EXISTS claimed_by TEXT;
+ALTER TABLE task_checkpoints ADD COLUMN IF NOT EXISTS claimed_until TIMESTAMPTZ;
@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS task_tool_approvals (
+    approval_id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL REFERENCES agent_tasks(task_id) ON DELETE CASCADE,
+    reasoning TEXT NOT NULL DEFAULT '',
+    calls JSONB NOT NULL,
+    messages JSONB NOT NULL,
+    iteration INTEGER NOT NULL DEFAULT 1,
+    call_counts JSONB NOT NULL DEFAULT '{}',
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'rejected')),
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    decided_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_tool_approvals_task_id ON task_tool_approvals(task_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_task_tool_approvals_one_pending
+    ON task_tool_approvals(task_id) WHERE status = 'pending';
@@ -74,6 +74,15 @@ fn context_schemas() -> Vec<SchemaDefinition> {
             include_str!("../schema/context_agents.sql"),
         )
         .with_required_columns(vec!["id".into(), "context_id".into(), "agent_name".into()]),
+        SchemaDefinition::new(
+            "context_summaries",
+            include_str!("../schema/context_summaries.sql"),
+        )
+        .with_required_columns(vec![
+            "id".into(),
+            "context_id".into(),
+            "covered_messages".into(),
+        ]),
         SchemaDefinition::new(
             "context_notifications",
             include_str!("../schema/context_notifications.sql"),
@@ -102,6 +111,24 @@ fn task_tracking_schemas() -> Vec<SchemaDefinition> {
             "task_id".into(),
             "step_type".into(),
         ]),
+        SchemaDefinition::new(
+            "task_tool_approvals",
+            include_str!("../schema/task_tool_approvals.sql"),
+        )
+        .with_required_columns(vec![
+            "approval_id".into(),
+            "task_id".into(),
+            "status".into(),
+        ]),
+        SchemaDefinition::new(
+            "task_checkpoints",
+            include_str!("../schema/task_checkpoints.sql"),
+        )
+        .with_required_columns(vec![
+            "task_id".into(),
+            "message".into(),
+            "resume_attempts".into(),
+        ]),
     ]
 }
 
@@ -20,8 +20,8 @@ pub use push_notification::{
 };
 pub use requests::{
     A2aJsonRpcRequest, A2aParseError, A2aRequest, A2aRequestParams, A2aResponse,
-    CancelTaskResponse, GetAuthenticatedExtendedCardResponse, GetTaskResponse,
-    MessageSendConfiguration, MessageSendParams, SendMessageResponse, SendStreamingMessageResponse,
-    TaskIdParams, TaskNotCancelableError, TaskNotFoundError, TaskQueryParams,
-    UnsupportedOperationError,
+    CancelTaskResponse, GetAuthenticatedExtendedCardResponse, GetTaskResponse, ListTasksParams,
+    ListTasksResult, MessageSendConfiguration, MessageSendParams, SendMessageResponse,
+    SendStreamingMessageResponse, TaskIdParams, TaskNotCancelableError, TaskNotFoundError,
+    TaskQueryParams, UnsupportedOperationError,
 };
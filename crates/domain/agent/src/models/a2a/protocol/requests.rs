@@ -5,6 +5,9 @@
 //! [`A2aJsonRpcRequest::parse_request`] dispatcher into [`A2aRequestParams`],
 //! the [`A2aResponse`] result variants, and the protocol error payloads.
 //!
+//! `ListTasks` pages newest first; its `pageToken` is the id of the last task
+//! on the previous page.
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
@@ -15,8 +18,9 @@ use super::push_notification::{
 };
 use crate::models::a2a::jsonrpc::{JsonRpcResponse, RequestId};
 use crate::models::a2a::{AgentCard, Task, TaskState};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use systemprompt_identifiers::TaskId;
+use systemprompt_identifiers::{ContextId, TaskId};
 use systemprompt_models::a2a::methods;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -46,6 +50,26 @@ pub struct TaskIdParams {
     pub id: TaskId,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTasksParams {
+    pub context_id: Option<ContextId>,
+    pub status: Option<TaskState>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub page_size: Option<u32>,
+    pub page_token: Option<TaskId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTasksResult {
+    pub tasks: Vec<Task>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<TaskId>,
+    pub page_size: u32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct A2aRequest {
     pub method: String,
@@ -81,6 +105,7 @@ impl A2aJsonRpcRequest {
         match self.method.as_str() {
             methods::SEND_MESSAGE => Ok(A2aRequestParams::SendMessage(self.parse_params()?)),
             methods::GET_TASK => Ok(A2aRequestParams::GetTask(self.parse_params()?)),
+            methods::LIST_TASKS => Ok(A2aRequestParams::ListTasks(self.parse_params()?)),
             methods::CANCEL_TASK => Ok(A2aRequestParams::CancelTask(self.parse_params()?)),
             methods::GET_EXTENDED_AGENT_CARD => Ok(A2aRequestParams::GetAuthenticatedExtendedCard(
                 self.parse_params()?,
@@ -121,6 +146,7 @@ impl A2aJsonRpcRequest {
 pub enum A2aRequestParams {
     SendMessage(MessageSendParams),
     GetTask(TaskQueryParams),
+    ListTasks(ListTasksParams),
     CancelTask(TaskIdParams),
     GetAuthenticatedExtendedCard(serde_json::Value),
     SendStreamingMessage(MessageSendParams),
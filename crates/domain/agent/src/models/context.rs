@@ -1,5 +1,6 @@
 //! Conversational context models: contexts, their messages, per-user views
-//! with aggregate statistics, and create/update request shapes.
+//! with aggregate statistics, rolling history summaries, and create/update
+//! request shapes.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -27,6 +28,29 @@ pub struct ContextDetail {
     pub messages: Vec<ContextMessage>,
 }
 
+/// A summary of a context's oldest messages, standing in for them when the
+/// history is replayed to an agent whose compaction strategy summarizes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextSummary {
+    pub id: i32,
+    pub context_id: ContextId,
+    pub agent_name: String,
+    pub summary: String,
+    /// Leading compactable messages of the history this summary replaces.
+    pub covered_messages: i32,
+    pub token_estimate: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NewContextSummary<'a> {
+    pub context_id: &'a ContextId,
+    pub agent_name: &'a str,
+    pub summary: &'a str,
+    pub covered_messages: i32,
+    pub token_estimate: i32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContextStateEvent {
@@ -8,6 +8,10 @@
 //! - [`external_integrations`] — descriptors for downstream MCP / OAuth
 //!   integrations
 //! - [`runtime`] — runtime metadata describing a live agent process
+//! - [`task_checkpoint`] — run checkpoints and the tasks a restarted agent left
+//!   working
+//! - [`tool_approval`] — human approval requests and decisions for gated tool
+//!   calls
 //! - [`web`] — request/response DTOs for the HTTP admin surface
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
@@ -19,6 +23,8 @@ pub mod context;
 pub mod database_rows;
 pub mod external_integrations;
 pub mod runtime;
+pub mod task_checkpoint;
+pub mod tool_approval;
 pub mod web;
 
 pub use a2a::{
@@ -30,9 +36,16 @@ pub use agent_info::AgentInfo;
 
 pub use runtime::AgentRuntimeInfo;
 
+pub use task_checkpoint::{OrphanedTask, TaskCheckpoint};
+
+pub use tool_approval::{
+    LoopProgress, PendingToolApproval, ToolApprovalCall, ToolApprovalDecision, ToolApprovalRequest,
+    ToolApprovalStatus,
+};
+
 pub use context::{
-    ContextDetail, ContextKind, ContextMessage, CreateContextRequest, UpdateContextRequest,
-    UserContext, UserContextWithStats,
+    ContextDetail, ContextKind, ContextMessage, ContextSummary, CreateContextRequest,
+    NewContextSummary, UpdateContextRequest, UserContext, UserContextWithStats,
 };
 
 pub use systemprompt_models::{
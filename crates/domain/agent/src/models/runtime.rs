@@ -5,7 +5,9 @@
 
 use serde::{Deserialize, Serialize};
 use systemprompt_models::ai::ToolModelOverrides;
-use systemprompt_models::services::PluginComponentRef;
+use systemprompt_models::services::{
+    AgentCompactionConfig, AgentExecutionConfig, AgentRecoveryConfig, PluginComponentRef,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +25,14 @@ pub struct AgentRuntimeInfo {
     pub skills: PluginComponentRef,
     #[serde(default)]
     pub tool_model_overrides: ToolModelOverrides,
+    #[serde(default)]
+    pub execution: AgentExecutionConfig,
+    #[serde(default)]
+    pub compaction: AgentCompactionConfig,
+    #[serde(default)]
+    pub recovery: AgentRecoveryConfig,
+    #[serde(default)]
+    pub delegates: Vec<String>,
 }
 
 impl From<systemprompt_models::AgentConfig> for AgentRuntimeInfo {
@@ -39,6 +49,10 @@ impl From<systemprompt_models::AgentConfig> for AgentRuntimeInfo {
             max_output_tokens: config.metadata.max_output_tokens,
             skills: config.metadata.skills,
             tool_model_overrides: config.metadata.tool_model_overrides,
+            execution: config.metadata.execution,
+            compaction: config.metadata.compaction,
+            recovery: config.metadata.recovery,
+            delegates: config.metadata.delegates,
         }
     }
 }
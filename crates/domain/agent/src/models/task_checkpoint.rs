@@ -0,0 +1,36 @@
+//! What a restarted agent needs to pick up a task it left working.
+//!
+//! Every run records a [`TaskCheckpoint`] — the message that started it and
+//! the caller's user type — when it begins, and drops it when it ends. On
+//! startup the agent lists its [`OrphanedTask`]s; one that still has a
+//! checkpoint can be resumed, one without it can only be failed. No bearer
+//! token is stored: a resumed run is issued a fresh one.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use chrono::{DateTime, Utc};
+use systemprompt_identifiers::{ContextId, SessionId, TaskId, TraceId, UserId};
+use systemprompt_models::auth::UserType;
+
+use super::a2a::{Message, TaskState};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskCheckpoint {
+    pub task_id: TaskId,
+    pub message: Message,
+    pub user_type: UserType,
+    /// Times the task has already been resumed after a restart.
+    pub resume_attempts: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanedTask {
+    pub task_id: TaskId,
+    pub context_id: ContextId,
+    pub user_id: Option<UserId>,
+    pub session_id: Option<SessionId>,
+    pub trace_id: Option<TraceId>,
+    pub state: TaskState,
+    pub updated_at: DateTime<Utc>,
+}
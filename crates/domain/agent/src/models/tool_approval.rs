@@ -0,0 +1,177 @@
+//! Human approval of tool calls the MCP deployment marks `requires_approval`.
+//!
+//! When a plan calls such a tool, the planned strategy suspends the task in
+//! `input-required` and stores the plan as a [`PendingToolApproval`]. The
+//! agent's status message carries a [`ToolApprovalRequest`] as a data part;
+//! the client answers with a follow-up message on the same task whose data
+//! part is a [`ToolApprovalDecision`], which either runs the stored calls or
+//! tells the model they were declined.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use systemprompt_identifiers::{ContextId, MessageId, TaskId};
+use systemprompt_models::AiMessage;
+use systemprompt_models::ai::PlannedToolCall;
+
+use super::a2a::{DataPart, Message, MessageRole, Part, TextPart};
+
+/// `kind` of the data part announcing a pending approval.
+pub const TOOL_APPROVAL_REQUEST_KIND: &str = "tool-approval-request";
+/// `kind` of the data part answering one.
+pub const TOOL_APPROVAL_DECISION_KIND: &str = "tool-approval-decision";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolApprovalCall {
+    pub tool_name: String,
+    pub arguments: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(
+    rename_all = "camelCase",
+    tag = "kind",
+    rename = "tool-approval-request"
+)]
+pub struct ToolApprovalRequest {
+    pub approval_id: String,
+    pub reasoning: String,
+    pub calls: Vec<ToolApprovalCall>,
+}
+
+impl ToolApprovalRequest {
+    #[must_use]
+    pub fn prompt(&self) -> String {
+        let tools = self
+            .calls
+            .iter()
+            .map(|c| c.tool_name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("Approval required to run: {tools}")
+    }
+
+    /// The agent message that asks for the decision: the prompt as text and
+    /// the request itself as a data part.
+    #[must_use]
+    pub fn to_message(&self, task_id: &TaskId, context_id: &ContextId) -> Message {
+        let mut parts = vec![Part::Text(TextPart {
+            text: self.prompt(),
+        })];
+        if let Ok(serde_json::Value::Object(data)) = serde_json::to_value(self) {
+            parts.push(Part::Data(DataPart { data }));
+        }
+        Message {
+            role: MessageRole::Agent,
+            parts,
+            message_id: MessageId::generate(),
+            task_id: Some(task_id.clone()),
+            context_id: context_id.clone(),
+            metadata: None,
+            extensions: None,
+            reference_task_ids: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(
+    rename_all = "camelCase",
+    tag = "kind",
+    rename = "tool-approval-decision"
+)]
+pub struct ToolApprovalDecision {
+    pub approval_id: String,
+    pub approved: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ToolApprovalDecision {
+    /// The first data part of `message` that is a decision, if any.
+    #[must_use]
+    pub fn from_message(message: &Message) -> Option<Self> {
+        message.parts.iter().find_map(|part| match part {
+            Part::Data(DataPart { data })
+                if data.get("kind").and_then(serde_json::Value::as_str)
+                    == Some(TOOL_APPROVAL_DECISION_KIND) =>
+            {
+                serde_json::from_value(serde_json::Value::Object(data.clone())).ok()
+            },
+            _ => None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ToolApprovalStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+/// A suspended plan, persisted in `task_tool_approvals` until decided.
+/// `messages` is the conversation the plan was made from, so the run resumes
+/// where it stopped.
+#[derive(Debug, Clone)]
+pub struct PendingToolApproval {
+    pub approval_id: String,
+    pub task_id: TaskId,
+    pub reasoning: String,
+    pub calls: Vec<PlannedToolCall>,
+    pub messages: Vec<AiMessage>,
+    pub progress: LoopProgress,
+}
+
+/// How far an iterative run had got when its plan was suspended.
+///
+/// `iteration` is the round it is in and `call_counts` how often each
+/// `tool:arguments` pair has been planned, so the resumed loop keeps counting
+/// toward `max_iterations` and `max_repeated_calls` instead of starting over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopProgress {
+    pub iteration: u32,
+    pub call_counts: HashMap<String, u32>,
+}
+
+impl Default for LoopProgress {
+    fn default() -> Self {
+        Self {
+            iteration: 1,
+            call_counts: HashMap::new(),
+        }
+    }
+}
+
+impl PendingToolApproval {
+    #[must_use]
+    pub fn request(&self) -> ToolApprovalRequest {
+        ToolApprovalRequest {
+            approval_id: self.approval_id.clone(),
+            reasoning: self.reasoning.clone(),
+            calls: self
+                .calls
+                .iter()
+                .map(|c| ToolApprovalCall {
+                    tool_name: c.tool_name.clone(),
+                    arguments: c.arguments.clone(),
+                })
+                .collect(),
+        }
+    }
+}
@@ -7,6 +7,7 @@ pub mod message;
 mod mutations;
 pub mod notifications;
 mod queries;
+mod summaries;
 
 pub use notifications::ContextNotificationRepository;
 
@@ -0,0 +1,82 @@
+//! Rolling history summaries — `context_summaries`. Each compaction appends a
+//! row; the newest row for a context and agent is the one replayed.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use systemprompt_identifiers::ContextId;
+use systemprompt_traits::RepositoryError;
+
+use super::ContextRepository;
+use crate::models::context::{ContextSummary, NewContextSummary};
+
+impl ContextRepository {
+    pub async fn create_summary(
+        &self,
+        summary: &NewContextSummary<'_>,
+    ) -> Result<ContextSummary, RepositoryError> {
+        let row = sqlx::query!(
+            r#"INSERT INTO context_summaries
+                (context_id, agent_name, summary, covered_messages, token_estimate)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING id, created_at"#,
+            summary.context_id.as_str(),
+            summary.agent_name,
+            summary.summary,
+            summary.covered_messages,
+            summary.token_estimate
+        )
+        .fetch_one(&*self.write_pool)
+        .await
+        .map_err(RepositoryError::database)?;
+
+        Ok(ContextSummary {
+            id: row.id,
+            context_id: summary.context_id.clone(),
+            agent_name: summary.agent_name.to_owned(),
+            summary: summary.summary.to_owned(),
+            covered_messages: summary.covered_messages,
+            token_estimate: summary.token_estimate,
+            created_at: row.created_at,
+        })
+    }
+
+    pub async fn latest_summary(
+        &self,
+        context_id: &ContextId,
+        agent_name: &str,
+    ) -> Result<Option<ContextSummary>, RepositoryError> {
+        sqlx::query_as!(
+            ContextSummary,
+            r#"SELECT id, context_id as "context_id: ContextId", agent_name, summary,
+                covered_messages, token_estimate, created_at
+                FROM context_summaries
+                WHERE context_id = $1 AND agent_name = $2
+                ORDER BY created_at DESC, id DESC
+                LIMIT 1"#,
+            context_id.as_str(),
+            agent_name
+        )
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(RepositoryError::database)
+    }
+
+    pub async fn list_summaries(
+        &self,
+        context_id: &ContextId,
+    ) -> Result<Vec<ContextSummary>, RepositoryError> {
+        sqlx::query_as!(
+            ContextSummary,
+            r#"SELECT id, context_id as "context_id: ContextId", agent_name, summary,
+                covered_messages, token_estimate, created_at
+                FROM context_summaries
+                WHERE context_id = $1
+                ORDER BY created_at, id"#,
+            context_id.as_str()
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(RepositoryError::database)
+    }
+}
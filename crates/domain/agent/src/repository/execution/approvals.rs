@@ -0,0 +1,110 @@
+//! Persistence for tool calls suspended for a human decision —
+//! `task_tool_approvals`. A task holds at most one pending approval; deciding
+//! it is a compare-and-set on `status`, so a decision is applied once.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use systemprompt_identifiers::TaskId;
+use systemprompt_traits::RepositoryError;
+
+use super::ExecutionStepRepository;
+use crate::models::{LoopProgress, PendingToolApproval, ToolApprovalDecision, ToolApprovalStatus};
+
+impl ExecutionStepRepository {
+    pub async fn create_tool_approval(
+        &self,
+        approval: &PendingToolApproval,
+    ) -> Result<(), RepositoryError> {
+        let calls = serde_json::to_value(&approval.calls).map_err(|e| {
+            RepositoryError::Internal(format!("Failed to serialize approval calls: {e}"))
+        })?;
+        let messages = serde_json::to_value(&approval.messages).map_err(|e| {
+            RepositoryError::Internal(format!("Failed to serialize approval messages: {e}"))
+        })?;
+        let call_counts = serde_json::to_value(&approval.progress.call_counts).map_err(|e| {
+            RepositoryError::Internal(format!("Failed to serialize approval call counts: {e}"))
+        })?;
+        let iteration = i32::try_from(approval.progress.iteration).unwrap_or(i32::MAX);
+        sqlx::query!(
+            r#"INSERT INTO task_tool_approvals
+                (approval_id, task_id, reasoning, calls, messages, iteration, call_counts)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+            approval.approval_id,
+            approval.task_id.as_str(),
+            approval.reasoning,
+            calls,
+            messages,
+            iteration,
+            call_counts
+        )
+        .execute(&*self.write_pool)
+        .await
+        .map_err(|e| RepositoryError::Internal(format!("Failed to create tool approval: {e}")))?;
+        Ok(())
+    }
+
+    pub async fn pending_tool_approval(
+        &self,
+        task_id: &TaskId,
+    ) -> Result<Option<PendingToolApproval>, RepositoryError> {
+        let row = sqlx::query!(
+            r#"SELECT approval_id, reasoning, calls, messages, iteration, call_counts
+                FROM task_tool_approvals
+                WHERE task_id = $1 AND status = 'pending'"#,
+            task_id.as_str()
+        )
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| {
+            RepositoryError::Internal(format!("Failed to get pending tool approval: {e}"))
+        })?;
+        row.map(|r| {
+            let calls = serde_json::from_value(r.calls)
+                .map_err(|e| RepositoryError::Internal(format!("Invalid approval calls: {e}")))?;
+            let messages = serde_json::from_value(r.messages).map_err(|e| {
+                RepositoryError::Internal(format!("Invalid approval messages: {e}"))
+            })?;
+            let call_counts = serde_json::from_value(r.call_counts).map_err(|e| {
+                RepositoryError::Internal(format!("Invalid approval call counts: {e}"))
+            })?;
+            Ok(PendingToolApproval {
+                approval_id: r.approval_id,
+                task_id: task_id.clone(),
+                reasoning: r.reasoning,
+                calls,
+                messages,
+                progress: LoopProgress {
+                    iteration: u32::try_from(r.iteration).unwrap_or(1),
+                    call_counts,
+                },
+            })
+        })
+        .transpose()
+    }
+
+    /// Records `decision` against a still-pending approval. Returns `false`
+    /// when the approval does not exist or was already decided.
+    pub async fn resolve_tool_approval(
+        &self,
+        decision: &ToolApprovalDecision,
+    ) -> Result<bool, RepositoryError> {
+        let status = if decision.approved {
+            ToolApprovalStatus::Approved
+        } else {
+            ToolApprovalStatus::Rejected
+        };
+        let result = sqlx::query!(
+            r#"UPDATE task_tool_approvals
+                SET status = $2, reason = $3, decided_at = CURRENT_TIMESTAMP
+                WHERE approval_id = $1 AND status = 'pending'"#,
+            decision.approval_id,
+            status.as_str(),
+            decision.reason
+        )
+        .execute(&*self.write_pool)
+        .await
+        .map_err(|e| RepositoryError::Internal(format!("Failed to resolve tool approval: {e}")))?;
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -2,11 +2,15 @@
 //! state.
 //!
 //! Read paths live here; write paths (create, complete, fail) live in the
-//! `mutations` submodule.
+//! `mutations` submodule. [`ExecutionStepRepository::ai_usage`] reads the AI
+//! spend a task has accrued in `ai_requests`, which the iterative planned
+//! strategy checks against its budget. Tool calls suspended for a human
+//! decision are persisted by the `approvals` submodule.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
+mod approvals;
 mod mutations;
 mod parse;
 
@@ -19,6 +23,13 @@ use systemprompt_traits::RepositoryError;
 
 use parse::{ParseStepParams, parse_step};
 
+/// Tokens and cost of every AI request attributed to one task.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskAiUsage {
+    pub tokens: i64,
+    pub cost_microdollars: i64,
+}
+
 #[derive(Debug, Clone)]
 pub struct ExecutionStepRepository {
     pool: Arc<PgPool>,
@@ -111,4 +122,22 @@ impl ExecutionStepRepository {
 
         Ok(exists)
     }
+
+    pub async fn ai_usage(&self, task_id: &TaskId) -> Result<TaskAiUsage, RepositoryError> {
+        let row = sqlx::query!(
+            r#"SELECT COALESCE(SUM(tokens_used), 0)::BIGINT as "tokens!",
+                    COALESCE(SUM(cost_microdollars), 0)::BIGINT as "cost_microdollars!"
+                FROM ai_requests WHERE task_id = $1"#,
+            task_id.as_str()
+        )
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|e| {
+            RepositoryError::Internal(format!("Failed to sum AI usage for task: {task_id}: {e}"))
+        })?;
+        Ok(TaskAiUsage {
+            tokens: row.tokens,
+            cost_microdollars: row.cost_microdollars,
+        })
+    }
 }
@@ -0,0 +1,246 @@
+//! Run checkpoints — `task_checkpoints` — and the orphaned-task scan a
+//! restarted agent runs before it serves traffic.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use systemprompt_identifiers::{ContextId, SessionId, TaskId, TraceId, UserId};
+use systemprompt_models::auth::UserType;
+use systemprompt_traits::RepositoryError;
+
+use super::TaskRepository;
+use crate::models::a2a::{Message, TaskState};
+use crate::models::{OrphanedTask, TaskCheckpoint};
+
+/// The run a checkpoint records: the message that started it and the
+/// process, `claimant`, that leases it for `lease` from the save.
+#[derive(Debug, Clone, Copy)]
+pub struct SaveCheckpointParams<'a> {
+    pub task_id: &'a TaskId,
+    pub message: &'a Message,
+    pub user_type: UserType,
+    pub claimant: &'a str,
+    pub lease: Duration,
+}
+
+impl TaskRepository {
+    /// Records the message that starts a run, replacing the checkpoint of
+    /// any earlier run and resetting its resume count. The running process
+    /// holds the lease from now and renews it while the run lives.
+    pub async fn save_checkpoint(
+        &self,
+        params: SaveCheckpointParams<'_>,
+    ) -> Result<(), RepositoryError> {
+        let SaveCheckpointParams {
+            task_id,
+            message,
+            user_type,
+            claimant,
+            lease,
+        } = params;
+        let message = serde_json::to_value(message).map_err(|e| {
+            RepositoryError::Internal(format!("Failed to serialize checkpoint message: {e}"))
+        })?;
+        sqlx::query!(
+            r#"INSERT INTO task_checkpoints (task_id, message, user_type, claimed_by, claimed_until)
+                VALUES ($1, $2, $3, $4, CURRENT_TIMESTAMP + make_interval(secs => $5))
+                ON CONFLICT (task_id) DO UPDATE
+                SET message = EXCLUDED.message,
+                    user_type = EXCLUDED.user_type,
+                    resume_attempts = 0,
+                    claimed_by = EXCLUDED.claimed_by,
+                    claimed_until = EXCLUDED.claimed_until"#,
+            task_id.as_str(),
+            message,
+            user_type.as_str(),
+            claimant,
+            lease.as_secs_f64()
+        )
+        .execute(&*self.write_pool)
+        .await
+        .map_err(RepositoryError::database)?;
+        Ok(())
+    }
+
+    pub async fn get_checkpoint(
+        &self,
+        task_id: &TaskId,
+    ) -> Result<Option<TaskCheckpoint>, RepositoryError> {
+        let row = sqlx::query!(
+            r#"SELECT message, user_type, resume_attempts
+                FROM task_checkpoints WHERE task_id = $1"#,
+            task_id.as_str()
+        )
+        .fetch_optional(&*self.write_pool)
+        .await
+        .map_err(RepositoryError::database)?;
+
+        row.map(|r| {
+            let message = serde_json::from_value(r.message).map_err(|e| {
+                RepositoryError::InvalidData(format!("Invalid checkpoint message: {e}"))
+            })?;
+            let user_type = r.user_type.parse().map_err(|e| {
+                RepositoryError::InvalidData(format!("Invalid checkpoint user type: {e}"))
+            })?;
+            Ok(TaskCheckpoint {
+                task_id: task_id.clone(),
+                message,
+                user_type,
+                resume_attempts: u32::try_from(r.resume_attempts).unwrap_or(0),
+            })
+        })
+        .transpose()
+    }
+
+    /// Takes the recovery lease on the checkpoint of `task_id` for
+    /// `claimant` until `lease` from now. False when there is no checkpoint
+    /// or another process holds a lease that has not yet run out.
+    pub async fn claim_checkpoint(
+        &self,
+        task_id: &TaskId,
+        claimant: &str,
+        lease: Duration,
+    ) -> Result<bool, RepositoryError> {
+        let claimed = sqlx::query_scalar!(
+            r#"UPDATE task_checkpoints
+                SET claimed_by = $2,
+                    claimed_until = CURRENT_TIMESTAMP + make_interval(secs => $3)
+                WHERE task_id = $1
+                  AND (claimed_until IS NULL OR claimed_until < CURRENT_TIMESTAMP)
+                RETURNING task_id"#,
+            task_id.as_str(),
+            claimant,
+            lease.as_secs_f64()
+        )
+        .fetch_optional(&*self.write_pool)
+        .await
+        .map_err(RepositoryError::database)?;
+
+        Ok(claimed.is_some())
+    }
+
+    /// Extends `claimant`'s lease on the checkpoint of `task_id` to `lease`
+    /// from now. False once the checkpoint is gone or another process has
+    /// taken the lease over.
+    pub async fn renew_checkpoint_lease(
+        &self,
+        task_id: &TaskId,
+        claimant: &str,
+        lease: Duration,
+    ) -> Result<bool, RepositoryError> {
+        let renewed = sqlx::query_scalar!(
+            r#"UPDATE task_checkpoints
+                SET claimed_until = CURRENT_TIMESTAMP + make_interval(secs => $3)
+                WHERE task_id = $1 AND claimed_by = $2
+                RETURNING task_id"#,
+            task_id.as_str(),
+            claimant,
+            lease.as_secs_f64()
+        )
+        .fetch_optional(&*self.write_pool)
+        .await
+        .map_err(RepositoryError::database)?;
+
+        Ok(renewed.is_some())
+    }
+
+    /// Counts one more resume of `task_id` by `claimant` and returns the new
+    /// total. Fails unless `claimant` holds the checkpoint's lease.
+    pub async fn record_resume_attempt(
+        &self,
+        task_id: &TaskId,
+        claimant: &str,
+    ) -> Result<u32, RepositoryError> {
+        let attempts = sqlx::query_scalar!(
+            r#"UPDATE task_checkpoints SET resume_attempts = resume_attempts + 1
+                WHERE task_id = $1 AND claimed_by = $2
+                RETURNING resume_attempts"#,
+            task_id.as_str(),
+            claimant
+        )
+        .fetch_optional(&*self.write_pool)
+        .await
+        .map_err(RepositoryError::database)?
+        .ok_or_else(|| {
+            RepositoryError::NotFound(format!(
+                "No checkpoint of task {task_id} claimed by {claimant}"
+            ))
+        })?;
+
+        Ok(u32::try_from(attempts).unwrap_or(0))
+    }
+
+    pub async fn delete_checkpoint(&self, task_id: &TaskId) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            "DELETE FROM task_checkpoints WHERE task_id = $1",
+            task_id.as_str()
+        )
+        .execute(&*self.write_pool)
+        .await
+        .map_err(RepositoryError::database)?;
+        Ok(())
+    }
+
+    /// Tasks of `agent_name` still submitted or working that were last
+    /// touched before `started_at` and that no live process is running —
+    /// runs a previous process never finished. Oldest first.
+    ///
+    /// A checkpointed task counts only once its lease has run out, since the
+    /// process running it renews the lease. A task without a checkpoint has
+    /// no lease to go by, so it counts only when untouched since
+    /// `unleased_before`.
+    pub async fn list_orphaned_tasks(
+        &self,
+        agent_name: &str,
+        started_at: DateTime<Utc>,
+        unleased_before: DateTime<Utc>,
+    ) -> Result<Vec<OrphanedTask>, RepositoryError> {
+        let rows = sqlx::query!(
+            r#"SELECT
+                t.task_id as "task_id!: TaskId",
+                t.context_id as "context_id!: ContextId",
+                t.user_id as "user_id?: UserId",
+                t.session_id as "session_id?: SessionId",
+                t.trace_id as "trace_id?: TraceId",
+                t.status,
+                t.updated_at
+            FROM agent_tasks t
+            LEFT JOIN task_checkpoints c ON c.task_id = t.task_id
+            WHERE t.agent_name = $1
+              AND t.status IN ('TASK_STATE_SUBMITTED', 'TASK_STATE_WORKING')
+              AND t.updated_at < $2
+              AND CASE
+                    WHEN c.task_id IS NULL THEN t.updated_at < $3
+                    ELSE c.claimed_until IS NULL OR c.claimed_until < CURRENT_TIMESTAMP
+                  END
+            ORDER BY t.updated_at ASC"#,
+            agent_name,
+            started_at,
+            unleased_before
+        )
+        .fetch_all(&*self.write_pool)
+        .await
+        .map_err(RepositoryError::database)?;
+
+        rows.into_iter()
+            .map(|r| {
+                let state = r
+                    .status
+                    .parse::<TaskState>()
+                    .map_err(RepositoryError::InvalidData)?;
+                Ok(OrphanedTask {
+                    task_id: r.task_id,
+                    context_id: r.context_id,
+                    user_id: r.user_id,
+                    session_id: r.session_id,
+                    trace_id: r.trace_id,
+                    state,
+                    updated_at: r.updated_at,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,92 @@
+//! Parent/child links between tasks created by agent-to-agent delegation.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use super::TaskRepository;
+use systemprompt_identifiers::TaskId;
+use systemprompt_traits::RepositoryError;
+
+impl TaskRepository {
+    pub async fn set_parent_task(
+        &self,
+        task_id: &TaskId,
+        parent_task_id: &TaskId,
+    ) -> Result<(), RepositoryError> {
+        if task_id == parent_task_id {
+            return Err(RepositoryError::InvalidData(format!(
+                "Task {task_id} cannot be its own parent"
+            )));
+        }
+
+        let result = sqlx::query!(
+            "UPDATE agent_tasks SET parent_task_id = $2 WHERE task_id = $1",
+            task_id.as_str(),
+            parent_task_id.as_str()
+        )
+        .execute(&*self.write_pool)
+        .await
+        .map_err(RepositoryError::database)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound(format!(
+                "Task {task_id} not found"
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn get_parent_task_id(
+        &self,
+        task_id: &TaskId,
+    ) -> Result<Option<TaskId>, RepositoryError> {
+        let parent = sqlx::query_scalar!(
+            r#"SELECT parent_task_id as "parent_task_id?: TaskId"
+            FROM agent_tasks WHERE task_id = $1"#,
+            task_id.as_str()
+        )
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(RepositoryError::database)?;
+
+        Ok(parent.flatten())
+    }
+
+    pub async fn list_child_task_ids(
+        &self,
+        parent_task_id: &TaskId,
+    ) -> Result<Vec<TaskId>, RepositoryError> {
+        sqlx::query_scalar!(
+            r#"SELECT task_id as "task_id!: TaskId"
+            FROM agent_tasks WHERE parent_task_id = $1
+            ORDER BY created_at ASC"#,
+            parent_task_id.as_str()
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(RepositoryError::database)
+    }
+
+    /// Number of delegation hops above `task_id`; a task nobody delegated
+    /// has depth zero.
+    pub async fn delegation_depth(&self, task_id: &TaskId) -> Result<i64, RepositoryError> {
+        let depth = sqlx::query_scalar!(
+            r#"WITH RECURSIVE ancestors(task_id, parent_task_id, depth) AS (
+                SELECT task_id, parent_task_id, 0::BIGINT
+                FROM agent_tasks WHERE task_id = $1
+                UNION ALL
+                SELECT t.task_id, t.parent_task_id, a.depth + 1
+                FROM agent_tasks t
+                JOIN ancestors a ON t.task_id = a.parent_task_id
+                WHERE a.depth < 64
+            )
+            SELECT MAX(depth) as "depth?" FROM ancestors"#,
+            task_id.as_str()
+        )
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(RepositoryError::database)?;
+
+        Ok(depth.unwrap_or(0))
+    }
+}
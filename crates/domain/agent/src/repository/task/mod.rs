@@ -1,7 +1,8 @@
 //! Persistence for A2A tasks, their messages, parts, and execution state.
 //!
 //! [`TaskRepository`] is the repository facade over the `agent_tasks` table and
-//! its satellites (`task_messages`, `message_parts`, `task_execution_steps`).
+//! its satellites (`task_messages`, `message_parts`, `task_execution_steps`,
+//! `task_checkpoints`).
 //! It splits reads and writes across separate pools, keeps the per-session
 //! task/message counters on `user_sessions` current as it writes, and
 //! delegates aggregate reassembly to [`TaskConstructor`]. Query, mutation, and
@@ -11,19 +12,23 @@
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
+mod checkpoints;
 pub mod constructor;
+mod lineage;
 mod mutations;
 mod queries;
 mod state;
 mod task_messages;
 mod task_updates;
 
+pub use checkpoints::SaveCheckpointParams;
 pub use constructor::TaskConstructor;
 pub use mutations::{
     CreateTaskParams, create_task, task_state_to_db_string, track_agent_in_context,
 };
 pub use queries::{
-    TaskContextInfo, get_task, get_task_context_info, get_tasks_by_user_id, list_tasks_by_context,
+    TaskContextInfo, TaskListFilter, get_task, get_task_context_info, get_tasks_by_user_id,
+    list_tasks, list_tasks_by_context,
 };
 pub use state::{apply_notification_status, update_task_failed_with_error, update_task_state};
 pub use task_updates::UpdateTaskAndSaveMessagesParams;
@@ -123,6 +128,10 @@ impl TaskRepository {
         list_tasks_by_context(&self.pool, &self.db_pool, context_id).await
     }
 
+    pub async fn list_tasks(&self, filter: &TaskListFilter) -> Result<Vec<Task>, RepositoryError> {
+        list_tasks(&self.pool, &self.db_pool, filter).await
+    }
+
     pub async fn get_tasks_by_user_id(
         &self,
         user_id: &UserId,
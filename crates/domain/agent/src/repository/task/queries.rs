@@ -4,6 +4,7 @@
 //! See <https://systemprompt.io> for licensing details.
 
 use crate::models::TaskRow;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use std::sync::Arc;
 use systemprompt_database::DbPool;
@@ -11,7 +12,22 @@ use systemprompt_identifiers::{AgentName, ContextId, SessionId, TaskId, TraceId,
 use systemprompt_traits::RepositoryError;
 
 use super::constructor::TaskConstructor;
-use crate::models::a2a::Task;
+use super::mutations::task_state_to_db_string;
+use crate::models::a2a::{Task, TaskState};
+
+/// Filters for [`list_tasks`]. `None` fields match every task; `after` is the
+/// keyset cursor, the last task of the previous page.
+#[derive(Debug, Clone, Default)]
+pub struct TaskListFilter {
+    pub user_id: Option<UserId>,
+    pub agent_name: Option<String>,
+    pub context_id: Option<ContextId>,
+    pub state: Option<TaskState>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub after: Option<TaskId>,
+    pub limit: i64,
+}
 
 pub async fn get_task(
     pool: &Arc<PgPool>,
@@ -137,6 +153,45 @@ pub async fn get_tasks_by_user_id(
     Ok(tasks)
 }
 
+/// Tasks matching `filter`, newest first, ordered by creation time and then
+/// task id so pages never overlap.
+pub async fn list_tasks(
+    pool: &Arc<PgPool>,
+    db_pool: &DbPool,
+    filter: &TaskListFilter,
+) -> Result<Vec<Task>, RepositoryError> {
+    let task_ids = sqlx::query_scalar!(
+        r#"SELECT task_id as "task_id!: TaskId"
+        FROM agent_tasks
+        WHERE ($1::text IS NULL OR user_id = $1)
+          AND ($2::text IS NULL OR agent_name = $2)
+          AND ($3::text IS NULL OR context_id = $3)
+          AND ($4::text IS NULL OR status = $4)
+          AND ($5::timestamptz IS NULL OR created_at >= $5)
+          AND ($6::timestamptz IS NULL OR created_at < $6)
+          AND ($7::text IS NULL OR (created_at, task_id) <
+              (SELECT created_at, task_id FROM agent_tasks WHERE task_id = $7))
+        ORDER BY created_at DESC, task_id DESC
+        LIMIT $8"#,
+        filter.user_id.as_ref().map(UserId::as_str),
+        filter.agent_name.as_deref(),
+        filter.context_id.as_ref().map(ContextId::as_str),
+        filter.state.map(task_state_to_db_string),
+        filter.created_after,
+        filter.created_before,
+        filter.after.as_ref().map(TaskId::as_str),
+        filter.limit
+    )
+    .fetch_all(pool.as_ref())
+    .await
+    .map_err(RepositoryError::database)?;
+
+    let constructor = TaskConstructor::new(db_pool)?;
+    let mut tasks = constructor.construct_tasks_batch(&task_ids).await?;
+    tasks.sort_by_key(|task| task_ids.iter().position(|id| *id == task.id));
+    Ok(tasks)
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskContextInfo {
     pub context_id: ContextId,
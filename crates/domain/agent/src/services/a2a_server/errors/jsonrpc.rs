@@ -2,8 +2,9 @@
 //!
 //! [`JsonRpcErrorBuilder`] assembles spec-coded error responses with optional
 //! data payloads and structured logging; [`unauthorized_response`] and
-//! [`forbidden_response`] are the auth-failure shortcuts, and
-//! [`classify_database_error`] maps repository errors to user-facing messages.
+//! [`forbidden_response`] are the auth-failure shortcuts,
+//! [`classify_database_error`] maps repository errors to user-facing messages,
+//! and [`status_for_code`] maps error codes onto HTTP statuses.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -115,16 +116,7 @@ impl JsonRpcErrorBuilder {
     }
 
     pub fn build_with_status(self, request_id: &NumberOrString) -> (StatusCode, Value) {
-        let status = match self.code {
-            -32600 => StatusCode::BAD_REQUEST,
-            -32601 => StatusCode::NOT_FOUND,
-            -32602 => StatusCode::BAD_REQUEST,
-            -32603 => StatusCode::INTERNAL_SERVER_ERROR,
-            -32700 => StatusCode::BAD_REQUEST,
-            _ => StatusCode::INTERNAL_SERVER_ERROR,
-        };
-
-        (status, self.build(request_id))
+        (status_for_code(self.code), self.build(request_id))
     }
 
     pub fn invalid_request() -> Self {
@@ -160,6 +152,16 @@ impl JsonRpcErrorBuilder {
     }
 }
 
+/// The HTTP status an error with JSON-RPC `code` maps to, shared by
+/// [`JsonRpcErrorBuilder::build_with_status`] and the HTTP+JSON binding.
+pub const fn status_for_code(code: i32) -> StatusCode {
+    match code {
+        -32600 | -32602 | -32700 => StatusCode::BAD_REQUEST,
+        -32601 => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
 pub fn unauthorized_response(
     reason: impl Into<String>,
     request_id: &NumberOrString,
@@ -11,5 +11,6 @@
 pub mod jsonrpc;
 
 pub use jsonrpc::{
-    JsonRpcErrorBuilder, classify_database_error, forbidden_response, unauthorized_response,
+    JsonRpcErrorBuilder, classify_database_error, forbidden_response, status_for_code,
+    unauthorized_response,
 };
@@ -0,0 +1,83 @@
+//! `ListTasks`: the agent's tasks, filtered and paged newest first.
+//!
+//! Callers see only the tasks they own unless their token carries the admin
+//! scope. A page is fetched one task long to learn whether another follows;
+//! when it does, the last task returned becomes `nextPageToken`.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use axum::extract::Json;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde_json::json;
+use systemprompt_models::RequestContext;
+use systemprompt_models::auth::UserType;
+
+use crate::models::a2a::jsonrpc::NumberOrString;
+use crate::models::a2a::protocol::{ListTasksParams, ListTasksResult};
+use crate::repository::task::TaskListFilter;
+use crate::services::a2a_server::errors::JsonRpcErrorBuilder;
+use crate::services::a2a_server::handlers::state::AgentHandlerState;
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 100;
+
+pub(super) async fn handle_list_tasks(
+    state: &AgentHandlerState,
+    context: &RequestContext,
+    params: ListTasksParams,
+    request_id: &NumberOrString,
+) -> axum::response::Response {
+    let page_size = params
+        .page_size
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let is_admin = context.user_type() == UserType::Admin;
+    tracing::info!(page_size, is_admin, "Handling ListTasks request");
+
+    let filter = TaskListFilter {
+        user_id: (!is_admin).then(|| context.user_id().clone()),
+        agent_name: Some(state.config.read().await.name.clone()),
+        context_id: params.context_id,
+        state: params.status,
+        created_after: params.created_after,
+        created_before: params.created_before,
+        after: params.page_token,
+        limit: i64::from(page_size) + 1,
+    };
+
+    let mut tasks = match state
+        .agent_state
+        .repositories()
+        .tasks
+        .list_tasks(&filter)
+        .await
+    {
+        Ok(tasks) => tasks,
+        Err(e) => {
+            let error_response = JsonRpcErrorBuilder::internal_error()
+                .with_data(json!(format!("Failed to list tasks: {e}")))
+                .log_error(format!("Failed to list tasks: {e}"))
+                .build(request_id);
+            return (StatusCode::OK, Json(error_response)).into_response();
+        },
+    };
+
+    let page_len = usize::try_from(page_size).unwrap_or(usize::MAX);
+    let next_page_token = (tasks.len() > page_len).then(|| {
+        tasks.truncate(page_len);
+        tasks.last().map(|task| task.id.clone())
+    });
+    let result = ListTasksResult {
+        tasks,
+        next_page_token: next_page_token.flatten(),
+        page_size,
+    };
+
+    (
+        StatusCode::OK,
+        Json(json!({"jsonrpc": "2.0", "id": request_id, "result": result})),
+    )
+        .into_response()
+}
@@ -1,15 +1,19 @@
 //! A2A JSON-RPC request dispatch.
 //!
-//! [`handle_agent_request`] is the single entry point: it parses the JSON-RPC
-//! envelope, enforces OAuth when required, derives the request context, and
-//! routes to the streaming, push-notification, or non-streaming handlers.
+//! [`handle_agent_request`] is the JSON-RPC entry point: it parses the
+//! envelope, enforces OAuth when required, and hands the parsed request to
+//! [`dispatch_a2a_request`], which derives the request context and routes to
+//! the streaming, subscription, task-listing, push-notification, or
+//! non-streaming handlers.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 pub mod helpers;
+mod list_tasks;
 mod non_streaming;
 mod streaming;
+mod subscribe;
 pub mod validation;
 
 use axum::body::Body;
@@ -19,15 +23,18 @@ use axum::response::IntoResponse;
 use serde_json::json;
 use std::sync::Arc;
 use systemprompt_models::RequestContext;
-use systemprompt_models::a2a::methods;
 
 use super::state::AgentHandlerState;
 use crate::models::a2a::A2aRequestParams;
+use crate::models::a2a::jsonrpc::NumberOrString;
 use crate::services::a2a_server::auth::validate_oauth_for_request;
 use crate::services::a2a_server::errors::JsonRpcErrorBuilder;
 
 use helpers::{handle_push_notification_requests, handle_streaming_path, parse_a2a_request};
+use list_tasks::handle_list_tasks;
 use non_streaming::handle_non_streaming_request;
+use subscribe::handle_resubscription;
+pub use subscribe::handle_task_subscription;
 use validation::should_require_oauth;
 
 pub async fn handle_agent_request(
@@ -70,14 +77,32 @@ pub async fn handle_agent_request(
         return response;
     }
 
-    let is_streaming = jsonrpc_request.method == methods::SEND_STREAMING_MESSAGE;
-
     let a2a_request = match parse_a2a_request(&jsonrpc_request, &request_id).await {
         Ok(req) => req,
         Err(response) => return response,
     };
 
-    let mut enriched_context = context.clone();
+    let response = dispatch_a2a_request(&state, context, a2a_request, request_id, start_time).await;
+
+    let latency_ms = start_time.elapsed().as_millis();
+    let latency_ms = i64::try_from(latency_ms).unwrap_or(i64::MAX);
+    tracing::info!(latency_ms = %latency_ms, oauth = %requires_oauth, method = %jsonrpc_request.method, "A2A request processed");
+
+    response
+}
+
+/// Runs a parsed A2A request and renders the JSON-RPC response.
+///
+/// Shared by both bindings: the HTTP+JSON routes in [`super::rest`] build the
+/// same [`A2aRequestParams`] and unwrap the envelope this returns.
+pub async fn dispatch_a2a_request(
+    state: &Arc<AgentHandlerState>,
+    context: RequestContext,
+    a2a_request: A2aRequestParams,
+    request_id: NumberOrString,
+    start_time: std::time::Instant,
+) -> axum::response::Response {
+    let mut enriched_context = context;
     match &a2a_request {
         A2aRequestParams::SendMessage(params) | A2aRequestParams::SendStreamingMessage(params) => {
             enriched_context = enriched_context.with_context_id(params.message.context_id.clone());
@@ -85,27 +110,38 @@ pub async fn handle_agent_request(
         _ => {},
     }
 
-    if is_streaming {
-        return handle_streaming_path(a2a_request, state, request_id, enriched_context, start_time)
-            .await;
+    if matches!(a2a_request, A2aRequestParams::SendStreamingMessage(_)) {
+        return handle_streaming_path(
+            a2a_request,
+            Arc::clone(state),
+            request_id,
+            enriched_context,
+            start_time,
+        )
+        .await;
+    }
+
+    if let A2aRequestParams::TaskResubscription(params) = &a2a_request {
+        return handle_resubscription(state, params, request_id).await;
+    }
+
+    if let A2aRequestParams::ListTasks(params) = a2a_request {
+        return handle_list_tasks(state, &enriched_context, params, &request_id).await;
     }
 
     if let Some(response) =
-        handle_push_notification_requests(&a2a_request, &state, &request_id, start_time).await
+        handle_push_notification_requests(&a2a_request, state, &request_id, start_time).await
     {
         return response;
     }
 
-    let response_result =
-        handle_non_streaming_request(a2a_request, &state, &enriched_context).await;
+    let response_result = handle_non_streaming_request(a2a_request, state, &enriched_context).await;
 
-    let json_rpc_response = build_json_rpc_response(response_result, &request_id);
-
-    let latency_ms = start_time.elapsed().as_millis();
-    let latency_ms = i64::try_from(latency_ms).unwrap_or(i64::MAX);
-    tracing::info!(latency_ms = %latency_ms, oauth = %requires_oauth, method = %jsonrpc_request.method, "A2A request processed");
-
-    (StatusCode::OK, Json(json_rpc_response)).into_response()
+    (
+        StatusCode::OK,
+        Json(build_json_rpc_response(response_result, &request_id)),
+    )
+        .into_response()
 }
 
 async fn parse_json_rpc_body(
@@ -144,15 +180,15 @@ async fn parse_json_rpc_body(
                 "Request must be valid JSON-RPC 2.0 with jsonrpc, method, params, and id"
             ))
             .log_error(format!("Invalid JSON-RPC request: {e}"))
-            .build(&crate::models::a2a::jsonrpc::NumberOrString::Number(0));
+            .build(&NumberOrString::Number(0));
         (StatusCode::BAD_REQUEST, Json(error_response)).into_response()
     })
 }
 
-async fn enforce_oauth(
+pub(crate) async fn enforce_oauth(
     state: &AgentHandlerState,
     headers: &HeaderMap,
-    request_id: &crate::models::a2a::jsonrpc::NumberOrString,
+    request_id: &NumberOrString,
 ) -> Result<(), axum::response::Response> {
     tracing::info!("Request requires OAuth2 authentication");
 
@@ -174,7 +210,7 @@ async fn enforce_oauth(
 
 fn build_json_rpc_response(
     response_result: Result<crate::models::a2a::Task, Box<dyn std::error::Error + Send + Sync>>,
-    request_id: &crate::models::a2a::jsonrpc::NumberOrString,
+    request_id: &NumberOrString,
 ) -> serde_json::Value {
     match response_result {
         Ok(task) => match serde_json::to_value(task) {
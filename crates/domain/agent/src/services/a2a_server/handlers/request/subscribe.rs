@@ -0,0 +1,131 @@
+//! `SubscribeToTask` over SSE.
+//!
+//! The first frame is the task as stored; while the task is still running its
+//! row is polled and every state change is sent as a `status-update` frame,
+//! the last one marked `final`. Polling the repository rather than a
+//! process-local channel lets a subscriber follow a task whose stream was
+//! opened by another connection.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::extract::Json;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::StreamExt;
+use serde_json::json;
+use systemprompt_identifiers::TaskId;
+use tokio::sync::mpsc::Sender;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::models::a2a::Task;
+use crate::models::a2a::jsonrpc::NumberOrString;
+use crate::models::a2a::protocol::{TaskResubscriptionRequest, TaskStatusUpdateEvent};
+use crate::repository::task::TaskRepository;
+use crate::services::a2a_server::errors::JsonRpcErrorBuilder;
+use crate::services::a2a_server::handlers::state::AgentHandlerState;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// JSON-RPC `SubscribeToTask`: registers the request's push-notification
+/// config for the task, then streams it.
+pub(super) async fn handle_resubscription(
+    state: &AgentHandlerState,
+    params: &TaskResubscriptionRequest,
+    request_id: NumberOrString,
+) -> axum::response::Response {
+    let configs = &state.agent_state.repositories().push_notification_configs;
+    if let Err(e) = configs.add_config(&params.task_id, &params.config).await {
+        let error_response = JsonRpcErrorBuilder::internal_error()
+            .with_data(json!("Failed to register push notification config"))
+            .log_error(format!("Failed to add config on resubscription: {e}"))
+            .build(&request_id);
+        return (StatusCode::OK, Json(error_response)).into_response();
+    }
+
+    handle_task_subscription(state, &params.task_id, request_id).await
+}
+
+pub async fn handle_task_subscription(
+    state: &AgentHandlerState,
+    task_id: &TaskId,
+    request_id: NumberOrString,
+) -> axum::response::Response {
+    tracing::info!(task_id = %task_id, "Handling SubscribeToTask request");
+
+    let tasks = state.agent_state.repositories().tasks.clone();
+    let task = match tasks.get_task(task_id).await {
+        Ok(Some(task)) => task,
+        Ok(None) => {
+            let error_response = JsonRpcErrorBuilder::invalid_params()
+                .with_data(json!(format!("Task not found: {task_id}")))
+                .build(&request_id);
+            return (StatusCode::OK, Json(error_response)).into_response();
+        },
+        Err(e) => {
+            let error_response = JsonRpcErrorBuilder::internal_error()
+                .with_data(json!(format!("Failed to retrieve task: {e}")))
+                .log_error(format!("Failed to load task for subscription: {e}"))
+                .build(&request_id);
+            return (StatusCode::OK, Json(error_response)).into_response();
+        },
+    };
+
+    let (tx, rx) = tokio::sync::mpsc::channel(16);
+    let snapshot = json!({"jsonrpc": "2.0", "id": &request_id, "result": &task});
+    if tx
+        .try_send(Event::default().data(snapshot.to_string()))
+        .is_err()
+    {
+        tracing::trace!("Failed to send task snapshot, channel closed");
+    }
+    if !task.status.state.is_terminal() {
+        tokio::spawn(follow_task(tasks, task, tx, request_id));
+    }
+
+    Sse::new(ReceiverStream::new(rx).map(Ok::<_, Infallible>))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+async fn follow_task(
+    tasks: TaskRepository,
+    task: Task,
+    tx: Sender<Event>,
+    request_id: NumberOrString,
+) {
+    let mut last_state = task.status.state;
+    while !tx.is_closed() {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let current = match tasks.get_task(&task.id).await {
+            Ok(Some(current)) => current,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::warn!(task_id = %task.id, error = %e, "Task subscription poll failed");
+                break;
+            },
+        };
+        if current.status.state == last_state {
+            continue;
+        }
+
+        last_state = current.status.state;
+        let is_final = last_state.is_terminal();
+        let event =
+            TaskStatusUpdateEvent::new(current.id, current.context_id, current.status, is_final);
+        let mut frame = event.to_jsonrpc_response();
+        frame["id"] = json!(&request_id);
+        if tx
+            .send(Event::default().data(frame.to_string()))
+            .await
+            .is_err()
+            || is_final
+        {
+            break;
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! The A2A HTTP+JSON (REST) binding.
+//!
+//! Each resource is mapped onto the [`A2aRequestParams`] the JSON-RPC endpoint
+//! parses and run through [`dispatch_a2a_request`], so both bindings share
+//! the handlers, the OAuth gate, and the error codes. The JSON-RPC envelope is
+//! unwrapped on the way out: a `result` becomes the body, and an `error`
+//! becomes `{"error": {...}}` with the HTTP status its code maps to. Streams
+//! carry the same SSE frames as the JSON-RPC binding.
+//!
+//! | Method | Path | A2A operation |
+//! |---|---|---|
+//! | `POST` | `/message:send` | `SendMessage` |
+//! | `POST` | `/message:stream` | `SendStreamingMessage` |
+//! | `GET` | `/tasks` | `ListTasks` |
+//! | `GET` | `/tasks/{id}` | `GetTask` |
+//! | `POST` | `/tasks/{id}:cancel` | `CancelTask` |
+//! | `GET` | `/tasks/{id}:subscribe` | `SubscribeToTask` |
+//! | `POST` | `/tasks/{id}/pushNotificationConfigs` | `CreateTaskPushNotificationConfig` |
+//! | `GET` | `/tasks/{id}/pushNotificationConfigs` | `GetTaskPushNotificationConfig` |
+//! | `DELETE` | `/tasks/{id}/pushNotificationConfigs` | `DeleteTaskPushNotificationConfig` |
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::Arc;
+
+use axum::body::{Body, Bytes};
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Extension, Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use systemprompt_identifiers::TaskId;
+use systemprompt_models::RequestContext;
+
+use super::request::validation::should_require_oauth;
+use super::request::{dispatch_a2a_request, enforce_oauth, handle_task_subscription};
+use super::state::AgentHandlerState;
+use crate::models::a2a::jsonrpc::NumberOrString;
+use crate::models::a2a::protocol::{
+    DeleteTaskPushNotificationConfigRequest, GetTaskPushNotificationConfigRequest, ListTasksParams,
+    PushNotificationConfig, SetTaskPushNotificationConfigRequest, TaskIdParams, TaskQueryParams,
+};
+use crate::models::a2a::{A2aRequestParams, MessageSendParams};
+use crate::services::a2a_server::errors::{JsonRpcErrorBuilder, status_for_code};
+
+// Why: the shared handlers echo a JSON-RPC id into every envelope and SSE
+// frame; REST requests have none, so a fixed one stands in.
+const REST_REQUEST_ID: NumberOrString = NumberOrString::Number(0);
+
+type RequestContextExtension = Option<Extension<RequestContext>>;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskQuery {
+    history_length: Option<u32>,
+}
+
+/// The HTTP+JSON routes, served next to the JSON-RPC endpoint behind the same
+/// authentication middleware.
+pub fn rest_routes() -> Router<Arc<AgentHandlerState>> {
+    Router::new()
+        .route("/message:send", post(handle_send_message))
+        .route("/message:stream", post(handle_stream_message))
+        .route("/tasks", get(handle_list_tasks))
+        .route("/tasks/{task}", get(handle_task_get).post(handle_task_post))
+        .route(
+            "/tasks/{task}/pushNotificationConfigs",
+            post(handle_set_push_config)
+                .get(handle_get_push_configs)
+                .delete(handle_delete_push_configs),
+        )
+}
+
+async fn handle_send_message(
+    State(state): State<Arc<AgentHandlerState>>,
+    context: RequestContextExtension,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    match parse_body(&body) {
+        Ok(params) => {
+            run(
+                &state,
+                context,
+                &headers,
+                A2aRequestParams::SendMessage(params),
+            )
+            .await
+        },
+        Err(response) => response,
+    }
+}
+
+async fn handle_stream_message(
+    State(state): State<Arc<AgentHandlerState>>,
+    context: RequestContextExtension,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    match parse_body::<MessageSendParams>(&body) {
+        Ok(params) => {
+            let request = A2aRequestParams::SendStreamingMessage(params);
+            run(&state, context, &headers, request).await
+        },
+        Err(response) => response,
+    }
+}
+
+async fn handle_list_tasks(
+    State(state): State<Arc<AgentHandlerState>>,
+    context: RequestContextExtension,
+    headers: HeaderMap,
+    query: Result<Query<ListTasksParams>, QueryRejection>,
+) -> Response {
+    match query {
+        Ok(Query(params)) => {
+            run(
+                &state,
+                context,
+                &headers,
+                A2aRequestParams::ListTasks(params),
+            )
+            .await
+        },
+        Err(e) => rest_error(
+            JsonRpcErrorBuilder::invalid_params()
+                .with_data(json!(format!("Invalid query: {e}")))
+                .log_warn(format!("Invalid A2A REST query: {e}")),
+        ),
+    }
+}
+
+async fn handle_task_get(
+    State(state): State<Arc<AgentHandlerState>>,
+    context: RequestContextExtension,
+    headers: HeaderMap,
+    Path(resource): Path<String>,
+    Query(query): Query<TaskQuery>,
+) -> Response {
+    match resource.split_once(':') {
+        None => {
+            let request = A2aRequestParams::GetTask(TaskQueryParams {
+                id: TaskId::new(resource),
+                history_length: query.history_length,
+            });
+            run(&state, context, &headers, request).await
+        },
+        Some((id, "subscribe")) => {
+            if let Err(response) = authorize(&state, context, &headers).await {
+                return response;
+            }
+            let response =
+                handle_task_subscription(&state, &TaskId::new(id), REST_REQUEST_ID).await;
+            rest_response(response).await
+        },
+        Some((_, action)) => unknown_task_method(action),
+    }
+}
+
+async fn handle_task_post(
+    State(state): State<Arc<AgentHandlerState>>,
+    context: RequestContextExtension,
+    headers: HeaderMap,
+    Path(resource): Path<String>,
+) -> Response {
+    match resource.split_once(':') {
+        Some((id, "cancel")) => {
+            let request = A2aRequestParams::CancelTask(TaskIdParams {
+                id: TaskId::new(id),
+            });
+            run(&state, context, &headers, request).await
+        },
+        Some((_, action)) => unknown_task_method(action),
+        None => unknown_task_method(""),
+    }
+}
+
+async fn handle_set_push_config(
+    State(state): State<Arc<AgentHandlerState>>,
+    context: RequestContextExtension,
+    headers: HeaderMap,
+    Path(task): Path<String>,
+    body: Bytes,
+) -> Response {
+    match parse_body::<PushNotificationConfig>(&body) {
+        Ok(config) => {
+            let request = A2aRequestParams::SetTaskPushNotificationConfig(
+                SetTaskPushNotificationConfigRequest {
+                    task_id: TaskId::new(task),
+                    config,
+                },
+            );
+            run(&state, context, &headers, request).await
+        },
+        Err(response) => response,
+    }
+}
+
+async fn handle_get_push_configs(
+    State(state): State<Arc<AgentHandlerState>>,
+    context: RequestContextExtension,
+    headers: HeaderMap,
+    Path(task): Path<String>,
+) -> Response {
+    let request =
+        A2aRequestParams::GetTaskPushNotificationConfig(GetTaskPushNotificationConfigRequest {
+            task_id: TaskId::new(task),
+        });
+    run(&state, context, &headers, request).await
+}
+
+async fn handle_delete_push_configs(
+    State(state): State<Arc<AgentHandlerState>>,
+    context: RequestContextExtension,
+    headers: HeaderMap,
+    Path(task): Path<String>,
+) -> Response {
+    let request = A2aRequestParams::DeleteTaskPushNotificationConfig(
+        DeleteTaskPushNotificationConfigRequest {
+            task_id: TaskId::new(task),
+        },
+    );
+    run(&state, context, &headers, request).await
+}
+
+async fn run(
+    state: &Arc<AgentHandlerState>,
+    context: RequestContextExtension,
+    headers: &HeaderMap,
+    request: A2aRequestParams,
+) -> Response {
+    let start_time = std::time::Instant::now();
+    let context = match authorize(state, context, headers).await {
+        Ok(context) => context,
+        Err(response) => return response,
+    };
+
+    let response = dispatch_a2a_request(state, context, request, REST_REQUEST_ID, start_time).await;
+
+    let latency_ms = i64::try_from(start_time.elapsed().as_millis()).unwrap_or(i64::MAX);
+    tracing::info!(latency_ms = %latency_ms, "A2A REST request processed");
+
+    rest_response(response).await
+}
+
+async fn authorize(
+    state: &AgentHandlerState,
+    context: RequestContextExtension,
+    headers: &HeaderMap,
+) -> Result<RequestContext, Response> {
+    let Some(Extension(context)) = context else {
+        tracing::error!(
+            "RequestContext missing from request extensions - middleware configuration error"
+        );
+        return Err(rest_error(JsonRpcErrorBuilder::internal_error().with_data(
+            json!("Internal server error: request context unavailable"),
+        )));
+    };
+
+    if should_require_oauth(state).await
+        && let Err(response) = enforce_oauth(state, headers, &REST_REQUEST_ID).await
+    {
+        return Err(rest_response(response).await);
+    }
+
+    Ok(context)
+}
+
+#[expect(
+    clippy::result_large_err,
+    reason = "the error is the finished response returned straight from the route handler"
+)]
+fn parse_body<T: DeserializeOwned>(body: &Bytes) -> Result<T, Response> {
+    serde_json::from_slice(body).map_err(|e| {
+        rest_error(
+            JsonRpcErrorBuilder::invalid_params()
+                .with_data(json!(format!("Invalid request body: {e}")))
+                .log_warn(format!("Invalid A2A REST request body: {e}")),
+        )
+    })
+}
+
+fn unknown_task_method(action: &str) -> Response {
+    rest_error(
+        JsonRpcErrorBuilder::method_not_found()
+            .with_data(json!(format!("Unsupported task method: '{action}'"))),
+    )
+}
+
+fn rest_error(error: JsonRpcErrorBuilder) -> Response {
+    let (status, envelope) = error.build_with_status(&REST_REQUEST_ID);
+    let (status, body) = unwrap_envelope(status, envelope);
+    (status, Json(body)).into_response()
+}
+
+/// Rewrites a JSON-RPC response from the shared handlers into its REST form;
+/// SSE and other non-JSON responses pass through unchanged.
+async fn rest_response(response: Response) -> Response {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Some(envelope) = axum::body::to_bytes(body, usize::MAX)
+        .await
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+    else {
+        return rest_error(
+            JsonRpcErrorBuilder::internal_error()
+                .with_data(json!("Handler returned an unreadable response"))
+                .log_error("A2A REST response body was not valid JSON"),
+        );
+    };
+
+    let (status, body) = unwrap_envelope(parts.status, envelope);
+    parts.status = status;
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(body.to_string()))
+}
+
+fn unwrap_envelope(status: StatusCode, mut envelope: Value) -> (StatusCode, Value) {
+    if let Some(error) = envelope.get_mut("error").map(Value::take) {
+        // Why: JSON-RPC reports handler failures with HTTP 200; REST clients
+        // read the outcome from the status instead.
+        let status = if status.is_success() {
+            error["code"]
+                .as_i64()
+                .and_then(|code| i32::try_from(code).ok())
+                .map_or(StatusCode::INTERNAL_SERVER_ERROR, status_for_code)
+        } else {
+            status
+        };
+        return (status, json!({ "error": error }));
+    }
+
+    let result = envelope.get_mut("result").map(Value::take);
+    (status, result.unwrap_or(envelope))
+}
@@ -12,11 +12,13 @@ pub mod auth;
 pub mod errors;
 pub mod handlers;
 pub mod processing;
+pub mod recovery;
 pub mod server;
 pub mod standalone;
 pub mod streaming;
 
 pub use handlers::AgentHandlerState;
+pub use recovery::{RecoveryReport, TaskRecoveryService};
 pub use server::Server;
 pub use standalone::run_standalone;
 pub use systemprompt_models::AgentConfig;
@@ -0,0 +1,57 @@
+//! Follow-up messages that answer a tool approval.
+//!
+//! A message whose `taskId` names an existing task is accepted only as the
+//! decision for that task's pending approval: the task must be
+//! `input-required` in the message's context and the message must carry a
+//! [`ToolApprovalDecision`] for the approval it is waiting on. Anything else
+//! is refused rather than starting a second run on the same task.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use crate::models::ToolApprovalDecision;
+use crate::models::a2a::{Message, TaskState};
+use crate::repository::A2ARepositories;
+use crate::services::shared::{AgentServiceError, Result};
+
+/// Whether `message` resumes a task suspended for approval. `Ok(false)` means
+/// the message starts a new task.
+pub async fn validate_approval_follow_up(
+    repositories: &A2ARepositories,
+    message: &Message,
+) -> Result<bool> {
+    let Some(task_id) = &message.task_id else {
+        return Ok(false);
+    };
+    let Some(task) = repositories.tasks.get_task(task_id).await? else {
+        return Ok(false);
+    };
+    if task.context_id != message.context_id {
+        return Err(AgentServiceError::Validation(
+            "taskId".to_owned(),
+            format!("task {task_id} belongs to a different context"),
+        ));
+    }
+    if task.status.state != TaskState::InputRequired {
+        return Err(AgentServiceError::Conflict(format!(
+            "task {task_id} is not awaiting input"
+        )));
+    }
+    let Some(decision) = ToolApprovalDecision::from_message(message) else {
+        return Err(AgentServiceError::Validation(
+            "parts".to_owned(),
+            format!("task {task_id} is awaiting a tool-approval-decision data part"),
+        ));
+    };
+    let pending = repositories
+        .execution_steps
+        .pending_tool_approval(task_id)
+        .await?;
+    match pending {
+        Some(pending) if pending.approval_id == decision.approval_id => Ok(true),
+        _ => Err(AgentServiceError::Conflict(format!(
+            "approval {} is not pending on task {task_id}",
+            decision.approval_id
+        ))),
+    }
+}
@@ -0,0 +1,86 @@
+//! Run checkpoints written around every message run.
+//!
+//! A run records the message that started it before any work happens and
+//! drops the record once it ends, whatever the outcome. While the run lives,
+//! its process holds the checkpoint's lease and renews it every
+//! `LEASE_RENEWAL`, so a checkpoint whose lease has run out marks a run no
+//! live process is finishing — one a restarting replica may take over.
+//! Checkpoint writes are best-effort: a failure is logged and the run carries
+//! on.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use systemprompt_identifiers::TaskId;
+use systemprompt_models::RequestContext;
+
+use crate::models::a2a::Message;
+use crate::repository::task::{SaveCheckpointParams, TaskRepository};
+
+/// How long a checkpoint stays leased to the process running it without a
+/// renewal.
+pub const RUN_LEASE: Duration = Duration::from_secs(120);
+
+const LEASE_RENEWAL: Duration = Duration::from_secs(30);
+
+static RUN_CLAIMANT: LazyLock<String> = LazyLock::new(|| uuid::Uuid::new_v4().to_string());
+
+/// This process's name on the checkpoint leases it holds.
+pub fn run_claimant() -> &'static str {
+    &RUN_CLAIMANT
+}
+
+pub async fn save_run_checkpoint(
+    tasks: &TaskRepository,
+    task_id: &TaskId,
+    message: &Message,
+    context: &RequestContext,
+) {
+    let mut message = message.clone();
+    message.task_id = Some(task_id.clone());
+    match tasks
+        .save_checkpoint(SaveCheckpointParams {
+            task_id,
+            message: &message,
+            user_type: context.user_type(),
+            claimant: run_claimant(),
+            lease: RUN_LEASE,
+        })
+        .await
+    {
+        Ok(()) => hold_run_lease(tasks.clone(), task_id.clone()),
+        Err(e) => {
+            tracing::warn!(task_id = %task_id, error = %e, "Failed to save run checkpoint");
+        },
+    }
+}
+
+/// Renews this process's lease on the checkpoint of `task_id` in the
+/// background until the checkpoint is cleared or another process takes the
+/// lease over.
+pub fn hold_run_lease(tasks: TaskRepository, task_id: TaskId) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(LEASE_RENEWAL).await;
+            match tasks
+                .renew_checkpoint_lease(&task_id, run_claimant(), RUN_LEASE)
+                .await
+            {
+                Ok(true) => {},
+                Ok(false) => break,
+                Err(e) => {
+                    tracing::warn!(task_id = %task_id, error = %e, "Failed to renew run checkpoint lease");
+                },
+            }
+        }
+    });
+}
+
+pub async fn clear_run_checkpoint(tasks: &TaskRepository, task_id: &TaskId) {
+    if let Err(e) = tasks.delete_checkpoint(task_id).await {
+        tracing::warn!(task_id = %task_id, error = %e, "Failed to clear run checkpoint");
+    }
+}
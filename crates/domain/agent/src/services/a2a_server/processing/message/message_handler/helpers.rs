@@ -5,15 +5,24 @@
 
 use systemprompt_models::{AgUiEventBuilder, AgUiMessageRole, RequestContext};
 
+use crate::models::ToolApprovalRequest;
 use crate::models::a2a::{Artifact, Message, Task};
 use crate::services::a2a_server::processing::message::StreamEvent;
 use crate::services::a2a_server::streaming::webhook_client::broadcast_agui_event;
 use crate::services::shared::{AgentServiceError, Result};
 
+pub(super) enum StreamOutcome {
+    Completed {
+        response_text: String,
+        artifacts: Vec<Artifact>,
+    },
+    InputRequired(ToolApprovalRequest),
+}
+
 pub(super) async fn collect_stream_response(
     mut chunk_rx: tokio::sync::mpsc::Receiver<StreamEvent>,
     context: &RequestContext,
-) -> Result<(String, Vec<Artifact>)> {
+) -> Result<StreamOutcome> {
     let mut response_text = String::new();
     let mut tool_artifacts = Vec::new();
 
@@ -29,6 +38,9 @@ pub(super) async fn collect_stream_response(
                 response_text = full_text;
                 tool_artifacts = artifacts;
             },
+            StreamEvent::InputRequired(request) => {
+                return Ok(StreamOutcome::InputRequired(request));
+            },
             StreamEvent::Error(error) => {
                 let error_event =
                     AgUiEventBuilder::run_error(error.clone(), Some("EXECUTION_ERROR".to_owned()));
@@ -47,7 +59,10 @@ pub(super) async fn collect_stream_response(
         }
     }
 
-    Ok((response_text, tool_artifacts))
+    Ok(StreamOutcome::Completed {
+        response_text,
+        artifacts: tool_artifacts,
+    })
 }
 
 pub(super) struct BroadcastAguiLifecycleParams<'a> {
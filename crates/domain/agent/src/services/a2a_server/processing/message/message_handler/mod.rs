@@ -1,9 +1,13 @@
 //! Non-streaming message handling for [`MessageProcessor`].
 //!
 //! Implements [`MessageProcessor::handle_message`]: it validates the context,
-//! persists a submitted task, runs the stream pipeline to completion, builds
-//! the finished [`Task`](crate::models::a2a::Task), persists it, and broadcasts
-//! the completion and AG-UI lifecycle events.
+//! persists a submitted task (or resumes one awaiting a tool approval), runs
+//! the stream pipeline to completion, builds the finished
+//! [`Task`](crate::models::a2a::Task), persists it, and broadcasts the
+//! completion and AG-UI lifecycle events. A run that stops for approval
+//! returns the task in `input-required` instead. Each run is checkpointed
+//! while in flight; [`MessageProcessor::resume_interrupted_task`] runs a task
+//! a previous agent process left working again from its checkpoint.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -15,7 +19,7 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use self::helpers::{
-    BroadcastAguiLifecycleParams, broadcast_agui_lifecycle, collect_stream_response,
+    BroadcastAguiLifecycleParams, StreamOutcome, broadcast_agui_lifecycle, collect_stream_response,
 };
 use crate::models::a2a::{Message, MessageRole, Part, Task, TaskState, TaskStatus, TextPart};
 use crate::services::a2a_server::processing::message::persistence::{
@@ -23,8 +27,10 @@ use crate::services::a2a_server::processing::message::persistence::{
 };
 use crate::services::a2a_server::processing::message::stream_processor::StreamProcessor;
 use crate::services::a2a_server::processing::message::{
-    MessageProcessor, ProcessMessageStreamParams,
+    MessageProcessor, PersistInputRequiredTaskOnProcessorParams, ProcessMessageStreamParams,
+    clear_run_checkpoint, save_run_checkpoint, validate_approval_follow_up,
 };
+use crate::services::a2a_server::processing::strategies::ToolReplay;
 use crate::services::a2a_server::processing::task_builder::build_completed_task;
 use crate::services::a2a_server::streaming::broadcast::{
     BroadcastTaskCreatedParams, broadcast_task_created,
@@ -56,49 +62,89 @@ impl MessageProcessor {
 
         let context_id = &message.context_id;
 
-        self.repositories
-            .contexts
-            .get_context(context_id, context.user_id())
-            .await
-            .map_err(|e| {
-                AgentServiceError::Internal(format!(
-                    "Context validation failed - context_id: {}, user_id: {}, error: {}",
-                    context_id,
-                    context.user_id(),
-                    e
-                ))
-            })?;
-
-        tracing::info!(
-            context_id = %context_id,
-            user_id = %context.user_id(),
-            "Context validated"
-        );
+        self.validate_context(context_id, context).await?;
 
         let task_id = resolve_task_id(&message);
-        let task = new_submitted_task(&task_id, context_id, agent_name);
-
-        self.persist_and_announce(&task, &message, agent_name, context)
+        self.start_or_resume_task(&task_id, &message, agent_name, context)
             .await?;
+        save_run_checkpoint(&self.repositories.tasks, &task_id, &message, context).await;
 
-        let stream_processor = StreamProcessor {
-            ai_service: Arc::clone(&self.ai_service),
-            context_service: self.context_service.clone(),
-            skill_service: Arc::clone(&self.skill_service),
-            execution_step_repo: Arc::clone(&self.execution_step_repo),
-        };
-
-        let chunk_rx = stream_processor
-            .process_message_stream(ProcessMessageStreamParams {
+        let result = self
+            .complete_run(ProcessMessageStreamParams {
                 a2a_message: &message,
                 agent_runtime,
                 agent_name,
                 context,
                 task_id: task_id.clone(),
+                tool_replay: ToolReplay::default(),
             })
-            .await?;
+            .await;
+        clear_run_checkpoint(&self.repositories.tasks, &task_id).await;
+        result
+    }
 
-        let (response_text, tool_artifacts) = collect_stream_response(chunk_rx, context).await?;
+    /// Runs `message` again for a task an earlier agent process left
+    /// working. The task already exists, so nothing is created or announced;
+    /// tool calls its completed steps recorded are answered from those
+    /// results rather than run twice.
+    pub async fn resume_interrupted_task(
+        &self,
+        message: &Message,
+        agent_runtime: &crate::models::AgentRuntimeInfo,
+        agent_name: &str,
+        context: &RequestContext,
+    ) -> Result<Task> {
+        let task_id = message.task_id.clone().ok_or_else(|| {
+            AgentServiceError::Internal("Checkpoint message has no task id".to_owned())
+        })?;
+        let steps = self.execution_step_repo.list_by_task(&task_id).await?;
+        let tool_replay = ToolReplay::from_steps(&steps);
+        tracing::info!(
+            task_id = %task_id,
+            recorded_tool_results = tool_replay.len(),
+            "Resuming interrupted task"
+        );
+
+        let result = self
+            .complete_run(ProcessMessageStreamParams {
+                a2a_message: message,
+                agent_runtime,
+                agent_name,
+                context,
+                task_id: task_id.clone(),
+                tool_replay,
+            })
+            .await;
+        clear_run_checkpoint(&self.repositories.tasks, &task_id).await;
+        result
+    }
+
+    async fn complete_run(&self, params: ProcessMessageStreamParams<'_>) -> Result<Task> {
+        let message = params.a2a_message;
+        let agent_name = params.agent_name;
+        let context = params.context;
+        let task_id = params.task_id.clone();
+        let context_id = &message.context_id;
+
+        let outcome = self.run_pipeline(params).await?;
+        let (response_text, tool_artifacts) = match outcome {
+            StreamOutcome::Completed {
+                response_text,
+                artifacts,
+            } => (response_text, artifacts),
+            StreamOutcome::InputRequired(request) => {
+                return self
+                    .persist_input_required_task(PersistInputRequiredTaskOnProcessorParams {
+                        task_id: &task_id,
+                        context_id,
+                        user_message: message,
+                        request: &request,
+                        context,
+                        agent_name,
+                    })
+                    .await;
+            },
+        };
 
         let task = build_completed_task(
             task_id,
@@ -108,7 +154,7 @@ impl MessageProcessor {
             tool_artifacts,
         );
 
-        let agent_message = resolve_agent_message(&task, &message, &response_text);
+        let agent_message = resolve_agent_message(&task, message, &response_text);
 
         if context.user_type() == systemprompt_models::auth::UserType::Anon {
             tracing::warn!(
@@ -118,7 +164,7 @@ impl MessageProcessor {
             );
         }
 
-        self.persist_or_mark_failed(&task, &message, &agent_message, context)
+        self.persist_or_mark_failed(&task, message, &agent_message, context)
             .await?;
 
         broadcast_completion(&task, context).await;
@@ -135,6 +181,68 @@ impl MessageProcessor {
         Ok(task)
     }
 
+    async fn validate_context(
+        &self,
+        context_id: &ContextId,
+        context: &RequestContext,
+    ) -> Result<()> {
+        self.repositories
+            .contexts
+            .get_context(context_id, context.user_id())
+            .await
+            .map_err(|e| {
+                AgentServiceError::Internal(format!(
+                    "Context validation failed - context_id: {}, user_id: {}, error: {}",
+                    context_id,
+                    context.user_id(),
+                    e
+                ))
+            })?;
+
+        tracing::info!(
+            context_id = %context_id,
+            user_id = %context.user_id(),
+            "Context validated"
+        );
+        Ok(())
+    }
+
+    async fn run_pipeline(&self, params: ProcessMessageStreamParams<'_>) -> Result<StreamOutcome> {
+        let context = params.context;
+        let stream_processor = StreamProcessor {
+            ai_service: Arc::clone(&self.ai_service),
+            context_service: self.context_service.clone(),
+            compaction_service: self.compaction_service.clone(),
+            skill_service: Arc::clone(&self.skill_service),
+            execution_step_repo: Arc::clone(&self.execution_step_repo),
+        };
+
+        let chunk_rx = stream_processor.process_message_stream(params).await?;
+        collect_stream_response(chunk_rx, context).await
+    }
+
+    async fn start_or_resume_task(
+        &self,
+        task_id: &TaskId,
+        message: &Message,
+        agent_name: &str,
+        context: &RequestContext,
+    ) -> Result<()> {
+        if !validate_approval_follow_up(&self.repositories, message).await? {
+            let task = new_submitted_task(task_id, &message.context_id, agent_name);
+            return self
+                .persist_and_announce(&task, message, agent_name, context)
+                .await;
+        }
+        self.repositories
+            .tasks
+            .update_task_state(task_id, TaskState::Working, &chrono::Utc::now())
+            .await
+            .map_err(|e| AgentServiceError::Internal(format!("Failed to resume task: {e}")))?;
+        tracing::info!(task_id = %task_id, "Resuming task suspended for tool approval");
+        Ok(())
+    }
+
     async fn persist_and_announce(
         &self,
         task: &Task,
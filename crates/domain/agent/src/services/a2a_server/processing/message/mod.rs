@@ -4,23 +4,32 @@
 //! inbound message and persist the resulting task. [`StreamProcessor`] drives
 //! the streaming execution pipeline, reporting progress as [`StreamEvent`]s
 //! over an mpsc channel. Both the streaming and non-streaming entry points live
-//! in the submodules.
+//! in the submodules. A message that answers a tool approval resumes its
+//! suspended task instead of creating one (see
+//! [`validate_approval_follow_up`]).
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
+mod approval;
+mod checkpoint;
 mod message_handler;
 mod persistence;
 mod stream_processor;
 
+pub use approval::validate_approval_follow_up;
+pub use checkpoint::{
+    RUN_LEASE, clear_run_checkpoint, hold_run_lease, run_claimant, save_run_checkpoint,
+};
 pub use stream_processor::StreamProcessor;
 
 use crate::services::shared::{AgentServiceError, Result};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
-use crate::models::AgentRuntimeInfo;
 use crate::models::a2a::{Artifact, Message, Task};
+use crate::models::{AgentRuntimeInfo, ToolApprovalRequest};
+use crate::services::a2a_server::processing::strategies::ToolReplay;
 use systemprompt_models::{AiProvider, CallToolResult, ToolCall};
 
 #[derive(Debug)]
@@ -38,12 +47,15 @@ pub enum StreamEvent {
         full_text: String,
         artifacts: Vec<Artifact>,
     },
+    /// The plan called a tool that needs a human decision; the task is
+    /// suspended until a follow-up message carries it.
+    InputRequired(ToolApprovalRequest),
     Error(String),
 }
 use crate::repository::A2ARepositories;
 use crate::repository::execution::ExecutionStepRepository;
-use crate::services::{ContextService, SkillService};
-use systemprompt_identifiers::TaskId;
+use crate::services::{ContextCompactionService, ContextService, SkillService};
+use systemprompt_identifiers::{ContextId, TaskId};
 use systemprompt_models::RequestContext;
 
 #[derive(Debug)]
@@ -56,6 +68,16 @@ pub struct PersistCompletedTaskOnProcessorParams<'a> {
     pub artifacts_already_published: bool,
 }
 
+#[derive(Debug)]
+pub struct PersistInputRequiredTaskOnProcessorParams<'a> {
+    pub task_id: &'a TaskId,
+    pub context_id: &'a ContextId,
+    pub user_message: &'a Message,
+    pub request: &'a ToolApprovalRequest,
+    pub context: &'a RequestContext,
+    pub agent_name: &'a str,
+}
+
 #[derive(Debug)]
 pub struct ProcessMessageStreamParams<'a> {
     pub a2a_message: &'a Message,
@@ -63,12 +85,16 @@ pub struct ProcessMessageStreamParams<'a> {
     pub agent_name: &'a str,
     pub context: &'a RequestContext,
     pub task_id: TaskId,
+    /// Results a resumed run answers repeated tool calls from; empty for a
+    /// fresh run.
+    pub tool_replay: ToolReplay,
 }
 
 pub struct MessageProcessor {
     repositories: Arc<A2ARepositories>,
     ai_service: Arc<dyn AiProvider>,
     context_service: ContextService,
+    compaction_service: ContextCompactionService,
     skill_service: Arc<SkillService>,
     execution_step_repo: Arc<ExecutionStepRepository>,
 }
@@ -87,6 +113,7 @@ impl MessageProcessor {
         ai_service: Arc<dyn AiProvider>,
     ) -> Result<Self> {
         let context_service = ContextService::new(repositories.tasks.clone());
+        let compaction_service = ContextCompactionService::new(repositories.contexts.clone());
         let execution_step_repo = Arc::new(repositories.execution_steps.clone());
         let skill_service = Arc::new(
             SkillService::new()?.with_execution_step_repo(Arc::clone(&execution_step_repo)),
@@ -96,6 +123,7 @@ impl MessageProcessor {
             repositories,
             ai_service,
             context_service,
+            compaction_service,
             skill_service,
             execution_step_repo,
         })
@@ -128,6 +156,25 @@ impl MessageProcessor {
         .await
     }
 
+    /// Suspends the task in `input-required` with the approval request as its
+    /// status message, persists both messages, and notifies the task's push
+    /// endpoints.
+    pub async fn persist_input_required_task(
+        &self,
+        params: PersistInputRequiredTaskOnProcessorParams<'_>,
+    ) -> Result<Task> {
+        persistence::persist_input_required_task(persistence::PersistInputRequiredTaskParams {
+            task_id: params.task_id,
+            context_id: params.context_id,
+            user_message: params.user_message,
+            request: params.request,
+            context: params.context,
+            agent_name: params.agent_name,
+            repositories: &self.repositories,
+        })
+        .await
+    }
+
     pub async fn process_message_stream(
         &self,
         params: ProcessMessageStreamParams<'_>,
@@ -135,6 +182,7 @@ impl MessageProcessor {
         let stream_processor = StreamProcessor {
             ai_service: Arc::clone(&self.ai_service),
             context_service: self.context_service.clone(),
+            compaction_service: self.compaction_service.clone(),
             skill_service: Arc::clone(&self.skill_service),
             execution_step_repo: Arc::clone(&self.execution_step_repo),
         };
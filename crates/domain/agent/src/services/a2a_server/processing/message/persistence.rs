@@ -1,17 +1,20 @@
-//! Message persistence during A2A processing.
+//! Message persistence during A2A processing: completed tasks with their
+//! artifacts, and tasks suspended in `input-required` for a tool approval.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 use crate::services::shared::{AgentServiceError, Result};
-use systemprompt_models::RequestContext;
+use systemprompt_identifiers::{ContextId, TaskId};
+use systemprompt_models::{RequestContext, TaskMetadata};
 
-use crate::models::a2a::{Message, Task};
+use crate::models::ToolApprovalRequest;
+use crate::models::a2a::{Message, Task, TaskState, TaskStatus};
 use crate::repository::A2ARepositories;
 use crate::repository::task::UpdateTaskAndSaveMessagesParams;
 use crate::services::ArtifactPublishingService;
 use crate::services::a2a_server::streaming::{
-    broadcast_artifact_created, broadcast_task_completed,
+    broadcast_artifact_created, broadcast_task_completed, deliver_push_notifications,
 };
 
 pub(super) struct PersistCompletedTaskParams<'a> {
@@ -101,3 +104,65 @@ pub(super) async fn persist_completed_task(params: PersistCompletedTaskParams<'_
 pub(super) async fn broadcast_completion(task: &Task, context: &RequestContext) {
     broadcast_task_completed(task, context.user_id(), context.auth_token().as_str()).await;
 }
+
+pub(super) struct PersistInputRequiredTaskParams<'a> {
+    pub task_id: &'a TaskId,
+    pub context_id: &'a ContextId,
+    pub user_message: &'a Message,
+    pub request: &'a ToolApprovalRequest,
+    pub context: &'a RequestContext,
+    pub agent_name: &'a str,
+    pub repositories: &'a A2ARepositories,
+}
+
+pub(super) async fn persist_input_required_task(
+    params: PersistInputRequiredTaskParams<'_>,
+) -> Result<Task> {
+    let PersistInputRequiredTaskParams {
+        task_id,
+        context_id,
+        user_message,
+        request,
+        context,
+        agent_name,
+        repositories,
+    } = params;
+    let now = chrono::Utc::now();
+    repositories
+        .tasks
+        .update_task_state(task_id, TaskState::InputRequired, &now)
+        .await?;
+
+    let agent_message = request.to_message(task_id, context_id);
+    let task = Task {
+        id: task_id.clone(),
+        context_id: context_id.clone(),
+        status: TaskStatus {
+            state: TaskState::InputRequired,
+            message: Some(agent_message.clone()),
+            timestamp: Some(now),
+        },
+        history: Some(vec![user_message.clone(), agent_message.clone()]),
+        artifacts: None,
+        metadata: Some(TaskMetadata::new_agent_message(agent_name.to_owned())),
+        created_at: Some(now),
+        last_modified: Some(now),
+    };
+    let persisted = persist_completed_task(PersistCompletedTaskParams {
+        task: &task,
+        user_message,
+        agent_message: &agent_message,
+        context,
+        repositories,
+        artifacts_already_published: true,
+    })
+    .await?;
+
+    tracing::info!(
+        task_id = %task_id,
+        approval_id = %request.approval_id,
+        "Task awaiting tool approval"
+    );
+    deliver_push_notifications(repositories, &persisted).await;
+    Ok(persisted)
+}
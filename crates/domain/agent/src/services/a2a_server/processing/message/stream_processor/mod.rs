@@ -3,7 +3,8 @@
 //! [`StreamProcessor`] extracts content from an A2A message (including
 //! supported file parts decoded into [`AiContentPart`]s), then spawns the
 //! strategy-driven pipeline that streams text, tool, and completion events back
-//! to the caller.
+//! to the caller. The context's history is compacted to the agent's budget
+//! before it is replayed.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -16,7 +17,7 @@ use std::sync::Arc;
 
 use crate::models::a2a::{FilePart, Message, Part};
 use crate::repository::execution::ExecutionStepRepository;
-use crate::services::{ContextService, SkillService};
+use crate::services::{ContextCompactionService, ContextService, SkillService};
 use systemprompt_models::{
     AiContentPart, AiProvider, is_supported_audio, is_supported_image, is_supported_text,
     is_supported_video,
@@ -29,6 +30,7 @@ use systemprompt_models::{
 pub struct StreamProcessor {
     pub ai_service: Arc<dyn AiProvider>,
     pub context_service: ContextService,
+    pub compaction_service: ContextCompactionService,
     pub skill_service: Arc<SkillService>,
     pub execution_step_repo: Arc<ExecutionStepRepository>,
 }
@@ -3,7 +3,10 @@
 //! Implements [`StreamProcessor::process_message_stream`] and the background
 //! task it spawns: it assembles AI messages, selects an execution strategy,
 //! runs it, builds artifacts, synthesizes a final response, and emits a
-//! `Complete` event.
+//! `Complete` event. The history replayed into the run is first compacted to
+//! the agent's budget. A message carrying a tool-approval decision resumes the
+//! suspended plan from its stored conversation instead, and a run that stops
+//! for approval emits `InputRequired` rather than completing.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -19,12 +22,14 @@ use super::StreamProcessor;
 use super::helpers::{
     SynthesizeFinalResponseParams, build_artifacts_from_results, synthesize_final_response,
 };
-use crate::models::AgentRuntimeInfo;
 use crate::models::a2a::Artifact;
+use crate::models::{AgentRuntimeInfo, ToolApprovalDecision, ToolApprovalRequest};
 use crate::services::a2a_server::processing::message::{ProcessMessageStreamParams, StreamEvent};
 use crate::services::a2a_server::processing::strategies::{
-    ExecutionContext, ExecutionResult, ExecutionStrategySelector,
+    ExecutionContext, ExecutionResult, ExecutionStrategySelector, PlannedAgenticStrategy,
+    ToolReplay,
 };
+use crate::services::compaction::CompactHistoryParams;
 use crate::services::shared::Result;
 use systemprompt_identifiers::AgentName;
 use systemprompt_models::{AiMessage, RequestContext};
@@ -40,6 +45,7 @@ impl StreamProcessor {
             agent_name,
             context,
             task_id,
+            tool_replay,
         } = params;
         let (tx, rx) = mpsc::channel(1024);
 
@@ -48,16 +54,27 @@ impl StreamProcessor {
         let agent_name_string = agent_name.to_owned();
         let agent_name_typed = AgentName::new(agent_name);
         let (user_text, user_parts) = Self::extract_message_content(a2a_message);
+        let decision = ToolApprovalDecision::from_message(a2a_message);
 
         let context_id = &a2a_message.context_id;
-        let conversation_history = self
+        let history_entries = self
             .context_service
-            .load_conversation_history(context_id)
+            .load_history_entries(context_id)
             .await
             .unwrap_or_else(|e| {
                 tracing::warn!(error = %e, context_id = %context_id, "Failed to load conversation history");
                 vec![]
             });
+        let conversation_history = self
+            .compaction_service
+            .compact(CompactHistoryParams {
+                ai_service: self.ai_service.as_ref(),
+                agent_runtime: &agent_runtime,
+                context_id,
+                request_ctx: context,
+                entries: history_entries,
+            })
+            .await;
 
         tracing::info!(
             context_id = %context_id,
@@ -65,9 +82,7 @@ impl StreamProcessor {
             "Loaded historical messages for context"
         );
 
-        let context_id_for_artifacts = context_id.clone();
         let context_id_owned = context_id.clone();
-        let task_id_for_artifacts = task_id.clone();
 
         let request_ctx = context
             .clone()
@@ -85,12 +100,12 @@ impl StreamProcessor {
             execution_step_repo,
             task_id,
             context_id_owned,
-            context_id_for_artifacts,
-            task_id_for_artifacts,
             request_ctx,
             conversation_history,
             user_text,
             user_parts,
+            decision,
+            tool_replay,
             tx,
         }));
 
@@ -107,12 +122,12 @@ struct RunStreamPipelineParams {
     execution_step_repo: Arc<crate::repository::execution::ExecutionStepRepository>,
     task_id: systemprompt_identifiers::TaskId,
     context_id_owned: systemprompt_identifiers::ContextId,
-    context_id_for_artifacts: systemprompt_identifiers::ContextId,
-    task_id_for_artifacts: systemprompt_identifiers::TaskId,
     request_ctx: RequestContext,
     conversation_history: Vec<AiMessage>,
     user_text: String,
     user_parts: Vec<systemprompt_models::AiContentPart>,
+    decision: Option<ToolApprovalDecision>,
+    tool_replay: ToolReplay,
     tx: mpsc::Sender<StreamEvent>,
 }
 
@@ -126,12 +141,12 @@ async fn run_stream_pipeline(params: RunStreamPipelineParams) {
         execution_step_repo,
         task_id,
         context_id_owned,
-        context_id_for_artifacts,
-        task_id_for_artifacts,
         request_ctx,
         conversation_history,
         user_text,
         user_parts,
+        decision,
+        tool_replay,
         tx,
     } = params;
 
@@ -141,41 +156,41 @@ async fn run_stream_pipeline(params: RunStreamPipelineParams) {
         "Processing streaming message for agent"
     );
 
-    let ai_messages = build_ai_messages(BuildAiMessagesParams {
-        agent_runtime: &agent_runtime,
-        conversation_history,
-        user_text,
-        user_parts,
-        skill_service: &skill_service,
-        request_ctx: &request_ctx,
-    })
-    .await;
-
-    let ai_messages_for_synthesis = ai_messages.clone();
-    let ai_service_for_builder = Arc::clone(&ai_service);
-
     let execution_context = ExecutionContext {
         ai_service: Arc::clone(&ai_service),
         skill_service: Arc::clone(&skill_service),
         agent_runtime: agent_runtime.clone(),
         agent_name: agent_name_typed,
         task_id: task_id.clone(),
-        context_id: context_id_owned,
+        context_id: context_id_owned.clone(),
         tx: tx.clone(),
         request_ctx: request_ctx.clone(),
         execution_step_repo: Arc::clone(&execution_step_repo),
+        tool_replay,
     };
 
-    let Some(execution_result) = run_strategy(execution_context, ai_messages).await else {
+    let messages_params = BuildAiMessagesParams {
+        agent_runtime: &agent_runtime,
+        conversation_history,
+        user_text,
+        user_parts,
+        skill_service: &skill_service,
+        request_ctx: &request_ctx,
+    };
+    let Some((mut execution_result, ai_messages_for_synthesis)) =
+        run_or_resume(execution_context, decision, messages_params).await
+    else {
         return;
     };
 
-    let Some(artifacts) = build_artifacts_or_report(
-        &execution_result,
-        &context_id_for_artifacts,
-        &task_id_for_artifacts,
-        &tx,
-    ) else {
+    if let Some(request) = execution_result.pending_approval.take() {
+        send_input_required_event(&tx, request);
+        return;
+    }
+
+    let Some(artifacts) =
+        build_artifacts_or_report(&execution_result, &context_id_owned, &task_id, &tx)
+    else {
         return;
     };
 
@@ -184,7 +199,7 @@ async fn run_stream_pipeline(params: RunStreamPipelineParams) {
         tool_results: &execution_result.tool_results,
         artifacts: &artifacts,
         accumulated_text: &execution_result.accumulated_text,
-        ai_service: ai_service_for_builder,
+        ai_service,
         agent_runtime: &agent_runtime,
         ai_messages_for_synthesis,
         tx: tx.clone(),
@@ -196,17 +211,29 @@ async fn run_stream_pipeline(params: RunStreamPipelineParams) {
     send_complete_event(&tx, final_text, artifacts);
 }
 
+async fn run_or_resume(
+    execution_context: ExecutionContext,
+    decision: Option<ToolApprovalDecision>,
+    messages_params: BuildAiMessagesParams<'_>,
+) -> Option<(ExecutionResult, Vec<AiMessage>)> {
+    if let Some(decision) = decision {
+        return resume_strategy(execution_context, &decision).await;
+    }
+    let ai_messages = build_ai_messages(messages_params).await;
+    run_strategy(execution_context, ai_messages.clone())
+        .await
+        .map(|result| (result, ai_messages))
+}
+
 async fn run_strategy(
     execution_context: ExecutionContext,
     ai_messages: Vec<AiMessage>,
 ) -> Option<ExecutionResult> {
-    let has_tools = !execution_context
-        .agent_runtime
-        .mcp_servers
-        .include
-        .is_empty();
+    let runtime = &execution_context.agent_runtime;
+    let has_tools = !runtime.mcp_servers.include.is_empty() || !runtime.delegates.is_empty();
     tracing::info!(
-        mcp_server_count = execution_context.agent_runtime.mcp_servers.include.len(),
+        mcp_server_count = runtime.mcp_servers.include.len(),
+        delegate_count = runtime.delegates.len(),
         has_tools = has_tools,
         "Agent MCP server status"
     );
@@ -216,12 +243,58 @@ async fn run_strategy(
     let tx = execution_context.tx.clone();
     let execution_step_repo = Arc::clone(&execution_context.execution_step_repo);
 
-    match strategy.execute(execution_context, ai_messages).await {
+    let result = strategy.execute(execution_context, ai_messages).await;
+    finish_strategy(result, &task_id, &tx, execution_step_repo).await
+}
+
+async fn resume_strategy(
+    execution_context: ExecutionContext,
+    decision: &ToolApprovalDecision,
+) -> Option<(ExecutionResult, Vec<AiMessage>)> {
+    let task_id = execution_context.task_id.clone();
+    let tx = execution_context.tx.clone();
+    let execution_step_repo = Arc::clone(&execution_context.execution_step_repo);
+
+    let pending = match execution_step_repo.pending_tool_approval(&task_id).await {
+        Ok(Some(pending)) if pending.approval_id == decision.approval_id => pending,
+        Ok(_) => {
+            report_stream_error(
+                &tx,
+                format!(
+                    "Tool approval {} is not pending on this task",
+                    decision.approval_id
+                ),
+            );
+            return None;
+        },
+        Err(e) => {
+            report_stream_error(&tx, format!("Failed to load tool approval: {e}"));
+            return None;
+        },
+    };
+    let ai_messages = pending.messages.clone();
+
+    let result = PlannedAgenticStrategy::new()
+        .resume(execution_context, pending, decision)
+        .await;
+    finish_strategy(result, &task_id, &tx, execution_step_repo)
+        .await
+        .map(|result| (result, ai_messages))
+}
+
+async fn finish_strategy(
+    result: Result<ExecutionResult>,
+    task_id: &systemprompt_identifiers::TaskId,
+    tx: &mpsc::Sender<StreamEvent>,
+    execution_step_repo: Arc<crate::repository::execution::ExecutionStepRepository>,
+) -> Option<ExecutionResult> {
+    match result {
         Ok(result) => {
             tracing::info!(
                 text_len = result.accumulated_text.len(),
                 tool_call_count = result.tool_calls.len(),
                 tool_result_count = result.tool_results.len(),
+                awaiting_approval = result.pending_approval.is_some(),
                 "Processing complete"
             );
             Some(result)
@@ -230,12 +303,12 @@ async fn run_strategy(
             tracing::error!(error = %e, "Execution failed");
             let tracking = crate::services::ExecutionTrackingService::new(execution_step_repo);
             if let Err(fail_err) = tracking
-                .fail_in_progress_steps(&task_id, &e.to_string())
+                .fail_in_progress_steps(task_id, &e.to_string())
                 .await
             {
                 tracing::error!(error = %fail_err, "Failed to mark steps as failed");
             }
-            report_stream_error(&tx, format!("Execution failed: {e}"));
+            report_stream_error(tx, format!("Execution failed: {e}"));
             None
         },
     }
@@ -269,6 +342,13 @@ fn report_stream_error(tx: &mpsc::Sender<StreamEvent>, message: String) {
     }
 }
 
+fn send_input_required_event(tx: &mpsc::Sender<StreamEvent>, request: ToolApprovalRequest) {
+    tracing::info!(approval_id = %request.approval_id, "Sending InputRequired event");
+    if tx.try_send(StreamEvent::InputRequired(request)).is_err() {
+        tracing::error!("Failed to send InputRequired event, channel closed");
+    }
+}
+
 fn send_complete_event(
     tx: &mpsc::Sender<StreamEvent>,
     final_text: String,
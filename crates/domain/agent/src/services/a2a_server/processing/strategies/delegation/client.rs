@@ -0,0 +1,272 @@
+//! Sends a delegated task to a peer agent and follows its status stream.
+//!
+//! Status frames are read generically: lifecycle frames carry the legacy
+//! lowercase state names while completion frames carry `TASK_STATE_*`, and
+//! [`TaskState`]'s `FromStr` accepts both. Every change of the peer's state is
+//! recorded on the delegating task as a delegation execution step.
+//!
+//! The peer is called on a delegated token rather than the caller's own: an
+//! actor token naming this agent is minted and exchanged with the caller's
+//! token as the subject, so the peer's `act` chain gains this hop.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use reqwest_eventsource::{Event, EventSource};
+use serde_json::{Value, json};
+use systemprompt_identifiers::{JwtToken, MessageId, TaskId, headers};
+use systemprompt_models::a2a::{Message, MessageRole, Part, TaskState, TextPart, methods};
+use systemprompt_models::{Config, StepContent};
+use systemprompt_security::jwt::{ActorTokenParams, JwtService, TokenExchangeParams};
+
+use super::super::ExecutionContext;
+use super::super::plan_executor::ToolOutcome;
+use super::PARENT_TASK_METADATA_KEY;
+use crate::models::a2a::jsonrpc::{JSON_RPC_VERSION_2_0, Request, RequestId};
+use crate::models::a2a::protocol::MessageSendParams;
+use crate::services::ExecutionTrackingService;
+use crate::services::a2a_server::processing::message::StreamEvent;
+use crate::services::registry::AgentRegistry;
+use crate::services::shared::{AgentServiceError, Result};
+
+/// One status frame from the peer's stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelegationUpdate {
+    pub task_id: TaskId,
+    pub state: TaskState,
+    pub text: Option<String>,
+    pub is_final: bool,
+}
+
+/// Parses an SSE data frame. Frames that are not status updates yield
+/// `None`; a JSON-RPC error frame is an error.
+pub fn parse_status_frame(data: &str) -> Result<Option<DelegationUpdate>> {
+    let Ok(frame) = serde_json::from_str::<Value>(data) else {
+        tracing::debug!(data = %data, "Ignoring non-JSON delegation frame");
+        return Ok(None);
+    };
+
+    if let Some(error) = frame.get("error") {
+        let code = error
+            .get("code")
+            .and_then(Value::as_i64)
+            .unwrap_or_default();
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Err(AgentServiceError::Internal(format!(
+            "peer agent returned error ({code}): {message}"
+        )));
+    }
+
+    let Some(result) = frame.get("result") else {
+        return Ok(None);
+    };
+    let (Some(task_id), Some(state)) = (
+        result.get("taskId").and_then(Value::as_str),
+        result
+            .pointer("/status/state")
+            .and_then(Value::as_str)
+            .and_then(|s| TaskState::from_str(s).ok()),
+    ) else {
+        return Ok(None);
+    };
+
+    let text = result
+        .pointer("/status/message/parts")
+        .and_then(Value::as_array)
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|part| part.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join("")
+        })
+        .filter(|text| !text.is_empty());
+
+    Ok(Some(DelegationUpdate {
+        task_id: TaskId::new(task_id),
+        state,
+        text,
+        is_final: result
+            .get("final")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    }))
+}
+
+/// Runs `arguments.message` on `peer` and returns its final answer.
+pub async fn delegate(
+    context: &ExecutionContext,
+    peer: &str,
+    arguments: &Value,
+) -> Result<ToolOutcome> {
+    let text = arguments
+        .get("message")
+        .and_then(Value::as_str)
+        .filter(|text| !text.trim().is_empty())
+        .ok_or_else(|| {
+            AgentServiceError::Validation(
+                "message".to_owned(),
+                format!("delegating to {peer} needs a non-empty message"),
+            )
+        })?;
+
+    let peer_config = AgentRegistry::new()?.get_agent(peer).await?;
+    let config = Config::get().map_err(|e| {
+        AgentServiceError::Configuration("api_internal_url".to_owned(), e.to_string())
+    })?;
+    let token = exchange_token(context, &peer_config.name, &config.jwt_issuer)?;
+
+    let http_request = reqwest::Client::new()
+        .post(peer_config.construct_url(&config.api_internal_url))
+        .header(headers::AUTHORIZATION, format!("Bearer {}", token.as_str()))
+        .header(headers::TRACE_ID, context.request_ctx.trace_id().as_str())
+        .header(headers::CONTEXT_ID, context.context_id.as_str())
+        .header("Accept", "text/event-stream")
+        .json(&send_request(context, text));
+
+    let mut source = EventSource::new(http_request)
+        .map_err(|e| AgentServiceError::Network(format!("cannot reach agent {peer}: {e}")))?;
+
+    let outcome = follow_stream(context, peer, &mut source).await;
+    source.close();
+    outcome
+}
+
+/// Trades the caller's token for one whose `act` names this agent and that
+/// only `peer` accepts.
+fn exchange_token(context: &ExecutionContext, peer: &str, issuer: &str) -> Result<JwtToken> {
+    let actor_token = JwtService::generate_actor_token(&ActorTokenParams {
+        actor: context.agent_name.as_str(),
+        issuer,
+    })
+    .map_err(|e| AgentServiceError::Internal(format!("cannot mint actor token: {e}")))?;
+    JwtService::exchange_token(&TokenExchangeParams {
+        subject_token: context.request_ctx.auth_token().as_str(),
+        actor_token: actor_token.as_str(),
+        peer,
+        issuer,
+    })
+    .map_err(|e| AgentServiceError::Authentication(format!("delegation token exchange: {e}")))
+}
+
+fn send_request(context: &ExecutionContext, text: &str) -> Request<MessageSendParams> {
+    let message = Message {
+        role: MessageRole::User,
+        parts: vec![Part::Text(TextPart {
+            text: text.to_owned(),
+        })],
+        message_id: MessageId::generate(),
+        task_id: None,
+        context_id: context.context_id.clone(),
+        metadata: Some(json!({
+            PARENT_TASK_METADATA_KEY: context.task_id.as_str(),
+            "delegatedBy": context.agent_name.as_str(),
+        })),
+        extensions: None,
+        reference_task_ids: Some(vec![context.task_id.clone()]),
+    };
+
+    Request {
+        jsonrpc: JSON_RPC_VERSION_2_0.to_owned(),
+        method: methods::SEND_STREAMING_MESSAGE.to_owned(),
+        params: MessageSendParams {
+            message,
+            configuration: None,
+            metadata: None,
+        },
+        id: RequestId::String(uuid::Uuid::new_v4().to_string()),
+    }
+}
+
+async fn follow_stream(
+    context: &ExecutionContext,
+    peer: &str,
+    source: &mut EventSource,
+) -> Result<ToolOutcome> {
+    let tracking = ExecutionTrackingService::new(Arc::clone(&context.execution_step_repo));
+    let mut last_state = None;
+
+    while let Some(event) = source.next().await {
+        let data = match event {
+            Ok(Event::Open) => continue,
+            Ok(Event::Message(message)) => message.data,
+            Err(reqwest_eventsource::Error::StreamEnded) => break,
+            Err(e) => {
+                return Err(AgentServiceError::Network(format!(
+                    "stream from agent {peer} failed: {e}"
+                )));
+            },
+        };
+        let Some(update) = parse_status_frame(&data)? else {
+            continue;
+        };
+
+        if last_state != Some(update.state) || update.text.is_some() {
+            record_progress(context, &tracking, peer, &update).await;
+            last_state = Some(update.state);
+        }
+        if update.is_final {
+            return finish(peer, update);
+        }
+    }
+
+    Err(AgentServiceError::Network(format!(
+        "agent {peer} closed its stream before finishing"
+    )))
+}
+
+async fn record_progress(
+    context: &ExecutionContext,
+    tracking: &ExecutionTrackingService,
+    peer: &str,
+    update: &DelegationUpdate,
+) {
+    let step_content = StepContent::delegation(
+        peer,
+        update.task_id.clone(),
+        update.state,
+        update.text.clone(),
+    );
+    match tracking.track(context.task_id.clone(), step_content).await {
+        Ok(step) => {
+            if context
+                .tx
+                .try_send(StreamEvent::ExecutionStepUpdate { step })
+                .is_err()
+            {
+                tracing::debug!("Stream receiver dropped");
+            }
+        },
+        Err(e) => tracing::warn!(error = %e, peer = %peer, "Failed to record delegation step"),
+    }
+}
+
+fn finish(peer: &str, update: DelegationUpdate) -> Result<ToolOutcome> {
+    if update.state != TaskState::Completed {
+        return Err(AgentServiceError::Internal(format!(
+            "agent {peer} ended task {} as {:?}{}",
+            update.task_id,
+            update.state,
+            update
+                .text
+                .map(|text| format!(": {text}"))
+                .unwrap_or_default()
+        )));
+    }
+
+    Ok(ToolOutcome {
+        output: json!({
+            "agent": peer,
+            "taskId": update.task_id.as_str(),
+            "response": update.text.unwrap_or_default(),
+        }),
+        meta: None,
+    })
+}
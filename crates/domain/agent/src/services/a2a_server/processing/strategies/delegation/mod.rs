@@ -0,0 +1,141 @@
+//! Agent-to-agent delegation exposed as tools.
+//!
+//! An agent whose `metadata.delegates` lists other hosted agents is offered
+//! one tool per delegate alongside its MCP tools. [`delegation_tools`] builds
+//! those tools from the agent registry; invoking one sends an A2A
+//! `SendStreamingMessage` to the peer (see the `client` submodule) in the
+//! caller's context, on a token exchanged (RFC 8693) for the caller's with
+//! this agent as the actor, so the `act` chain records every hop. The message
+//! names the delegating task in its
+//! metadata; the peer links its task to that parent with
+//! [`link_delegated_task`], which also caps how deep a delegation chain may
+//! grow.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+mod client;
+
+pub use client::{DelegationUpdate, delegate, parse_status_frame};
+
+use serde_json::json;
+use systemprompt_identifiers::{McpServerId, TaskId, UserId};
+use systemprompt_models::McpTool;
+use systemprompt_models::a2a::Message;
+
+use super::ExecutionContext;
+use crate::repository::task::TaskRepository;
+use crate::services::registry::AgentRegistry;
+use crate::services::shared::{AgentServiceError, Result};
+
+/// Service id carried by delegation tools so they are routed to a peer agent
+/// rather than an MCP server.
+pub const DELEGATION_SERVICE_ID: &str = "a2a-delegation";
+
+pub const DELEGATION_TOOL_PREFIX: &str = "delegate_to_";
+
+/// Message-metadata key naming the task that delegated the message.
+pub const PARENT_TASK_METADATA_KEY: &str = "parentTaskId";
+
+/// Most delegation hops a task may sit below its root task.
+pub const MAX_DELEGATION_DEPTH: i64 = 4;
+
+#[must_use]
+pub fn delegation_tool_name(agent_name: &str) -> String {
+    format!("{DELEGATION_TOOL_PREFIX}{agent_name}")
+}
+
+/// The delegate a tool hands work to, or `None` for any other tool.
+#[must_use]
+pub fn delegate_of(tool: &McpTool) -> Option<&str> {
+    if tool.service_id.as_str() != DELEGATION_SERVICE_ID {
+        return None;
+    }
+    tool.name.strip_prefix(DELEGATION_TOOL_PREFIX)
+}
+
+/// One tool per delegate the registry knows; unknown delegates are skipped.
+pub async fn delegation_tools(delegates: &[String], registry: &AgentRegistry) -> Vec<McpTool> {
+    let mut tools = Vec::with_capacity(delegates.len());
+    for delegate in delegates {
+        let config = match registry.get_agent(delegate).await {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!(delegate = %delegate, error = %e, "Skipping unknown delegate agent");
+                continue;
+            },
+        };
+        let description = format!(
+            "Hand a task to the {} agent and return its answer. {}",
+            config.card.display_name, config.card.description
+        );
+        tools.push(
+            McpTool::new(
+                delegation_tool_name(delegate),
+                McpServerId::new(DELEGATION_SERVICE_ID),
+            )
+            .with_description(description.trim_end())
+            .with_input_schema(json!({
+                "type": "object",
+                "properties": {
+                    "message": {
+                        "type": "string",
+                        "description": "What the agent should do, with all the context it needs."
+                    }
+                },
+                "required": ["message"]
+            })),
+        );
+    }
+    tools
+}
+
+/// The agent's MCP tools followed by a tool for each of its delegates.
+pub async fn available_tools(context: &ExecutionContext) -> Result<Vec<McpTool>> {
+    let mut tools = context
+        .ai_service
+        .list_available_tools_for_agent(&context.agent_name, &context.request_ctx)
+        .await?;
+    let delegates = &context.agent_runtime.delegates;
+    if !delegates.is_empty() {
+        let registry = AgentRegistry::new()?;
+        tools.extend(delegation_tools(delegates, &registry).await);
+    }
+    Ok(tools)
+}
+
+/// The task that delegated `message`, when it came from another agent.
+#[must_use]
+pub fn delegating_task_id(message: &Message) -> Option<TaskId> {
+    message
+        .metadata
+        .as_ref()?
+        .get(PARENT_TASK_METADATA_KEY)?
+        .as_str()
+        .map(TaskId::new)
+}
+
+/// Links a delegated task to the task that delegated it. The parent must
+/// belong to the same user, and the chain above the new task may not exceed
+/// [`MAX_DELEGATION_DEPTH`] hops.
+pub async fn link_delegated_task(
+    tasks: &TaskRepository,
+    task_id: &TaskId,
+    parent_task_id: &TaskId,
+    user_id: &UserId,
+) -> Result<()> {
+    tasks
+        .validate_task_ownership(parent_task_id, user_id)
+        .await?;
+    let depth = tasks.delegation_depth(parent_task_id).await? + 1;
+    if depth > MAX_DELEGATION_DEPTH {
+        return Err(AgentServiceError::Validation(
+            "delegation".to_owned(),
+            format!(
+                "delegation chain would be {depth} hops deep; the limit is {MAX_DELEGATION_DEPTH}"
+            ),
+        ));
+    }
+    tasks.set_parent_task(task_id, parent_task_id).await?;
+    Ok(())
+}
@@ -5,7 +5,8 @@
 //! [`ExecutionStrategySelector`] chooses between [`StandardExecutionStrategy`]
 //! (no tools) and [`PlannedAgenticStrategy`] (plan → execute → respond). The
 //! `plan_executor` and `tool_executor` submodules provide the shared
-//! tool-running primitives.
+//! tool-running primitives; `delegation` offers other hosted agents to the
+//! model as tools, and `replay` answers calls a resumed run already made.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -20,7 +21,7 @@ use systemprompt_models::{
 use tokio::sync::mpsc;
 
 use super::message::StreamEvent;
-use crate::models::AgentRuntimeInfo;
+use crate::models::{AgentRuntimeInfo, ToolApprovalRequest};
 use crate::repository::execution::ExecutionStepRepository;
 use crate::services::SkillService;
 
@@ -35,6 +36,7 @@ pub struct ExecutionContext {
     pub tx: mpsc::Sender<StreamEvent>,
     pub request_ctx: RequestContext,
     pub execution_step_repo: Arc<ExecutionStepRepository>,
+    pub tool_replay: ToolReplay,
 }
 
 impl std::fmt::Debug for ExecutionContext {
@@ -49,6 +51,7 @@ impl std::fmt::Debug for ExecutionContext {
             .field("tx", &"<Sender>")
             .field("request_ctx", &self.request_ctx)
             .field("execution_step_repo", &"<Arc<ExecutionStepRepository>>")
+            .field("tool_replay", &self.tool_replay)
             .finish()
     }
 }
@@ -60,6 +63,9 @@ pub struct ExecutionResult {
     pub tool_results: Vec<CallToolResult>,
     pub tools: Vec<McpTool>,
     pub iterations: usize,
+    /// Set when the plan called a tool that needs a human decision; the task
+    /// is suspended in `input-required` instead of completing.
+    pub pending_approval: Option<ToolApprovalRequest>,
 }
 
 impl Default for ExecutionResult {
@@ -70,6 +76,7 @@ impl Default for ExecutionResult {
             tool_results: Vec::new(),
             tools: Vec::new(),
             iterations: 1,
+            pending_approval: None,
         }
     }
 }
@@ -88,8 +95,10 @@ pub trait ExecutionStrategy: Send + Sync {
     fn name(&self) -> &'static str;
 }
 
+pub mod delegation;
 pub mod plan_executor;
 pub mod planned;
+pub mod replay;
 pub mod selector;
 pub mod standard;
 pub mod tool_executor;
@@ -99,6 +108,7 @@ pub use plan_executor::{
     execute_tools_sequentially, execute_tools_with_templates, format_results_for_response,
 };
 pub use planned::PlannedAgenticStrategy;
+pub use replay::ToolReplay;
 pub use selector::ExecutionStrategySelector;
 pub use standard::StandardExecutionStrategy;
 pub use tool_executor::ContextToolExecutor;
@@ -59,5 +59,6 @@ pub(super) async fn handle_direct_response(
         tool_results: vec![],
         tools: vec![],
         iterations: 1,
+        pending_approval: None,
     })
 }
@@ -3,7 +3,14 @@
 //! [`PlannedAgenticStrategy`] runs a plan → execute → respond flow: it asks the
 //! AI to produce a [`PlanningResult`],
 //! then either returns a direct response or executes the planned tool calls and
-//! synthesizes a final answer, tracking each step as an execution step.
+//! synthesizes a final answer, tracking each step as an execution step. An
+//! agent whose `execution.mode` is `iterative` instead loops, planning again
+//! after each batch of tool results until the model answers or a limit in its
+//! `AgentExecutionConfig` stops the loop.
+//!
+//! A plan that calls a tool marked `requires_approval` suspends the task
+//! instead of running it; [`PlannedAgenticStrategy::resume`] continues that
+//! plan once the decision arrives.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -20,7 +27,8 @@ use systemprompt_identifiers::TaskId;
 use systemprompt_models::AiMessage;
 use systemprompt_models::ai::PlanningResult;
 
-use super::{ExecutionContext, ExecutionResult, ExecutionStrategy};
+use super::{ExecutionContext, ExecutionResult, ExecutionStrategy, delegation};
+use crate::models::{LoopProgress, PendingToolApproval, ToolApprovalDecision};
 use crate::services::ExecutionTrackingService;
 use crate::services::a2a_server::processing::message::StreamEvent;
 use helpers::build_ai_request;
@@ -34,6 +42,85 @@ impl PlannedAgenticStrategy {
     }
 }
 
+impl PlannedAgenticStrategy {
+    /// Continues a plan suspended for approval. The decision is recorded
+    /// first, so a decision that arrives twice is refused. Approved calls run
+    /// as planned; rejected ones are never run and the model explains that to
+    /// the user.
+    pub async fn resume(
+        &self,
+        context: ExecutionContext,
+        pending: PendingToolApproval,
+        decision: &ToolApprovalDecision,
+    ) -> Result<ExecutionResult> {
+        if !context
+            .execution_step_repo
+            .resolve_tool_approval(decision)
+            .await?
+        {
+            return Err(AgentServiceError::Conflict(format!(
+                "Tool approval {} was already decided",
+                decision.approval_id
+            )));
+        }
+        let tracking = ExecutionTrackingService::new(Arc::clone(&context.execution_step_repo));
+        let task_id = TaskId::new(context.task_id.as_str());
+        tracing::info!(
+            approval_id = %decision.approval_id,
+            approved = decision.approved,
+            "Resuming planned execution after tool approval"
+        );
+
+        if !decision.approved {
+            let summary = tool_execution::declined_summary(&pending, decision.reason.as_deref());
+            let response =
+                tool_execution::synthesize_response(&context, pending.messages, &summary, None)
+                    .await?;
+            if let Ok(step) = tracking.track_completion(task_id).await
+                && context
+                    .tx
+                    .try_send(StreamEvent::ExecutionStepUpdate { step })
+                    .is_err()
+            {
+                tracing::debug!("Stream receiver dropped");
+            }
+            if context
+                .tx
+                .try_send(StreamEvent::Text(response.clone()))
+                .is_err()
+            {
+                tracing::debug!("Stream receiver dropped");
+            }
+            return Ok(ExecutionResult {
+                accumulated_text: response,
+                ..ExecutionResult::default()
+            });
+        }
+
+        let tools = delegation::available_tools(&context).await?;
+        let planning_tracked = tracking
+            .track_planning_async(task_id.clone(), None, None)
+            .await;
+        let params = tool_execution::HandleToolCallsParams {
+            reasoning: pending.reasoning,
+            calls: pending.calls,
+            context: &context,
+            tracking: &tracking,
+            planning_tracked,
+            task_id,
+            messages: pending.messages,
+            tools,
+            pre_approved: true,
+            progress: pending.progress,
+        };
+        if context.agent_runtime.execution.is_iterative() {
+            tool_execution::handle_tool_calls_iteratively(params).await
+        } else {
+            tool_execution::handle_tool_calls(params).await
+        }
+    }
+}
+
 impl Default for PlannedAgenticStrategy {
     fn default() -> Self {
         Self::new()
@@ -50,7 +137,10 @@ impl ExecutionStrategy for PlannedAgenticStrategy {
         let tracking = ExecutionTrackingService::new(Arc::clone(&context.execution_step_repo));
         let task_id = TaskId::new(context.task_id.as_str());
 
-        tracing::info!("Starting PLAN → EXECUTE → RESPOND flow");
+        tracing::info!(
+            iterative = context.agent_runtime.execution.is_iterative(),
+            "Starting PLAN → EXECUTE → RESPOND flow"
+        );
 
         if let Ok(step) = tracking.track_understanding(task_id.clone()).await
             && context
@@ -61,10 +151,7 @@ impl ExecutionStrategy for PlannedAgenticStrategy {
             tracing::debug!("Stream receiver dropped");
         }
 
-        let tools = context
-            .ai_service
-            .list_available_tools_for_agent(&context.agent_name, &context.request_ctx)
-            .await?;
+        let tools = delegation::available_tools(&context).await?;
 
         tracing::info!(tool_count = tools.len(), "Available tools");
 
@@ -110,7 +197,7 @@ impl ExecutionStrategy for PlannedAgenticStrategy {
             },
 
             PlanningResult::ToolCalls { reasoning, calls } => {
-                tool_execution::handle_tool_calls(tool_execution::HandleToolCallsParams {
+                let params = tool_execution::HandleToolCallsParams {
                     reasoning,
                     calls,
                     context: &context,
@@ -119,8 +206,14 @@ impl ExecutionStrategy for PlannedAgenticStrategy {
                     task_id,
                     messages,
                     tools,
-                })
-                .await
+                    pre_approved: false,
+                    progress: LoopProgress::default(),
+                };
+                if context.agent_runtime.execution.is_iterative() {
+                    tool_execution::handle_tool_calls_iteratively(params).await
+                } else {
+                    tool_execution::handle_tool_calls(params).await
+                }
             },
         }
     }
@@ -0,0 +1,89 @@
+//! Approval gate of the planned strategy.
+//!
+//! A batch that calls any tool whose MCP metadata sets `requires_approval` is
+//! not run: the plan and the conversation it was made from are stored as a
+//! [`PendingToolApproval`] and the strategy returns with
+//! [`ExecutionResult::pending_approval`] set, which suspends the task in
+//! `input-required`. The batch that answers an approved decision is run with
+//! `pre_approved` set and passes the gate; an iterative run also gets back the
+//! [`LoopProgress`] it suspended with.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use systemprompt_identifiers::TaskId;
+use systemprompt_models::ai::PlannedToolCall;
+use systemprompt_models::{AiMessage, McpTool};
+
+use super::super::super::{ExecutionContext, ExecutionResult};
+use crate::models::{LoopProgress, PendingToolApproval};
+use crate::services::shared::Result;
+
+pub(super) fn requires_approval(calls: &[PlannedToolCall], tools: &[McpTool]) -> bool {
+    calls.iter().any(|call| {
+        tools
+            .iter()
+            .any(|tool| tool.name == call.tool_name && tool.requires_approval)
+    })
+}
+
+pub(super) struct SuspendParams<'a> {
+    pub context: &'a ExecutionContext,
+    pub task_id: TaskId,
+    pub reasoning: String,
+    pub calls: Vec<PlannedToolCall>,
+    pub messages: Vec<AiMessage>,
+    pub tools: Vec<McpTool>,
+    pub progress: LoopProgress,
+}
+
+pub(super) async fn suspend_for_approval(params: SuspendParams<'_>) -> Result<ExecutionResult> {
+    let SuspendParams {
+        context,
+        task_id,
+        reasoning,
+        calls,
+        messages,
+        tools,
+        progress,
+    } = params;
+    let pending = PendingToolApproval {
+        approval_id: uuid::Uuid::new_v4().to_string(),
+        task_id,
+        reasoning,
+        calls,
+        messages,
+        progress,
+    };
+    context
+        .execution_step_repo
+        .create_tool_approval(&pending)
+        .await?;
+    tracing::info!(
+        approval_id = %pending.approval_id,
+        tool_count = pending.calls.len(),
+        "Tool calls require approval; suspending task"
+    );
+
+    Ok(ExecutionResult {
+        tools,
+        pending_approval: Some(pending.request()),
+        ..ExecutionResult::default()
+    })
+}
+
+pub(in super::super) fn declined_summary(
+    pending: &PendingToolApproval,
+    reason: Option<&str>,
+) -> String {
+    let tools = pending
+        .calls
+        .iter()
+        .map(|c| c.tool_name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let reason = reason.map_or_else(String::new, |r| format!(" Reason given: {r}"));
+    format!(
+        "The user declined to run the planned tool calls ({tools}), so none of them ran.{reason}"
+    )
+}
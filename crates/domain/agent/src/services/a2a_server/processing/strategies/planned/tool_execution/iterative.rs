@@ -0,0 +1,397 @@
+//! Iterative (`ReAct`) mode of the planned strategy.
+//!
+//! With `execution.mode: iterative`, the tool calls of each plan are run and
+//! their results are fed back to the model as an observation; the model then
+//! plans again, either calling further tools or answering. Every round records
+//! a planning step and a tool-execution step, so the `ExecutionStepUpdate`
+//! stream shows the loop as it runs. A plan that fails template validation is
+//! fed back the same way rather than ending the task.
+//!
+//! The loop ends when the model answers directly. It stops early after
+//! `max_iterations` rounds, once the task's AI usage recorded in `ai_requests`
+//! reaches `max_tokens` or `max_cost_microdollars`, or when a tool is planned
+//! with identical arguments more than `max_repeated_calls` times; an early
+//! stop synthesizes the response from every round's results. A round that
+//! calls a tool needing approval suspends the task with the transcript so far
+//! and the loop's iteration and call counts, and an approved decision resumes
+//! the loop from that round with its limits still counting.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::HashMap;
+
+use systemprompt_identifiers::{AiToolCallId, TaskId};
+use systemprompt_models::ai::{
+    PlanValidationError, PlannedToolCall, PlanningResult, TemplateValidator,
+};
+use systemprompt_models::services::AgentExecutionConfig;
+use systemprompt_models::{AiMessage, CallToolResult, McpTool, ToolCall};
+
+use super::super::super::plan_executor::{
+    convert_to_call_tool_results, convert_to_tool_calls, execute_tools_with_templates,
+    format_results_for_response,
+};
+use super::super::super::tool_executor::ContextToolExecutor;
+use super::super::super::{ExecutionContext, ExecutionResult};
+use super::super::helpers::build_ai_request;
+use super::approval::{SuspendParams, requires_approval, suspend_for_approval};
+use super::recording::record_execution_status;
+use super::{
+    HandleToolCallsParams, PlanningTracked, emit, emit_planning_complete, join_failure_errors,
+    start_tool_execution, synthesize_response,
+};
+use crate::models::LoopProgress;
+use crate::services::ExecutionTrackingService;
+use crate::services::a2a_server::processing::message::StreamEvent;
+use crate::services::shared::{AgentServiceError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoopStop {
+    MaxIterations,
+    Budget,
+    RepeatedCall,
+}
+
+impl LoopStop {
+    fn describe(self, iteration: u32) -> String {
+        let reason = match self {
+            Self::MaxIterations => "the iteration limit was reached",
+            Self::Budget => "the task's token or cost budget was used up",
+            Self::RepeatedCall => "the plan repeated a tool call it had already made",
+        };
+        format!("Stopped after {iteration} iteration(s) because {reason}.")
+    }
+}
+
+struct Round {
+    reasoning: String,
+    calls: Vec<PlannedToolCall>,
+    planning_tracked: PlanningTracked,
+}
+
+enum NextStep {
+    Answer(String),
+    Tools(Box<Round>),
+}
+
+struct ReactLoop<'a> {
+    context: &'a ExecutionContext,
+    tracking: &'a ExecutionTrackingService,
+    task_id: TaskId,
+    tools: Vec<McpTool>,
+    config: AgentExecutionConfig,
+    request: Vec<AiMessage>,
+    transcript: Vec<AiMessage>,
+    iteration: u32,
+    call_counts: HashMap<String, u32>,
+    summaries: Vec<String>,
+    failures: Vec<String>,
+    tool_calls: Vec<ToolCall>,
+    tool_results: Vec<CallToolResult>,
+}
+
+pub(in super::super) async fn handle_tool_calls_iteratively(
+    params: HandleToolCallsParams<'_>,
+) -> Result<ExecutionResult> {
+    let HandleToolCallsParams {
+        reasoning,
+        calls,
+        context,
+        tracking,
+        planning_tracked,
+        task_id,
+        messages,
+        tools,
+        mut pre_approved,
+        progress,
+    } = params;
+    let mut round = Round {
+        reasoning,
+        calls,
+        planning_tracked,
+    };
+    let mut react = ReactLoop {
+        context,
+        tracking,
+        task_id,
+        tools,
+        config: context.agent_runtime.execution,
+        transcript: messages.clone(),
+        request: messages,
+        iteration: progress.iteration,
+        call_counts: progress.call_counts,
+        summaries: Vec::new(),
+        failures: Vec::new(),
+        tool_calls: Vec::new(),
+        tool_results: Vec::new(),
+    };
+
+    let stop = loop {
+        let Round {
+            reasoning,
+            calls,
+            planning_tracked,
+        } = round;
+        tracing::info!(
+            iteration = react.iteration,
+            tool_count = calls.len(),
+            reasoning = %reasoning,
+            "Tool calls planned"
+        );
+        let label = format!("Iteration {}: {reasoning}", react.iteration);
+        emit_planning_complete(tracking, planning_tracked, label, &calls, context).await;
+
+        if react.repeats_a_call(&calls) {
+            break LoopStop::RepeatedCall;
+        }
+        if !pre_approved && requires_approval(&calls, &react.tools) {
+            return react.suspend(reasoning, calls).await;
+        }
+        pre_approved = false;
+        react.count_calls(&calls);
+        react.run_round(&reasoning, &calls).await?;
+        if let Some(stop) = react.limit_reached().await {
+            break stop;
+        }
+        match react.replan().await? {
+            NextStep::Answer(content) => return Ok(react.finish(content).await),
+            NextStep::Tools(next) => round = *next,
+        }
+    };
+
+    react.finish_stopped(stop).await
+}
+
+impl ReactLoop<'_> {
+    // Why: the check leaves `call_counts` alone so a round suspended for
+    // approval is stored uncounted; it is counted once, when it runs.
+    fn repeats_a_call(&self, calls: &[PlannedToolCall]) -> bool {
+        let mut batch: HashMap<String, u32> = HashMap::new();
+        calls.iter().any(|call| {
+            let key = call_key(call);
+            let seen = self.call_counts.get(&key).copied().unwrap_or(0);
+            let in_batch = batch.entry(key).or_default();
+            *in_batch += 1;
+            seen + *in_batch > self.config.max_repeated_calls
+        })
+    }
+
+    fn count_calls(&mut self, calls: &[PlannedToolCall]) {
+        for call in calls {
+            *self.call_counts.entry(call_key(call)).or_default() += 1;
+        }
+    }
+
+    async fn run_round(&mut self, reasoning: &str, calls: &[PlannedToolCall]) -> Result<()> {
+        self.transcript
+            .push(AiMessage::assistant(describe_calls(reasoning, calls)));
+
+        let schemas = TemplateValidator::get_tool_output_schemas(calls, &self.tools);
+        if let Err(errors) = TemplateValidator::validate_plan(calls, &schemas) {
+            self.observe(&validation_observation(&errors));
+            return Ok(());
+        }
+
+        let tracked =
+            start_tool_execution(self.tracking, self.context, &self.task_id, calls).await?;
+
+        let executor = ContextToolExecutor {
+            context: self.context.clone(),
+        };
+        let state =
+            execute_tools_with_templates(calls, &self.tools, &self.context.request_ctx, &executor)
+                .await?;
+        let has_failures = !state.failed_results().is_empty();
+        record_execution_status(self.tracking, &tracked, &state, has_failures).await;
+        if let Some(failure) = join_failure_errors(&state, has_failures) {
+            self.failures.push(failure);
+        }
+
+        let offset = self.tool_calls.len();
+        self.tool_calls
+            .extend(
+                convert_to_tool_calls(calls)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, mut call)| {
+                        call.ai_tool_call_id =
+                            AiToolCallId::new(format!("plan_call_{}", offset + i));
+                        call
+                    }),
+            );
+        self.tool_results
+            .extend(convert_to_call_tool_results(&state));
+        self.observe(&format_results_for_response(&state));
+        Ok(())
+    }
+
+    async fn suspend(
+        self,
+        reasoning: String,
+        calls: Vec<PlannedToolCall>,
+    ) -> Result<ExecutionResult> {
+        suspend_for_approval(SuspendParams {
+            context: self.context,
+            task_id: self.task_id,
+            reasoning,
+            calls,
+            messages: self.transcript,
+            tools: self.tools,
+            progress: LoopProgress {
+                iteration: self.iteration,
+                call_counts: self.call_counts,
+            },
+        })
+        .await
+    }
+
+    fn observe(&mut self, summary: &str) {
+        self.transcript.push(AiMessage::user(format!(
+            "Tool results from iteration {}:\n\n{summary}",
+            self.iteration
+        )));
+        self.summaries
+            .push(format!("Iteration {}:\n{summary}", self.iteration));
+    }
+
+    async fn limit_reached(&self) -> Option<LoopStop> {
+        if self.iteration >= self.config.max_iterations {
+            return Some(LoopStop::MaxIterations);
+        }
+        if self.config.max_tokens.is_none() && self.config.max_cost_microdollars.is_none() {
+            return None;
+        }
+        match self
+            .context
+            .execution_step_repo
+            .ai_usage(&self.task_id)
+            .await
+        {
+            Ok(usage) => {
+                let over_tokens = self.config.max_tokens.is_some_and(|m| usage.tokens >= m);
+                let over_cost = self
+                    .config
+                    .max_cost_microdollars
+                    .is_some_and(|m| usage.cost_microdollars >= m);
+                (over_tokens || over_cost).then_some(LoopStop::Budget)
+            },
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to read task AI usage; budget not enforced");
+                None
+            },
+        }
+    }
+
+    async fn replan(&mut self) -> Result<NextStep> {
+        self.iteration += 1;
+        let planning_tracked = self
+            .tracking
+            .track_planning_async(self.task_id.clone(), None, None)
+            .await;
+        if let Ok((_, ref step)) = planning_tracked {
+            emit(
+                self.context,
+                StreamEvent::ExecutionStepUpdate { step: step.clone() },
+            );
+        }
+
+        let request = build_ai_request(self.context, self.transcript.clone());
+        match self
+            .context
+            .ai_service
+            .generate_plan(&request, &self.tools)
+            .await
+        {
+            Ok(PlanningResult::ToolCalls { reasoning, calls }) => {
+                Ok(NextStep::Tools(Box::new(Round {
+                    reasoning,
+                    calls,
+                    planning_tracked,
+                })))
+            },
+            Ok(PlanningResult::DirectResponse { content }) => {
+                self.complete_final_planning(planning_tracked).await;
+                Ok(NextStep::Answer(content))
+            },
+            Err(e) => {
+                if let Ok((tracked, _)) = planning_tracked
+                    && let Err(fail_err) = self.tracking.fail(&tracked, e.to_string()).await
+                {
+                    tracing::warn!(error = %fail_err, "Failed to record planning failure");
+                }
+                Err(AgentServiceError::Internal(format!("{e}")))
+            },
+        }
+    }
+
+    async fn complete_final_planning(&self, planning_tracked: PlanningTracked) {
+        let reasoning = format!(
+            "Iteration {}: responding from the tool results",
+            self.iteration
+        );
+        if let Ok((tracked, _)) = planning_tracked
+            && let Ok(step) = self
+                .tracking
+                .complete_planning(tracked, Some(reasoning), None)
+                .await
+        {
+            emit(self.context, StreamEvent::ExecutionStepUpdate { step });
+        }
+    }
+
+    async fn finish(self, response: String) -> ExecutionResult {
+        tracing::info!(iterations = self.iteration, "Iterative execution complete");
+        if let Ok(step) = self.tracking.track_completion(self.task_id.clone()).await {
+            emit(self.context, StreamEvent::ExecutionStepUpdate { step });
+        }
+        emit(self.context, StreamEvent::Text(response.clone()));
+        ExecutionResult {
+            accumulated_text: response,
+            tool_calls: self.tool_calls,
+            tool_results: self.tool_results,
+            tools: self.tools,
+            iterations: self.iteration as usize,
+            pending_approval: None,
+        }
+    }
+
+    async fn finish_stopped(self, stop: LoopStop) -> Result<ExecutionResult> {
+        tracing::warn!(
+            iterations = self.iteration,
+            stop = ?stop,
+            "Iterative execution stopped before the model answered"
+        );
+        let summary = format!(
+            "{}\n\n{}",
+            self.summaries.join("\n\n"),
+            stop.describe(self.iteration)
+        );
+        let failures = (!self.failures.is_empty()).then(|| self.failures.join("; "));
+        let response =
+            synthesize_response(self.context, self.request.clone(), &summary, failures).await?;
+        Ok(self.finish(response).await)
+    }
+}
+
+fn call_key(call: &PlannedToolCall) -> String {
+    format!("{}:{}", call.tool_name, call.arguments)
+}
+
+fn describe_calls(reasoning: &str, calls: &[PlannedToolCall]) -> String {
+    let calls = calls
+        .iter()
+        .map(|c| format!("- {}({})", c.tool_name, c.arguments))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("{reasoning}\n\nCalling tools:\n{calls}")
+}
+
+fn validation_observation(errors: &[PlanValidationError]) -> String {
+    let errors = errors
+        .iter()
+        .map(|e| format!("- {e}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("The planned calls were not run because the plan is invalid:\n{errors}")
+}
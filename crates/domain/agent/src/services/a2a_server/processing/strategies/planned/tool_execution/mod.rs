@@ -3,13 +3,19 @@
 //! [`handle_tool_calls`] validates the plan's argument templates, executes the
 //! tools, records execution status (see [`recording`]), and synthesizes the
 //! final response; validation failures are funneled back through the model for
-//! a user-facing explanation.
+//! a user-facing explanation. [`handle_tool_calls_iteratively`] is the
+//! iterative counterpart, which feeds results back to the model and plans
+//! again (see [`iterative`]). Both stop before running a batch that needs a
+//! human decision (see [`approval`]).
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
+mod approval;
+mod iterative;
 mod recording;
 
+use crate::models::LoopProgress;
 use crate::services::shared::{AgentServiceError, Result};
 use systemprompt_identifiers::TaskId;
 use systemprompt_models::ai::{
@@ -25,17 +31,28 @@ use super::super::tool_executor::ContextToolExecutor;
 use super::super::{ExecutionContext, ExecutionResult};
 use crate::services::ExecutionTrackingService;
 use crate::services::a2a_server::processing::message::StreamEvent;
+use approval::{SuspendParams, requires_approval, suspend_for_approval};
 use recording::{build_tool_summary, record_execution_status};
 
+pub(super) use approval::declined_summary;
+pub(super) use iterative::handle_tool_calls_iteratively;
+
+type PlanningTracked = std::result::Result<(TrackedStep, ExecutionStep), AgentServiceError>;
+
 pub(super) struct HandleToolCallsParams<'a> {
     pub reasoning: String,
     pub calls: Vec<PlannedToolCall>,
     pub context: &'a ExecutionContext,
     pub tracking: &'a ExecutionTrackingService,
-    pub planning_tracked: std::result::Result<(TrackedStep, ExecutionStep), AgentServiceError>,
+    pub planning_tracked: PlanningTracked,
     pub task_id: TaskId,
     pub messages: Vec<AiMessage>,
     pub tools: Vec<McpTool>,
+    /// The batch answers an approved decision and skips the approval gate.
+    pub pre_approved: bool,
+    /// Where an iterative run resumes counting from; the default for a fresh
+    /// plan.
+    pub progress: LoopProgress,
 }
 
 pub(super) async fn handle_tool_calls(
@@ -50,6 +67,8 @@ pub(super) async fn handle_tool_calls(
         task_id,
         messages,
         tools,
+        pre_approved,
+        ..
     } = params;
     tracing::info!(
         tool_count = calls.len(),
@@ -57,7 +76,14 @@ pub(super) async fn handle_tool_calls(
         "Tool calls planned"
     );
 
-    emit_planning_complete(tracking, planning_tracked, reasoning, &calls, context).await;
+    emit_planning_complete(
+        tracking,
+        planning_tracked,
+        reasoning.clone(),
+        &calls,
+        context,
+    )
+    .await;
 
     let tool_output_schemas = TemplateValidator::get_tool_output_schemas(&calls, &tools);
     if let Err(validation_errors) = TemplateValidator::validate_plan(&calls, &tool_output_schemas) {
@@ -65,11 +91,20 @@ pub(super) async fn handle_tool_calls(
     }
     tracing::info!("Template validation passed");
 
-    let (tool_name, tool_arguments) = build_tool_summary(&calls);
-    let (tracked, step) = tracking
-        .track_tool_execution(task_id.clone(), tool_name, tool_arguments)
-        .await?;
-    emit(context, StreamEvent::ExecutionStepUpdate { step });
+    if !pre_approved && requires_approval(&calls, &tools) {
+        return suspend_for_approval(SuspendParams {
+            context,
+            task_id,
+            reasoning,
+            calls,
+            messages,
+            tools,
+            progress: LoopProgress::default(),
+        })
+        .await;
+    }
+
+    let tracked = start_tool_execution(tracking, context, &task_id, &calls).await?;
 
     let tool_executor = ContextToolExecutor {
         context: context.clone(),
@@ -96,18 +131,29 @@ pub(super) async fn handle_tool_calls(
         synthesize_response(context, messages, &execution_summary, tool_error_message).await?;
     emit(context, StreamEvent::Text(response.clone()));
 
-    let tool_calls = convert_to_tool_calls(&calls);
-    let tool_results = convert_to_call_tool_results(&state);
-
     Ok(ExecutionResult {
         accumulated_text: response,
-        tool_calls,
-        tool_results,
+        tool_calls: convert_to_tool_calls(&calls),
+        tool_results: convert_to_call_tool_results(&state),
         tools,
-        iterations: 1,
+        ..ExecutionResult::default()
     })
 }
 
+async fn start_tool_execution(
+    tracking: &ExecutionTrackingService,
+    context: &ExecutionContext,
+    task_id: &TaskId,
+    calls: &[PlannedToolCall],
+) -> Result<TrackedStep> {
+    let (tool_name, tool_arguments) = build_tool_summary(calls);
+    let (tracked, step) = tracking
+        .track_tool_execution(task_id.clone(), tool_name, tool_arguments)
+        .await?;
+    emit(context, StreamEvent::ExecutionStepUpdate { step });
+    Ok(tracked)
+}
+
 fn emit(context: &ExecutionContext, event: StreamEvent) {
     if context.tx.try_send(event).is_err() {
         tracing::debug!("Stream receiver dropped");
@@ -128,7 +174,7 @@ fn join_failure_errors(state: &ExecutionState, has_failures: bool) -> Option<Str
 
 async fn emit_planning_complete(
     tracking: &ExecutionTrackingService,
-    planning_tracked: std::result::Result<(TrackedStep, ExecutionStep), AgentServiceError>,
+    planning_tracked: PlanningTracked,
     reasoning: String,
     calls: &[PlannedToolCall],
     context: &ExecutionContext,
@@ -150,7 +196,7 @@ async fn emit_planning_complete(
     }
 }
 
-async fn synthesize_response(
+pub(super) async fn synthesize_response(
     context: &ExecutionContext,
     messages: Vec<AiMessage>,
     execution_summary: &str,
@@ -226,5 +272,6 @@ async fn handle_validation_failure(
         tool_results: vec![],
         tools: vec![],
         iterations: 1,
+        pending_approval: None,
     })
 }
@@ -52,6 +52,7 @@ pub(super) async fn record_execution_status(
         let tool_result = if state.results.len() == 1 {
             serde_json::json!({
                 "tool": state.results[0].tool_name,
+                "arguments": state.results[0].arguments,
                 "output": state.results[0].output,
                 "duration_ms": state.results[0].duration_ms
             })
@@ -60,6 +61,7 @@ pub(super) async fn record_execution_status(
                 "results": state.results.iter().map(|r| {
                     serde_json::json!({
                         "tool": r.tool_name,
+                        "arguments": r.arguments,
                         "output": r.output,
                         "duration_ms": r.duration_ms
                     })
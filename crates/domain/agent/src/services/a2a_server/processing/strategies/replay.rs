@@ -0,0 +1,93 @@
+//! Recorded tool results a resumed run answers from instead of calling the
+//! tool again.
+//!
+//! A task resumed after an agent restart replans from its original message,
+//! so the model usually asks for the tools it had already run. [`ToolReplay`]
+//! is built from the task's completed tool-execution steps; when a call
+//! matches a recorded one by tool name and arguments, the recorded output is
+//! returned once. Calls that were never recorded, or whose step was cut off
+//! mid-flight, run normally.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+use systemprompt_models::{ExecutionStep, StepContent, StepStatus};
+
+#[derive(Debug, Clone)]
+struct RecordedCall {
+    tool_name: String,
+    arguments: Value,
+    output: Value,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ToolReplay {
+    recorded: Arc<Mutex<Vec<RecordedCall>>>,
+}
+
+impl ToolReplay {
+    /// Collects the results of every completed tool-execution step in
+    /// `steps`, oldest first.
+    #[must_use]
+    pub fn from_steps(steps: &[ExecutionStep]) -> Self {
+        let recorded = steps
+            .iter()
+            .filter(|step| step.status == StepStatus::Completed)
+            .filter_map(|step| match &step.content {
+                StepContent::ToolExecution {
+                    tool_result: Some(result),
+                    ..
+                } => Some(result),
+                _ => None,
+            })
+            .flat_map(recorded_calls)
+            .collect();
+        Self {
+            recorded: Arc::new(Mutex::new(recorded)),
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.recorded.lock().map_or(0, |recorded| recorded.len())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The recorded output of `tool_name` called with `arguments`, consumed
+    /// so a second identical call runs the tool.
+    #[must_use]
+    pub fn take(&self, tool_name: &str, arguments: &Value) -> Option<Value> {
+        let mut recorded = self.recorded.lock().ok()?;
+        let index = recorded
+            .iter()
+            .position(|call| call.tool_name == tool_name && call.arguments == *arguments)?;
+        Some(recorded.remove(index).output)
+    }
+}
+
+// JSON: a single call is `{"tool", "arguments", "output", ...}`; a batch is
+// `{"results": [...]}` of the same entries.
+fn recorded_calls(result: &Value) -> Vec<RecordedCall> {
+    let entries = result
+        .get("results")
+        .and_then(Value::as_array)
+        .map_or_else(|| vec![result], |results| results.iter().collect());
+
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            Some(RecordedCall {
+                tool_name: entry.get("tool")?.as_str()?.to_owned(),
+                arguments: entry.get("arguments")?.clone(),
+                output: entry.get("output")?.clone(),
+            })
+        })
+        .collect()
+}
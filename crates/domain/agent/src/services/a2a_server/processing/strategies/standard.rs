@@ -80,6 +80,7 @@ impl ExecutionStrategy for StandardExecutionStrategy {
             tool_results,
             tools: vec![],
             iterations: 1,
+            pending_approval: None,
         })
     }
 
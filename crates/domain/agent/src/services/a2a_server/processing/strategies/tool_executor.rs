@@ -1,5 +1,9 @@
 //! `ToolExecutorTrait` implementation executing tools in context.
 //!
+//! A call a resumed run already made is answered from its recorded result;
+//! delegation tools are sent to their peer agent; every other tool runs through
+//! the AI service's MCP client.
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
@@ -9,8 +13,8 @@ use serde_json::Value;
 use systemprompt_identifiers::AiToolCallId;
 use systemprompt_models::{McpTool, RequestContext, ToolCall};
 
-use super::ExecutionContext;
 use super::plan_executor::{ToolExecutorTrait, ToolOutcome};
+use super::{ExecutionContext, delegation};
 
 #[derive(Debug)]
 pub struct ContextToolExecutor {
@@ -26,6 +30,19 @@ impl ToolExecutorTrait for ContextToolExecutor {
         tools: &[McpTool],
         ctx: &RequestContext,
     ) -> Result<ToolOutcome> {
+        if let Some(output) = self.context.tool_replay.take(tool_name, &arguments) {
+            tracing::info!(tool = %tool_name, "Replaying recorded tool result");
+            return Ok(ToolOutcome { output, meta: None });
+        }
+
+        if let Some(peer) = tools
+            .iter()
+            .find(|tool| tool.name == tool_name)
+            .and_then(delegation::delegate_of)
+        {
+            return delegation::delegate(&self.context, peer, &arguments).await;
+        }
+
         let tool_call = ToolCall {
             ai_tool_call_id: AiToolCallId::new(format!("call_{}", tool_name)),
             name: tool_name.to_owned(),
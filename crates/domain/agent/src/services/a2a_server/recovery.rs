@@ -0,0 +1,382 @@
+//! Startup recovery of tasks a previous agent process left working.
+//!
+//! Before the server accepts traffic, [`TaskRecoveryService::recover`] lists
+//! the agent's tasks still submitted or working from before the process
+//! started whose checkpoint lease has run out — a live replica renews the
+//! lease on every run it is still executing — and claims each one's lease,
+//! leaving tasks another replica claims first to it. Under the agent's
+//! `recovery` config each claimed task is either resumed — its checkpointed
+//! message is run again in the background on a freshly issued token carrying
+//! the roles the user directory grants the task's owner now, with completed
+//! tool calls replayed from their recorded results — or failed with a reason. A
+//! task whose owner is gone or inactive is always failed. A failed task has its
+//! open execution steps closed, its push-notification endpoints called, and the
+//! failure broadcast to the user's A2A and AG-UI subscribers.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use systemprompt_identifiers::{Actor, AgentName, SessionId, TaskId, TraceId, UserId};
+use systemprompt_models::auth::{AuthenticatedUser, Permission, UserType};
+use systemprompt_models::{A2AEventBuilder, AgUiEventBuilder, AiProvider, RequestContext};
+use systemprompt_traits::{GenerateTokenParams, RepositoryError};
+
+use super::processing::message::{MessageProcessor, RUN_LEASE, hold_run_lease, run_claimant};
+use super::streaming::deliver_push_notifications;
+use super::streaming::webhook_client::WebhookContext;
+use crate::models::a2a::{Message, TaskState};
+use crate::models::{AgentRuntimeInfo, OrphanedTask, ToolApprovalDecision};
+use crate::services::ExecutionTrackingService;
+use crate::state::AgentState;
+
+/// Lifetime of the token a resumed run acts on.
+const RESUME_TOKEN_HOURS: u32 = 1;
+
+/// How long a task with no checkpoint must go untouched to count as
+/// orphaned: it has no lease to show a live process is running it, so only
+/// its age can.
+const UNLEASED_ORPHAN_HOURS: i64 = 1;
+
+const INTERRUPTED: &str = "Interrupted by an agent restart";
+
+/// Which path each orphaned task took.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecoveryReport {
+    pub resumed: Vec<TaskId>,
+    pub failed: Vec<TaskId>,
+    /// Left alone: another replica holds the checkpoint lease, or the lease
+    /// could not be taken.
+    pub skipped: Vec<TaskId>,
+}
+
+#[derive(Clone)]
+pub struct TaskRecoveryService {
+    agent_state: Arc<AgentState>,
+    ai_service: Arc<dyn AiProvider>,
+}
+
+impl std::fmt::Debug for TaskRecoveryService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskRecoveryService")
+            .field("agent_state", &self.agent_state)
+            .field("ai_service", &"<Arc<dyn AiProvider>>")
+            .finish()
+    }
+}
+
+struct ResumeRun {
+    message: Message,
+    context: RequestContext,
+}
+
+impl TaskRecoveryService {
+    #[must_use]
+    pub fn new(agent_state: Arc<AgentState>, ai_service: Arc<dyn AiProvider>) -> Self {
+        Self {
+            agent_state,
+            ai_service,
+        }
+    }
+
+    /// Resumes or fails every task of `agent_runtime` left submitted or
+    /// working before `started_at` that no live process holds. Resumed runs
+    /// continue in the background after this returns.
+    pub async fn recover(
+        &self,
+        agent_runtime: &AgentRuntimeInfo,
+        started_at: DateTime<Utc>,
+    ) -> Result<RecoveryReport, RepositoryError> {
+        let orphans = self
+            .agent_state
+            .repositories()
+            .tasks
+            .list_orphaned_tasks(
+                &agent_runtime.name,
+                started_at,
+                started_at - chrono::Duration::hours(UNLEASED_ORPHAN_HOURS),
+            )
+            .await?;
+
+        let mut report = RecoveryReport::default();
+        for orphan in orphans {
+            if !self.claim(&orphan.task_id).await {
+                report.skipped.push(orphan.task_id);
+                continue;
+            }
+            match self.prepare_resume(&orphan, agent_runtime).await {
+                Ok(run) => {
+                    self.spawn_resume(orphan.clone(), run, agent_runtime.clone());
+                    report.resumed.push(orphan.task_id);
+                },
+                Err(reason) => {
+                    self.fail(&orphan, &reason).await;
+                    report.failed.push(orphan.task_id);
+                },
+            }
+        }
+
+        if !report.resumed.is_empty() || !report.failed.is_empty() {
+            tracing::info!(
+                agent = %agent_runtime.name,
+                resumed = report.resumed.len(),
+                failed = report.failed.len(),
+                skipped = report.skipped.len(),
+                "Recovered tasks interrupted by the last agent restart"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Whether this process may act on `task_id`: it now holds the task's
+    /// checkpoint lease, or the task has no checkpoint to lease — listed only
+    /// once stale — and can only be failed.
+    async fn claim(&self, task_id: &TaskId) -> bool {
+        let tasks = &self.agent_state.repositories().tasks;
+        match tasks
+            .claim_checkpoint(task_id, run_claimant(), RUN_LEASE)
+            .await
+        {
+            Ok(true) => true,
+            Ok(false) => matches!(tasks.get_checkpoint(task_id).await, Ok(None)),
+            Err(e) => {
+                tracing::warn!(task_id = %task_id, error = %e, "Failed to claim interrupted task");
+                false
+            },
+        }
+    }
+
+    /// The run to resume `orphan` with, or the reason it must be failed.
+    async fn prepare_resume(
+        &self,
+        orphan: &OrphanedTask,
+        agent_runtime: &AgentRuntimeInfo,
+    ) -> Result<ResumeRun, String> {
+        let config = agent_runtime.recovery;
+        if !config.resumes() {
+            return Err(INTERRUPTED.to_owned());
+        }
+
+        let tasks = &self.agent_state.repositories().tasks;
+        let checkpoint = match tasks.get_checkpoint(&orphan.task_id).await {
+            Ok(Some(checkpoint)) => checkpoint,
+            Ok(None) => return Err(format!("{INTERRUPTED}; no checkpoint to resume from")),
+            Err(e) => return Err(format!("{INTERRUPTED}; checkpoint unreadable: {e}")),
+        };
+        if checkpoint.resume_attempts >= config.max_attempts {
+            return Err(format!(
+                "{INTERRUPTED}; gave up after {} resume attempts",
+                checkpoint.resume_attempts
+            ));
+        }
+        // Why: the approval was resolved before the crash, so the suspended
+        // plan a decision resumes from no longer exists.
+        if ToolApprovalDecision::from_message(&checkpoint.message).is_some() {
+            return Err(format!(
+                "{INTERRUPTED} while applying a tool approval decision"
+            ));
+        }
+
+        let (Some(user_id), Some(session_id)) = (&orphan.user_id, &orphan.session_id) else {
+            return Err(format!("{INTERRUPTED}; the task has no owning session"));
+        };
+        let (user, token) = self
+            .authorize(user_id, session_id)
+            .await
+            .map_err(|e| format!("{INTERRUPTED}; {e}"))?;
+        let user_type = user.user_type();
+
+        let attempt = tasks
+            .record_resume_attempt(&orphan.task_id, run_claimant())
+            .await
+            .map_err(|e| format!("{INTERRUPTED}; could not record the resume: {e}"))?;
+        self.close_open_steps(&orphan.task_id, INTERRUPTED).await;
+        tracing::info!(task_id = %orphan.task_id, attempt, "Resuming interrupted task");
+
+        let context = RequestContext::new(
+            session_id.clone(),
+            orphan.trace_id.clone().unwrap_or_else(TraceId::generate),
+            orphan.context_id.clone(),
+            AgentName::new(agent_runtime.name.as_str()),
+        )
+        .with_user(user)
+        .with_actor(Actor::user(user_id.clone()))
+        .with_user_type(user_type)
+        .with_auth_token(token)
+        .with_task_id(orphan.task_id.clone());
+
+        Ok(ResumeRun {
+            message: checkpoint.message,
+            context,
+        })
+    }
+
+    fn spawn_resume(&self, orphan: OrphanedTask, run: ResumeRun, agent_runtime: AgentRuntimeInfo) {
+        hold_run_lease(
+            self.agent_state.repositories().tasks.clone(),
+            orphan.task_id.clone(),
+        );
+        let service = self.clone();
+        tokio::spawn(async move {
+            let ResumeRun { message, context } = run;
+            let result = match MessageProcessor::new(
+                Arc::clone(service.agent_state.repositories()),
+                Arc::clone(&service.ai_service),
+            ) {
+                Ok(processor) => {
+                    processor
+                        .resume_interrupted_task(
+                            &message,
+                            &agent_runtime,
+                            &agent_runtime.name,
+                            &context,
+                        )
+                        .await
+                },
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(task) => tracing::info!(
+                    task_id = %task.id,
+                    state = ?task.status.state,
+                    "Resumed interrupted task"
+                ),
+                Err(e) => {
+                    service
+                        .fail(
+                            &orphan,
+                            &format!("Resumed run after an agent restart failed: {e}"),
+                        )
+                        .await;
+                },
+            }
+        });
+    }
+
+    async fn fail(&self, orphan: &OrphanedTask, reason: &str) {
+        let repositories = self.agent_state.repositories();
+        tracing::warn!(task_id = %orphan.task_id, reason = %reason, "Failing interrupted task");
+
+        self.close_open_steps(&orphan.task_id, reason).await;
+        if let Err(e) = repositories
+            .tasks
+            .update_task_failed_with_error(&orphan.task_id, reason, &Utc::now())
+            .await
+        {
+            tracing::error!(task_id = %orphan.task_id, error = %e, "Failed to mark interrupted task failed");
+            return;
+        }
+
+        match repositories.tasks.get_task(&orphan.task_id).await {
+            Ok(Some(task)) => deliver_push_notifications(repositories, &task).await,
+            Ok(None) => {},
+            Err(e) => {
+                tracing::warn!(task_id = %orphan.task_id, error = %e, "Failed to reload failed task");
+            },
+        }
+
+        self.broadcast_failure(orphan, reason).await;
+
+        if let Err(e) = repositories.tasks.delete_checkpoint(&orphan.task_id).await {
+            tracing::warn!(task_id = %orphan.task_id, error = %e, "Failed to clear run checkpoint");
+        }
+    }
+
+    async fn broadcast_failure(&self, orphan: &OrphanedTask, reason: &str) {
+        let (Some(user_id), Some(session_id)) = (&orphan.user_id, &orphan.session_id) else {
+            return;
+        };
+        let token = match self.authorize(user_id, session_id).await {
+            Ok((_, token)) => token,
+            Err(e) => {
+                tracing::warn!(task_id = %orphan.task_id, error = %e, "Failed to authorize task failure broadcast");
+                return;
+            },
+        };
+
+        let webhook_context = WebhookContext::new(user_id.clone(), token);
+        let a2a_event = A2AEventBuilder::task_status_update(
+            orphan.task_id.clone(),
+            orphan.context_id.clone(),
+            TaskState::Failed,
+            Some(reason.to_owned()),
+        );
+        if let Err(e) = webhook_context.broadcast_a2a(a2a_event).await {
+            tracing::warn!(error = %e, "Failed to broadcast A2A task_status_update");
+        }
+
+        let error_event =
+            AgUiEventBuilder::run_error(reason.to_owned(), Some("AGENT_RESTARTED".to_owned()));
+        if let Err(e) = webhook_context.broadcast_agui(error_event).await {
+            tracing::warn!(error = %e, "Failed to broadcast RUN_ERROR");
+        }
+    }
+
+    async fn close_open_steps(&self, task_id: &TaskId, reason: &str) {
+        let tracking = ExecutionTrackingService::new(Arc::new(
+            self.agent_state.repositories().execution_steps.clone(),
+        ));
+        if let Err(e) = tracking.fail_in_progress_steps(task_id, reason).await {
+            tracing::warn!(task_id = %task_id, error = %e, "Failed to close interrupted steps");
+        }
+    }
+
+    /// An identity and token for acting as `user_id` now, carrying the
+    /// roles the user directory currently grants them. Refused for a user
+    /// who is gone, inactive, or holds no role.
+    async fn authorize(
+        &self,
+        user_id: &UserId,
+        session_id: &SessionId,
+    ) -> Result<(AuthenticatedUser, String), String> {
+        let users = self
+            .agent_state
+            .user_provider()
+            .ok_or_else(|| "no user directory to verify the task owner against".to_owned())?;
+        let owner = users
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| format!("could not load the task owner: {e}"))?
+            .ok_or_else(|| "the task owner no longer exists".to_owned())?;
+        if !owner.is_active {
+            return Err("the task owner is inactive".to_owned());
+        }
+        let permissions: Vec<Permission> =
+            owner.roles.iter().filter_map(|r| r.parse().ok()).collect();
+        if permissions.is_empty() {
+            return Err("the task owner holds no role".to_owned());
+        }
+
+        let token = self
+            .issue_token(user_id, session_id, &permissions)
+            .map_err(|e| format!("could not authorize a resumed run: {e}"))?;
+        let id = user_id
+            .as_str()
+            .parse()
+            .map_err(|e| format!("invalid task owner: {e}"))?;
+        let user = AuthenticatedUser::new_with_roles(
+            id,
+            owner.name,
+            owner.email,
+            permissions,
+            owner.roles,
+        );
+        Ok((user, token))
+    }
+
+    fn issue_token(
+        &self,
+        user_id: &UserId,
+        session_id: &SessionId,
+        permissions: &[Permission],
+    ) -> systemprompt_traits::JwtResult<String> {
+        let params =
+            GenerateTokenParams::new(user_id.clone(), user_id.as_str(), session_id.clone())
+                .with_user_type(UserType::from_permissions(permissions).as_str())
+                .with_permissions(permissions.iter().map(ToString::to_string).collect())
+                .with_expires_in_hours(RESUME_TOKEN_HOURS);
+        self.agent_state.jwt_provider().generate_token(params)
+    }
+}
@@ -2,13 +2,15 @@
 //!
 //! [`Server`] loads an agent's configuration, wires OAuth state and the AI
 //! provider, and builds the axum [`Router`] exposing the agent card and the A2A
-//! request endpoint, then runs the listener.
+//! JSON-RPC and HTTP+JSON bindings, then runs the listener once the tasks the
+//! previous process left working have been recovered.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 use axum::routing::{get, post};
 use axum::{Router, middleware};
+use chrono::Utc;
 use std::pin::Pin;
 use std::sync::Arc;
 use systemprompt_database::DbPool;
@@ -19,7 +21,9 @@ use tower_http::cors::CorsLayer;
 use tower_http::services::ServeDir;
 
 use super::auth::{AgentOAuthConfig, AgentOAuthState, agent_oauth_middleware_wrapper};
-use super::handlers::{AgentHandlerState, handle_agent_card, handle_agent_request};
+use super::handlers::{AgentHandlerState, handle_agent_card, handle_agent_request, rest_routes};
+use super::recovery::TaskRecoveryService;
+use crate::models::AgentRuntimeInfo;
 use crate::state::AgentState;
 
 pub struct Server {
@@ -103,8 +107,9 @@ impl Server {
             stream_semaphore: Arc::clone(&self.stream_semaphore),
         });
 
-        let post_router = Router::new()
+        let a2a_router = Router::new()
             .route("/", post(handle_agent_request))
+            .merge(rest_routes())
             .with_state(Arc::clone(&state))
             .layer(middleware::from_fn_with_state(
                 Arc::clone(&state),
@@ -116,7 +121,7 @@ impl Server {
             .route(ApiPaths::A2A_CARD, get(handle_agent_card))
             .with_state(state);
 
-        let api_router = Router::new().merge(post_router).merge(get_router);
+        let api_router = Router::new().merge(a2a_router).merge(get_router);
 
         let web_dist_path = std::path::Path::new("web/dist");
         let router = if web_dist_path.exists() {
@@ -130,9 +135,19 @@ impl Server {
 
     pub async fn run(self) -> Result<(), crate::error::AgentError> {
         Self::log_server_configuration();
+        self.recover_interrupted_tasks().await;
         self.start_server(None).await
     }
 
+    async fn recover_interrupted_tasks(&self) {
+        let agent_runtime = AgentRuntimeInfo::from(self.config.read().await.clone());
+        let recovery =
+            TaskRecoveryService::new(Arc::clone(&self.agent_state), Arc::clone(&self.ai_service));
+        if let Err(e) = recovery.recover(&agent_runtime, Utc::now()).await {
+            tracing::error!(agent = %agent_runtime.name, error = %e, "Failed to recover interrupted tasks");
+        }
+    }
+
     const fn log_server_configuration() {}
 
     async fn start_server(
@@ -13,9 +13,9 @@ use systemprompt_models::{
 };
 use tokio::sync::mpsc::{Receiver, Sender};
 
-use crate::models::ExecutionStep;
 use crate::models::a2a::jsonrpc::NumberOrString;
 use crate::models::a2a::{Artifact, Message, TaskState};
+use crate::models::{ExecutionStep, ToolApprovalRequest};
 use crate::repository::task::TaskRepository;
 use crate::services::a2a_server::processing::message::{MessageProcessor, StreamEvent};
 
@@ -23,7 +23,8 @@ use super::event_loop_lifecycle::{
     EmitRunStartedParams, SendA2aStatusEventParams, emit_run_started, send_a2a_status_event,
 };
 use super::handlers::{
-    HandleCompleteParams, HandleErrorParams, TextStreamState, handle_complete, handle_error,
+    HandleCompleteParams, HandleErrorParams, HandleInputRequiredParams, TextStreamState,
+    handle_complete, handle_error, handle_input_required,
 };
 use super::webhook_client::WebhookContext;
 
@@ -112,17 +113,9 @@ pub async fn process_events(params: ProcessEventsParams) {
             StreamEvent::ExecutionStepUpdate { step } => {
                 broadcast_execution_step(&webhook_context, step, &context_id).await;
             },
-            StreamEvent::Complete {
-                full_text,
-                artifacts,
-            } => {
+            terminal => {
                 text_state.finalize(&message_id).await;
-                finish_completed(&ctx, full_text, artifacts).await;
-                break;
-            },
-            StreamEvent::Error(error) => {
-                text_state.finalize(&message_id).await;
-                finish_failed(&ctx, error).await;
+                finish(&ctx, terminal).await;
                 break;
             },
         }
@@ -198,6 +191,21 @@ async fn broadcast_execution_step(
     }
 }
 
+async fn finish(ctx: &EventLoopCtx<'_>, event: StreamEvent) {
+    match event {
+        StreamEvent::Complete {
+            full_text,
+            artifacts,
+        } => finish_completed(ctx, full_text, artifacts).await,
+        StreamEvent::InputRequired(request) => finish_input_required(ctx, request).await,
+        StreamEvent::Error(error) => finish_failed(ctx, error).await,
+        StreamEvent::Text(_)
+        | StreamEvent::ToolCallStarted(_)
+        | StreamEvent::ToolResult { .. }
+        | StreamEvent::ExecutionStepUpdate { .. } => {},
+    }
+}
+
 async fn finish_completed(ctx: &EventLoopCtx<'_>, full_text: String, artifacts: Vec<Artifact>) {
     let complete_params = HandleCompleteParams {
         tx: ctx.tx,
@@ -236,6 +244,31 @@ async fn finish_completed(ctx: &EventLoopCtx<'_>, full_text: String, artifacts:
     }
 }
 
+async fn finish_input_required(ctx: &EventLoopCtx<'_>, request: ToolApprovalRequest) {
+    handle_input_required(HandleInputRequiredParams {
+        tx: ctx.tx,
+        webhook_context: ctx.webhook_context,
+        request,
+        task_id: ctx.task_id,
+        context_id: ctx.context_id,
+        original_message: ctx.original_message,
+        agent_name: ctx.agent_name,
+        context: ctx.context,
+        task_repo: ctx.task_repo,
+        processor: ctx.processor,
+    })
+    .await;
+
+    send_a2a_status_event(&SendA2aStatusEventParams {
+        tx: ctx.tx,
+        task_id: ctx.task_id,
+        context_id: ctx.context_id,
+        state: "input-required",
+        is_final: true,
+        request_id: ctx.request_id,
+    });
+}
+
 async fn finish_failed(ctx: &EventLoopCtx<'_>, error: String) {
     handle_error(HandleErrorParams {
         tx: ctx.tx,
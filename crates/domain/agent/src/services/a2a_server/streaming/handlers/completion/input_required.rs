@@ -0,0 +1,109 @@
+//! The input-required stream handler.
+//!
+//! [`handle_input_required`] suspends the task for a tool approval: it
+//! persists the task with the approval request as its status message, sends
+//! the final `input-required` status frame carrying that message, and
+//! broadcasts the A2A and AG-UI events so other clients see the task waiting.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::Arc;
+
+use axum::response::sse::Event;
+use systemprompt_identifiers::{ContextId, TaskId};
+use systemprompt_models::{A2AEventBuilder, AgUiEventBuilder, RequestContext};
+use tokio::sync::mpsc::Sender;
+
+use super::send_a2a_status_event;
+use crate::models::ToolApprovalRequest;
+use crate::models::a2a::{Message, TaskState, TaskStatus};
+use crate::repository::task::TaskRepository;
+use crate::services::a2a_server::processing::message::{
+    MessageProcessor, PersistInputRequiredTaskOnProcessorParams,
+};
+use crate::services::a2a_server::streaming::webhook_client::WebhookContext;
+
+pub(in crate::services::a2a_server::streaming) struct HandleInputRequiredParams<'a> {
+    pub tx: &'a Sender<Event>,
+    pub webhook_context: &'a WebhookContext,
+    pub request: ToolApprovalRequest,
+    pub task_id: &'a TaskId,
+    pub context_id: &'a ContextId,
+    pub original_message: &'a Message,
+    pub agent_name: &'a str,
+    pub context: &'a RequestContext,
+    pub task_repo: &'a TaskRepository,
+    pub processor: &'a Arc<MessageProcessor>,
+}
+
+pub(in crate::services::a2a_server::streaming) async fn handle_input_required(
+    params: HandleInputRequiredParams<'_>,
+) {
+    let HandleInputRequiredParams {
+        tx,
+        webhook_context,
+        request,
+        task_id,
+        context_id,
+        original_message,
+        agent_name,
+        context,
+        task_repo,
+        processor,
+    } = params;
+
+    let task = match processor
+        .persist_input_required_task(PersistInputRequiredTaskOnProcessorParams {
+            task_id,
+            context_id,
+            user_message: original_message,
+            request: &request,
+            context,
+            agent_name,
+        })
+        .await
+    {
+        Ok(task) => task,
+        Err(e) => {
+            tracing::error!(task_id = %task_id, error = %e, "Failed to suspend task for approval");
+            let error_msg = format!("Failed to suspend task for approval: {e}");
+            if let Err(update_err) = task_repo
+                .update_task_failed_with_error(task_id, &error_msg, &chrono::Utc::now())
+                .await
+            {
+                tracing::error!(task_id = %task_id, error = %update_err, "Failed to update task to failed state");
+            }
+            let event =
+                AgUiEventBuilder::run_error(error_msg, Some("PERSISTENCE_ERROR".to_owned()));
+            if let Err(broadcast_err) = webhook_context.broadcast_agui(event).await {
+                tracing::error!(error = %broadcast_err, "Failed to broadcast RUN_ERROR");
+            }
+            return;
+        },
+    };
+
+    let status = TaskStatus {
+        state: TaskState::InputRequired,
+        message: task.status.message.clone(),
+        timestamp: Some(chrono::Utc::now()),
+    };
+    send_a2a_status_event(tx, task_id, context_id, status, true);
+
+    let a2a_event =
+        A2AEventBuilder::input_required(task_id.clone(), context_id.clone(), request.prompt());
+    if let Err(e) = webhook_context.broadcast_a2a(a2a_event).await {
+        tracing::error!(error = %e, "Failed to broadcast A2A input_required");
+    }
+
+    let agui_result = serde_json::json!({
+        "inputRequired": request,
+        "taskId": task_id.as_str(),
+        "contextId": context_id.as_str()
+    });
+    let event =
+        AgUiEventBuilder::run_finished(context_id.clone(), task_id.clone(), Some(agui_result));
+    if let Err(e) = webhook_context.broadcast_agui(event).await {
+        tracing::error!(error = %e, "Failed to broadcast RUN_FINISHED");
+    }
+}
@@ -1,7 +1,8 @@
 //! Terminal stream-event handlers: task completion and failure.
 //!
 //! [`handle_complete`] persists the finished task and broadcasts the success
-//! events; [`handle_error`] records the failure. [`send_a2a_status_event`] is
+//! events; [`handle_input_required`] suspends the task for a tool approval;
+//! [`handle_error`] records the failure. [`send_a2a_status_event`] is
 //! the shared helper for emitting an A2A `TaskStatusUpdate` over the SSE
 //! channel.
 //!
@@ -10,12 +11,16 @@
 
 mod complete;
 mod error;
+mod input_required;
 mod success;
 
 pub(in crate::services::a2a_server::streaming) use complete::{
     HandleCompleteParams, handle_complete,
 };
 pub(in crate::services::a2a_server::streaming) use error::{HandleErrorParams, handle_error};
+pub(in crate::services::a2a_server::streaming) use input_required::{
+    HandleInputRequiredParams, handle_input_required,
+};
 
 use axum::response::sse::Event;
 use systemprompt_identifiers::{ContextId, TaskId};
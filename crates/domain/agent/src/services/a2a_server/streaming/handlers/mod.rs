@@ -1,8 +1,8 @@
 //! Per-event handlers for the A2A streaming pipeline.
 //!
 //! Routes incoming stream events to their handlers: `completion` for terminal
-//! completion and error events, `text` for incremental text accumulation via
-//! [`TextStreamState`].
+//! completion, input-required, and error events, `text` for incremental text
+//! accumulation via [`TextStreamState`].
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -11,6 +11,7 @@ mod completion;
 mod text;
 
 pub(super) use completion::{
-    HandleCompleteParams, HandleErrorParams, handle_complete, handle_error,
+    HandleCompleteParams, HandleErrorParams, HandleInputRequiredParams, handle_complete,
+    handle_error, handle_input_required,
 };
 pub(super) use text::TextStreamState;
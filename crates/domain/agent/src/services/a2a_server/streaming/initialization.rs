@@ -1,5 +1,6 @@
 //! Stream-setup orchestration: detect the agent kind, validate the context,
-//! persist the initial task, register a push-notification config, and assemble
+//! persist the initial task (or resume one suspended for tool approval),
+//! register a push-notification config, checkpoint the run, and assemble
 //! a [`StreamSetupResult`] for the streaming event loop.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
@@ -17,12 +18,14 @@ use uuid::Uuid;
 use crate::models::a2a::Message;
 use crate::models::a2a::jsonrpc::NumberOrString;
 use crate::services::a2a_server::handlers::AgentHandlerState;
-use crate::services::a2a_server::processing::message::MessageProcessor;
+use crate::services::a2a_server::processing::message::{MessageProcessor, save_run_checkpoint};
+use crate::services::a2a_server::processing::strategies::delegation::delegating_task_id;
 
 use super::agent_loader::{LoadAgentRuntimeParams, load_agent_runtime};
 use super::broadcast::{BroadcastTaskCreatedParams, broadcast_task_created};
 use super::initialization_steps::{
-    persist_initial_task, save_push_notification_config, validate_context,
+    check_approval_follow_up, persist_initial_task, resume_suspended_task,
+    save_push_notification_config, validate_context,
 };
 use super::types::{PersistTaskInput, StreamInput, StreamSetupResult};
 
@@ -111,28 +114,35 @@ pub(super) async fn setup_stream(
 
     validate_context(&context_id, context.user_id(), &state, tx, &request_id).await?;
 
-    let persist_input = PersistTaskInput {
-        task_id: &task_id,
-        context_id: &context_id,
-        agent_name: &agent_name,
-        context: &context,
-        state: &state,
-        tx,
-        request_id: &request_id,
+    let resuming = check_approval_follow_up(&message, &state, tx, &request_id).await?;
+    let task_repo = if resuming {
+        resume_suspended_task(&task_id, &state, tx, &request_id).await?
+    } else {
+        let task_repo = persist_initial_task(PersistTaskInput {
+            task_id: &task_id,
+            context_id: &context_id,
+            agent_name: &agent_name,
+            parent_task_id: delegating_task_id(&message).as_ref(),
+            context: &context,
+            state: &state,
+            tx,
+            request_id: &request_id,
+        })
+        .await?;
+        broadcast_task_created(BroadcastTaskCreatedParams {
+            task_id: &task_id,
+            context_id: &context_id,
+            user_id: context.user_id().as_str(),
+            user_message: &message,
+            agent_name: &agent_name,
+            token: context.auth.auth_token.as_str(),
+        })
+        .await;
+        task_repo
     };
-    let task_repo = persist_initial_task(persist_input).await?;
-
-    broadcast_task_created(BroadcastTaskCreatedParams {
-        task_id: &task_id,
-        context_id: &context_id,
-        user_id: context.user_id().as_str(),
-        user_message: &message,
-        agent_name: &agent_name,
-        token: context.auth.auth_token.as_str(),
-    })
-    .await;
 
     save_push_notification_config(&task_id, callback_config.as_ref(), &state).await;
+    save_run_checkpoint(&task_repo, &task_id, &message, &context).await;
 
     let agent_runtime = load_agent_runtime(LoadAgentRuntimeParams {
         registry,
@@ -1,6 +1,7 @@
 //! Per-step helpers used by [`super::initialization::setup_stream`]:
-//! context validation, initial task persistence, and push-notification config
-//! storage.
+//! context validation, initial task persistence (linking a delegated task to
+//! the task that delegated it), resumption of a task
+//! suspended for tool approval, and push-notification config storage.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -14,10 +15,12 @@ use tokio::sync::mpsc::Sender;
 
 use crate::models::a2a::jsonrpc::NumberOrString;
 use crate::models::a2a::protocol::PushNotificationConfig;
-use crate::models::a2a::{Task, TaskState, TaskStatus};
+use crate::models::a2a::{Message, Task, TaskState, TaskStatus};
 use crate::repository::task::TaskRepository;
 use crate::services::a2a_server::errors::classify_database_error;
 use crate::services::a2a_server::handlers::AgentHandlerState;
+use crate::services::a2a_server::processing::message::validate_approval_follow_up;
+use crate::services::a2a_server::processing::strategies::delegation::link_delegated_task;
 
 use super::initialization::create_jsonrpc_error_event;
 use super::types::PersistTaskInput;
@@ -70,6 +73,7 @@ pub(super) async fn persist_initial_task(
         task_id,
         context_id,
         agent_name,
+        parent_task_id,
         context,
         state,
         tx,
@@ -120,6 +124,15 @@ pub(super) async fn persist_initial_task(
 
     tracing::info!(task_id = %task_id, "Task persisted to database at stream start");
 
+    if let Some(parent_task_id) = parent_task_id {
+        link_delegated_task(&task_repo, task_id, parent_task_id, context.user_id())
+            .await
+            .map_err(|e| {
+                tracing::warn!(task_id = %task_id, parent_task_id = %parent_task_id, error = %e, "Delegation refused");
+                send_setup_error(tx, &format!("Delegation refused: {e}"), request_id);
+            })?;
+    }
+
     if let Err(e) = task_repo
         .track_agent_in_context(context_id, agent_name)
         .await
@@ -130,6 +143,49 @@ pub(super) async fn persist_initial_task(
     Ok(task_repo)
 }
 
+fn send_setup_error(tx: &Sender<Event>, message: &str, request_id: &NumberOrString) {
+    if tx
+        .try_send(create_jsonrpc_error_event(-32603, message, request_id))
+        .is_err()
+    {
+        tracing::trace!("Failed to send error event, channel closed");
+    }
+}
+
+/// Whether `message` answers the pending tool approval of an existing task;
+/// a follow-up that does not is refused.
+pub(super) async fn check_approval_follow_up(
+    message: &Message,
+    state: &Arc<AgentHandlerState>,
+    tx: &Sender<Event>,
+    request_id: &NumberOrString,
+) -> Result<bool, ()> {
+    validate_approval_follow_up(state.agent_state.repositories(), message)
+        .await
+        .map_err(|e| {
+            tracing::warn!(task_id = ?message.task_id, error = %e, "Follow-up message refused");
+            send_setup_error(tx, &format!("Follow-up message refused: {e}"), request_id);
+        })
+}
+
+pub(super) async fn resume_suspended_task(
+    task_id: &TaskId,
+    state: &Arc<AgentHandlerState>,
+    tx: &Sender<Event>,
+    request_id: &NumberOrString,
+) -> Result<TaskRepository, ()> {
+    let task_repo = state.agent_state.repositories().tasks.clone();
+    task_repo
+        .update_task_state(task_id, TaskState::Working, &chrono::Utc::now())
+        .await
+        .map_err(|e| {
+            tracing::error!(task_id = %task_id, error = %e, "Failed to resume task");
+            send_setup_error(tx, &format!("Failed to resume task: {e}"), request_id);
+        })?;
+    tracing::info!(task_id = %task_id, "Resuming task suspended for tool approval");
+    Ok(task_repo)
+}
+
 pub(super) async fn save_push_notification_config(
     task_id: &TaskId,
     callback_config: Option<&PushNotificationConfig>,
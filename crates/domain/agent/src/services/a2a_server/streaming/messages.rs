@@ -14,7 +14,10 @@ use crate::models::a2a::Message;
 use crate::models::a2a::jsonrpc::NumberOrString;
 use crate::models::a2a::protocol::PushNotificationConfig;
 use crate::services::a2a_server::handlers::AgentHandlerState;
-use crate::services::a2a_server::processing::message::ProcessMessageStreamParams;
+use crate::services::a2a_server::processing::message::{
+    ProcessMessageStreamParams, clear_run_checkpoint,
+};
+use crate::services::a2a_server::processing::strategies::ToolReplay;
 use crate::services::registry::AgentRegistry;
 
 use super::event_loop::{ProcessEventsParams, process_events};
@@ -108,6 +111,9 @@ async fn run_stream(input: StreamInput, tx: tokio::sync::mpsc::Sender<Event>) {
 
     tracing::info!(agent = %setup.agent_name, "Starting message stream processing for agent");
 
+    let task_id = setup.task_id.clone();
+    let task_repo = setup.task_repo.clone();
+
     match setup
         .processor
         .process_message_stream(ProcessMessageStreamParams {
@@ -116,6 +122,7 @@ async fn run_stream(input: StreamInput, tx: tokio::sync::mpsc::Sender<Event>) {
             agent_name: &setup.agent_name,
             context: &setup.context,
             task_id: setup.task_id.clone(),
+            tool_replay: ToolReplay::default(),
         })
         .await
     {
@@ -150,4 +157,6 @@ async fn run_stream(input: StreamInput, tx: tokio::sync::mpsc::Sender<Event>) {
             .await;
         },
     }
+
+    clear_run_checkpoint(&task_repo, &task_id).await;
 }
@@ -2,6 +2,9 @@
 //! and the per-task event loop that fans the model's stream out to A2A,
 //! AG-UI webhooks, and SSE clients.
 //!
+//! Tasks suspended for a tool approval are also pushed to the notification
+//! endpoints the client registered (see [`push_notifications`]).
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
@@ -13,6 +16,7 @@ mod handlers;
 mod initialization;
 mod initialization_steps;
 mod messages;
+pub mod push_notifications;
 mod types;
 pub mod webhook_client;
 
@@ -24,4 +28,5 @@ pub use event_loop_lifecycle::{
 pub use messages::{
     CreateSseStreamParams, StreamRejected, create_sse_stream, create_sse_stream_with_registry,
 };
+pub use push_notifications::deliver_push_notifications;
 pub use types::{PersistTaskInput, StreamContext, StreamInput, StreamSetupResult};
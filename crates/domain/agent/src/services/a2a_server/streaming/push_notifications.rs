@@ -0,0 +1,81 @@
+//! Push-notification delivery to the endpoints a client registered for a task.
+//!
+//! The task is sent as a JSON `POST` to each stored config's `url`, with the
+//! config's `token` in `X-A2A-Notification-Token` and its custom headers added
+//! as-is.
+//! Delivery is best effort: a failing endpoint is logged and never affects the
+//! task.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::time::Duration;
+
+use reqwest::Client;
+
+use crate::models::a2a::Task;
+use crate::models::a2a::protocol::PushNotificationConfig;
+use crate::repository::A2ARepositories;
+
+const NOTIFICATION_TOKEN_HEADER: &str = "X-A2A-Notification-Token";
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub async fn deliver_push_notifications(repositories: &A2ARepositories, task: &Task) {
+    let configs = match repositories
+        .push_notification_configs
+        .list_configs(&task.id)
+        .await
+    {
+        Ok(configs) => configs,
+        Err(e) => {
+            tracing::warn!(task_id = %task.id, error = %e, "Failed to load push notification configs");
+            return;
+        },
+    };
+    if configs.is_empty() {
+        return;
+    }
+
+    let client = match Client::builder().timeout(DELIVERY_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to build push notification client");
+            return;
+        },
+    };
+    for config in &configs {
+        match post_notification(&client, config, task).await {
+            Ok(()) => {
+                tracing::debug!(task_id = %task.id, url = %config.url, "Push notification delivered");
+            },
+            Err(reason) => tracing::warn!(
+                task_id = %task.id,
+                url = %config.url,
+                reason = %reason,
+                "Push notification delivery failed"
+            ),
+        }
+    }
+}
+
+async fn post_notification(
+    client: &Client,
+    config: &PushNotificationConfig,
+    task: &Task,
+) -> Result<(), String> {
+    let mut request = client.post(&config.url).json(task);
+    if let Some(token) = &config.token {
+        request = request.header(NOTIFICATION_TOKEN_HEADER, token);
+    }
+    for (name, value) in config.headers.iter().flatten() {
+        if let Some(value) = value.as_str() {
+            request = request.header(name.as_str(), value);
+        }
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("endpoint answered {}", response.status()))
+    }
+}
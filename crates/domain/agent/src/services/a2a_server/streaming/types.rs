@@ -49,6 +49,7 @@ pub struct PersistTaskInput<'a> {
     pub task_id: &'a TaskId,
     pub context_id: &'a ContextId,
     pub agent_name: &'a str,
+    pub parent_task_id: Option<&'a TaskId>,
     pub context: &'a RequestContext,
     pub state: &'a Arc<AgentHandlerState>,
     pub tx: &'a Sender<Event>,
@@ -0,0 +1,233 @@
+//! The pure half of history compaction: estimating tokens, deciding which
+//! messages a new summary should absorb, and fitting the history to the
+//! budget.
+//!
+//! Messages are split into pinned ones (artifacts, when the agent pins them)
+//! and compactable ones, which are numbered in history order. A summary
+//! replaces the first `covered_messages` compactable messages; the last
+//! `keep_recent_messages` are never touched. Truncation drops compactable
+//! messages between the two, oldest first, until the history fits.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use systemprompt_models::{AgentCompactionConfig, AiContentPart, AiMessage, MessageRole};
+
+use crate::models::ContextSummary;
+use crate::services::context::HistoryEntry;
+
+const CHARS_PER_TOKEN: usize = 4;
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+// Why: the character heuristic cannot size media, and providers bill an
+// image or clip at hundreds of tokens regardless of its base64 length.
+const MEDIA_PART_TOKENS: usize = 256;
+
+const SUMMARY_INSTRUCTIONS: &str = "You maintain a running summary of a conversation between a \
+                                    user and an AI agent. Merge the existing summary, if any, \
+                                    with the new messages into one concise summary. Keep facts, \
+                                    decisions, open questions, names, and identifiers the agent \
+                                    needs to continue the conversation. Reply with the summary \
+                                    only.";
+
+/// Compactable messages a new summary should absorb, and how many leading
+/// compactable messages that summary will then cover.
+#[derive(Debug)]
+pub struct SummaryBatch<'a> {
+    pub messages: Vec<&'a AiMessage>,
+    pub covered_messages: usize,
+}
+
+struct Slot<'a> {
+    message: &'a AiMessage,
+    ordinal: Option<usize>,
+}
+
+struct Layout<'a> {
+    slots: Vec<Slot<'a>>,
+    covered: usize,
+    recent_start: usize,
+}
+
+/// A rough token count for `message`: four characters a token, a flat cost
+/// for each media part, and a small per-message overhead.
+#[must_use]
+pub fn estimate_tokens(message: &AiMessage) -> usize {
+    let body = if message.parts.is_empty() {
+        message.content.len().div_ceil(CHARS_PER_TOKEN)
+    } else {
+        message
+            .parts
+            .iter()
+            .map(|part| match part {
+                AiContentPart::Text { text } => text.len().div_ceil(CHARS_PER_TOKEN),
+                AiContentPart::Image { .. }
+                | AiContentPart::Audio { .. }
+                | AiContentPart::Video { .. } => MEDIA_PART_TOKENS,
+            })
+            .sum()
+    };
+    body + MESSAGE_OVERHEAD_TOKENS
+}
+
+#[must_use]
+pub fn history_tokens(messages: &[AiMessage]) -> usize {
+    messages.iter().map(estimate_tokens).sum()
+}
+
+/// The system message that stands in for the messages `summary` covers.
+#[must_use]
+pub fn summary_message(summary: &ContextSummary) -> AiMessage {
+    AiMessage::system(format!(
+        "[Summary of the {} earliest messages in this conversation]\n{}",
+        summary.covered_messages, summary.summary
+    ))
+}
+
+/// The request messages asking a model to fold `batch` into `previous`.
+#[must_use]
+pub fn summary_prompt(
+    previous: Option<&ContextSummary>,
+    batch: &SummaryBatch<'_>,
+) -> Vec<AiMessage> {
+    let mut transcript = String::new();
+    if let Some(previous) = previous {
+        transcript.push_str("Existing summary:\n");
+        transcript.push_str(&previous.summary);
+        transcript.push_str("\n\n");
+    }
+    transcript.push_str("New messages:\n");
+    for message in &batch.messages {
+        let speaker = match message.role {
+            MessageRole::System => "System",
+            MessageRole::User => "User",
+            MessageRole::Assistant => "Assistant",
+        };
+        transcript.push_str(&format!("{speaker}: {}\n", message.content));
+    }
+    vec![
+        AiMessage::system(SUMMARY_INSTRUCTIONS),
+        AiMessage::user(transcript),
+    ]
+}
+
+/// The batch a new summary should absorb, or `None` when the agent does not
+/// summarize, the history already fits its budget, or every message outside
+/// the recent tail is already summarized.
+#[must_use]
+pub fn pending_summary<'a>(
+    entries: &'a [HistoryEntry],
+    config: &AgentCompactionConfig,
+    summary: Option<&ContextSummary>,
+) -> Option<SummaryBatch<'a>> {
+    if !config.summarizes() {
+        return None;
+    }
+    let layout = Layout::new(entries, config, summary);
+    let retained_tokens: usize = summary
+        .filter(|_| layout.covered > 0)
+        .map_or(0, |s| estimate_tokens(&summary_message(s)))
+        + layout
+            .slots
+            .iter()
+            .filter(|slot| slot.ordinal.is_none_or(|n| n >= layout.covered))
+            .map(|slot| estimate_tokens(slot.message))
+            .sum::<usize>();
+    if retained_tokens <= budget(config) {
+        return None;
+    }
+
+    let messages: Vec<&AiMessage> = layout
+        .slots
+        .iter()
+        .filter(|slot| {
+            slot.ordinal
+                .is_some_and(|n| n >= layout.covered && n < layout.recent_start)
+        })
+        .map(|slot| slot.message)
+        .collect();
+    (!messages.is_empty()).then_some(SummaryBatch {
+        messages,
+        covered_messages: layout.recent_start,
+    })
+}
+
+/// The history to replay, fitted to the budget.
+///
+/// `summary` stands in for the messages it covers, then the oldest
+/// compactable messages outside the recent tail are dropped until the
+/// estimate fits. Pinned and recent messages are always kept, so the result
+/// can still exceed the budget.
+#[must_use]
+pub fn fit_history(
+    entries: &[HistoryEntry],
+    config: &AgentCompactionConfig,
+    summary: Option<&ContextSummary>,
+) -> Vec<AiMessage> {
+    let layout = Layout::new(entries, config, summary);
+    let head = summary.filter(|_| layout.covered > 0).map(summary_message);
+
+    let mut kept: Vec<&Slot<'_>> = layout
+        .slots
+        .iter()
+        .filter(|slot| slot.ordinal.is_none_or(|n| n >= layout.covered))
+        .collect();
+    let mut total = head.as_ref().map_or(0, estimate_tokens)
+        + kept
+            .iter()
+            .map(|slot| estimate_tokens(slot.message))
+            .sum::<usize>();
+
+    let limit = budget(config);
+    kept.retain(|slot| {
+        let droppable = slot.ordinal.is_some_and(|n| n < layout.recent_start);
+        if total > limit && droppable {
+            total -= estimate_tokens(slot.message);
+            return false;
+        }
+        true
+    });
+
+    head.into_iter()
+        .chain(kept.into_iter().map(|slot| slot.message.clone()))
+        .collect()
+}
+
+fn budget(config: &AgentCompactionConfig) -> usize {
+    usize::try_from(config.max_history_tokens).unwrap_or(usize::MAX)
+}
+
+impl<'a> Layout<'a> {
+    fn new(
+        entries: &'a [HistoryEntry],
+        config: &AgentCompactionConfig,
+        summary: Option<&ContextSummary>,
+    ) -> Self {
+        let mut next = 0;
+        let slots: Vec<Slot<'a>> = entries
+            .iter()
+            .map(|entry| {
+                let ordinal = (!(config.pin_artifacts && entry.is_artifact)).then(|| {
+                    next += 1;
+                    next - 1
+                });
+                Slot {
+                    message: &entry.message,
+                    ordinal,
+                }
+            })
+            .collect();
+
+        let keep_recent = usize::try_from(config.keep_recent_messages).unwrap_or(usize::MAX);
+        let recent_start = next.saturating_sub(keep_recent);
+        let covered = summary
+            .and_then(|s| usize::try_from(s.covered_messages).ok())
+            .unwrap_or(0)
+            .min(next);
+
+        Self {
+            slots,
+            covered,
+            recent_start,
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Keeping a long-lived context's history inside the model window.
+//!
+//! [`ContextCompactionService`] applies an agent's
+//! [`systemprompt_models::AgentCompactionConfig`] to the history loaded by
+//! [`super::ContextService`] before it is replayed into an AI call. The
+//! `truncate` strategy drops the oldest messages to the token budget; the
+//! `summarize` strategy first folds them into a rolling summary, stored in
+//! `context_summaries` so later turns reuse it and only summarize again once
+//! the history outgrows the budget. A failed summarization falls back to
+//! truncation rather than failing the turn.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+mod history;
+
+pub use history::{
+    SummaryBatch, estimate_tokens, fit_history, history_tokens, pending_summary, summary_message,
+    summary_prompt,
+};
+
+use systemprompt_identifiers::ContextId;
+use systemprompt_models::{AiMessage, AiProvider, AiRequest, RequestContext};
+
+use crate::models::{AgentRuntimeInfo, ContextSummary, NewContextSummary};
+use crate::repository::ContextRepository;
+use crate::services::a2a_server::processing::ai_executor::resolve_provider_config;
+use crate::services::context::HistoryEntry;
+use crate::services::shared::{AgentServiceError, Result};
+
+#[expect(
+    missing_debug_implementations,
+    reason = "params struct holds non-Debug references"
+)]
+pub struct CompactHistoryParams<'a> {
+    pub ai_service: &'a dyn AiProvider,
+    pub agent_runtime: &'a AgentRuntimeInfo,
+    pub context_id: &'a ContextId,
+    pub request_ctx: &'a RequestContext,
+    pub entries: Vec<HistoryEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContextCompactionService {
+    contexts: ContextRepository,
+}
+
+impl ContextCompactionService {
+    #[must_use]
+    pub const fn new(contexts: ContextRepository) -> Self {
+        Self { contexts }
+    }
+
+    /// The history to replay for the agent, compacted per its configuration.
+    pub async fn compact(&self, params: CompactHistoryParams<'_>) -> Vec<AiMessage> {
+        let config = &params.agent_runtime.compaction;
+        if !config.is_enabled() {
+            return params.entries.into_iter().map(|e| e.message).collect();
+        }
+
+        let mut summary = None;
+        if config.summarizes() {
+            summary = self
+                .contexts
+                .latest_summary(params.context_id, &params.agent_runtime.name)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!(error = %e, context_id = %params.context_id, "Failed to load context summary");
+                    None
+                });
+            if let Some(batch) = pending_summary(&params.entries, config, summary.as_ref()) {
+                match self.summarize(&params, summary.as_ref(), &batch).await {
+                    Ok(fresh) => summary = Some(fresh),
+                    Err(e) => tracing::warn!(
+                        error = %e,
+                        context_id = %params.context_id,
+                        "Summarization failed, truncating history instead"
+                    ),
+                }
+            }
+        }
+
+        let messages = fit_history(&params.entries, config, summary.as_ref());
+        tracing::info!(
+            context_id = %params.context_id,
+            strategy = ?config.strategy,
+            original_count = params.entries.len(),
+            compacted_count = messages.len(),
+            estimated_tokens = history_tokens(&messages),
+            "Compacted conversation history"
+        );
+        messages
+    }
+
+    async fn summarize(
+        &self,
+        params: &CompactHistoryParams<'_>,
+        previous: Option<&ContextSummary>,
+        batch: &SummaryBatch<'_>,
+    ) -> Result<ContextSummary> {
+        let (provider, model, _) =
+            resolve_provider_config(params.request_ctx, params.agent_runtime, params.ai_service);
+        let request = AiRequest::builder(
+            summary_prompt(previous, batch),
+            &provider,
+            &model,
+            params.agent_runtime.compaction.summary_max_tokens,
+            params.request_ctx.clone(),
+        )
+        .build();
+        let response = params.ai_service.generate(&request).await?;
+
+        let text = response.content.trim();
+        if text.is_empty() {
+            return Err(AgentServiceError::Internal(
+                "summarization returned no text".to_owned(),
+            ));
+        }
+        let summary = self
+            .contexts
+            .create_summary(&NewContextSummary {
+                context_id: params.context_id,
+                agent_name: &params.agent_runtime.name,
+                summary: text,
+                covered_messages: i32::try_from(batch.covered_messages).unwrap_or(i32::MAX),
+                token_estimate: i32::try_from(estimate_tokens(&AiMessage::system(text)))
+                    .unwrap_or(i32::MAX),
+            })
+            .await?;
+        tracing::info!(
+            context_id = %params.context_id,
+            covered_messages = summary.covered_messages,
+            "Stored context summary"
+        );
+        Ok(summary)
+    }
+}
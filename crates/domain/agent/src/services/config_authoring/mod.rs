@@ -230,7 +230,7 @@ fn default_model_for(provider: &str) -> String {
         .and_then(|registry| {
             registry
                 .find_provider(provider)
-                .and_then(|entry| entry.models.first().map(|m| m.id.as_str().to_owned()))
+                .and_then(|entry| entry.all_models().next().map(|m| m.id.as_str().to_owned()))
         })
         .unwrap_or_else(|| "claude-sonnet-4-6".to_owned())
 }
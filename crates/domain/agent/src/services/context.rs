@@ -1,6 +1,9 @@
 //! Reconstructing conversation history for a context into AI-ready messages,
 //! including decoding file parts and serializing artifacts as context.
 //!
+//! [`HistoryEntry`] keeps track of which messages stand for artifacts so that
+//! history compaction can pin them.
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
@@ -14,6 +17,13 @@ use systemprompt_models::{
 use crate::models::a2a::{Artifact, FilePart, Message, Part};
 use crate::repository::task::TaskRepository;
 
+/// One replayable history message.
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub message: AiMessage,
+    pub is_artifact: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct ContextService {
     task_repo: TaskRepository,
@@ -29,6 +39,18 @@ impl ContextService {
         &self,
         context_id: &systemprompt_identifiers::ContextId,
     ) -> Result<Vec<AiMessage>> {
+        Ok(self
+            .load_history_entries(context_id)
+            .await?
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect())
+    }
+
+    pub async fn load_history_entries(
+        &self,
+        context_id: &systemprompt_identifiers::ContextId,
+    ) -> Result<Vec<HistoryEntry>> {
         let tasks = self
             .task_repo
             .list_tasks_by_context(context_id)
@@ -52,10 +74,13 @@ impl ContextService {
                         crate::models::a2a::MessageRole::Agent => MessageRole::Assistant,
                     };
 
-                    history_messages.push(AiMessage {
-                        role,
-                        content: text,
-                        parts,
+                    history_messages.push(HistoryEntry {
+                        message: AiMessage {
+                            role,
+                            content: text,
+                            parts,
+                        },
+                        is_artifact: false,
                     });
                 }
             }
@@ -63,10 +88,9 @@ impl ContextService {
             if let Some(artifacts) = task.artifacts {
                 for artifact in artifacts {
                     let artifact_content = Self::serialize_artifact_for_context(&artifact);
-                    history_messages.push(AiMessage {
-                        role: MessageRole::Assistant,
-                        content: artifact_content,
-                        parts: Vec::new(),
+                    history_messages.push(HistoryEntry {
+                        message: AiMessage::assistant(artifact_content),
+                        is_artifact: true,
                     });
                 }
             }
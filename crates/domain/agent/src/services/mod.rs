@@ -3,7 +3,7 @@
 //! Submodules group runtime services by responsibility: the embedded A2A HTTP
 //! server, orchestration of agent processes, on-disk agent config authoring,
 //! MCP tool bridging, registry, disk-backed skills, message and context
-//! services, and shared helpers.
+//! services, history compaction, and shared helpers.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -11,6 +11,7 @@
 pub mod a2a_server;
 pub mod agent_orchestration;
 pub mod artifact_publishing;
+pub mod compaction;
 pub mod config_authoring;
 pub mod context;
 pub mod context_provider;
@@ -51,7 +52,8 @@ pub use message::{
     PersistMessagesParams,
 };
 
-pub use context::ContextService;
+pub use compaction::ContextCompactionService;
+pub use context::{ContextService, HistoryEntry};
 
 pub use context_provider::ContextProviderService;
 
@@ -134,11 +134,11 @@ impl AgentRegistry {
         Ok(AgentCard {
             name: agent.name.clone(),
             description: agent.card.description.clone(),
-            supported_interfaces: vec![AgentInterface {
-                url,
+            supported_interfaces: served_interfaces(
+                &url,
                 protocol_binding,
-                protocol_version: agent.card.protocol_version.clone(),
-            }],
+                &agent.card.protocol_version,
+            ),
             version: agent.card.version.clone(),
             icon_url: agent.card.icon_url.clone(),
             documentation_url: agent.card.documentation_url.clone(),
@@ -188,6 +188,29 @@ impl AgentRegistry {
     }
 }
 
+/// The card's preferred binding first, followed by the bindings every agent
+/// server answers on: JSON-RPC at the agent URL and HTTP+JSON beneath it.
+fn served_interfaces(
+    url: &str,
+    preferred: TransportProtocol,
+    protocol_version: &str,
+) -> Vec<AgentInterface> {
+    let mut bindings = vec![preferred];
+    for served in [TransportProtocol::JsonRpc, TransportProtocol::HttpJson] {
+        if !bindings.contains(&served) {
+            bindings.push(served);
+        }
+    }
+    bindings
+        .into_iter()
+        .map(|protocol_binding| AgentInterface {
+            url: url.to_owned(),
+            protocol_binding,
+            protocol_version: protocol_version.to_owned(),
+        })
+        .collect()
+}
+
 fn build_extensions(
     agent: &AgentConfig,
     runtime_status: Option<&(String, Option<u16>, Option<u32>)>,
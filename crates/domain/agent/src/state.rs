@@ -6,7 +6,7 @@
 use std::sync::Arc;
 use systemprompt_database::DbPool;
 use systemprompt_models::Config;
-use systemprompt_traits::DynJwtValidationProvider;
+use systemprompt_traits::{DynJwtValidationProvider, DynUserProvider};
 
 use crate::repository::A2ARepositories;
 
@@ -16,6 +16,7 @@ pub struct AgentState {
     config: Arc<Config>,
     jwt_provider: DynJwtValidationProvider,
     repositories: Arc<A2ARepositories>,
+    user_provider: Option<DynUserProvider>,
 }
 
 impl AgentState {
@@ -31,9 +32,16 @@ impl AgentState {
             config,
             jwt_provider,
             repositories,
+            user_provider: None,
         }
     }
 
+    #[must_use]
+    pub fn with_user_provider(mut self, provider: DynUserProvider) -> Self {
+        self.user_provider = Some(provider);
+        self
+    }
+
     #[must_use]
     pub const fn db_pool(&self) -> &DbPool {
         &self.db_pool
@@ -53,6 +61,13 @@ impl AgentState {
     pub const fn repositories(&self) -> &Arc<A2ARepositories> {
         &self.repositories
     }
+
+    /// The user directory, when the process was given one. Startup recovery
+    /// reads a task owner's current roles and status from it.
+    #[must_use]
+    pub const fn user_provider(&self) -> Option<&DynUserProvider> {
+        self.user_provider.as_ref()
+    }
 }
 
 impl std::fmt::Debug for AgentState {
@@ -62,6 +77,7 @@ impl std::fmt::Debug for AgentState {
             .field("config", &"<Arc<Config>>")
             .field("jwt_provider", &"<DynJwtValidationProvider>")
             .field("repositories", &"<A2ARepositories>")
+            .field("user_provider", &self.user_provider.is_some())
             .finish()
     }
 }
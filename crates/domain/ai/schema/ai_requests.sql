@@ -14,6 +14,7 @@ CREATE TABLE IF NOT EXISTS ai_requests (
     requested_model TEXT,
     system_prompt_override TEXT,
     route_match TEXT,
+    route_attempts JSONB,
     temperature DOUBLE PRECISION,
     top_p DOUBLE PRECISION,
     max_tokens INTEGER,
@@ -26,6 +27,7 @@ CREATE TABLE IF NOT EXISTS ai_requests (
     cache_hit BOOLEAN NOT NULL DEFAULT FALSE,
     cache_read_tokens INTEGER,
     cache_creation_tokens INTEGER,
+    response_cache_hit BOOLEAN NOT NULL DEFAULT FALSE,
     is_streaming BOOLEAN NOT NULL DEFAULT FALSE,
     status VARCHAR(255) NOT NULL DEFAULT 'pending',
     error_message TEXT,
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS ai_response_cache (
+    cache_key TEXT PRIMARY KEY,
+    scope VARCHAR(16) NOT NULL,
+    model VARCHAR(255) NOT NULL,
+    response JSONB NOT NULL,
+    byte_len INTEGER NOT NULL,
+    source_request_id TEXT,
+    hit_count BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_ai_response_cache_expires_at ON ai_response_cache(expires_at);
@@ -6,6 +6,7 @@ CREATE TABLE IF NOT EXISTS ai_safety_findings (
     category VARCHAR(64) NOT NULL,
     scanner VARCHAR(64) NOT NULL,
     excerpt TEXT,
+    redaction_count INTEGER NOT NULL DEFAULT 0,
     created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
     FOREIGN KEY (ai_request_id) REFERENCES ai_requests(id) ON DELETE CASCADE
 );
@@ -0,0 +1,10 @@
+-- Record every upstream hop a gateway request tried.
+--
+-- A route may list several upstream targets and fall back between them on
+-- rate limits, upstream errors, timeouts, or an open circuit. This column holds
+-- the ordered attempts — provider, upstream model, and outcome of each — so an
+-- audit shows which hop served the request and what failed before it. NULL
+-- for routes with a single upstream and for non-gateway requests.
+
+ALTER TABLE ai_requests
+    ADD COLUMN IF NOT EXISTS route_attempts JSONB;
@@ -0,0 +1,28 @@
+-- Exact-match response cache for the gateway.
+--
+-- A policy with `cache.enabled` stores the response to a deterministic
+-- (`temperature: 0`) request under a hash of its normalized canonical form,
+-- and replays it to identical requests until `expires_at`. The key already
+-- folds in the policy's scope (the user, or the whole deployment), so rows
+-- are looked up by `cache_key` alone. Expired rows are removed by the
+-- `database_cleanup` job.
+--
+-- `ai_requests.response_cache_hit` marks requests answered from this table.
+-- It is separate from `cache_hit`, which records an upstream prompt-cache
+-- read on a request that did reach the provider.
+
+CREATE TABLE IF NOT EXISTS ai_response_cache (
+    cache_key TEXT PRIMARY KEY,
+    scope VARCHAR(16) NOT NULL,
+    model VARCHAR(255) NOT NULL,
+    response JSONB NOT NULL,
+    byte_len INTEGER NOT NULL,
+    source_request_id TEXT,
+    hit_count BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_ai_response_cache_expires_at ON ai_response_cache(expires_at);
+
+ALTER TABLE ai_requests
+    ADD COLUMN IF NOT EXISTS response_cache_hit BOOLEAN NOT NULL DEFAULT FALSE;
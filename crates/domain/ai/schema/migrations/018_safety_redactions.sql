@@ -0,0 +1,10 @@
+-- Redaction as a third safety outcome.
+--
+-- A finding in a policy's `redaction.categories` no longer only allows or
+-- denies: the offending spans are rewritten with placeholders before the
+-- request leaves the gateway (or before response text reaches the client).
+-- `redaction_count` records how many spans one finding row rewrote; 0 means
+-- the finding was only observed.
+
+ALTER TABLE ai_safety_findings
+    ADD COLUMN IF NOT EXISTS redaction_count INTEGER NOT NULL DEFAULT 0;
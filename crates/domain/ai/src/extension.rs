@@ -74,6 +74,15 @@ impl Extension for AiExtension {
                 include_str!("../schema/ai_gateway_policies.sql"),
             )
             .with_required_columns(vec!["id".into(), "name".into(), "spec".into()]),
+            SchemaDefinition::new(
+                "ai_response_cache",
+                include_str!("../schema/ai_response_cache.sql"),
+            )
+            .with_required_columns(vec![
+                "cache_key".into(),
+                "response".into(),
+                "expires_at".into(),
+            ]),
         ]
     }
 
@@ -60,15 +60,18 @@ pub use extension::AiExtension;
 pub use services::core::{AiService, AiServiceProviders, ImageService};
 
 pub use services::gateway::{
-    Finding, GATEWAY_POLICIES_FILE, GatewayPolicyConfig, GatewayPolicyEntry,
+    BudgetAlertTarget, Finding, GATEWAY_POLICIES_FILE, GatewayPolicyConfig, GatewayPolicyEntry,
     GatewayPolicyIngestionService, GatewayPolicySpec, HeuristicConfig, HeuristicScanner,
     IngestOptions as GatewayPolicyIngestOptions, IngestReport as GatewayPolicyIngestReport,
     NullScanner, OverrideAction, OverrideContext, OverrideContextBuilder, OverrideEngine,
     OverrideError, OverrideResolution, OverrideSource, PHASE_REQUEST, PHASE_REQUEST_HISTORY,
-    PHASE_RESPONSE, QuotaWindow, RouteSelector, RouteSelectorEngine, RouteSelectorError,
-    RouteSelectorRegistration, SafetyConfig, SafetyHistoryMode, SafetyScanner,
-    SafetyScannerRegistration, Severity, SystemPromptOverride, SystemPromptOverrideRegistration,
-    USER_QUOTA_SUBJECT, load_from_yaml as load_gateway_policies_from_yaml,
+    PHASE_RESPONSE, PatternConfig, PatternDetector, PatternMatch, PatternRule, PatternScanner,
+    QuotaWindow, RateLimit, RedactionConfig, RedactionTally, RedactionVault, Redactor,
+    ResponseCacheConfig, ResponseCacheScope, ResponseRewriter, RouteSelector, RouteSelectorEngine,
+    RouteSelectorError, RouteSelectorRegistration, STREAM_HOLDBACK, SafetyConfig,
+    SafetyHistoryMode, SafetyScanner, SafetyScannerRegistration, Severity, StreamSafetyConfig,
+    SystemPromptOverride, SystemPromptOverrideRegistration, USER_QUOTA_SUBJECT,
+    load_from_yaml as load_gateway_policies_from_yaml,
 };
 pub use services::storage::{ImageStorage, StorageConfig};
 pub use services::tools::NoopToolProvider;
@@ -89,12 +92,14 @@ pub use models::image_generation::{
     ImageResolution, ReferenceImage,
 };
 
-pub use services::providers::{GeminiImageProvider, ImageProvider, ImageProviderCapabilities};
+pub use services::providers::{
+    GeminiImageProvider, ImageProvider, ImageProviderCapabilities, discover_local_models,
+};
 
 pub use repository::{
     AiGatewayPolicyRepository, AiQuotaBucketRepository, AiRequestPayloadRepository,
     AiRequestRepository, AiSafetyFindingRepository, GatewayPolicyRow, IncrementParams,
-    InsertSafetyFinding, QuotaBucketDelta, QuotaBucketState, UpsertPayloadParams,
+    InsertSafetyFinding, QuotaBucketDelta, QuotaBucketRow, QuotaBucketState, UpsertPayloadParams,
 };
 
 pub use services::tooled::ToolResultFormatter;
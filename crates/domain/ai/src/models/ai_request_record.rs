@@ -28,6 +28,7 @@ pub enum RequestStatus {
     Completed,
     Failed,
     Rejected,
+    Truncated,
 }
 
 impl RequestStatus {
@@ -37,6 +38,7 @@ impl RequestStatus {
             Self::Completed => "completed",
             Self::Failed => "failed",
             Self::Rejected => "rejected",
+            Self::Truncated => "truncated",
         }
     }
 }
@@ -240,6 +242,12 @@ impl AiRequestRecordBuilder {
         self
     }
 
+    pub fn truncated(mut self, error_message: impl Into<String>) -> Self {
+        self.status = RequestStatus::Truncated;
+        self.error_message = Some(error_message.into());
+        self
+    }
+
     #[must_use]
     pub fn build(self) -> AiRequestRecord {
         let actor = self
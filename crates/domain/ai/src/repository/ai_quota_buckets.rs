@@ -43,6 +43,16 @@ pub struct QuotaBucketState {
     pub cost_microdollars: i64,
 }
 
+/// One subject's totals in a single window, as listed by
+/// [`AiQuotaBucketRepository::list_by_spend`].
+#[derive(Debug, Clone)]
+pub struct QuotaBucketRow {
+    pub subject_id: String,
+    pub requests: i64,
+    pub cost_microdollars: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
 impl AiQuotaBucketRepository {
     pub fn new(db: &DbPool) -> Result<Self, RepositoryError> {
         let write_pool = db
@@ -98,4 +108,34 @@ impl AiQuotaBucketRepository {
             cost_microdollars: row.cost_microdollars,
         })
     }
+
+    /// Buckets of one window whose spend is at least `min_cost_microdollars`,
+    /// highest spend first.
+    pub async fn list_by_spend(
+        &self,
+        subject_kind: &str,
+        window_seconds: i32,
+        window_start: DateTime<Utc>,
+        min_cost_microdollars: i64,
+    ) -> Result<Vec<QuotaBucketRow>, RepositoryError> {
+        let rows = sqlx::query_as!(
+            QuotaBucketRow,
+            r#"
+            SELECT subject_id, requests, cost_microdollars, updated_at
+            FROM ai_quota_buckets
+            WHERE subject_kind = $1
+              AND window_seconds = $2
+              AND window_start = $3
+              AND cost_microdollars >= $4
+            ORDER BY cost_microdollars DESC, subject_id
+            "#,
+            subject_kind,
+            window_seconds,
+            window_start,
+            min_cost_microdollars,
+        )
+        .fetch_all(self.write_pool.as_ref())
+        .await?;
+        Ok(rows)
+    }
 }
@@ -23,6 +23,8 @@ pub struct UpdateCompletionParams {
     pub cache_hit: bool,
     pub cache_read_tokens: i32,
     pub cache_creation_tokens: i32,
+    pub status: RequestStatus,
+    pub error_message: Option<String>,
 }
 
 impl AiRequestRepository {
@@ -38,9 +40,9 @@ impl AiRequestRepository {
             SET tokens_used = $1, input_tokens = $2, output_tokens = $3,
                 cost_microdollars = $4, latency_ms = $5,
                 cache_hit = $6, cache_read_tokens = $7, cache_creation_tokens = $8,
-                status = $9,
+                status = $9, error_message = $10,
                 completed_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
-            WHERE id = $10
+            WHERE id = $11
             RETURNING id as "id!: AiRequestId",
                       request_id as "request_id!: AiRequestId",
                       user_id as "user_id!: UserId",
@@ -63,7 +65,8 @@ impl AiRequestRepository {
             params.cache_hit,
             params.cache_read_tokens,
             params.cache_creation_tokens,
-            RequestStatus::Completed.as_str(),
+            params.status.as_str(),
+            params.error_message,
             params.id.as_str()
         )
         .fetch_one(self.write_pool())
@@ -156,6 +159,35 @@ impl AiRequestRepository {
         Ok(())
     }
 
+    #[must_use = "this returns a Result that should not be ignored"]
+    pub async fn mark_response_cache_hit(&self, id: &AiRequestId) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            r#"UPDATE ai_requests SET response_cache_hit = TRUE, updated_at = CURRENT_TIMESTAMP WHERE id = $1"#,
+            id.as_str()
+        )
+        .execute(self.write_pool())
+        .await?;
+        Ok(())
+    }
+
+    #[must_use = "this returns a Result that should not be ignored"]
+    pub async fn update_route_attempts(
+        &self,
+        id: &AiRequestId,
+        provider: &str,
+        attempts: &serde_json::Value,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            r#"UPDATE ai_requests SET provider = $1, route_attempts = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $3"#,
+            provider,
+            attempts,
+            id.as_str()
+        )
+        .execute(self.write_pool())
+        .await?;
+        Ok(())
+    }
+
     #[must_use = "this returns a Result that should not be ignored"]
     pub async fn insert(&self, record: &AiRequestRecord) -> Result<AiRequestId, RepositoryError> {
         self.insert_with_id(&AiRequestId::generate(), record).await
@@ -175,7 +207,10 @@ impl AiRequestRepository {
 
         let use_completed_at = matches!(
             record.status,
-            RequestStatus::Completed | RequestStatus::Failed | RequestStatus::Rejected
+            RequestStatus::Completed
+                | RequestStatus::Failed
+                | RequestStatus::Rejected
+                | RequestStatus::Truncated
         );
 
         let (actor_kind, actor_id) = record.actor.audit_columns();
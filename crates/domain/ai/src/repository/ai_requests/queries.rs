@@ -58,7 +58,7 @@ impl AiRequestRepository {
                 COALESCE(SUM(cost_microdollars), 0)::float8 / 1000000.0 as "total_cost!",
                 AVG(latency_ms)::bigint as "avg_latency_ms"
             FROM ai_requests
-            WHERE created_at > $1 AND status = 'completed'
+            WHERE created_at > $1 AND status IN ('completed', 'truncated')
               AND provider IS NOT NULL AND model IS NOT NULL
             GROUP BY provider, model
             ORDER BY COUNT(*) DESC
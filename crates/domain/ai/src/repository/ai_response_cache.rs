@@ -0,0 +1,97 @@
+//! Repository for `ai_response_cache` entries.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use crate::error::RepositoryError;
+use serde_json::Value;
+use sqlx::PgPool;
+use std::sync::Arc;
+use systemprompt_database::DbPool;
+use systemprompt_identifiers::AiRequestId;
+
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct AiResponseCacheRepository {
+    write_pool: Arc<PgPool>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PutResponseCacheEntry<'a> {
+    pub cache_key: &'a str,
+    pub scope: &'a str,
+    pub model: &'a str,
+    pub response: &'a Value,
+    pub byte_len: i32,
+    pub source_request_id: &'a AiRequestId,
+    pub ttl_seconds: i32,
+}
+
+impl AiResponseCacheRepository {
+    pub fn new(db: &DbPool) -> Result<Self, RepositoryError> {
+        let write_pool = db
+            .write_pool_arc()
+            .map_err(|e| RepositoryError::PoolInitialization(e.to_string()))?;
+        Ok(Self { write_pool })
+    }
+
+    /// Returns the live entry for `cache_key` and counts the hit. Reads go to
+    /// the write pool so an entry stored a moment ago by another replica is
+    /// not missed on replication lag.
+    pub async fn hit(&self, cache_key: &str) -> Result<Option<Value>, RepositoryError> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE ai_response_cache
+            SET hit_count = hit_count + 1
+            WHERE cache_key = $1 AND expires_at > CURRENT_TIMESTAMP
+            RETURNING response
+            "#,
+            cache_key
+        )
+        .fetch_optional(self.write_pool.as_ref())
+        .await?;
+        Ok(row.map(|r| r.response))
+    }
+
+    pub async fn put(&self, entry: PutResponseCacheEntry<'_>) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO ai_response_cache (
+                cache_key, scope, model, response, byte_len, source_request_id,
+                hit_count, created_at, expires_at
+            )
+            VALUES (
+                $1, $2, $3, $4, $5, $6, 0, CURRENT_TIMESTAMP,
+                CURRENT_TIMESTAMP + make_interval(secs => $7::INTEGER)
+            )
+            ON CONFLICT (cache_key) DO UPDATE
+            SET scope = EXCLUDED.scope,
+                model = EXCLUDED.model,
+                response = EXCLUDED.response,
+                byte_len = EXCLUDED.byte_len,
+                source_request_id = EXCLUDED.source_request_id,
+                hit_count = 0,
+                created_at = EXCLUDED.created_at,
+                expires_at = EXCLUDED.expires_at
+            "#,
+            entry.cache_key,
+            entry.scope,
+            entry.model,
+            entry.response,
+            entry.byte_len,
+            entry.source_request_id.as_str(),
+            entry.ttl_seconds
+        )
+        .execute(self.write_pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_expired(&self) -> Result<u64, RepositoryError> {
+        let result =
+            sqlx::query!("DELETE FROM ai_response_cache WHERE expires_at <= CURRENT_TIMESTAMP")
+                .execute(self.write_pool.as_ref())
+                .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -23,6 +23,7 @@ pub struct InsertSafetyFinding<'a> {
     pub category: &'a str,
     pub scanner: &'a str,
     pub excerpt: Option<&'a str>,
+    pub redaction_count: i32,
 }
 
 impl AiSafetyFindingRepository {
@@ -41,9 +42,10 @@ impl AiSafetyFindingRepository {
         sqlx::query!(
             r#"
             INSERT INTO ai_safety_findings (
-                id, ai_request_id, phase, severity, category, scanner, excerpt, created_at
+                id, ai_request_id, phase, severity, category, scanner, excerpt,
+                redaction_count, created_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, CURRENT_TIMESTAMP)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CURRENT_TIMESTAMP)
             "#,
             id.as_str(),
             params.ai_request_id.as_str(),
@@ -51,7 +53,8 @@ impl AiSafetyFindingRepository {
             params.severity,
             params.category,
             params.scanner,
-            params.excerpt
+            params.excerpt,
+            params.redaction_count
         )
         .execute(self.write_pool.as_ref())
         .await?;
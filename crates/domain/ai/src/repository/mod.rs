@@ -3,7 +3,7 @@
 //! Every type here owns `SQLx` queries against the AI domain tables
 //! (`ai_requests`, `ai_request_messages`, `ai_tool_calls`,
 //! `ai_request_payloads`, `ai_quota_buckets`, `ai_safety_findings`,
-//! `ai_gateway_policies`).
+//! `ai_gateway_policies`, `ai_response_cache`).
 //!
 //! All repositories return [`crate::error::RepositoryError`]. Services are
 //! the only callers — repositories never execute application logic.
@@ -15,6 +15,7 @@ pub mod ai_gateway_policies;
 pub mod ai_quota_buckets;
 pub mod ai_request_payloads;
 pub mod ai_requests;
+pub mod ai_response_cache;
 pub mod ai_safety_findings;
 
 use crate::error::RepositoryError;
@@ -22,10 +23,11 @@ use systemprompt_database::DbPool;
 
 pub use ai_gateway_policies::{AiGatewayPolicyRepository, GatewayPolicyRow};
 pub use ai_quota_buckets::{
-    AiQuotaBucketRepository, IncrementParams, QuotaBucketDelta, QuotaBucketState,
+    AiQuotaBucketRepository, IncrementParams, QuotaBucketDelta, QuotaBucketRow, QuotaBucketState,
 };
 pub use ai_request_payloads::{AiRequestPayload, AiRequestPayloadRepository, UpsertPayloadParams};
 pub use ai_requests::{AiRequestRepository, InsertToolCallParams};
+pub use ai_response_cache::{AiResponseCacheRepository, PutResponseCacheEntry};
 pub use ai_safety_findings::{AiSafetyFindingRepository, InsertSafetyFinding};
 
 #[derive(Debug, Clone)]
@@ -35,6 +37,7 @@ pub struct AiRepositories {
     pub gateway_policies: AiGatewayPolicyRepository,
     pub quota_buckets: AiQuotaBucketRepository,
     pub safety_findings: AiSafetyFindingRepository,
+    pub response_cache: AiResponseCacheRepository,
 }
 
 impl AiRepositories {
@@ -45,6 +48,7 @@ impl AiRepositories {
             gateway_policies: AiGatewayPolicyRepository::new(db)?,
             quota_buckets: AiQuotaBucketRepository::new(db)?,
             safety_findings: AiSafetyFindingRepository::new(db)?,
+            response_cache: AiResponseCacheRepository::new(db)?,
         })
     }
 }
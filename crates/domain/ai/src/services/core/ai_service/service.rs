@@ -144,6 +144,9 @@ impl AiService {
             let secret_name = entry.api_key_secret.as_str();
             let api_key = secrets.get(secret_name).map_or_else(
                 || {
+                    if entry.is_local() {
+                        return String::new();
+                    }
                     tracing::warn!(
                         provider = %name,
                         secret = %secret_name,
@@ -171,6 +174,7 @@ impl AiService {
         api_key: String,
         db_pool: &DbPool,
     ) -> Result<Arc<dyn AiProvider>> {
+        let models: Vec<_> = entry.all_models().cloned().collect();
         let params = ProviderClientParams {
             name: entry.name.as_str(),
             wire: entry.wire,
@@ -178,7 +182,7 @@ impl AiService {
             api_key,
             google_search_enabled: policy.google_search_enabled,
             resilience: &policy.resilience,
-            models: &entry.models,
+            models: &models,
             default_model: (!policy.default_model.is_empty())
                 .then_some(policy.default_model.as_str()),
         };
@@ -89,6 +89,10 @@ pub(super) fn build_record(params: &BuildRecordParams<'_>) -> AiRequestRecord {
             builder.failed(error_text)
         },
         RequestStatus::Rejected => builder.rejected(),
+        RequestStatus::Truncated => {
+            let error_text = params.error_message.unwrap_or("Response truncated");
+            builder.truncated(error_text)
+        },
         RequestStatus::Pending => builder,
     };
 
@@ -14,9 +14,15 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::spec::GatewayPolicySpec;
+use super::safety::PatternScanner;
+use super::spec::{
+    BudgetAlertTarget, GatewayPolicySpec, PatternConfig, QuotaWindow, RateLimit, SafetyConfig,
+};
 use crate::error::RepositoryError;
 
+const MIN_STREAM_WINDOW_BYTES: u32 = 256;
+const MAX_STREAM_WINDOW_BYTES: u32 = 64 * 1024;
+
 const fn default_enabled() -> bool {
     true
 }
@@ -65,7 +71,216 @@ impl GatewayPolicyConfig {
                         .to_owned(),
                 });
             }
+            if safety.scanners.iter().any(|s| s == "pattern") {
+                validate_pattern(idx, &safety.pattern)?;
+            }
+            validate_redaction(idx, safety)?;
+            validate_stream(idx, safety)?;
+            for (limit_idx, limit) in policy.spec.rate_limits.iter().enumerate() {
+                validate_rate_limit(idx, limit_idx, limit)?;
+            }
+            for (window_idx, window) in policy.spec.quota_windows.iter().enumerate() {
+                validate_alert_thresholds(idx, window_idx, window)?;
+            }
+            for (alert_idx, alert) in policy.spec.alerts.iter().enumerate() {
+                validate_alert_target(idx, alert_idx, alert)?;
+            }
+            let cache = &policy.spec.cache;
+            if cache.enabled && (cache.ttl_seconds == 0 || cache.max_entry_bytes == 0) {
+                return Err(RepositoryError::InvalidData {
+                    field: format!("policies[{idx}].spec.cache"),
+                    reason: "response cache is enabled but ttl_seconds or max_entry_bytes is 0 \
+                             — nothing could be stored"
+                        .to_owned(),
+                });
+            }
         }
         Ok(())
     }
 }
+
+fn validate_pattern(idx: usize, pattern: &PatternConfig) -> Result<(), RepositoryError> {
+    if pattern.effective_detectors().is_empty() && pattern.rules.is_empty() {
+        return Err(RepositoryError::InvalidData {
+            field: format!("policies[{idx}].spec.safety.pattern"),
+            reason: "pattern scanner is enabled but selects no detectors and has no rules — \
+                     list detectors/rules or remove the scanner"
+                .to_owned(),
+        });
+    }
+    for (rule_idx, rule) in pattern.rules.iter().enumerate() {
+        let field = format!("policies[{idx}].spec.safety.pattern.rules[{rule_idx}]");
+        if rule.category.trim().is_empty() {
+            return Err(RepositoryError::InvalidData {
+                field,
+                reason: "pattern rule category must not be empty".to_owned(),
+            });
+        }
+        if let Err(e) = PatternScanner::compile_rule(rule) {
+            return Err(RepositoryError::InvalidData {
+                field,
+                reason: format!("pattern rule regex does not compile: {e}"),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn validate_rate_limit(
+    idx: usize,
+    limit_idx: usize,
+    limit: &RateLimit,
+) -> Result<(), RepositoryError> {
+    let field = format!("policies[{idx}].spec.rate_limits[{limit_idx}]");
+    if limit.subject.trim().is_empty() {
+        return Err(RepositoryError::InvalidData {
+            field,
+            reason: "rate limit subject must not be empty".to_owned(),
+        });
+    }
+    let limits = [
+        limit.max_concurrent,
+        limit.requests_per_minute,
+        limit.tokens_per_minute,
+    ];
+    if limits.iter().all(Option::is_none) {
+        return Err(RepositoryError::InvalidData {
+            field,
+            reason: "rate limit sets none of max_concurrent, requests_per_minute or \
+                     tokens_per_minute"
+                .to_owned(),
+        });
+    }
+    if limits.contains(&Some(0)) {
+        return Err(RepositoryError::InvalidData {
+            field,
+            reason: "rate limits must be positive — a zero limit would refuse every request"
+                .to_owned(),
+        });
+    }
+    Ok(())
+}
+
+fn validate_alert_thresholds(
+    idx: usize,
+    window_idx: usize,
+    window: &QuotaWindow,
+) -> Result<(), RepositoryError> {
+    if window.alert_thresholds.is_empty() {
+        return Ok(());
+    }
+    let field = format!("policies[{idx}].spec.quota_windows[{window_idx}].alert_thresholds");
+    if window.max_cost_microdollars.is_none() {
+        return Err(RepositoryError::InvalidData {
+            field,
+            reason: "alert thresholds are percentages of max_cost_microdollars, which this \
+                     window does not set"
+                .to_owned(),
+        });
+    }
+    if let Some(bad) = window
+        .alert_thresholds
+        .iter()
+        .find(|pct| !(1..=100).contains(*pct))
+    {
+        return Err(RepositoryError::InvalidData {
+            field,
+            reason: format!("alert threshold {bad} is outside 1..=100"),
+        });
+    }
+    Ok(())
+}
+
+fn validate_alert_target(
+    idx: usize,
+    alert_idx: usize,
+    alert: &BudgetAlertTarget,
+) -> Result<(), RepositoryError> {
+    let required: &[(&str, &str)] = match alert {
+        BudgetAlertTarget::Webhook { url, .. } => &[("url", url)],
+        BudgetAlertTarget::Slack { app, channel } => &[("app", app), ("channel", channel)],
+        BudgetAlertTarget::Teams {
+            app,
+            service_url,
+            conversation_id,
+        } => &[
+            ("app", app),
+            ("service_url", service_url),
+            ("conversation_id", conversation_id),
+        ],
+    };
+    if let Some((name, _)) = required.iter().find(|(_, value)| value.trim().is_empty()) {
+        return Err(RepositoryError::InvalidData {
+            field: format!("policies[{idx}].spec.alerts[{alert_idx}].{name}"),
+            reason: "budget alert target field must not be empty".to_owned(),
+        });
+    }
+    Ok(())
+}
+
+fn validate_stream(idx: usize, safety: &SafetyConfig) -> Result<(), RepositoryError> {
+    let stream = safety.stream;
+    if !stream.enforce {
+        return Ok(());
+    }
+    let field = format!("policies[{idx}].spec.safety.stream");
+    if safety.block_response_categories.is_empty() {
+        return Err(RepositoryError::InvalidData {
+            field,
+            reason: "stream enforcement is on but block_response_categories is empty — nothing \
+                     could cut a stream off"
+                .to_owned(),
+        });
+    }
+    if !(MIN_STREAM_WINDOW_BYTES..=MAX_STREAM_WINDOW_BYTES).contains(&stream.window_bytes) {
+        return Err(RepositoryError::InvalidData {
+            field,
+            reason: format!(
+                "window_bytes must be between {MIN_STREAM_WINDOW_BYTES} and \
+                 {MAX_STREAM_WINDOW_BYTES}"
+            ),
+        });
+    }
+    Ok(())
+}
+
+fn validate_redaction(idx: usize, safety: &SafetyConfig) -> Result<(), RepositoryError> {
+    let redaction = &safety.redaction;
+    let field = format!("policies[{idx}].spec.safety.redaction");
+    let detectors = safety.pattern.effective_detectors();
+    let located = |category: &String| {
+        detectors.iter().any(|d| d.category() == category)
+            || safety.pattern.rules.iter().any(|r| &r.category == category)
+    };
+    let mut listed = redaction
+        .categories
+        .iter()
+        .chain(&redaction.response_categories);
+    if let Some(category) = listed.find(|c| !located(c)) {
+        return Err(RepositoryError::InvalidData {
+            field,
+            reason: format!(
+                "redaction category '{category}' is not produced by the pattern detectors or \
+                 rules, so its spans cannot be located"
+            ),
+        });
+    }
+    let request_overlap = redaction
+        .categories
+        .iter()
+        .find(|c| safety.block_categories.contains(c));
+    let response_overlap = redaction
+        .response_categories
+        .iter()
+        .find(|c| safety.block_response_categories.contains(c));
+    if let Some(category) = request_overlap.or(response_overlap) {
+        return Err(RepositoryError::InvalidData {
+            field,
+            reason: format!(
+                "category '{category}' is both redacted and blocked — redaction runs first, so \
+                 the block could never fire"
+            ),
+        });
+    }
+    Ok(())
+}
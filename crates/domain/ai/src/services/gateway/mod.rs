@@ -30,9 +30,11 @@ pub use route_selector::{
 };
 pub use safety::{
     Finding, HeuristicScanner, NullScanner, PHASE_REQUEST, PHASE_REQUEST_HISTORY, PHASE_RESPONSE,
-    SafetyScanner, SafetyScannerRegistration, Severity,
+    PatternMatch, PatternScanner, RedactionTally, RedactionVault, Redactor, ResponseRewriter,
+    STREAM_HOLDBACK, SafetyScanner, SafetyScannerRegistration, Severity,
 };
 pub use spec::{
-    GatewayPolicySpec, HeuristicConfig, QuotaWindow, SafetyConfig, SafetyHistoryMode,
-    USER_QUOTA_SUBJECT,
+    BudgetAlertTarget, GatewayPolicySpec, HeuristicConfig, PatternConfig, PatternDetector,
+    PatternRule, QuotaWindow, RateLimit, RedactionConfig, ResponseCacheConfig, ResponseCacheScope,
+    SafetyConfig, SafetyHistoryMode, StreamSafetyConfig, USER_QUOTA_SUBJECT,
 };
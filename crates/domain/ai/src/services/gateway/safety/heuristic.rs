@@ -8,6 +8,7 @@ use systemprompt_models::wire::canonical::{CanonicalRequest, CanonicalResponse,
 
 use super::{
     Finding, PHASE_REQUEST, PHASE_REQUEST_HISTORY, PHASE_RESPONSE, SafetyScanner, Severity,
+    history_units,
 };
 use crate::services::gateway::spec::HeuristicConfig;
 
@@ -94,19 +95,12 @@ impl SafetyScanner for HeuristicScanner {
         }
         findings
     }
-}
 
-fn history_units(req: &CanonicalRequest) -> Vec<String> {
-    let mut units = req.message_units();
-    if req.system.is_some() && !units.is_empty() {
-        units.remove(0);
-    }
-    if let Some(newest) = req.latest_message_text(Role::User)
-        && units.last() == Some(&newest)
-    {
-        units.pop();
+    fn scan_response_window(&self, window: &str) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        scan_text(&self.phrases, PHASE_RESPONSE, window, &mut findings);
+        findings
     }
-    units
 }
 
 fn scan_text(phrases: &[String], phase: &'static str, text: &str, out: &mut Vec<Finding>) {
@@ -3,8 +3,10 @@
 //! The [`SafetyScanner`] trait inspects a canonical request or final response
 //! and returns [`Finding`]s graded by [`Severity`]. Scanners are selected by
 //! policy (`SafetyConfig::scanners`) and resolved against a registry: the
-//! built-in [`HeuristicScanner`] applies pattern-based checks, [`NullScanner`]
-//! is the no-op used when scanning is disabled.
+//! built-in [`HeuristicScanner`] matches a jailbreak phrase list,
+//! [`PatternScanner`] runs regex rules and PII/secret detectors, and
+//! [`NullScanner`] is the no-op used when scanning is disabled. Categories a
+//! policy redacts are rewritten by a [`Redactor`] instead of denied.
 //!
 //! Extensions contribute additional scanners the same way they contribute
 //! gateway upstreams or marketplace filters — by submitting a
@@ -17,13 +19,17 @@
 
 mod heuristic;
 mod null;
+mod pattern;
+mod redact;
 
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use systemprompt_models::wire::canonical::{CanonicalRequest, CanonicalResponse};
+use serde::{Deserialize, Serialize};
+use systemprompt_models::wire::canonical::{CanonicalRequest, CanonicalResponse, Role};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Low,
     Medium,
@@ -69,6 +75,15 @@ pub trait SafetyScanner: Send + Sync {
     }
 
     async fn scan_response_final(&self, response: &CanonicalResponse) -> Vec<Finding>;
+
+    /// Scans the trailing window of a reply that is still streaming. Only
+    /// scanners that implement this can cut a live stream off; the default
+    /// finds nothing.
+    fn scan_response_window(&self, window: &str) -> Vec<Finding> {
+        // Why: unused-arg suppression in a default trait method body.
+        let _ = window;
+        Vec::new()
+    }
 }
 
 /// Compile-time registration of a [`SafetyScanner`] implementation.
@@ -96,5 +111,22 @@ macro_rules! register_safety_scanner {
     };
 }
 
+/// Earlier turns of the conversation: every message unit except the system
+/// prompt and the newest user turn, which the request phase already covers.
+fn history_units(req: &CanonicalRequest) -> Vec<String> {
+    let mut units = req.message_units();
+    if req.system.is_some() && !units.is_empty() {
+        units.remove(0);
+    }
+    if let Some(newest) = req.latest_message_text(Role::User)
+        && units.last() == Some(&newest)
+    {
+        units.pop();
+    }
+    units
+}
+
 pub use heuristic::{HeuristicScanner, effective_phrases};
 pub use null::NullScanner;
+pub use pattern::{PatternMatch, PatternScanner};
+pub use redact::{RedactionTally, RedactionVault, Redactor, ResponseRewriter, STREAM_HOLDBACK};
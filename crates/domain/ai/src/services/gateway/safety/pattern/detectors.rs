@@ -0,0 +1,223 @@
+//! Built-in PII and cloud-credential detectors for the `pattern` scanner.
+//!
+//! A regex finds candidates; where the format carries a check digit the
+//! candidate must also pass it — Luhn for card numbers, ISO 7064 mod-97 for
+//! IBANs — and US SSNs must sit outside the ranges the SSA never issues. That
+//! keeps an order number or a timestamp from reading as a card.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::super::Severity;
+use crate::services::gateway::spec::PatternDetector;
+
+#[expect(
+    clippy::expect_used,
+    reason = "compile-time-constant regex; failure is a programmer bug, not runtime input"
+)]
+fn compile(expr: &str) -> Regex {
+    Regex::new(expr).expect("built-in pattern detector regex is a compile-time constant")
+}
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    compile(r"\b[A-Za-z0-9._%+-]{2,}@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}\b")
+});
+
+// Why: a phone number needs either a `+` country code or separators between
+// digit groups — a bare digit run is as likely an id or an amount.
+static PHONE: LazyLock<Regex> = LazyLock::new(|| {
+    compile(
+        r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{1,4}\)[ .-]?)?\d{2,4}[ .-]\d{3,4}(?:[ .-]\d{3,4})?\b|\+\d{10,14}\b",
+    )
+});
+
+static CARD: LazyLock<Regex> = LazyLock::new(|| compile(r"\b\d(?:[ -]?\d){12,18}\b"));
+
+static IBAN: LazyLock<Regex> = LazyLock::new(|| compile(r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]){11,30}\b"));
+
+
+static US_SSN: LazyLock<Regex> = LazyLock::new(|| compile(r"\b(\d{3})-(\d{2})-(\d{4})\b"));
+
+static UK_NINO: LazyLock<Regex> = LazyLock::new(|| {
+    compile(r"\b[A-CEGHJ-PR-TW-Z][A-CEGHJ-NPR-TW-Z] ?\d{2} ?\d{2} ?\d{2} ?[A-D]\b")
+});
+
+static CLOUD_CREDENTIALS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        // AWS access key id.
+        r"\b(?:AKIA|ASIA|ABIA|ACCA)[0-9A-Z]{16}\b",
+        // AWS secret access key assigned to its well-known name.
+        r#"(?i)aws_?secret_?access_?key["']?\s*[:=]\s*["']?[A-Za-z0-9/+=]{40}\b"#,
+        // Google Cloud API key.
+        r"\bAIza[0-9A-Za-z_-]{35}\b",
+        // Google Cloud service-account key file.
+        r#""private_key"\s*:\s*"-----BEGIN (?:RSA )?PRIVATE KEY-----"#,
+        // Azure storage account key inside a connection string.
+        r"(?i)AccountKey=[A-Za-z0-9+/]{86}==",
+        // Azure AD client secret.
+        r"\b[A-Za-z0-9_~.-]{3}\dQ~[A-Za-z0-9_~.-]{31,34}\b",
+    ]
+    .into_iter()
+    .map(compile)
+    .collect()
+});
+
+pub(super) const fn severity(detector: PatternDetector) -> Severity {
+    match detector {
+        PatternDetector::Email | PatternDetector::Phone => Severity::Low,
+        PatternDetector::CreditCard
+        | PatternDetector::Iban
+        | PatternDetector::NationalId
+        | PatternDetector::CloudCredential => Severity::High,
+    }
+}
+
+/// Byte ranges of every confirmed match of `detector` in `text`.
+pub(super) fn find(detector: PatternDetector, text: &str) -> Vec<Range<usize>> {
+    match detector {
+        PatternDetector::Email => EMAIL.find_iter(text).map(|m| m.range()).collect(),
+        PatternDetector::Phone => PHONE
+            .find_iter(text)
+            .filter(|m| {
+                (10..=15).contains(&digits(m.as_str()).len())
+                    && !continues_digit_run(&text[..m.start()], &text[m.end()..])
+            })
+            .map(|m| m.range())
+            .collect(),
+        PatternDetector::CreditCard => CARD
+            .find_iter(text)
+            .filter(|m| {
+                let d = digits(m.as_str());
+                (13..=19).contains(&d.len()) && luhn(&d)
+            })
+            .map(|m| m.range())
+            .collect(),
+        PatternDetector::Iban => IBAN
+            .find_iter(text)
+            .filter_map(|m| iban_len(m.as_str()).map(|len| m.start()..m.start() + len))
+            .collect(),
+        PatternDetector::NationalId => {
+            let ssn = US_SSN.captures_iter(text).filter_map(|c| {
+                let whole = c.get(0)?;
+                ssn_issuable(
+                    c.get(1).map_or("", |m| m.as_str()),
+                    c.get(2).map_or("", |m| m.as_str()),
+                    c.get(3).map_or("", |m| m.as_str()),
+                )
+                .then(|| whole.range())
+            });
+            ssn.chain(UK_NINO.find_iter(text).map(|m| m.range()))
+                .collect()
+        },
+        PatternDetector::CloudCredential => CLOUD_CREDENTIALS
+            .iter()
+            .flat_map(|re| re.find_iter(text).map(|m| m.range()))
+            .collect(),
+    }
+}
+
+fn digits(s: &str) -> Vec<u8> {
+    s.bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| b - b'0')
+        .collect()
+}
+
+// Why: a match that a further digit group runs on from is a slice of a longer
+// number — a card or an account — not a phone number.
+fn continues_digit_run(before: &str, after: &str) -> bool {
+    let trailing = after.trim_start_matches([' ', '-', '.']);
+    let leading = before.trim_end_matches([' ', '-', '.']);
+    trailing.starts_with(|c: char| c.is_ascii_digit())
+        || leading.ends_with(|c: char| c.is_ascii_digit())
+}
+
+fn luhn(digits: &[u8]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            let d = u32::from(d);
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+// Why: ISO 13616 — move the country code and check digits to the end, map
+// letters to 10..35, and the whole number must be 1 mod 97. Returns the byte
+// length of the valid IBAN within `candidate`, spaces included.
+fn iban_len(candidate: &str) -> Option<usize> {
+    let len = iban_length(candidate.as_bytes().get(..2)?)?;
+    let mut compact = Vec::with_capacity(len);
+    let mut byte_len = 0;
+    for b in candidate.bytes() {
+        if compact.len() == len {
+            break;
+        }
+        byte_len += 1;
+        if b != b' ' {
+            compact.push(b);
+        }
+    }
+    if compact.len() < len {
+        return None;
+    }
+    let (head, tail) = compact.split_at(4);
+    let mut remainder = 0u32;
+    for b in tail.iter().chain(head) {
+        let value = match b {
+            b'0'..=b'9' => u32::from(b - b'0'),
+            b'A'..=b'Z' => u32::from(b - b'A') + 10,
+            _ => return None,
+        };
+        remainder = if value >= 10 {
+            (remainder * 100 + value) % 97
+        } else {
+            (remainder * 10 + value) % 97
+        };
+    }
+    (remainder == 1).then_some(byte_len)
+}
+
+// Why: the national length is what stops a greedy match from swallowing the
+// next uppercase word — the candidate is cut to it before the checksum.
+const fn iban_length(country: &[u8]) -> Option<usize> {
+    match country {
+        b"NO" => Some(15),
+        b"BE" => Some(16),
+        b"DK" | b"FI" | b"FO" | b"GL" | b"NL" => Some(18),
+        b"MK" | b"SI" => Some(19),
+        b"AT" | b"BA" | b"EE" | b"KZ" | b"LT" | b"LU" | b"XK" => Some(20),
+        b"CH" | b"HR" | b"LI" | b"LV" => Some(21),
+        b"BG" | b"BH" | b"CR" | b"DE" | b"GB" | b"GE" | b"IE" | b"ME" | b"RS" | b"VA" => Some(22),
+        b"AE" | b"GI" | b"IL" | b"IQ" | b"TL" => Some(23),
+        b"AD" | b"CZ" | b"ES" | b"MD" | b"PK" | b"RO" | b"SA" | b"SE" | b"SK" | b"TN" | b"VG" => {
+            Some(24)
+        },
+        b"PT" | b"ST" => Some(25),
+        b"IS" | b"TR" => Some(26),
+        b"FR" | b"GR" | b"IT" | b"MC" | b"MR" | b"SM" => Some(27),
+        b"AL" | b"AZ" | b"BY" | b"CY" | b"DO" | b"GT" | b"HU" | b"LB" | b"PL" | b"SV" => Some(28),
+        b"BR" | b"EG" | b"PS" | b"QA" | b"UA" => Some(29),
+        b"JO" | b"KW" | b"MU" => Some(30),
+        b"MT" | b"SC" => Some(31),
+        b"LC" => Some(32),
+        _ => None,
+    }
+}
+
+fn ssn_issuable(area: &str, group: &str, serial: &str) -> bool {
+    area != "000" && area != "666" && !area.starts_with('9') && group != "00" && serial != "0000"
+}
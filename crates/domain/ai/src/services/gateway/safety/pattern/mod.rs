@@ -0,0 +1,224 @@
+//! Regex and PII/secret-pattern safety scanner.
+//!
+//! Runs the built-in detectors a policy selects (`SafetyConfig::pattern`)
+//! plus any operator-supplied regex rules over the same units the heuristic
+//! scanner reads. Detector findings carry no excerpt — the match is the
+//! personal data or credential itself, and findings are persisted. Rule
+//! findings keep one, since a rule usually targets phrasing, not data.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+mod detectors;
+
+use std::ops::Range;
+
+use async_trait::async_trait;
+use regex::{Regex, RegexBuilder};
+use systemprompt_models::wire::canonical::{CanonicalRequest, CanonicalResponse, Role};
+
+use super::{
+    Finding, PHASE_REQUEST, PHASE_REQUEST_HISTORY, PHASE_RESPONSE, SafetyScanner, Severity,
+    history_units,
+};
+use crate::services::gateway::spec::{PatternConfig, PatternDetector, PatternRule};
+
+const EXCERPT_CAP: usize = 240;
+
+// Why: rules come from policy files, so bound what one may cost to compile
+// and hold in memory.
+const RULE_SIZE_LIMIT: usize = 1 << 20;
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    category: String,
+    severity: Severity,
+    regex: Regex,
+}
+
+/// A located match: the byte range in the scanned text and what it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternMatch {
+    pub range: Range<usize>,
+    pub category: String,
+    pub severity: Severity,
+}
+
+#[derive(Debug, Clone)]
+pub struct PatternScanner {
+    detectors: Vec<PatternDetector>,
+    rules: Vec<CompiledRule>,
+}
+
+impl Default for PatternScanner {
+    fn default() -> Self {
+        Self {
+            detectors: PatternDetector::ALL.to_vec(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl PatternScanner {
+    pub fn new(config: &PatternConfig) -> Result<Self, regex::Error> {
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| {
+                Ok(CompiledRule {
+                    category: rule.category.clone(),
+                    severity: rule.severity,
+                    regex: Self::compile_rule(rule)?,
+                })
+            })
+            .collect::<Result<Vec<_>, regex::Error>>()?;
+        Ok(Self {
+            detectors: config.effective_detectors(),
+            rules,
+        })
+    }
+
+    pub fn compile_rule(rule: &PatternRule) -> Result<Regex, regex::Error> {
+        RegexBuilder::new(&rule.pattern)
+            .case_insensitive(rule.case_insensitive)
+            .size_limit(RULE_SIZE_LIMIT)
+            .build()
+    }
+
+    /// A scanner restricted to the detectors and rules whose category is in
+    /// `categories` — the redaction engine's view of a policy's config.
+    pub fn for_categories(
+        config: &PatternConfig,
+        categories: &[String],
+    ) -> Result<Self, regex::Error> {
+        let mut scanner = Self::new(config)?;
+        scanner
+            .detectors
+            .retain(|d| categories.iter().any(|c| c == d.category()));
+        scanner.rules.retain(|r| categories.contains(&r.category));
+        Ok(scanner)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.detectors.is_empty() && self.rules.is_empty()
+    }
+
+    /// Every located match in `text`, ordered by start, overlaps dropped in
+    /// favour of the earlier (then longer) span.
+    pub fn find_matches(&self, text: &str) -> Vec<PatternMatch> {
+        let mut matches: Vec<PatternMatch> = Vec::new();
+        for &detector in &self.detectors {
+            matches.extend(
+                detectors::find(detector, text)
+                    .into_iter()
+                    .map(|range| PatternMatch {
+                        range,
+                        category: detector.category().to_owned(),
+                        severity: detectors::severity(detector),
+                    }),
+            );
+        }
+        for rule in &self.rules {
+            matches.extend(rule.regex.find_iter(text).map(|m| PatternMatch {
+                range: m.range(),
+                category: rule.category.clone(),
+                severity: rule.severity,
+            }));
+        }
+        matches.sort_by(|a, b| {
+            a.range
+                .start
+                .cmp(&b.range.start)
+                .then(b.range.end.cmp(&a.range.end))
+        });
+        let mut end = 0;
+        matches.retain(|m| {
+            let keep = m.range.start >= end && !m.range.is_empty();
+            if keep {
+                end = m.range.end;
+            }
+            keep
+        });
+        matches
+    }
+
+    fn scan_text(&self, phase: &'static str, text: &str, out: &mut Vec<Finding>) {
+        for &detector in &self.detectors {
+            if !detectors::find(detector, text).is_empty() {
+                out.push(Finding {
+                    phase,
+                    severity: detectors::severity(detector),
+                    category: detector.category().to_owned(),
+                    excerpt: None,
+                    scanner: "pattern",
+                });
+            }
+        }
+        for rule in &self.rules {
+            if let Some(m) = rule.regex.find(text) {
+                out.push(Finding {
+                    phase,
+                    severity: rule.severity,
+                    category: rule.category.clone(),
+                    excerpt: Some(excerpt(text, m.start(), m.end())),
+                    scanner: "pattern",
+                });
+            }
+        }
+    }
+}
+
+fn excerpt(text: &str, start: usize, end: usize) -> String {
+    let mut from = start.saturating_sub(40);
+    while !text.is_char_boundary(from) {
+        from -= 1;
+    }
+    let mut to = (end + 80).min(text.len());
+    while !text.is_char_boundary(to) {
+        to += 1;
+    }
+    text[from..to].chars().take(EXCERPT_CAP).collect()
+}
+
+#[async_trait]
+impl SafetyScanner for PatternScanner {
+    fn name(&self) -> &'static str {
+        "pattern"
+    }
+
+    async fn scan_request(&self, req: &CanonicalRequest) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        if let Some(sys) = &req.system {
+            self.scan_text(PHASE_REQUEST, sys, &mut findings);
+        }
+        if let Some(text) = req.latest_message_text(Role::User) {
+            self.scan_text(PHASE_REQUEST, &text, &mut findings);
+        }
+        for leaf in req.forwarded_surface.leaves() {
+            self.scan_text(PHASE_REQUEST, &leaf.value, &mut findings);
+        }
+        findings
+    }
+
+    async fn scan_request_history(&self, req: &CanonicalRequest) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for unit in history_units(req) {
+            self.scan_text(PHASE_REQUEST_HISTORY, &unit, &mut findings);
+        }
+        findings
+    }
+
+    async fn scan_response_final(&self, response: &CanonicalResponse) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for unit in response.content_units() {
+            self.scan_text(PHASE_RESPONSE, &unit, &mut findings);
+        }
+        findings
+    }
+
+    fn scan_response_window(&self, window: &str) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        self.scan_text(PHASE_RESPONSE, window, &mut findings);
+        findings
+    }
+}
@@ -0,0 +1,265 @@
+//! Placeholder redaction of safety findings.
+//!
+//! A policy's `redaction` block turns a finding into a rewrite: each located
+//! span becomes a stable placeholder such as `[PII_EMAIL_1]` — the same
+//! original always maps to the same placeholder within one request — and the
+//! [`RedactionVault`] remembers the mapping so a [`ResponseRewriter`] can put
+//! the caller's originals back into the reply.
+//!
+//! Streamed text is rewritten through a hold-back buffer: the last
+//! [`STREAM_HOLDBACK`] bytes of each block stay buffered so a span or
+//! placeholder split across two deltas is still seen whole.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::{BTreeMap, HashMap};
+
+use systemprompt_models::wire::canonical::{CanonicalContent, CanonicalRequest, CanonicalResponse};
+
+use super::Severity;
+use super::pattern::PatternScanner;
+use crate::services::gateway::spec::PatternConfig;
+
+pub const STREAM_HOLDBACK: usize = 256;
+
+/// How many spans of one category a redaction pass rewrote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionTally {
+    pub category: String,
+    pub severity: Severity,
+    pub count: u32,
+}
+
+/// Placeholder ↔ original mapping for one request, plus per-category counts
+/// not yet drained by [`RedactionVault::take_tallies`].
+#[derive(Debug, Clone, Default)]
+pub struct RedactionVault {
+    by_original: HashMap<String, String>,
+    entries: Vec<(String, String)>,
+    next_index: HashMap<String, u32>,
+    tallies: BTreeMap<String, (Severity, u32)>,
+}
+
+impl RedactionVault {
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn take_tallies(&mut self) -> Vec<RedactionTally> {
+        std::mem::take(&mut self.tallies)
+            .into_iter()
+            .map(|(category, (severity, count))| RedactionTally {
+                category,
+                severity,
+                count,
+            })
+            .collect()
+    }
+
+    fn placeholder_for(&mut self, category: &str, severity: Severity, original: &str) -> String {
+        let tally = self
+            .tallies
+            .entry(category.to_owned())
+            .or_insert((severity, 0));
+        tally.1 += 1;
+        if let Some(existing) = self.by_original.get(original) {
+            return existing.clone();
+        }
+        let n = self.next_index.entry(category.to_owned()).or_insert(0);
+        *n += 1;
+        let placeholder = format!("[{}_{n}]", category.to_ascii_uppercase());
+        self.by_original
+            .insert(original.to_owned(), placeholder.clone());
+        self.entries
+            .push((placeholder.clone(), original.to_owned()));
+        placeholder
+    }
+}
+
+/// Locates and rewrites the spans of a policy's redaction categories.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    scanner: PatternScanner,
+}
+
+impl Redactor {
+    /// `Ok(None)` when no detector or rule produces any of `categories`.
+    pub fn new(
+        config: &PatternConfig,
+        categories: &[String],
+    ) -> Result<Option<Self>, regex::Error> {
+        if categories.is_empty() {
+            return Ok(None);
+        }
+        let scanner = PatternScanner::for_categories(config, categories)?;
+        Ok((!scanner.is_empty()).then_some(Self { scanner }))
+    }
+
+    /// The rewritten text, or `None` when nothing in it matched.
+    pub fn redact(&self, vault: &mut RedactionVault, text: &str) -> Option<String> {
+        let matches = self.scanner.find_matches(text);
+        if matches.is_empty() {
+            return None;
+        }
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for m in matches {
+            out.push_str(&text[cursor..m.range.start]);
+            out.push_str(&vault.placeholder_for(&m.category, m.severity, &text[m.range.clone()]));
+            cursor = m.range.end;
+        }
+        out.push_str(&text[cursor..]);
+        Some(out)
+    }
+
+    /// Rewrites the system prompt and every text part of every message,
+    /// tool results included. Returns whether anything changed.
+    pub fn redact_request(
+        &self,
+        vault: &mut RedactionVault,
+        request: &mut CanonicalRequest,
+    ) -> bool {
+        let mut changed = request
+            .system
+            .as_mut()
+            .is_some_and(|system| self.redact_in_place(vault, system));
+        for message in &mut request.messages {
+            changed |= self.redact_parts(vault, &mut message.content);
+        }
+        changed
+    }
+
+    fn redact_in_place(&self, vault: &mut RedactionVault, text: &mut String) -> bool {
+        let Some(rewritten) = self.redact(vault, text) else {
+            return false;
+        };
+        *text = rewritten;
+        true
+    }
+
+    fn redact_parts(&self, vault: &mut RedactionVault, parts: &mut [CanonicalContent]) -> bool {
+        let mut changed = false;
+        for part in parts {
+            match part {
+                CanonicalContent::Text(text) => changed |= self.redact_in_place(vault, text),
+                CanonicalContent::ToolResult { content, .. } => {
+                    changed |= self.redact_parts(vault, content);
+                },
+                _ => {},
+            }
+        }
+        changed
+    }
+}
+
+/// Rewrites response text on its way to the client: response-category spans
+/// are redacted, then request placeholders are rehydrated when asked for.
+#[derive(Debug)]
+pub struct ResponseRewriter {
+    redactor: Option<Redactor>,
+    vault: RedactionVault,
+    rehydrate: usize,
+    pending: BTreeMap<u32, String>,
+}
+
+impl ResponseRewriter {
+    /// `None` when the response would pass through unchanged.
+    pub fn new(redactor: Option<Redactor>, vault: RedactionVault, rehydrate: bool) -> Option<Self> {
+        let rehydrate = if rehydrate { vault.entries.len() } else { 0 };
+        if redactor.is_none() && rehydrate == 0 {
+            return None;
+        }
+        Some(Self {
+            redactor,
+            vault,
+            rehydrate,
+            pending: BTreeMap::new(),
+        })
+    }
+
+    pub fn take_tallies(&mut self) -> Vec<RedactionTally> {
+        self.vault.take_tallies()
+    }
+
+    pub fn rewrite(&mut self, text: &str) -> String {
+        let redacted = self
+            .redactor
+            .as_ref()
+            .and_then(|r| r.redact(&mut self.vault, text));
+        let mut out = redacted.unwrap_or_else(|| text.to_owned());
+        for (placeholder, original) in &self.vault.entries[..self.rehydrate] {
+            if out.contains(placeholder.as_str()) {
+                out = out.replace(placeholder.as_str(), original);
+            }
+        }
+        out
+    }
+
+    pub fn rewrite_response(&mut self, response: &mut CanonicalResponse) {
+        for part in &mut response.content {
+            if let CanonicalContent::Text(text) = part {
+                *text = self.rewrite(text);
+            }
+        }
+    }
+
+    /// Buffers `delta` for block `index` and returns the rewritten text that
+    /// is now safe to emit — possibly empty.
+    pub fn push(&mut self, index: u32, delta: &str) -> String {
+        let mut buffer = self.pending.remove(&index).unwrap_or_default();
+        buffer.push_str(delta);
+        let cut = self.safe_cut(&buffer);
+        let tail = buffer.split_off(cut);
+        self.pending.insert(index, tail);
+        if buffer.is_empty() {
+            return buffer;
+        }
+        self.rewrite(&buffer)
+    }
+
+    /// Rewrites and returns whatever block `index` still holds back.
+    pub fn flush(&mut self, index: u32) -> String {
+        let buffer = self.pending.remove(&index).unwrap_or_default();
+        if buffer.is_empty() {
+            return buffer;
+        }
+        self.rewrite(&buffer)
+    }
+
+    /// Blocks still holding text, for a stream that stops without closing
+    /// them.
+    pub fn pending_blocks(&self) -> Vec<u32> {
+        self.pending.keys().copied().collect()
+    }
+
+    fn safe_cut(&self, buffer: &str) -> usize {
+        if buffer.len() <= STREAM_HOLDBACK {
+            return 0;
+        }
+        let mut cut = buffer.len() - STREAM_HOLDBACK;
+        while !buffer.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        let spans = self
+            .redactor
+            .iter()
+            .flat_map(|r| r.scanner.find_matches(buffer))
+            .map(|m| m.range)
+            .chain(
+                self.vault.entries[..self.rehydrate]
+                    .iter()
+                    .flat_map(|(p, _)| {
+                        buffer
+                            .match_indices(p.as_str())
+                            .map(|(start, p)| start..start + p.len())
+                    }),
+            );
+        for span in spans {
+            if span.start < cut && cut < span.end {
+                cut = span.start;
+            }
+        }
+        cut
+    }
+}
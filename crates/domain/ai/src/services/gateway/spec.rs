@@ -1,8 +1,8 @@
 //! Declarative gateway-policy specification.
 //!
 //! Spec payload of `ai_gateway_policies` rows, shared with the YAML schema in
-//! `services/gateway/policies.yaml`. Carries quota windows and safety
-//! configuration.
+//! `services/gateway/policies.yaml`. Carries quota windows, safety
+//! configuration, and the opt-in response cache.
 //!
 //! Model exposure lives on the profile's gateway catalog, not here — see
 //! `GatewayConfig::is_model_exposed`.
@@ -12,6 +12,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::safety::Severity;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct QuotaWindow {
@@ -23,6 +25,10 @@ pub struct QuotaWindow {
     pub max_output_tokens: Option<i64>,
     #[serde(default)]
     pub max_cost_microdollars: Option<i64>,
+    /// Percentages of `max_cost_microdollars` whose crossing raises a budget
+    /// alert, e.g. `[50, 80, 100]`.
+    #[serde(default)]
+    pub alert_thresholds: Vec<u8>,
 }
 
 impl Default for QuotaWindow {
@@ -34,16 +40,96 @@ impl Default for QuotaWindow {
             max_input_tokens: None,
             max_output_tokens: None,
             max_cost_microdollars: None,
+            alert_thresholds: Vec::new(),
         }
     }
 }
 
+impl QuotaWindow {
+    /// The `alert_thresholds` a cost increase from `before` to `after`
+    /// crosses, ascending. Each threshold is crossed by exactly one increment
+    /// of a bucket, so replicas racing on it never report the same one twice.
+    #[must_use]
+    pub fn crossed_thresholds(&self, before: i64, after: i64) -> Vec<u8> {
+        let Some(max) = self.max_cost_microdollars.filter(|max| *max > 0) else {
+            return Vec::new();
+        };
+        let mut crossed: Vec<u8> = self
+            .alert_thresholds
+            .iter()
+            .copied()
+            .filter(|&percent| {
+                let line = i128::from(max) * i128::from(percent);
+                i128::from(before) * 100 < line && line <= i128::from(after) * 100
+            })
+            .collect();
+        crossed.sort_unstable();
+        crossed.dedup();
+        crossed
+    }
+}
+
+/// Where a policy's budget alerts are delivered, in addition to the
+/// `BUDGET_THRESHOLD_CROSSED` event every crossing emits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase", deny_unknown_fields)]
+pub enum BudgetAlertTarget {
+    /// A JSON `POST`, HMAC-signed when `secret_ref` names a profile secret.
+    Webhook {
+        url: String,
+        #[serde(default)]
+        secret_ref: Option<String>,
+    },
+    /// A message to `channel`, posted with the bot token of the Slack app
+    /// configured as `app` under `services/slack`.
+    Slack { app: String, channel: String },
+    /// A proactive message into an existing Teams conversation, sent as the
+    /// Teams app configured as `app` under `services/teams`.
+    Teams {
+        app: String,
+        service_url: String,
+        conversation_id: String,
+    },
+}
+
 fn default_subject() -> String {
     "user".to_owned()
 }
 
 pub const USER_QUOTA_SUBJECT: &str = "user";
 
+/// Smoothed per-subject limits, unlike a [`QuotaWindow`]'s fixed buckets.
+///
+/// `max_concurrent` caps requests in flight — a stream counts until its body
+/// is dropped. The per-minute limits are token buckets refilled continuously,
+/// so a subject that spends its minute in a burst waits only for the share
+/// it needs rather than for a window boundary. Tokens are input plus output,
+/// debited once the response completes; a large reply can drive the bucket
+/// into debt and hold off the subject's next request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimit {
+    #[serde(default = "default_subject")]
+    pub subject: String,
+    #[serde(default)]
+    pub max_concurrent: Option<u32>,
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+    #[serde(default)]
+    pub tokens_per_minute: Option<u32>,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            subject: default_subject(),
+            max_concurrent: None,
+            requests_per_minute: None,
+            tokens_per_minute: None,
+        }
+    }
+}
+
 /// How far back into a conversation the request-phase scanners look.
 ///
 /// A request carries the whole conversation, so scanning all of it re-reads
@@ -72,6 +158,133 @@ pub struct HeuristicConfig {
     pub disable_builtin: bool,
 }
 
+/// A built-in detector of the `pattern` scanner, named by the category
+/// family it reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternDetector {
+    Email,
+    Phone,
+    CreditCard,
+    Iban,
+    NationalId,
+    CloudCredential,
+}
+
+impl PatternDetector {
+    pub const ALL: [Self; 6] = [
+        Self::Email,
+        Self::Phone,
+        Self::CreditCard,
+        Self::Iban,
+        Self::NationalId,
+        Self::CloudCredential,
+    ];
+
+    #[must_use]
+    pub const fn category(self) -> &'static str {
+        match self {
+            Self::Email => "pii_email",
+            Self::Phone => "pii_phone",
+            Self::CreditCard => "pii_credit_card",
+            Self::Iban => "pii_iban",
+            Self::NationalId => "pii_national_id",
+            Self::CloudCredential => "secret_cloud_credential",
+        }
+    }
+}
+
+/// An operator-supplied regex reported under `category` when it matches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PatternRule {
+    pub category: String,
+    pub pattern: String,
+    #[serde(default = "default_rule_severity")]
+    pub severity: Severity,
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+const fn default_rule_severity() -> Severity {
+    Severity::Medium
+}
+
+/// Detector selection and custom rules for the builtin `pattern` scanner.
+///
+/// `detectors` unset runs every built-in detector; an explicit list runs
+/// only those, so `[]` leaves just the custom `rules`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PatternConfig {
+    #[serde(default)]
+    pub detectors: Option<Vec<PatternDetector>>,
+    #[serde(default)]
+    pub rules: Vec<PatternRule>,
+}
+
+impl PatternConfig {
+    #[must_use]
+    pub fn effective_detectors(&self) -> Vec<PatternDetector> {
+        self.detectors
+            .clone()
+            .unwrap_or_else(|| PatternDetector::ALL.to_vec())
+    }
+}
+
+/// Categories rewritten with placeholders instead of allowed or denied.
+///
+/// Spans are located by the `pattern` scanner's detectors and rules, so every
+/// category listed here must be one the policy's `pattern` block produces.
+/// `rehydrate` puts the caller's originals back wherever the model echoes a
+/// request placeholder in its reply.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RedactionConfig {
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub response_categories: Vec<String>,
+    #[serde(default)]
+    pub rehydrate: bool,
+}
+
+impl RedactionConfig {
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        !self.categories.is_empty() || !self.response_categories.is_empty()
+    }
+}
+
+/// Enforcing `block_response_categories` on a live stream.
+///
+/// Off by default, leaving streamed replies audit-only. When on, each text
+/// delta is scanned together with the text before it, up to `window_bytes`,
+/// and the last [`super::safety::STREAM_HOLDBACK`] bytes of each block are held
+/// until the block stops; a blocking finding ends the stream with the
+/// caller's protocol error event instead of the held text.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StreamSafetyConfig {
+    #[serde(default)]
+    pub enforce: bool,
+    #[serde(default = "default_stream_window_bytes")]
+    pub window_bytes: u32,
+}
+
+impl Default for StreamSafetyConfig {
+    fn default() -> Self {
+        Self {
+            enforce: false,
+            window_bytes: default_stream_window_bytes(),
+        }
+    }
+}
+
+const fn default_stream_window_bytes() -> u32 {
+    4096
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(deny_unknown_fields)]
 pub struct SafetyConfig {
@@ -80,20 +293,89 @@ pub struct SafetyConfig {
     #[serde(default)]
     pub heuristic: HeuristicConfig,
     #[serde(default)]
+    pub pattern: PatternConfig,
+    #[serde(default)]
     pub block_categories: Vec<String>,
     #[serde(default)]
     pub block_response_categories: Vec<String>,
     #[serde(default)]
+    pub redaction: RedactionConfig,
+    #[serde(default)]
+    pub stream: StreamSafetyConfig,
+    #[serde(default)]
     pub history: SafetyHistoryMode,
 }
 
+/// Who may be served an entry another request stored.
+///
+/// `user` keeps entries private to the caller that produced them. `tenant`
+/// shares them across every caller of the deployment — worth it only when
+/// prompts carry nothing user-specific.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseCacheScope {
+    #[default]
+    User,
+    Tenant,
+}
+
+impl ResponseCacheScope {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Tenant => "tenant",
+        }
+    }
+}
+
+/// Exact-match response cache for deterministic requests. Off unless a
+/// policy enables it; only requests sent with `temperature: 0` are eligible.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseCacheConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_cache_ttl_seconds")]
+    pub ttl_seconds: u32,
+    #[serde(default = "default_cache_max_entry_bytes")]
+    pub max_entry_bytes: u32,
+    #[serde(default)]
+    pub scope: ResponseCacheScope,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_seconds: default_cache_ttl_seconds(),
+            max_entry_bytes: default_cache_max_entry_bytes(),
+            scope: ResponseCacheScope::default(),
+        }
+    }
+}
+
+const fn default_cache_ttl_seconds() -> u32 {
+    3600
+}
+
+const fn default_cache_max_entry_bytes() -> u32 {
+    256 * 1024
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(deny_unknown_fields)]
 pub struct GatewayPolicySpec {
     #[serde(default)]
     pub quota_windows: Vec<QuotaWindow>,
     #[serde(default)]
+    pub rate_limits: Vec<RateLimit>,
+    #[serde(default)]
+    pub alerts: Vec<BudgetAlertTarget>,
+    #[serde(default)]
     pub safety: SafetyConfig,
+    #[serde(default)]
+    pub cache: ResponseCacheConfig,
 }
 
 impl GatewayPolicySpec {
@@ -0,0 +1,143 @@
+//! Boot-time model discovery for local OpenAI-compatible providers.
+//!
+//! Every registry entry with a `local` block is asked for its catalog once,
+//! after the profile loads: `GET {endpoint}/models` for llama.cpp and vLLM,
+//! `GET {host}/api/tags` for Ollama. Listed models the profile does not
+//! already declare are recorded in the entry's
+//! [`DiscoveredModels`](systemprompt_models::profile::DiscoveredModels), which
+//! every catalog lookup reads alongside the declared models. Discovered models
+//! carry no pricing.
+//!
+//! Discovery never fails boot: an unreachable server or an unparseable answer
+//! is logged and the entry keeps only its declared models.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use serde_json::Value;
+use systemprompt_config::SecretsBootstrap;
+use systemprompt_identifiers::ModelId;
+use systemprompt_models::profile::{
+    ModelDiscovery, ProviderEntry, ProviderModel, ProviderRegistry,
+};
+use systemprompt_models::services::ai::{ModelCapabilities, ModelLimits, ModelPricing};
+
+use super::http_client::build_client;
+
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Discovers the catalog of every local provider in `registry`.
+pub async fn discover_local_models(registry: &ProviderRegistry) {
+    let client = build_client(DISCOVERY_TIMEOUT, DISCOVERY_TIMEOUT);
+    for entry in &registry.providers {
+        let Some(local) = entry.local else {
+            continue;
+        };
+        let Some(url) = discovery_url(&entry.endpoint, local.discovery) else {
+            continue;
+        };
+        match fetch_ids(&client, entry, &url, local.discovery).await {
+            Ok(ids) => {
+                let models = discovered_models(entry, ids);
+                tracing::info!(
+                    provider = %entry.name.as_str(),
+                    models = models.len(),
+                    "discovered local provider models"
+                );
+                entry.discovered.set(models);
+            },
+            Err(reason) => tracing::warn!(
+                provider = %entry.name.as_str(),
+                url = %url,
+                reason = %reason,
+                "local provider model discovery failed; using declared models only"
+            ),
+        }
+    }
+}
+
+/// The listing URL for `discovery` against a provider `endpoint`. Ollama's
+/// native listing lives beside its OpenAI-compatible `/v1` prefix, not under
+/// it.
+#[must_use]
+pub fn discovery_url(endpoint: &str, discovery: ModelDiscovery) -> Option<String> {
+    let base = endpoint.trim_end_matches('/');
+    match discovery {
+        ModelDiscovery::OpenAi => Some(format!("{base}/models")),
+        ModelDiscovery::Ollama => {
+            let host = base.strip_suffix("/v1").unwrap_or(base);
+            Some(format!("{host}/api/tags"))
+        },
+        ModelDiscovery::None => None,
+    }
+}
+
+/// Model ids from an `OpenAI` `GET /models` body (`data[].id`).
+#[must_use]
+pub fn parse_openai_models(body: &Value) -> Vec<String> {
+    string_fields(body, "data", "id")
+}
+
+/// Model names from an Ollama `GET /api/tags` body (`models[].name`).
+#[must_use]
+pub fn parse_ollama_tags(body: &Value) -> Vec<String> {
+    string_fields(body, "models", "name")
+}
+
+fn string_fields(body: &Value, list: &str, field: &str) -> Vec<String> {
+    body.get(list)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item.get(field).and_then(Value::as_str))
+        .filter(|id| !id.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+async fn fetch_ids(
+    client: &reqwest::Client,
+    entry: &ProviderEntry,
+    url: &str,
+    discovery: ModelDiscovery,
+) -> Result<Vec<String>, String> {
+    let mut request = client.get(url);
+    if let Some(key) = api_key(entry) {
+        request = request.bearer_auth(key);
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("server answered {}", response.status()));
+    }
+    let body: Value = response.json().await.map_err(|e| e.to_string())?;
+    Ok(match discovery {
+        ModelDiscovery::Ollama => parse_ollama_tags(&body),
+        ModelDiscovery::OpenAi | ModelDiscovery::None => parse_openai_models(&body),
+    })
+}
+
+fn api_key(entry: &ProviderEntry) -> Option<String> {
+    SecretsBootstrap::get()
+        .ok()?
+        .get(entry.api_key_secret.as_str())
+        .filter(|key| !key.is_empty())
+        .cloned()
+}
+
+fn discovered_models(entry: &ProviderEntry, ids: Vec<String>) -> Vec<ProviderModel> {
+    let mut seen: HashSet<String> = HashSet::new();
+    ids.into_iter()
+        .filter(|id| entry.find_model(id).is_none() && seen.insert(id.clone()))
+        .map(|id| ProviderModel {
+            id: ModelId::new(id),
+            aliases: Vec::new(),
+            upstream_model: None,
+            pricing: ModelPricing::default(),
+            capabilities: ModelCapabilities::default(),
+            limits: ModelLimits::default(),
+        })
+        .collect()
+}
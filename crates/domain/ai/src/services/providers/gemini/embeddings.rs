@@ -0,0 +1,48 @@
+//! Gemini `batchEmbedContents` entry point.
+//!
+//! Renders the request with the shared embeddings codec, posts it with the
+//! `x-goog-api-key` header, and parses the reply back into an
+//! [`EmbeddingResponse`].
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use systemprompt_models::wire::embeddings::{EmbeddingRequest, gemini as embeddings};
+use systemprompt_models::wire::gemini;
+
+use crate::error::{AiError, Result};
+use crate::services::providers::{EmbeddingParams, EmbeddingResponse};
+
+use super::provider::GeminiProvider;
+
+pub(super) async fn embed(
+    provider: &GeminiProvider,
+    params: EmbeddingParams<'_>,
+) -> Result<EmbeddingResponse> {
+    let request = EmbeddingRequest {
+        model: params.model.to_owned(),
+        inputs: params.inputs.to_vec(),
+        dimensions: params.dimensions,
+    };
+    let body = embeddings::build_request_body(&request, params.model);
+    let url = format!(
+        "{}{}",
+        provider.endpoint,
+        embeddings::upstream_path(params.model)
+    );
+    let response = provider
+        .client
+        .post(&url)
+        .header(gemini::API_KEY_HEADER, &provider.api_key)
+        .json(&body)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(AiError::from_error_response("gemini", response).await);
+    }
+    let value = response.json().await?;
+    embeddings::parse_response(&value, &request).map_err(|e| AiError::ProviderError {
+        provider: "gemini".to_owned(),
+        message: e.to_string(),
+    })
+}
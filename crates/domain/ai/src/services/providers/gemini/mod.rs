@@ -1,7 +1,7 @@
 //! Gemini provider driver.
 //!
 //! Chat completions, streaming, code-execution tool, Google Search grounding,
-//! and tool use. Vendor wire translation is delegated to the shared
+//! embeddings, and tool use. Vendor wire translation is delegated to the shared
 //! `systemprompt_models::wire::gemini` codec; this module keeps the transport,
 //! the schema transformer / tool-name mapper, and the canonical glue.
 //!
@@ -10,6 +10,7 @@
 
 mod code_execution;
 mod constants;
+mod embeddings;
 mod generation;
 mod params;
 mod provider;
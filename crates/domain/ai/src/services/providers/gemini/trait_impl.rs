@@ -11,14 +11,14 @@ use std::pin::Pin;
 use crate::models::ai::{AiResponse, SamplingParams, SearchGroundedResponse, StreamChunk};
 use crate::models::tools::ToolCall;
 use crate::services::providers::{
-    AiProvider, GenerationParams, ModelPricing, SchemaGenerationParams, SearchGenerationParams,
-    ToolGenerationParams, ToolResultsParams, catalog_default_model, catalog_pricing,
-    catalog_supports_model,
+    AiProvider, EmbeddingParams, EmbeddingResponse, GenerationParams, ModelPricing,
+    SchemaGenerationParams, SearchGenerationParams, ToolGenerationParams, ToolResultsParams,
+    catalog_default_model, catalog_pricing, catalog_supports_model,
 };
 use crate::services::schema::ProviderCapabilities;
 
 use super::provider::GeminiProvider;
-use super::{generation, search, streaming, tools};
+use super::{embeddings, generation, search, streaming, tools};
 
 #[async_trait]
 impl AiProvider for GeminiProvider {
@@ -123,4 +123,12 @@ impl AiProvider for GeminiProvider {
         }
         search::generate_with_google_search(self, builder.build()).await
     }
+
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn embed(&self, params: EmbeddingParams<'_>) -> Result<EmbeddingResponse> {
+        embeddings::embed(self, params).await
+    }
 }
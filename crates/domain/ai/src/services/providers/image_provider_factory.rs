@@ -89,8 +89,7 @@ impl ImageProviderFactory {
 
     fn model_definitions(entry: &ProviderEntry) -> HashMap<String, ModelDefinition> {
         entry
-            .models
-            .iter()
+            .all_models()
             .map(|m| {
                 (
                     m.id.as_str().to_owned(),
@@ -1,7 +1,8 @@
 //! Provider drivers and shared abstractions.
 //!
 //! Drivers live for Anthropic, `OpenAI`, Gemini (chat + image generation),
-//! along with image provider abstractions. The internal
+//! along with image provider abstractions and boot-time model discovery for
+//! local OpenAI-compatible servers. The internal
 //! [`provider_trait::AiProvider`] trait is the dispatch surface
 //! used by [`crate::AiService`].
 //!
@@ -10,6 +11,7 @@
 
 pub mod anthropic;
 pub mod canonical_bridge;
+pub mod discovery;
 pub mod gemini;
 mod gemini_image_mapping;
 pub mod gemini_images;
@@ -25,6 +27,7 @@ pub mod shared;
 
 pub use anthropic::AnthropicProvider;
 pub use canonical_bridge::CodeExecutionResponse;
+pub use discovery::discover_local_models;
 pub use gemini::GeminiProvider;
 pub use gemini_images::GeminiImageProvider;
 pub use image_provider_factory::{ImageProviderFactory, ImageProviderParams};
@@ -33,8 +36,9 @@ pub use openai::OpenAiProvider;
 pub use openai_images::OpenAiImageProvider;
 pub use provider_factory::{ProviderClientParams, ProviderFactory};
 pub use provider_trait::{
-    AiProvider, GenerationParams, ModelPricing, SchemaGenerationParams, SearchGenerationParams,
-    StructuredGenerationParams, ToolGenerationParams, ToolResultsParams, catalog_default_model,
-    catalog_pricing, catalog_supports_model,
+    AiProvider, EmbeddingParams, EmbeddingResponse, GenerationParams, ModelPricing,
+    SchemaGenerationParams, SearchGenerationParams, StructuredGenerationParams,
+    ToolGenerationParams, ToolResultsParams, catalog_default_model, catalog_pricing,
+    catalog_supports_model,
 };
 pub use resilient_provider::ResilientProvider;
@@ -0,0 +1,41 @@
+//! `OpenAI` `/embeddings` entry point.
+//!
+//! Renders the request with the shared embeddings codec, posts it, and parses
+//! the reply back into an [`EmbeddingResponse`].
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use systemprompt_models::wire::embeddings::{EmbeddingRequest, openai};
+
+use crate::error::{AiError, Result};
+use crate::services::providers::{EmbeddingParams, EmbeddingResponse};
+
+use super::provider::OpenAiProvider;
+
+pub(super) async fn embed(
+    provider: &OpenAiProvider,
+    params: EmbeddingParams<'_>,
+) -> Result<EmbeddingResponse> {
+    let request = EmbeddingRequest {
+        model: params.model.to_owned(),
+        inputs: params.inputs.to_vec(),
+        dimensions: params.dimensions,
+    };
+    let body = openai::build_request_body(&request, params.model);
+    let response = provider
+        .client
+        .post(format!("{}{}", provider.endpoint, openai::UPSTREAM_PATH))
+        .bearer_auth(&provider.api_key)
+        .json(&body)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(AiError::from_error_response("openai", response).await);
+    }
+    let value = response.json().await?;
+    openai::parse_response(&value, &request).map_err(|e| AiError::ProviderError {
+        provider: "openai".to_owned(),
+        message: e.to_string(),
+    })
+}
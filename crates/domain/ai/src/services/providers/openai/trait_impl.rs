@@ -11,16 +11,16 @@ use std::pin::Pin;
 use crate::models::ai::{AiResponse, SamplingParams, SearchGroundedResponse, StreamChunk};
 use crate::models::tools::ToolCall;
 use crate::services::providers::{
-    AiProvider, GenerationParams, ModelPricing, SchemaGenerationParams, SearchGenerationParams,
-    StructuredGenerationParams, ToolGenerationParams, catalog_default_model, catalog_pricing,
-    catalog_supports_model,
+    AiProvider, EmbeddingParams, EmbeddingResponse, GenerationParams, ModelPricing,
+    SchemaGenerationParams, SearchGenerationParams, StructuredGenerationParams,
+    ToolGenerationParams, catalog_default_model, catalog_pricing, catalog_supports_model,
 };
 use crate::services::schema::ProviderCapabilities;
 
 use crate::services::providers::canonical_bridge::tools_to_canonical;
 
 use super::provider::OpenAiProvider;
-use super::{generation, search};
+use super::{embeddings, generation, search};
 
 #[async_trait]
 impl AiProvider for OpenAiProvider {
@@ -121,4 +121,12 @@ impl AiProvider for OpenAiProvider {
         };
         search::generate_with_web_search(self, search_params).await
     }
+
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn embed(&self, params: EmbeddingParams<'_>) -> Result<EmbeddingResponse> {
+        embeddings::embed(self, params).await
+    }
 }
@@ -5,7 +5,10 @@
 //! grounding, and streaming. The borrowed parameter structs
 //! ([`GenerationParams`], [`ToolGenerationParams`], [`SchemaGenerationParams`],
 //! [`StructuredGenerationParams`], [`SearchGenerationParams`],
-//! [`ToolResultsParams`]) keep large call signatures readable.
+//! [`ToolResultsParams`], [`EmbeddingParams`]) keep large call signatures
+//! readable. Embeddings are an optional capability: providers that speak an
+//! embeddings wire protocol override [`AiProvider::embed`] and report it via
+//! [`AiProvider::supports_embeddings`].
 //! [`systemprompt_models::services::ai::ModelPricing`] is re-exported here as
 //! the single pricing type for usage accounting.
 //!
@@ -25,6 +28,7 @@ use std::pin::Pin;
 
 use systemprompt_models::profile::ProviderModel;
 pub use systemprompt_models::services::ai::ModelPricing;
+pub use systemprompt_models::wire::embeddings::EmbeddingResponse;
 
 #[must_use]
 pub fn catalog_supports_model(models: &[ProviderModel], model: &str) -> bool {
@@ -166,6 +170,28 @@ impl<'a> SearchGenerationParams<'a> {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddingParams<'a> {
+    pub inputs: &'a [String],
+    pub model: &'a str,
+    pub dimensions: Option<u32>,
+}
+
+impl<'a> EmbeddingParams<'a> {
+    pub const fn new(inputs: &'a [String], model: &'a str) -> Self {
+        Self {
+            inputs,
+            model,
+            dimensions: None,
+        }
+    }
+
+    pub const fn with_dimensions(mut self, dimensions: u32) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+}
+
 // Why: providers are dispatched as `Box<dyn AiProvider>` by the factory, so
 // the trait must be dyn-compatible — native `async fn` in traits is not.
 #[async_trait]
@@ -288,4 +314,15 @@ pub trait AiProvider: Send + Sync {
             self.name()
         )))
     }
+
+    fn supports_embeddings(&self) -> bool {
+        false
+    }
+
+    async fn embed(&self, _params: EmbeddingParams<'_>) -> Result<EmbeddingResponse> {
+        Err(crate::error::AiError::Internal(format!(
+            "Embeddings not supported by provider {}",
+            self.name()
+        )))
+    }
 }
@@ -27,8 +27,9 @@ use crate::models::tools::ToolCall;
 use crate::services::schema::ProviderCapabilities;
 
 use super::provider_trait::{
-    AiProvider, GenerationParams, ModelPricing, SchemaGenerationParams, SearchGenerationParams,
-    StructuredGenerationParams, ToolGenerationParams, ToolResultsParams,
+    AiProvider, EmbeddingParams, EmbeddingResponse, GenerationParams, ModelPricing,
+    SchemaGenerationParams, SearchGenerationParams, StructuredGenerationParams,
+    ToolGenerationParams, ToolResultsParams,
 };
 
 type StreamResult = Result<Pin<Box<dyn Stream<Item = Result<StreamChunk>> + Send>>>;
@@ -151,6 +152,10 @@ impl AiProvider for ResilientProvider {
         self.inner.supports_google_search()
     }
 
+    fn supports_embeddings(&self) -> bool {
+        self.inner.supports_embeddings()
+    }
+
     async fn generate(&self, params: GenerationParams<'_>) -> Result<AiResponse> {
         self.guard
             .execute(AiError::classify, || self.inner.generate(params.clone()))
@@ -215,6 +220,13 @@ impl AiProvider for ResilientProvider {
             .map_err(|err| self.map_err(err))
     }
 
+    async fn embed(&self, params: EmbeddingParams<'_>) -> Result<EmbeddingResponse> {
+        self.guard
+            .execute(AiError::classify, || self.inner.embed(params))
+            .await
+            .map_err(|err| self.map_err(err))
+    }
+
     async fn generate_stream(&self, params: GenerationParams<'_>) -> StreamResult {
         self.guarded_stream_call(self.inner.generate_stream(params))
             .await
@@ -26,6 +26,7 @@ pub fn mcp_tool_to_definition(tool: &McpTool) -> ToolDefinition {
         output_schema: tool.output_schema.clone(),
         service_id: tool.service_id.to_string(),
         terminal_on_success: tool.terminal_on_success,
+        requires_approval: tool.requires_approval,
         model_config: tool.model_config.as_ref().and_then(|c| {
             serde_json::to_value(c)
                 .map_err(|e| {
@@ -49,6 +50,7 @@ pub fn definition_to_mcp_tool(def: &ToolDefinition) -> McpTool {
         output_schema: def.output_schema.clone(),
         service_id: McpServerId::new(def.service_id.clone()),
         terminal_on_success: def.terminal_on_success,
+        requires_approval: def.requires_approval,
         model_config: def.model_config.as_ref().and_then(|c| {
             serde_json::from_value(c.clone())
                 .map_err(|e| {
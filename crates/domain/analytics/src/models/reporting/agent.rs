@@ -72,10 +72,20 @@ pub struct ConversationListRow {
     pub name: Option<String>,
     pub task_count: i64,
     pub message_count: i64,
+    pub summary_count: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ConversationSummaryRow {
+    pub agent_name: String,
+    pub covered_messages: i32,
+    pub token_estimate: i32,
+    pub summary: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct GatewaySessionListRow {
     pub session_id: SessionId,
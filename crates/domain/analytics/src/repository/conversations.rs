@@ -2,7 +2,8 @@
 //!
 //! [`ConversationAnalyticsRepository`] lists agent-task contexts and
 //! task-less gateway AI sessions, and reports task, message, and timestamp
-//! counts used to build conversation activity trends.
+//! counts used to build conversation activity trends, plus the rolling
+//! summaries that history compaction stored for a context.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -12,9 +13,12 @@ use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use std::sync::Arc;
 use systemprompt_database::DbPool;
+use systemprompt_identifiers::ContextId;
 use systemprompt_models::ContextKind;
 
-use crate::models::reporting::{ConversationListRow, GatewaySessionListRow, TimestampRow};
+use crate::models::reporting::{
+    ConversationListRow, ConversationSummaryRow, GatewaySessionListRow, TimestampRow,
+};
 
 #[derive(Debug)]
 pub struct ConversationAnalyticsRepository {
@@ -43,6 +47,8 @@ impl ConversationAnalyticsRepository {
                 (SELECT COUNT(*) FROM task_messages tm
                  JOIN agent_tasks at ON at.task_id = tm.task_id
                  WHERE at.context_id = uc.context_id)::bigint as "message_count!",
+                (SELECT COUNT(*) FROM context_summaries cs
+                 WHERE cs.context_id = uc.context_id)::bigint as "summary_count!",
                 uc.created_at as "created_at!",
                 uc.updated_at as "updated_at!"
             FROM user_contexts uc
@@ -60,6 +66,25 @@ impl ConversationAnalyticsRepository {
         .map_err(Into::into)
     }
 
+    pub async fn list_context_summaries(
+        &self,
+        context_id: &ContextId,
+    ) -> Result<Vec<ConversationSummaryRow>> {
+        sqlx::query_as!(
+            ConversationSummaryRow,
+            r#"
+            SELECT agent_name, covered_messages, token_estimate, summary, created_at
+            FROM context_summaries
+            WHERE context_id = $1
+            ORDER BY created_at, id
+            "#,
+            context_id.as_str()
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
     pub async fn list_gateway_sessions(
         &self,
         start: DateTime<Utc>,
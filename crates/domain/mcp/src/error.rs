@@ -27,6 +27,9 @@ domain_error! {
         #[error("Process spawn failed for {server}: {message}")]
         ProcessSpawn { server: String, message: String },
 
+        #[error("Sandbox setup failed for {server}: {message}")]
+        Sandbox { server: String, message: String },
+
         #[error("Port unavailable: {port} - {message}")]
         PortUnavailable { port: u16, message: String },
 
@@ -152,6 +152,7 @@ impl McpClient {
 
             let tool_meta = tool_metadata.get(tool.name.as_ref());
             let terminal_on_success = tool_meta.is_some_and(|m| m.terminal_on_success);
+            let requires_approval = tool_meta.is_some_and(|m| m.requires_approval);
 
             let model_config = tool_meta
                 .and_then(|m| m.model_config.clone())
@@ -164,6 +165,7 @@ impl McpClient {
                 output_schema,
                 service_id: McpServerId::new(service_id),
                 terminal_on_success,
+                requires_approval,
                 model_config,
             });
         }
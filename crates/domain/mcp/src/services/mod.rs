@@ -14,6 +14,7 @@ pub mod orchestrator;
 pub mod process;
 pub mod registry;
 pub mod schema;
+pub mod stdio_bridge;
 pub mod tool_provider;
 pub mod ui_renderer;
 
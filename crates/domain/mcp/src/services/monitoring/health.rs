@@ -6,6 +6,8 @@
 //! and recovery transitions on a fixed interval. Accessor-backed external
 //! servers are reported healthy without probing: their bearer is minted
 //! per-user on demand, so the monitor has no credential to authenticate with.
+//! Results for a server with sandbox limits carry its cgroup's cumulative
+//! limit events.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -14,6 +16,7 @@ use crate::McpServerConfig;
 use crate::error::McpDomainResult;
 use crate::models::ValidationResultType;
 use crate::services::client::McpConnectionResult;
+use crate::services::process::sandbox::{self, SandboxEventCounts};
 use std::time::Duration;
 use tokio::time::timeout;
 
@@ -61,6 +64,7 @@ pub struct HealthCheckDetails {
     pub validation_type: String,
     pub error_message: Option<String>,
     pub server_version: Option<String>,
+    pub sandbox: Option<SandboxEventCounts>,
 }
 
 impl HealthCheckResult {
@@ -89,6 +93,7 @@ impl HealthCheckResult {
             validation_type: validation_type.to_string(),
             error_message: result.error_message.clone(),
             server_version: result.server_info.as_ref().map(|info| info.version.clone()),
+            sandbox: sandbox::read_events(config),
         };
 
         Self {
@@ -111,6 +116,7 @@ impl HealthCheckResult {
                 validation_type: "external_accessor_backed".to_owned(),
                 error_message: None,
                 server_version: None,
+                sandbox: None,
             },
         }
     }
@@ -127,6 +133,7 @@ impl HealthCheckResult {
                 validation_type: ValidationResultType::Error.to_string(),
                 error_message: Some(error),
                 server_version: None,
+                sandbox: sandbox::read_events(config),
             },
         }
     }
@@ -146,7 +153,7 @@ pub async fn perform_health_check(config: &McpServerConfig) -> McpDomainResult<H
     }
 
     let connection_result = match config.server_type {
-        McpServerType::Internal => {
+        McpServerType::Internal | McpServerType::Stdio => {
             timeout(
                 Duration::from_secs(30),
                 validate_connection_with_auth(
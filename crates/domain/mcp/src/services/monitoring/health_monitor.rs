@@ -2,7 +2,8 @@
 //!
 //! Polls [`perform_health_check`] on a fixed interval and logs degradation and
 //! recovery transitions, tracking consecutive failures and downtime between the
-//! last failure and recovery.
+//! last failure and recovery. Sandbox limit events (OOM kills, memory and pids
+//! cap hits) that appear between polls are logged as violations.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -15,12 +16,14 @@ use tracing::Instrument;
 use super::health::{HealthCheckResult, HealthStatus, perform_health_check};
 use crate::McpServerConfig;
 use crate::error::McpDomainResult;
+use crate::services::process::sandbox::SandboxEventCounts;
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct HealthMonitorState {
     previous_status: Option<HealthStatus>,
     failure_count: u32,
     last_failure_time: Option<DateTime<Utc>>,
+    sandbox_seen: SandboxEventCounts,
 }
 
 impl HealthMonitorState {
@@ -30,12 +33,17 @@ impl HealthMonitorState {
             previous_status: None,
             failure_count: 0,
             last_failure_time: None,
+            sandbox_seen: SandboxEventCounts::ZERO,
         }
     }
 
     pub fn observe(&mut self, config: &McpServerConfig, result: &HealthCheckResult) {
         handle_health_result(config, result, self);
         self.previous_status = Some(result.status);
+        if let Some(events) = result.details.sandbox {
+            log_sandbox_violations(config, &events.since(&self.sandbox_seen));
+            self.sandbox_seen = events;
+        }
     }
 
     #[must_use]
@@ -47,6 +55,13 @@ impl HealthMonitorState {
     pub const fn previous_status(&self) -> Option<HealthStatus> {
         self.previous_status
     }
+
+    /// Sandbox limit events already reported; the first poll reports
+    /// everything the cgroup has recorded, including earlier runs.
+    #[must_use]
+    pub const fn sandbox_seen(&self) -> SandboxEventCounts {
+        self.sandbox_seen
+    }
 }
 
 pub async fn monitor_health_continuously(
@@ -144,6 +159,20 @@ fn handle_degraded(
     }
 }
 
+fn log_sandbox_violations(config: &McpServerConfig, new_events: &SandboxEventCounts) {
+    if !new_events.has_violations() {
+        return;
+    }
+    tracing::warn!(
+        service_name = %config.name,
+        oom_kills = new_events.oom_kills,
+        memory_max_hits = new_events.memory_max_hits,
+        pids_max_hits = new_events.pids_max_hits,
+        cpu_throttled_periods = new_events.cpu_throttled_periods,
+        "MCP server sandbox violation"
+    );
+}
+
 fn log_health_check_error(config: &McpServerConfig, error: &crate::error::McpDomainError) {
     tracing::info!(
         service_name = %config.name,
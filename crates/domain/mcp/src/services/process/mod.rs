@@ -1,5 +1,5 @@
-//! OS-process lifecycle for MCP servers: spawning, PID discovery,
-//! liveness monitoring, and graceful/forced termination.
+//! OS-process lifecycle for MCP servers: spawning (optionally sandboxed), PID
+//! discovery, liveness monitoring, and graceful/forced termination.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -7,6 +7,7 @@
 pub mod cleanup;
 pub mod monitor;
 pub mod pid;
+pub mod sandbox;
 pub mod spawner;
 pub mod utils;
 
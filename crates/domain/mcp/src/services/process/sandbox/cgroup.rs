@@ -0,0 +1,171 @@
+//! cgroup v2 placement, limits and event counters for sandboxed servers.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use systemprompt_models::mcp::ResourceLimits;
+
+const CPU_PERIOD_US: u64 = 100_000;
+
+/// Cumulative limit events the kernel has recorded for a server's cgroup.
+///
+/// Read from `memory.events`, `pids.events` and `cpu.stat`. The counters
+/// survive restarts of the server because the cgroup is reused, so callers
+/// compare snapshots with [`SandboxEventCounts::since`] to find new events.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SandboxEventCounts {
+    pub oom_kills: u64,
+    pub memory_max_hits: u64,
+    pub pids_max_hits: u64,
+    pub cpu_throttled_periods: u64,
+}
+
+impl SandboxEventCounts {
+    pub const ZERO: Self = Self {
+        oom_kills: 0,
+        memory_max_hits: 0,
+        pids_max_hits: 0,
+        cpu_throttled_periods: 0,
+    };
+
+    pub fn parse(memory_events: &str, pids_events: &str, cpu_stat: &str) -> Self {
+        Self {
+            oom_kills: flat_key(memory_events, "oom_kill"),
+            memory_max_hits: flat_key(memory_events, "max"),
+            pids_max_hits: flat_key(pids_events, "max"),
+            cpu_throttled_periods: flat_key(cpu_stat, "nr_throttled"),
+        }
+    }
+
+    pub const fn since(&self, earlier: &Self) -> Self {
+        Self {
+            oom_kills: self.oom_kills.saturating_sub(earlier.oom_kills),
+            memory_max_hits: self.memory_max_hits.saturating_sub(earlier.memory_max_hits),
+            pids_max_hits: self.pids_max_hits.saturating_sub(earlier.pids_max_hits),
+            cpu_throttled_periods: self
+                .cpu_throttled_periods
+                .saturating_sub(earlier.cpu_throttled_periods),
+        }
+    }
+
+    /// Whether any event that stopped the server from doing work occurred.
+    ///
+    /// CPU throttling is expected under a CPU cap and is not counted.
+    pub const fn has_violations(&self) -> bool {
+        self.oom_kills > 0 || self.memory_max_hits > 0 || self.pids_max_hits > 0
+    }
+}
+
+fn flat_key(contents: &str, key: &str) -> u64 {
+    contents
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(name, _)| *name == key)
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+pub fn cgroup_dir(limits: &ResourceLimits, server_name: &str) -> PathBuf {
+    Path::new(&limits.cgroup_parent).join(server_name)
+}
+
+/// Interface files and the values written to them for `limits`.
+pub fn limit_values(limits: &ResourceLimits) -> Vec<(&'static str, String)> {
+    let mut values = Vec::new();
+    if let Some(mb) = limits.memory_max_mb {
+        values.push(("memory.max", (mb * 1024 * 1024).to_string()));
+    }
+    if let Some(percent) = limits.cpu_max_percent {
+        let quota = u64::from(percent) * CPU_PERIOD_US / 100;
+        values.push(("cpu.max", format!("{quota} {CPU_PERIOD_US}")));
+    }
+    if let Some(pids) = limits.pids_max {
+        values.push(("pids.max", pids.to_string()));
+    }
+    values
+}
+
+#[cfg(target_os = "linux")]
+fn controllers(limits: &ResourceLimits) -> String {
+    [
+        ("+memory", limits.memory_max_mb.is_some()),
+        ("+cpu", limits.cpu_max_percent.is_some()),
+        ("+pids", limits.pids_max.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(controller, wanted)| wanted.then_some(controller))
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// Creates (or reuses) the server's cgroup, writes its limits, and opens its
+/// `cgroup.procs` for the child to join itself into before `exec`.
+#[cfg(target_os = "linux")]
+pub(super) fn prepare(
+    limits: &ResourceLimits,
+    server_name: &str,
+) -> Result<std::os::fd::OwnedFd, String> {
+    let parent = Path::new(&limits.cgroup_parent);
+    fs::create_dir_all(parent)
+        .map_err(|e| format!("cannot create cgroup {}: {e}", parent.display()))?;
+    let subtree_control = parent.join("cgroup.subtree_control");
+    fs::write(&subtree_control, controllers(limits)).map_err(|e| {
+        format!(
+            "cannot enable controllers in {} (is the cgroup delegated to this user?): {e}",
+            subtree_control.display()
+        )
+    })?;
+
+    let dir = cgroup_dir(limits, server_name);
+    match fs::create_dir(&dir) {
+        Ok(()) => {},
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {},
+        Err(e) => return Err(format!("cannot create cgroup {}: {e}", dir.display())),
+    }
+    for (file, value) in limit_values(limits) {
+        let path = dir.join(file);
+        fs::write(&path, &value)
+            .map_err(|e| format!("cannot write {value} to {}: {e}", path.display()))?;
+    }
+
+    let procs = dir.join("cgroup.procs");
+    fs::OpenOptions::new()
+        .write(true)
+        .open(&procs)
+        .map(std::os::fd::OwnedFd::from)
+        .map_err(|e| format!("cannot open {}: {e}", procs.display()))
+}
+
+/// Moves the calling process into the cgroup whose `cgroup.procs` is open
+/// as `procs`. Runs in the forked child: writing `0` names the writer itself.
+#[cfg(target_os = "linux")]
+#[expect(
+    unsafe_code,
+    reason = "the forked child may not allocate, so the pid is written with raw write"
+)]
+pub(super) fn join(procs: std::os::fd::RawFd) -> std::io::Result<()> {
+    // SAFETY: writes one byte from a static buffer to an fd the plan owns.
+    if unsafe { libc::write(procs, b"0".as_ptr().cast(), 1) } < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+pub(super) fn read_events(
+    limits: &ResourceLimits,
+    server_name: &str,
+) -> Option<SandboxEventCounts> {
+    let dir = cgroup_dir(limits, server_name);
+    if !dir.is_dir() {
+        return None;
+    }
+    let read = |file: &str| fs::read_to_string(dir.join(file)).unwrap_or_default();
+    Some(SandboxEventCounts::parse(
+        &read("memory.events"),
+        &read("pids.events"),
+        &read("cpu.stat"),
+    ))
+}
@@ -0,0 +1,190 @@
+//! Landlock filesystem allowlist for sandboxed servers.
+//!
+//! The ruleset is built in the parent, where failures can be reported, and
+//! only `landlock_restrict_self` runs in the forked child. Rights the running
+//! kernel's Landlock ABI does not know are left unhandled rather than failing
+//! the spawn.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::fs::OpenOptions;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+use systemprompt_models::mcp::FilesystemAllowlist;
+
+const CREATE_RULESET_VERSION: u32 = 1;
+const RULE_PATH_BENEATH: libc::c_int = 1;
+
+const EXECUTE: u64 = 1 << 0;
+const WRITE_FILE: u64 = 1 << 1;
+const READ_FILE: u64 = 1 << 2;
+const READ_DIR: u64 = 1 << 3;
+const ABI_V1_ALL: u64 = (1 << 13) - 1;
+const REFER: u64 = 1 << 13;
+const TRUNCATE: u64 = 1 << 14;
+
+const READ_ONLY: u64 = EXECUTE | READ_FILE | READ_DIR;
+const FILE_RIGHTS: u64 = EXECUTE | WRITE_FILE | READ_FILE | TRUNCATE;
+
+#[repr(C)]
+struct RulesetAttr {
+    handled_access_fs: u64,
+}
+
+#[repr(C, packed)]
+struct PathBeneathAttr {
+    allowed_access: u64,
+    parent_fd: i32,
+}
+
+const fn handled_access(abi: i64) -> u64 {
+    let mut handled = ABI_V1_ALL;
+    if abi >= 2 {
+        handled |= REFER;
+    }
+    if abi >= 3 {
+        handled |= TRUNCATE;
+    }
+    handled
+}
+
+/// Builds a ruleset granting `allowlist` (and read/execute on `executable`),
+/// ready for [`restrict_self`].
+pub(super) fn build_ruleset(
+    allowlist: &FilesystemAllowlist,
+    executable: Option<&Path>,
+) -> Result<OwnedFd, String> {
+    let abi = abi_version();
+    if abi < 1 {
+        return Err("Landlock is not available on this kernel".to_owned());
+    }
+    let handled = handled_access(abi);
+    let ruleset = create_ruleset(handled)?;
+
+    let read_only = allowlist
+        .read_only
+        .iter()
+        .map(Path::new)
+        .chain(executable)
+        .map(|path| (path, READ_ONLY));
+    let read_write = allowlist
+        .read_write
+        .iter()
+        .map(|path| (Path::new(path), handled));
+    for (path, access) in read_only.chain(read_write) {
+        add_path_rule(&ruleset, path, access & handled)?;
+    }
+    Ok(ruleset)
+}
+
+fn add_path_rule(ruleset: &OwnedFd, path: &Path, access: u64) -> Result<(), String> {
+    let Ok(metadata) = path.metadata() else {
+        tracing::warn!(path = %path.display(), "Skipping missing sandbox filesystem path");
+        return Ok(());
+    };
+    let allowed_access = if metadata.is_dir() {
+        access
+    } else {
+        access & FILE_RIGHTS
+    };
+    let parent = OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_PATH)
+        .open(path)
+        .map_err(|e| format!("cannot open {}: {e}", path.display()))?;
+
+    add_rule(
+        ruleset.as_raw_fd(),
+        &PathBeneathAttr {
+            allowed_access,
+            parent_fd: parent.as_raw_fd(),
+        },
+    )
+    .map_err(|e| format!("cannot allow {}: {e}", path.display()))
+}
+
+#[expect(
+    unsafe_code,
+    reason = "Landlock has no libc wrapper; its syscalls are only reachable through \
+              libc::syscall"
+)]
+fn abi_version() -> i64 {
+    // SAFETY: a NULL attribute with size 0 and the VERSION flag is the
+    // documented ABI probe; it reads no memory and returns an integer.
+    unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<RulesetAttr>(),
+            0usize,
+            CREATE_RULESET_VERSION,
+        )
+    }
+}
+
+#[expect(
+    unsafe_code,
+    reason = "Landlock has no libc wrapper; its syscalls are only reachable through \
+              libc::syscall"
+)]
+fn create_ruleset(handled_access_fs: u64) -> Result<OwnedFd, String> {
+    let attr = RulesetAttr { handled_access_fs };
+    // SAFETY: `attr` is a live, correctly sized `landlock_ruleset_attr` for
+    // the duration of the call; the kernel only reads it.
+    let fd = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            &raw const attr,
+            size_of::<RulesetAttr>(),
+            0u32,
+        )
+    };
+    if fd < 0 {
+        return Err(format!(
+            "cannot create Landlock ruleset: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    // SAFETY: a non-negative return is a new ruleset fd owned by nothing else.
+    Ok(unsafe { OwnedFd::from_raw_fd(fd as RawFd) })
+}
+
+#[expect(
+    unsafe_code,
+    reason = "Landlock has no libc wrapper; its syscalls are only reachable through \
+              libc::syscall"
+)]
+fn add_rule(ruleset: RawFd, rule: &PathBeneathAttr) -> std::io::Result<()> {
+    // SAFETY: `rule` is a live `landlock_path_beneath_attr` whose `parent_fd`
+    // stays open for the call; the kernel only reads it.
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_add_rule,
+            ruleset,
+            RULE_PATH_BENEATH,
+            std::ptr::from_ref(rule),
+            0u32,
+        )
+    };
+    if result < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Confines the calling process to `ruleset`. Runs in the forked child, so
+/// it makes exactly one syscall and allocates nothing.
+#[expect(
+    unsafe_code,
+    reason = "Landlock has no libc wrapper; its syscalls are only reachable through \
+              libc::syscall"
+)]
+pub(super) fn restrict_self(ruleset: RawFd) -> std::io::Result<()> {
+    // SAFETY: takes an fd and a flags word; no memory is passed.
+    if unsafe { libc::syscall(libc::SYS_landlock_restrict_self, ruleset, 0u32) } < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
@@ -0,0 +1,201 @@
+//! Sandboxing for spawned MCP server processes.
+//!
+//! [`prepare`] turns a server's `SandboxConfig` into a [`SandboxPlan`] in
+//! the parent: the cgroup is created and capped, the Landlock ruleset built,
+//! the seccomp program assembled and namespace ids captured, so every
+//! failure is reported before anything is forked. [`SandboxPlan::install`]
+//! then registers a `pre_exec` hook that, in the child, joins the cgroup,
+//! unshares the network namespace, sets `no_new_privs`, restricts the
+//! filesystem and loads the seccomp filter, in that order. A hook that fails
+//! fails the spawn.
+//!
+//! Limit events the kernel records against the cgroup are read back with
+//! [`read_events`] for the health monitor.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+mod cgroup;
+#[cfg(target_os = "linux")]
+mod landlock;
+#[cfg(target_os = "linux")]
+mod namespace;
+#[cfg(target_os = "linux")]
+mod seccomp;
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::McpServerConfig;
+use crate::error::{McpDomainError, McpDomainResult};
+
+pub use cgroup::{SandboxEventCounts, cgroup_dir, limit_values};
+
+fn sandbox_error(config: &McpServerConfig, message: String) -> McpDomainError {
+    McpDomainError::Sandbox {
+        server: config.name.clone(),
+        message,
+    }
+}
+
+pub fn read_events(config: &McpServerConfig) -> Option<SandboxEventCounts> {
+    let limits = config.sandbox.as_ref()?.limits.as_ref()?;
+    cgroup::read_events(limits, &config.name)
+}
+
+/// Kernel state a sandboxed child applies to itself between `fork` and
+/// `exec`.
+#[cfg(target_os = "linux")]
+pub struct SandboxPlan {
+    cgroup_procs: Option<std::os::fd::OwnedFd>,
+    network: Option<namespace::NetworkIsolation>,
+    no_new_privs: bool,
+    landlock: Option<std::os::fd::OwnedFd>,
+    seccomp: Option<Vec<libc::sock_filter>>,
+}
+
+#[cfg(target_os = "linux")]
+impl std::fmt::Debug for SandboxPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SandboxPlan")
+            .field("cgroup", &self.cgroup_procs.is_some())
+            .field("network_isolated", &self.network.is_some())
+            .field("no_new_privs", &self.no_new_privs)
+            .field("landlock", &self.landlock.is_some())
+            .field("seccomp_instructions", &self.seccomp.as_ref().map(Vec::len))
+            .finish()
+    }
+}
+
+/// Prepares the sandbox for `config`, or `None` when it declares none.
+///
+/// `executable` is the binary about to be run, granted read and execute
+/// under Landlock so an allowlist need not repeat it.
+#[cfg(target_os = "linux")]
+pub fn prepare(
+    config: &McpServerConfig,
+    executable: Option<&Path>,
+) -> McpDomainResult<Option<SandboxPlan>> {
+    use systemprompt_models::mcp::NetworkMode;
+
+    let Some(sandbox) = config.sandbox.as_ref() else {
+        return Ok(None);
+    };
+
+    let cgroup_procs = sandbox
+        .limits
+        .as_ref()
+        .map(|limits| cgroup::prepare(limits, &config.name))
+        .transpose()
+        .map_err(|e| sandbox_error(config, e))?;
+    let landlock = sandbox
+        .filesystem
+        .as_ref()
+        .map(|allowlist| landlock::build_ruleset(allowlist, executable))
+        .transpose()
+        .map_err(|e| sandbox_error(config, e))?;
+    let seccomp = seccomp::build_filter(sandbox.seccomp).map_err(|e| sandbox_error(config, e))?;
+    let network =
+        (sandbox.network == NetworkMode::Isolated).then(namespace::NetworkIsolation::prepare);
+
+    log_sandbox(config, sandbox);
+    Ok(Some(SandboxPlan {
+        cgroup_procs,
+        network,
+        no_new_privs: sandbox.requires_no_new_privs(),
+        landlock,
+        seccomp,
+    }))
+}
+
+#[cfg(target_os = "linux")]
+impl SandboxPlan {
+    /// Registers the plan to run in the child of `command`.
+    #[expect(
+        unsafe_code,
+        reason = "std::os::unix::process::CommandExt::pre_exec is an unsafe fn; the sandbox \
+                  must be entered in the forked child before exec"
+    )]
+    pub fn install(self, command: &mut Command) {
+        use std::os::unix::process::CommandExt;
+
+        // SAFETY: the closure runs in the forked child between `fork` and
+        // `execve`. `apply` only issues syscalls on state prepared in the
+        // parent; it does not allocate, lock, or log.
+        unsafe {
+            command.pre_exec(move || self.apply());
+        }
+    }
+
+    fn apply(&self) -> std::io::Result<()> {
+        use std::os::fd::AsRawFd;
+
+        if let Some(procs) = self.cgroup_procs.as_ref() {
+            cgroup::join(procs.as_raw_fd())?;
+        }
+        if let Some(network) = self.network.as_ref() {
+            network.enter()?;
+        }
+        if self.no_new_privs {
+            set_no_new_privs()?;
+        }
+        if let Some(ruleset) = self.landlock.as_ref() {
+            landlock::restrict_self(ruleset.as_raw_fd())?;
+        }
+        if let Some(filter) = self.seccomp.as_deref() {
+            seccomp::install(filter)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[expect(
+    unsafe_code,
+    reason = "no_new_privs is only reachable through a raw prctl"
+)]
+fn set_no_new_privs() -> std::io::Result<()> {
+    // SAFETY: PR_SET_NO_NEW_PRIVS takes integer arguments only.
+    if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+#[derive(Debug)]
+pub enum SandboxPlan {}
+
+#[cfg(not(target_os = "linux"))]
+pub fn prepare(
+    config: &McpServerConfig,
+    _executable: Option<&Path>,
+) -> McpDomainResult<Option<SandboxPlan>> {
+    match config.sandbox {
+        Some(_) => Err(sandbox_error(
+            config,
+            "process sandboxing is only supported on Linux".to_owned(),
+        )),
+        None => Ok(None),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl SandboxPlan {
+    pub fn install(self, _command: &mut Command) {
+        match self {}
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn log_sandbox(config: &McpServerConfig, sandbox: &systemprompt_models::mcp::SandboxConfig) {
+    tracing::info!(
+        service = %config.name,
+        no_new_privs = sandbox.requires_no_new_privs(),
+        filesystem = sandbox.filesystem.is_some(),
+        seccomp = sandbox.seccomp.as_str(),
+        network = ?sandbox.network,
+        limits = ?sandbox.limits.as_ref().map(limit_values),
+        "Sandboxing MCP server process"
+    );
+}
@@ -0,0 +1,84 @@
+//! Private network namespace for sandboxed servers.
+//!
+//! As root the child unshares only its network namespace. Otherwise it first
+//! enters a user namespace that maps its own uid and gid to themselves, which
+//! is what lets an unprivileged process create the network namespace; files
+//! it creates keep their real owner.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::ffi::CStr;
+
+#[derive(Debug)]
+pub(super) struct NetworkIsolation {
+    user_namespace: bool,
+    uid_map: Vec<u8>,
+    gid_map: Vec<u8>,
+}
+
+impl NetworkIsolation {
+    /// Captures the ids to map while still in the parent, so the child only
+    /// writes prepared bytes.
+    #[expect(
+        unsafe_code,
+        reason = "geteuid/getegid are libc calls with no safe std equivalent"
+    )]
+    pub(super) fn prepare() -> Self {
+        // SAFETY: geteuid and getegid take no arguments and cannot fail.
+        let (uid, gid) = unsafe { (libc::geteuid(), libc::getegid()) };
+        Self {
+            user_namespace: uid != 0,
+            uid_map: format!("{uid} {uid} 1\n").into_bytes(),
+            gid_map: format!("{gid} {gid} 1\n").into_bytes(),
+        }
+    }
+
+    /// Moves the calling process into a fresh network namespace. Runs in the
+    /// forked child and allocates nothing.
+    #[expect(
+        unsafe_code,
+        reason = "unshare is a raw libc call; namespaces have no safe std API"
+    )]
+    pub(super) fn enter(&self) -> std::io::Result<()> {
+        let flags = if self.user_namespace {
+            libc::CLONE_NEWUSER | libc::CLONE_NEWNET
+        } else {
+            libc::CLONE_NEWNET
+        };
+        // SAFETY: unshare takes a flags word and touches no caller memory.
+        if unsafe { libc::unshare(flags) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if self.user_namespace {
+            write_proc_file(c"/proc/self/setgroups", b"deny")?;
+            write_proc_file(c"/proc/self/uid_map", &self.uid_map)?;
+            write_proc_file(c"/proc/self/gid_map", &self.gid_map)?;
+        }
+        Ok(())
+    }
+}
+
+#[expect(
+    unsafe_code,
+    reason = "the forked child may not allocate, so /proc files are written with raw \
+              open/write/close instead of std::fs"
+)]
+fn write_proc_file(path: &CStr, contents: &[u8]) -> std::io::Result<()> {
+    // SAFETY: `path` is NUL-terminated and lives for the call.
+    let fd = unsafe { libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: `contents` is valid for `contents.len()` bytes; `fd` was just
+    // opened and is closed exactly once below.
+    let written = unsafe { libc::write(fd, contents.as_ptr().cast(), contents.len()) };
+    let result = if written < 0 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(())
+    };
+    // SAFETY: `fd` is owned here and not used after this call.
+    unsafe { libc::close(fd) };
+    result
+}
@@ -0,0 +1,142 @@
+//! Seccomp denylist for sandboxed servers.
+//!
+//! The BPF program is assembled in the parent; the forked child only loads
+//! it. Syscalls from a foreign architecture (including x32 on x86-64) are
+//! killed outright, since the denylist is keyed by this architecture's
+//! numbers.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use systemprompt_models::mcp::SeccompProfile;
+
+#[cfg(target_arch = "x86_64")]
+const AUDIT_ARCH: Option<u32> = Some(0xC000_003E);
+#[cfg(target_arch = "aarch64")]
+const AUDIT_ARCH: Option<u32> = Some(0xC000_00B7);
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+const AUDIT_ARCH: Option<u32> = None;
+
+#[cfg(target_arch = "x86_64")]
+const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+const SECCOMP_DATA_NR: u32 = 0;
+const SECCOMP_DATA_ARCH: u32 = 4;
+
+const LD_W_ABS: u16 = (libc::BPF_LD | libc::BPF_W | libc::BPF_ABS) as u16;
+const JEQ_K: u16 = (libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K) as u16;
+#[cfg(target_arch = "x86_64")]
+const JGE_K: u16 = (libc::BPF_JMP | libc::BPF_JGE | libc::BPF_K) as u16;
+const RET_K: u16 = (libc::BPF_RET | libc::BPF_K) as u16;
+
+const DENIED_SYSCALLS: &[libc::c_long] = &[
+    libc::SYS_mount,
+    libc::SYS_umount2,
+    libc::SYS_pivot_root,
+    libc::SYS_fsopen,
+    libc::SYS_fsconfig,
+    libc::SYS_fsmount,
+    libc::SYS_fspick,
+    libc::SYS_move_mount,
+    libc::SYS_open_tree,
+    libc::SYS_swapon,
+    libc::SYS_swapoff,
+    libc::SYS_reboot,
+    libc::SYS_kexec_load,
+    libc::SYS_kexec_file_load,
+    libc::SYS_init_module,
+    libc::SYS_finit_module,
+    libc::SYS_delete_module,
+    libc::SYS_acct,
+    libc::SYS_quotactl,
+    libc::SYS_ptrace,
+    libc::SYS_process_vm_readv,
+    libc::SYS_process_vm_writev,
+    libc::SYS_bpf,
+    libc::SYS_perf_event_open,
+    libc::SYS_userfaultfd,
+    libc::SYS_keyctl,
+    libc::SYS_add_key,
+    libc::SYS_request_key,
+    libc::SYS_setns,
+    libc::SYS_unshare,
+    libc::SYS_open_by_handle_at,
+    libc::SYS_settimeofday,
+    libc::SYS_clock_settime,
+    libc::SYS_clock_adjtime,
+    libc::SYS_adjtimex,
+];
+
+const fn statement(code: u16, k: u32) -> libc::sock_filter {
+    libc::sock_filter {
+        code,
+        jt: 0,
+        jf: 0,
+        k,
+    }
+}
+
+const fn jump(code: u16, k: u32, jt: u8, jf: u8) -> libc::sock_filter {
+    libc::sock_filter { code, jt, jf, k }
+}
+
+/// Assembles the filter for `profile`, or `None` when it is `none`.
+pub(super) fn build_filter(
+    profile: SeccompProfile,
+) -> Result<Option<Vec<libc::sock_filter>>, String> {
+    let deny = match profile {
+        SeccompProfile::None => return Ok(None),
+        SeccompProfile::Default => libc::SECCOMP_RET_ERRNO | (libc::EPERM as u32),
+        SeccompProfile::Strict => libc::SECCOMP_RET_KILL_PROCESS,
+    };
+    let Some(arch) = AUDIT_ARCH else {
+        return Err(format!(
+            "seccomp profile '{}' is not supported on {}",
+            profile.as_str(),
+            std::env::consts::ARCH
+        ));
+    };
+
+    let mut filter = vec![
+        statement(LD_W_ABS, SECCOMP_DATA_ARCH),
+        jump(JEQ_K, arch, 1, 0),
+        statement(RET_K, libc::SECCOMP_RET_KILL_PROCESS),
+        statement(LD_W_ABS, SECCOMP_DATA_NR),
+    ];
+
+    let remaining = DENIED_SYSCALLS.len();
+    #[cfg(target_arch = "x86_64")]
+    filter.push(jump(JGE_K, X32_SYSCALL_BIT, (remaining + 1) as u8, 0));
+    for (index, syscall) in DENIED_SYSCALLS.iter().enumerate() {
+        filter.push(jump(JEQ_K, *syscall as u32, (remaining - index) as u8, 0));
+    }
+    filter.push(statement(RET_K, libc::SECCOMP_RET_ALLOW));
+    filter.push(statement(RET_K, deny));
+    Ok(Some(filter))
+}
+
+/// Loads `filter` into the calling process. Runs in the forked child after
+/// `no_new_privs` is set; it allocates nothing.
+#[expect(
+    unsafe_code,
+    reason = "installing a seccomp filter is a raw prctl taking a pointer to the BPF program"
+)]
+pub(super) fn install(filter: &[libc::sock_filter]) -> std::io::Result<()> {
+    let program = libc::sock_fprog {
+        len: filter.len() as libc::c_ushort,
+        filter: filter.as_ptr().cast_mut(),
+    };
+    // SAFETY: `program` points at `filter`, which outlives the call; the
+    // kernel copies the program and never writes through the pointer.
+    let result = unsafe {
+        libc::prctl(
+            libc::PR_SET_SECCOMP,
+            libc::SECCOMP_MODE_FILTER,
+            &raw const program,
+        )
+    };
+    if result != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
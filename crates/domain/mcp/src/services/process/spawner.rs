@@ -3,7 +3,11 @@
 //! [`spawn_server`] launches an MCP server binary in its own process group with
 //! a sanitised environment (profile, secrets, per-server config, and the SSRF
 //! trust allowlist), redirecting output to a size-rotated log file and
-//! detaching the child so it outlives this call. Also covers binary
+//! detaching the child so it outlives this call. A `stdio` server is launched
+//! as this same executable running `plugins mcp bridge`, which owns the stdio
+//! command and serves it over HTTP on the server's port. A native server's
+//! `sandbox` settings are applied to its binary here; a `stdio` server's are
+//! applied by the bridge to the command it runs. Also covers binary
 //! verification and an on-demand debug build path.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
@@ -15,7 +19,7 @@ use std::fs;
 use std::path::Path;
 use std::process::Command;
 use systemprompt_config::{ProfileBootstrap, SecretsBootstrap};
-use systemprompt_models::{AppPaths, Config, Secrets};
+use systemprompt_models::{AppPaths, BuildPaths, CliPaths, Config, Secrets};
 
 const MAX_LOG_SIZE: u64 = 10 * 1024 * 1024;
 
@@ -160,7 +164,24 @@ pub fn serialize_server_configs(config: &McpServerConfig) -> McpDomainResult<(St
     Ok((tools_config_json, server_model_config_json))
 }
 
-pub fn spawn_server(paths: &AppPaths, config: &McpServerConfig) -> McpDomainResult<u32> {
+fn launch_command(paths: &AppPaths, config: &McpServerConfig) -> McpDomainResult<Command> {
+    if config.is_stdio() {
+        let self_path = BuildPaths::resolve_self().map_err(|e| {
+            crate::error::McpDomainError::Internal(format!(
+                "Failed to resolve the bridge executable for {}: {e}",
+                config.name
+            ))
+        })?;
+        let mut command = Command::new(self_path);
+        command
+            .args(CliPaths::mcp_bridge_args())
+            .arg("--server-name")
+            .arg(&config.name)
+            .arg("--port")
+            .arg(config.port.to_string());
+        return Ok(command);
+    }
+
     let binary_path = paths.build().resolve_binary(&config.binary).map_err(|e| {
         crate::error::McpDomainError::Internal(format!("{}: {e}", {
             format!(
@@ -169,6 +190,15 @@ pub fn spawn_server(paths: &AppPaths, config: &McpServerConfig) -> McpDomainResu
             )
         }))
     })?;
+    let mut command = Command::new(&binary_path);
+    if let Some(plan) = super::sandbox::prepare(config, Some(&binary_path))? {
+        plan.install(&mut command);
+    }
+    Ok(command)
+}
+
+pub fn spawn_server(paths: &AppPaths, config: &McpServerConfig) -> McpDomainResult<u32> {
+    let mut child_command = launch_command(paths, config)?;
 
     let config_global = Config::get()?;
 
@@ -186,7 +216,6 @@ pub fn spawn_server(paths: &AppPaths, config: &McpServerConfig) -> McpDomainResu
         ))
     })?;
 
-    let mut child_command = Command::new(&binary_path);
     configure_environment(
         &mut child_command,
         &SpawnEnvSpec {
@@ -233,6 +262,16 @@ fn place_in_own_process_group(command: &mut Command) {
 }
 
 pub fn verify_binary(paths: &AppPaths, config: &McpServerConfig) -> McpDomainResult<()> {
+    if config.is_stdio() {
+        return match config.stdio.as_ref() {
+            Some(stdio) if !stdio.command.trim().is_empty() => Ok(()),
+            _ => Err(crate::error::McpDomainError::Internal(format!(
+                "{}: stdio server has no command",
+                config.name
+            ))),
+        };
+    }
+
     let binary_path = paths.build().resolve_binary(&config.binary)?;
 
     let metadata = fs::metadata(&binary_path).map_err(|e| {
@@ -253,6 +292,11 @@ pub fn verify_binary(paths: &AppPaths, config: &McpServerConfig) -> McpDomainRes
 }
 
 pub fn build_server(config: &McpServerConfig) -> McpDomainResult<()> {
+    if config.is_stdio() {
+        tracing::debug!(service = %config.name, "Stdio server has nothing to build");
+        return Ok(());
+    }
+
     tracing::info!(service = %config.name, binary = %config.binary, "Building service (debug mode)");
 
     let output = Command::new("cargo")
@@ -54,13 +54,16 @@ impl RegistryService {
 
             let crate_path = match deployment.server_type {
                 McpServerType::Internal => registry.get_path(&deployment.binary)?,
-                McpServerType::External => PathBuf::new(),
+                McpServerType::External | McpServerType::Stdio => PathBuf::new(),
             };
 
-            let display_name = deployment
-                .package
-                .clone()
-                .unwrap_or_else(|| deployment.binary.clone());
+            let display_name = deployment.package.clone().unwrap_or_else(|| {
+                if deployment.binary.is_empty() {
+                    server_name.clone()
+                } else {
+                    deployment.binary.clone()
+                }
+            });
 
             let config = crate::McpServerConfig {
                 name: server_name.clone(),
@@ -86,6 +89,8 @@ impl RegistryService {
                 remote_endpoint: deployment.endpoint.clone().unwrap_or_default(),
                 external_auth: deployment.external_auth.clone(),
                 headers: deployment.headers.clone(),
+                stdio: deployment.stdio.clone(),
+                sandbox: deployment.sandbox.clone(),
             };
             enabled.push(config);
         }
@@ -110,7 +115,7 @@ impl RegistryService {
         Ok(self
             .get_enabled_servers_as_config()?
             .into_iter()
-            .filter(crate::McpServerConfig::is_internal)
+            .filter(crate::McpServerConfig::is_managed)
             .collect())
     }
 
@@ -2,7 +2,7 @@
 //!
 //! [`validate_registry`] runs the full suite of pre-flight checks — port
 //! conflicts, per-server field requirements, OAuth scope coherence, and
-//! internal-vs-external type constraints — before any server is brought up,
+//! internal/external/stdio type constraints — before any server is brought up,
 //! surfacing every failure together rather than aborting on the first.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
@@ -35,7 +35,7 @@ fn validate_port_conflicts(config: &RegistryConfig) -> McpDomainResult<()> {
     let conflicts: Vec<_> = config
         .servers
         .iter()
-        .filter(|s| s.enabled && s.is_internal())
+        .filter(|s| s.enabled && s.is_managed())
         .filter(|s| !seen_ports.insert(s.port))
         .map(|s| format!("{}:{}", s.name, s.port))
         .collect();
@@ -94,6 +94,12 @@ fn validate_single_server(
                 return errors;
             }
         },
+        McpServerType::Stdio => {
+            if server_config.port < 1024 {
+                errors.push(format!("{name}: invalid port {}", server_config.port));
+                return errors;
+            }
+        },
         McpServerType::External => {},
     }
 
@@ -170,5 +176,21 @@ fn validate_server_type_constraints(
             }
             None
         },
+        McpServerType::Stdio => {
+            if server
+                .stdio
+                .as_ref()
+                .is_none_or(|stdio| stdio.command.trim().is_empty())
+            {
+                return Some(format!("{}: stdio server has no command", server.name));
+            }
+            if !server.binary.is_empty() {
+                return Some(format!(
+                    "{}: stdio server should not have a binary",
+                    server.name
+                ));
+            }
+            None
+        },
     }
 }
@@ -0,0 +1,83 @@
+//! Spawning the command behind a `stdio` MCP server.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::path::Path;
+use std::process::Stdio;
+
+use systemprompt_models::mcp::StdioCommand;
+use tokio::process::{Child, Command};
+
+use crate::McpServerConfig;
+use crate::error::{McpDomainError, McpDomainResult};
+use crate::services::process::sandbox;
+
+/// Environment handed to a stdio command: `PATH` and `HOME`, plus exactly
+/// the variables the deployment lists in `env_vars`.
+///
+/// The bridge process itself carries the profile, database URL and secrets;
+/// none of that reaches third-party code unless the operator names it.
+pub fn stdio_environment(
+    config: &McpServerConfig,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Vec<(String, String)> {
+    let mut env = Vec::new();
+    for inherited in ["PATH", "HOME"] {
+        if let Some(value) = lookup(inherited) {
+            env.push((inherited.to_owned(), value));
+        }
+    }
+    for var_name in &config.env_vars {
+        match lookup(var_name) {
+            Some(value) => env.push((var_name.clone(), value)),
+            None => {
+                tracing::warn!(
+                    var = %var_name,
+                    service = %config.name,
+                    "Optional env var not set for stdio MCP server"
+                );
+            },
+        }
+    }
+    env
+}
+
+pub fn spawn_stdio_command(config: &McpServerConfig) -> McpDomainResult<Child> {
+    let stdio = stdio_command(config)?;
+
+    let mut command = Command::new(&stdio.command);
+    command
+        .args(&stdio.args)
+        .env_clear()
+        .envs(stdio_environment(config, |name| std::env::var(name).ok()))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true);
+    if let Some(dir) = stdio.working_dir.as_deref() {
+        command.current_dir(dir);
+    }
+    let executable = Path::new(&stdio.command);
+    if let Some(plan) = sandbox::prepare(config, executable.is_absolute().then_some(executable))? {
+        plan.install(command.as_std_mut());
+    }
+
+    command.spawn().map_err(|e| McpDomainError::ProcessSpawn {
+        server: config.name.clone(),
+        message: format!("failed to run `{}`: {e}", stdio.command),
+    })
+}
+
+fn stdio_command(config: &McpServerConfig) -> McpDomainResult<&StdioCommand> {
+    config
+        .stdio
+        .as_ref()
+        .filter(|stdio| !stdio.command.trim().is_empty())
+        .ok_or_else(|| {
+            McpDomainError::Configuration(format!(
+                "MCP server '{}' has no stdio command to run",
+                config.name
+            ))
+        })
+}
@@ -0,0 +1,219 @@
+//! [`ServerHandler`] that forwards MCP requests to a stdio server.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::Arc;
+
+use rmcp::model::{
+    CallToolRequestParams, CallToolResponse, CompleteRequestParams, CompleteResult,
+    GetPromptRequestParams, GetPromptResponse, Implementation, ListPromptsResult,
+    ListResourceTemplatesResult, ListResourcesResult, ListToolsResult, PaginatedRequestParams,
+    ReadResourceRequestParams, ReadResourceResponse, ServerInfo, ServerPeerInfo,
+};
+use rmcp::service::{RequestContext, RunningService, ServiceError};
+use rmcp::transport::IntoTransport;
+use rmcp::{ErrorData as McpError, RoleClient, RoleServer, ServerHandler, ServiceExt};
+use systemprompt_security::authz::SharedAuthzHook;
+
+use crate::error::{McpDomainError, McpDomainResult};
+use crate::middleware::rbac::enforce_rbac_from_registry;
+
+/// Re-serves one stdio MCP server.
+///
+/// Every request is authorised with [`enforce_rbac_from_registry`] against
+/// the bridged server's registry entry before it is relayed to the child.
+/// Advertised capabilities are the child's, minus the logging, experimental
+/// and extension surfaces the bridge does not relay.
+#[derive(Clone)]
+pub struct StdioBridge {
+    server_name: String,
+    info: ServerInfo,
+    upstream: Arc<RunningService<RoleClient, ()>>,
+    authz_hook: SharedAuthzHook,
+}
+
+impl std::fmt::Debug for StdioBridge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StdioBridge")
+            .field("server_name", &self.server_name)
+            .field("server_info", &self.info.server_info)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StdioBridge {
+    /// Runs the MCP handshake with the stdio server on `transport` (the
+    /// child's stdout and stdin) and returns a bridge to it.
+    pub async fn connect<T, E, A>(
+        server_name: &str,
+        transport: T,
+        authz_hook: SharedAuthzHook,
+    ) -> McpDomainResult<Self>
+    where
+        T: IntoTransport<RoleClient, E, A>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let upstream =
+            ().serve(transport)
+                .await
+                .map_err(|e| McpDomainError::ConnectionFailed {
+                    server: server_name.to_owned(),
+                    message: format!("stdio handshake failed: {e}"),
+                })?;
+        let peer = upstream
+            .peer_info()
+            .ok_or_else(|| McpDomainError::ConnectionFailed {
+                server: server_name.to_owned(),
+                message: "stdio server sent no initialize result".to_owned(),
+            })?;
+
+        Ok(Self {
+            server_name: server_name.to_owned(),
+            info: bridged_info(server_name, &peer),
+            upstream: Arc::new(upstream),
+            authz_hook,
+        })
+    }
+
+    pub fn server_name(&self) -> &str {
+        &self.server_name
+    }
+
+    async fn authorize(&self, context: &RequestContext<RoleServer>) -> Result<(), McpError> {
+        enforce_rbac_from_registry(context, &self.server_name, &self.authz_hook)
+            .await
+            .map(|_| ())
+    }
+}
+
+fn bridged_info(server_name: &str, peer: &ServerPeerInfo) -> ServerInfo {
+    let mut capabilities = peer.capabilities.clone();
+    capabilities.logging = None;
+    capabilities.experimental = None;
+    capabilities.extensions = None;
+
+    let mut info = ServerInfo::new(capabilities)
+        .with_protocol_version(peer.protocol_version.clone())
+        .with_server_info(
+            peer.server_info
+                .clone()
+                .unwrap_or_else(|| Implementation::new(server_name, "stdio")),
+        );
+    if let Some(instructions) = peer.instructions.as_deref() {
+        info = info.with_instructions(instructions);
+    }
+    info
+}
+
+fn upstream_error(server_name: &str, error: ServiceError) -> McpError {
+    match error {
+        ServiceError::McpError(error) => error,
+        other => {
+            tracing::warn!(server = %server_name, error = %other, "Stdio MCP server request failed");
+            McpError::internal_error(format!("stdio server '{server_name}': {other}"), None)
+        },
+    }
+}
+
+impl ServerHandler for StdioBridge {
+    fn get_info(&self) -> ServerInfo {
+        self.info.clone()
+    }
+
+    async fn list_tools(
+        &self,
+        request: Option<PaginatedRequestParams>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        self.authorize(&context).await?;
+        self.upstream
+            .list_tools(request)
+            .await
+            .map_err(|e| upstream_error(&self.server_name, e))
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResponse, McpError> {
+        self.authorize(&context).await?;
+        self.upstream
+            .call_tool_once(request)
+            .await
+            .map_err(|e| upstream_error(&self.server_name, e))
+    }
+
+    async fn list_prompts(
+        &self,
+        request: Option<PaginatedRequestParams>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        self.authorize(&context).await?;
+        self.upstream
+            .list_prompts(request)
+            .await
+            .map_err(|e| upstream_error(&self.server_name, e))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResponse, McpError> {
+        self.authorize(&context).await?;
+        self.upstream
+            .get_prompt_once(request)
+            .await
+            .map_err(|e| upstream_error(&self.server_name, e))
+    }
+
+    async fn list_resources(
+        &self,
+        request: Option<PaginatedRequestParams>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        self.authorize(&context).await?;
+        self.upstream
+            .list_resources(request)
+            .await
+            .map_err(|e| upstream_error(&self.server_name, e))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        request: Option<PaginatedRequestParams>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        self.authorize(&context).await?;
+        self.upstream
+            .list_resource_templates(request)
+            .await
+            .map_err(|e| upstream_error(&self.server_name, e))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResponse, McpError> {
+        self.authorize(&context).await?;
+        self.upstream
+            .read_resource_once(request)
+            .await
+            .map_err(|e| upstream_error(&self.server_name, e))
+    }
+
+    async fn complete(
+        &self,
+        request: CompleteRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, McpError> {
+        self.authorize(&context).await?;
+        self.upstream
+            .complete(request)
+            .await
+            .map_err(|e| upstream_error(&self.server_name, e))
+    }
+}
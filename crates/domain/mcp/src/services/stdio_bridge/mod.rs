@@ -0,0 +1,20 @@
+//! Bridge that serves a stdio-only MCP server over streamable HTTP.
+//!
+//! A `stdio` server is supervised like a native one: the orchestrator
+//! launches `plugins mcp bridge` on the server's port, which spawns the
+//! configured command ([`child`]), speaks MCP to it over its pipes, and
+//! re-serves every request through [`StdioBridge`] with the same per-server
+//! RBAC check native servers run. The proxy in front of the port supplies
+//! OAuth and the audit tap unchanged. The server's `sandbox` settings confine
+//! the command, not the bridge.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+pub mod child;
+mod handler;
+mod runner;
+
+pub use child::{spawn_stdio_command, stdio_environment};
+pub use handler::StdioBridge;
+pub use runner::{StdioBridgeParams, run_stdio_bridge};
@@ -0,0 +1,61 @@
+//! Process entry point for a stdio MCP bridge.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::Arc;
+
+use systemprompt_security::authz::SharedAuthzHook;
+
+use super::child::spawn_stdio_command;
+use super::handler::StdioBridge;
+use crate::error::{McpDomainError, McpDomainResult};
+use crate::repository::McpSessionRepository;
+use crate::{McpHttpConfig, McpServerConfig, create_router};
+
+#[derive(Debug)]
+pub struct StdioBridgeParams {
+    pub config: McpServerConfig,
+    pub session_repository: Arc<McpSessionRepository>,
+    pub authz_hook: SharedAuthzHook,
+}
+
+/// Spawns the stdio command, serves it on `config.port` and runs until the
+/// listener fails or the command exits.
+///
+/// A command that exits is reported as an error so the bridge process dies
+/// with it and health monitoring restarts the pair.
+pub async fn run_stdio_bridge(params: StdioBridgeParams) -> McpDomainResult<()> {
+    let StdioBridgeParams {
+        config,
+        session_repository,
+        authz_hook,
+    } = params;
+
+    let mut child = spawn_stdio_command(&config)?;
+    let (Some(stdout), Some(stdin)) = (child.stdout.take(), child.stdin.take()) else {
+        return Err(McpDomainError::ProcessSpawn {
+            server: config.name.clone(),
+            message: "stdio pipes were not captured".to_owned(),
+        });
+    };
+    let bridge = StdioBridge::connect(&config.name, (stdout, stdin), authz_hook).await?;
+
+    let router = create_router(bridge, session_repository, McpHttpConfig::default());
+    let addr = format!("{}:{}", config.host, config.port);
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    tracing::info!(service = %config.name, %addr, "Stdio MCP bridge listening");
+
+    tokio::select! {
+        served = axum::serve(listener, router) => served.map_err(|e| McpDomainError::Transport(
+            format!("stdio bridge for {} stopped serving: {e}", config.name),
+        )),
+        status = child.wait() => Err(McpDomainError::ProcessSpawn {
+            server: config.name.clone(),
+            message: match status {
+                Ok(status) => format!("stdio command exited: {status}"),
+                Err(e) => format!("failed to wait for stdio command: {e}"),
+            },
+        }),
+    }
+}
@@ -14,6 +14,7 @@ pub fn to_tool_definition(mcp_tool: &McpTool) -> ToolDefinition {
         output_schema: mcp_tool.output_schema.clone(),
         service_id: mcp_tool.service_id.to_string(),
         terminal_on_success: mcp_tool.terminal_on_success,
+        requires_approval: mcp_tool.requires_approval,
         model_config: mcp_tool
             .model_config
             .as_ref()
@@ -0,0 +1,22 @@
+-- RFC 8628 device authorization grant. `/oauth/device_authorization` issues a
+-- device code (kept only as HMAC-SHA-256 under `oauth_at_rest_pepper`, like
+-- authorisation codes) and a short user code the user types into the
+-- verification page. Signing in there sets `user_id`; the device's next poll
+-- of `/oauth/token` claims the row by setting `consumed_at`. `last_polled_at`
+-- and `interval_seconds` back the `slow_down` response.
+CREATE TABLE IF NOT EXISTS oauth_device_codes (
+    device_code_hash TEXT        PRIMARY KEY,
+    user_code        TEXT        NOT NULL UNIQUE,
+    client_id        TEXT        NOT NULL REFERENCES oauth_clients(client_id) ON DELETE CASCADE,
+    scope            TEXT        NOT NULL,
+    resource         TEXT,
+    user_id          TEXT        REFERENCES users(id) ON DELETE CASCADE,
+    interval_seconds INTEGER     NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at       TIMESTAMPTZ NOT NULL,
+    last_polled_at   TIMESTAMPTZ,
+    approved_at      TIMESTAMPTZ,
+    consumed_at      TIMESTAMPTZ,
+    CHECK (expires_at > created_at)
+);
+CREATE INDEX IF NOT EXISTS oauth_device_codes_expires_at_idx ON oauth_device_codes (expires_at);
@@ -0,0 +1,17 @@
+-- RFC 9449 DPoP sender-constrained tokens.
+--
+-- A refresh token issued against a DPoP proof records the proof key's JWK
+-- thumbprint in `dpop_jkt`; the refresh grant only redeems it with a proof
+-- signed by the same key. Proof `jti`s are single-use and share the JTI
+-- revocation table (under a `dpop:` prefix) so replay detection is
+-- authoritative across API instances. Those rows belong to no user, so
+-- `user_id` becomes nullable.
+BEGIN;
+
+ALTER TABLE oauth_refresh_tokens
+    ADD COLUMN IF NOT EXISTS dpop_jkt TEXT;
+
+ALTER TABLE oauth_jti_revocations
+    ALTER COLUMN user_id DROP NOT NULL;
+
+COMMIT;
@@ -0,0 +1,23 @@
+-- Upstream OpenID Connect sign-in. `/oauth/federated/{provider}/start` keeps
+-- the nonce and PKCE verifier it sent upstream, plus the authorization
+-- request the user was on, keyed by an HMAC-SHA-256 digest of the `state`
+-- (like `oauth_state_bindings`). The provider's callback claims the row by
+-- setting `consumed_at`, then resumes that authorization request.
+CREATE TABLE IF NOT EXISTS oauth_federated_logins (
+    state_token_hash      TEXT        PRIMARY KEY,
+    provider_id           TEXT        NOT NULL,
+    nonce                 TEXT        NOT NULL,
+    code_verifier         TEXT        NOT NULL,
+    client_id             TEXT        NOT NULL REFERENCES oauth_clients(client_id) ON DELETE CASCADE,
+    redirect_uri          TEXT        NOT NULL,
+    scope                 TEXT        NOT NULL,
+    client_state          TEXT,
+    code_challenge        TEXT,
+    code_challenge_method TEXT,
+    resource              TEXT,
+    created_at            TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at            TIMESTAMPTZ NOT NULL,
+    consumed_at           TIMESTAMPTZ,
+    CHECK (expires_at > created_at)
+);
+CREATE INDEX IF NOT EXISTS oauth_federated_logins_expires_at_idx ON oauth_federated_logins (expires_at);
@@ -0,0 +1,10 @@
+-- Device authorization consent. A passkey sign-in on the verification page
+-- no longer approves the device code: it records the signed-in user in
+-- `verified_user_id` with a one-time consent token (kept only as an
+-- HMAC-SHA-256 digest), and the consent page's approve or deny action then
+-- sets `user_id` or `denied_at`. A denied code answers the device's poll
+-- with `access_denied`.
+ALTER TABLE oauth_device_codes
+    ADD COLUMN IF NOT EXISTS verified_user_id TEXT REFERENCES users(id) ON DELETE CASCADE;
+ALTER TABLE oauth_device_codes ADD COLUMN IF NOT EXISTS consent_token_hash TEXT;
+ALTER TABLE oauth_device_codes ADD COLUMN IF NOT EXISTS denied_at TIMESTAMPTZ;
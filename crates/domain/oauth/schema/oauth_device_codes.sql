@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS oauth_device_codes (
+    device_code_hash TEXT        PRIMARY KEY,
+    user_code        TEXT        NOT NULL UNIQUE,
+    client_id        TEXT        NOT NULL REFERENCES oauth_clients(client_id) ON DELETE CASCADE,
+    scope            TEXT        NOT NULL,
+    resource         TEXT,
+    user_id          TEXT        REFERENCES users(id) ON DELETE CASCADE,
+    verified_user_id TEXT        REFERENCES users(id) ON DELETE CASCADE,
+    consent_token_hash TEXT,
+    interval_seconds INTEGER     NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at       TIMESTAMPTZ NOT NULL,
+    last_polled_at   TIMESTAMPTZ,
+    approved_at      TIMESTAMPTZ,
+    denied_at        TIMESTAMPTZ,
+    consumed_at      TIMESTAMPTZ,
+    CHECK (expires_at > created_at)
+);
+CREATE INDEX IF NOT EXISTS oauth_device_codes_expires_at_idx ON oauth_device_codes (expires_at);
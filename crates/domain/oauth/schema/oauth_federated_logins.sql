@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS oauth_federated_logins (
+    state_token_hash      TEXT        PRIMARY KEY,
+    provider_id           TEXT        NOT NULL,
+    nonce                 TEXT        NOT NULL,
+    code_verifier         TEXT        NOT NULL,
+    client_id             TEXT        NOT NULL REFERENCES oauth_clients(client_id) ON DELETE CASCADE,
+    redirect_uri          TEXT        NOT NULL,
+    scope                 TEXT        NOT NULL,
+    client_state          TEXT,
+    code_challenge        TEXT,
+    code_challenge_method TEXT,
+    resource              TEXT,
+    created_at            TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at            TIMESTAMPTZ NOT NULL,
+    consumed_at           TIMESTAMPTZ,
+    CHECK (expires_at > created_at)
+);
+CREATE INDEX IF NOT EXISTS oauth_federated_logins_expires_at_idx ON oauth_federated_logins (expires_at);
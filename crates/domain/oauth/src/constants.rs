@@ -15,6 +15,18 @@ pub mod token {
     pub const ANONYMOUS_TOKEN_EXPIRY_SECONDS: i64 = 24 * 3600;
 }
 
+pub mod device {
+    pub const DEVICE_CODE_EXPIRY_SECONDS: i64 = 600;
+    pub const POLL_INTERVAL_SECONDS: i32 = 5;
+    pub const SLOW_DOWN_INCREMENT_SECONDS: i32 = 5;
+    pub const USER_CODE_ALPHABET: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+    pub const USER_CODE_LENGTH: usize = 8;
+}
+
+pub mod federation {
+    pub const LOGIN_EXPIRY_SECONDS: i64 = 600;
+}
+
 pub mod webauthn {
     pub const CHALLENGE_EXPIRY_SECONDS: u64 = 300;
     pub const CLEANUP_INTERVAL_SECONDS: u64 = 300;
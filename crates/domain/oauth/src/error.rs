@@ -91,6 +91,9 @@ pub enum OauthError {
     #[error("crypto error: {0}")]
     Crypto(String),
 
+    #[error("upstream sign-in failed: {0}")]
+    Federation(String),
+
     #[error("CIMD metadata fetch failed: {0}")]
     CimdFetch(String),
 
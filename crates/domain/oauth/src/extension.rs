@@ -86,6 +86,27 @@ fn token_schemas() -> Vec<SchemaDefinition> {
             include_str!("../schema/oauth_auth_codes.sql"),
         )
         .with_required_columns(vec!["code".into(), "client_id".into(), "user_id".into()]),
+        SchemaDefinition::new(
+            "oauth_device_codes",
+            include_str!("../schema/oauth_device_codes.sql"),
+        )
+        .with_required_columns(vec![
+            "device_code_hash".into(),
+            "user_code".into(),
+            "client_id".into(),
+            "expires_at".into(),
+        ]),
+        SchemaDefinition::new(
+            "oauth_federated_logins",
+            include_str!("../schema/oauth_federated_logins.sql"),
+        )
+        .with_required_columns(vec![
+            "state_token_hash".into(),
+            "provider_id".into(),
+            "nonce".into(),
+            "client_id".into(),
+            "expires_at".into(),
+        ]),
         SchemaDefinition::new(
             "oauth_state_bindings",
             include_str!("../schema/oauth_state_bindings.sql"),
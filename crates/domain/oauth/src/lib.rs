@@ -17,8 +17,18 @@
 //!   the client owner's role set, and mints a delegated token whose the `act`
 //!   claim records the calling client. Pre-existing `act` chains on the subject
 //!   token are preserved and chained underneath.
+//! - **RFC 8628 device authorization** — `/oauth/device_authorization` issues a
+//!   device code and a short user code; the user signs in on the verification
+//!   page with their passkey, and the device's poll of `/oauth/token`
+//!   (`authorization_pending`, `slow_down`) then yields tokens. Device codes
+//!   are stored as at-rest digests like authorisation codes.
 //! - **Federated identities** — `find_or_create_federated` provisions a user
 //!   from a trusted-issuer subject token on first appearance.
+//! - **Upstream `OpenID` Connect sign-in** — the browser sign-in page offers
+//!   the providers in `profile.security.oidc_providers` (Keycloak, Entra ID,
+//!   Google Workspace, a mock `IdP`). The callback validates the ID token
+//!   through a shared JWKS cache, provisions the user just in time, and maps
+//!   the provider's role claim onto RBAC roles.
 //! - **`WebAuthn`** — passkey registration and authentication backed by
 //!   `webauthn-rs`.
 //! - **JWT** — admin and anonymous-session token generation; tokens are signed
@@ -76,6 +76,7 @@ pub enum GrantType {
     ClientCredentials,
     TokenExchange,
     JwtBearer,
+    DeviceCode,
 }
 
 impl_str_enum!(GrantType, GrantType, {
@@ -84,6 +85,7 @@ impl_str_enum!(GrantType, GrantType, {
     ClientCredentials => "client_credentials",
     TokenExchange => "urn:ietf:params:oauth:grant-type:token-exchange",
     JwtBearer => "urn:ietf:params:oauth:grant-type:jwt-bearer",
+    DeviceCode => "urn:ietf:params:oauth:grant-type:device_code",
 });
 
 impl GrantType {
@@ -19,8 +19,10 @@ pub use client::{
 };
 pub use exchange_code::CreateExchangeCodeParams;
 pub use oauth::{
-    AuthCodeParams, AuthCodeValidationResult, JtiRevocationCache, OAuthRepository,
-    RefreshTokenParams, StateBindingParams, StateBindingRow,
+    AuthCodeParams, AuthCodeValidationResult, DecidedDeviceCode, DeviceCodeGrant, DeviceCodeParams,
+    DeviceCodePoll, FederatedLogin, FederatedLoginParams, JtiRevocationCache, OAuthRepository,
+    PendingAuthorization, PendingDeviceCode, RefreshTokenParams, StateBindingParams,
+    StateBindingRow,
 };
 pub use setup_token::{
     CreateSetupTokenParams, SetupTokenPurpose, SetupTokenRecord, TokenValidationResult,
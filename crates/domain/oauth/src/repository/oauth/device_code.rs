@@ -0,0 +1,288 @@
+//! RFC 8628 device-code persistence. The device code is stored as an
+//! HMAC-SHA-256 digest under the deployment pepper, like authorisation codes;
+//! the user code is stored as issued because a person has to type it.
+//!
+//! A row moves pending → verified (a passkey sign-in on the verification page
+//! sets `verified_user_id` and a one-time consent token, stored hashed) →
+//! approved (the consent page's approve action sets `user_id`) or denied (its
+//! deny action sets `denied_at`). An approved row is then consumed: the
+//! device's poll claims it with a single conditional UPDATE, so two concurrent
+//! polls cannot both receive tokens. [`DeviceCodePoll`] is what a poll of the
+//! token endpoint observes.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use super::OAuthRepository;
+use super::at_rest::hash_at_rest;
+use crate::constants::device::SLOW_DOWN_INCREMENT_SECONDS;
+use crate::error::OauthResult;
+use chrono::{DateTime, Duration, Utc};
+use systemprompt_identifiers::{ClientId, UserId};
+
+#[derive(Debug)]
+pub struct DeviceCodeParams<'a> {
+    pub device_code: &'a str,
+    pub user_code: &'a str,
+    pub client_id: &'a ClientId,
+    pub scope: &'a str,
+    pub resource: Option<&'a str>,
+    pub interval_seconds: i32,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A device code still waiting for a user, as shown on the verification page.
+#[derive(Debug, Clone)]
+pub struct PendingDeviceCode {
+    pub client_id: ClientId,
+    pub scope: String,
+    pub resource: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// The device code a consent action decided, and the user who decided it.
+#[derive(Debug, Clone)]
+pub struct DecidedDeviceCode {
+    pub client_id: ClientId,
+    pub user_id: UserId,
+}
+
+/// What the user approved, handed to token issuance on the winning poll.
+#[derive(Debug, Clone)]
+pub struct DeviceCodeGrant {
+    pub user_id: UserId,
+    pub scope: String,
+    pub resource: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum DeviceCodePoll {
+    /// Unknown code, another client's code, or one already redeemed.
+    Invalid,
+    Expired,
+    Pending,
+    /// Polled faster than the current interval; the interval has been raised.
+    SlowDown,
+    /// The user denied the device on the consent page.
+    Denied,
+    Approved(DeviceCodeGrant),
+}
+
+impl OAuthRepository {
+    /// Inserts the device code, returning `false` when the user code collides
+    /// with a row that has not been cleaned up yet.
+    pub async fn store_device_code(&self, params: DeviceCodeParams<'_>) -> OauthResult<bool> {
+        let device_code_hash = hash_at_rest(params.device_code)?;
+        let result = sqlx::query!(
+            "INSERT INTO oauth_device_codes
+             (device_code_hash, user_code, client_id, scope, resource, interval_seconds,
+              created_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, now(), $7)
+             ON CONFLICT (user_code) DO NOTHING",
+            device_code_hash,
+            params.user_code,
+            params.client_id.as_str(),
+            params.scope,
+            params.resource,
+            params.interval_seconds,
+            params.expires_at,
+        )
+        .execute(self.write_pool_ref())
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn find_pending_device_code(
+        &self,
+        user_code: &str,
+    ) -> OauthResult<Option<PendingDeviceCode>> {
+        let row = sqlx::query!(
+            "SELECT client_id, scope, resource, expires_at
+               FROM oauth_device_codes
+              WHERE user_code = $1
+                AND user_id IS NULL
+                AND denied_at IS NULL
+                AND consumed_at IS NULL
+                AND expires_at > now()",
+            user_code,
+        )
+        .fetch_optional(self.pool_ref())
+        .await?;
+
+        Ok(row.map(|r| PendingDeviceCode {
+            client_id: ClientId::new(r.client_id),
+            scope: r.scope,
+            resource: r.resource,
+            expires_at: r.expires_at,
+        }))
+    }
+
+    /// Records the user who signed in on the verification page against a
+    /// pending device code, with the one-time `consent_token` their approve
+    /// or deny action must present. A later sign-in replaces both. `false`
+    /// means the code is unknown, expired, or already decided.
+    pub async fn verify_device_code(
+        &self,
+        user_code: &str,
+        user_id: &UserId,
+        consent_token: &str,
+    ) -> OauthResult<bool> {
+        let consent_token_hash = hash_at_rest(consent_token)?;
+        let result = sqlx::query!(
+            "UPDATE oauth_device_codes
+                SET verified_user_id = $2, consent_token_hash = $3
+              WHERE user_code = $1
+                AND user_id IS NULL
+                AND denied_at IS NULL
+                AND consumed_at IS NULL
+                AND expires_at > now()",
+            user_code,
+            user_id.as_str(),
+            consent_token_hash,
+        )
+        .execute(self.write_pool_ref())
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Approves a verified device code on behalf of the user who signed in,
+    /// spending the consent token. `None` means the token does not match a
+    /// verified code that is still undecided and unexpired.
+    pub async fn approve_device_code(
+        &self,
+        user_code: &str,
+        consent_token: &str,
+    ) -> OauthResult<Option<DecidedDeviceCode>> {
+        let consent_token_hash = hash_at_rest(consent_token)?;
+        let row = sqlx::query!(
+            r#"UPDATE oauth_device_codes
+                SET user_id = verified_user_id, approved_at = now(), consent_token_hash = NULL
+              WHERE user_code = $1
+                AND consent_token_hash = $2
+                AND verified_user_id IS NOT NULL
+                AND user_id IS NULL
+                AND denied_at IS NULL
+                AND consumed_at IS NULL
+                AND expires_at > now()
+              RETURNING client_id, user_id AS "user_id!""#,
+            user_code,
+            consent_token_hash,
+        )
+        .fetch_optional(self.write_pool_ref())
+        .await?;
+
+        Ok(row.map(|r| DecidedDeviceCode {
+            client_id: ClientId::new(r.client_id),
+            user_id: UserId::new(r.user_id),
+        }))
+    }
+
+    /// Denies a verified device code, spending the consent token; the
+    /// device's next poll receives `access_denied`. `None` as for
+    /// [`Self::approve_device_code`].
+    pub async fn deny_device_code(
+        &self,
+        user_code: &str,
+        consent_token: &str,
+    ) -> OauthResult<Option<DecidedDeviceCode>> {
+        let consent_token_hash = hash_at_rest(consent_token)?;
+        let row = sqlx::query!(
+            r#"UPDATE oauth_device_codes
+                SET denied_at = now(), consent_token_hash = NULL
+              WHERE user_code = $1
+                AND consent_token_hash = $2
+                AND verified_user_id IS NOT NULL
+                AND user_id IS NULL
+                AND denied_at IS NULL
+                AND consumed_at IS NULL
+                AND expires_at > now()
+              RETURNING client_id, verified_user_id AS "verified_user_id!""#,
+            user_code,
+            consent_token_hash,
+        )
+        .fetch_optional(self.write_pool_ref())
+        .await?;
+
+        Ok(row.map(|r| DecidedDeviceCode {
+            client_id: ClientId::new(r.client_id),
+            user_id: UserId::new(r.verified_user_id),
+        }))
+    }
+
+    pub async fn poll_device_code(
+        &self,
+        device_code: &str,
+        client_id: &ClientId,
+    ) -> OauthResult<DeviceCodePoll> {
+        let device_code_hash = hash_at_rest(device_code)?;
+        let now = Utc::now();
+
+        let row = sqlx::query!(
+            "SELECT client_id, user_id, scope, resource, interval_seconds, expires_at,
+                    last_polled_at, denied_at, consumed_at
+               FROM oauth_device_codes
+              WHERE device_code_hash = $1",
+            device_code_hash,
+        )
+        .fetch_optional(self.write_pool_ref())
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(DeviceCodePoll::Invalid);
+        };
+        if row.client_id != client_id.as_str() || row.consumed_at.is_some() {
+            return Ok(DeviceCodePoll::Invalid);
+        }
+        if row.expires_at <= now {
+            return Ok(DeviceCodePoll::Expired);
+        }
+        if row.denied_at.is_some() {
+            return Ok(DeviceCodePoll::Denied);
+        }
+
+        if let Some(user_id) = row.user_id {
+            let claimed = sqlx::query!(
+                "UPDATE oauth_device_codes
+                    SET consumed_at = $2
+                  WHERE device_code_hash = $1 AND consumed_at IS NULL",
+                device_code_hash,
+                now,
+            )
+            .execute(self.write_pool_ref())
+            .await?;
+            if claimed.rows_affected() != 1 {
+                return Ok(DeviceCodePoll::Invalid);
+            }
+            return Ok(DeviceCodePoll::Approved(DeviceCodeGrant {
+                user_id: UserId::new(user_id),
+                scope: row.scope,
+                resource: row.resource,
+            }));
+        }
+
+        let too_fast = row
+            .last_polled_at
+            .is_some_and(|last| now - last < Duration::seconds(i64::from(row.interval_seconds)));
+        let interval_seconds = if too_fast {
+            row.interval_seconds + SLOW_DOWN_INCREMENT_SECONDS
+        } else {
+            row.interval_seconds
+        };
+        sqlx::query!(
+            "UPDATE oauth_device_codes
+                SET last_polled_at = $2, interval_seconds = $3
+              WHERE device_code_hash = $1",
+            device_code_hash,
+            now,
+            interval_seconds,
+        )
+        .execute(self.write_pool_ref())
+        .await?;
+
+        Ok(if too_fast {
+            DeviceCodePoll::SlowDown
+        } else {
+            DeviceCodePoll::Pending
+        })
+    }
+}
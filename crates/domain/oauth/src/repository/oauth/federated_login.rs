@@ -0,0 +1,114 @@
+//! Pending upstream `OpenID` Connect sign-ins.
+//!
+//! Starting a federated sign-in stores the `nonce` and PKCE verifier sent to
+//! the upstream provider together with the authorization request the user
+//! was on, keyed by an HMAC-SHA-256 digest of the `state` token under the
+//! deployment pepper. The callback consumes the row with a single
+//! conditional UPDATE, so a `state` value works once, for the provider it was
+//! issued for, within the TTL.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use super::OAuthRepository;
+use super::at_rest::hash_at_rest;
+use crate::error::OauthResult;
+use chrono::{DateTime, Utc};
+use systemprompt_identifiers::ClientId;
+
+/// The authorization request a federated sign-in resumes once the upstream
+/// provider has vouched for the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingAuthorization {
+    pub client_id: ClientId,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub resource: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct FederatedLoginParams<'a> {
+    pub state_token: &'a str,
+    pub provider_id: &'a str,
+    pub nonce: &'a str,
+    pub code_verifier: &'a str,
+    pub authorization: &'a PendingAuthorization,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FederatedLogin {
+    pub nonce: String,
+    pub code_verifier: String,
+    pub authorization: PendingAuthorization,
+}
+
+impl OAuthRepository {
+    pub async fn store_federated_login(&self, params: FederatedLoginParams<'_>) -> OauthResult<()> {
+        let state_token_hash = hash_at_rest(params.state_token)?;
+        let authorization = params.authorization;
+        sqlx::query!(
+            "INSERT INTO oauth_federated_logins
+             (state_token_hash, provider_id, nonce, code_verifier, client_id, redirect_uri,
+              scope, client_state, code_challenge, code_challenge_method, resource,
+              created_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, now(), $12)",
+            state_token_hash,
+            params.provider_id,
+            params.nonce,
+            params.code_verifier,
+            authorization.client_id.as_str(),
+            authorization.redirect_uri,
+            authorization.scope,
+            authorization.state,
+            authorization.code_challenge,
+            authorization.code_challenge_method,
+            authorization.resource,
+            params.expires_at,
+        )
+        .execute(self.write_pool_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Claims the sign-in `state_token` was issued for. `None` covers an
+    /// unknown, expired, already used, or other-provider `state`.
+    pub async fn consume_federated_login(
+        &self,
+        state_token: &str,
+        provider_id: &str,
+    ) -> OauthResult<Option<FederatedLogin>> {
+        let state_token_hash = hash_at_rest(state_token)?;
+        let row = sqlx::query!(
+            "UPDATE oauth_federated_logins
+                SET consumed_at = now()
+              WHERE state_token_hash = $1
+                AND provider_id = $2
+                AND consumed_at IS NULL
+                AND expires_at > now()
+              RETURNING nonce, code_verifier, client_id, redirect_uri, scope, client_state,
+                        code_challenge, code_challenge_method, resource",
+            state_token_hash,
+            provider_id,
+        )
+        .fetch_optional(self.write_pool_ref())
+        .await?;
+
+        Ok(row.map(|r| FederatedLogin {
+            nonce: r.nonce,
+            code_verifier: r.code_verifier,
+            authorization: PendingAuthorization {
+                client_id: ClientId::new(r.client_id),
+                redirect_uri: r.redirect_uri,
+                scope: r.scope,
+                state: r.client_state,
+                code_challenge: r.code_challenge,
+                code_challenge_method: r.code_challenge_method,
+                resource: r.resource,
+            },
+        }))
+    }
+}
@@ -3,6 +3,11 @@
 //! authenticated request. The `exp` column carries the JWT's original expiry
 //! so cleanup can drop rows that are no longer load-bearing.
 //!
+//! RFC 9449 `DPoP` proofs are single-use, so the same table doubles as their
+//! replay store: [`OAuthRepository::record_dpop_proof`] claims a
+//! `dpop:`-prefixed row (with no `user_id`) per proof and reports whether the
+//! claim was new.
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
@@ -75,6 +80,25 @@ impl OAuthRepository {
         Ok(inserted)
     }
 
+    pub async fn record_dpop_proof(
+        &self,
+        jkt: &str,
+        proof_jti: &str,
+        exp: DateTime<Utc>,
+    ) -> OauthResult<bool> {
+        let key = format!("dpop:{jkt}:{proof_jti}");
+        let result = sqlx::query!(
+            "INSERT INTO oauth_jti_revocations (jti, exp)
+             VALUES ($1, $2)
+             ON CONFLICT (jti) DO NOTHING",
+            key,
+            exp,
+        )
+        .execute(self.write_pool_ref())
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
     pub async fn cleanup_expired_jti_revocations(&self) -> OauthResult<u64> {
         let result = sqlx::query!("DELETE FROM oauth_jti_revocations WHERE exp < now()")
             .execute(self.write_pool_ref())
@@ -7,6 +7,8 @@
 mod at_rest;
 mod auth_code;
 mod cleanup;
+mod device_code;
+mod federated_login;
 mod id_jag_replay;
 mod jti_revocation;
 mod refresh_token;
@@ -15,6 +17,10 @@ mod state_binding;
 mod user;
 
 pub use auth_code::{AuthCodeParams, AuthCodeValidationResult};
+pub use device_code::{
+    DecidedDeviceCode, DeviceCodeGrant, DeviceCodeParams, DeviceCodePoll, PendingDeviceCode,
+};
+pub use federated_login::{FederatedLogin, FederatedLoginParams, PendingAuthorization};
 pub use jti_revocation::JtiRevocationCache;
 pub use refresh_token::RefreshTokenParams;
 pub use state_binding::{StateBindingParams, StateBindingRow};
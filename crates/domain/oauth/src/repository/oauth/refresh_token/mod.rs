@@ -3,7 +3,8 @@
 //! values never touch the database. Consumed tokens are retained as
 //! tombstones (`consumed_at IS NOT NULL`) so a replay can be distinguished
 //! from "token never existed" and trigger family-wide revocation per
-//! RFC 6819 §5.2.2.3.
+//! RFC 6819 §5.2.2.3. A token issued against a `DPoP` proof keeps the proof
+//! key's thumbprint and only redeems with a proof from that key.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -20,6 +21,7 @@ pub struct RefreshTokenParams<'a> {
     pub scope: &'a str,
     pub expires_at: i64,
     pub family_id: Option<&'a str>,
+    pub dpop_jkt: Option<&'a str>,
 }
 
 #[derive(Debug)]
@@ -30,6 +32,7 @@ pub struct RefreshTokenParamsBuilder<'a> {
     scope: &'a str,
     expires_at: i64,
     family_id: Option<&'a str>,
+    dpop_jkt: Option<&'a str>,
 }
 
 impl<'a> RefreshTokenParamsBuilder<'a> {
@@ -47,6 +50,7 @@ impl<'a> RefreshTokenParamsBuilder<'a> {
             scope,
             expires_at,
             family_id: None,
+            dpop_jkt: None,
         }
     }
 
@@ -55,6 +59,11 @@ impl<'a> RefreshTokenParamsBuilder<'a> {
         self
     }
 
+    pub const fn with_dpop_jkt(mut self, dpop_jkt: &'a str) -> Self {
+        self.dpop_jkt = Some(dpop_jkt);
+        self
+    }
+
     pub const fn build(self) -> RefreshTokenParams<'a> {
         RefreshTokenParams {
             token_id: self.token_id,
@@ -63,6 +72,7 @@ impl<'a> RefreshTokenParamsBuilder<'a> {
             scope: self.scope,
             expires_at: self.expires_at,
             family_id: self.family_id,
+            dpop_jkt: self.dpop_jkt,
         }
     }
 }
@@ -84,4 +94,5 @@ pub struct ConsumedRefreshToken {
     pub user_id: UserId,
     pub scope: String,
     pub family_id: String,
+    pub dpop_jkt: Option<String>,
 }
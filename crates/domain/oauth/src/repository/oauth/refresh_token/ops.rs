@@ -24,15 +24,16 @@ impl OAuthRepository {
 
         sqlx::query!(
             "INSERT INTO oauth_refresh_tokens (token_id, client_id, user_id, scope, expires_at, \
-             created_at, family_id)
-             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+             created_at, family_id, dpop_jkt)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
             token_id_hash,
             client_id,
             user_id,
             params.scope,
             expires_at_dt,
             now,
-            family_id
+            family_id,
+            params.dpop_jkt
         )
         .execute(self.write_pool_ref())
         .await?;
@@ -98,6 +99,22 @@ impl OAuthRepository {
         &self,
         token_id: &RefreshTokenId,
         client_id: &ClientId,
+    ) -> OauthResult<ConsumedRefreshToken> {
+        self.consume_bound_refresh_token(token_id, client_id, None)
+            .await
+    }
+
+    /// Claims a refresh token presented alongside a `DPoP` proof for
+    /// `dpop_jkt`.
+    ///
+    /// A token issued against a `DPoP` key only redeems with a proof from the
+    /// same key; a mismatch (or a missing proof) leaves the row untouched and
+    /// reports it as invalid without revoking the family.
+    pub async fn consume_bound_refresh_token(
+        &self,
+        token_id: &RefreshTokenId,
+        client_id: &ClientId,
+        dpop_jkt: Option<&str>,
     ) -> OauthResult<ConsumedRefreshToken> {
         let now = Utc::now();
         let token_id_hash = hash_at_rest(token_id.as_str())?;
@@ -109,10 +126,12 @@ impl OAuthRepository {
              WHERE token_id = $2 AND client_id = $3
                AND consumed_at IS NULL
                AND expires_at >= $1
-             RETURNING user_id, scope, family_id",
+               AND (dpop_jkt IS NULL OR dpop_jkt = $4)
+             RETURNING user_id, scope, family_id, dpop_jkt",
             now,
             token_id_hash,
-            client_id_str
+            client_id_str,
+            dpop_jkt
         )
         .fetch_optional(self.write_pool_ref())
         .await?;
@@ -122,6 +141,7 @@ impl OAuthRepository {
                 user_id: UserId::new(row.user_id),
                 scope: row.scope,
                 family_id: row.family_id,
+                dpop_jkt: row.dpop_jkt,
             });
         }
 
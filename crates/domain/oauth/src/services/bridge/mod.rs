@@ -170,6 +170,7 @@ fn build_bridge_jwt_config(auth_user: &AuthenticatedUser, ttl_hours: i64) -> Jwt
         expires_in_hours: Some(ttl_hours),
         resource: None,
         plugin_id: None,
+        dpop_jkt: None,
     }
 }
 
@@ -0,0 +1,114 @@
+//! Authorization-code exchange at the upstream token endpoint.
+//!
+//! The request authenticates with `client_secret_post` when the provider has
+//! a `client_secret` configured and always carries the PKCE verifier. Only
+//! the ID token is kept; the upstream access and refresh tokens are not
+//! needed once the user's identity is known.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use reqwest::Client;
+use serde::Deserialize;
+use systemprompt_config::SecretsBootstrap;
+use systemprompt_models::net::HTTP_AUTH_VERIFY_TIMEOUT;
+use systemprompt_models::profile::OidcProvider;
+
+use crate::error::{OauthError, OauthResult};
+
+/// The code returned to our callback and what is needed to redeem it.
+#[derive(Debug, Clone, Copy)]
+pub struct UpstreamCodeExchange<'a> {
+    pub code: &'a str,
+    pub redirect_uri: &'a str,
+    pub code_verifier: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpstreamTokenResponse {
+    #[serde(default)]
+    id_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpstreamTokenError {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// Redeems the code and returns the raw ID token.
+pub async fn exchange_upstream_code(
+    provider: &OidcProvider,
+    exchange: UpstreamCodeExchange<'_>,
+) -> OauthResult<String> {
+    let token_endpoint = provider.token_endpoint().ok_or_else(|| {
+        OauthError::Config(format!(
+            "OIDC provider '{}' has no token endpoint",
+            provider.id
+        ))
+    })?;
+    let client_secret = resolve_client_secret(provider)?;
+
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", exchange.code),
+        ("redirect_uri", exchange.redirect_uri),
+        ("client_id", provider.client_id.as_str()),
+        ("code_verifier", exchange.code_verifier),
+    ];
+    if let Some(secret) = client_secret.as_deref() {
+        form.push(("client_secret", secret));
+    }
+
+    let http = Client::builder()
+        .timeout(HTTP_AUTH_VERIFY_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|e| OauthError::Internal(format!("Failed to build HTTP client: {e}")))?;
+    let response = http
+        .post(&token_endpoint)
+        .header("Accept", "application/json")
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| OauthError::Federation(format!("token endpoint unreachable: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let detail = response.json::<UpstreamTokenError>().await.map_or_else(
+            |_e| format!("HTTP {status}"),
+            |err| match err.error_description {
+                Some(description) => format!("{}: {description}", err.error),
+                None => err.error,
+            },
+        );
+        return Err(OauthError::Federation(format!(
+            "code exchange rejected: {detail}"
+        )));
+    }
+
+    response
+        .json::<UpstreamTokenResponse>()
+        .await
+        .map_err(|e| OauthError::Federation(format!("unreadable token response: {e}")))?
+        .id_token
+        .ok_or_else(|| OauthError::Federation("token response carried no id_token".to_owned()))
+}
+
+fn resolve_client_secret(provider: &OidcProvider) -> OauthResult<Option<String>> {
+    let Some(name) = provider.client_secret.as_ref() else {
+        return Ok(None);
+    };
+    SecretsBootstrap::get()?
+        .get(name.as_str())
+        .cloned()
+        .map(Some)
+        .ok_or_else(|| {
+            OauthError::Config(format!(
+                "OIDC provider '{}' client secret '{}' is not configured",
+                provider.id,
+                name.as_str()
+            ))
+        })
+}
@@ -0,0 +1,163 @@
+//! Upstream ID-token validation.
+//!
+//! The token must be RS256-signed under a `kid` published at the provider's
+//! JWKS URI, issued by the configured issuer to our `client_id`, unexpired,
+//! and carry the `nonce` this server sent with the authorization request.
+//! Keys come from one process-wide [`JwksClient`], so its per-issuer cache
+//! serves every sign-in rather than being rebuilt on each callback.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::OnceLock;
+
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
+use serde_json::{Map, Value};
+use subtle::ConstantTimeEq;
+use systemprompt_models::profile::OidcProvider;
+use systemprompt_security::keys::JwksClient;
+
+use crate::error::{OauthError, OauthResult};
+
+static UPSTREAM_JWKS: OnceLock<JwksClient> = OnceLock::new();
+
+/// The identity an upstream provider vouched for.
+#[derive(Debug, Clone, Default)]
+pub struct UpstreamIdentity {
+    pub issuer: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+    pub hosted_domain: Option<String>,
+    pub upstream_roles: Vec<String>,
+}
+
+/// The shared JWKS client, allowed to fetch from the JWKS host of every
+/// configured provider. The allowlist is fixed on first use, like the
+/// profile it comes from.
+pub fn upstream_jwks(providers: &[OidcProvider]) -> &'static JwksClient {
+    UPSTREAM_JWKS.get_or_init(|| {
+        let hosts = providers
+            .iter()
+            .filter_map(OidcProvider::jwks_uri)
+            .filter_map(|uri| reqwest::Url::parse(&uri).ok())
+            .filter_map(|url| url.host_str().map(str::to_owned))
+            .collect();
+        JwksClient::new(hosts)
+    })
+}
+
+pub async fn validate_upstream_id_token(
+    provider: &OidcProvider,
+    jwks: &JwksClient,
+    id_token: &str,
+    expected_nonce: &str,
+) -> OauthResult<UpstreamIdentity> {
+    let header = decode_header(id_token).map_err(|e| rejected(&e))?;
+    if header.alg != Algorithm::RS256 {
+        return Err(OauthError::Federation(format!(
+            "ID token signed with `{:?}`, expected `RS256`",
+            header.alg
+        )));
+    }
+    let kid = header
+        .kid
+        .ok_or_else(|| OauthError::Federation("ID token is missing the `kid` header".to_owned()))?;
+
+    let jwks_uri = provider.jwks_uri().ok_or_else(|| {
+        OauthError::Config(format!("OIDC provider '{}' has no JWKS URI", provider.id))
+    })?;
+    let jwk = jwks
+        .fetch_at(&provider.issuer, &jwks_uri, &kid)
+        .await
+        .map_err(|e| OauthError::Federation(format!("JWKS resolution failed: {e}")))?;
+    let key = DecodingKey::from_rsa_components(&jwk.n, &jwk.e).map_err(|e| rejected(&e))?;
+
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_issuer(&[&provider.issuer]);
+    validation.set_audience(&[&provider.client_id]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+    let claims = decode::<Map<String, Value>>(id_token, &key, &validation)
+        .map_err(|e| rejected(&e))?
+        .claims;
+
+    let nonce_matches: bool = claims
+        .get("nonce")
+        .and_then(Value::as_str)
+        .is_some_and(|nonce| nonce.as_bytes().ct_eq(expected_nonce.as_bytes()).into());
+    if !nonce_matches {
+        return Err(OauthError::Federation(
+            "ID token nonce does not match the sign-in request".to_owned(),
+        ));
+    }
+
+    identity_from_claims(provider, &claims)
+}
+
+// Why: a bad upstream token is a failed sign-in for the user, not a fault in
+// this server's own tokens, so it is reported as such.
+fn rejected(err: &jsonwebtoken::errors::Error) -> OauthError {
+    OauthError::Federation(format!("ID token rejected: {err}"))
+}
+
+fn identity_from_claims(
+    provider: &OidcProvider,
+    claims: &Map<String, Value>,
+) -> OauthResult<UpstreamIdentity> {
+    let text = |name: &str| {
+        claims
+            .get(name)
+            .and_then(Value::as_str)
+            .filter(|value| !value.is_empty())
+            .map(str::to_owned)
+    };
+    let subject =
+        text("sub").ok_or_else(|| OauthError::Federation("ID token has no subject".to_owned()))?;
+
+    Ok(UpstreamIdentity {
+        issuer: provider.issuer.clone(),
+        subject,
+        email: text("email"),
+        email_verified: claims.get("email_verified").is_some_and(is_true),
+        name: text("name"),
+        preferred_username: text("preferred_username"),
+        hosted_domain: text("hd"),
+        upstream_roles: provider
+            .role_claim()
+            .map(|path| claim_values(claims, path))
+            .unwrap_or_default(),
+    })
+}
+
+// Why: some providers send `email_verified` as the string "true".
+fn is_true(value: &Value) -> bool {
+    value
+        .as_bool()
+        .unwrap_or_else(|| value.as_str() == Some("true"))
+}
+
+/// The string values at a dotted claim path; a single string counts as one
+/// value, an array contributes its string members.
+#[must_use]
+pub fn claim_values(claims: &Map<String, Value>, path: &str) -> Vec<String> {
+    let mut segments = path.split('.');
+    let Some(first) = segments.next() else {
+        return Vec::new();
+    };
+    let mut found = claims.get(first);
+    for segment in segments {
+        found = found.and_then(|value| value.get(segment));
+    }
+    match found {
+        Some(Value::String(value)) => vec![value.clone()],
+        Some(Value::Array(values)) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_owned)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
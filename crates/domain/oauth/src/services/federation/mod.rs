@@ -0,0 +1,188 @@
+//! Upstream `OpenID` Connect sign-in for the browser authorization flow.
+//!
+//! A provider from `security.oidc_providers` authenticates the user with the
+//! authorization-code flow (PKCE and `nonce` on every request). The callback
+//! exchanges the code ([`exchange`]), validates the returned ID token against
+//! the provider's keys through the process-wide JWKS cache ([`id_token`]),
+//! and [`sign_in_federated`] then provisions or links the account through
+//! `find_or_create_federated` and applies the provider's claim-to-role
+//! mapping ([`roles`]).
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+pub mod exchange;
+pub mod id_token;
+pub mod roles;
+
+use base64::Engine;
+use rand::distr::Alphanumeric;
+use rand::{RngExt, rng};
+use sha2::{Digest, Sha256};
+use systemprompt_identifiers::UserId;
+use systemprompt_models::profile::{OidcProvider, OidcProviderKind};
+use systemprompt_traits::FederatedIdentityClaims;
+
+use crate::error::{OauthError, OauthResult};
+use crate::state::OAuthState;
+
+pub use exchange::{UpstreamCodeExchange, exchange_upstream_code};
+pub use id_token::{UpstreamIdentity, claim_values, upstream_jwks, validate_upstream_id_token};
+pub use roles::{map_upstream_roles, reconcile_roles};
+
+const PKCE_VERIFIER_LENGTH: usize = 64;
+
+/// A PKCE verifier and its `S256` challenge for the upstream request.
+#[derive(Debug, Clone)]
+pub struct UpstreamPkce {
+    pub verifier: String,
+    pub challenge: String,
+}
+
+impl UpstreamPkce {
+    #[must_use]
+    pub fn generate() -> Self {
+        let mut rng = rng();
+        let verifier: String = (0..PKCE_VERIFIER_LENGTH)
+            .map(|_| rng.sample(Alphanumeric))
+            .map(char::from)
+            .collect();
+        let challenge = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(Sha256::digest(verifier.as_bytes()));
+        Self {
+            verifier,
+            challenge,
+        }
+    }
+}
+
+/// The upstream authorization request parameters for one sign-in attempt.
+#[derive(Debug, Clone, Copy)]
+pub struct UpstreamAuthorizationRequest<'a> {
+    pub redirect_uri: &'a str,
+    pub state: &'a str,
+    pub nonce: &'a str,
+    pub code_challenge: &'a str,
+}
+
+pub fn upstream_authorization_url(
+    provider: &OidcProvider,
+    request: UpstreamAuthorizationRequest<'_>,
+) -> OauthResult<String> {
+    let endpoint = provider.authorization_endpoint().ok_or_else(|| {
+        OauthError::Config(format!(
+            "OIDC provider '{}' has no authorization endpoint",
+            provider.id
+        ))
+    })?;
+    let scope = provider.scopes.join(" ");
+    let url = reqwest::Url::parse_with_params(
+        &endpoint,
+        &[
+            ("response_type", "code"),
+            ("client_id", provider.client_id.as_str()),
+            ("redirect_uri", request.redirect_uri),
+            ("scope", scope.as_str()),
+            ("state", request.state),
+            ("nonce", request.nonce),
+            ("code_challenge", request.code_challenge),
+            ("code_challenge_method", "S256"),
+        ],
+    )
+    .map_err(|e| {
+        OauthError::Config(format!(
+            "OIDC provider '{}' authorization endpoint is not a URL: {e}",
+            provider.id
+        ))
+    })?;
+    Ok(url.into())
+}
+
+/// Provisions or links the local account for a validated upstream identity
+/// and brings its provider-managed roles in line with the ID token.
+pub async fn sign_in_federated(
+    state: &OAuthState,
+    provider: &OidcProvider,
+    identity: &UpstreamIdentity,
+) -> OauthResult<UserId> {
+    ensure_allowed_domain(provider, identity)?;
+
+    let granted = map_upstream_roles(provider, &identity.upstream_roles);
+    let claims = FederatedIdentityClaims {
+        email: identity.email.clone(),
+        email_verified: identity.email_verified,
+        name: identity.name.clone(),
+        preferred_username: identity.preferred_username.clone(),
+        roles: granted.clone(),
+    };
+
+    let user_id = state
+        .user_provider()
+        .find_or_create_federated(&identity.issuer, &identity.subject, &claims)
+        .await
+        .map_err(|e| OauthError::Provider(format!("failed to link the upstream identity: {e}")))?;
+
+    let user = state
+        .user_provider()
+        .find_by_id(&user_id)
+        .await
+        .map_err(|e| OauthError::Provider(format!("failed to load the linked account: {e}")))?
+        .ok_or_else(|| OauthError::Provider("linked upstream account vanished".to_owned()))?;
+
+    if !user.is_active {
+        return Err(OauthError::Unauthorized(
+            "the upstream identity is linked to an inactive account".to_owned(),
+        ));
+    }
+
+    if !provider.role_mappings.is_empty() {
+        let roles = reconcile_roles(provider, &user.roles, &granted);
+        if roles != user.roles {
+            state
+                .user_provider()
+                .assign_roles(&user_id, &roles)
+                .await
+                .map_err(|e| OauthError::Provider(format!("failed to apply mapped roles: {e}")))?;
+        }
+    }
+
+    Ok(user_id)
+}
+
+/// Enforces the provider's `allowed_domains`, when it lists any.
+///
+/// Google's `hd` claim names the Workspace domain the account belongs to;
+/// other providers only offer the email, which counts once it is verified.
+pub fn ensure_allowed_domain(
+    provider: &OidcProvider,
+    identity: &UpstreamIdentity,
+) -> OauthResult<()> {
+    if provider.allowed_domains.is_empty() {
+        return Ok(());
+    }
+    // Why: `hd` is only defined by Google. Any other provider may pass through
+    // a claim of that name from user-editable attributes, so it is ignored.
+    let domain = match provider.kind {
+        OidcProviderKind::GoogleWorkspace => identity.hosted_domain.as_deref(),
+        OidcProviderKind::Keycloak | OidcProviderKind::Entra | OidcProviderKind::Mock => identity
+            .email
+            .as_deref()
+            .filter(|_| identity.email_verified)
+            .and_then(|email| email.rsplit_once('@'))
+            .map(|(_, domain)| domain),
+    };
+    let allowed = domain.is_some_and(|domain| {
+        provider
+            .allowed_domains
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(domain))
+    });
+    if allowed {
+        Ok(())
+    } else {
+        Err(OauthError::Unauthorized(format!(
+            "accounts from this domain may not sign in with '{}'",
+            provider.display_name()
+        )))
+    }
+}
@@ -0,0 +1,55 @@
+//! Claim-to-role mapping for upstream `OpenID` Connect sign-ins.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use systemprompt_models::auth::Permission;
+use systemprompt_models::profile::OidcProvider;
+
+/// Local roles the upstream role-claim values grant, in mapping order and
+/// without duplicates. Values with no mapping grant nothing.
+#[must_use]
+pub fn map_upstream_roles(provider: &OidcProvider, upstream_roles: &[String]) -> Vec<String> {
+    let mut granted: Vec<String> = Vec::new();
+    for role in upstream_roles
+        .iter()
+        .filter_map(|value| provider.role_mappings.get(value))
+        .flatten()
+    {
+        if !granted.contains(role) {
+            granted.push(role.clone());
+        }
+    }
+    granted
+}
+
+/// The role set an account should hold after signing in with `provider`.
+///
+/// Roles the provider manages (any mapping target) follow the ID token; every
+/// other role on the account is kept. An account left with no role at all
+/// falls back to `user`, as a freshly provisioned one does.
+#[must_use]
+pub fn reconcile_roles(
+    provider: &OidcProvider,
+    current: &[String],
+    granted: &[String],
+) -> Vec<String> {
+    let mut roles: Vec<String> = current
+        .iter()
+        .filter(|role| {
+            !provider
+                .managed_roles()
+                .any(|managed| managed == role.as_str())
+        })
+        .cloned()
+        .collect();
+    for role in granted {
+        if !roles.contains(role) {
+            roles.push(role.clone());
+        }
+    }
+    if roles.is_empty() {
+        roles.push(Permission::User.as_str().to_owned());
+    }
+    roles
+}
@@ -12,18 +12,21 @@ use crate::models::JwtClaims;
 use systemprompt_identifiers::{SessionId, UserId};
 use systemprompt_models::Config;
 use systemprompt_models::auth::{
-    ActClaim, AuthenticatedUser, JwtAudience, Permission, RateLimitTier, TokenType, UserType,
+    ActClaim, AuthenticatedUser, ConfirmationClaim, JwtAudience, Permission, RateLimitTier,
+    TokenType, UserType,
 };
 use systemprompt_security::keys::authority;
 
 pub mod id_jag;
 mod secret;
+mod user_code;
 
 pub use id_jag::{IdJagGrant, mint_id_jag};
 pub use secret::{
     generate_access_token_jti, generate_client_secret, generate_secure_token, hash_client_secret,
     verify_client_secret,
 };
+pub use user_code::{generate_user_code, normalize_user_code};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JwtConfig {
@@ -34,6 +37,8 @@ pub struct JwtConfig {
     pub resource: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub plugin_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dpop_jkt: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -49,6 +54,7 @@ impl Default for JwtConfig {
             expires_in_hours: Some(24),
             resource: None,
             plugin_id: None,
+            dpop_jkt: None,
         }
     }
 }
@@ -117,6 +123,8 @@ fn build_claims(
         rate_limit_tier: Some(user_type.rate_tier()),
         plugin_id: config.plugin_id,
         act: None,
+        cnf: config.dpop_jkt.map(|jkt| ConfirmationClaim { jkt }),
+        parent_jti: None,
     })
 }
 
@@ -208,6 +216,8 @@ pub fn generate_anonymous_jwt_with_expiry(
         rate_limit_tier: Some(RateLimitTier::Anon),
         plugin_id: None,
         act: None,
+        cnf: None,
+        parent_jti: None,
     };
 
     encode_with_authority(&claims)
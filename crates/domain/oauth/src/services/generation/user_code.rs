@@ -0,0 +1,37 @@
+//! RFC 8628 user codes: short, case-insensitive, typed by a person.
+//!
+//! Codes draw from a consonant-only alphabet (§6.1) so they cannot spell
+//! words and survive being read aloud, and are shown as two dash-separated
+//! halves. [`normalize_user_code`] accepts what a person actually types —
+//! lower case, spaces, a missing or extra dash — and returns the stored form.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use rand::{RngExt, rng};
+
+use crate::constants::device::{USER_CODE_ALPHABET, USER_CODE_LENGTH};
+
+pub fn generate_user_code() -> String {
+    let mut rng = rng();
+    let chars: String = (0..USER_CODE_LENGTH)
+        .map(|_| char::from(USER_CODE_ALPHABET[rng.random_range(0..USER_CODE_ALPHABET.len())]))
+        .collect();
+    format_user_code(&chars)
+}
+
+pub fn normalize_user_code(input: &str) -> Option<String> {
+    let chars: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let valid =
+        chars.len() == USER_CODE_LENGTH && chars.bytes().all(|b| USER_CODE_ALPHABET.contains(&b));
+    valid.then(|| format_user_code(&chars))
+}
+
+fn format_user_code(chars: &str) -> String {
+    let (head, tail) = chars.split_at(USER_CODE_LENGTH / 2);
+    format!("{head}-{tail}")
+}
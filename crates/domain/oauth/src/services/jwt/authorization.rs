@@ -19,6 +19,26 @@ impl AuthorizationService {
         headers: &HeaderMap,
         service_name: &str,
     ) -> Result<AuthenticatedUser, StatusCode> {
+        Self::service_access_claims(headers, service_name)
+            .and_then(Self::create_authenticated_user_from_claims)
+    }
+
+    pub fn authorize_bound_service_access(
+        headers: &HeaderMap,
+        service_name: &str,
+    ) -> Result<(AuthenticatedUser, Option<String>), StatusCode> {
+        let claims = Self::service_access_claims(headers, service_name)?;
+        let dpop_jkt = claims.dpop_jkt().map(str::to_owned);
+        Ok((
+            Self::create_authenticated_user_from_claims(claims)?,
+            dpop_jkt,
+        ))
+    }
+
+    fn service_access_claims(
+        headers: &HeaderMap,
+        service_name: &str,
+    ) -> Result<JwtClaims, StatusCode> {
         let Ok(token) = TokenExtractor::standard().extract(headers) else {
             tracing::warn!(
                 service = %service_name,
@@ -50,7 +70,7 @@ impl AuthorizationService {
             return Err(StatusCode::FORBIDDEN);
         }
 
-        Self::create_authenticated_user_from_claims(claims)
+        Ok(claims)
     }
 
     pub fn authorize_required_audience(
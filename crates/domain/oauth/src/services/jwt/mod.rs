@@ -71,6 +71,13 @@ impl AuthService {
         AuthorizationService::authorize_service_access(headers, service_name)
     }
 
+    pub fn authorize_bound_service_access(
+        headers: &http::HeaderMap,
+        service_name: &str,
+    ) -> Result<(AuthenticatedUser, Option<String>), http::StatusCode> {
+        AuthorizationService::authorize_bound_service_access(headers, service_name)
+    }
+
     pub fn authorize_required_audience(
         headers: &http::HeaderMap,
         required_audience: &str,
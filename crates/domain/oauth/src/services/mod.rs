@@ -1,5 +1,6 @@
 //! OAuth domain services: token generation, JWT, plugin-scoped token minting,
-//! session, `WebAuthn`, CIMD, validation, templating.
+//! session, `WebAuthn`, upstream `OpenID` Connect federation, CIMD,
+//! validation, templating.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -7,6 +8,7 @@
 pub mod bridge;
 pub mod cimd;
 pub mod ema;
+pub mod federation;
 pub mod generation;
 pub mod http;
 pub mod jwt;
@@ -35,7 +37,8 @@ pub use webauthn::{JwtTokenValidator, UserCreationService, WebAuthnConfig, WebAu
 pub use generation::{
     JwtConfig, JwtSigningParams, generate_access_token_jti, generate_anonymous_jwt,
     generate_anonymous_jwt_with_expiry, generate_client_secret, generate_jwt,
-    generate_jwt_with_act, generate_secure_token, hash_client_secret, verify_client_secret,
+    generate_jwt_with_act, generate_secure_token, generate_user_code, hash_client_secret,
+    normalize_user_code, verify_client_secret,
 };
 
 pub use validation::{
@@ -41,4 +41,20 @@ impl TemplateEngine {
     pub const fn load_link_passkey_template() -> &'static str {
         include_str!("../../templates/link_passkey.html")
     }
+
+    pub const fn load_device_verification_template() -> &'static str {
+        include_str!("../../templates/device_verification.html")
+    }
+
+    pub const fn load_device_consent_template() -> &'static str {
+        include_str!("../../templates/device_consent.html")
+    }
+
+    pub const fn load_device_approved_template() -> &'static str {
+        include_str!("../../templates/device_approved.html")
+    }
+
+    pub const fn load_device_denied_template() -> &'static str {
+        include_str!("../../templates/device_denied.html")
+    }
 }
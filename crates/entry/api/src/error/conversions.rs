@@ -93,6 +93,7 @@ impl From<OauthError> for ApiHttpError {
                 ApiError::conflict(message)
             },
             OauthError::Unauthorized(_)
+            | OauthError::Federation(_)
             | OauthError::InvalidGrant(_)
             | OauthError::InvalidClient(_)
             | OauthError::TokenInvalid(_)
@@ -137,6 +138,7 @@ impl From<ContextExtractionError> for ApiHttpError {
             | ContextExtractionError::MissingAuthHeader
             | ContextExtractionError::InvalidToken(_)
             | ContextExtractionError::Revoked
+            | ContextExtractionError::InvalidDpopProof(_)
             | ContextExtractionError::MissingSessionId
             | ContextExtractionError::MissingUserId => ApiError::unauthorized(message),
             ContextExtractionError::MissingContextId
@@ -8,7 +8,7 @@ use std::sync::Arc;
 
 use systemprompt_ai::repository::{
     AiGatewayPolicyRepository, AiQuotaBucketRepository, AiRequestPayloadRepository,
-    AiRequestRepository, AiSafetyFindingRepository,
+    AiRequestRepository, AiResponseCacheRepository, AiSafetyFindingRepository,
 };
 use systemprompt_database::DbPool;
 use systemprompt_traits::DynContextMaterializer;
@@ -20,6 +20,7 @@ pub struct GatewayRepositories {
     pub payloads: Arc<AiRequestPayloadRepository>,
     pub safety_findings: AiSafetyFindingRepository,
     pub gateway_policies: AiGatewayPolicyRepository,
+    pub response_cache: AiResponseCacheRepository,
     pub context_materializer: DynContextMaterializer,
 }
 
@@ -41,6 +42,7 @@ impl GatewayRepositories {
             payloads: Arc::new(AiRequestPayloadRepository::new(db)?),
             safety_findings: AiSafetyFindingRepository::new(db)?,
             gateway_policies: AiGatewayPolicyRepository::new(db)?,
+            response_cache: AiResponseCacheRepository::new(db)?,
             context_materializer,
         })
     }
@@ -11,7 +11,8 @@ use axum::response::Response;
 
 use crate::services::gateway::audit::GatewayRequestContext;
 use crate::services::gateway::protocol::inbound::InboundAdapter;
-use crate::services::gateway::protocol::outbound::UpstreamError;
+use crate::services::gateway::protocol::outbound::{EmbeddingsUnsupported, UpstreamError};
+use crate::services::gateway::rate_limit::RateLimited;
 use crate::services::gateway::service::{
     DispatchError, DispatchInputs, GatewayService, GovernanceDenied, GuardForbidden, PolicyDenied,
     QuotaExceeded, SafetyBlocked,
@@ -50,6 +51,7 @@ pub(super) async fn dispatch_to_provider(
         context_id,
         gateway_conversation_id,
     } = prepared;
+    let inbound = inbound.bind_request(&body_bytes).unwrap_or(inbound);
 
     let max_tokens = gateway_request.max_tokens;
     let is_streaming = gateway_request.stream;
@@ -81,6 +83,7 @@ pub(super) async fn dispatch_to_provider(
             persist: true,
         })?;
 
+    let surface = Arc::clone(&inbound);
     match GatewayService::dispatch(
         gateway_config,
         &rc.profile.providers,
@@ -98,10 +101,33 @@ pub(super) async fn dispatch_to_provider(
     .await
     {
         Ok(resp) => Ok(resp),
-        Err(e) => map_dispatch_error(e),
+        Err(e) => {
+            rate_limited_response(surface.as_ref(), &e).map_or_else(|| map_dispatch_error(e), Ok)
+        },
     }
 }
 
+// Why: a gateway-side rate limit answers in the caller's own error shape and
+// headers, so client SDKs back off exactly as they would for the provider.
+fn rate_limited_response(
+    inbound: &dyn InboundAdapter,
+    e: &DispatchError,
+) -> Option<Response<Body>> {
+    let (DispatchError::PreAudit(inner) | DispatchError::Recorded(inner)) = e;
+    let limited = inner.downcast_ref::<RateLimited>()?;
+    let status = StatusCode::TOO_MANY_REQUESTS;
+    let mut builder = Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .header("retry-after", limited.retry_after_seconds.to_string());
+    for (name, value) in inbound.rate_limit_headers(&limited.status) {
+        builder = builder.header(name, value);
+    }
+    builder
+        .body(Body::from(inbound.render_error(status, &limited.message)))
+        .ok()
+}
+
 const ERROR_TYPE_API: &str = "api_error";
 const ERROR_TYPE_PERMISSION: &str = "permission_error";
 const ERROR_TYPE_INVALID_REQUEST: &str = "invalid_request_error";
@@ -230,6 +256,9 @@ pub fn classify_dispatch_error(e: &anyhow::Error) -> (StatusCode, String) {
             policy_denial_message(&blocked.to_string()),
         );
     }
+    if let Some(unsupported) = e.downcast_ref::<EmbeddingsUnsupported>() {
+        return (StatusCode::BAD_REQUEST, unsupported.to_string());
+    }
     if let Some(upstream) = e.downcast_ref::<UpstreamError>() {
         return map_upstream_error(upstream);
     }
@@ -1,10 +1,11 @@
 //! Gateway message-dispatch entry point.
 //!
-//! [`handle`] is the shared handler behind the `/messages` and `/responses`
-//! routes: it builds a `RequestContext`, extracts and authorizes the request
-//! (`extract`), then dispatches to the resolved provider (`dispatch`),
-//! persisting a rejection record (`rejection`) on any early failure. Inbound
-//! wire format is selected by the [`InboundAdapter`] passed in by the router.
+//! [`handle`] is the shared handler behind the `/messages`, `/responses`,
+//! `/chat/completions`, and `/embeddings` routes: it builds a
+//! `RequestContext`, extracts and authorizes the request (`extract`), then
+//! dispatches to the resolved provider (`dispatch`), persisting a rejection
+//! record (`rejection`) on any early failure. Inbound wire format is selected
+//! by the [`InboundAdapter`] passed in by the router.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -1,8 +1,8 @@
 //! LLM gateway router and its access log.
 //!
-//! [`gateway_router`] assembles the bridge-facing surface: the `/messages`
-//! and `/responses` proxy endpoints (each bound to an
-//! [`InboundAdapter`]), the
+//! [`gateway_router`] assembles the bridge-facing surface: the `/messages`,
+//! `/responses`, `/chat/completions`, and `/embeddings` proxy endpoints (each
+//! bound to an [`InboundAdapter`]), the
 //! `/auth/bridge/*` credential-exchange routes ([`auth`]), the `/bridge/*`
 //! manifest and heartbeat routes, the unauthenticated `/otel` ingest
 //! ([`otel`]), and `/models`. The router is gated on the availability of the
@@ -41,6 +41,8 @@ use systemprompt_traits::AppContext as _;
 
 use crate::services::gateway::protocol::inbound::InboundAdapter;
 use crate::services::gateway::protocol::inbound::anthropic_messages::AnthropicMessagesInbound;
+use crate::services::gateway::protocol::inbound::openai_chat::OpenAiChatInbound;
+use crate::services::gateway::protocol::inbound::openai_embeddings::OpenAiEmbeddingsInbound;
 use crate::services::gateway::protocol::inbound::openai_responses::OpenAiResponsesInbound;
 use crate::services::middleware::{JtiRevocationChecker, JwtContextExtractor};
 
@@ -153,12 +155,20 @@ fn inference_routes(
 ) -> Router {
     let ctx_messages = ctx.clone();
     let ctx_responses = ctx.clone();
+    let ctx_chat = ctx.clone();
+    let ctx_embeddings = ctx.clone();
     let repos_messages = Arc::clone(repos);
     let repos_responses = Arc::clone(repos);
+    let repos_chat = Arc::clone(repos);
+    let repos_embeddings = Arc::clone(repos);
     let jwt_messages = Arc::clone(jwt_extractor);
     let jwt_responses = Arc::clone(jwt_extractor);
+    let jwt_chat = Arc::clone(jwt_extractor);
+    let jwt_embeddings = Arc::clone(jwt_extractor);
     let anthropic_inbound: Arc<dyn InboundAdapter> = Arc::new(AnthropicMessagesInbound);
     let responses_inbound: Arc<dyn InboundAdapter> = Arc::new(OpenAiResponsesInbound);
+    let chat_inbound: Arc<dyn InboundAdapter> = Arc::new(OpenAiChatInbound::default());
+    let embeddings_inbound: Arc<dyn InboundAdapter> = Arc::new(OpenAiEmbeddingsInbound);
 
     Router::new()
         .route(
@@ -181,6 +191,26 @@ fn inference_routes(
                 async move { messages::handle(inbound, extractor, context, repos, request).await }
             }),
         )
+        .route(
+            "/chat/completions",
+            post(move |request| {
+                let extractor = Arc::clone(&jwt_chat);
+                let context = ctx_chat.clone();
+                let repos = Arc::clone(&repos_chat);
+                let inbound = Arc::clone(&chat_inbound);
+                async move { messages::handle(inbound, extractor, context, repos, request).await }
+            }),
+        )
+        .route(
+            "/embeddings",
+            post(move |request| {
+                let extractor = Arc::clone(&jwt_embeddings);
+                let context = ctx_embeddings.clone();
+                let repos = Arc::clone(&repos_embeddings);
+                let inbound = Arc::clone(&embeddings_inbound);
+                async move { messages::handle(inbound, extractor, context, repos, request).await }
+            }),
+        )
 }
 
 fn bridge_auth_routes(ctx: &AppContext, jwt_extractor: &Arc<JwtContextExtractor>) -> Router {
@@ -54,6 +54,7 @@ pub(super) fn mint_a2a_token(
         expires_in_hours: Some(1),
         resource: None,
         plugin_id: None,
+        dpop_jkt: None,
     };
     let signing = JwtSigningParams {
         issuer: &ctx.config().jwt_issuer,
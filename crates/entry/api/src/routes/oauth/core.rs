@@ -26,6 +26,18 @@ pub fn public_router() -> Router<OAuthState> {
             get(endpoints::webauthn_complete::handle_webauthn_complete),
         )
         .route("/token", post(endpoints::token::handle_token))
+        .route(
+            "/device_authorization",
+            post(endpoints::device_authorization::handle_device_authorization),
+        )
+        .route(
+            "/device",
+            get(endpoints::device::handle_device_verification),
+        )
+        .route(
+            "/device/consent",
+            post(endpoints::consent::handle_device_consent_post),
+        )
         .route(
             "/authorize",
             get(endpoints::authorize::handle_authorize_get),
@@ -35,6 +47,14 @@ pub fn public_router() -> Router<OAuthState> {
             post(endpoints::authorize::handle_authorize_post),
         )
         .route("/callback", get(endpoints::callback::handle_callback))
+        .route(
+            "/federated/{provider_id}/start",
+            get(endpoints::federated::handle_federated_start),
+        )
+        .route(
+            "/federated/{provider_id}/callback",
+            get(endpoints::federated::handle_federated_callback),
+        )
         .route(
             "/webauthn/register/start",
             post(webauthn::register::start_register),
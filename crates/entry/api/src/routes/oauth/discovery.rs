@@ -12,6 +12,7 @@ use systemprompt_models::modules::ApiPaths;
 use systemprompt_models::oauth::{OAuthServerConfig, ProtectedResourceMetadata};
 use systemprompt_oauth::services::validation::id_jag::ID_JAG_GRANT_PROFILE;
 use systemprompt_runtime::AppContext;
+use systemprompt_security::dpop::DPOP_SIGNING_ALG_VALUES;
 
 use crate::routes::proxy::mcp::get_mcp_server_scopes;
 use crate::services::request_base_url::RequestBaseUrl;
@@ -21,6 +22,7 @@ pub struct WellKnownResponse {
     pub issuer: String,
     pub authorization_endpoint: String,
     pub token_endpoint: String,
+    pub device_authorization_endpoint: String,
     pub userinfo_endpoint: String,
     pub introspection_endpoint: String,
     pub revocation_endpoint: String,
@@ -38,6 +40,7 @@ pub struct WellKnownResponse {
     pub subject_token_types_supported: Vec<String>,
     pub issued_token_types_supported: Vec<String>,
     pub authorization_grant_profiles_supported: Vec<String>,
+    pub dpop_signing_alg_values_supported: Vec<String>,
 }
 
 pub async fn handle_well_known(base: RequestBaseUrl) -> impl IntoResponse {
@@ -47,6 +50,10 @@ pub async fn handle_well_known(base: RequestBaseUrl) -> impl IntoResponse {
         issuer: config.issuer.clone(),
         authorization_endpoint: format!("{}/api/v1/core/oauth/authorize", config.issuer),
         token_endpoint: format!("{}/api/v1/core/oauth/token", config.issuer),
+        device_authorization_endpoint: format!(
+            "{}/api/v1/core/oauth/device_authorization",
+            config.issuer
+        ),
         userinfo_endpoint: format!("{}/api/v1/core/oauth/userinfo", config.issuer),
         introspection_endpoint: format!("{}/api/v1/core/oauth/introspect", config.issuer),
         revocation_endpoint: format!("{}/api/v1/core/oauth/revoke", config.issuer),
@@ -88,6 +95,10 @@ pub async fn handle_well_known(base: RequestBaseUrl) -> impl IntoResponse {
             "urn:ietf:params:oauth:token-type:id-jag".to_owned(),
         ],
         authorization_grant_profiles_supported: vec![ID_JAG_GRANT_PROFILE.to_owned()],
+        dpop_signing_alg_values_supported: DPOP_SIGNING_ALG_VALUES
+            .iter()
+            .map(|alg| (*alg).to_owned())
+            .collect(),
     };
 
     (StatusCode::OK, Json(response)).into_response()
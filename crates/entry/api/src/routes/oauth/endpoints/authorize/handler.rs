@@ -78,7 +78,7 @@ fn require_csrf_token(params: &AuthorizeQuery) -> Result<CsrfToken, OAuthHttpErr
     }
 }
 
-fn resolve_self_origins(base: &RequestBaseUrl) -> Result<SelfOrigins, OAuthHttpError> {
+pub(crate) fn resolve_self_origins(base: &RequestBaseUrl) -> Result<SelfOrigins, OAuthHttpError> {
     let primary_origin = Config::get()
         .map_err(|e| {
             tracing::error!(error = %e, "Failed to load config for OAuth self-origin");
@@ -12,6 +12,7 @@ mod handler;
 pub mod response_builder;
 pub mod validation;
 
+pub(crate) use handler::resolve_self_origins;
 pub use handler::{handle_authorize_get, handle_authorize_post, login_page_redirect_target};
 
 use serde::Deserialize;
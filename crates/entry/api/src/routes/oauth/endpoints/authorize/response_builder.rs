@@ -1,4 +1,5 @@
-//! Authorization-response construction (code, state, iss).
+//! Authorization-response construction (code, state, iss) and the `WebAuthn`
+//! sign-in page shared by the authorize and device verification flows.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -31,6 +32,27 @@ pub fn is_user_consent_granted(form: &AuthorizeRequest) -> bool {
 }
 
 pub fn generate_webauthn_form(params: &AuthorizeQuery, resolved_scope: &str) -> String {
+    render_webauthn_form(params, resolved_scope, None)
+}
+
+/// The sign-in page for the RFC 8628 verification URI.
+///
+/// It is the authorize page with the user code shown and carried through to
+/// `webauthn/complete`, which asks the signed-in user to approve or deny the
+/// device instead of minting an authorization code.
+pub fn generate_device_webauthn_form(
+    params: &AuthorizeQuery,
+    resolved_scope: &str,
+    user_code: &str,
+) -> String {
+    render_webauthn_form(params, resolved_scope, Some(user_code))
+}
+
+fn render_webauthn_form(
+    params: &AuthorizeQuery,
+    resolved_scope: &str,
+    user_code: Option<&str>,
+) -> String {
     let template = TemplateEngine::load_webauthn_oauth_template();
     let mut context = HashMap::new();
 
@@ -54,6 +76,34 @@ pub fn generate_webauthn_form(params: &AuthorizeQuery, resolved_scope: &str) ->
     let allow_registration = Config::get().map_or(true, |c| c.allow_registration);
     let register_class = if allow_registration { "" } else { "hidden" };
     context.insert("register_class", register_class);
+    context.insert("user_code", user_code.unwrap_or(""));
+    context.insert(
+        "device_class",
+        if user_code.is_some() { "" } else { "hidden" },
+    );
+
+    // Why: upstream sign-in mints an authorization code, so it is offered on
+    // the authorize page only, never on the device verification page.
+    let federated_providers = federated_providers_json();
+    let federated_class = if user_code.is_none() && federated_providers != "[]" {
+        ""
+    } else {
+        "hidden"
+    };
+    context.insert("federated_providers", federated_providers.as_str());
+    context.insert("federated_class", federated_class);
 
     TemplateEngine::render(template, context)
 }
+
+/// The configured upstream `OpenID` Connect providers as `[{id, name}]`, for
+/// the sign-in page to build its "Continue with" links from.
+fn federated_providers_json() -> String {
+    let providers: Vec<serde_json::Value> = Config::get()
+        .map(|c| c.oidc_providers.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|p| serde_json::json!({ "id": p.id, "name": p.display_name() }))
+        .collect();
+    serde_json::Value::Array(providers).to_string()
+}
@@ -11,6 +11,8 @@
 mod entropy;
 mod resource;
 
+pub use resource::validate_resource_uri;
+
 use super::AuthorizeQuery;
 use anyhow::Result;
 use systemprompt_oauth::repository::OAuthRepository;
@@ -67,21 +69,41 @@ pub async fn validate_authorize_request(
         })?;
     }
 
-    let resource_scopes = match &params.resource {
+    resolve_requested_scope(
+        state,
+        &client.scopes,
+        params.scope.as_deref(),
+        params.resource.as_deref(),
+    )
+    .await
+}
+
+/// The effective scope of an authorization request.
+///
+/// The explicit `scope` wins, else the scopes of the `resource` it targets,
+/// else everything the client registered. Shared by `/authorize` and
+/// `/device_authorization`.
+pub async fn resolve_requested_scope(
+    state: &systemprompt_oauth::OAuthState,
+    client_scopes: &[String],
+    scope: Option<&str>,
+    resource: Option<&str>,
+) -> Result<String> {
+    let resource_scopes = match resource {
         Some(resource) => resource::resolve_resource_scopes(state, resource).await,
         None => None,
     };
 
-    let scope = if let Some(scope_param) = params.scope.as_deref() {
+    let scope = if let Some(scope_param) = scope {
         scope_param.to_owned()
-    } else if let Some(ref rs) = resource_scopes {
-        rs.clone()
-    } else if client.scopes.is_empty() {
+    } else if let Some(rs) = resource_scopes {
+        rs
+    } else if client_scopes.is_empty() {
         return Err(anyhow::anyhow!(
             "Client has no registered scopes and none provided in request"
         ));
     } else {
-        client.scopes.join(" ")
+        client_scopes.join(" ")
     };
 
     let requested_scopes = OAuthRepository::parse_scopes(&scope);
@@ -121,7 +143,7 @@ pub fn validate_oauth_parameters(
     }
 
     if let Some(resource) = &params.resource {
-        resource::validate_resource_uri(resource, self_origins)?;
+        validate_resource_uri(resource, self_origins)?;
     }
 
     Ok(())
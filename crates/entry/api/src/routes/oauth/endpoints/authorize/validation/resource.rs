@@ -7,10 +7,7 @@ use systemprompt_models::net::OutboundUrlError;
 
 use super::SelfOrigins;
 
-pub(super) fn validate_resource_uri(
-    resource: &str,
-    self_origins: &SelfOrigins,
-) -> Result<(), String> {
+pub fn validate_resource_uri(resource: &str, self_origins: &SelfOrigins) -> Result<(), String> {
     let url = reqwest::Url::parse(resource)
         .map_err(|_e| format!("Invalid resource URI: '{resource}' is not a valid absolute URI"))?;
 
@@ -2,18 +2,24 @@
 //!
 //! Serves the scope-consent page (GET) and records the user's allow/deny
 //! decision (POST), validating requested scopes against the client's
-//! registered grant.
+//! registered grant. The RFC 8628 device flow has its own consent step: after
+//! a passkey sign-in on the verification page the user is shown
+//! [`render_device_consent`], and its approve or deny action posts to
+//! [`handle_device_consent_post`], which decides the device code.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 use axum::Json;
-use axum::extract::Query;
+use axum::extract::{Form, Query};
 use axum::http::StatusCode;
 use axum::response::{Html, IntoResponse, Response};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use systemprompt_identifiers::ClientId;
-use systemprompt_oauth::repository::OAuthRepository;
+use systemprompt_oauth::repository::{OAuthRepository, PendingDeviceCode};
+use systemprompt_oauth::services::normalize_user_code;
+use systemprompt_oauth::services::templating::TemplateEngine;
 
 use crate::routes::oauth::OAuthHttpError;
 use crate::routes::oauth::extractors::OAuthRepo;
@@ -55,6 +61,85 @@ pub async fn handle_consent_post(Json(decision): Json<ConsentRequest>) -> impl I
     (StatusCode::OK, Json(response)).into_response()
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceConsentDecision {
+    Approve,
+    Deny,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeviceConsentForm {
+    pub user_code: String,
+    pub consent_token: String,
+    pub decision: DeviceConsentDecision,
+}
+
+/// Applies the approve or deny action from the device consent page.
+///
+/// The consent token was issued to the user who signed in on the
+/// verification page, so the decision is recorded for them; a missing, spent,
+/// or expired token decides nothing.
+pub async fn handle_device_consent_post(
+    OAuthRepo(repo): OAuthRepo,
+    Form(form): Form<DeviceConsentForm>,
+) -> Result<Response, OAuthHttpError> {
+    let user_code = normalize_user_code(&form.user_code)
+        .ok_or_else(|| OAuthHttpError::invalid_request("Malformed user_code"))?;
+
+    let (decided, template) = match form.decision {
+        DeviceConsentDecision::Approve => (
+            repo.approve_device_code(&user_code, &form.consent_token)
+                .await?,
+            TemplateEngine::load_device_approved_template(),
+        ),
+        DeviceConsentDecision::Deny => (
+            repo.deny_device_code(&user_code, &form.consent_token)
+                .await?,
+            TemplateEngine::load_device_denied_template(),
+        ),
+    };
+    let decided = decided.ok_or_else(|| {
+        OAuthHttpError::invalid_grant("Device code is unknown, expired, or already decided")
+    })?;
+
+    tracing::info!(
+        client_id = %decided.client_id,
+        user_id = %decided.user_id,
+        decision = ?form.decision,
+        "Device authorization decided"
+    );
+
+    let mut context = HashMap::new();
+    context.insert("client_id", decided.client_id.as_str());
+    Ok(Html(TemplateEngine::render(template, context)).into_response())
+}
+
+/// The device consent page shown after a passkey sign-in on the verification
+/// URI, carrying the one-time consent token its approve and deny actions
+/// present.
+pub async fn render_device_consent(
+    repo: &OAuthRepository,
+    pending: &PendingDeviceCode,
+    user_code: &str,
+    consent_token: &str,
+) -> Result<String, OAuthHttpError> {
+    let client = repo
+        .find_client_by_id(&pending.client_id)
+        .await?
+        .ok_or_else(|| OAuthHttpError::invalid_client("Client not found"))?;
+
+    let mut context = HashMap::new();
+    context.insert("client_name", client.client_name.as_str());
+    context.insert("scope", pending.scope.as_str());
+    context.insert("user_code", user_code);
+    context.insert("consent_token", consent_token);
+    Ok(TemplateEngine::render(
+        TemplateEngine::load_device_consent_template(),
+        context,
+    ))
+}
+
 async fn get_consent_info(
     repo: &OAuthRepository,
     params: &ConsentQuery,
@@ -0,0 +1,79 @@
+//! RFC 8628 verification URI.
+//!
+//! Without a `user_code` the page asks for one. A code that names a pending
+//! device authorization renders the passkey sign-in page for that client and
+//! scope; `webauthn/complete` then records the signed-in user against the
+//! code and shows the consent page, whose approve or deny action decides it.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use axum::extract::Query;
+use axum::response::{Html, IntoResponse, Response};
+use serde::Deserialize;
+use std::collections::HashMap;
+use systemprompt_oauth::services::normalize_user_code;
+use systemprompt_oauth::services::templating::TemplateEngine;
+
+use super::authorize::AuthorizeQuery;
+use super::authorize::response_builder::generate_device_webauthn_form;
+use crate::routes::oauth::OAuthHttpError;
+use crate::routes::oauth::extractors::OAuthRepo;
+
+#[derive(Debug, Deserialize)]
+pub struct DeviceVerificationQuery {
+    pub user_code: Option<String>,
+}
+
+pub async fn handle_device_verification(
+    OAuthRepo(repo): OAuthRepo,
+    Query(query): Query<DeviceVerificationQuery>,
+) -> Result<Response, OAuthHttpError> {
+    let Some(entered) = query.user_code.as_deref().filter(|c| !c.trim().is_empty()) else {
+        return Ok(render_code_entry(None, "").into_response());
+    };
+
+    let Some(user_code) = normalize_user_code(entered) else {
+        return Ok(render_code_entry(Some("That code is not valid."), entered).into_response());
+    };
+
+    let Some(pending) = repo.find_pending_device_code(&user_code).await? else {
+        return Ok(render_code_entry(
+            Some("That code is unknown, expired, or already used."),
+            &user_code,
+        )
+        .into_response());
+    };
+
+    let params = AuthorizeQuery {
+        response_type: "device_code".to_owned(),
+        client_id: pending.client_id,
+        redirect_uri: None,
+        scope: Some(pending.scope.clone()),
+        state: None,
+        code_challenge: None,
+        code_challenge_method: None,
+        response_mode: None,
+        display: None,
+        prompt: None,
+        max_age: None,
+        ui_locales: None,
+        resource: pending.resource,
+    };
+
+    Ok(Html(generate_device_webauthn_form(
+        &params,
+        &pending.scope,
+        &user_code,
+    ))
+    .into_response())
+}
+
+fn render_code_entry(error: Option<&str>, user_code: &str) -> Html<String> {
+    let template = TemplateEngine::load_device_verification_template();
+    let mut context = HashMap::new();
+    context.insert("error", error.unwrap_or(""));
+    context.insert("error_class", if error.is_some() { "" } else { "hidden" });
+    context.insert("user_code", user_code);
+    Html(TemplateEngine::render(template, context))
+}
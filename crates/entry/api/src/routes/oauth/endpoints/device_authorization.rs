@@ -0,0 +1,148 @@
+//! RFC 8628 device authorization endpoint.
+//!
+//! A device without a browser posts its `client_id` here and gets back a
+//! `device_code` to poll the token endpoint with and a short `user_code` for
+//! the person to enter at the verification URI. Only clients registered with
+//! the device-code grant may start the flow; the scope is resolved exactly as
+//! `/authorize` resolves it.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Form, Json};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use systemprompt_identifiers::ClientId;
+use systemprompt_models::oauth::OAuthServerConfig;
+use systemprompt_oauth::constants::device::{DEVICE_CODE_EXPIRY_SECONDS, POLL_INTERVAL_SECONDS};
+use systemprompt_oauth::repository::{DeviceCodeParams, OAuthRepository};
+use systemprompt_oauth::services::validation::validate_client_credentials;
+use systemprompt_oauth::services::{generate_secure_token, generate_user_code};
+use systemprompt_oauth::{GrantType, OAuthState};
+
+use super::authorize::resolve_self_origins;
+use super::authorize::validation::{resolve_requested_scope, validate_resource_uri};
+use crate::routes::oauth::OAuthHttpError;
+use crate::routes::oauth::extractors::OAuthRepo;
+use crate::services::request_base_url::RequestBaseUrl;
+
+// Why: user codes come from a 20^8 space, so a collision with a live row is
+// rare; a few retries make it practically impossible without a loop that
+// could spin on a database fault.
+const USER_CODE_ATTEMPTS: usize = 3;
+
+#[derive(Debug, Deserialize)]
+pub struct DeviceAuthorizationRequest {
+    pub client_id: ClientId,
+    pub client_secret: Option<String>,
+    pub scope: Option<String>,
+    pub resource: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeviceAuthorizationResponse {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: String,
+    pub expires_in: i64,
+    pub interval: i32,
+}
+
+pub async fn handle_device_authorization(
+    State(state): State<OAuthState>,
+    OAuthRepo(repo): OAuthRepo,
+    base: RequestBaseUrl,
+    Form(request): Form<DeviceAuthorizationRequest>,
+) -> Result<Response, OAuthHttpError> {
+    let client = repo
+        .find_client_by_id(&request.client_id)
+        .await?
+        .ok_or_else(|| OAuthHttpError::invalid_client("Unknown client_id"))?;
+
+    if !client
+        .grant_types
+        .iter()
+        .any(|g| g == GrantType::DeviceCode.as_str())
+    {
+        return Err(OAuthHttpError::unauthorized_client(
+            "Client is not registered for the device_code grant",
+        ));
+    }
+
+    validate_client_credentials(&repo, &request.client_id, request.client_secret.as_deref())
+        .await
+        .map_err(|_e| OAuthHttpError::invalid_client("Invalid client credentials"))?;
+
+    if let Some(resource) = request.resource.as_deref() {
+        validate_resource_uri(resource, &resolve_self_origins(&base)?)
+            .map_err(OAuthHttpError::invalid_target)?;
+    }
+
+    let scope = resolve_requested_scope(
+        &state,
+        &client.scopes,
+        request.scope.as_deref(),
+        request.resource.as_deref(),
+    )
+    .await
+    .map_err(|e| OAuthHttpError::invalid_scope(e.to_string()))?;
+
+    let device_code = generate_secure_token("device_code");
+    let user_code = store_with_fresh_user_code(&repo, &request, &device_code, &scope).await?;
+
+    let issuer = OAuthServerConfig::from_api_server_url(base.as_str()).issuer;
+    let verification_uri = format!("{issuer}/api/v1/core/oauth/device");
+    let verification_uri_complete = format!(
+        "{verification_uri}?user_code={}",
+        urlencoding::encode(&user_code)
+    );
+
+    tracing::info!(
+        client_id = %request.client_id,
+        scope = %scope,
+        "Device authorization started"
+    );
+
+    let response = DeviceAuthorizationResponse {
+        device_code,
+        user_code,
+        verification_uri,
+        verification_uri_complete,
+        expires_in: DEVICE_CODE_EXPIRY_SECONDS,
+        interval: POLL_INTERVAL_SECONDS,
+    };
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+async fn store_with_fresh_user_code(
+    repo: &OAuthRepository,
+    request: &DeviceAuthorizationRequest,
+    device_code: &str,
+    scope: &str,
+) -> Result<String, OAuthHttpError> {
+    let expires_at = Utc::now() + Duration::seconds(DEVICE_CODE_EXPIRY_SECONDS);
+    for _ in 0..USER_CODE_ATTEMPTS {
+        let user_code = generate_user_code();
+        let stored = repo
+            .store_device_code(DeviceCodeParams {
+                device_code,
+                user_code: &user_code,
+                client_id: &request.client_id,
+                scope,
+                resource: request.resource.as_deref(),
+                interval_seconds: POLL_INTERVAL_SECONDS,
+                expires_at,
+            })
+            .await?;
+        if stored {
+            return Ok(user_code);
+        }
+    }
+    Err(OAuthHttpError::server_error(
+        "Could not allocate a unique user code",
+    ))
+}
@@ -0,0 +1,236 @@
+//! Upstream `OpenID` Connect sign-in for the authorization-code flow.
+//!
+//! `GET /federated/{provider}/start` carries the authorization request the
+//! sign-in page was rendered for. It is validated as `/authorize` validates
+//! it, stored with a fresh `state`, `nonce` and PKCE verifier, and the browser
+//! is sent to the provider. `GET /federated/{provider}/callback` consumes that
+//! `state`, redeems the code, validates the ID token, provisions or links the
+//! account, and then answers the original client exactly as the `WebAuthn`
+//! completion step does.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use axum::extract::{Path, Query, State};
+use axum::response::{IntoResponse, Redirect, Response};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use systemprompt_identifiers::UserId;
+use systemprompt_models::Config;
+use systemprompt_models::oauth::OAuthServerConfig;
+use systemprompt_models::profile::OidcProvider;
+use systemprompt_oauth::OAuthState;
+use systemprompt_oauth::constants::federation::LOGIN_EXPIRY_SECONDS;
+use systemprompt_oauth::repository::{FederatedLogin, FederatedLoginParams, PendingAuthorization};
+use systemprompt_oauth::services::federation::{
+    UpstreamAuthorizationRequest, UpstreamCodeExchange, UpstreamPkce, exchange_upstream_code,
+    sign_in_federated, upstream_authorization_url, upstream_jwks, validate_upstream_id_token,
+};
+use systemprompt_oauth::services::generate_secure_token;
+
+use super::authorize::validation::{validate_authorize_request, validate_oauth_parameters};
+use super::authorize::{AuthorizeQuery, resolve_self_origins};
+use super::webauthn_complete::{WebAuthnCompleteQuery, complete_authorization};
+use crate::routes::oauth::OAuthHttpError;
+use crate::routes::oauth::extractors::OAuthRepo;
+use crate::services::request_base_url::RequestBaseUrl;
+
+#[derive(Debug, Deserialize)]
+pub struct FederatedCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+pub async fn handle_federated_start(
+    State(state): State<OAuthState>,
+    OAuthRepo(repo): OAuthRepo,
+    base: RequestBaseUrl,
+    Path(provider_id): Path<String>,
+    Query(params): Query<AuthorizeQuery>,
+) -> Result<Response, OAuthHttpError> {
+    let config = Config::get()?;
+    let provider = find_provider(config, &provider_id)?;
+
+    if params.state.as_deref().is_none_or(str::is_empty) {
+        return Err(OAuthHttpError::invalid_request(
+            "CSRF token (state parameter) is required",
+        ));
+    }
+    let self_origins = resolve_self_origins(&base)?;
+    validate_oauth_parameters(&params, &self_origins).map_err(OAuthHttpError::invalid_request)?;
+    let scope = validate_authorize_request(&state, &params, &repo)
+        .await
+        .map_err(|e| OAuthHttpError::invalid_request(e.to_string()))?;
+    let redirect_uri = params
+        .redirect_uri
+        .clone()
+        .ok_or_else(|| OAuthHttpError::invalid_request("Missing redirect_uri parameter"))?;
+
+    let authorization = PendingAuthorization {
+        client_id: params.client_id.clone(),
+        redirect_uri,
+        scope,
+        state: params.state.clone(),
+        code_challenge: params.code_challenge.clone(),
+        code_challenge_method: params.code_challenge_method.clone(),
+        resource: params.resource.clone(),
+    };
+    let upstream_state = generate_secure_token("federated_state");
+    let nonce = generate_secure_token("nonce");
+    let pkce = UpstreamPkce::generate();
+
+    repo.store_federated_login(FederatedLoginParams {
+        state_token: &upstream_state,
+        provider_id: &provider.id,
+        nonce: &nonce,
+        code_verifier: &pkce.verifier,
+        authorization: &authorization,
+        expires_at: Utc::now() + Duration::seconds(LOGIN_EXPIRY_SECONDS),
+    })
+    .await?;
+
+    let target = upstream_authorization_url(
+        provider,
+        UpstreamAuthorizationRequest {
+            redirect_uri: &callback_url(&base, provider),
+            state: &upstream_state,
+            nonce: &nonce,
+            code_challenge: &pkce.challenge,
+        },
+    )?;
+    Ok(Redirect::to(&target).into_response())
+}
+
+pub async fn handle_federated_callback(
+    State(state): State<OAuthState>,
+    OAuthRepo(repo): OAuthRepo,
+    base: RequestBaseUrl,
+    Path(provider_id): Path<String>,
+    Query(params): Query<FederatedCallbackQuery>,
+) -> Result<Response, OAuthHttpError> {
+    let config = Config::get()?;
+    let provider = find_provider(config, &provider_id)?;
+
+    let upstream_state = params
+        .state
+        .as_deref()
+        .ok_or_else(|| OAuthHttpError::invalid_request("Missing state parameter"))?;
+    let login = repo
+        .consume_federated_login(upstream_state, &provider.id)
+        .await?
+        .ok_or_else(|| {
+            OAuthHttpError::invalid_request("Sign-in state is unknown, expired, or already used")
+        })?;
+    let authorization = &login.authorization;
+
+    let user_id = federated_user(
+        &state,
+        provider,
+        &callback_url(&base, provider),
+        &login,
+        &params,
+    )
+    .await
+    .map_err(|e| e.with_redirect(&authorization.redirect_uri, authorization.state.clone()))?;
+
+    tracing::info!(
+        provider = %provider.id,
+        client_id = %authorization.client_id,
+        user_id = %user_id,
+        "Upstream OIDC sign-in completed"
+    );
+
+    let issuer = OAuthServerConfig::from_api_server_url(base.as_str()).issuer;
+    complete_authorization(
+        &repo,
+        &completion_query(user_id, authorization),
+        &issuer,
+        true,
+    )
+    .await
+}
+
+async fn federated_user(
+    state: &OAuthState,
+    provider: &OidcProvider,
+    redirect_uri: &str,
+    login: &FederatedLogin,
+    params: &FederatedCallbackQuery,
+) -> Result<UserId, OAuthHttpError> {
+    if let Some(error) = params.error.as_deref() {
+        let detail = params.error_description.as_deref().unwrap_or(error);
+        tracing::info!(provider = %provider.id, error, "Upstream OIDC sign-in refused");
+        return Err(OAuthHttpError::access_denied(format!(
+            "{} sign-in failed: {detail}",
+            provider.display_name()
+        )));
+    }
+    let code = params
+        .code
+        .as_deref()
+        .ok_or_else(|| OAuthHttpError::invalid_request("Missing code parameter"))?;
+
+    let id_token = exchange_upstream_code(
+        provider,
+        UpstreamCodeExchange {
+            code,
+            redirect_uri,
+            code_verifier: &login.code_verifier,
+        },
+    )
+    .await?;
+    let identity = validate_upstream_id_token(
+        provider,
+        upstream_jwks(&Config::get()?.oidc_providers),
+        &id_token,
+        &login.nonce,
+    )
+    .await?;
+
+    Ok(sign_in_federated(state, provider, &identity).await?)
+}
+
+fn find_provider<'a>(
+    config: &'a Config,
+    provider_id: &str,
+) -> Result<&'a OidcProvider, OAuthHttpError> {
+    config
+        .oidc_providers
+        .iter()
+        .find(|p| p.id == provider_id)
+        .ok_or_else(|| {
+            OAuthHttpError::not_found(format!("Unknown sign-in provider '{provider_id}'"))
+        })
+}
+
+/// The redirect URI registered with the upstream provider.
+#[must_use]
+pub fn callback_url(base: &RequestBaseUrl, provider: &OidcProvider) -> String {
+    format!(
+        "{}/api/v1/core/oauth/federated/{}/callback",
+        OAuthServerConfig::from_api_server_url(base.as_str()).issuer,
+        provider.id
+    )
+}
+
+fn completion_query(
+    user_id: UserId,
+    authorization: &PendingAuthorization,
+) -> WebAuthnCompleteQuery {
+    WebAuthnCompleteQuery {
+        user_id,
+        auth_token: None,
+        response_type: Some("code".to_owned()),
+        client_id: Some(authorization.client_id.clone()),
+        redirect_uri: Some(authorization.redirect_uri.clone()),
+        scope: Some(authorization.scope.clone()),
+        state: authorization.state.clone(),
+        code_challenge: authorization.code_challenge.clone(),
+        code_challenge_method: authorization.code_challenge_method.clone(),
+        response_mode: None,
+        resource: authorization.resource.clone(),
+        user_code: None,
+    }
+}
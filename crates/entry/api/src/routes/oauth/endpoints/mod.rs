@@ -3,7 +3,9 @@
 //! Collects every handler that backs the OAuth surface: [`authorize`],
 //! [`token`], [`callback`], [`consent`], dynamic registration ([`register`],
 //! [`client_config`]), introspection and revocation, [`userinfo`], [`logout`],
-//! the [`anonymous`] grant, and the [`webauthn_complete`] step.
+//! the [`anonymous`] grant, RFC 8628 device authorization
+//! ([`device_authorization`], [`device`]), upstream `OpenID` Connect sign-in
+//! ([`federated`]), and the [`webauthn_complete`] step.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -13,6 +15,9 @@ pub mod authorize;
 pub mod callback;
 pub mod client_config;
 pub mod consent;
+pub mod device;
+pub mod device_authorization;
+pub mod federated;
 pub mod introspect;
 pub mod logout;
 pub mod register;
@@ -28,6 +33,9 @@ pub use authorize::{
 pub use callback::*;
 pub use client_config::*;
 pub use consent::*;
+pub use device::*;
+pub use device_authorization::*;
+pub use federated::*;
 pub use introspect::*;
 pub use logout::handle_logout;
 pub use register::*;
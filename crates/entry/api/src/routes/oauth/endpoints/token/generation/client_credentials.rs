@@ -21,7 +21,7 @@ use systemprompt_traits::{CreateSessionInput, ExtractSignals};
 use thiserror::Error;
 
 use super::super::TokenResponse;
-use super::RequestOrigin;
+use super::{RequestOrigin, access_token_type};
 
 #[derive(Debug, Default)]
 pub struct ClientTokenOptions<'a> {
@@ -172,6 +172,7 @@ pub async fn generate_client_tokens(
         audience,
         expires_in_hours: Some(global_config.jwt_access_token_expiration / 3600),
         plugin_id: options.plugin_id.map(str::to_owned),
+        dpop_jkt: origin.dpop_jkt.map(str::to_owned),
         ..Default::default()
     };
     let session_id =
@@ -191,7 +192,7 @@ pub async fn generate_client_tokens(
 
     Ok(TokenResponse {
         access_token: jwt_token,
-        token_type: "Bearer".to_owned(),
+        token_type: access_token_type(origin.dpop_jkt),
         expires_in,
         refresh_token: None,
         scope: Some(
@@ -50,6 +50,7 @@ use systemprompt_traits::ExtractSignals;
 pub struct RequestOrigin<'a> {
     pub headers: &'a HeaderMap,
     pub caller_ip: Option<IpAddr>,
+    pub dpop_jkt: Option<&'a str>,
 }
 
 #[derive(Debug)]
@@ -61,6 +62,7 @@ pub struct TokenGenerationParams<'a> {
     pub caller_ip: Option<IpAddr>,
     pub resource: Option<&'a str>,
     pub family_id: Option<&'a str>,
+    pub dpop_jkt: Option<&'a str>,
 }
 
 #[derive(Debug)]
@@ -114,7 +116,7 @@ pub async fn generate_tokens_by_user_id(
     Ok(GeneratedTokens {
         response: TokenResponse {
             access_token: jwt_and_refresh.access_token,
-            token_type: "Bearer".to_owned(),
+            token_type: access_token_type(params.dpop_jkt),
             expires_in,
             refresh_token: Some(jwt_and_refresh.refresh_token_value),
             scope: Some(jwt_and_refresh.scope_string),
@@ -158,6 +160,7 @@ async fn create_jwt_and_refresh_token(
         resource: params.resource.map(String::from),
         expires_in_hours: Some(global_config.jwt_access_token_expiration / 3600),
         plugin_id: None,
+        dpop_jkt: params.dpop_jkt.map(String::from),
     };
     let signing = JwtSigningParams {
         issuer: &global_config.jwt_issuer,
@@ -179,6 +182,9 @@ async fn create_jwt_and_refresh_token(
     if let Some(family) = params.family_id {
         builder = builder.with_family(family);
     }
+    if let Some(jkt) = params.dpop_jkt {
+        builder = builder.with_dpop_jkt(jkt);
+    }
     repo.store_refresh_token(builder.build()).await?;
 
     Ok(JwtAndRefreshToken {
@@ -189,6 +195,13 @@ async fn create_jwt_and_refresh_token(
     })
 }
 
+/// RFC 9449 §5: a token bound to a proof key is issued as `DPoP`, not
+/// `Bearer`.
+#[must_use]
+pub fn access_token_type(dpop_jkt: Option<&str>) -> String {
+    if dpop_jkt.is_some() { "DPoP" } else { "Bearer" }.to_owned()
+}
+
 pub fn resolve_user_permissions(
     requested_permissions: &[Permission],
     user_permissions: &[Permission],
@@ -36,7 +36,7 @@ use systemprompt_oauth::services::{
 };
 
 use super::super::{TokenError, TokenResponse};
-use super::RequestOrigin;
+use super::{RequestOrigin, access_token_type};
 
 mod claims;
 mod id_jag_subject;
@@ -145,6 +145,7 @@ pub async fn handle_token_exchange(
         expires_in_hours: Some(global.jwt_access_token_expiration / 3600),
         resource,
         plugin_id: None,
+        dpop_jkt: origin.dpop_jkt.map(str::to_owned),
     };
     let signing = JwtSigningParams {
         issuer: &global.jwt_issuer,
@@ -167,7 +168,7 @@ pub async fn handle_token_exchange(
 
     Ok(TokenResponse {
         access_token,
-        token_type: "Bearer".to_owned(),
+        token_type: access_token_type(origin.dpop_jkt),
         expires_in: global.jwt_access_token_expiration,
         refresh_token: None,
         scope: Some(scope_string),
@@ -0,0 +1,102 @@
+//! RFC 9449 proof check for `/oauth/token`.
+//!
+//! A token request that carries a `DPoP` header must prove possession of the
+//! key it names: the proof covers `POST` to this endpoint, echoes a nonce this
+//! server issued, and is recorded so it cannot be replayed. The key thumbprint
+//! it yields is bound into the issued tokens. A request without the header
+//! gets ordinary bearer tokens.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+use systemprompt_models::oauth::OAuthServerConfig;
+use systemprompt_oauth::repository::OAuthRepository;
+use systemprompt_security::dpop::{
+    DPOP_HEADER, DpopProofCheck, VerifiedDpopProof, issue_nonce, verify_nonce, verify_proof,
+};
+
+use super::super::TokenError;
+use crate::services::request_base_url::RequestBaseUrl;
+
+pub(super) async fn verify_token_request_proof(
+    repo: &OAuthRepository,
+    headers: &HeaderMap,
+    base: &RequestBaseUrl,
+) -> Result<Option<String>, TokenError> {
+    let mut proofs = headers.get_all(DPOP_HEADER).iter();
+    let Some(proof) = proofs.next() else {
+        return Ok(None);
+    };
+    if proofs.next().is_some() {
+        return Err(invalid("more than one DPoP proof header"));
+    }
+    let proof = proof
+        .to_str()
+        .map_err(|_e| invalid("DPoP proof header is not ASCII"))?;
+
+    let now = Utc::now().timestamp();
+    let token_endpoint = format!(
+        "{}/api/v1/core/oauth/token",
+        OAuthServerConfig::from_api_server_url(base.as_str()).issuer
+    );
+    let verified = verify_proof(
+        proof,
+        &DpopProofCheck {
+            method: "POST",
+            url: &token_endpoint,
+            access_token: None,
+            now,
+        },
+    )
+    .map_err(|e| invalid(&e.to_string()))?;
+
+    if !carries_current_nonce(&verified, now)? {
+        return Err(TokenError::UseDpopNonce {
+            nonce: fresh_nonce()?,
+        });
+    }
+    ensure_first_use(repo, &verified).await?;
+    Ok(Some(verified.jkt))
+}
+
+pub(super) fn fresh_nonce() -> Result<String, TokenError> {
+    issue_nonce(Utc::now().timestamp()).map_err(|e| TokenError::ServerError {
+        message: e.to_string(),
+    })
+}
+
+fn carries_current_nonce(proof: &VerifiedDpopProof, now: i64) -> Result<bool, TokenError> {
+    let Some(nonce) = proof.nonce.as_deref() else {
+        return Ok(false);
+    };
+    verify_nonce(nonce, now).map_err(|e| TokenError::ServerError {
+        message: e.to_string(),
+    })
+}
+
+async fn ensure_first_use(
+    repo: &OAuthRepository,
+    proof: &VerifiedDpopProof,
+) -> Result<(), TokenError> {
+    let exp: DateTime<Utc> = DateTime::from_timestamp(proof.replay_window_end(), 0)
+        .ok_or_else(|| invalid("DPoP proof iat out of range"))?;
+    let first_use = repo
+        .record_dpop_proof(&proof.jkt, &proof.jti, exp)
+        .await
+        .map_err(|e| TokenError::ServerError {
+            message: format!("DPoP proof replay record failed: {e}"),
+        })?;
+    if first_use {
+        Ok(())
+    } else {
+        Err(invalid("DPoP proof has already been used"))
+    }
+}
+
+fn invalid(reason: &str) -> TokenError {
+    TokenError::InvalidDpopProof {
+        reason: reason.to_owned(),
+    }
+}
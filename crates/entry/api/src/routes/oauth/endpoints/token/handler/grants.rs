@@ -1,15 +1,13 @@
 //! Per-`grant_type` token issuance: authorization-code, refresh-token,
-//! client-credentials, RFC 8693 token-exchange, and the RFC 7523 jwt-bearer
-//! assertion grant that redeems an ID-JAG.
+//! client-credentials, RFC 8693 token-exchange, the RFC 7523 jwt-bearer
+//! assertion grant that redeems an ID-JAG, and the RFC 8628 device-code poll.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
-use axum::http::HeaderMap;
-use std::net::IpAddr;
 use systemprompt_identifiers::{AuthorizationCode, ClientId, RefreshTokenId};
 use systemprompt_oauth::OAuthState;
-use systemprompt_oauth::repository::OAuthRepository;
+use systemprompt_oauth::repository::{DeviceCodePoll, OAuthRepository};
 use systemprompt_oauth::services::validation::id_jag::ID_JAG_TOKEN_TYPE;
 
 use super::super::generation::{
@@ -27,8 +25,7 @@ use super::map_exchange_error;
 pub(super) async fn handle_authorization_code_grant(
     repo: OAuthRepository,
     request: TokenRequest,
-    headers: &HeaderMap,
-    caller_ip: Option<IpAddr>,
+    origin: RequestOrigin<'_>,
     state: &OAuthState,
 ) -> Result<TokenResponse, TokenError> {
     let code_str = extract_required_field(request.code.as_deref(), "code")?;
@@ -70,10 +67,11 @@ pub(super) async fn handle_authorization_code_grant(
             client_id: &client_id,
             user_id: &validation_result.user_id,
             scope: Some(&validation_result.scope),
-            headers,
-            caller_ip,
+            headers: origin.headers,
+            caller_ip: origin.caller_ip,
             resource: validation_result.resource.as_deref(),
             family_id: None,
+            dpop_jkt: origin.dpop_jkt,
         },
         state,
     )
@@ -107,8 +105,7 @@ pub(super) async fn handle_authorization_code_grant(
 pub(super) async fn handle_refresh_token_grant(
     repo: OAuthRepository,
     request: TokenRequest,
-    headers: &HeaderMap,
-    caller_ip: Option<IpAddr>,
+    origin: RequestOrigin<'_>,
     state: &OAuthState,
 ) -> Result<TokenResponse, TokenError> {
     let refresh_token_str =
@@ -133,7 +130,7 @@ pub(super) async fn handle_refresh_token_grant(
         .map_err(|_e| TokenError::InvalidClientSecret)?;
 
     let consumed = repo
-        .consume_refresh_token(&refresh_token, &client_id)
+        .consume_bound_refresh_token(&refresh_token, &client_id, origin.dpop_jkt)
         .await
         .map_err(|e| TokenError::InvalidRefreshToken {
             reason: e.to_string(),
@@ -165,10 +162,11 @@ pub(super) async fn handle_refresh_token_grant(
             client_id: &client_id,
             user_id: &user_id,
             scope: Some(effective_scope),
-            headers,
-            caller_ip,
+            headers: origin.headers,
+            caller_ip: origin.caller_ip,
             resource: request.resource.as_deref(),
             family_id: Some(family_id.as_str()),
+            dpop_jkt: origin.dpop_jkt,
         },
         state,
     )
@@ -194,8 +192,7 @@ pub(super) async fn handle_refresh_token_grant(
 pub(super) async fn handle_token_exchange_grant(
     repo: OAuthRepository,
     request: TokenRequest,
-    headers: &HeaderMap,
-    caller_ip: Option<IpAddr>,
+    origin: RequestOrigin<'_>,
     state: &OAuthState,
 ) -> Result<TokenResponse, TokenError> {
     let subject_token = extract_required_field(request.subject_token.as_deref(), "subject_token")?;
@@ -219,7 +216,6 @@ pub(super) async fn handle_token_exchange_grant(
         resource: request.resource.as_deref(),
     };
 
-    let origin = RequestOrigin { headers, caller_ip };
     let response = handle_token_exchange(&repo, &client_id, exchange, origin, state)
         .await
         .map_err(|e| map_exchange_error(&e))?;
@@ -241,8 +237,7 @@ pub(super) async fn handle_token_exchange_grant(
 pub(super) async fn handle_jwt_bearer_grant(
     repo: OAuthRepository,
     request: TokenRequest,
-    headers: &HeaderMap,
-    caller_ip: Option<IpAddr>,
+    origin: RequestOrigin<'_>,
     state: &OAuthState,
 ) -> Result<TokenResponse, TokenError> {
     let assertion = extract_required_field(request.assertion.as_deref(), "assertion")?;
@@ -262,7 +257,6 @@ pub(super) async fn handle_jwt_bearer_grant(
         ..Default::default()
     };
 
-    let origin = RequestOrigin { headers, caller_ip };
     let response = handle_token_exchange(&repo, &client_id, exchange, origin, state)
         .await
         .map_err(|e| map_exchange_error(&e))?;
@@ -277,11 +271,80 @@ pub(super) async fn handle_jwt_bearer_grant(
     Ok(response)
 }
 
+// Why: RFC 8628 §3.4: the device polls with the code it was issued. Until the
+// user signs in on the verification page the answer is
+// `authorization_pending`, or `slow_down` when the device ignores its
+// interval; the first poll after approval claims the row and gets tokens.
+pub(super) async fn handle_device_code_grant(
+    repo: OAuthRepository,
+    request: TokenRequest,
+    origin: RequestOrigin<'_>,
+    state: &OAuthState,
+) -> Result<TokenResponse, TokenError> {
+    let device_code = extract_required_field(request.device_code.as_deref(), "device_code")?;
+    let client_id_str = extract_required_field(request.client_id.as_deref(), "client_id")?;
+    let client_id = ClientId::new(client_id_str);
+
+    validate_client_credentials(&repo, &client_id, request.client_secret.as_deref())
+        .await
+        .map_err(|_e| TokenError::InvalidClientSecret)?;
+
+    let poll = repo
+        .poll_device_code(device_code, &client_id)
+        .await
+        .map_err(|e| TokenError::ServerError {
+            message: format!("Failed to look up device code: {e}"),
+        })?;
+    let grant = match poll {
+        DeviceCodePoll::Approved(grant) => grant,
+        DeviceCodePoll::Pending => return Err(TokenError::AuthorizationPending),
+        DeviceCodePoll::SlowDown => return Err(TokenError::SlowDown),
+        DeviceCodePoll::Expired => return Err(TokenError::ExpiredDeviceCode),
+        DeviceCodePoll::Denied => return Err(TokenError::AccessDenied),
+        DeviceCodePoll::Invalid => {
+            return Err(TokenError::InvalidGrant {
+                reason: "Invalid device code".to_owned(),
+            });
+        },
+    };
+
+    let generated = generate_tokens_by_user_id(
+        &repo,
+        TokenGenerationParams {
+            client_id: &client_id,
+            user_id: &grant.user_id,
+            scope: Some(&grant.scope),
+            headers: origin.headers,
+            caller_ip: origin.caller_ip,
+            resource: grant.resource.as_deref(),
+            family_id: None,
+            dpop_jkt: origin.dpop_jkt,
+        },
+        state,
+    )
+    .await
+    .map_err(|e| TokenError::ServerError {
+        message: e.to_string(),
+    })?;
+
+    let token_response = generated.response;
+    tracing::info!(
+        grant_type = "urn:ietf:params:oauth:grant-type:device_code",
+        client_id = %client_id,
+        user_id = %grant.user_id,
+        scope = %grant.scope,
+        token_type = %token_response.token_type,
+        expires_in = token_response.expires_in,
+        "Token issued"
+    );
+
+    Ok(token_response)
+}
+
 pub(super) async fn handle_client_credentials_grant(
     repo: OAuthRepository,
     request: TokenRequest,
-    headers: &HeaderMap,
-    caller_ip: Option<IpAddr>,
+    origin: RequestOrigin<'_>,
     state: &OAuthState,
 ) -> Result<TokenResponse, TokenError> {
     let client_id_str = extract_required_field(request.client_id.as_deref(), "client_id")?;
@@ -303,7 +366,6 @@ pub(super) async fn handle_client_credentials_grant(
         plugin_id: request.plugin_id.as_deref(),
         audience: request.audience.as_deref(),
     };
-    let origin = RequestOrigin { headers, caller_ip };
     let token_response = generate_client_tokens(&repo, &client_id, origin, state, options)
         .await
         .map_err(|e| map_client_credentials_error(&client_id, e))?;
@@ -1,62 +1,79 @@
 //! `/oauth/token` endpoint: dispatches by `grant_type` to the per-grant
 //! handlers in [`grants`] and normalizes token-exchange errors back into the
-//! endpoint's `TokenError` wire type.
+//! endpoint's `TokenError` wire type. A `DPoP` proof, when present, is checked
+//! in [`dpop`] before dispatch and its key bound into whatever is issued.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 use axum::extract::{Extension, State};
-use axum::http::StatusCode;
+use axum::http::{HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::{Form, Json};
 use systemprompt_models::RequestContext;
 use systemprompt_oauth::{GrantType, OAuthState};
+use systemprompt_security::dpop::DPOP_NONCE_HEADER;
 use tracing::instrument;
 
+use super::generation::RequestOrigin;
 use super::{TokenError, TokenRequest};
 use crate::routes::oauth::OAuthHttpError;
 use crate::routes::oauth::extractors::OAuthRepo;
 use crate::services::middleware::client_addr::ClientIp;
+use crate::services::request_base_url::RequestBaseUrl;
 
+mod dpop;
 mod grants;
 
 use axum::http::HeaderMap;
+use dpop::{fresh_nonce, verify_token_request_proof};
 use grants::{
-    handle_authorization_code_grant, handle_client_credentials_grant, handle_jwt_bearer_grant,
-    handle_refresh_token_grant, handle_token_exchange_grant,
+    handle_authorization_code_grant, handle_client_credentials_grant, handle_device_code_grant,
+    handle_jwt_bearer_grant, handle_refresh_token_grant, handle_token_exchange_grant,
 };
 
 #[expect(
     clippy::too_many_arguments,
     reason = "axum handler: each extractor is a separate parameter"
 )]
-#[instrument(skip(state, _req_ctx, caller_ip, headers, request, repo), fields(grant_type = %request.grant_type))]
+#[instrument(skip(state, _req_ctx, base, caller_ip, headers, request, repo), fields(grant_type = %request.grant_type))]
 pub async fn handle_token(
     Extension(_req_ctx): Extension<RequestContext>,
     State(state): State<OAuthState>,
     OAuthRepo(repo): OAuthRepo,
+    base: RequestBaseUrl,
     ClientIp(caller_ip): ClientIp,
     headers: HeaderMap,
     Form(request): Form<TokenRequest>,
 ) -> Result<Response, OAuthHttpError> {
     tracing::info!(grant_type = %request.grant_type, "Token request received");
 
+    let dpop_jkt = verify_token_request_proof(&repo, &headers, &base).await?;
+    let origin = RequestOrigin {
+        headers: &headers,
+        caller_ip,
+        dpop_jkt: dpop_jkt.as_deref(),
+    };
+
     let parsed = request.grant_type.parse::<GrantType>().ok();
     let response = match parsed {
         Some(GrantType::AuthorizationCode) => {
-            handle_authorization_code_grant(repo, request, &headers, caller_ip, &state).await?
+            handle_authorization_code_grant(repo, request, origin, &state).await?
         },
         Some(GrantType::RefreshToken) => {
-            handle_refresh_token_grant(repo, request, &headers, caller_ip, &state).await?
+            handle_refresh_token_grant(repo, request, origin, &state).await?
         },
         Some(GrantType::ClientCredentials) => {
-            handle_client_credentials_grant(repo, request, &headers, caller_ip, &state).await?
+            handle_client_credentials_grant(repo, request, origin, &state).await?
         },
         Some(GrantType::TokenExchange) => {
-            handle_token_exchange_grant(repo, request, &headers, caller_ip, &state).await?
+            handle_token_exchange_grant(repo, request, origin, &state).await?
         },
         Some(GrantType::JwtBearer) => {
-            handle_jwt_bearer_grant(repo, request, &headers, caller_ip, &state).await?
+            handle_jwt_bearer_grant(repo, request, origin, &state).await?
+        },
+        Some(GrantType::DeviceCode) => {
+            handle_device_code_grant(repo, request, origin, &state).await?
         },
         None => {
             return Err(TokenError::UnsupportedGrantType {
@@ -65,7 +82,16 @@ pub async fn handle_token(
             .into());
         },
     };
-    Ok((StatusCode::OK, Json(response)).into_response())
+
+    let mut http_response = (StatusCode::OK, Json(response)).into_response();
+    // Why: RFC 9449 §8.2 — hand a DPoP client the nonce for its next proof
+    // up front, sparing it a `use_dpop_nonce` round trip.
+    if dpop_jkt.is_some()
+        && let Ok(value) = HeaderValue::from_str(&fresh_nonce()?)
+    {
+        http_response.headers_mut().insert(DPOP_NONCE_HEADER, value);
+    }
+    Ok(http_response)
 }
 
 #[cfg(feature = "test-api")]
@@ -117,5 +143,15 @@ fn clone_token_error(err: &TokenError) -> TokenError {
         TokenError::InvalidScope { message } => TokenError::InvalidScope {
             message: message.clone(),
         },
+        TokenError::AuthorizationPending => TokenError::AuthorizationPending,
+        TokenError::SlowDown => TokenError::SlowDown,
+        TokenError::ExpiredDeviceCode => TokenError::ExpiredDeviceCode,
+        TokenError::AccessDenied => TokenError::AccessDenied,
+        TokenError::InvalidDpopProof { reason } => TokenError::InvalidDpopProof {
+            reason: reason.clone(),
+        },
+        TokenError::UseDpopNonce { nonce } => TokenError::UseDpopNonce {
+            nonce: nonce.clone(),
+        },
     }
 }
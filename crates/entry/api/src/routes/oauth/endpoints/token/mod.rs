@@ -17,6 +17,7 @@ pub use handler::handle_token;
 #[cfg(feature = "test-api")]
 pub use handler::test_api as handler_test_api;
 
+use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 
 use crate::routes::oauth::OAuthHttpError;
@@ -42,6 +43,7 @@ pub struct TokenRequest {
     pub actor_token_type: Option<String>,
     pub requested_token_type: Option<String>,
     pub assertion: Option<String>,
+    pub device_code: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -93,6 +95,24 @@ pub enum TokenError {
 
     #[error("Invalid scope: {message}")]
     InvalidScope { message: String },
+
+    #[error("Authorization pending")]
+    AuthorizationPending,
+
+    #[error("Polling too fast")]
+    SlowDown,
+
+    #[error("Device code expired")]
+    ExpiredDeviceCode,
+
+    #[error("Device authorization denied")]
+    AccessDenied,
+
+    #[error("Invalid DPoP proof: {reason}")]
+    InvalidDpopProof { reason: String },
+
+    #[error("DPoP nonce required")]
+    UseDpopNonce { nonce: String },
 }
 
 impl From<TokenError> for OAuthHttpError {
@@ -115,6 +135,26 @@ impl From<TokenError> for OAuthHttpError {
             TokenError::ServerError { message } => Self::server_error(message),
             TokenError::InvalidTarget { message } => Self::invalid_target(message),
             TokenError::InvalidScope { message } => Self::invalid_scope(message),
+            TokenError::AuthorizationPending => {
+                Self::authorization_pending("The user has not yet approved the device")
+            },
+            TokenError::SlowDown => {
+                Self::slow_down("Polling too fast; wait the returned interval plus five seconds")
+            },
+            TokenError::ExpiredDeviceCode => {
+                Self::expired_token("Device code expired; restart device authorization")
+            },
+            // Why: RFC 8628 §3.5 returns `access_denied` as a token-endpoint
+            // error, which RFC 6749 §5.2 answers with 400 rather than 401.
+            TokenError::AccessDenied => {
+                Self::access_denied("The user denied the device authorization")
+                    .with_status(StatusCode::BAD_REQUEST)
+            },
+            TokenError::InvalidDpopProof { reason } => Self::invalid_dpop_proof(reason),
+            TokenError::UseDpopNonce { nonce } => {
+                Self::use_dpop_nonce("Resend the DPoP proof with the server-provided nonce")
+                    .with_dpop_nonce(nonce)
+            },
         }
     }
 }
@@ -3,7 +3,10 @@
 //! Consumes a verified-authentication token, confirms it matches the claimed
 //! user, mints an authorization code bound to the request's PKCE/resource
 //! parameters, and returns it as a browser redirect or JSON depending on the
-//! caller.
+//! caller. When the page was opened from the RFC 8628 verification URI the
+//! query carries a `user_code` instead: the verified user is recorded against
+//! that device authorization and shown the consent page, whose approve or deny
+//! action decides it.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -11,10 +14,11 @@
 use axum::Json;
 use axum::extract::{Query, State};
 use axum::http::HeaderMap;
-use axum::response::{IntoResponse, Redirect, Response};
+use axum::response::{Html, IntoResponse, Redirect, Response};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use super::consent::render_device_consent;
 use crate::routes::oauth::OAuthHttpError;
 use crate::routes::oauth::extractors::OAuthRepo;
 use crate::services::request_base_url::RequestBaseUrl;
@@ -23,7 +27,9 @@ use systemprompt_models::oauth::OAuthServerConfig;
 use systemprompt_oauth::OAuthState;
 use systemprompt_oauth::repository::{AuthCodeParams, OAuthRepository};
 use systemprompt_oauth::services::webauthn::WebAuthnRegistry;
-use systemprompt_oauth::services::{generate_secure_token, is_browser_request};
+use systemprompt_oauth::services::{
+    generate_secure_token, is_browser_request, normalize_user_code,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct WebAuthnCompleteQuery {
@@ -38,13 +44,14 @@ pub struct WebAuthnCompleteQuery {
     pub code_challenge_method: Option<String>,
     pub response_mode: Option<String>,
     pub resource: Option<String>,
+    pub user_code: Option<String>,
 }
 
-async fn verify_completion(
+async fn verify_authenticated_user(
     params: &WebAuthnCompleteQuery,
     state: &OAuthState,
     repo: &OAuthRepository,
-) -> Result<(UserId, String), OAuthHttpError> {
+) -> Result<UserId, OAuthHttpError> {
     let auth_token = params
         .auth_token
         .as_deref()
@@ -68,18 +75,32 @@ async fn verify_completion(
         ));
     }
 
+    if state
+        .user_provider()
+        .find_by_id(&verified_user_id)
+        .await?
+        .is_none()
+    {
+        return Err(OAuthHttpError::access_denied("User not found"));
+    }
+
+    Ok(verified_user_id)
+}
+
+async fn verify_completion(
+    params: &WebAuthnCompleteQuery,
+    state: &OAuthState,
+    repo: &OAuthRepository,
+) -> Result<(), OAuthHttpError> {
+    verify_authenticated_user(params, state, repo).await?;
+
     if params.client_id.is_none() {
         return Err(OAuthHttpError::invalid_request(
             "Missing client_id parameter",
         ));
     }
 
-    let redirect_uri = params
-        .redirect_uri
-        .clone()
-        .ok_or_else(|| OAuthHttpError::invalid_request("Missing redirect_uri parameter"))?;
-
-    Ok((verified_user_id, redirect_uri))
+    Ok(())
 }
 
 pub async fn handle_webauthn_complete(
@@ -89,30 +110,81 @@ pub async fn handle_webauthn_complete(
     State(state): State<OAuthState>,
     OAuthRepo(repo): OAuthRepo,
 ) -> Result<Response, OAuthHttpError> {
-    let (verified_user_id, redirect_uri) = verify_completion(&params, &state, &repo).await?;
-
-    let user = state.user_provider().find_by_id(&verified_user_id).await?;
-    if user.is_none() {
-        return Err(OAuthHttpError::access_denied("User not found"));
+    if let Some(user_code) = params.user_code.as_deref() {
+        return verify_device(&params, user_code, &state, &repo).await;
     }
 
-    let authorization_code = generate_secure_token("auth_code");
-    store_authorization_code(&repo, &authorization_code, &params).await?;
+    verify_completion(&params, &state, &repo).await?;
 
     // Why: RFC 9207: the authorization response carries `iss` so the client can
     // bind the code to this issuer. Derive it the same way discovery does, so
     // the emitted value is byte-identical to the advertised `issuer`.
     let issuer = OAuthServerConfig::from_api_server_url(base.as_str()).issuer;
 
+    complete_authorization(&repo, &params, &issuer, is_browser_request(&headers)).await
+}
+
+/// Mints the authorization code for an authenticated user and answers the
+/// client: a redirect for a browser, JSON otherwise. Shared with the upstream
+/// `OpenID` Connect callback, which authenticates the user differently.
+pub(crate) async fn complete_authorization(
+    repo: &OAuthRepository,
+    params: &WebAuthnCompleteQuery,
+    issuer: &str,
+    browser: bool,
+) -> Result<Response, OAuthHttpError> {
+    let redirect_uri = params
+        .redirect_uri
+        .as_deref()
+        .ok_or_else(|| OAuthHttpError::invalid_request("Missing redirect_uri parameter"))?;
+
+    let authorization_code = generate_secure_token("auth_code");
+    store_authorization_code(repo, &authorization_code, params).await?;
+
     Ok(create_successful_response(
-        &headers,
-        &redirect_uri,
+        browser,
+        redirect_uri,
         &authorization_code,
-        &params,
-        &issuer,
+        params,
+        issuer,
     ))
 }
 
+async fn verify_device(
+    params: &WebAuthnCompleteQuery,
+    user_code: &str,
+    state: &OAuthState,
+    repo: &OAuthRepository,
+) -> Result<Response, OAuthHttpError> {
+    let verified_user_id = verify_authenticated_user(params, state, repo).await?;
+
+    let user_code = normalize_user_code(user_code)
+        .ok_or_else(|| OAuthHttpError::invalid_request("Malformed user_code"))?;
+    let pending = repo
+        .find_pending_device_code(&user_code)
+        .await?
+        .ok_or_else(|| OAuthHttpError::invalid_grant("Device code is unknown, expired, or used"))?;
+
+    let consent_token = generate_secure_token("device_consent");
+    if !repo
+        .verify_device_code(&user_code, &verified_user_id, &consent_token)
+        .await?
+    {
+        return Err(OAuthHttpError::invalid_grant(
+            "Device code is unknown, expired, or used",
+        ));
+    }
+
+    tracing::info!(
+        client_id = %pending.client_id,
+        user_id = %verified_user_id,
+        "Device authorization awaiting consent"
+    );
+
+    let page = render_device_consent(repo, &pending, &user_code, &consent_token).await?;
+    Ok(Html(page).into_response())
+}
+
 async fn store_authorization_code(
     repo: &OAuthRepository,
     code_str: &str,
@@ -170,7 +242,7 @@ pub struct WebAuthnCompleteResponse {
 }
 
 fn create_successful_response(
-    headers: &HeaderMap,
+    browser: bool,
     redirect_uri: &str,
     authorization_code: &str,
     params: &WebAuthnCompleteQuery,
@@ -178,7 +250,7 @@ fn create_successful_response(
 ) -> Response {
     let state = params.state.as_deref().filter(|s| !s.is_empty());
 
-    if is_browser_request(headers) {
+    if browser {
         let mut target = format!("{redirect_uri}?code={authorization_code}");
 
         if let Some(client_id_val) = params.client_id.as_ref() {
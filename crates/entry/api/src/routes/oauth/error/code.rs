@@ -1,5 +1,6 @@
-//! RFC 6749 §5.2 error codes plus the `WebAuthn` / RFC 7591 extensions this
-//! server emits, and their default HTTP status mapping.
+//! RFC 6749 §5.2 error codes plus the `WebAuthn` / RFC 7591 / RFC 8628 /
+//! RFC 9449 extensions this server emits, and their default HTTP status
+//! mapping.
 //!
 //! `Display` (via [`OAuthErrorCode::as_str`]) yields the wire string. The
 //! default status follows §5.2: token-endpoint errors return 400 except
@@ -35,6 +36,11 @@ pub enum OAuthErrorCode {
     LinkFailed,
     InvalidTarget,
     NotFound,
+    AuthorizationPending,
+    SlowDown,
+    ExpiredToken,
+    InvalidDpopProof,
+    UseDpopNonce,
 }
 
 impl OAuthErrorCode {
@@ -61,6 +67,11 @@ impl OAuthErrorCode {
             Self::LinkFailed => "link_failed",
             Self::InvalidTarget => "invalid_target",
             Self::NotFound => "not_found",
+            Self::AuthorizationPending => "authorization_pending",
+            Self::SlowDown => "slow_down",
+            Self::ExpiredToken => "expired_token",
+            Self::InvalidDpopProof => "invalid_dpop_proof",
+            Self::UseDpopNonce => "use_dpop_nonce",
         }
     }
 
@@ -77,7 +88,12 @@ impl OAuthErrorCode {
             | Self::InvalidCredential
             | Self::LinkFailed
             | Self::InvalidTarget
-            | Self::RegistrationFailed => StatusCode::BAD_REQUEST,
+            | Self::RegistrationFailed
+            | Self::AuthorizationPending
+            | Self::SlowDown
+            | Self::ExpiredToken
+            | Self::InvalidDpopProof
+            | Self::UseDpopNonce => StatusCode::BAD_REQUEST,
             Self::InvalidClient
             | Self::AccessDenied
             | Self::AuthenticationFailed
@@ -29,7 +29,9 @@ impl From<OauthError> for OAuthHttpError {
             | OauthError::PkceMismatch(_)
             | OauthError::Expired(_) => Self::invalid_grant(err.to_string()),
             OauthError::Validation(_) => Self::invalid_request(err.to_string()),
-            OauthError::Unauthorized(_) => Self::access_denied(err.to_string()),
+            OauthError::Unauthorized(_) | OauthError::Federation(_) => {
+                Self::access_denied(err.to_string())
+            },
             OauthError::UsernameTaken(_) => Self::username_unavailable(
                 "Username is already taken. Please choose a different username.",
             ),
@@ -18,6 +18,7 @@ use axum::Json;
 use axum::http::{HeaderValue, StatusCode, header};
 use axum::response::{IntoResponse, Redirect, Response};
 use serde::Serialize;
+use systemprompt_security::dpop::DPOP_NONCE_HEADER;
 
 mod code;
 mod conversions;
@@ -36,6 +37,7 @@ pub struct OAuthHttpError {
     status: StatusCode,
     description: String,
     redirect: Option<RedirectContext>,
+    dpop_nonce: Option<String>,
 }
 
 impl OAuthHttpError {
@@ -46,6 +48,7 @@ impl OAuthHttpError {
             code,
             description: description.into(),
             redirect: None,
+            dpop_nonce: None,
         }
     }
 
@@ -144,6 +147,31 @@ impl OAuthHttpError {
         Self::new(OAuthErrorCode::NotFound, description)
     }
 
+    #[must_use]
+    pub fn authorization_pending(description: impl Into<String>) -> Self {
+        Self::new(OAuthErrorCode::AuthorizationPending, description)
+    }
+
+    #[must_use]
+    pub fn slow_down(description: impl Into<String>) -> Self {
+        Self::new(OAuthErrorCode::SlowDown, description)
+    }
+
+    #[must_use]
+    pub fn expired_token(description: impl Into<String>) -> Self {
+        Self::new(OAuthErrorCode::ExpiredToken, description)
+    }
+
+    #[must_use]
+    pub fn invalid_dpop_proof(description: impl Into<String>) -> Self {
+        Self::new(OAuthErrorCode::InvalidDpopProof, description)
+    }
+
+    #[must_use]
+    pub fn use_dpop_nonce(description: impl Into<String>) -> Self {
+        Self::new(OAuthErrorCode::UseDpopNonce, description)
+    }
+
     #[must_use]
     pub const fn with_status(mut self, status: StatusCode) -> Self {
         self.status = status;
@@ -159,6 +187,14 @@ impl OAuthHttpError {
         self
     }
 
+    /// Attaches the `DPoP-Nonce` the client must echo in its next proof
+    /// (RFC 9449 §8).
+    #[must_use]
+    pub fn with_dpop_nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.dpop_nonce = Some(nonce.into());
+        self
+    }
+
     #[must_use]
     pub const fn code(&self) -> OAuthErrorCode {
         self.code
@@ -218,6 +254,14 @@ impl IntoResponse for OAuthHttpError {
         };
         let mut response = (self.status, Json(body)).into_response();
 
+        if let Some(value) = self
+            .dpop_nonce
+            .as_deref()
+            .and_then(|nonce| HeaderValue::from_str(nonce).ok())
+        {
+            response.headers_mut().insert(DPOP_NONCE_HEADER, value);
+        }
+
         if self.status == StatusCode::UNAUTHORIZED
             && let Ok(value) = HeaderValue::from_str(
                 "Bearer resource_metadata=\"/.well-known/oauth-protected-resource\"",
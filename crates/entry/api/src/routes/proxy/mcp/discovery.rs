@@ -1,5 +1,5 @@
 //! RFC 9728 protected-resource and authorization-server metadata per MCP
-//! service, including the RFC 8693 token-type and EMA advertisements.
+//! service, including the RFC 8693 token-type, EMA and `DPoP` advertisements.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -14,6 +14,7 @@ use systemprompt_models::mcp::McpExtensionId;
 use systemprompt_models::oauth::ProtectedResourceMetadata;
 use systemprompt_oauth::services::validation::id_jag::{ID_JAG_GRANT_PROFILE, ID_JAG_TOKEN_TYPE};
 use systemprompt_oauth::{GrantType, PkceMethod, ResponseType, TokenAuthMethod};
+use systemprompt_security::dpop::DPOP_SIGNING_ALG_VALUES;
 use systemprompt_traits::McpRegistryProvider;
 
 use super::{McpState, get_mcp_server_scopes};
@@ -36,6 +37,7 @@ struct McpAuthorizationServerMetadata {
     subject_token_types_supported: Vec<String>,
     issued_token_types_supported: Vec<String>,
     authorization_grant_profiles_supported: Vec<String>,
+    dpop_signing_alg_values_supported: Vec<String>,
 }
 
 pub(super) async fn handle_mcp_protected_resource(
@@ -124,6 +126,10 @@ pub(super) async fn handle_mcp_authorization_server(
             ID_JAG_TOKEN_TYPE.to_owned(),
         ],
         authorization_grant_profiles_supported: vec![ID_JAG_GRANT_PROFILE.to_owned()],
+        dpop_signing_alg_values_supported: DPOP_SIGNING_ALG_VALUES
+            .iter()
+            .map(|alg| (*alg).to_owned())
+            .collect(),
     };
 
     (StatusCode::OK, Json(metadata)).into_response()
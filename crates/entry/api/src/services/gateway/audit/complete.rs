@@ -4,8 +4,11 @@
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
+use std::sync::atomic::Ordering;
+
 use anyhow::Result;
 use bytes::Bytes;
+use systemprompt_ai::models::RequestStatus;
 use systemprompt_ai::repository::ai_requests::UpdateCompletionParams;
 use systemprompt_ai::repository::{InsertToolCallParams, UpsertPayloadParams};
 use systemprompt_identifiers::AiToolCallId;
@@ -29,6 +32,18 @@ impl GatewayAudit {
             .unwrap_or_else(|| self.ctx.model.clone())
     }
 
+    fn effective_provider(&self) -> String {
+        self.served_provider
+            .lock()
+            .map_err(|e| {
+                tracing::warn!(error = %e, "served_provider mutex poisoned");
+                e
+            })
+            .ok()
+            .and_then(|s| s.clone())
+            .unwrap_or_else(|| self.ctx.provider.clone())
+    }
+
     pub async fn complete(
         &self,
         usage: CapturedUsage,
@@ -38,6 +53,7 @@ impl GatewayAudit {
     ) -> Result<i64> {
         let latency_ms = self.started_at.elapsed().as_millis().min(i32::MAX as u128) as i32;
         let effective_model = self.effective_model();
+        let effective_provider = self.effective_provider();
         let profile = systemprompt_config::ProfileBootstrap::get().ok();
         let gateway = profile
             .as_ref()
@@ -50,16 +66,22 @@ impl GatewayAudit {
             self.ctx.model.as_str(),
             self.ctx.requested_model.as_deref().unwrap_or(""),
         ];
-        let pricing_rates = pricing::resolve(&self.ctx.provider, &candidates, gateway, registry);
-        let cost = pricing::cost_microdollars(
-            pricing_rates,
-            pricing::CostTokens {
-                input: usage.input_tokens,
-                output: usage.output_tokens,
-                cache_read: usage.cache_read_tokens,
-                cache_creation: usage.cache_creation_tokens,
-            },
-        );
+        let pricing_rates = pricing::resolve(&effective_provider, &candidates, gateway, registry);
+        let from_response_cache = self.response_cache_hit.load(Ordering::Relaxed);
+        let cost = if from_response_cache {
+            0
+        } else {
+            pricing::cost_microdollars(
+                pricing_rates,
+                pricing::CostTokens {
+                    input: usage.input_tokens,
+                    output: usage.output_tokens,
+                    cache_read: usage.cache_read_tokens,
+                    cache_creation: usage.cache_creation_tokens,
+                },
+            )
+        };
+        let (status, truncated) = self.terminal_status();
         let tokens_used = usage.input_tokens
             + usage.output_tokens
             + usage.cache_read_tokens
@@ -76,6 +98,8 @@ impl GatewayAudit {
                 cache_hit: usage.cache_read_tokens > 0,
                 cache_read_tokens: usage.cache_read_tokens as i32,
                 cache_creation_tokens: usage.cache_creation_tokens as i32,
+                status,
+                error_message: truncated,
             })
             .await?;
 
@@ -85,20 +109,32 @@ impl GatewayAudit {
         tracing::info!(
             ai_request_id = %self.ctx.ai_request_id,
             user_id = %self.ctx.user_id,
-            provider = %self.ctx.provider,
+            provider = %effective_provider,
             model = %effective_model,
             wire_protocol = %self.ctx.wire_protocol,
             input_tokens = usage.input_tokens,
             output_tokens = usage.output_tokens,
             cache_read_tokens = usage.cache_read_tokens,
             cost_microdollars = cost,
+            from_response_cache,
             latency_ms,
             tool_calls = tool_calls.len(),
+            status = status.as_str(),
             "Gateway audit: request completed"
         );
         Ok(cost)
     }
 
+    fn terminal_status(&self) -> (RequestStatus, Option<String>) {
+        let truncated = self.truncated.lock().map_or(None, |reason| reason.clone());
+        let status = if truncated.is_some() {
+            RequestStatus::Truncated
+        } else {
+            RequestStatus::Completed
+        };
+        (status, truncated)
+    }
+
     async fn persist_response(&self, response: &CanonicalResponse, response_body: &Bytes) {
         let capture = slice_payload(response_body);
         if let Err(e) = self
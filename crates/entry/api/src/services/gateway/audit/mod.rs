@@ -4,8 +4,10 @@
 //! submodule), records the canonical messages and request payload, then closes
 //! it on completion with token usage, resolved cost, latency, captured tool
 //! calls, and the response payload (see the `complete` submodule) — or marks it
-//! failed. [`GatewayRequestContext`] carries the identifiers and routing
-//! metadata bound to a single request.
+//! failed. A stream the safety policy cut off closes like a completion, with
+//! the usage consumed up to that point, but is marked truncated.
+//! [`GatewayRequestContext`] carries the identifiers and routing metadata bound
+//! to a single request.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -20,6 +22,7 @@ pub mod test_api {
     pub use super::message_text::flatten_message_content;
 }
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
@@ -60,6 +63,9 @@ pub struct GatewayAudit {
     context_materializer: systemprompt_traits::DynContextMaterializer,
     pub ctx: GatewayRequestContext,
     served_model: Mutex<Option<String>>,
+    served_provider: Mutex<Option<String>>,
+    response_cache_hit: AtomicBool,
+    truncated: Mutex<Option<String>>,
     started_at: Instant,
 }
 
@@ -71,6 +77,9 @@ impl GatewayAudit {
             context_materializer: Arc::clone(&repos.context_materializer),
             ctx,
             served_model: Mutex::new(None),
+            served_provider: Mutex::new(None),
+            response_cache_hit: AtomicBool::new(false),
+            truncated: Mutex::new(None),
             started_at: Instant::now(),
         }
     }
@@ -122,6 +131,45 @@ impl GatewayAudit {
         }
     }
 
+    /// Record the hops a multi-target route tried, and the provider that
+    /// served (or last failed) the call. Completion prices against that
+    /// provider rather than the one the request was opened with.
+    pub async fn set_route_attempts(&self, provider: &str, attempts: &serde_json::Value) {
+        if let Ok(mut slot) = self.served_provider.lock() {
+            *slot = Some(provider.to_owned());
+        }
+        if let Err(e) = self
+            .requests
+            .update_route_attempts(&self.ctx.ai_request_id, provider, attempts)
+            .await
+        {
+            tracing::warn!(error = %e, "update_route_attempts failed");
+        }
+    }
+
+    /// Mark the request as answered from the gateway response cache. No
+    /// upstream was called, so completion records the replayed token counts
+    /// at zero cost.
+    pub async fn mark_response_cache_hit(&self) {
+        self.response_cache_hit.store(true, Ordering::Relaxed);
+        if let Err(e) = self
+            .requests
+            .mark_response_cache_hit(&self.ctx.ai_request_id)
+            .await
+        {
+            tracing::warn!(error = %e, "mark_response_cache_hit failed");
+        }
+    }
+
+    /// Mark the response as cut off before its end. The next
+    /// [`Self::complete`] still records usage and cost, but closes the record
+    /// as truncated with `reason` as its error message.
+    pub fn mark_truncated(&self, reason: &str) {
+        if let Ok(mut slot) = self.truncated.lock() {
+            *slot = Some(reason.to_owned());
+        }
+    }
+
     pub async fn fail(&self, error: &str) -> Result<()> {
         if let Err(e) = self
             .requests
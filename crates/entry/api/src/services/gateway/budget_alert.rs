@@ -0,0 +1,212 @@
+//! Budget alerts for gateway cost quotas.
+//!
+//! A [`QuotaWindow`] with `alert_thresholds` raises an alert the moment a
+//! subject's spend in the current window crosses one of them. Detection rides
+//! the atomic bucket increment in [`super::quota::post_update_tokens`]: the
+//! increment returns the new total, and exactly one increment moves a bucket
+//! across any given threshold, so replicas never alert twice for the same
+//! crossing and no delivery-state table is needed.
+//!
+//! Every crossing is emitted as a `BUDGET_THRESHOLD_CROSSED` event on the
+//! `systemprompt_events` bus, then delivered to each of the policy's
+//! [`BudgetAlertTarget`]s from a spawned task. Delivery failures are logged
+//! and never affect the request that crossed the threshold.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Utc};
+use systemprompt_agent::services::external_integrations::{WebhookConfig, WebhookService};
+use systemprompt_ai::BudgetAlertTarget;
+use systemprompt_config::SecretsBootstrap;
+use systemprompt_events::EventRouter;
+use systemprompt_identifiers::{TeamsConversationId, UserId};
+use systemprompt_loader::ConfigLoader;
+use systemprompt_models::{BudgetThresholdCrossedPayload, SystemEvent, SystemEventBuilder};
+use systemprompt_slack::client::SlackClient;
+use systemprompt_teams::client::TeamsClient;
+
+use super::policy::QuotaWindow;
+use crate::routes::messaging::http_client;
+
+static WEBHOOKS: LazyLock<WebhookService> = LazyLock::new(WebhookService::new);
+
+/// The bucket a cost increment landed in.
+#[derive(Debug, Clone, Copy)]
+pub struct BucketSpend<'a> {
+    pub subject_kind: &'a str,
+    pub subject_id: &'a str,
+    pub window_start: DateTime<Utc>,
+    pub spent_before: i64,
+    pub spent_after: i64,
+}
+
+/// One payload per alert threshold of `window` that `spend` crossed.
+#[must_use]
+pub fn crossings(
+    window: &QuotaWindow,
+    spend: BucketSpend<'_>,
+) -> Vec<BudgetThresholdCrossedPayload> {
+    let Some(limit) = window.max_cost_microdollars else {
+        return Vec::new();
+    };
+    window
+        .crossed_thresholds(spend.spent_before, spend.spent_after)
+        .into_iter()
+        .map(|threshold_percent| BudgetThresholdCrossedPayload {
+            subject_kind: spend.subject_kind.to_owned(),
+            subject_id: spend.subject_id.to_owned(),
+            window_seconds: window.window_seconds,
+            window_start: spend.window_start,
+            threshold_percent,
+            spent_microdollars: spend.spent_after,
+            limit_microdollars: limit,
+        })
+        .collect()
+}
+
+/// The human-readable line Slack and Teams targets receive.
+#[must_use]
+pub fn alert_text(crossing: &BudgetThresholdCrossedPayload) -> String {
+    format!(
+        "Budget alert: {} `{}` has reached {}% of its {}s cost budget (${} of ${}).",
+        crossing.subject_kind,
+        crossing.subject_id,
+        crossing.threshold_percent,
+        crossing.window_seconds,
+        dollars(crossing.spent_microdollars),
+        dollars(crossing.limit_microdollars),
+    )
+}
+
+fn dollars(microdollars: i64) -> String {
+    format!("{:.2}", microdollars as f64 / 1_000_000.0)
+}
+
+/// Emits each crossing on the event bus and delivers it to every target, off
+/// the request path.
+pub(super) fn spawn_notify(
+    user_id: &UserId,
+    crossings: Vec<BudgetThresholdCrossedPayload>,
+    targets: &[BudgetAlertTarget],
+) {
+    if crossings.is_empty() {
+        return;
+    }
+    let user_id = user_id.clone();
+    let targets = targets.to_vec();
+    tokio::spawn(async move {
+        for crossing in crossings {
+            tracing::info!(
+                subject_kind = %crossing.subject_kind,
+                subject_id = %crossing.subject_id,
+                window_seconds = crossing.window_seconds,
+                threshold_percent = crossing.threshold_percent,
+                "gateway budget threshold crossed"
+            );
+            let event = SystemEventBuilder::budget_threshold_crossed(crossing.clone());
+            for target in &targets {
+                deliver(target, &crossing, &event).await;
+            }
+            EventRouter::route_system(&user_id, event).await;
+        }
+    });
+}
+
+async fn deliver(
+    target: &BudgetAlertTarget,
+    crossing: &BudgetThresholdCrossedPayload,
+    event: &SystemEvent,
+) {
+    let result = match target {
+        BudgetAlertTarget::Webhook { url, secret_ref } => {
+            deliver_webhook(url, secret_ref.as_deref(), event).await
+        },
+        BudgetAlertTarget::Slack { app, channel } => deliver_slack(app, channel, crossing).await,
+        BudgetAlertTarget::Teams {
+            app,
+            service_url,
+            conversation_id,
+        } => deliver_teams(app, service_url, conversation_id, crossing).await,
+    };
+    if let Err(reason) = result {
+        tracing::warn!(target = ?target, reason = %reason, "budget alert delivery failed");
+    }
+}
+
+async fn deliver_webhook(
+    url: &str,
+    secret_ref: Option<&str>,
+    event: &SystemEvent,
+) -> Result<(), String> {
+    let payload = serde_json::to_value(event).map_err(|e| e.to_string())?;
+    let secret = match secret_ref {
+        Some(name) => Some(secret(name).ok_or_else(|| format!("secret '{name}' is not set"))?),
+        None => None,
+    };
+    let config = WebhookConfig {
+        secret,
+        ..WebhookConfig::default()
+    };
+    let delivery = WEBHOOKS
+        .send_webhook(url, payload, Some(config))
+        .await
+        .map_err(|e| e.to_string())?;
+    if delivery.success {
+        Ok(())
+    } else {
+        Err(delivery
+            .error
+            .unwrap_or_else(|| format!("webhook answered {}", delivery.status_code)))
+    }
+}
+
+async fn deliver_slack(
+    app: &str,
+    channel: &str,
+    crossing: &BudgetThresholdCrossedPayload,
+) -> Result<(), String> {
+    let config = ConfigLoader::load().map_err(|e| e.to_string())?;
+    let app_config = config
+        .slack_apps
+        .get(app)
+        .ok_or_else(|| format!("slack app '{app}' is not configured"))?;
+    let token = secret(app_config.bot_token_ref.as_str())
+        .ok_or_else(|| format!("slack app '{app}' has no bot token"))?;
+    let blocks = systemprompt_slack::blockkit::render_blocks(&alert_text(crossing));
+    SlackClient::new(http_client(), token)
+        .post_message(channel, blocks)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn deliver_teams(
+    app: &str,
+    service_url: &str,
+    conversation_id: &str,
+    crossing: &BudgetThresholdCrossedPayload,
+) -> Result<(), String> {
+    let config = ConfigLoader::load().map_err(|e| e.to_string())?;
+    let app_config = config
+        .teams_apps
+        .get(app)
+        .ok_or_else(|| format!("teams app '{app}' is not configured"))?;
+    let password = secret(app_config.app_password_ref.as_str())
+        .ok_or_else(|| format!("teams app '{app}' has no app password"))?;
+    let attachments = systemprompt_teams::cards::render_card(&alert_text(crossing));
+    TeamsClient::new(http_client(), app_config.app_id.clone(), password)
+        .reply(
+            service_url,
+            &TeamsConversationId::new(conversation_id),
+            attachments,
+            Utc::now().timestamp(),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn secret(name: &str) -> Option<String> {
+    SecretsBootstrap::get().ok()?.get(name).cloned()
+}
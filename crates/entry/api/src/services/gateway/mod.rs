@@ -5,9 +5,10 @@
 //! Responses) are parsed into a canonical form, dispatched to an upstream
 //! provider via the [`protocol`] adapters, and rendered back in the caller's
 //! protocol. [`GatewayService`] orchestrates the flow; supporting modules cover
-//! [`policy`] resolution, [`quota`] enforcement, safety scanning, usage
-//! [`captures`], [`pricing`], the upstream and safety-scanner [`registry`], and
-//! the [`audit`] trail.
+//! [`policy`] resolution, [`quota`] and [`rate_limit`] enforcement with
+//! [`budget_alert`] notifications, safety
+//! scanning, usage [`captures`], [`pricing`], the upstream and safety-scanner
+//! [`registry`], the opt-in [`response_cache`], and the [`audit`] trail.
 //!
 //! The safety-scanner contract —
 //! [`SafetyScanner`](systemprompt_ai::SafetyScanner),
@@ -21,13 +22,16 @@
 //! See <https://systemprompt.io> for licensing details.
 
 pub mod audit;
+pub mod budget_alert;
 pub mod captures;
 pub mod parse;
 pub mod policy;
 pub mod pricing;
 pub mod protocol;
 pub mod quota;
+pub mod rate_limit;
 pub mod registry;
+pub mod response_cache;
 pub mod service;
 pub mod signature_cache;
 pub mod stream_tap;
@@ -13,7 +13,9 @@ use std::time::{Duration, Instant};
 
 use systemprompt_ai::repository::AiGatewayPolicyRepository;
 
-pub use systemprompt_ai::{GatewayPolicySpec, QuotaWindow, SafetyConfig};
+pub use systemprompt_ai::{
+    BudgetAlertTarget, GatewayPolicySpec, QuotaWindow, RateLimit, SafetyConfig,
+};
 
 const CACHE_TTL: Duration = Duration::from_secs(60);
 
@@ -80,12 +82,22 @@ fn merge(rows: Vec<systemprompt_ai::GatewayPolicyRow>) -> GatewayPolicySpec {
         if !spec.quota_windows.is_empty() {
             merged.quota_windows = spec.quota_windows;
         }
+        if !spec.rate_limits.is_empty() {
+            merged.rate_limits = spec.rate_limits;
+        }
+        if !spec.alerts.is_empty() {
+            merged.alerts = spec.alerts;
+        }
         if !spec.safety.scanners.is_empty()
             || !spec.safety.block_categories.is_empty()
             || !spec.safety.block_response_categories.is_empty()
+            || spec.safety.redaction.is_active()
         {
             merged.safety = spec.safety;
         }
+        if spec.cache.enabled {
+            merged.cache = spec.cache;
+        }
     }
     merged
 }
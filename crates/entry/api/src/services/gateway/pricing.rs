@@ -6,11 +6,17 @@
 //! (`gpt-5-mini-2025-08-07`) absent from the catalog must still bill against
 //! the configured model, so the first candidate that resolves wins. For each
 //! candidate, resolution is top-down:
-//!   1. Profile `GatewayRoute.pricing` whose `model_pattern` matches (operator
-//!      override, the strongest "we pay a custom rate here" signal).
-//!   2. The matching `ProviderModel.pricing` in `profile.providers` — the route
-//!      provider's catalog entry, else any provider that serves it. The
-//!      provider registry is the single source of model pricing.
+//!   1. Profile `GatewayRoute.pricing` whose `model_pattern` matches, when the
+//!      route's primary provider served the call (operator override, the
+//!      strongest "we pay a custom rate here" signal). The override prices the
+//!      primary only; a fallback hop is billed at its own provider's rate.
+//!   2. The matching `ProviderModel.pricing` in the serving provider's entry in
+//!      `profile.providers`.
+//!
+//! Only when no candidate resolves against the serving provider does the
+//! lookup widen to the route provider's catalog entry, else any provider that
+//! serves the model. The provider registry is the single source of model
+//! pricing.
 //!
 //! If no candidate resolves, emit a WARN and return zero pricing — a real
 //! configuration gap, not noise to silence.
@@ -27,10 +33,12 @@ pub fn resolve(
     gateway: Option<&GatewayConfig>,
     registry: &ProviderRegistry,
 ) -> ModelPricing {
-    for model in candidates.iter().filter(|m| !m.is_empty()) {
-        if let Some(p) = lookup(model, gateway, registry) {
-            return p;
-        }
+    let models = || candidates.iter().copied().filter(|m| !m.is_empty());
+    if let Some(p) = models()
+        .find_map(|model| served_pricing(provider, model, gateway, registry))
+        .or_else(|| models().find_map(|model| registry_pricing(registry, gateway, model)))
+    {
+        return p;
     }
 
     tracing::warn!(
@@ -41,18 +49,22 @@ pub fn resolve(
     ModelPricing::default()
 }
 
-fn lookup(
+fn served_pricing(
+    provider: &str,
     model: &str,
     gateway: Option<&GatewayConfig>,
     registry: &ProviderRegistry,
 ) -> Option<ModelPricing> {
-    if let Some(gw) = gateway
-        && let Some(route) = gw.find_route(model)
+    if let Some(route) = gateway.and_then(|gw| gw.find_route(model))
+        && route.provider.as_str() == provider
         && let Some(p) = route.pricing
     {
         return Some(p);
     }
-    registry_pricing(registry, gateway, model)
+    registry
+        .find_provider(provider)
+        .and_then(|entry| entry.find_model(model))
+        .map(|m| m.pricing)
 }
 
 fn registry_pricing(
@@ -15,6 +15,7 @@ use systemprompt_models::profile::WireProtocol;
 use super::super::canonical::CanonicalRequest;
 use super::super::canonical_response::{CanonicalEvent, CanonicalResponse};
 use super::{InboundAdapter, InboundParseError};
+use crate::services::gateway::rate_limit::RateLimitStatus;
 
 mod parse;
 mod render;
@@ -61,4 +62,8 @@ impl InboundAdapter for AnthropicMessagesInbound {
         );
         Bytes::from(body)
     }
+
+    fn rate_limit_headers(&self, status: &RateLimitStatus) -> Vec<(String, String)> {
+        status.anthropic_headers()
+    }
 }
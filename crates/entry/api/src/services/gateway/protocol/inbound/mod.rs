@@ -3,21 +3,27 @@
 //! The [`InboundAdapter`] trait parses a request body into a
 //! [`CanonicalRequest`] and renders canonical responses, streaming events, and
 //! errors back in the caller's protocol. Implementations cover the Anthropic
-//! Messages and `OpenAI` Responses surfaces; [`InboundParseError`] reports
-//! malformed or unsupported inputs.
+//! Messages, `OpenAI` Responses, `OpenAI` Chat Completions, and `OpenAI`
+//! Embeddings surfaces; [`InboundParseError`] reports malformed or unsupported
+//! inputs.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 pub mod anthropic_messages;
+pub mod openai_chat;
+pub mod openai_embeddings;
 pub mod openai_responses;
 
+use std::sync::Arc;
+
 use bytes::Bytes;
 use http::StatusCode;
 use systemprompt_models::profile::WireProtocol;
 
 use super::canonical::CanonicalRequest;
 use super::canonical_response::{CanonicalEvent, CanonicalResponse};
+use crate::services::gateway::rate_limit::RateLimitStatus;
 
 #[derive(Debug, thiserror::Error)]
 pub enum InboundParseError {
@@ -36,7 +42,22 @@ pub trait InboundAdapter: Send + Sync + std::fmt::Debug {
         None
     }
 
+    /// Whether this surface requests embeddings rather than a generation;
+    /// dispatch then takes the embeddings lane instead of the chat send.
+    fn serves_embeddings(&self) -> bool {
+        false
+    }
+
     fn parse_request(&self, raw: &Bytes) -> Result<CanonicalRequest, InboundParseError>;
+
+    /// Returns a per-request adapter for protocols whose rendering carries
+    /// state across a stream, or `None` when the shared instance suffices.
+    fn bind_request(&self, raw: &Bytes) -> Option<Arc<dyn InboundAdapter>> {
+        // Why: unused-arg suppression in a default trait method body.
+        let _ = raw;
+        None
+    }
+
     fn render_response(&self, response: &CanonicalResponse) -> Bytes;
     fn render_event(&self, event: &CanonicalEvent, model: &str) -> Option<Bytes>;
 
@@ -52,6 +73,15 @@ pub trait InboundAdapter: Send + Sync + std::fmt::Debug {
     }
 
     fn render_error(&self, status: StatusCode, message: &str) -> Bytes;
+
+    /// Provider-style headers describing the limit behind a `429` the gateway
+    /// itself returns. The default sends none beyond `retry-after`.
+    fn rate_limit_headers(&self, status: &RateLimitStatus) -> Vec<(String, String)> {
+        // Why: unused-arg suppression in a default trait method body.
+        let _ = status;
+        Vec::new()
+    }
+
     fn streaming_content_type(&self) -> &'static str {
         "text/event-stream"
     }
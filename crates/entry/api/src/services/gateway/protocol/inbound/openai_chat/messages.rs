@@ -0,0 +1,192 @@
+//! Parses `OpenAI` Chat Completions messages into canonical messages.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+// JSON: protocol boundary — OpenAI Chat Completions wire format is dynamic
+// JSON.
+use serde_json::Value;
+
+use super::super::super::canonical::{
+    CanonicalContent, CanonicalMessage, ImageDetail, ImageSource, Role,
+};
+use super::super::InboundParseError;
+
+pub(super) fn parse_messages(
+    arr: &[Value],
+) -> Result<(Option<String>, Vec<CanonicalMessage>), InboundParseError> {
+    let mut system: Vec<String> = Vec::new();
+    let mut messages: Vec<CanonicalMessage> = Vec::with_capacity(arr.len());
+    for item in arr {
+        let role = item
+            .get("role")
+            .and_then(Value::as_str)
+            .ok_or(InboundParseError::MissingField("messages[].role"))?;
+        match role {
+            "system" | "developer" => {
+                let text = flatten_text(item.get("content").unwrap_or(&Value::Null))?;
+                if !text.is_empty() {
+                    system.push(text);
+                }
+            },
+            "user" => messages.push(CanonicalMessage {
+                role: Role::User,
+                content: parse_content(item.get("content").unwrap_or(&Value::Null))?,
+            }),
+            "assistant" => messages.push(parse_assistant(item)?),
+            "tool" => messages.push(parse_tool_result(item)?),
+            other => {
+                return Err(InboundParseError::Unsupported {
+                    field: "messages[].role",
+                    detail: other.to_owned(),
+                });
+            },
+        }
+    }
+    let system = (!system.is_empty()).then(|| system.join("\n\n"));
+    Ok((system, messages))
+}
+
+fn parse_assistant(item: &Value) -> Result<CanonicalMessage, InboundParseError> {
+    let mut content = parse_content(item.get("content").unwrap_or(&Value::Null))?;
+    if let Some(calls) = item.get("tool_calls").and_then(Value::as_array) {
+        content.extend(calls.iter().map(parse_tool_call));
+    }
+    Ok(CanonicalMessage {
+        role: Role::Assistant,
+        content,
+    })
+}
+
+fn parse_tool_call(call: &Value) -> CanonicalContent {
+    let id = call
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_owned();
+    let function = call.get("function").unwrap_or(&Value::Null);
+    let name = function
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_owned();
+    let arguments = function
+        .get("arguments")
+        .and_then(Value::as_str)
+        .filter(|a| !a.is_empty())
+        .unwrap_or("{}");
+    let input: Value = serde_json::from_str(arguments).unwrap_or(Value::Null);
+    CanonicalContent::ToolUse {
+        id,
+        name,
+        input,
+        signature: None,
+    }
+}
+
+fn parse_tool_result(item: &Value) -> Result<CanonicalMessage, InboundParseError> {
+    let tool_use_id = item
+        .get("tool_call_id")
+        .and_then(Value::as_str)
+        .ok_or(InboundParseError::MissingField("messages[].tool_call_id"))?
+        .to_owned();
+    let text = flatten_text(item.get("content").unwrap_or(&Value::Null))?;
+    Ok(CanonicalMessage {
+        role: Role::Tool,
+        content: vec![CanonicalContent::ToolResult {
+            tool_use_id,
+            content: vec![CanonicalContent::Text(text)],
+            is_error: false,
+            structured_content: None,
+            meta: None,
+        }],
+    })
+}
+
+fn parse_content(value: &Value) -> Result<Vec<CanonicalContent>, InboundParseError> {
+    match value {
+        Value::Null => Ok(Vec::new()),
+        Value::String(s) if s.is_empty() => Ok(Vec::new()),
+        Value::String(s) => Ok(vec![CanonicalContent::Text(s.clone())]),
+        Value::Array(parts) => parts.iter().map(parse_content_part).collect(),
+        other => Err(InboundParseError::Unsupported {
+            field: "messages[].content",
+            detail: format!("unexpected shape: {other}"),
+        }),
+    }
+}
+
+fn parse_content_part(part: &Value) -> Result<CanonicalContent, InboundParseError> {
+    let kind = part.get("type").and_then(Value::as_str).unwrap_or("");
+    match kind {
+        "text" => Ok(CanonicalContent::Text(
+            part.get("text")
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_owned(),
+        )),
+        "image_url" => {
+            let image = part
+                .get("image_url")
+                .ok_or(InboundParseError::MissingField(
+                    "messages[].content[].image_url",
+                ))?;
+            let url =
+                image
+                    .get("url")
+                    .and_then(Value::as_str)
+                    .ok_or(InboundParseError::MissingField(
+                        "messages[].content[].image_url.url",
+                    ))?;
+            let detail = image
+                .get("detail")
+                .and_then(Value::as_str)
+                .and_then(parse_image_detail);
+            Ok(CanonicalContent::Image(image_source(url, detail)))
+        },
+        other => Err(InboundParseError::Unsupported {
+            field: "messages[].content[].type",
+            detail: other.to_owned(),
+        }),
+    }
+}
+
+// Why: a `data:` URL is an inline image. Carrying it as base64 lets the
+// Anthropic and Gemini codecs send it inline instead of as a URL they would
+// have to fetch.
+fn image_source(url: &str, detail: Option<ImageDetail>) -> ImageSource {
+    url.strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"))
+        .map_or_else(
+            || ImageSource::Url {
+                url: url.to_owned(),
+                detail,
+            },
+            |(media_type, data)| ImageSource::Base64 {
+                media_type: media_type.to_owned(),
+                data: data.to_owned(),
+                detail,
+            },
+        )
+}
+
+fn parse_image_detail(value: &str) -> Option<ImageDetail> {
+    match value {
+        "auto" => Some(ImageDetail::Auto),
+        "low" => Some(ImageDetail::Low),
+        "high" => Some(ImageDetail::High),
+        _ => None,
+    }
+}
+
+fn flatten_text(value: &Value) -> Result<String, InboundParseError> {
+    let parts = parse_content(value)?;
+    Ok(parts
+        .iter()
+        .filter_map(|p| match p {
+            CanonicalContent::Text(t) => Some(t.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
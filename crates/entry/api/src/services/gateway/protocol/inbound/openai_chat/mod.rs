@@ -0,0 +1,108 @@
+//! Inbound adapter for the `OpenAI` Chat Completions wire protocol.
+//!
+//! [`OpenAiChatInbound`] parses Chat Completions request bodies into the
+//! canonical request model and renders canonical responses, streaming chunks,
+//! and errors back in Chat Completions format. The router holds an unbound
+//! instance; [`InboundAdapter::bind_request`] gives each request its own
+//! stream state (completion id, tool-call numbering, usage opt-in).
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use http::StatusCode;
+use serde_json::Value;
+
+use super::super::canonical::CanonicalRequest;
+use super::super::canonical_response::{CanonicalEvent, CanonicalResponse};
+use super::{InboundAdapter, InboundParseError};
+use crate::services::gateway::rate_limit::RateLimitStatus;
+
+mod messages;
+mod parse;
+mod render;
+
+pub use render::ChatStream;
+
+#[cfg(feature = "test-api")]
+pub mod test_api {
+    pub use super::parse::parse as parse_request;
+    pub use super::render::{render_chunk_frame, render_completion_object, render_finish_frames};
+}
+
+#[derive(Debug, Default)]
+pub struct OpenAiChatInbound {
+    stream: ChatStream,
+}
+
+impl OpenAiChatInbound {
+    #[must_use]
+    pub const fn with_stream(stream: ChatStream) -> Self {
+        Self { stream }
+    }
+}
+
+impl InboundAdapter for OpenAiChatInbound {
+    fn wire_name(&self) -> &'static str {
+        "openai.chat"
+    }
+
+    fn parse_request(&self, raw: &Bytes) -> Result<CanonicalRequest, InboundParseError> {
+        let value: Value = serde_json::from_slice(raw)
+            .map_err(|e| InboundParseError::InvalidJson(e.to_string()))?;
+        parse::parse(&value)
+    }
+
+    fn bind_request(&self, raw: &Bytes) -> Option<Arc<dyn InboundAdapter>> {
+        let include_usage = serde_json::from_slice::<Value>(raw)
+            .ok()
+            .and_then(|v| {
+                v.pointer("/stream_options/include_usage")
+                    .and_then(Value::as_bool)
+            })
+            .unwrap_or(false);
+        let id = format!("chatcmpl-{}", uuid::Uuid::new_v4().simple());
+        Some(Arc::new(Self::with_stream(ChatStream::new(
+            id,
+            include_usage,
+        ))))
+    }
+
+    fn render_response(&self, response: &CanonicalResponse) -> Bytes {
+        let value = render::render_completion_object(response);
+        Bytes::from(serde_json::to_vec(&value).unwrap_or_else(|_| b"{}".to_vec()))
+    }
+
+    fn render_event(&self, event: &CanonicalEvent, model: &str) -> Option<Bytes> {
+        render::render_chunk_frame(event, model, &self.stream)
+    }
+
+    fn render_terminal_event(
+        &self,
+        event: &CanonicalEvent,
+        snapshot: &CanonicalResponse,
+        model: &str,
+    ) -> Option<Bytes> {
+        let CanonicalEvent::MessageStop { stop_reason, .. } = event else {
+            return None;
+        };
+        Some(render::render_finish_frames(
+            *stop_reason,
+            snapshot,
+            model,
+            &self.stream,
+        ))
+    }
+
+    fn render_error(&self, _status: StatusCode, message: &str) -> Bytes {
+        let escaped = message.replace('\\', "\\\\").replace('"', "\\\"");
+        let body = format!("{{\"error\":{{\"type\":\"api_error\",\"message\":\"{escaped}\"}}}}");
+        Bytes::from(body)
+    }
+
+    fn rate_limit_headers(&self, status: &RateLimitStatus) -> Vec<(String, String)> {
+        status.openai_headers()
+    }
+}
@@ -0,0 +1,205 @@
+//! Parses `OpenAI` Chat Completions requests into the canonical request.
+//!
+//! System and developer messages are folded into the canonical `system`
+//! prompt rather than kept as `Role::System` turns: the Anthropic and Gemini
+//! codecs drop system-role messages, so a Chat caller's instructions would
+//! otherwise vanish on those upstreams.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+// JSON: protocol boundary — OpenAI Chat Completions wire format is dynamic
+// JSON.
+use serde_json::{Map, Value};
+use systemprompt_models::wire::inspect::ForwardedSurface;
+
+use super::super::super::canonical::{
+    CanonicalRequest, CanonicalTool, CanonicalToolChoice, ReasoningEffort, ResponseFormat,
+};
+use super::super::InboundParseError;
+use super::messages::parse_messages;
+
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+#[cfg_attr(
+    not(feature = "test-api"),
+    expect(
+        unreachable_pub,
+        reason = "items are re-exported via `test_api` only when the feature is on"
+    )
+)]
+pub fn parse(value: &Value) -> Result<CanonicalRequest, InboundParseError> {
+    let model = value
+        .get("model")
+        .and_then(Value::as_str)
+        .ok_or(InboundParseError::MissingField("model"))?
+        .to_owned();
+    if value
+        .get("n")
+        .and_then(Value::as_u64)
+        .is_some_and(|n| n > 1)
+    {
+        return Err(InboundParseError::Unsupported {
+            field: "n",
+            detail: "the gateway returns a single choice".to_owned(),
+        });
+    }
+
+    let raw_messages = value
+        .get("messages")
+        .and_then(Value::as_array)
+        .ok_or(InboundParseError::MissingField("messages"))?;
+    let (system, messages) = parse_messages(raw_messages)?;
+
+    // Why: `max_completion_tokens` replaced `max_tokens` on the Chat surface;
+    // current SDKs send the new name, older ones the legacy one.
+    let max_tokens = value
+        .get("max_completion_tokens")
+        .or_else(|| value.get("max_tokens"))
+        .and_then(Value::as_u64)
+        .map_or(DEFAULT_MAX_TOKENS, |v| v as u32);
+    let stream = value
+        .get("stream")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let response_format = value
+        .get("response_format")
+        .map(parse_response_format)
+        .transpose()?
+        .flatten();
+    let reasoning_effort = value
+        .get("reasoning_effort")
+        .and_then(Value::as_str)
+        .map(parse_reasoning_effort)
+        .transpose()?;
+
+    Ok(CanonicalRequest {
+        model,
+        system,
+        messages,
+        max_tokens,
+        temperature: f32_field(value, "temperature"),
+        top_p: f32_field(value, "top_p"),
+        top_k: None,
+        stop_sequences: parse_stop(value.get("stop")),
+        tools: value
+            .get("tools")
+            .and_then(Value::as_array)
+            .map_or_else(Vec::new, |arr| arr.iter().filter_map(parse_tool).collect()),
+        tool_choice: value.get("tool_choice").and_then(parse_tool_choice),
+        stream,
+        thinking: None,
+        metadata: value.get("metadata").cloned(),
+        response_format,
+        reasoning_effort,
+        search: None,
+        code_execution: false,
+        presence_penalty: f32_field(value, "presence_penalty"),
+        frequency_penalty: f32_field(value, "frequency_penalty"),
+        forwarded_surface: ForwardedSurface::default(),
+    })
+}
+
+fn f32_field(value: &Value, key: &str) -> Option<f32> {
+    value.get(key).and_then(Value::as_f64).map(|v| v as f32)
+}
+
+fn parse_stop(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(s)) => vec![s.clone()],
+        Some(Value::Array(arr)) => arr
+            .iter()
+            .filter_map(|x| x.as_str().map(str::to_owned))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_tool(value: &Value) -> Option<CanonicalTool> {
+    let kind = value
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("function");
+    if kind != "function" {
+        return None;
+    }
+    let function = value.get("function")?;
+    let name = function.get("name").and_then(Value::as_str)?;
+    let description = function
+        .get("description")
+        .and_then(Value::as_str)
+        .map(str::to_owned);
+    let parameters = function
+        .get("parameters")
+        .cloned()
+        .unwrap_or(Value::Object(Map::new()));
+    Some(CanonicalTool {
+        name: name.to_owned(),
+        description,
+        input_schema: parameters,
+    })
+}
+
+fn parse_tool_choice(value: &Value) -> Option<CanonicalToolChoice> {
+    if let Some(s) = value.as_str() {
+        return match s {
+            "auto" => Some(CanonicalToolChoice::Auto),
+            "none" => Some(CanonicalToolChoice::None),
+            "required" => Some(CanonicalToolChoice::Required),
+            _ => None,
+        };
+    }
+    value
+        .get("function")
+        .and_then(|f| f.get("name"))
+        .and_then(Value::as_str)
+        .map(|n| CanonicalToolChoice::Tool(n.to_owned()))
+}
+
+fn parse_response_format(value: &Value) -> Result<Option<ResponseFormat>, InboundParseError> {
+    let kind = value.get("type").and_then(Value::as_str).unwrap_or("text");
+    match kind {
+        "text" => Ok(None),
+        "json_object" => Ok(Some(ResponseFormat::JsonObject)),
+        "json_schema" => {
+            let spec = value
+                .get("json_schema")
+                .ok_or(InboundParseError::MissingField(
+                    "response_format.json_schema",
+                ))?;
+            let name = spec
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or(InboundParseError::MissingField(
+                    "response_format.json_schema.name",
+                ))?
+                .to_owned();
+            let schema = spec
+                .get("schema")
+                .cloned()
+                .unwrap_or(Value::Object(Map::new()));
+            let strict = spec.get("strict").and_then(Value::as_bool).unwrap_or(false);
+            Ok(Some(ResponseFormat::JsonSchema {
+                name,
+                schema,
+                strict,
+            }))
+        },
+        other => Err(InboundParseError::Unsupported {
+            field: "response_format.type",
+            detail: other.to_owned(),
+        }),
+    }
+}
+
+fn parse_reasoning_effort(value: &str) -> Result<ReasoningEffort, InboundParseError> {
+    match value {
+        "minimal" | "low" => Ok(ReasoningEffort::Low),
+        "medium" => Ok(ReasoningEffort::Medium),
+        "high" => Ok(ReasoningEffort::High),
+        other => Err(InboundParseError::Unsupported {
+            field: "reasoning_effort",
+            detail: other.to_owned(),
+        }),
+    }
+}
@@ -0,0 +1,256 @@
+//! Renders canonical responses and events as `OpenAI` Chat Completions
+//! objects and `chat.completion.chunk` SSE frames.
+//!
+//! A Chat stream repeats the completion id and `created` stamp on every chunk
+//! and numbers tool calls by their own ordinal rather than by content block,
+//! so each stream renders through a [`ChatStream`] bound to its request. The
+//! stream ends with a `finish_reason` chunk, an optional usage chunk, and the
+//! `data: [DONE]` sentinel.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::Mutex;
+
+use bytes::Bytes;
+// JSON: protocol boundary — OpenAI Chat Completions wire format is dynamic
+// JSON.
+use serde_json::{Map, Value, json};
+
+use super::super::super::canonical::CanonicalContent;
+use super::super::super::canonical_response::{
+    CanonicalEvent, CanonicalResponse, CanonicalStopReason, CanonicalUsage, ContentBlockKind,
+};
+
+const DONE_FRAME: &str = "data: [DONE]\n\n";
+
+/// Per-request stream state: the completion id and `created` stamp every
+/// chunk repeats, whether the caller asked for a usage chunk, and the content
+/// blocks that opened as tool calls, in order.
+#[derive(Debug, Default)]
+pub struct ChatStream {
+    pub id: String,
+    pub created: u64,
+    pub include_usage: bool,
+    tool_blocks: Mutex<Vec<u32>>,
+}
+
+impl ChatStream {
+    #[must_use]
+    pub fn new(id: String, include_usage: bool) -> Self {
+        Self {
+            id,
+            created: current_unix_ts(),
+            include_usage,
+            tool_blocks: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn open_tool_call(&self, block_index: u32) -> u32 {
+        let Ok(mut blocks) = self.tool_blocks.lock() else {
+            return block_index;
+        };
+        blocks.push(block_index);
+        (blocks.len() - 1) as u32
+    }
+
+    fn tool_call_index(&self, block_index: u32) -> u32 {
+        self.tool_blocks
+            .lock()
+            .ok()
+            .and_then(|blocks| blocks.iter().position(|b| *b == block_index))
+            .map_or(block_index, |p| p as u32)
+    }
+
+    fn chunk(&self, model: &str, choices: &Value) -> Value {
+        json!({
+            "id": self.id,
+            "object": "chat.completion.chunk",
+            "created": self.created,
+            "model": model,
+            "choices": choices,
+        })
+    }
+
+    fn delta_chunk(&self, model: &str, delta: &Value) -> Value {
+        self.chunk(
+            model,
+            &json!([{ "index": 0, "delta": delta, "finish_reason": null }]),
+        )
+    }
+}
+
+fn current_unix_ts() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg_attr(
+    not(feature = "test-api"),
+    expect(
+        unreachable_pub,
+        reason = "items are re-exported via `test_api` only when the feature is on"
+    )
+)]
+pub fn render_completion_object(response: &CanonicalResponse) -> Value {
+    let mut text = String::new();
+    let mut tool_calls: Vec<Value> = Vec::new();
+    for part in &response.content {
+        match part {
+            CanonicalContent::Text(t) => text.push_str(t),
+            CanonicalContent::ToolUse {
+                id, name, input, ..
+            } => tool_calls.push(tool_call_value(id, name, input)),
+            CanonicalContent::Thinking { .. }
+            | CanonicalContent::Image(_)
+            | CanonicalContent::ToolResult { .. } => {},
+        }
+    }
+    let mut message = Map::new();
+    message.insert("role".into(), json!("assistant"));
+    message.insert(
+        "content".into(),
+        if text.is_empty() {
+            Value::Null
+        } else {
+            Value::String(text)
+        },
+    );
+    if !tool_calls.is_empty() {
+        message.insert("tool_calls".into(), Value::Array(tool_calls));
+    }
+    json!({
+        "id": response.id,
+        "object": "chat.completion",
+        "created": current_unix_ts(),
+        "model": response.model,
+        "choices": [{
+            "index": 0,
+            "message": Value::Object(message),
+            "logprobs": null,
+            "finish_reason": finish_reason(response.stop_reason),
+        }],
+        "usage": usage_value(&response.usage),
+    })
+}
+
+fn tool_call_value(id: &str, name: &str, input: &Value) -> Value {
+    json!({
+        "id": id,
+        "type": "function",
+        "function": {
+            "name": name,
+            "arguments": serde_json::to_string(input).unwrap_or_else(|_| "{}".into()),
+        },
+    })
+}
+
+fn finish_reason(stop_reason: Option<CanonicalStopReason>) -> &'static str {
+    stop_reason.map_or("stop", CanonicalStopReason::openai_str)
+}
+
+fn usage_value(usage: &CanonicalUsage) -> Value {
+    json!({
+        "prompt_tokens": usage.input_tokens,
+        "completion_tokens": usage.output_tokens,
+        "total_tokens": usage.input_tokens + usage.output_tokens,
+        "prompt_tokens_details": { "cached_tokens": usage.cache_read_tokens },
+    })
+}
+
+#[cfg_attr(
+    not(feature = "test-api"),
+    expect(
+        unreachable_pub,
+        reason = "items are re-exported via `test_api` only when the feature is on"
+    )
+)]
+pub fn render_chunk_frame(
+    event: &CanonicalEvent,
+    model: &str,
+    stream: &ChatStream,
+) -> Option<Bytes> {
+    let chunk = match event {
+        CanonicalEvent::MessageStart { model: m, .. } => stream.delta_chunk(
+            if m.is_empty() { model } else { m },
+            &json!({ "role": "assistant", "content": "" }),
+        ),
+        CanonicalEvent::ContentBlockStart {
+            index,
+            block: ContentBlockKind::ToolUse { id, name, .. },
+        } => stream.delta_chunk(
+            model,
+            &json!({ "tool_calls": [{
+                "index": stream.open_tool_call(*index),
+                "id": id,
+                "type": "function",
+                "function": { "name": name, "arguments": "" },
+            }] }),
+        ),
+        CanonicalEvent::TextDelta { text, .. } => {
+            stream.delta_chunk(model, &json!({ "content": text }))
+        },
+        CanonicalEvent::ToolUseDelta {
+            index,
+            partial_json,
+        } => stream.delta_chunk(
+            model,
+            &json!({ "tool_calls": [{
+                "index": stream.tool_call_index(*index),
+                "function": { "arguments": partial_json },
+            }] }),
+        ),
+        CanonicalEvent::Error(msg) => json!({
+            "error": { "type": "api_error", "message": msg },
+        }),
+        CanonicalEvent::ContentBlockStart { .. }
+        | CanonicalEvent::ThinkingDelta { .. }
+        | CanonicalEvent::SignatureDelta { .. }
+        | CanonicalEvent::EncryptedContentDelta { .. }
+        | CanonicalEvent::ContentBlockStop { .. }
+        | CanonicalEvent::UsageDelta(_)
+        | CanonicalEvent::MessageStop { .. } => return None,
+    };
+    let mut frame = String::new();
+    push_frame(&mut frame, &chunk);
+    Some(Bytes::from(frame))
+}
+
+#[cfg_attr(
+    not(feature = "test-api"),
+    expect(
+        unreachable_pub,
+        reason = "items are re-exported via `test_api` only when the feature is on"
+    )
+)]
+pub fn render_finish_frames(
+    stop_reason: Option<CanonicalStopReason>,
+    snapshot: &CanonicalResponse,
+    model: &str,
+    stream: &ChatStream,
+) -> Bytes {
+    let mut frames = String::new();
+    push_frame(
+        &mut frames,
+        &stream.chunk(
+            model,
+            &json!([{ "index": 0, "delta": {}, "finish_reason": finish_reason(stop_reason) }]),
+        ),
+    );
+    if stream.include_usage {
+        let mut chunk = stream.chunk(model, &json!([]));
+        if let Some(obj) = chunk.as_object_mut() {
+            obj.insert("usage".into(), usage_value(&snapshot.usage));
+        }
+        push_frame(&mut frames, &chunk);
+    }
+    frames.push_str(DONE_FRAME);
+    Bytes::from(frames)
+}
+
+fn push_frame(buf: &mut String, payload: &Value) {
+    buf.push_str("data: ");
+    buf.push_str(&serde_json::to_string(payload).unwrap_or_else(|_| "{}".into()));
+    buf.push_str("\n\n");
+}
@@ -0,0 +1,84 @@
+//! Inbound adapter for the `OpenAI` Embeddings wire protocol.
+//!
+//! [`OpenAiEmbeddingsInbound`] parses `/embeddings` request bodies into a
+//! canonical request — one user text message per input — so authentication,
+//! routing, quota, governance, and the safety scanners run unchanged. The
+//! adapter reports [`InboundAdapter::serves_embeddings`], which sends dispatch
+//! down the embeddings lane; that lane re-reads the body with
+//! [`parse_embeddings`] and renders the reply with [`render_embeddings`].
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use bytes::Bytes;
+use http::StatusCode;
+
+use super::super::canonical::{CanonicalContent, CanonicalMessage, CanonicalRequest, Role};
+use super::super::canonical_response::{CanonicalEvent, CanonicalResponse};
+use super::{InboundAdapter, InboundParseError};
+use crate::services::gateway::rate_limit::RateLimitStatus;
+
+mod parse;
+mod render;
+
+pub use parse::{EmbeddingEncoding, ParsedEmbeddings, parse_embeddings};
+pub use render::render_embeddings;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OpenAiEmbeddingsInbound;
+
+impl InboundAdapter for OpenAiEmbeddingsInbound {
+    fn wire_name(&self) -> &'static str {
+        "openai.embeddings"
+    }
+
+    fn serves_embeddings(&self) -> bool {
+        true
+    }
+
+    fn parse_request(&self, raw: &Bytes) -> Result<CanonicalRequest, InboundParseError> {
+        let parsed = parse_embeddings(raw)?;
+        let messages = parsed
+            .request
+            .inputs
+            .into_iter()
+            .map(|text| CanonicalMessage {
+                role: Role::User,
+                content: vec![CanonicalContent::Text(text)],
+            })
+            .collect();
+        Ok(CanonicalRequest {
+            model: parsed.request.model,
+            messages,
+            ..CanonicalRequest::default()
+        })
+    }
+
+    fn render_response(&self, response: &CanonicalResponse) -> Bytes {
+        let value = serde_json::json!({
+            "object": "list",
+            "data": [],
+            "model": response.model,
+        });
+        Bytes::from(serde_json::to_vec(&value).unwrap_or_else(|_| b"{}".to_vec()))
+    }
+
+    fn render_event(&self, _event: &CanonicalEvent, _model: &str) -> Option<Bytes> {
+        None
+    }
+
+    fn render_error(&self, _status: StatusCode, message: &str) -> Bytes {
+        let body = serde_json::json!({
+            "error": { "type": "api_error", "message": message },
+        });
+        Bytes::from(serde_json::to_vec(&body).unwrap_or_else(|_| b"{}".to_vec()))
+    }
+
+    fn rate_limit_headers(&self, status: &RateLimitStatus) -> Vec<(String, String)> {
+        status.openai_headers()
+    }
+
+    fn streaming_content_type(&self) -> &'static str {
+        "application/json"
+    }
+}
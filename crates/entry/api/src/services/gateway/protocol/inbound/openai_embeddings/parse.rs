@@ -0,0 +1,96 @@
+//! `OpenAI` Embeddings request parsing.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use bytes::Bytes;
+use serde_json::Value;
+use systemprompt_models::wire::embeddings::EmbeddingRequest;
+
+use super::super::InboundParseError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmbeddingEncoding {
+    #[default]
+    Float,
+    Base64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedEmbeddings {
+    pub request: EmbeddingRequest,
+    pub encoding: EmbeddingEncoding,
+}
+
+pub fn parse_embeddings(raw: &Bytes) -> Result<ParsedEmbeddings, InboundParseError> {
+    let value: Value =
+        serde_json::from_slice(raw).map_err(|e| InboundParseError::InvalidJson(e.to_string()))?;
+    let model = value
+        .get("model")
+        .and_then(Value::as_str)
+        .filter(|m| !m.is_empty())
+        .ok_or(InboundParseError::MissingField("model"))?
+        .to_owned();
+    let inputs = parse_inputs(value.get("input"))?;
+    let dimensions = match value.get("dimensions") {
+        None | Some(Value::Null) => None,
+        Some(v) => Some(
+            v.as_u64()
+                .and_then(|d| u32::try_from(d).ok())
+                .filter(|d| *d > 0)
+                .ok_or_else(|| InboundParseError::Unsupported {
+                    field: "dimensions",
+                    detail: v.to_string(),
+                })?,
+        ),
+    };
+    let encoding = match value.get("encoding_format").and_then(Value::as_str) {
+        None | Some("float") => EmbeddingEncoding::Float,
+        Some("base64") => EmbeddingEncoding::Base64,
+        Some(other) => {
+            return Err(InboundParseError::Unsupported {
+                field: "encoding_format",
+                detail: other.to_owned(),
+            });
+        },
+    };
+    Ok(ParsedEmbeddings {
+        request: EmbeddingRequest {
+            model,
+            inputs,
+            dimensions,
+        },
+        encoding,
+    })
+}
+
+// Why: token-id inputs (arrays of integers) are rejected rather than decoded —
+// the gateway cannot scan or govern text it never sees, and the ids are only
+// meaningful to one tokenizer.
+fn parse_inputs(input: Option<&Value>) -> Result<Vec<String>, InboundParseError> {
+    let inputs = match input {
+        None | Some(Value::Null) => return Err(InboundParseError::MissingField("input")),
+        Some(Value::String(text)) => vec![text.clone()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| {
+                item.as_str()
+                    .map(ToOwned::to_owned)
+                    .ok_or_else(|| InboundParseError::Unsupported {
+                        field: "input",
+                        detail: "only text inputs are supported".to_owned(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Some(_) => {
+            return Err(InboundParseError::Unsupported {
+                field: "input",
+                detail: "expected a string or an array of strings".to_owned(),
+            });
+        },
+    };
+    if inputs.is_empty() {
+        return Err(InboundParseError::MissingField("input"));
+    }
+    Ok(inputs)
+}
@@ -0,0 +1,51 @@
+//! `OpenAI` Embeddings response rendering.
+//!
+//! `base64` encoding packs each vector as little-endian `f32` bytes, matching
+//! what the `OpenAI` SDKs decode.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use bytes::Bytes;
+use serde_json::{Value, json};
+use systemprompt_models::wire::embeddings::EmbeddingResponse;
+
+use super::EmbeddingEncoding;
+
+#[must_use]
+pub fn render_embeddings(response: &EmbeddingResponse, encoding: EmbeddingEncoding) -> Bytes {
+    let data: Vec<Value> = response
+        .vectors
+        .iter()
+        .enumerate()
+        .map(|(index, vector)| {
+            json!({
+                "object": "embedding",
+                "index": index,
+                "embedding": encode_vector(vector, encoding),
+            })
+        })
+        .collect();
+    let body = json!({
+        "object": "list",
+        "data": data,
+        "model": response.model,
+        "usage": {
+            "prompt_tokens": response.input_tokens,
+            "total_tokens": response.input_tokens,
+        },
+    });
+    Bytes::from(serde_json::to_vec(&body).unwrap_or_else(|_| b"{}".to_vec()))
+}
+
+fn encode_vector(vector: &[f32], encoding: EmbeddingEncoding) -> Value {
+    match encoding {
+        EmbeddingEncoding::Float => json!(vector),
+        EmbeddingEncoding::Base64 => {
+            let bytes: Vec<u8> = vector.iter().flat_map(|f| f.to_le_bytes()).collect();
+            Value::String(STANDARD.encode(bytes))
+        },
+    }
+}
@@ -14,6 +14,7 @@ use serde_json::Value;
 use super::super::canonical::CanonicalRequest;
 use super::super::canonical_response::{CanonicalEvent, CanonicalResponse};
 use super::{InboundAdapter, InboundParseError};
+use crate::services::gateway::rate_limit::RateLimitStatus;
 
 mod input;
 mod parse;
@@ -64,4 +65,8 @@ impl InboundAdapter for OpenAiResponsesInbound {
         let body = format!("{{\"error\":{{\"type\":\"api_error\",\"message\":\"{escaped}\"}}}}");
         Bytes::from(body)
     }
+
+    fn rate_limit_headers(&self, status: &RateLimitStatus) -> Vec<(String, String)> {
+        status.openai_headers()
+    }
 }
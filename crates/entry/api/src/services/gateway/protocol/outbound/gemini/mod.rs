@@ -3,8 +3,8 @@
 //! [`GeminiOutbound`] renders the canonical model to a Gemini `generateContent`
 //! request via [`systemprompt_models::wire::gemini`], sends it upstream, and
 //! returns either a buffered [`CanonicalResponse`] or a stream of canonical
-//! events translated from the Gemini `?alt=sse` byte stream. Auth rides the
-//! `x-goog-api-key` header.
+//! events translated from the Gemini `?alt=sse` byte stream. Embeddings go to
+//! `batchEmbedContents`. Auth rides the `x-goog-api-key` header.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -12,6 +12,7 @@
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use serde_json::Value;
+use systemprompt_models::wire::embeddings::{self, EmbeddingRequest, EmbeddingResponse};
 use systemprompt_models::wire::gemini;
 
 use super::super::canonical_response::CanonicalResponse;
@@ -68,4 +69,29 @@ impl OutboundAdapter for GeminiOutbound {
         let canon: CanonicalResponse = gemini::parse_response(&value, ctx.request.model.as_str());
         Ok(OutboundOutcome::Buffered(Box::new(canon)))
     }
+
+    async fn embed(
+        &self,
+        ctx: OutboundCtx<'_>,
+        request: &EmbeddingRequest,
+    ) -> Result<EmbeddingResponse> {
+        let path = embeddings::gemini::upstream_path(ctx.upstream_model);
+        let url = format!("{}{path}", ctx.endpoint.trim_end_matches('/'));
+        let body = embeddings::gemini::build_request_body(request, ctx.upstream_model);
+
+        let mut req = super::http_client()
+            .post(&url)
+            .header(gemini::API_KEY_HEADER, ctx.api_key)
+            .json(&body);
+        for (name, value) in &ctx.route.extra_headers {
+            req = req.header(name.as_str(), value.as_str());
+        }
+        let upstream_response = super::send_checked(ctx.route.provider.as_str(), req).await?;
+        let value: Value = upstream_response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Gemini embeddings response not valid JSON: {e}"))?;
+        embeddings::gemini::parse_response(&value, request)
+            .map_err(|e| anyhow!("Gemini embeddings response: {e}"))
+    }
 }
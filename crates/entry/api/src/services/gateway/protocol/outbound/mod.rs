@@ -5,7 +5,8 @@
 //! of canonical events. Adapters register themselves via
 //! [`OutboundAdapterRegistration`] (collected by `inventory`) so the upstream
 //! registry can resolve one by provider tag. Implementations cover Anthropic,
-//! `OpenAI` Chat Completions, and `OpenAI` Responses.
+//! `OpenAI` Chat Completions, `OpenAI` Responses, and Gemini. Adapters whose
+//! provider serves embeddings also implement [`OutboundAdapter::embed`].
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -22,6 +23,7 @@ use async_trait::async_trait;
 use futures_util::stream::BoxStream;
 use systemprompt_models::profile::GatewayRoute;
 use systemprompt_models::services::ai::ModelLimits;
+use systemprompt_models::wire::embeddings::{EmbeddingRequest, EmbeddingResponse};
 use thiserror::Error;
 
 use super::canonical::CanonicalRequest;
@@ -73,6 +75,12 @@ impl UpstreamError {
     }
 }
 
+#[derive(Debug, Error)]
+#[error("provider {provider} does not serve embeddings")]
+pub struct EmbeddingsUnsupported {
+    pub provider: String,
+}
+
 // Why: one process-wide client — a client per request would open a fresh
 // connection pool and TLS handshake on every gateway call.
 pub(in crate::services::gateway) fn http_client() -> &'static reqwest::Client {
@@ -152,6 +160,18 @@ pub trait OutboundAdapter: Send + Sync {
     fn build_body(&self, ctx: &OutboundCtx<'_>) -> Result<PreparedBody>;
 
     async fn send(&self, ctx: OutboundCtx<'_>, body: &PreparedBody) -> Result<OutboundOutcome>;
+
+    async fn embed(
+        &self,
+        ctx: OutboundCtx<'_>,
+        request: &EmbeddingRequest,
+    ) -> Result<EmbeddingResponse> {
+        // Why: unused-arg suppression in a default trait method body.
+        let _ = request;
+        Err(anyhow::Error::new(EmbeddingsUnsupported {
+            provider: ctx.route.provider.to_string(),
+        }))
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
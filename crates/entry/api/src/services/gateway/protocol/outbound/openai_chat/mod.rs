@@ -4,7 +4,8 @@
 //! handling, stream-vs-buffered dispatch — and delegates every wire concern
 //! (request build, response parse, SSE-to-event mapping) to the shared
 //! [`systemprompt_models::wire::openai_chat`] codec. Also serves
-//! OpenAI-compatible providers exposing the same surface.
+//! OpenAI-compatible providers exposing the same surface, including their
+//! `/embeddings` endpoint via [`embed_openai`].
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -12,6 +13,7 @@
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use serde_json::Value;
+use systemprompt_models::wire::embeddings::{self, EmbeddingRequest, EmbeddingResponse};
 use systemprompt_models::wire::openai_chat as codec;
 
 use super::{OutboundAdapter, OutboundCtx, OutboundOutcome, PreparedBody};
@@ -70,4 +72,39 @@ impl OutboundAdapter for OpenAiChatOutbound {
         let canon = codec::parse_response(&value, &ctx.request.model);
         Ok(OutboundOutcome::Buffered(Box::new(canon)))
     }
+
+    async fn embed(
+        &self,
+        ctx: OutboundCtx<'_>,
+        request: &EmbeddingRequest,
+    ) -> Result<EmbeddingResponse> {
+        embed_openai(&ctx, request).await
+    }
+}
+
+pub(in crate::services::gateway) async fn embed_openai(
+    ctx: &OutboundCtx<'_>,
+    request: &EmbeddingRequest,
+) -> Result<EmbeddingResponse> {
+    let url = format!(
+        "{}{}",
+        ctx.endpoint.trim_end_matches('/'),
+        embeddings::openai::UPSTREAM_PATH
+    );
+    let body = embeddings::openai::build_request_body(request, ctx.upstream_model);
+
+    let mut req = super::http_client()
+        .post(&url)
+        .header("authorization", format!("Bearer {}", ctx.api_key))
+        .json(&body);
+    for (name, value) in &ctx.route.extra_headers {
+        req = req.header(name.as_str(), value.as_str());
+    }
+    let upstream_response = super::send_checked(ctx.route.provider.as_str(), req).await?;
+    let value: Value = upstream_response
+        .json()
+        .await
+        .map_err(|e| anyhow!("OpenAI embeddings response not valid JSON: {e}"))?;
+    embeddings::openai::parse_response(&value, request)
+        .map_err(|e| anyhow!("OpenAI embeddings response: {e}"))
 }
@@ -3,7 +3,8 @@
 //! [`OpenAiResponsesOutbound`] orchestrates transport — auth headers, HTTP
 //! status handling, stream-vs-buffered dispatch — and delegates every wire
 //! concern (request build, response parse, SSE-to-event mapping) to the shared
-//! [`systemprompt_models::wire::openai_responses`] codec.
+//! [`systemprompt_models::wire::openai_responses`] codec. Embeddings use the
+//! same `/embeddings` surface as the Chat Completions adapter.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -11,6 +12,7 @@
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use serde_json::Value;
+use systemprompt_models::wire::embeddings::{EmbeddingRequest, EmbeddingResponse};
 use systemprompt_models::wire::openai_responses as codec;
 
 use super::{OutboundAdapter, OutboundCtx, OutboundOutcome, PreparedBody};
@@ -69,4 +71,12 @@ impl OutboundAdapter for OpenAiResponsesOutbound {
         let canon = codec::parse_response_object(&value, &ctx.request.model);
         Ok(OutboundOutcome::Buffered(Box::new(canon)))
     }
+
+    async fn embed(
+        &self,
+        ctx: OutboundCtx<'_>,
+        request: &EmbeddingRequest,
+    ) -> Result<EmbeddingResponse> {
+        super::openai_chat::embed_openai(&ctx, request).await
+    }
 }
@@ -3,7 +3,8 @@
 //! Windows are keyed by a subject: the requesting user by default, or any
 //! subject-attribute dimension an extension registers (for example
 //! `organization`). Cost ceilings are enforced one request late — cost is
-//! known only after the response.
+//! known only after the response — so windows may also set alert thresholds
+//! that warn, via [`super::budget_alert`], before the ceiling is reached.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -23,7 +24,8 @@ use systemprompt_security::authz::{
     AuthzHookContext, NullAuditSink, SharedSubjectAttributeProvider, discover_subject_providers,
 };
 
-use super::policy::QuotaWindow;
+use super::budget_alert::{self, BucketSpend};
+use super::policy::{BudgetAlertTarget, QuotaWindow, RateLimit};
 
 #[derive(Debug, Clone)]
 pub struct QuotaDecision {
@@ -33,9 +35,9 @@ pub struct QuotaDecision {
     pub state: QuotaBucketState,
 }
 
-struct WindowSubject<'a> {
-    kind: &'a str,
-    id: String,
+pub(super) struct WindowSubject<'a> {
+    pub(super) kind: &'a str,
+    pub(super) id: String,
 }
 
 fn subject_providers(pool: &Arc<PgPool>) -> &'static [SharedSubjectAttributeProvider] {
@@ -48,12 +50,12 @@ fn subject_providers(pool: &Arc<PgPool>) -> &'static [SharedSubjectAttributeProv
     })
 }
 
-async fn resolve_subject<'a>(
-    window: &'a QuotaWindow,
+pub(super) async fn resolve_subject<'a>(
+    subject: &'a str,
     user_id: &UserId,
     pool: &Arc<PgPool>,
 ) -> Option<WindowSubject<'a>> {
-    if window.subject == USER_QUOTA_SUBJECT {
+    if subject == USER_QUOTA_SUBJECT {
         return Some(WindowSubject {
             kind: USER_QUOTA_SUBJECT,
             id: user_id.as_str().to_owned(),
@@ -61,12 +63,9 @@ async fn resolve_subject<'a>(
     }
     let provider = subject_providers(pool)
         .iter()
-        .find(|p| p.dimension().rule_type.as_str() == window.subject)?;
+        .find(|p| p.dimension().rule_type.as_str() == subject)?;
     let id = provider.values_for(user_id).await.into_iter().next()?;
-    Some(WindowSubject {
-        kind: &window.subject,
-        id,
-    })
+    Some(WindowSubject { kind: subject, id })
 }
 
 pub async fn precheck_and_reserve(
@@ -84,7 +83,7 @@ pub async fn precheck_and_reserve(
 
     let now = Utc::now();
     for window in windows {
-        let Some(subject) = resolve_subject(window, user_id, &pool).await else {
+        let Some(subject) = resolve_subject(&window.subject, user_id, &pool).await else {
             continue;
         };
         let window_start = align_window(now, window.window_seconds);
@@ -138,6 +137,8 @@ pub async fn precheck_and_reserve(
 pub struct PostUpdateParams<'a> {
     pub user_id: &'a UserId,
     pub windows: &'a [QuotaWindow],
+    pub rate_limits: &'a [RateLimit],
+    pub alerts: &'a [BudgetAlertTarget],
     pub input_tokens: u32,
     pub output_tokens: u32,
     pub cost_microdollars: i64,
@@ -148,7 +149,7 @@ pub async fn post_update_tokens(
     repo: &AiQuotaBucketRepository,
     params: PostUpdateParams<'_>,
 ) {
-    if params.windows.is_empty() {
+    if params.windows.is_empty() && params.rate_limits.is_empty() {
         return;
     }
     let pool = match db.pool_arc() {
@@ -158,13 +159,20 @@ pub async fn post_update_tokens(
             return;
         },
     };
+    super::rate_limit::debit_tokens(
+        &pool,
+        params.user_id,
+        params.rate_limits,
+        params.input_tokens.saturating_add(params.output_tokens),
+    )
+    .await;
     let now = Utc::now();
     for window in params.windows {
-        let Some(subject) = resolve_subject(window, params.user_id, &pool).await else {
+        let Some(subject) = resolve_subject(&window.subject, params.user_id, &pool).await else {
             continue;
         };
         let window_start = align_window(now, window.window_seconds);
-        if let Err(e) = repo
+        let incremented = repo
             .increment(IncrementParams {
                 subject_kind: subject.kind,
                 subject_id: &subject.id,
@@ -177,14 +185,31 @@ pub async fn post_update_tokens(
                     cost_microdollars: params.cost_microdollars,
                 },
             })
-            .await
-        {
-            tracing::warn!(error = %e, window_seconds = window.window_seconds, "quota post_update failed");
+            .await;
+        match incremented {
+            Ok(state) => budget_alert::spawn_notify(
+                params.user_id,
+                budget_alert::crossings(
+                    window,
+                    BucketSpend {
+                        subject_kind: subject.kind,
+                        subject_id: &subject.id,
+                        window_start,
+                        spent_before: state.cost_microdollars - params.cost_microdollars,
+                        spent_after: state.cost_microdollars,
+                    },
+                ),
+                params.alerts,
+            ),
+            Err(e) => {
+                tracing::warn!(error = %e, window_seconds = window.window_seconds, "quota post_update failed");
+            },
         }
     }
 }
 
-fn align_window(now: DateTime<Utc>, window_seconds: i32) -> DateTime<Utc> {
+#[must_use]
+pub fn align_window(now: DateTime<Utc>, window_seconds: i32) -> DateTime<Utc> {
     let secs = now.timestamp();
     let w = i64::from(window_seconds.max(1));
     let aligned = (secs / w) * w;
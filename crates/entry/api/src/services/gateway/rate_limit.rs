@@ -0,0 +1,457 @@
+//! Concurrency and per-minute rate limiting for gateway policies.
+//!
+//! Each [`RateLimit`] a policy declares is keyed by the same subjects as its
+//! quota windows. `max_concurrent` counts requests in flight: admission hands
+//! back a [`RateLimitPermit`] whose slot is released when the response body is
+//! dropped, so a stream holds its slot until the caller stops reading. The
+//! per-minute limits are token buckets refilled continuously; tokens are
+//! debited when the response completes and may leave a bucket in debt.
+//!
+//! State is process-local, like the governance rate limiter: each replica
+//! enforces the limits on the traffic it serves.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use axum::body::Body;
+use axum::response::Response;
+use chrono::Utc;
+use futures_util::StreamExt;
+use sqlx::PgPool;
+use systemprompt_database::DbPool;
+use systemprompt_identifiers::UserId;
+
+use super::policy::RateLimit;
+use super::quota::resolve_subject;
+
+// Why: bucket levels are kept in limit-units × milliseconds, so a bucket that
+// refills `limit` per minute gains exactly `limit` units per millisecond and
+// one request or token costs a whole minute's worth — integer math throughout.
+const UNITS_PER_ITEM: i64 = 60_000;
+
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// One limit's standing, as reported in provider-style response headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitStatus {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset: Duration,
+}
+
+/// The request and token buckets of the limit that refused a request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    pub requests: Option<LimitStatus>,
+    pub tokens: Option<LimitStatus>,
+}
+
+impl RateLimitStatus {
+    /// `anthropic-ratelimit-*` headers; resets are RFC 3339 timestamps.
+    pub fn anthropic_headers(&self) -> Vec<(String, String)> {
+        let now = Utc::now();
+        let mut headers = Vec::new();
+        for (name, status) in [("requests", self.requests), ("tokens", self.tokens)] {
+            let Some(status) = status else {
+                continue;
+            };
+            let reset = chrono::Duration::from_std(status.reset)
+                .map_or(now, |d| now + d)
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+            headers.extend([
+                (
+                    format!("anthropic-ratelimit-{name}-limit"),
+                    status.limit.to_string(),
+                ),
+                (
+                    format!("anthropic-ratelimit-{name}-remaining"),
+                    status.remaining.to_string(),
+                ),
+                (format!("anthropic-ratelimit-{name}-reset"), reset),
+            ]);
+        }
+        headers
+    }
+
+    /// `x-ratelimit-*` headers; resets are durations such as `1m30s`.
+    pub fn openai_headers(&self) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+        for (name, status) in [("requests", self.requests), ("tokens", self.tokens)] {
+            let Some(status) = status else {
+                continue;
+            };
+            headers.extend([
+                (
+                    format!("x-ratelimit-limit-{name}"),
+                    status.limit.to_string(),
+                ),
+                (
+                    format!("x-ratelimit-remaining-{name}"),
+                    status.remaining.to_string(),
+                ),
+                (
+                    format!("x-ratelimit-reset-{name}"),
+                    format_reset(status.reset),
+                ),
+            ]);
+        }
+        headers
+    }
+}
+
+fn format_reset(reset: Duration) -> String {
+    let millis = reset.as_millis();
+    if millis < 1000 {
+        return format!("{millis}ms");
+    }
+    let secs = reset.as_secs() + u64::from(reset.subsec_millis() > 0);
+    if secs < 60 {
+        return format!("{secs}s");
+    }
+    format!("{}m{}s", secs / 60, secs % 60)
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct RateLimited {
+    pub message: String,
+    pub retry_after_seconds: u32,
+    pub status: RateLimitStatus,
+}
+
+/// A resolved subject paired with the limit that applies to it. `index` is
+/// the limit's position in the policy, so two limits on the same subject keep
+/// separate state.
+#[derive(Debug, Clone, Copy)]
+pub struct LimitSubject<'a> {
+    pub index: usize,
+    pub kind: &'a str,
+    pub id: &'a str,
+    pub limit: &'a RateLimit,
+}
+
+impl LimitSubject<'_> {
+    fn key(&self) -> String {
+        format!("{}:{}:{}", self.index, self.kind, self.id)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    limit: u32,
+    level: i64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(limit: u32, now: Instant) -> Self {
+        Self {
+            limit,
+            level: Self::capacity(limit),
+            updated: now,
+        }
+    }
+
+    fn capacity(limit: u32) -> i64 {
+        i64::from(limit) * UNITS_PER_ITEM
+    }
+
+    fn refill(&mut self, limit: u32, now: Instant) {
+        if limit != self.limit {
+            *self = Self::new(limit, now);
+            return;
+        }
+        let elapsed = now.saturating_duration_since(self.updated).as_millis();
+        let gained = i64::try_from(elapsed)
+            .unwrap_or(i64::MAX)
+            .saturating_mul(i64::from(limit));
+        self.level = self.level.saturating_add(gained).min(Self::capacity(limit));
+        self.updated = now;
+    }
+
+    fn wait_for(&self, units: i64) -> Duration {
+        let deficit = (units - self.level).max(0);
+        let limit = i64::from(self.limit.max(1));
+        Duration::from_millis(((deficit + limit - 1) / limit) as u64)
+    }
+
+    fn status(&self) -> LimitStatus {
+        LimitStatus {
+            limit: self.limit,
+            remaining: (self.level.max(0) / UNITS_PER_ITEM) as u32,
+            reset: self.wait_for(Self::capacity(self.limit)),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SubjectState {
+    in_flight: u32,
+    requests: Option<Bucket>,
+    tokens: Option<Bucket>,
+}
+
+impl SubjectState {
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        self.requests = refilled(self.requests, limit.requests_per_minute, now);
+        self.tokens = refilled(self.tokens, limit.tokens_per_minute, now);
+    }
+
+    fn status(&self) -> RateLimitStatus {
+        RateLimitStatus {
+            requests: self.requests.as_ref().map(Bucket::status),
+            tokens: self.tokens.as_ref().map(Bucket::status),
+        }
+    }
+
+    fn refusal(&self, subject: &LimitSubject<'_>) -> Option<RateLimited> {
+        let (message, wait) = if let Some(max) = subject.limit.max_concurrent
+            && self.in_flight >= max
+        {
+            (
+                format!(
+                    "concurrency limit reached for {} ({}/{max} requests in flight)",
+                    subject.kind, self.in_flight
+                ),
+                Duration::from_secs(1),
+            )
+        } else if let Some(bucket) = self.requests
+            && bucket.level < UNITS_PER_ITEM
+        {
+            (
+                format!(
+                    "rate limit exceeded for {}: {} requests per minute",
+                    subject.kind, bucket.limit
+                ),
+                bucket.wait_for(UNITS_PER_ITEM),
+            )
+        } else if let Some(bucket) = self.tokens
+            && bucket.level <= 0
+        {
+            (
+                format!(
+                    "rate limit exceeded for {}: {} tokens per minute",
+                    subject.kind, bucket.limit
+                ),
+                bucket.wait_for(UNITS_PER_ITEM),
+            )
+        } else {
+            return None;
+        };
+        Some(RateLimited {
+            message,
+            retry_after_seconds: wait.as_secs_f64().ceil().max(1.0) as u32,
+            status: self.status(),
+        })
+    }
+
+    fn is_idle(&self) -> bool {
+        let full =
+            |bucket: Option<Bucket>| bucket.is_none_or(|b| b.level >= Bucket::capacity(b.limit));
+        self.in_flight == 0 && full(self.requests) && full(self.tokens)
+    }
+}
+
+fn refilled(bucket: Option<Bucket>, limit: Option<u32>, now: Instant) -> Option<Bucket> {
+    let limit = limit?;
+    let mut bucket = bucket.unwrap_or_else(|| Bucket::new(limit, now));
+    bucket.refill(limit, now);
+    Some(bucket)
+}
+
+/// In-flight counts and per-minute buckets for every subject a policy
+/// limits. Clones share state.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    state: Arc<Mutex<HashMap<String, SubjectState>>>,
+}
+
+impl RateLimiter {
+    pub fn global() -> &'static Self {
+        static LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+        LIMITER.get_or_init(Self::default)
+    }
+
+    /// Admits a request against every subject, or refuses it without
+    /// charging any of them.
+    pub fn try_admit(
+        &self,
+        subjects: &[LimitSubject<'_>],
+        now: Instant,
+    ) -> Result<RateLimitPermit, RateLimited> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.len() > PRUNE_THRESHOLD {
+            state.retain(|_, s| !s.is_idle());
+        }
+        for subject in subjects {
+            let entry = state.entry(subject.key()).or_default();
+            entry.refill(subject.limit, now);
+            if let Some(refused) = entry.refusal(subject) {
+                return Err(refused);
+            }
+        }
+        let mut held = Vec::new();
+        for subject in subjects {
+            let key = subject.key();
+            let entry = state.entry(key.clone()).or_default();
+            if let Some(bucket) = entry.requests.as_mut() {
+                bucket.level -= UNITS_PER_ITEM;
+            }
+            if subject.limit.max_concurrent.is_some() {
+                entry.in_flight += 1;
+                held.push(key);
+            }
+        }
+        drop(state);
+        Ok(RateLimitPermit {
+            limiter: self.clone(),
+            keys: held,
+        })
+    }
+
+    /// Charges a completed response's tokens to every subject with a
+    /// tokens-per-minute limit.
+    pub fn debit_tokens(&self, subjects: &[LimitSubject<'_>], tokens: u32, now: Instant) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        for subject in subjects {
+            let Some(per_minute) = subject.limit.tokens_per_minute else {
+                continue;
+            };
+            let entry = state.entry(subject.key()).or_default();
+            let bucket = entry
+                .tokens
+                .get_or_insert_with(|| Bucket::new(per_minute, now));
+            bucket.refill(per_minute, now);
+            bucket.level = bucket
+                .level
+                .saturating_sub(i64::from(tokens) * UNITS_PER_ITEM);
+        }
+        drop(state);
+    }
+
+    fn release(&self, keys: &[String]) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        for key in keys {
+            if let Some(entry) = state.get_mut(key) {
+                entry.in_flight = entry.in_flight.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// The concurrency slots an admitted request holds; dropping it frees them.
+#[derive(Debug)]
+pub struct RateLimitPermit {
+    limiter: RateLimiter,
+    keys: Vec<String>,
+}
+
+impl RateLimitPermit {
+    /// A permit that holds no slot — for requests no policy limits.
+    pub fn unlimited() -> Self {
+        Self {
+            limiter: RateLimiter::default(),
+            keys: Vec::new(),
+        }
+    }
+
+    /// Ties the permit to `response`'s body, so a streamed reply keeps its
+    /// slot until the caller has read it or gone away.
+    pub fn hold(self, response: Response<Body>) -> Response<Body> {
+        if self.keys.is_empty() {
+            return response;
+        }
+        let (parts, body) = response.into_parts();
+        let stream = body.into_data_stream().map(move |chunk| {
+            let _held = &self;
+            chunk
+        });
+        Response::from_parts(parts, Body::from_stream(stream))
+    }
+}
+
+impl Drop for RateLimitPermit {
+    fn drop(&mut self) {
+        if !self.keys.is_empty() {
+            self.limiter.release(&self.keys);
+        }
+    }
+}
+
+struct ResolvedSubject<'a> {
+    index: usize,
+    kind: &'a str,
+    id: String,
+    limit: &'a RateLimit,
+}
+
+async fn resolve_subjects<'a>(
+    limits: &'a [RateLimit],
+    user_id: &UserId,
+    pool: &Arc<PgPool>,
+) -> Vec<ResolvedSubject<'a>> {
+    let mut resolved = Vec::with_capacity(limits.len());
+    for (index, limit) in limits.iter().enumerate() {
+        if let Some(subject) = resolve_subject(&limit.subject, user_id, pool).await {
+            resolved.push(ResolvedSubject {
+                index,
+                kind: subject.kind,
+                id: subject.id,
+                limit,
+            });
+        }
+    }
+    resolved
+}
+
+fn borrowed<'a>(resolved: &'a [ResolvedSubject<'a>]) -> Vec<LimitSubject<'a>> {
+    resolved
+        .iter()
+        .map(|s| LimitSubject {
+            index: s.index,
+            kind: s.kind,
+            id: &s.id,
+            limit: s.limit,
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub enum Admission {
+    Granted(RateLimitPermit),
+    Refused(RateLimited),
+}
+
+pub async fn admit(db: &DbPool, user_id: &UserId, limits: &[RateLimit]) -> Result<Admission> {
+    if limits.is_empty() {
+        return Ok(Admission::Granted(RateLimitPermit::unlimited()));
+    }
+    let pool = db
+        .pool_arc()
+        .map_err(|e| anyhow::anyhow!("rate limit pool init: {e}"))?;
+    let resolved = resolve_subjects(limits, user_id, &pool).await;
+    Ok(
+        match RateLimiter::global().try_admit(&borrowed(&resolved), Instant::now()) {
+            Ok(permit) => Admission::Granted(permit),
+            Err(refused) => Admission::Refused(refused),
+        },
+    )
+}
+
+pub(super) async fn debit_tokens(
+    pool: &Arc<PgPool>,
+    user_id: &UserId,
+    limits: &[RateLimit],
+    tokens: u32,
+) {
+    if tokens == 0 || !limits.iter().any(|l| l.tokens_per_minute.is_some()) {
+        return;
+    }
+    let resolved = resolve_subjects(limits, user_id, pool).await;
+    RateLimiter::global().debit_tokens(&borrowed(&resolved), tokens, Instant::now());
+}
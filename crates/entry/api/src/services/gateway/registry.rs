@@ -101,16 +101,16 @@ impl SafetyScannerRegistry {
         self.entries.keys().map(String::as_str).collect()
     }
 
-    // Why: the builtin `heuristic` scanner is NOT registered here — it is
-    // constructed per policy from `SafetyConfig.heuristic` at scan time, and
-    // only when no extension registration shadows the name.
+    // Why: the builtin `heuristic` and `pattern` scanners are NOT registered
+    // here — they are constructed per policy from `SafetyConfig` at scan time,
+    // and only when no extension registration shadows the name.
     pub(super) fn build() -> Self {
         let mut entries: HashMap<String, Arc<dyn SafetyScanner>> = HashMap::new();
         entries.insert("null".to_owned(), Arc::new(NullScanner));
 
         for registration in inventory::iter::<SafetyScannerRegistration> {
             let name = registration.name.to_owned();
-            if entries.contains_key(&name) || name == "heuristic" {
+            if entries.contains_key(&name) || matches!(name.as_str(), "heuristic" | "pattern") {
                 tracing::warn!(
                     name = %registration.name,
                     "Extension-registered safety scanner shadows a built-in"
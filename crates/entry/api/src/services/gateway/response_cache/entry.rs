@@ -0,0 +1,270 @@
+//! Stored form of a cached response.
+//!
+//! The canonical response model carries no serde derives — it is an internal
+//! vocabulary, not a wire format — so the cache persists this mirror instead.
+//! Storing the canonical form rather than any one client's rendering is what
+//! lets an entry written by an Anthropic Messages caller replay to an `OpenAI`
+//! Chat caller, and a buffered entry replay as a stream.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use serde::{Deserialize, Serialize};
+// JSON: `input` on a tool call is arbitrary model-produced JSON.
+use serde_json::Value;
+
+use super::super::protocol::canonical::CanonicalContent;
+use super::super::protocol::canonical_response::{
+    CanonicalResponse, CanonicalStopReason, CanonicalUsage, CodeExecutionOutput, GroundedSource,
+    Grounding,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StoredResponse {
+    id: String,
+    model: String,
+    content: Vec<StoredBlock>,
+    stop_reason: Option<StoredStopReason>,
+    raw_finish_reason: Option<String>,
+    usage: StoredUsage,
+    #[serde(default)]
+    grounding: Option<StoredGrounding>,
+    #[serde(default)]
+    code_execution: Option<StoredCodeExecution>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StoredBlock {
+    Text {
+        text: String,
+    },
+    Thinking {
+        text: String,
+        signature: Option<String>,
+        id: Option<String>,
+        encrypted_content: Option<String>,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: Value,
+        signature: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StoredStopReason {
+    EndTurn,
+    MaxTokens,
+    StopSequence,
+    ToolUse,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[expect(
+    clippy::struct_field_names,
+    reason = "mirrors `CanonicalUsage`, whose fields are all token counts"
+)]
+struct StoredUsage {
+    input_tokens: u32,
+    output_tokens: u32,
+    cache_read_tokens: u32,
+    cache_creation_tokens: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredGrounding {
+    sources: Vec<StoredSource>,
+    queries: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredSource {
+    uri: String,
+    title: Option<String>,
+    snippet: Option<String>,
+    relevance: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredCodeExecution {
+    language: Option<String>,
+    code: String,
+    result: Option<String>,
+    outcome: Option<String>,
+}
+
+impl StoredResponse {
+    /// `None` when the response holds content the cache cannot reproduce
+    /// faithfully (tool results or images, which a model does not emit).
+    #[must_use]
+    pub fn from_canonical(response: &CanonicalResponse) -> Option<Self> {
+        let content = response
+            .content
+            .iter()
+            .map(StoredBlock::from_canonical)
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            id: response.id.clone(),
+            model: response.model.clone(),
+            content,
+            stop_reason: response.stop_reason.map(StoredStopReason::from_canonical),
+            raw_finish_reason: response.raw_finish_reason.clone(),
+            usage: StoredUsage {
+                input_tokens: response.usage.input_tokens,
+                output_tokens: response.usage.output_tokens,
+                cache_read_tokens: response.usage.cache_read_tokens,
+                cache_creation_tokens: response.usage.cache_creation_tokens,
+            },
+            grounding: response.grounding.as_ref().map(|g| StoredGrounding {
+                sources: g
+                    .sources
+                    .iter()
+                    .map(|s| StoredSource {
+                        uri: s.uri.clone(),
+                        title: s.title.clone(),
+                        snippet: s.snippet.clone(),
+                        relevance: s.relevance,
+                    })
+                    .collect(),
+                queries: g.queries.clone(),
+            }),
+            code_execution: response
+                .code_execution
+                .as_ref()
+                .map(|c| StoredCodeExecution {
+                    language: c.language.clone(),
+                    code: c.code.clone(),
+                    result: c.result.clone(),
+                    outcome: c.outcome.clone(),
+                }),
+        })
+    }
+
+    #[must_use]
+    pub fn into_canonical(self) -> CanonicalResponse {
+        let usage = CanonicalUsage {
+            input_tokens: self.usage.input_tokens,
+            output_tokens: self.usage.output_tokens,
+            cache_read_tokens: self.usage.cache_read_tokens,
+            cache_creation_tokens: self.usage.cache_creation_tokens,
+            total_tokens: self.usage.input_tokens + self.usage.output_tokens,
+        };
+        CanonicalResponse {
+            id: self.id,
+            model: self.model,
+            content: self
+                .content
+                .into_iter()
+                .map(StoredBlock::into_canonical)
+                .collect(),
+            stop_reason: self.stop_reason.map(StoredStopReason::into_canonical),
+            usage,
+            grounding: self.grounding.map(|g| Grounding {
+                sources: g
+                    .sources
+                    .into_iter()
+                    .map(|s| GroundedSource {
+                        uri: s.uri,
+                        title: s.title,
+                        snippet: s.snippet,
+                        relevance: s.relevance,
+                    })
+                    .collect(),
+                queries: g.queries,
+            }),
+            code_execution: self.code_execution.map(|c| CodeExecutionOutput {
+                language: c.language,
+                code: c.code,
+                result: c.result,
+                outcome: c.outcome,
+            }),
+            raw_finish_reason: self.raw_finish_reason,
+            ..CanonicalResponse::default()
+        }
+    }
+}
+
+impl StoredBlock {
+    fn from_canonical(part: &CanonicalContent) -> Option<Self> {
+        match part {
+            CanonicalContent::Text(text) => Some(Self::Text { text: text.clone() }),
+            CanonicalContent::Thinking {
+                text,
+                signature,
+                id,
+                encrypted_content,
+            } => Some(Self::Thinking {
+                text: text.clone(),
+                signature: signature.clone(),
+                id: id.clone(),
+                encrypted_content: encrypted_content.clone(),
+            }),
+            CanonicalContent::ToolUse {
+                id,
+                name,
+                input,
+                signature,
+            } => Some(Self::ToolUse {
+                id: id.clone(),
+                name: name.clone(),
+                input: input.clone(),
+                signature: signature.clone(),
+            }),
+            CanonicalContent::Image(_) | CanonicalContent::ToolResult { .. } => None,
+        }
+    }
+
+    fn into_canonical(self) -> CanonicalContent {
+        match self {
+            Self::Text { text } => CanonicalContent::Text(text),
+            Self::Thinking {
+                text,
+                signature,
+                id,
+                encrypted_content,
+            } => CanonicalContent::Thinking {
+                text,
+                signature,
+                id,
+                encrypted_content,
+            },
+            Self::ToolUse {
+                id,
+                name,
+                input,
+                signature,
+            } => CanonicalContent::ToolUse {
+                id,
+                name,
+                input,
+                signature,
+            },
+        }
+    }
+}
+
+impl StoredStopReason {
+    const fn from_canonical(reason: CanonicalStopReason) -> Self {
+        match reason {
+            CanonicalStopReason::EndTurn => Self::EndTurn,
+            CanonicalStopReason::MaxTokens => Self::MaxTokens,
+            CanonicalStopReason::StopSequence => Self::StopSequence,
+            CanonicalStopReason::ToolUse => Self::ToolUse,
+            CanonicalStopReason::Other => Self::Other,
+        }
+    }
+
+    const fn into_canonical(self) -> CanonicalStopReason {
+        match self {
+            Self::EndTurn => CanonicalStopReason::EndTurn,
+            Self::MaxTokens => CanonicalStopReason::MaxTokens,
+            Self::StopSequence => CanonicalStopReason::StopSequence,
+            Self::ToolUse => CanonicalStopReason::ToolUse,
+            Self::Other => CanonicalStopReason::Other,
+        }
+    }
+}
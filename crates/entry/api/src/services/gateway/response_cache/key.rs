@@ -0,0 +1,160 @@
+//! Cache eligibility and the normalized request key.
+//!
+//! The key is a SHA-256 over a JSON projection of the canonical request with
+//! object keys sorted at every depth, so two clients that serialise the same
+//! tool schema in a different key order share an entry. Fields that do not
+//! change what the upstream generates — `stream`, `metadata`, and the derived
+//! forwarded surface — are left out, which is what lets a streaming request
+//! replay an entry a buffered one stored.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+// JSON: the key projection is hashed, never sent — JSON gives a stable,
+// self-delimiting encoding of nested tool schemas for free.
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+use systemprompt_ai::ResponseCacheScope;
+
+use super::super::protocol::canonical::{
+    CanonicalContent, CanonicalRequest, CanonicalToolChoice, ImageSource, ReasoningEffort,
+    ResponseFormat,
+};
+
+/// What, besides the request itself, partitions the key space.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyPartition<'a> {
+    pub scope: ResponseCacheScope,
+    pub user_id: &'a str,
+    pub provider: &'a str,
+    pub upstream_model: &'a str,
+}
+
+/// Only requests whose output is a function of their input are cached.
+///
+/// That means `temperature: 0`, no hosted search or code execution (their
+/// results come from outside the prompt), no extended thinking, and no image
+/// fetched by URL (the bytes behind it can change).
+#[must_use]
+pub fn is_cacheable(request: &CanonicalRequest) -> bool {
+    request.temperature.is_some_and(|t| t == 0.0)
+        && !request.messages.is_empty()
+        && request.search.is_none()
+        && !request.code_execution
+        && !request.thinking.is_some_and(|t| t.enabled)
+        && !request
+            .messages
+            .iter()
+            .flat_map(|m| &m.content)
+            .any(references_remote_image)
+}
+
+fn references_remote_image(part: &CanonicalContent) -> bool {
+    match part {
+        CanonicalContent::Image(ImageSource::Url { .. }) => true,
+        CanonicalContent::ToolResult { content, .. } => content.iter().any(references_remote_image),
+        _ => false,
+    }
+}
+
+#[must_use]
+pub fn cache_key(request: &CanonicalRequest, partition: KeyPartition<'_>) -> String {
+    let subject = match partition.scope {
+        ResponseCacheScope::User => partition.user_id,
+        ResponseCacheScope::Tenant => "",
+    };
+    let projection = json!({
+        "scope": partition.scope.as_str(),
+        "subject": subject,
+        "provider": partition.provider,
+        "upstream_model": partition.upstream_model,
+        "model": request.model,
+        "system": request.system,
+        "messages": request.messages.iter().map(|m| json!({
+            "role": m.role.as_str(),
+            "content": m.content.iter().map(content_projection).collect::<Vec<_>>(),
+        })).collect::<Vec<_>>(),
+        "tools": request.tools.iter().map(|t| json!({
+            "name": t.name,
+            "description": t.description,
+            "input_schema": sorted(&t.input_schema),
+        })).collect::<Vec<_>>(),
+        "tool_choice": request.tool_choice.as_ref().map(tool_choice_projection),
+        "max_tokens": request.max_tokens,
+        "temperature": request.temperature,
+        "top_p": request.top_p,
+        "top_k": request.top_k,
+        "stop_sequences": request.stop_sequences,
+        "presence_penalty": request.presence_penalty,
+        "frequency_penalty": request.frequency_penalty,
+        "reasoning_effort": request.reasoning_effort.map(ReasoningEffort::as_str),
+        "response_format": request.response_format.as_ref().map(response_format_projection),
+    });
+    let bytes = serde_json::to_vec(&projection).unwrap_or_default();
+    hex::encode(Sha256::digest(&bytes))
+}
+
+fn content_projection(part: &CanonicalContent) -> Value {
+    match part {
+        CanonicalContent::Text(text) => json!({ "text": text }),
+        CanonicalContent::Image(ImageSource::Base64 {
+            media_type, data, ..
+        }) => json!({ "image": { "media_type": media_type, "data": data } }),
+        CanonicalContent::Image(ImageSource::Url { url, .. }) => json!({ "image": { "url": url } }),
+        CanonicalContent::ToolUse {
+            id, name, input, ..
+        } => json!({ "tool_use": { "id": id, "name": name, "input": sorted(input) } }),
+        CanonicalContent::ToolResult {
+            tool_use_id,
+            content,
+            is_error,
+            structured_content,
+            ..
+        } => json!({ "tool_result": {
+            "tool_use_id": tool_use_id,
+            "content": content.iter().map(content_projection).collect::<Vec<_>>(),
+            "is_error": is_error,
+            "structured_content": structured_content.as_ref().map(sorted),
+        } }),
+        CanonicalContent::Thinking {
+            text, signature, ..
+        } => json!({ "thinking": { "text": text, "signature": signature } }),
+    }
+}
+
+fn tool_choice_projection(choice: &CanonicalToolChoice) -> Value {
+    match choice {
+        CanonicalToolChoice::Auto => json!("auto"),
+        CanonicalToolChoice::Any => json!("any"),
+        CanonicalToolChoice::None => json!("none"),
+        CanonicalToolChoice::Required => json!("required"),
+        CanonicalToolChoice::Tool(name) => json!({ "tool": name }),
+    }
+}
+
+fn response_format_projection(format: &ResponseFormat) -> Value {
+    match format {
+        ResponseFormat::JsonObject => json!("json_object"),
+        ResponseFormat::JsonSchema {
+            name,
+            schema,
+            strict,
+        } => json!({ "json_schema": { "name": name, "schema": sorted(schema), "strict": strict } }),
+    }
+}
+
+fn sorted(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let out: Map<String, Value> = entries
+                .into_iter()
+                .map(|(k, v)| (k.clone(), sorted(v)))
+                .collect();
+            Value::Object(out)
+        },
+        Value::Array(items) => Value::Array(items.iter().map(sorted).collect()),
+        other => other.clone(),
+    }
+}
@@ -0,0 +1,129 @@
+//! Opt-in exact-match response cache.
+//!
+//! A policy with `cache.enabled` lets deterministic requests (see
+//! [`is_cacheable`]) be answered from `ai_response_cache` instead of the
+//! upstream. The lookup runs after every request-phase check — quota,
+//! guards, governance, safety — so a hit is denied exactly when a miss would
+//! be, and still counts against the caller's request quota. A hit is audited
+//! like any completion, with `response_cache_hit` set and zero cost.
+//!
+//! Entries are written after a successful completion in either lane and
+//! replayed through the normal finalize path, as a buffered body or as the
+//! synthetic stream [`replay_events`] builds, whichever the caller asked for.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+mod entry;
+mod key;
+mod replay;
+
+pub use entry::StoredResponse;
+pub use key::{KeyPartition, cache_key, is_cacheable};
+pub use replay::replay_events;
+
+use systemprompt_ai::ResponseCacheConfig;
+use systemprompt_ai::repository::{AiResponseCacheRepository, PutResponseCacheEntry};
+use systemprompt_identifiers::AiRequestId;
+
+use super::protocol::canonical::CanonicalRequest;
+use super::protocol::canonical_response::CanonicalResponse;
+
+/// One request's position in the cache: where its entry lives and the limits
+/// that apply when writing it.
+#[derive(Debug, Clone)]
+pub struct ResponseCacheSlot {
+    repo: AiResponseCacheRepository,
+    key: String,
+    config: ResponseCacheConfig,
+}
+
+impl ResponseCacheSlot {
+    #[must_use]
+    pub fn for_request(
+        config: &ResponseCacheConfig,
+        repo: &AiResponseCacheRepository,
+        request: &CanonicalRequest,
+        partition: KeyPartition<'_>,
+    ) -> Option<Self> {
+        if !config.enabled || !is_cacheable(request) {
+            return None;
+        }
+        Some(Self {
+            repo: repo.clone(),
+            key: cache_key(request, partition),
+            config: *config,
+        })
+    }
+
+    /// The same slot moved to another partition's key, for a response that
+    /// was served by a different provider or model than the one looked up.
+    #[must_use]
+    pub fn repartition(self, request: &CanonicalRequest, partition: KeyPartition<'_>) -> Self {
+        Self {
+            key: cache_key(request, partition),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// A lookup failure is a miss: the cache is an optimisation, never a
+    /// reason to fail a request the upstream could serve.
+    pub async fn lookup(&self) -> Option<CanonicalResponse> {
+        let value = match self.repo.hit(&self.key).await {
+            Ok(value) => value?,
+            Err(e) => {
+                tracing::warn!(error = %e, "response cache lookup failed — treating as a miss");
+                return None;
+            },
+        };
+        match serde_json::from_value::<StoredResponse>(value) {
+            Ok(stored) => Some(stored.into_canonical()),
+            Err(e) => {
+                tracing::warn!(error = %e, cache_key = %self.key, "response cache entry unreadable — treating as a miss");
+                None
+            },
+        }
+    }
+
+    /// Only complete responses are stored: a response with no stop reason
+    /// was cut off, and one over `max_entry_bytes` is skipped rather than
+    /// truncated.
+    pub async fn store(&self, response: &CanonicalResponse, source: &AiRequestId) {
+        if response.stop_reason.is_none() || response.content.is_empty() {
+            return;
+        }
+        let Some(stored) = StoredResponse::from_canonical(response) else {
+            return;
+        };
+        let Ok(value) = serde_json::to_value(&stored) else {
+            return;
+        };
+        let byte_len = value.to_string().len();
+        if byte_len > self.config.max_entry_bytes as usize {
+            tracing::debug!(
+                cache_key = %self.key,
+                byte_len,
+                max_entry_bytes = self.config.max_entry_bytes,
+                "response too large for the response cache — not stored"
+            );
+            return;
+        }
+        let entry = PutResponseCacheEntry {
+            cache_key: &self.key,
+            scope: self.config.scope.as_str(),
+            model: &response.model,
+            response: &value,
+            byte_len: i32::try_from(byte_len).unwrap_or(i32::MAX),
+            source_request_id: source,
+            ttl_seconds: i32::try_from(self.config.ttl_seconds).unwrap_or(i32::MAX),
+        };
+        if let Err(e) = self.repo.put(entry).await {
+            tracing::warn!(error = %e, cache_key = %self.key, "response cache store failed");
+        }
+    }
+}
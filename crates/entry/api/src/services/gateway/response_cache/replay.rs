@@ -0,0 +1,114 @@
+//! Synthetic event sequence for replaying a cached response as a stream.
+//!
+//! Mirrors the order an upstream stream arrives in — message start, one
+//! start/delta/stop triple per content block, usage, then message stop — so
+//! the stream tap and every inbound renderer handle a replay exactly like a
+//! live stream. Usage comes before the stop for the reason the Anthropic SSE
+//! decoder gives: a consumer finalizing on the terminal event must already
+//! hold the token counts.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use super::super::protocol::canonical::CanonicalContent;
+use super::super::protocol::canonical_response::{
+    CanonicalEvent, CanonicalResponse, CanonicalUsage, CanonicalUsageUpdate, ContentBlockKind,
+};
+
+#[must_use]
+pub fn replay_events(response: &CanonicalResponse) -> Vec<CanonicalEvent> {
+    let mut events = vec![CanonicalEvent::MessageStart {
+        id: response.id.clone(),
+        model: response.model.clone(),
+        usage: CanonicalUsage {
+            input_tokens: response.usage.input_tokens,
+            cache_read_tokens: response.usage.cache_read_tokens,
+            cache_creation_tokens: response.usage.cache_creation_tokens,
+            ..CanonicalUsage::default()
+        },
+    }];
+    let mut index = 0u32;
+    for part in &response.content {
+        if push_block(&mut events, index, part) {
+            index += 1;
+        }
+    }
+    events.push(CanonicalEvent::UsageDelta(CanonicalUsageUpdate {
+        input_tokens: Some(response.usage.input_tokens),
+        output_tokens: Some(response.usage.output_tokens),
+        cache_read_tokens: Some(response.usage.cache_read_tokens),
+        cache_creation_tokens: Some(response.usage.cache_creation_tokens),
+    }));
+    events.push(CanonicalEvent::MessageStop {
+        id: response.id.clone(),
+        stop_reason: response.stop_reason,
+    });
+    events
+}
+
+fn push_block(events: &mut Vec<CanonicalEvent>, index: u32, part: &CanonicalContent) -> bool {
+    match part {
+        CanonicalContent::Text(text) => {
+            events.push(CanonicalEvent::ContentBlockStart {
+                index,
+                block: ContentBlockKind::Text,
+            });
+            events.push(CanonicalEvent::TextDelta {
+                index,
+                text: text.clone(),
+            });
+        },
+        CanonicalContent::Thinking {
+            text,
+            signature,
+            id,
+            encrypted_content,
+        } => {
+            events.push(CanonicalEvent::ContentBlockStart {
+                index,
+                block: ContentBlockKind::Thinking {
+                    id: id.clone(),
+                    signature: None,
+                },
+            });
+            events.push(CanonicalEvent::ThinkingDelta {
+                index,
+                text: text.clone(),
+            });
+            if let Some(signature) = signature {
+                events.push(CanonicalEvent::SignatureDelta {
+                    index,
+                    signature: signature.clone(),
+                });
+            }
+            if let Some(data) = encrypted_content {
+                events.push(CanonicalEvent::EncryptedContentDelta {
+                    index,
+                    data: data.clone(),
+                });
+            }
+        },
+        CanonicalContent::ToolUse {
+            id,
+            name,
+            input,
+            signature,
+        } => {
+            events.push(CanonicalEvent::ContentBlockStart {
+                index,
+                block: ContentBlockKind::ToolUse {
+                    id: id.clone(),
+                    name: name.clone(),
+                    signature: signature.clone(),
+                },
+            });
+            events.push(CanonicalEvent::ToolUseDelta {
+                index,
+                partial_json: input.to_string(),
+            });
+        },
+        CanonicalContent::Image(_) | CanonicalContent::ToolResult { .. } => return false,
+    }
+    events.push(CanonicalEvent::ContentBlockStop { index });
+    true
+}
@@ -0,0 +1,90 @@
+//! Response-cache lookup for gateway dispatch.
+//!
+//! Wraps the upstream send, so it runs after governance and the request-phase
+//! safety scan. A hit becomes an `OutboundOutcome` of the shape the caller
+//! asked for, so finalize renders, scans, and audits it like any upstream
+//! reply. A miss is stored under the provider and model of the hop that
+//! actually served it.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use futures_util::{StreamExt, stream};
+
+use super::super::audit::GatewayAudit;
+use super::super::policy::GatewayPolicySpec;
+use super::super::protocol::canonical::CanonicalRequest;
+use super::super::protocol::canonical_response::CanonicalResponse;
+use super::super::protocol::outbound::OutboundOutcome;
+use super::super::response_cache::{KeyPartition, ResponseCacheSlot, replay_events};
+use super::DispatchError;
+use super::failover::{FallbackSend, send_with_fallback};
+use super::resolve::ResolvedUpstream;
+
+/// Serve the request from the cache when the policy allows it and an entry
+/// exists, otherwise send it upstream. The returned slot is `Some` only for a
+/// cacheable miss, telling finalize where to store the response.
+pub(super) async fn send_or_replay(
+    repos: &super::super::GatewayRepositories,
+    policy: &GatewayPolicySpec,
+    send: FallbackSend<'_>,
+) -> Result<(OutboundOutcome, Option<ResponseCacheSlot>), DispatchError> {
+    let (plan, request, audit) = (send.plan, send.request, send.audit);
+    let user_id = audit.ctx.user_id.as_str();
+    let slot = ResponseCacheSlot::for_request(
+        &policy.cache,
+        &repos.response_cache,
+        request,
+        partition(policy, user_id, plan.primary(), request),
+    );
+    let Some(slot) = slot else {
+        return Ok((send_with_fallback(send).await?.outcome, None));
+    };
+    if let Some(response) = slot.lookup().await {
+        return Ok((replay(response, &slot, request, audit).await, None));
+    }
+    let served = send_with_fallback(send).await?;
+    // Why: a fallback hop's reply comes from a different provider or model.
+    // Filed under the primary's key, it would be replayed as the primary's
+    // answer to every later caller, long after the primary recovered.
+    let served_by = &plan.hops[served.index];
+    let slot = slot.repartition(request, partition(policy, user_id, served_by, request));
+    Ok((served.outcome, Some(slot)))
+}
+
+fn partition<'a>(
+    policy: &GatewayPolicySpec,
+    user_id: &'a str,
+    upstream: &'a ResolvedUpstream<'_>,
+    request: &'a CanonicalRequest,
+) -> KeyPartition<'a> {
+    KeyPartition {
+        scope: policy.cache.scope,
+        user_id,
+        provider: upstream.route.provider.as_str(),
+        upstream_model: upstream.route.effective_upstream_model(&request.model),
+    }
+}
+
+async fn replay(
+    response: CanonicalResponse,
+    slot: &ResponseCacheSlot,
+    request: &CanonicalRequest,
+    audit: &GatewayAudit,
+) -> OutboundOutcome {
+    tracing::info!(
+        ai_request_id = %audit.ctx.ai_request_id,
+        user_id = %audit.ctx.user_id,
+        model = %request.model,
+        cache_key = %slot.key(),
+        streaming = request.stream,
+        "Gateway request served from response cache"
+    );
+    audit.mark_response_cache_hit().await;
+    if request.stream {
+        let events = replay_events(&response);
+        OutboundOutcome::Streaming(stream::iter(events).map(Ok).boxed())
+    } else {
+        OutboundOutcome::Buffered(Box::new(response))
+    }
+}
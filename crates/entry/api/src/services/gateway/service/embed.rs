@@ -0,0 +1,198 @@
+//! Embeddings lane of gateway dispatch.
+//!
+//! Embeddings share everything up to the upstream call with chat traffic —
+//! route resolution, audit row, quota pre-check, request guards, redaction,
+//! governance, and the request-phase safety scan — then call the primary
+//! target's
+//! [`OutboundAdapter::embed`](super::super::protocol::outbound::OutboundAdapter::embed)
+//! instead of the chat send. The inputs sent upstream are rebuilt from the
+//! redacted canonical messages, and every input is scanned as request-phase
+//! text rather than only the last one. Input tokens are charged against the
+//! route's `ModelPricing` and the caller's `ai_quota_buckets` exactly as a
+//! buffered chat completion would be.
+//!
+//! Fallback chains do not apply: a different target would return vectors from
+//! a different embedding space, which callers cannot mix with stored ones.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::body::Body;
+use axum::response::Response;
+use bytes::Bytes;
+use systemprompt_database::DbPool;
+use systemprompt_models::wire::embeddings::EmbeddingResponse;
+use systemprompt_models::wire::inspect;
+
+use super::super::audit::GatewayAudit;
+use super::super::captures::CapturedUsage;
+use super::super::policy::GatewayPolicySpec;
+use super::super::protocol::canonical::{CanonicalContent, CanonicalRequest};
+use super::super::protocol::canonical_response::CanonicalResponse;
+use super::super::protocol::inbound::openai_embeddings::{parse_embeddings, render_embeddings};
+use super::super::quota;
+use super::resolve::ResolvedUpstream;
+use super::{
+    CtxParts, DispatchError, audit_upstream_failure, enforce_governance, enforce_request_safety,
+    outbound_ctx,
+};
+
+pub(super) struct EmbedDispatch<'a> {
+    pub(super) db: &'a DbPool,
+    pub(super) repos: &'a super::super::GatewayRepositories,
+    pub(super) upstream: &'a ResolvedUpstream<'a>,
+    pub(super) request: CanonicalRequest,
+    pub(super) raw_body: &'a Bytes,
+    pub(super) policy: GatewayPolicySpec,
+    pub(super) audit: Arc<GatewayAudit>,
+}
+
+pub(super) async fn dispatch_embeddings(
+    dispatch: EmbedDispatch<'_>,
+) -> Result<Response<Body>, DispatchError> {
+    let EmbedDispatch {
+        db,
+        repos,
+        upstream,
+        mut request,
+        raw_body,
+        policy,
+        audit,
+    } = dispatch;
+    let mut parsed = parse_embeddings(raw_body).map_err(|e| DispatchError::Recorded(anyhow!(e)))?;
+    parsed.request.inputs = redacted_inputs(&request, parsed.request.inputs.len())?;
+    request.forwarded_surface = inspect::string_leaves(
+        &serde_json::to_vec(&parsed.request.inputs).unwrap_or_default(),
+        inspect::SurfaceBudget::default(),
+    );
+
+    let ctx = &audit.ctx;
+    enforce_governance(db, ctx, &request, &audit).await?;
+    enforce_request_safety(repos, &ctx.ai_request_id, &request, &policy.safety, &audit).await?;
+
+    let upstream_model = upstream
+        .route
+        .effective_upstream_model(&request.model)
+        .to_owned();
+    let out_ctx = outbound_ctx(
+        upstream,
+        &request,
+        CtxParts {
+            upstream_model: &upstream_model,
+            model_limits: None,
+            forward_headers: &[],
+            raw_body: None,
+        },
+    );
+    let response = match upstream.adapter.embed(out_ctx, &parsed.request).await {
+        Ok(response) => response,
+        Err(e) => {
+            audit_upstream_failure(
+                &audit,
+                upstream.route.provider.as_str(),
+                &upstream_model,
+                &e,
+            )
+            .await;
+            return Err(DispatchError::Recorded(e));
+        },
+    };
+
+    let body = render_embeddings(&response, parsed.encoding);
+    tokio::spawn(complete_embeddings(EmbedCompletion {
+        response,
+        body: body.clone(),
+        audit,
+        db: db.clone(),
+        repos: repos.clone(),
+        policy,
+    }));
+    Ok(Response::builder()
+        .status(http::StatusCode::OK)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap_or_else(|_| Response::new(Body::empty())))
+}
+
+// Why: the inbound adapter maps each input to exactly one user message, so
+// the redacted messages are the inputs in order. A count mismatch means the
+// two drifted apart and the raw inputs must not be sent in their place.
+fn redacted_inputs(
+    request: &CanonicalRequest,
+    expected: usize,
+) -> Result<Vec<String>, DispatchError> {
+    let inputs: Vec<String> = request
+        .messages
+        .iter()
+        .map(|message| {
+            message
+                .content
+                .iter()
+                .filter_map(|part| match part {
+                    CanonicalContent::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect()
+        })
+        .collect();
+    if inputs.len() != expected {
+        return Err(DispatchError::Recorded(anyhow!(
+            "embeddings input count changed during redaction: expected {expected}, got {}",
+            inputs.len()
+        )));
+    }
+    Ok(inputs)
+}
+
+struct EmbedCompletion {
+    response: EmbeddingResponse,
+    body: Bytes,
+    audit: Arc<GatewayAudit>,
+    db: DbPool,
+    repos: super::super::GatewayRepositories,
+    policy: GatewayPolicySpec,
+}
+
+async fn complete_embeddings(completion: EmbedCompletion) {
+    let EmbedCompletion {
+        response,
+        body,
+        audit,
+        db,
+        repos,
+        policy,
+    } = completion;
+    audit.set_served_model(&response.model).await;
+    let usage = CapturedUsage {
+        input_tokens: response.input_tokens,
+        ..CapturedUsage::default()
+    };
+    let canonical = CanonicalResponse {
+        model: response.model,
+        ..CanonicalResponse::default()
+    };
+    let cost_microdollars = match audit.complete(usage, Vec::new(), &canonical, &body).await {
+        Ok(cost) => cost,
+        Err(e) => {
+            tracing::warn!(error = %e, "embeddings audit complete failed");
+            0
+        },
+    };
+    quota::post_update_tokens(
+        &db,
+        &repos.quota_buckets,
+        quota::PostUpdateParams {
+            user_id: &audit.ctx.user_id,
+            windows: &policy.quota_windows,
+            rate_limits: &policy.rate_limits,
+            alerts: &policy.alerts,
+            input_tokens: usage.input_tokens,
+            output_tokens: 0,
+            cost_microdollars,
+        },
+    )
+    .await;
+}
@@ -0,0 +1,428 @@
+//! Upstream send across a route's hops: per-provider circuit breakers,
+//! failure classification, and the fallback walk.
+//!
+//! A single-hop plan sends exactly as before. A plan with fallback hops sends
+//! to the first hop and, when the failure is one the route's `fallback.on`
+//! lists, moves on to the next — re-preparing the payload for that hop's wire
+//! and model and re-running the request guards, governance, and the
+//! request-phase safety scan against it. A hop whose breaker is open is never
+//! sent to. Every hop tried is written to `ai_requests.route_attempts`.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use serde::Serialize;
+use systemprompt_ai::SafetyConfig;
+use systemprompt_database::DbPool;
+use systemprompt_database::resilience::{BreakerConfig, CircuitBreaker};
+use systemprompt_models::profile::{FallbackTrigger, GatewayConfig};
+
+use super::super::GatewayRepositories;
+use super::super::audit::GatewayAudit;
+use super::super::protocol::canonical::CanonicalRequest;
+use super::super::protocol::outbound::{OutboundOutcome, UpstreamError};
+use super::resolve::{ResolvedUpstream, UpstreamPlan};
+use super::{
+    CtxParts, DispatchError, Prepared, UpstreamRelay, attach_forwarded_surface,
+    audit_upstream_failure, enforce_governance, enforce_request_guards, enforce_request_safety,
+    outbound_ctx, prepare_payload,
+};
+
+/// Process-wide circuit breakers, one per upstream provider.
+struct UpstreamHealth {
+    breakers: Mutex<HashMap<String, Arc<CircuitBreaker>>>,
+}
+
+impl UpstreamHealth {
+    fn global() -> &'static Self {
+        static HEALTH: OnceLock<UpstreamHealth> = OnceLock::new();
+        HEALTH.get_or_init(|| Self {
+            breakers: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn breaker(&self, provider: &str) -> Arc<CircuitBreaker> {
+        let mut breakers = self
+            .breakers
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        Arc::clone(breakers.entry(provider.to_owned()).or_insert_with(|| {
+            Arc::new(CircuitBreaker::new(
+                format!("gateway:{provider}"),
+                BreakerConfig::default(),
+            ))
+        }))
+    }
+}
+
+/// Map an upstream failure to the fallback trigger it satisfies. `None` means
+/// the upstream answered and the request itself was refused — another provider
+/// would refuse it too.
+#[cfg_attr(
+    not(feature = "test-api"),
+    expect(
+        unreachable_pub,
+        reason = "re-exported via `test_api` only when the feature is on"
+    )
+)]
+pub fn classify(error: &anyhow::Error) -> Option<FallbackTrigger> {
+    match error.downcast_ref::<UpstreamError>()? {
+        UpstreamError::Status { status: 429, .. } => Some(FallbackTrigger::RateLimited),
+        UpstreamError::Status {
+            status: 408 | 504, ..
+        } => Some(FallbackTrigger::Timeout),
+        UpstreamError::Status { status, .. } if *status >= 500 => {
+            Some(FallbackTrigger::ServerError)
+        },
+        UpstreamError::Status { .. } => None,
+        UpstreamError::Transport { source, .. } if source.is_timeout() => {
+            Some(FallbackTrigger::Timeout)
+        },
+        UpstreamError::Transport { .. } => Some(FallbackTrigger::ServerError),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct HopAttempt {
+    provider: String,
+    upstream_model: String,
+    outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl HopAttempt {
+    fn new(upstream: &ResolvedUpstream<'_>, upstream_model: &str, outcome: &'static str) -> Self {
+        Self {
+            provider: upstream.provider.name.as_str().to_owned(),
+            upstream_model: upstream_model.to_owned(),
+            outcome,
+            status: None,
+            error: None,
+        }
+    }
+
+    fn failed(mut self, error: &anyhow::Error) -> Self {
+        if let Some(UpstreamError::Status { status, .. }) = error.downcast_ref::<UpstreamError>() {
+            self.status = Some(*status);
+        }
+        self.error = Some(error.to_string());
+        self
+    }
+}
+
+pub(super) struct FallbackSend<'a> {
+    pub(super) config: &'a GatewayConfig,
+    pub(super) plan: &'a UpstreamPlan<'a>,
+    pub(super) request: &'a CanonicalRequest,
+    pub(super) prepared: Prepared,
+    // Why: the canonical request as it stood before the first hop's system
+    // prompt override and identity strip. A fallback hop re-runs both for its
+    // own provider and model, which needs the untouched original.
+    pub(super) base_request: Option<CanonicalRequest>,
+    pub(super) forward_headers: &'a [(String, String)],
+    pub(super) relay: UpstreamRelay<'a>,
+    pub(super) audit: &'a GatewayAudit,
+    pub(super) checks: HopChecks<'a>,
+}
+
+impl<'a> FallbackSend<'a> {
+    const fn hop(&self) -> HopSend<'a> {
+        HopSend {
+            config: self.config,
+            request: self.request,
+            relay: self.relay,
+            audit: self.audit,
+            checks: self.checks,
+        }
+    }
+}
+
+/// The pre-dispatch checks a fallback hop must pass before it is sent. The
+/// primary hop passed them in `dispatch`; a fallback hop carries a different
+/// route, provider, and prepared body, so it is checked again on its own.
+#[derive(Clone, Copy)]
+pub(super) struct HopChecks<'a> {
+    db: &'a DbPool,
+    repos: &'a GatewayRepositories,
+    safety: &'a SafetyConfig,
+}
+
+impl<'a> HopChecks<'a> {
+    pub(super) const fn new(
+        db: &'a DbPool,
+        repos: &'a GatewayRepositories,
+        safety: &'a SafetyConfig,
+    ) -> Self {
+        Self { db, repos, safety }
+    }
+
+    async fn run(
+        self,
+        upstream: &ResolvedUpstream<'_>,
+        request: &CanonicalRequest,
+        audit: &GatewayAudit,
+    ) -> Result<(), DispatchError> {
+        let ctx = &audit.ctx;
+        enforce_request_guards(self.db, &ctx.user_id, upstream, request, audit).await?;
+        enforce_governance(self.db, ctx, request, audit).await?;
+        enforce_request_safety(self.repos, &ctx.ai_request_id, request, self.safety, audit).await
+    }
+}
+
+/// An upstream reply and the index of the plan hop that produced it.
+pub(super) struct Served {
+    pub(super) outcome: OutboundOutcome,
+    pub(super) index: usize,
+}
+
+pub(super) async fn send_with_fallback(send: FallbackSend<'_>) -> Result<Served, DispatchError> {
+    let hop = send.hop();
+    let FallbackSend {
+        plan,
+        request,
+        prepared,
+        base_request,
+        forward_headers,
+        audit,
+        ..
+    } = send;
+
+    let Some(base_request) = base_request else {
+        let outcome = send_single(plan, request, &prepared, forward_headers, audit).await?;
+        return Ok(Served { outcome, index: 0 });
+    };
+
+    let health = UpstreamHealth::global();
+    let mut attempts = Vec::with_capacity(plan.hops.len());
+    let mut first = Some(prepared);
+    let last = plan.hops.len() - 1;
+    for (index, upstream) in plan.hops.iter().enumerate() {
+        let breaker = health.breaker(upstream.provider.name.as_str());
+        let prepared_for_hop = first.take();
+        if breaker.acquire().is_err() {
+            let model = upstream.route.effective_upstream_model(&request.model);
+            let skipped = FallbackTrigger::CircuitOpen.as_str();
+            attempts.push(HopAttempt::new(upstream, model, skipped));
+            if index < last && plan.falls_back_on(FallbackTrigger::CircuitOpen) {
+                continue;
+            }
+            return Err(circuit_open(audit, upstream, request, &attempts).await);
+        }
+
+        let ready = prepare_hop(hop, upstream, prepared_for_hop, &base_request).await;
+        let (hop_request, prepared) = match ready {
+            Ok(ready) => ready,
+            Err(refused) => {
+                breaker.release();
+                attempts.push(refused.attempt);
+                record_attempts(audit, upstream, &attempts).await;
+                return Err(refused.error);
+            },
+        };
+        let hop_request = hop_request.as_ref();
+
+        let result = send_hop(upstream, hop_request, &prepared, forward_headers).await;
+        let model = prepared.upstream_model.as_str();
+        let error = match result {
+            Ok(outcome) => {
+                breaker.record_success();
+                attempts.push(HopAttempt::new(upstream, model, "served"));
+                record_served(audit, upstream, index, model, &attempts).await;
+                return Ok(Served { outcome, index });
+            },
+            Err(e) => e,
+        };
+
+        let trigger = classify(&error);
+        record_health(&breaker, trigger);
+        let outcome = trigger.map_or("failed", FallbackTrigger::as_str);
+        attempts.push(HopAttempt::new(upstream, model, outcome).failed(&error));
+
+        if index < last && trigger.is_some_and(|t| plan.falls_back_on(t)) {
+            tracing::warn!(
+                provider = %upstream.provider.name,
+                trigger = outcome,
+                error = %error,
+                "Gateway upstream failed — falling back to the next route target"
+            );
+            continue;
+        }
+        record_attempts(audit, upstream, &attempts).await;
+        return Err(fail(audit, upstream, hop_request, error).await);
+    }
+    // Why: unreachable in practice — the last hop never `continue`s — but a
+    // plan is not trusted to be non-empty by construction here.
+    Err(DispatchError::Recorded(anyhow::anyhow!(
+        "gateway route has no upstream hop left to try"
+    )))
+}
+
+#[derive(Clone, Copy)]
+struct HopSend<'a> {
+    config: &'a GatewayConfig,
+    request: &'a CanonicalRequest,
+    relay: UpstreamRelay<'a>,
+    audit: &'a GatewayAudit,
+    checks: HopChecks<'a>,
+}
+
+struct HopRefused {
+    attempt: HopAttempt,
+    error: DispatchError,
+}
+
+// Why: the first hop arrives already prepared and checked by `dispatch`. A
+// hop that never reached the wire still belongs in `route_attempts` — the
+// trail has to say why the walk stopped there.
+async fn prepare_hop<'r>(
+    hop: HopSend<'r>,
+    upstream: &ResolvedUpstream<'_>,
+    first: Option<Prepared>,
+    base_request: &CanonicalRequest,
+) -> Result<(Cow<'r, CanonicalRequest>, Prepared), HopRefused> {
+    let HopSend {
+        config,
+        request,
+        relay,
+        audit,
+        checks,
+    } = hop;
+    let refused = |outcome: &'static str, error: DispatchError| {
+        let model = upstream.route.effective_upstream_model(&request.model);
+        let (DispatchError::PreAudit(cause) | DispatchError::Recorded(cause)) = &error;
+        HopRefused {
+            attempt: HopAttempt::new(upstream, model, outcome).failed(cause),
+            error,
+        }
+    };
+
+    if let Some(prepared) = first {
+        return Ok((Cow::Borrowed(request), prepared));
+    }
+    let mut hop_request = base_request.clone();
+    let prepared = prepare_payload(config, upstream, &mut hop_request, audit, relay)
+        .await
+        .map_err(|e| refused("failed", e))?;
+    audit.set_prepared_body_digest(&prepared.body.bytes).await;
+    attach_forwarded_surface(&mut hop_request, &prepared, &audit.ctx.ai_request_id);
+    checks
+        .run(upstream, &hop_request, audit)
+        .await
+        .map_err(|e| refused("refused", e))?;
+    Ok((Cow::Owned(hop_request), prepared))
+}
+
+async fn record_served(
+    audit: &GatewayAudit,
+    upstream: &ResolvedUpstream<'_>,
+    index: usize,
+    model: &str,
+    attempts: &[HopAttempt],
+) {
+    if index > 0 {
+        audit.set_served_model(model).await;
+    }
+    record_attempts(audit, upstream, attempts).await;
+}
+
+async fn circuit_open(
+    audit: &GatewayAudit,
+    upstream: &ResolvedUpstream<'_>,
+    request: &CanonicalRequest,
+    attempts: &[HopAttempt],
+) -> DispatchError {
+    record_attempts(audit, upstream, attempts).await;
+    let open = anyhow::anyhow!(
+        "upstream provider '{}' is unavailable: circuit breaker open",
+        upstream.provider.name
+    );
+    fail(audit, upstream, request, open).await
+}
+
+// Why: only failures that say something about the upstream's health trip
+// its breaker. A rate limit or a client error means the provider answered.
+fn record_health(breaker: &CircuitBreaker, trigger: Option<FallbackTrigger>) {
+    match trigger {
+        Some(FallbackTrigger::ServerError | FallbackTrigger::Timeout) => breaker.record_failure(),
+        _ => breaker.record_success(),
+    }
+}
+
+async fn send_single(
+    plan: &UpstreamPlan<'_>,
+    request: &CanonicalRequest,
+    prepared: &Prepared,
+    forward_headers: &[(String, String)],
+    audit: &GatewayAudit,
+) -> Result<OutboundOutcome, DispatchError> {
+    let upstream = plan.primary();
+    let result = send_hop(upstream, request, prepared, forward_headers).await;
+    if plan.multi_target {
+        let attempt = match &result {
+            Ok(_) => HopAttempt::new(upstream, &prepared.upstream_model, "served"),
+            Err(e) => HopAttempt::new(upstream, &prepared.upstream_model, "failed").failed(e),
+        };
+        record_attempts(audit, upstream, &[attempt]).await;
+    }
+    match result {
+        Ok(outcome) => Ok(outcome),
+        Err(e) => Err(fail(audit, upstream, request, e).await),
+    }
+}
+
+async fn send_hop(
+    upstream: &ResolvedUpstream<'_>,
+    request: &CanonicalRequest,
+    prepared: &Prepared,
+    forward_headers: &[(String, String)],
+) -> anyhow::Result<OutboundOutcome> {
+    let ctx = outbound_ctx(
+        upstream,
+        request,
+        CtxParts {
+            upstream_model: &prepared.upstream_model,
+            model_limits: prepared.model_limits,
+            forward_headers,
+            raw_body: None,
+        },
+    );
+    upstream.adapter.send(ctx, &prepared.body).await
+}
+
+async fn fail(
+    audit: &GatewayAudit,
+    upstream: &ResolvedUpstream<'_>,
+    request: &CanonicalRequest,
+    error: anyhow::Error,
+) -> DispatchError {
+    audit_upstream_failure(
+        audit,
+        upstream.provider.name.as_str(),
+        &request.model,
+        &error,
+    )
+    .await;
+    DispatchError::Recorded(error)
+}
+
+async fn record_attempts(
+    audit: &GatewayAudit,
+    upstream: &ResolvedUpstream<'_>,
+    attempts: &[HopAttempt],
+) {
+    match serde_json::to_value(attempts) {
+        Ok(value) => {
+            audit
+                .set_route_attempts(upstream.provider.name.as_str(), &value)
+                .await;
+        },
+        Err(e) => tracing::warn!(error = %e, "route attempts serialization failed"),
+    }
+}
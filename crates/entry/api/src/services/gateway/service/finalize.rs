@@ -8,10 +8,16 @@
 //! `SafetyConfig::block_response_categories`; audit, quota, and cost stay in
 //! the spawned completion task so only the scan sits on the critical path.
 //!
-//! Streaming stays audit-only. The frames are already flowing by the time a
-//! whole-response scan can run, so terminating mid-stream would still leak
-//! everything sent up to that point — the block would be theatre, not
-//! containment.
+//! Streaming is audit-only by default. The frames are already flowing by the
+//! time a whole-response scan can run, so terminating mid-stream would still
+//! leak everything sent up to that point. A policy that sets
+//! `safety.stream.enforce` instead scans each delta with the text before it
+//! ahead of sending it, and the stream tap cuts the stream off on a blocking
+//! finding — the delta completing the match never reaches the client.
+//!
+//! Redaction is different: it rewrites spans rather than denying, so a
+//! `ResponseRewriter` applies it to both lanes. The streaming lane holds back a
+//! short tail of each text block so a span split across deltas is still caught.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -24,7 +30,8 @@ use http::HeaderValue;
 use systemprompt_ai::repository::AiSafetyFindingRepository;
 use systemprompt_ai::{
     Finding, HeuristicScanner, InsertSafetyFinding, OverrideAction, OverrideContext,
-    OverrideEngine, SafetyConfig, SafetyHistoryMode, SafetyScanner,
+    OverrideEngine, PHASE_RESPONSE, PatternScanner, RedactionTally, ResponseRewriter, SafetyConfig,
+    SafetyHistoryMode, SafetyScanner,
 };
 use systemprompt_database::DbPool;
 use systemprompt_identifiers::{AiRequestId, ModelId, ProviderId};
@@ -38,6 +45,7 @@ use super::super::protocol::canonical_response::CanonicalResponse;
 use super::super::protocol::inbound::InboundAdapter;
 use super::super::protocol::outbound::OutboundOutcome;
 use super::super::registry::SafetyScannerRegistry;
+use super::super::response_cache::ResponseCacheSlot;
 use super::super::signature_cache::ThoughtSignatureCache;
 use super::super::{parse, quota, stream_tap};
 use super::REQUEST_ID_HEADER;
@@ -50,6 +58,8 @@ pub(super) struct FinalizeCtx {
     pub(super) policy: GatewayPolicySpec,
     pub(super) inbound: Arc<dyn InboundAdapter>,
     pub(super) request_model: String,
+    pub(super) cache: Option<ResponseCacheSlot>,
+    pub(super) rewriter: Option<ResponseRewriter>,
 }
 
 #[cfg_attr(
@@ -92,16 +102,26 @@ pub(super) async fn finalize(outcome: OutboundOutcome, fctx: FinalizeCtx) -> Res
         policy,
         inbound,
         request_model,
+        cache,
+        rewriter,
     } = fctx;
-    let tap_ctx = stream_tap::TapFinalizeCtx {
+    // Why: a rewritten reply is specific to this request's vault — caching it
+    // would serve one caller's rehydrated originals to the next.
+    let cache = if rewriter.is_some() { None } else { cache };
+    let mut tap_ctx = stream_tap::TapFinalizeCtx {
         db,
         repos,
         policy,
         ai_request_id,
+        cache,
+        rewriter,
     };
     match outcome {
         OutboundOutcome::Buffered(canonical) => {
-            let canonical = *canonical;
+            let mut canonical = *canonical;
+            if let Some(rewriter) = tap_ctx.rewriter.as_mut() {
+                rewriter.rewrite_response(&mut canonical);
+            }
             let body = inbound.render_response(&canonical);
             finalize_buffered(canonical, body, "application/json", &audit, tap_ctx).await
         },
@@ -135,7 +155,7 @@ async fn finalize_buffered(
     body: bytes::Bytes,
     content_type: &str,
     audit: &Arc<GatewayAudit>,
-    tap_ctx: stream_tap::TapFinalizeCtx,
+    mut tap_ctx: stream_tap::TapFinalizeCtx,
 ) -> Response<Body> {
     canonical.received_surface = string_leaves(&body, SurfaceBudget::default());
     if tap_ctx.policy.safety.block_response_categories.is_empty() {
@@ -154,6 +174,9 @@ async fn finalize_buffered(
         .iter()
         .find(|f| safety.block_response_categories.contains(&f.category))
         .map(|f| (f.category.clone(), f.scanner));
+    if blocked.is_some() {
+        tap_ctx.cache = None;
+    }
     spawn_buffered_completion(canonical, body.clone(), audit, tap_ctx, true);
     match blocked {
         Some((category, scanner)) => {
@@ -222,7 +245,7 @@ async fn buffered_completion(
     canonical: CanonicalResponse,
     body: bytes::Bytes,
     audit: Arc<GatewayAudit>,
-    ctx: stream_tap::TapFinalizeCtx,
+    mut ctx: stream_tap::TapFinalizeCtx,
     response_scanned: bool,
 ) {
     let served_model = canonical.model.clone();
@@ -243,6 +266,8 @@ async fn buffered_completion(
         quota::PostUpdateParams {
             user_id: &audit.ctx.user_id,
             windows: &ctx.policy.quota_windows,
+            rate_limits: &ctx.policy.rate_limits,
+            alerts: &ctx.policy.alerts,
             input_tokens: usage.input_tokens,
             output_tokens: usage.output_tokens,
             cost_microdollars,
@@ -258,6 +283,10 @@ async fn buffered_completion(
         )
         .await;
     }
+    persist_response_redactions(&mut ctx).await;
+    if let Some(cache) = &ctx.cache {
+        cache.store(&canonical, &ctx.ai_request_id).await;
+    }
 }
 
 pub(super) async fn run_request_safety_scan(
@@ -286,9 +315,23 @@ pub(super) async fn run_request_safety_scan(
     findings
 }
 
-// Why: an extension registration named `heuristic` shadows the builtin (and
-// the policy's `heuristic` config block is then ignored); the builtin is
-// constructed per policy so each policy's phrase list applies.
+/// The policy's scanners, resolved once for a stream tap that scans the
+/// reply window by window.
+pub(in crate::services::gateway) fn stream_scanners(
+    safety: &SafetyConfig,
+) -> Vec<Arc<dyn SafetyScanner>> {
+    let registry = SafetyScannerRegistry::global();
+    safety
+        .scanners
+        .iter()
+        .filter_map(|name| resolve_scanner(registry, name, safety))
+        .collect()
+}
+
+// Why: an extension registration named `heuristic` or `pattern` shadows the
+// builtin (and the policy's matching config block is then ignored); the
+// builtins are constructed per policy so each policy's phrase list, detectors,
+// and rules apply.
 fn resolve_scanner(
     registry: &SafetyScannerRegistry,
     name: &str,
@@ -297,11 +340,17 @@ fn resolve_scanner(
     if let Some(scanner) = registry.get(name) {
         return Some(Arc::clone(scanner));
     }
-    if name == "heuristic" {
-        let scanner: Arc<dyn SafetyScanner> = Arc::new(HeuristicScanner::new(&safety.heuristic));
-        return Some(scanner);
+    match name {
+        "heuristic" => Some(Arc::new(HeuristicScanner::new(&safety.heuristic))),
+        "pattern" => match PatternScanner::new(&safety.pattern) {
+            Ok(scanner) => Some(Arc::new(scanner)),
+            Err(e) => {
+                tracing::warn!(error = %e, "pattern scanner rule failed to compile — scanner skipped");
+                None
+            },
+        },
+        _ => None,
     }
-    None
 }
 
 #[cfg_attr(
@@ -338,7 +387,7 @@ pub(in crate::services::gateway) async fn run_response_safety_scan(
     findings
 }
 
-async fn persist_findings(
+pub(in crate::services::gateway) async fn persist_findings(
     repo: &AiSafetyFindingRepository,
     ai_request_id: &AiRequestId,
     findings: &[Finding],
@@ -351,6 +400,7 @@ async fn persist_findings(
             category: &f.category,
             scanner: f.scanner,
             excerpt: f.excerpt.as_deref(),
+            redaction_count: 0,
         };
         if let Err(e) = repo.insert(params).await {
             tracing::warn!(error = %e, "safety finding insert failed");
@@ -358,6 +408,44 @@ async fn persist_findings(
     }
 }
 
+pub(in crate::services::gateway) async fn persist_response_redactions(
+    ctx: &mut stream_tap::TapFinalizeCtx,
+) {
+    let Some(rewriter) = ctx.rewriter.as_mut() else {
+        return;
+    };
+    let tallies = rewriter.take_tallies();
+    persist_redactions(
+        &ctx.repos.safety_findings,
+        &ctx.ai_request_id,
+        PHASE_RESPONSE,
+        &tallies,
+    )
+    .await;
+}
+
+pub(super) async fn persist_redactions(
+    repo: &AiSafetyFindingRepository,
+    ai_request_id: &AiRequestId,
+    phase: &str,
+    tallies: &[RedactionTally],
+) {
+    for tally in tallies {
+        let params = InsertSafetyFinding {
+            ai_request_id,
+            phase,
+            severity: tally.severity.as_str(),
+            category: &tally.category,
+            scanner: "pattern",
+            excerpt: None,
+            redaction_count: i32::try_from(tally.count).unwrap_or(i32::MAX),
+        };
+        if let Err(e) = repo.insert(params).await {
+            tracing::warn!(error = %e, "safety redaction insert failed");
+        }
+    }
+}
+
 #[cfg_attr(
     not(feature = "test-api"),
     expect(
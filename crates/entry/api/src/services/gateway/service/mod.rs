@@ -1,5 +1,10 @@
-//! Gateway dispatch entry point: route resolution, policy and quota checks,
-//! upstream send, and response finalization.
+//! Gateway dispatch entry point: route resolution, policy, rate-limit and quota
+//! checks, upstream send, and response finalization.
+//!
+//! Redaction rewrites the request right after the request guards, so
+//! governance, the scanners, and the cache all see placeholders. Embeddings
+//! requests branch off to the `embed` lane after redaction. The response cache
+//! wraps the upstream send, after every request-phase check.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -8,14 +13,21 @@
     reason = "Arc::clone usage is intentional and ergonomic in this gateway dispatch path"
 )]
 
+mod cache;
+mod embed;
+mod failover;
 mod finalize;
+mod redact;
 mod resolve;
 
-pub(super) use self::finalize::run_response_safety_scan;
+pub(super) use self::finalize::{
+    persist_findings, persist_response_redactions, run_response_safety_scan, stream_scanners,
+};
 
 #[cfg(feature = "test-api")]
 pub mod test_api {
     pub use super::blocks_at_phase;
+    pub use super::failover::classify;
     pub use super::finalize::{apply_system_prompt_override, attach_request_id, dedupe_findings};
 }
 
@@ -30,16 +42,18 @@ use systemprompt_database::DbPool;
 use systemprompt_identifiers::{AiRequestId, UserId};
 use systemprompt_models::profile::{GatewayConfig, ProviderRegistry};
 
+use self::failover::{FallbackSend, HopChecks};
 use self::finalize::{
     FinalizeCtx, apply_system_prompt_override, attach_request_id, finalize, run_request_safety_scan,
 };
 use self::resolve::{ResolvedUpstream, resolve_upstream};
 use super::audit::{GatewayAudit, GatewayRequestContext};
-use super::policy::{PolicyResolver, QuotaWindow};
+use super::policy::{GatewayPolicySpec, PolicyResolver, QuotaWindow};
 use super::protocol::canonical::CanonicalRequest;
 use super::protocol::inbound::InboundAdapter;
-use super::protocol::outbound::{OutboundCtx, OutboundOutcome, PreparedBody};
+use super::protocol::outbound::{OutboundCtx, PreparedBody};
 use super::quota;
+use super::rate_limit::{self, Admission, RateLimitPermit};
 use systemprompt_identifiers::{CallId, SessionId};
 use systemprompt_models::services::ai::ModelLimits;
 use systemprompt_models::wire::inspect;
@@ -122,52 +136,51 @@ impl GatewayService {
             forward_headers,
             identity_headers,
         } = inputs;
-        if ctx.session_id.is_none() {
-            return Err(DispatchError::PreAudit(anyhow!(
-                "gateway dispatch missing conversation binding (session_id)"
-            )));
-        }
+        require_conversation_binding(&ctx)?;
 
         let ai_request_id = ctx.ai_request_id.clone();
-        let upstream = resolve_upstream(config, registry, &request, &ai_request_id).await?;
+        let plan = resolve_upstream(config, registry, &request, &ai_request_id).await?;
+        let upstream = plan.primary();
 
-        tracing::info!(
-            ai_request_id = %ai_request_id,
-            user_id = %ctx.user_id,
-            model = %request.model,
-            provider = %upstream.route.provider,
-            upstream = %upstream.provider.endpoint,
-            wire_protocol = %ctx.wire_protocol,
-            streaming = request.stream,
-            "Gateway request dispatched"
-        );
+        log_dispatched(&ctx, &request, upstream);
 
         let resolver = PolicyResolver::from_repository(repos.gateway_policies.clone());
         let policy = resolver.resolve().await;
 
         let audit = open_audit(repos, &ctx, &request, &raw_body, &identity_headers).await?;
-
-        if let Some(descriptor) = upstream.route_match_descriptor.as_deref() {
+        if let Some(descriptor) = plan.route_match_descriptor.as_deref() {
             audit.set_route_match(descriptor).await;
         }
 
-        enforce_quota(db, repos, &ctx.user_id, &policy.quota_windows, &audit).await?;
-        enforce_request_guards(db, &ctx.user_id, &upstream, &request, &audit).await?;
+        let permit = admit(db, repos, &ctx.user_id, &policy, &audit).await?;
+        enforce_request_guards(db, &ctx.user_id, upstream, &request, &audit).await?;
+
+        let redaction =
+            redact::redact_request(repos, &ai_request_id, &mut request, &policy.safety, &audit)
+                .await?;
+
+        if inbound.serves_embeddings() {
+            let response = embed::dispatch_embeddings(embed::EmbedDispatch {
+                db,
+                repos,
+                upstream,
+                request,
+                raw_body: &raw_body,
+                policy,
+                audit,
+            })
+            .await?;
+            return Ok(permit.hold(attach_request_id(response, &ai_request_id)));
+        }
+
+        let base_request = plan.has_fallback_hops().then(|| request.clone());
+        let canonical_only = redaction.needs_canonical() || stream_enforced(&request, &policy);
+        let relay = UpstreamRelay::new(&raw_body, inbound.as_ref(), canonical_only);
 
         // Why: the payload is built before the scan so governance inspects the
         // exact bytes that will go on the wire. Scanning the canonical form and
         // sending something derived from it separately is how the two drift.
-        let prepared = prepare_payload(
-            config,
-            &upstream,
-            &mut request,
-            &audit,
-            UpstreamRelay {
-                raw_body: &raw_body,
-                inbound: inbound.as_ref(),
-            },
-        )
-        .await?;
+        let prepared = prepare_payload(config, upstream, &mut request, &audit, relay).await?;
         audit.set_prepared_body_digest(&prepared.body.bytes).await;
         attach_forwarded_surface(&mut request, &prepared, &ai_request_id);
 
@@ -177,8 +190,18 @@ impl GatewayService {
         enforce_governance(db, &ctx, &request, &audit).await?;
         enforce_request_safety(repos, &ai_request_id, &request, &policy.safety, &audit).await?;
 
-        let outcome =
-            send_to_upstream(&upstream, &request, &prepared, &forward_headers, &audit).await?;
+        let send = FallbackSend {
+            config,
+            plan: &plan,
+            request: &request,
+            prepared,
+            base_request,
+            forward_headers: &forward_headers,
+            relay,
+            audit: &audit,
+            checks: HopChecks::new(db, repos, &policy.safety),
+        };
+        let (outcome, cache) = cache::send_or_replay(repos, &policy, send).await?;
 
         let response = finalize(
             outcome,
@@ -190,16 +213,66 @@ impl GatewayService {
                 policy,
                 inbound,
                 request_model: request.model.clone(),
+                cache,
+                rewriter: redaction.rewriter,
             },
         )
         .await;
-        Ok(attach_request_id(response, &ai_request_id))
+        Ok(permit.hold(attach_request_id(response, &ai_request_id)))
     }
 }
 
+// Why: the stream tap can only cut off a stream it renders itself, so an
+// enforced stream gives up the raw passthrough lane.
+const fn stream_enforced(request: &CanonicalRequest, policy: &GatewayPolicySpec) -> bool {
+    request.stream && policy.safety.stream.enforce
+}
+
+fn require_conversation_binding(ctx: &GatewayRequestContext) -> Result<(), DispatchError> {
+    if ctx.session_id.is_none() {
+        return Err(DispatchError::PreAudit(anyhow!(
+            "gateway dispatch missing conversation binding (session_id)"
+        )));
+    }
+    Ok(())
+}
+
+fn log_dispatched(
+    ctx: &GatewayRequestContext,
+    request: &CanonicalRequest,
+    upstream: &ResolvedUpstream<'_>,
+) {
+    tracing::info!(
+        ai_request_id = %ctx.ai_request_id,
+        user_id = %ctx.user_id,
+        model = %request.model,
+        provider = %upstream.route.provider,
+        upstream = %upstream.provider.endpoint,
+        wire_protocol = %ctx.wire_protocol,
+        streaming = request.stream,
+        "Gateway request dispatched"
+    );
+}
+
+#[derive(Clone, Copy)]
 struct UpstreamRelay<'a> {
     raw_body: &'a Bytes,
     inbound: &'a dyn InboundAdapter,
+    canonical_only: bool,
+}
+
+impl<'a> UpstreamRelay<'a> {
+    const fn new(
+        raw_body: &'a Bytes,
+        inbound: &'a dyn InboundAdapter,
+        canonical_only: bool,
+    ) -> Self {
+        Self {
+            raw_body,
+            inbound,
+            canonical_only,
+        }
+    }
 }
 
 async fn open_audit(
@@ -264,9 +337,11 @@ async fn prepare_payload(
         .provider
         .find_model(&upstream_model)
         .map(|m| m.limits);
-    // Why: an applied override rewrote the canonical request, so the caller's
-    // original bytes no longer describe what the gateway decided to send.
+    // Why: an applied override or a request redaction rewrote the canonical
+    // request, so the caller's original bytes no longer describe what the
+    // gateway decided to send; a response rewrite needs canonical events back.
     let raw_body = (override_descriptor.is_none()
+        && !relay.canonical_only
         && relay.inbound.passthrough_wire() == Some(upstream.provider.wire))
     .then_some(relay.raw_body);
     // Why: unconditional, because an adapter may decline the raw lane and fall
@@ -328,33 +403,6 @@ fn outbound_ctx<'a>(
     }
 }
 
-async fn send_to_upstream(
-    upstream: &ResolvedUpstream<'_>,
-    request: &CanonicalRequest,
-    prepared: &Prepared,
-    forward_headers: &[(String, String)],
-    audit: &GatewayAudit,
-) -> Result<OutboundOutcome, DispatchError> {
-    let ctx = outbound_ctx(
-        upstream,
-        request,
-        CtxParts {
-            upstream_model: &prepared.upstream_model,
-            model_limits: prepared.model_limits,
-            forward_headers,
-            raw_body: None,
-        },
-    );
-    match upstream.adapter.send(ctx, &prepared.body).await {
-        Ok(o) => Ok(o),
-        Err(e) => {
-            audit_upstream_failure(audit, upstream.provider.name.as_str(), &request.model, &e)
-                .await;
-            Err(DispatchError::Recorded(e))
-        },
-    }
-}
-
 // Why: `metadata.user_id` is an end-user identifier meant for the provider the
 // caller chose, so it must not reach a different wire's upstream. The
 // passthrough lane applies the same rule to the raw body in
@@ -372,6 +420,36 @@ fn strip_caller_identity(request: &mut CanonicalRequest) {
     }
 }
 
+// Why: the in-process limits run before the quota windows, so a request they
+// refuse never reserves a window slot it will not use.
+async fn admit(
+    db: &DbPool,
+    repos: &super::GatewayRepositories,
+    user_id: &UserId,
+    policy: &GatewayPolicySpec,
+    audit: &GatewayAudit,
+) -> Result<RateLimitPermit, DispatchError> {
+    let admission = rate_limit::admit(db, user_id, &policy.rate_limits)
+        .await
+        .map_err(DispatchError::Recorded)?;
+    let permit = match admission {
+        Admission::Granted(permit) => permit,
+        Admission::Refused(refused) => {
+            tracing::warn!(
+                user_id = %user_id,
+                reason = %refused.message,
+                "Gateway request refused by rate limit"
+            );
+            if let Err(e) = audit.fail(&refused.message).await {
+                tracing::warn!(error = %e, "rate-limit audit fail failed");
+            }
+            return Err(DispatchError::Recorded(refused.into()));
+        },
+    };
+    enforce_quota(db, repos, user_id, &policy.quota_windows, audit).await?;
+    Ok(permit)
+}
+
 async fn enforce_quota(
     db: &DbPool,
     repos: &super::GatewayRepositories,
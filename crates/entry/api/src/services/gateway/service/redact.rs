@@ -0,0 +1,86 @@
+//! Request-phase redaction for gateway dispatch.
+//!
+//! Runs after the request guards and before the payload is built, so every
+//! later stage — governance, the scanners, the cache key, each fallback hop,
+//! the embeddings lane — sees the placeholders rather than the originals. A rewritten request can
+//! no longer ride the raw passthrough lane, and neither can one whose response
+//! will be rewritten, because both need the canonical form.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use systemprompt_ai::{PHASE_REQUEST, RedactionVault, Redactor, ResponseRewriter, SafetyConfig};
+use systemprompt_identifiers::AiRequestId;
+
+use super::super::audit::GatewayAudit;
+use super::super::protocol::canonical::CanonicalRequest;
+use super::finalize::persist_redactions;
+use super::{DispatchError, SafetyBlocked};
+
+#[derive(Debug, Default)]
+pub(super) struct Redaction {
+    pub(super) rewritten: bool,
+    pub(super) rewriter: Option<ResponseRewriter>,
+}
+
+impl Redaction {
+    pub(super) const fn needs_canonical(&self) -> bool {
+        self.rewritten || self.rewriter.is_some()
+    }
+}
+
+pub(super) async fn redact_request(
+    repos: &super::super::GatewayRepositories,
+    ai_request_id: &AiRequestId,
+    request: &mut CanonicalRequest,
+    safety: &SafetyConfig,
+    audit: &GatewayAudit,
+) -> Result<Redaction, DispatchError> {
+    let config = &safety.redaction;
+    if !config.is_active() {
+        return Ok(Redaction::default());
+    }
+    // Why: fail closed. A redaction rule that cannot compile would otherwise
+    // forward the very spans the policy promised to keep from the provider.
+    let (request_redactor, response_redactor) = match (
+        Redactor::new(&safety.pattern, &config.categories),
+        Redactor::new(&safety.pattern, &config.response_categories),
+    ) {
+        (Ok(req), Ok(resp)) => (req, resp),
+        (Err(e), _) | (_, Err(e)) => return Err(compile_failure(audit, e.to_string()).await),
+    };
+    let mut vault = RedactionVault::default();
+    let rewritten = request_redactor
+        .as_ref()
+        .is_some_and(|r| r.redact_request(&mut vault, request));
+    let tallies = vault.take_tallies();
+    if !tallies.is_empty() {
+        persist_redactions(
+            &repos.safety_findings,
+            ai_request_id,
+            PHASE_REQUEST,
+            &tallies,
+        )
+        .await;
+    }
+    Ok(Redaction {
+        rewritten,
+        rewriter: ResponseRewriter::new(response_redactor, vault, config.rehydrate),
+    })
+}
+
+async fn compile_failure(audit: &GatewayAudit, error: String) -> DispatchError {
+    let msg =
+        format!("request blocked by safety policy: redaction rule failed to compile: {error}");
+    tracing::warn!(error = %error, "Gateway redaction rule failed to compile");
+    if let Err(e) = audit.fail(&msg).await {
+        tracing::warn!(error = %e, "redaction audit fail failed");
+    }
+    DispatchError::Recorded(
+        SafetyBlocked {
+            category: "redaction".to_owned(),
+            message: msg,
+        }
+        .into(),
+    )
+}
@@ -1,6 +1,11 @@
 //! Pre-dispatch upstream resolution: model-exposure check, route and provider
 //! lookup, API-key secret, and outbound wire adapter.
 //!
+//! A route with extra `targets` resolves to several hops; [`UpstreamPlan`]
+//! holds them in the order this request will try them, each checked for its
+//! provider, secret, and adapter up front so a misconfigured fallback fails
+//! before anything is sent rather than midway through an outage.
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
@@ -10,7 +15,9 @@ use std::sync::Arc;
 use anyhow::anyhow;
 use systemprompt_ai::RouteSelectorEngine;
 use systemprompt_identifiers::AiRequestId;
-use systemprompt_models::profile::{GatewayConfig, GatewayRoute, ProviderEntry, ProviderRegistry};
+use systemprompt_models::profile::{
+    FallbackTrigger, GatewayConfig, GatewayRoute, ProviderEntry, ProviderRegistry, RouteFallback,
+};
 
 use super::super::protocol::canonical::CanonicalRequest;
 use super::super::protocol::outbound::OutboundAdapter;
@@ -22,7 +29,29 @@ pub(super) struct ResolvedUpstream<'a> {
     pub(super) provider: &'a ProviderEntry,
     pub(super) api_key: &'static str,
     pub(super) adapter: &'static Arc<dyn OutboundAdapter>,
+}
+
+pub(super) struct UpstreamPlan<'a> {
+    pub(super) hops: Vec<ResolvedUpstream<'a>>,
     pub(super) route_match_descriptor: Option<String>,
+    pub(super) fallback: Option<RouteFallback>,
+    pub(super) multi_target: bool,
+}
+
+impl<'a> UpstreamPlan<'a> {
+    pub(super) fn primary(&self) -> &ResolvedUpstream<'a> {
+        &self.hops[0]
+    }
+
+    pub(super) fn falls_back_on(&self, trigger: FallbackTrigger) -> bool {
+        self.fallback
+            .as_ref()
+            .is_some_and(|f| f.triggers_on(trigger))
+    }
+
+    pub(super) const fn has_fallback_hops(&self) -> bool {
+        self.hops.len() > 1
+    }
 }
 
 pub(super) async fn resolve_upstream<'a>(
@@ -30,7 +59,7 @@ pub(super) async fn resolve_upstream<'a>(
     registry: &'a ProviderRegistry,
     request: &CanonicalRequest,
     ai_request_id: &AiRequestId,
-) -> Result<ResolvedUpstream<'a>, DispatchError> {
+) -> Result<UpstreamPlan<'a>, DispatchError> {
     if !config.is_model_exposed(registry, &request.model) {
         tracing::warn!(
             ai_request_id = %ai_request_id,
@@ -76,6 +105,30 @@ pub(super) async fn resolve_upstream<'a>(
             .join(";")
     });
 
+    let fallback = route.fallback.clone();
+    let multi_target = !route.targets.is_empty();
+    let hops = if multi_target {
+        route
+            .hops(rand::random::<u64>())
+            .iter()
+            .map(|target| resolve_hop(Cow::Owned(route.for_target(target)), registry))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        vec![resolve_hop(route, registry)?]
+    };
+
+    Ok(UpstreamPlan {
+        hops,
+        route_match_descriptor,
+        fallback,
+        multi_target,
+    })
+}
+
+fn resolve_hop<'a>(
+    route: Cow<'a, GatewayRoute>,
+    registry: &'a ProviderRegistry,
+) -> Result<ResolvedUpstream<'a>, DispatchError> {
     let provider = route.resolve(registry).ok_or_else(|| {
         DispatchError::PreAudit(anyhow!(
             "Gateway route '{}' provider '{}' is not declared in profile.providers",
@@ -87,8 +140,12 @@ pub(super) async fn resolve_upstream<'a>(
     let secrets = systemprompt_config::SecretsBootstrap::get()
         .map_err(|e| DispatchError::PreAudit(anyhow!("Secrets not available: {e}")))?;
 
+    // Why: a local server usually runs without authentication, so an unset key
+    // is sent as an empty one rather than failing the hop.
     let api_key = secrets
         .get(provider.api_key_secret.as_str())
+        .map(String::as_str)
+        .or_else(|| provider.is_local().then_some(""))
         .ok_or_else(|| {
             DispatchError::PreAudit(anyhow!(
                 "Gateway API key secret '{}' not configured",
@@ -110,6 +167,5 @@ pub(super) async fn resolve_upstream<'a>(
         provider,
         api_key,
         adapter,
-        route_match_descriptor,
     })
 }
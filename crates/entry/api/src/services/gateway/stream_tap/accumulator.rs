@@ -32,9 +32,18 @@ pub struct TapState {
     saw_usage_delta: bool,
     pub(super) final_bytes: BytesMut,
     pub(super) error: Option<String>,
+    pub(super) cut_off: Option<CutOff>,
     pub(super) finalized: bool,
 }
 
+/// Why the stream guard ended a stream early, and how many bytes of text the
+/// upstream had generated that the client never received.
+#[derive(Debug)]
+pub(super) struct CutOff {
+    pub(super) reason: String,
+    pub(super) withheld_bytes: usize,
+}
+
 #[derive(Debug, Clone)]
 enum BlockAccumulator {
     Text(String),
@@ -67,6 +76,7 @@ pub struct Summary {
     pub final_bytes: Bytes,
     pub served_model: Option<String>,
     pub error: Option<String>,
+    pub cut_off: Option<String>,
     pub saw_stop: bool,
     pub saw_usage_delta: bool,
 }
@@ -80,7 +90,7 @@ pub struct Summary {
 )]
 pub fn extract_summary(state: &mut TapState) -> Summary {
     let mut response = build_response(state);
-    let usage = CapturedUsage {
+    let mut usage = CapturedUsage {
         input_tokens: state.usage.input_tokens,
         output_tokens: state.usage.output_tokens,
         cache_read_tokens: state.usage.cache_read_tokens,
@@ -107,6 +117,12 @@ pub fn extract_summary(state: &mut TapState) -> Summary {
             }
         })
         .collect();
+    let cut_off = state.cut_off.take().map(|cut| {
+        if usage.output_tokens == 0 {
+            usage.output_tokens = estimate_output_tokens(&response, cut.withheld_bytes);
+        }
+        cut.reason
+    });
     let final_bytes = std::mem::take(&mut state.final_bytes).freeze();
     response.received_surface = sse_string_leaves(&final_bytes, SurfaceBudget::default());
     let served_model = if state.served_model.is_empty() {
@@ -121,6 +137,7 @@ pub fn extract_summary(state: &mut TapState) -> Summary {
         final_bytes,
         served_model,
         error: state.error.clone(),
+        cut_off,
         saw_stop: state.final_stop_reason.is_some(),
         saw_usage_delta: state.saw_usage_delta,
     }
@@ -137,6 +154,22 @@ pub fn snapshot(state: &TapState) -> CanonicalResponse {
     build_response(state)
 }
 
+// Why: a cut-off stream never reaches the frame that reports output tokens,
+// yet the provider bills everything it generated. Four bytes a token is the
+// same rough rate the route's context-window estimate uses.
+fn estimate_output_tokens(response: &CanonicalResponse, withheld_bytes: usize) -> u32 {
+    let generated: usize = response
+        .content
+        .iter()
+        .map(|part| match part {
+            CanonicalContent::Text(text) | CanonicalContent::Thinking { text, .. } => text.len(),
+            CanonicalContent::ToolUse { input, .. } => input.to_string().len(),
+            _ => 0,
+        })
+        .sum();
+    u32::try_from((generated + withheld_bytes) / 4 + 1).unwrap_or(u32::MAX)
+}
+
 fn build_response(state: &TapState) -> CanonicalResponse {
     let content = state
         .blocks
@@ -0,0 +1,109 @@
+//! Response-phase enforcement for the canonical streaming lane.
+//!
+//! Each text delta is appended to a sliding window of the reply and the window
+//! is scanned before any of it is rendered. Like the redaction lane, the guard
+//! holds back the last [`STREAM_HOLDBACK`] bytes of each block, so a match
+//! split across several deltas is caught while every part of it is still
+//! held — none of it is ever sent. Held text is released once the block stops
+//! or the message ends; it was scanned as it arrived. The window bounds how
+//! far back a single match can reach.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+
+use systemprompt_ai::{Finding, STREAM_HOLDBACK, SafetyConfig, SafetyScanner};
+
+use super::super::protocol::canonical_response::CanonicalEvent;
+use super::super::service::stream_scanners;
+use super::rewrite::push_text;
+
+pub(super) struct StreamGuard {
+    scanners: Vec<Arc<dyn SafetyScanner>>,
+    block: Vec<String>,
+    window: String,
+    window_bytes: usize,
+    held: BTreeMap<u32, String>,
+}
+
+impl StreamGuard {
+    /// `None` unless the policy enforces on streams and one of its scanners
+    /// is known.
+    pub(super) fn for_policy(safety: &SafetyConfig) -> Option<Self> {
+        if !safety.stream.enforce || safety.block_response_categories.is_empty() {
+            return None;
+        }
+        let scanners = stream_scanners(safety);
+        if scanners.is_empty() {
+            return None;
+        }
+        Some(Self {
+            scanners,
+            block: safety.block_response_categories.clone(),
+            window: String::new(),
+            window_bytes: safety.stream.window_bytes as usize,
+            held: BTreeMap::new(),
+        })
+    }
+
+    /// Appends block `index`'s `text` to the window and scans it. Returns the
+    /// first finding in a blocked category, else the block's text that is now
+    /// safe to send — possibly empty.
+    pub(super) fn inspect(&mut self, index: u32, text: &str) -> Result<String, Finding> {
+        self.window.push_str(text);
+        let finding = self
+            .scanners
+            .iter()
+            .flat_map(|scanner| scanner.scan_response_window(&self.window))
+            .find(|finding| self.block.contains(&finding.category));
+        if self.window.len() > self.window_bytes {
+            let mut cut = self.window.len() - self.window_bytes;
+            while !self.window.is_char_boundary(cut) {
+                cut += 1;
+            }
+            self.window.drain(..cut);
+        }
+        if let Some(finding) = finding {
+            return Err(finding);
+        }
+
+        let held = self.held.entry(index).or_default();
+        held.push_str(text);
+        if held.len() <= STREAM_HOLDBACK {
+            return Ok(String::new());
+        }
+        let mut cut = held.len() - STREAM_HOLDBACK;
+        while !held.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        let tail = held.split_off(cut);
+        Ok(std::mem::replace(held, tail))
+    }
+
+    /// Everything block `index` still holds back.
+    pub(super) fn release(&mut self, index: u32) -> String {
+        self.held.remove(&index).unwrap_or_default()
+    }
+
+    /// Blocks still holding text, for a message that stops without closing
+    /// them.
+    pub(super) fn held_blocks(&self) -> Vec<u32> {
+        self.held.keys().copied().collect()
+    }
+
+    /// Drops every block's held text and returns how many bytes it was.
+    pub(super) fn discard(&mut self) -> usize {
+        std::mem::take(&mut self.held)
+            .into_values()
+            .map(|text| text.len())
+            .sum()
+    }
+}
+
+pub(super) fn release_all(guard: &mut StreamGuard, out: &mut VecDeque<CanonicalEvent>) {
+    for index in guard.held_blocks() {
+        push_text(out, index, guard.release(index));
+    }
+}
@@ -1,16 +1,26 @@
 //! Streaming response tap: re-renders upstream canonical events to the inbound
 //! wire format while accumulating a full response snapshot for the audit sink.
 //!
+//! When the policy redacts response text, events pass through a
+//! `ResponseRewriter` first, so the snapshot records what the client received.
+//! When it enforces on streams, a `StreamGuard` scans each text delta and holds
+//! back the tail of its block before anything is rendered, and ends the stream
+//! with the inbound protocol's error event on a blocking finding. A cut-off
+//! stream settles as `truncated`, charging the tokens consumed before the cut.
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 mod accumulator;
+mod guard;
+mod rewrite;
 
 #[cfg(feature = "test-api")]
 pub mod test_api {
     pub use super::accumulator::{Summary, TapState, accumulate_event, extract_summary, snapshot};
 }
 
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
@@ -18,27 +28,39 @@ use std::task::{Context, Poll};
 use axum::body::Body;
 use bytes::Bytes;
 use futures_util::stream::{BoxStream, Stream};
+use systemprompt_ai::ResponseRewriter;
 use systemprompt_database::DbPool;
 use systemprompt_identifiers::AiRequestId;
 
-use self::accumulator::{Summary, TapState, accumulate_event, extract_summary, snapshot};
+use self::accumulator::{CutOff, Summary, TapState, accumulate_event, extract_summary, snapshot};
+use self::guard::StreamGuard;
+use self::rewrite::push_text;
 use super::audit::GatewayAudit;
 use super::policy::GatewayPolicySpec;
-use super::protocol::canonical_response::CanonicalEvent;
+use super::protocol::canonical_response::{CanonicalEvent, CanonicalResponse};
 use super::protocol::inbound::InboundAdapter;
 use super::protocol::outbound::anthropic::streaming::SseDecoder;
 use super::quota;
-use super::service::run_response_safety_scan;
+use super::response_cache::ResponseCacheSlot;
+use super::service::{persist_findings, persist_response_redactions, run_response_safety_scan};
 use super::signature_cache::ThoughtSignatureCache;
 
-/// Shared by the streaming and buffered completion tasks so both debit quota
-/// and run the response-phase safety scan identically.
+/// Shared by the streaming and buffered completion tasks so both debit quota,
+/// run the response-phase safety scan, and fill the response cache
+/// identically.
+///
+/// `cache` is `None` when the policy leaves caching off, the request is not
+/// cacheable, the response is itself a cache replay, or `rewriter` is set.
+/// `rewriter` is `Some` only when the policy redacts or rehydrates response
+/// text; the raw lane never carries one.
 #[derive(Debug)]
 pub struct TapFinalizeCtx {
     pub db: DbPool,
     pub repos: crate::services::gateway::GatewayRepositories,
     pub policy: GatewayPolicySpec,
     pub ai_request_id: AiRequestId,
+    pub cache: Option<ResponseCacheSlot>,
+    pub rewriter: Option<ResponseRewriter>,
 }
 
 pub fn tap(
@@ -46,7 +68,7 @@ pub fn tap(
     inbound: Arc<dyn InboundAdapter>,
     request_model: String,
     audit: Arc<GatewayAudit>,
-    finalize_ctx: TapFinalizeCtx,
+    mut finalize_ctx: TapFinalizeCtx,
 ) -> Body {
     let state = Arc::new(Mutex::new(TapState::default()));
     let tapped = TappedStream {
@@ -55,6 +77,11 @@ pub fn tap(
         inbound,
         request_model,
         audit,
+        rewriter: finalize_ctx.rewriter.take(),
+        guard: StreamGuard::for_policy(&finalize_ctx.policy.safety),
+        cut_off: false,
+        pending: VecDeque::new(),
+        guarded: VecDeque::new(),
         finalize_ctx: Some(finalize_ctx),
     };
     Body::from_stream(tapped)
@@ -145,6 +172,11 @@ struct TappedStream {
     inbound: Arc<dyn InboundAdapter>,
     request_model: String,
     audit: Arc<GatewayAudit>,
+    rewriter: Option<ResponseRewriter>,
+    guard: Option<StreamGuard>,
+    cut_off: bool,
+    pending: VecDeque<CanonicalEvent>,
+    guarded: VecDeque<CanonicalEvent>,
     finalize_ctx: Option<TapFinalizeCtx>,
 }
 
@@ -152,45 +184,48 @@ impl Stream for TappedStream {
     type Item = Result<Bytes, std::io::Error>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
         loop {
-            match self.inner.as_mut().poll_next(cx) {
+            if let Some(event) = this.guarded.pop_front() {
+                if let Some(bytes) = this.emit(&event) {
+                    return Poll::Ready(Some(Ok(bytes)));
+                }
+                continue;
+            }
+            if this.cut_off {
+                return this.finalize_on_eof();
+            }
+            if let Some(event) = this.pending.pop_front() {
+                this.enforce(event);
+                continue;
+            }
+            match this.inner.as_mut().poll_next(cx) {
                 Poll::Pending => return Poll::Pending,
                 Poll::Ready(None) => {
-                    return self.finalize_on_eof();
+                    if let Some(rewriter) = this.rewriter.as_mut() {
+                        rewrite::flush_all(rewriter, &mut this.pending);
+                        if !this.pending.is_empty() {
+                            continue;
+                        }
+                    }
+                    if let Some(guard) = this.guard.as_mut() {
+                        guard::release_all(guard, &mut this.guarded);
+                        if !this.guarded.is_empty() {
+                            continue;
+                        }
+                    }
+                    return this.finalize_on_eof();
                 },
                 Poll::Ready(Some(Err(e))) => {
-                    if let Ok(mut s) = self.state.lock() {
+                    if let Ok(mut s) = this.state.lock() {
                         s.error = Some(e.clone());
                     }
                     let err = std::io::Error::new(std::io::ErrorKind::BrokenPipe, e);
                     return Poll::Ready(Some(Err(err)));
                 },
-                Poll::Ready(Some(Ok(event))) => {
-                    let terminal = matches!(
-                        event,
-                        CanonicalEvent::ContentBlockStop { .. }
-                            | CanonicalEvent::MessageStop { .. }
-                    );
-                    let snap = self.state.lock().map_or(None, |mut s| {
-                        accumulate_event(&mut s, &event);
-                        terminal.then(|| snapshot(&s))
-                    });
-                    let rendered = snap
-                        .as_ref()
-                        .and_then(|snapshot| {
-                            self.inbound.render_terminal_event(
-                                &event,
-                                snapshot,
-                                &self.request_model,
-                            )
-                        })
-                        .or_else(|| self.inbound.render_event(&event, &self.request_model));
-                    if let Some(bytes) = rendered {
-                        if let Ok(mut s) = self.state.lock() {
-                            s.final_bytes.extend_from_slice(&bytes);
-                        }
-                        return Poll::Ready(Some(Ok(bytes)));
-                    }
+                Poll::Ready(Some(Ok(event))) => match this.rewriter.as_mut() {
+                    Some(rewriter) => rewrite::rewrite_event(rewriter, event, &mut this.pending),
+                    None => this.pending.push_back(event),
                 },
             }
         }
@@ -198,8 +233,100 @@ impl Stream for TappedStream {
 }
 
 impl TappedStream {
+    /// Passes `event` on to `guarded` through the stream guard: text is held
+    /// back until its block stops, and a delta that completes a blocking
+    /// finding is swapped for the error event that ends the stream.
+    fn enforce(&mut self, event: CanonicalEvent) {
+        let Some(guard) = self.guard.as_mut() else {
+            self.guarded.push_back(event);
+            return;
+        };
+        let (index, text) = match event {
+            CanonicalEvent::TextDelta { index, text } => (index, text),
+            CanonicalEvent::ContentBlockStop { index } => {
+                push_text(&mut self.guarded, index, guard.release(index));
+                self.guarded.push_back(event);
+                return;
+            },
+            CanonicalEvent::MessageStop { .. } => {
+                guard::release_all(guard, &mut self.guarded);
+                self.guarded.push_back(event);
+                return;
+            },
+            other => {
+                self.guarded.push_back(other);
+                return;
+            },
+        };
+        let finding = match guard.inspect(index, &text) {
+            Ok(released) => {
+                push_text(&mut self.guarded, index, released);
+                return;
+            },
+            Err(finding) => finding,
+        };
+        self.cut_off = true;
+        let withheld_bytes = text.len()
+            + guard.discard()
+            + self
+                .pending
+                .drain(..)
+                .map(|pending| match pending {
+                    CanonicalEvent::TextDelta { text, .. } => text.len(),
+                    _ => 0,
+                })
+                .sum::<usize>();
+        tracing::warn!(
+            ai_request_id = %self.audit.ctx.ai_request_id,
+            category = %finding.category,
+            scanner = %finding.scanner,
+            "Gateway cut off streamed response by safety policy"
+        );
+        let message = format!(
+            "response stream cut off by safety policy: category '{}'",
+            finding.category
+        );
+        if let Ok(mut s) = self.state.lock() {
+            s.cut_off = Some(CutOff {
+                reason: message.clone(),
+                withheld_bytes,
+            });
+        }
+        if let Some(ctx) = &self.finalize_ctx {
+            let repo = ctx.repos.safety_findings.clone();
+            let ai_request_id = ctx.ai_request_id.clone();
+            tokio::spawn(async move {
+                persist_findings(&repo, &ai_request_id, &[finding]).await;
+            });
+        }
+        self.guarded.push_back(CanonicalEvent::Error(message));
+    }
+
+    fn emit(&self, event: &CanonicalEvent) -> Option<Bytes> {
+        let terminal = matches!(
+            event,
+            CanonicalEvent::ContentBlockStop { .. } | CanonicalEvent::MessageStop { .. }
+        );
+        let snap = self.state.lock().map_or(None, |mut s| {
+            accumulate_event(&mut s, event);
+            terminal.then(|| snapshot(&s))
+        });
+        let rendered = snap
+            .as_ref()
+            .and_then(|snapshot| {
+                self.inbound
+                    .render_terminal_event(event, snapshot, &self.request_model)
+            })
+            .or_else(|| self.inbound.render_event(event, &self.request_model))?;
+        if let Ok(mut s) = self.state.lock() {
+            s.final_bytes.extend_from_slice(&rendered);
+        }
+        Some(rendered)
+    }
+
     fn take_summary(&mut self) -> Option<(Summary, TapFinalizeCtx)> {
-        let ctx = self.finalize_ctx.take()?;
+        let mut ctx = self.finalize_ctx.take()?;
+        ctx.rewriter = self.rewriter.take();
         self.state.lock().ok().and_then(|mut s| {
             if s.finalized {
                 return None;
@@ -254,7 +381,12 @@ pub const fn classify(
     }
 }
 
-fn finalize(audit: Arc<GatewayAudit>, summary: Summary, ctx: TapFinalizeCtx, origin: &'static str) {
+fn finalize(
+    audit: Arc<GatewayAudit>,
+    summary: Summary,
+    mut ctx: TapFinalizeCtx,
+    origin: &'static str,
+) {
     if let Some(conversation) = &audit.ctx.gateway_conversation_id {
         ThoughtSignatureCache::global().store_from_response(conversation, &summary.response);
     }
@@ -262,7 +394,13 @@ fn finalize(audit: Arc<GatewayAudit>, summary: Summary, ctx: TapFinalizeCtx, ori
         if let Some(model) = summary.served_model.as_deref() {
             audit.set_served_model(model).await;
         }
+        persist_response_redactions(&mut ctx).await;
 
+        if let Some(reason) = summary.cut_off.as_deref() {
+            audit.mark_truncated(reason);
+            settle(&audit, &ctx, summary, origin).await;
+            return;
+        }
         let has_content = !summary.final_bytes.is_empty();
         let has_usage = summary.saw_usage_delta
             && (summary.usage.input_tokens > 0 || summary.usage.output_tokens > 0);
@@ -285,41 +423,59 @@ fn finalize(audit: Arc<GatewayAudit>, summary: Summary, ctx: TapFinalizeCtx, ori
                         "stream completed with content but zero usage: cost capture miss"
                     );
                 }
-                let cost_microdollars = match audit
-                    .complete(
-                        summary.usage,
-                        summary.tool_calls,
-                        &summary.response,
-                        &summary.final_bytes,
-                    )
-                    .await
-                {
-                    Ok(cost) => cost,
-                    Err(e) => {
-                        tracing::warn!(origin, error = %e, "stream audit complete failed");
-                        0
-                    },
-                };
-                quota::post_update_tokens(
-                    &ctx.db,
-                    &ctx.repos.quota_buckets,
-                    quota::PostUpdateParams {
-                        user_id: &audit.ctx.user_id,
-                        windows: &ctx.policy.quota_windows,
-                        input_tokens: summary.usage.input_tokens,
-                        output_tokens: summary.usage.output_tokens,
-                        cost_microdollars,
-                    },
-                )
-                .await;
+                let response = settle(&audit, &ctx, summary, origin).await;
                 run_response_safety_scan(
                     &ctx.repos.safety_findings,
                     &ctx.ai_request_id,
-                    &summary.response,
+                    &response,
                     &ctx.policy.safety,
                 )
                 .await;
+                if let Some(cache) = &ctx.cache {
+                    cache.store(&response, &ctx.ai_request_id).await;
+                }
             },
         }
     });
 }
+
+// Why: a cut-off stream settles here too — the provider billed the tokens it
+// generated before the cut, so they are charged even though the reply was
+// never delivered whole.
+async fn settle(
+    audit: &GatewayAudit,
+    ctx: &TapFinalizeCtx,
+    summary: Summary,
+    origin: &'static str,
+) -> CanonicalResponse {
+    let cost_microdollars = match audit
+        .complete(
+            summary.usage,
+            summary.tool_calls,
+            &summary.response,
+            &summary.final_bytes,
+        )
+        .await
+    {
+        Ok(cost) => cost,
+        Err(e) => {
+            tracing::warn!(origin, error = %e, "stream audit complete failed");
+            0
+        },
+    };
+    quota::post_update_tokens(
+        &ctx.db,
+        &ctx.repos.quota_buckets,
+        quota::PostUpdateParams {
+            user_id: &audit.ctx.user_id,
+            windows: &ctx.policy.quota_windows,
+            rate_limits: &ctx.policy.rate_limits,
+            alerts: &ctx.policy.alerts,
+            input_tokens: summary.usage.input_tokens,
+            output_tokens: summary.usage.output_tokens,
+            cost_microdollars,
+        },
+    )
+    .await;
+    summary.response
+}
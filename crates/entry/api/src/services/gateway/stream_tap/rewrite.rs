@@ -0,0 +1,48 @@
+//! Response redaction for the canonical streaming lane.
+//!
+//! Text deltas pass through the request's `ResponseRewriter`, which holds back
+//! the tail of each block until it can no longer be part of a span. Held text
+//! is released before the block's stop event, and any still held at message
+//! stop or end of stream is released then.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::VecDeque;
+
+use systemprompt_ai::ResponseRewriter;
+
+use super::super::protocol::canonical_response::CanonicalEvent;
+
+pub(super) fn rewrite_event(
+    rewriter: &mut ResponseRewriter,
+    event: CanonicalEvent,
+    out: &mut VecDeque<CanonicalEvent>,
+) {
+    match event {
+        CanonicalEvent::TextDelta { index, text } => {
+            push_text(out, index, rewriter.push(index, &text));
+        },
+        CanonicalEvent::ContentBlockStop { index } => {
+            push_text(out, index, rewriter.flush(index));
+            out.push_back(event);
+        },
+        CanonicalEvent::MessageStop { .. } => {
+            flush_all(rewriter, out);
+            out.push_back(event);
+        },
+        other => out.push_back(other),
+    }
+}
+
+pub(super) fn flush_all(rewriter: &mut ResponseRewriter, out: &mut VecDeque<CanonicalEvent>) {
+    for index in rewriter.pending_blocks() {
+        push_text(out, index, rewriter.flush(index));
+    }
+}
+
+pub(super) fn push_text(out: &mut VecDeque<CanonicalEvent>, index: u32, text: String) {
+    if !text.is_empty() {
+        out.push_back(CanonicalEvent::TextDelta { index, text });
+    }
+}
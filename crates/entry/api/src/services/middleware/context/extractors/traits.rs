@@ -6,7 +6,7 @@
 use async_trait::async_trait;
 use axum::body::Body;
 use axum::extract::Request;
-use axum::http::HeaderMap;
+use axum::http::{HeaderMap, Method, Uri};
 use systemprompt_models::execution::{ContextExtractionError, RequestContext};
 
 // Why: `#[async_trait]`: ContextExtractor is dispatched as a trait object (`dyn
@@ -18,6 +18,17 @@ pub trait ContextExtractor: Send + Sync {
         headers: &HeaderMap,
     ) -> Result<RequestContext, ContextExtractionError>;
 
+    // Why: header-only flavours still know the request target; extractors that
+    // verify RFC 9449 DPoP proofs need it for `htm`/`htu`, the rest ignore it.
+    async fn extract_from_parts(
+        &self,
+        _method: &Method,
+        _uri: &Uri,
+        headers: &HeaderMap,
+    ) -> Result<RequestContext, ContextExtractionError> {
+        self.extract_from_headers(headers).await
+    }
+
     async fn extract_from_request(
         &self,
         request: Request<Body>,
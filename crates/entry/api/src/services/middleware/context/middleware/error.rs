@@ -17,6 +17,9 @@ pub(crate) fn extraction_error_to_api_error(error: &ContextExtractionError) -> A
             ApiError::unauthorized("Invalid or expired JWT token")
         },
         ContextExtractionError::Revoked => ApiError::unauthorized("Token revoked"),
+        ContextExtractionError::InvalidDpopProof(_) => {
+            ApiError::unauthorized("Invalid or missing DPoP proof")
+        },
         ContextExtractionError::UserNotFound(_) => ApiError::unauthorized("User no longer exists"),
         ContextExtractionError::MissingSessionId => {
             ApiError::bad_request("JWT missing required 'session_id' claim")
@@ -75,6 +78,13 @@ pub(super) fn log_error_response(
                 "Context extraction failed: invalid token"
             );
         },
+        ContextExtractionError::InvalidDpopProof(reason) => {
+            tracing::warn!(
+                reason = %reason,
+                error_type = "invalid_dpop_proof",
+                "Context extraction failed: invalid DPoP proof"
+            );
+        },
         ContextExtractionError::UserNotFound(user_id) => {
             tracing::warn!(
                 user_id = %user_id,
@@ -12,11 +12,12 @@
 
 use std::sync::Arc;
 
-use axum::extract::Request;
+use axum::extract::{OriginalUri, Request};
+use axum::http::Uri;
 use axum::middleware::Next;
 use axum::response::Response;
 use systemprompt_identifiers::{AgentName, ContextId};
-use systemprompt_models::execution::context::RequestContext;
+use systemprompt_models::execution::context::{ContextExtractionError, RequestContext};
 use systemprompt_security::HeaderExtractor;
 use tracing::Instrument;
 
@@ -92,7 +93,12 @@ impl UserOnlyContextMiddleware {
         let path = request.uri().path().to_owned();
         let method = request.method().to_string();
 
-        match self.extractor.extract_from_headers(request.headers()).await {
+        let uri = original_uri(&request);
+        match self
+            .extractor
+            .extract_from_parts(request.method(), &uri, request.headers())
+            .await
+        {
             Ok(context) => {
                 let span = create_request_span(&context);
                 request.extensions_mut().insert(context);
@@ -156,7 +162,9 @@ impl A2AContextMiddleware {
 /// The session-context fallback is load-bearing: MCP clients (Cowork,
 /// Claude Code, etc.) only begin OAuth discovery on a 401 carrying the
 /// challenge — collapsing this to a 4xx-without-challenge breaks them. See
-/// `crates/tests/integration/api/routes_mcp_unauth_challenge.rs`.
+/// `crates/tests/integration/api/routes_mcp_unauth_challenge.rs`. A rejected
+/// `DPoP` proof is the exception: the caller did authenticate, so falling back
+/// to the anonymous context would only hide the failure.
 #[derive(Clone)]
 pub struct McpContextMiddleware {
     extractor: DynExtractor,
@@ -183,13 +191,21 @@ impl McpContextMiddleware {
         let path = request.uri().path().to_owned();
         let method = request.method().to_string();
 
-        match self.extractor.extract_from_headers(request.headers()).await {
+        let uri = original_uri(&request);
+        match self
+            .extractor
+            .extract_from_parts(request.method(), &uri, request.headers())
+            .await
+        {
             Ok(context) => {
                 let span = create_request_span(&context);
                 let mut req = request;
                 req.extensions_mut().insert(context);
                 next.run(req).instrument(span).await
             },
+            Err(e @ ContextExtractionError::InvalidDpopProof(_)) => {
+                log_error_response(&e, &trace_id, &path, &method)
+            },
             Err(e) => {
                 if let Some(ctx) = request.extensions().get::<RequestContext>().cloned() {
                     tracing::debug!(
@@ -206,3 +222,12 @@ impl McpContextMiddleware {
         }
     }
 }
+
+// Why: nested routers strip their prefix from `request.uri()`; a DPoP `htu`
+// names the full path the client dialled.
+fn original_uri(request: &Request) -> Uri {
+    request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or_else(|| request.uri().clone(), |original| original.0.clone())
+}
@@ -1,8 +1,10 @@
 //! JWT-backed request-context extractor.
 //!
 //! [`JwtContextExtractor`] implements [`ContextExtractor`] by validating the
-//! bearer token (signature, session existence, user existence, and JTI
-//! revocation) and building a `RequestContext`. It resolves the context id from
+//! bearer token (signature, session existence, user existence, JTI revocation
+//! of the token and of the token it derives from, the `DPoP` proof for
+//! sender-constrained tokens, and the route a derived token is confined to)
+//! and building a `RequestContext`. It resolves the context id from
 //! the `x-context-id` header on standard routes and from the JSON-RPC body on
 //! A2A routes, and exposes a gateway decode path for pre-authenticated tokens.
 //!
@@ -11,8 +13,8 @@
 
 use async_trait::async_trait;
 use axum::body::Body;
-use axum::extract::Request;
-use axum::http::HeaderMap;
+use axum::extract::{OriginalUri, Request};
+use axum::http::{HeaderMap, Method, Uri};
 use std::sync::Arc;
 
 use crate::services::middleware::context::ContextExtractor;
@@ -21,6 +23,8 @@ use systemprompt_models::execution::context::{ContextExtractionError, RequestCon
 use systemprompt_security::{JwtUserContext, TokenExtractor, extract_user_context};
 use systemprompt_traits::{AnalyticsProvider, UserProvider};
 
+use super::dpop::{RequestTarget, ensure_sender_constrained};
+use super::hop::ensure_hop_target;
 use super::params::{BuildContextParams, build_context, extract_common_headers};
 use super::revocation::JtiRevocationChecker;
 use super::validation::{UserCache, user_is_admin, validate_session_exists, validate_user_exists};
@@ -91,15 +95,28 @@ impl JwtContextExtractor {
         self.jti_revocation
             .ensure_not_revoked(&jwt_context.jti)
             .await?;
+        if let Some(parent_jti) = jwt_context.parent_jti.as_deref() {
+            self.jti_revocation.ensure_not_revoked(parent_jti).await?;
+        }
         Ok(validated.user)
     }
 
     pub async fn extract_standard(
         &self,
         headers: &HeaderMap,
+    ) -> Result<RequestContext, ContextExtractionError> {
+        self.extract_for_target(headers, None).await
+    }
+
+    async fn extract_for_target(
+        &self,
+        headers: &HeaderMap,
+        target: Option<RequestTarget<'_>>,
     ) -> Result<RequestContext, ContextExtractionError> {
         let jwt_context = self.extract_jwt_context(headers)?;
         let user = self.validate(&jwt_context, "").await?;
+        ensure_sender_constrained(&jwt_context, headers, target, &self.jti_revocation).await?;
+        ensure_hop_target(&jwt_context, target)?;
 
         let context_id = headers
             .get("x-context-id")
@@ -134,6 +151,12 @@ impl JwtContextExtractor {
     ) -> Result<(JwtUserContext, systemprompt_traits::AuthUser), ContextExtractionError> {
         let jwt_context = extract_user_context(jwt_token.as_str())
             .map_err(|e| ContextExtractionError::InvalidToken(e.to_string()))?;
+        if jwt_context.dpop_jkt.is_some() {
+            return Err(ContextExtractionError::InvalidDpopProof(
+                "DPoP-bound tokens are not accepted by the gateway".to_owned(),
+            ));
+        }
+        ensure_hop_target(&jwt_context, None)?;
 
         let user = self.validate(&jwt_context, "gateway").await?;
         Ok((jwt_context, user))
@@ -159,6 +182,17 @@ impl JwtContextExtractor {
 
         let jwt_context = self.extract_jwt_context(&headers)?;
         let user = self.validate(&jwt_context, " (A2A route)").await?;
+        let uri = request
+            .extensions()
+            .get::<OriginalUri>()
+            .map_or_else(|| request.uri().clone(), |original| original.0.clone());
+        let target = RequestTarget {
+            method: request.method(),
+            uri: &uri,
+        };
+        ensure_sender_constrained(&jwt_context, &headers, Some(target), &self.jti_revocation)
+            .await?;
+        ensure_hop_target(&jwt_context, Some(target))?;
 
         let (body_bytes, reconstructed_request) =
             PayloadSource::read_and_reconstruct(request).await?;
@@ -210,6 +244,16 @@ impl ContextExtractor for JwtContextExtractor {
         self.extract_standard(headers).await
     }
 
+    async fn extract_from_parts(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+    ) -> Result<RequestContext, ContextExtractionError> {
+        self.extract_for_target(headers, Some(RequestTarget { method, uri }))
+            .await
+    }
+
     async fn extract_from_request(
         &self,
         request: Request<Body>,
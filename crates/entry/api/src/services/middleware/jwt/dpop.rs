@@ -0,0 +1,97 @@
+//! RFC 9449 sender-constraint check for the JWT context extractor.
+//!
+//! A token carrying `cnf.jkt` is only accepted under the `DPoP` authorization
+//! scheme together with exactly one `DPoP` proof that covers this request
+//! (`htm`, `htu`), hashes this token (`ath`), is signed by the bound key, and
+//! has not been seen before. The `DPoP` scheme with an unbound token is
+//! rejected too, so a downgrade cannot pass unnoticed. Paths that cannot name
+//! the request target fail closed for bound tokens.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use axum::http::{HeaderMap, Method, Uri, header};
+use systemprompt_models::Config;
+use systemprompt_models::execution::context::ContextExtractionError;
+use systemprompt_security::dpop::{DPOP_HEADER, DpopProofCheck, verify_proof};
+use systemprompt_security::{JwtUserContext, TokenExtractor};
+
+use super::revocation::JtiRevocationChecker;
+use crate::services::request_base_url;
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct RequestTarget<'a> {
+    pub method: &'a Method,
+    pub uri: &'a Uri,
+}
+
+pub(super) async fn ensure_sender_constrained(
+    jwt_context: &JwtUserContext,
+    headers: &HeaderMap,
+    target: Option<RequestTarget<'_>>,
+    revocation: &JtiRevocationChecker,
+) -> Result<(), ContextExtractionError> {
+    let dpop_scheme = TokenExtractor::uses_dpop_scheme(headers);
+    let Some(bound_jkt) = jwt_context.dpop_jkt.as_deref() else {
+        if dpop_scheme {
+            return Err(invalid(
+                "DPoP scheme used with a token that is not DPoP-bound",
+            ));
+        }
+        return Ok(());
+    };
+    if !dpop_scheme {
+        return Err(invalid(
+            "DPoP-bound token must be sent with the DPoP scheme",
+        ));
+    }
+    let target =
+        target.ok_or_else(|| invalid("DPoP-bound token cannot be verified on this route"))?;
+
+    let proof = single_proof(headers)?;
+    let token =
+        TokenExtractor::extract_from_authorization(headers).map_err(|e| invalid(&e.to_string()))?;
+    let url = target_url(headers, target.uri)?;
+
+    let verified = verify_proof(
+        proof,
+        &DpopProofCheck {
+            method: target.method.as_str(),
+            url: &url,
+            access_token: Some(&token),
+            now: chrono::Utc::now().timestamp(),
+        },
+    )
+    .map_err(|e| invalid(&e.to_string()))?;
+
+    if verified.jkt != bound_jkt {
+        return Err(invalid("DPoP proof key does not match the token binding"));
+    }
+    revocation.ensure_fresh_dpop_proof(&verified).await
+}
+
+fn single_proof(headers: &HeaderMap) -> Result<&str, ContextExtractionError> {
+    let mut proofs = headers.get_all(DPOP_HEADER).iter();
+    let proof = proofs
+        .next()
+        .ok_or_else(|| invalid("missing DPoP proof header"))?;
+    if proofs.next().is_some() {
+        return Err(invalid("more than one DPoP proof header"));
+    }
+    proof
+        .to_str()
+        .map_err(|_e| invalid("DPoP proof header is not ASCII"))
+}
+
+fn target_url(headers: &HeaderMap, uri: &Uri) -> Result<String, ContextExtractionError> {
+    let config = Config::get().map_err(|_e| invalid("request URL cannot be resolved"))?;
+    let configured = url::Url::parse(&config.api_external_url)
+        .map_err(|_e| invalid("request URL cannot be resolved"))?;
+    let raw_host = headers.get(header::HOST).and_then(|v| v.to_str().ok());
+    let base = request_base_url::resolve(raw_host, &configured);
+    Ok(format!("{}{}", base.as_str(), uri.path()))
+}
+
+fn invalid(reason: &str) -> ContextExtractionError {
+    ContextExtractionError::InvalidDpopProof(reason.to_owned())
+}
@@ -0,0 +1,66 @@
+//! Route confinement for tokens derived for one backend.
+//!
+//! A delegated or forwarded token names its backend in a [`HopTarget`]
+//! audience. An agent's token is accepted on that agent's routes, on the MCP
+//! routes its tools are called through, and on the webhook routes it reports
+//! progress on; an MCP server's token only on that server's routes and the
+//! webhook routes. Paths that cannot name the request target refuse such
+//! tokens, so a backend cannot replay what it was sent anywhere else.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use systemprompt_models::auth::HopTarget;
+use systemprompt_models::execution::context::ContextExtractionError;
+use systemprompt_models::modules::ApiPaths;
+use systemprompt_security::JwtUserContext;
+
+use super::dpop::RequestTarget;
+
+pub(super) fn ensure_hop_target(
+    jwt_context: &JwtUserContext,
+    target: Option<RequestTarget<'_>>,
+) -> Result<(), ContextExtractionError> {
+    let Some(hop) = jwt_context.hop_target.as_ref() else {
+        return Ok(());
+    };
+    let target = target.ok_or_else(|| {
+        ContextExtractionError::InvalidToken(format!(
+            "token for '{}' cannot be used on this route",
+            hop.name()
+        ))
+    })?;
+    if route_allowed(hop, target.uri.path()) {
+        return Ok(());
+    }
+    Err(ContextExtractionError::InvalidToken(format!(
+        "token for '{}' is not accepted on {}",
+        hop.name(),
+        target.uri.path()
+    )))
+}
+
+#[cfg_attr(
+    not(feature = "test-api"),
+    expect(
+        unreachable_pub,
+        reason = "re-exported via `test_api` only when the feature is on"
+    )
+)]
+pub fn route_allowed(hop: &HopTarget, path: &str) -> bool {
+    if is_under(path, ApiPaths::WEBHOOK) {
+        return true;
+    }
+    match hop {
+        HopTarget::Agent(name) => {
+            is_under(path, &format!("{}/{name}", ApiPaths::AGENTS_BASE))
+                || is_under(path, ApiPaths::MCP_BASE)
+        },
+        HopTarget::McpServer(name) => is_under(path, &format!("{}/{name}", ApiPaths::MCP_BASE)),
+    }
+}
+
+fn is_under(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
@@ -2,18 +2,22 @@
 //!
 //! Provides [`JwtContextExtractor`], which validates bearer tokens and derives
 //! a request context, together with the [`JtiRevocationChecker`] it consults to
-//! reject revoked token identifiers.
+//! reject revoked token identifiers and replayed `DPoP` proofs. Tokens derived
+//! for one backend are held to that backend's routes.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 mod context;
+mod dpop;
+mod hop;
 mod params;
 mod revocation;
 mod validation;
 
 #[cfg(feature = "test-api")]
 pub mod test_api {
+    pub use super::hop::route_allowed as hop_route_allowed;
     pub use super::params::{BuildContextParams, build_context, extract_common_headers};
     pub use super::validation::{UserCache, ValidatedUser, user_is_admin, validate_user_exists};
 }
@@ -7,12 +7,16 @@
 //! cached so the hot path costs one map lookup. Fails closed — a revocation
 //! store error rejects the request rather than admitting an unverifiable token.
 //!
+//! The same store backs single-use RFC 9449 `DPoP` proofs: a proof `jti` is
+//! claimed once and any second presentation is rejected.
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 use std::sync::Arc;
 use systemprompt_models::execution::context::ContextExtractionError;
 use systemprompt_oauth::repository::{JtiRevocationCache, OAuthRepository};
+use systemprompt_security::dpop::VerifiedDpopProof;
 
 #[derive(Clone)]
 pub struct JtiRevocationChecker {
@@ -57,4 +61,28 @@ impl JtiRevocationChecker {
             Ok(())
         }
     }
+
+    pub async fn ensure_fresh_dpop_proof(
+        &self,
+        proof: &VerifiedDpopProof,
+    ) -> Result<(), ContextExtractionError> {
+        let exp =
+            chrono::DateTime::from_timestamp(proof.replay_window_end(), 0).ok_or_else(|| {
+                ContextExtractionError::InvalidDpopProof("proof `iat` is out of range".to_owned())
+            })?;
+        let fresh = self
+            .repo
+            .record_dpop_proof(&proof.jkt, &proof.jti, exp)
+            .await
+            .map_err(|e| ContextExtractionError::DatabaseError {
+                message: format!("DPoP proof replay record failed: {e}"),
+            })?;
+        if fresh {
+            Ok(())
+        } else {
+            Err(ContextExtractionError::InvalidDpopProof(
+                "proof `jti` has already been used".to_owned(),
+            ))
+        }
+    }
 }
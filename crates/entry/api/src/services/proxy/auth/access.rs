@@ -4,7 +4,8 @@
 //! caller's bearer token and scopes, and either returns the authenticated user
 //! or converts the failure into an RFC 9728 challenge. For MCP it permits a
 //! session-only fallback when a prior authenticated initialize established the
-//! identity in the proxy cache.
+//! identity in the proxy cache. A DPoP-bound token is only admitted when the
+//! context middleware already verified its proof for this request.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -68,9 +69,9 @@ impl AccessValidator {
                 has_authorization,
             })
         };
-        let authenticated_user =
+        let (authenticated_user, dpop_jkt) =
             match AuthValidator::validate_service_access(headers, service_name, req_context) {
-                Ok(user) => user,
+                Ok(validated) => validated,
                 Err(status_code) => {
                     if let Some(outcome) = mcp_session_fallback(
                         &requirement.module,
@@ -83,6 +84,13 @@ impl AccessValidator {
                     return Err(challenge(status_code));
                 },
             };
+        if dpop_jkt.is_some() && !proof_verified_upstream(headers, req_context) {
+            tracing::warn!(
+                service = %service_name,
+                "DPoP-bound token reached the proxy without a verified proof"
+            );
+            return Err(challenge(StatusCode::UNAUTHORIZED));
+        }
         if let Err(status_code) =
             enforce_required_audience(headers, service_name, &requirement.audience)
         {
@@ -173,6 +181,17 @@ fn enforce_required_audience(
         })
 }
 
+// Why: the JWT context middleware verifies the DPoP proof (and records its
+// `jti`) before it stores the presented token on the request context; a
+// session-fallback context carries a different token, so equality proves the
+// proof check ran for this token without replaying it here.
+fn proof_verified_upstream(headers: &HeaderMap, req_context: Option<&RequestContext>) -> bool {
+    let Ok(presented) = AuthService::extract_bearer_token(headers) else {
+        return false;
+    };
+    req_context.is_some_and(|rc| rc.auth_token().as_str() == presented)
+}
+
 fn resource_path_for(module_name: &str, service_name: &str) -> String {
     match module_name {
         "mcp" => ApiPaths::mcp_server_endpoint(service_name),
@@ -200,7 +219,7 @@ fn mcp_session_fallback(
     let has_bearer_token = headers
         .get("authorization")
         .and_then(|v| v.to_str().ok())
-        .is_some_and(|v| v.starts_with("Bearer "));
+        .is_some_and(|v| v.starts_with("Bearer ") || v.starts_with("DPoP "));
     if has_bearer_token {
         tracing::info!(
             service = %service_name,
@@ -31,8 +31,8 @@ impl AuthValidator {
         headers: &HeaderMap,
         service_name: &str,
         req_context: Option<&RequestContext>,
-    ) -> Result<AuthenticatedUser, StatusCode> {
-        let result = AuthService::authorize_service_access(headers, service_name);
+    ) -> Result<(AuthenticatedUser, Option<String>), StatusCode> {
+        let result = AuthService::authorize_bound_service_access(headers, service_name);
 
         if let Err(status) = &result {
             let trace_id =
@@ -6,6 +6,12 @@
 //! session-identity cache so a session-only follow-up request can be enriched
 //! with the identity established on the authenticated initialize call.
 //!
+//! A `DPoP`-bound caller token, or one already derived for another backend, is
+//! not forwarded as it is: once the context middleware has verified it, the
+//! backend receives a short-lived token without the binding, confined to that
+//! backend and revoked with the caller's token, so it can call its own hops
+//! with it but cannot replay it anywhere else.
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
@@ -24,7 +30,11 @@ use std::sync::Arc;
 use systemprompt_database::ServiceConfig;
 use systemprompt_identifiers::AgentName;
 use systemprompt_models::RequestContext;
+use systemprompt_models::auth::HopTarget;
 use systemprompt_runtime::AppContext;
+use systemprompt_security::dpop::DPOP_HEADER;
+use systemprompt_security::jwt::ForwardTokenParams;
+use systemprompt_security::{JwtService, extract_user_context};
 use tokio::sync::RwLock;
 
 use super::auth::{AccessValidator, build_mcp_unknown_service_challenge};
@@ -135,6 +145,7 @@ impl ProxyEngine {
         let req_context = self
             .build_forward_context(req_ctx, &service, service_name, &request_headers)
             .await?;
+        let req_context = confine_forward_token(req_context, &ctx, service_name, proxy_kind)?;
 
         inject_forward_headers(&mut headers, &req_context, service_name);
 
@@ -249,6 +260,42 @@ fn unknown_service_error(
     err
 }
 
+// Why: the context middleware only stores a `DPoP`-bound token on the request
+// context after verifying this request's proof for it, so a bound token here
+// has been proven. The backend cannot sign proofs for the caller's key, and
+// must not hold anything it could replay elsewhere, so it gets a token for
+// itself alone. A token already derived for one backend is narrowed to the
+// next the same way.
+fn confine_forward_token(
+    req_context: RequestContext,
+    ctx: &AppContext,
+    service_name: &str,
+    proxy_kind: ProxyKind,
+) -> Result<RequestContext, ProxyError> {
+    let token = req_context.auth_token().as_str();
+    let confine = extract_user_context(token)
+        .is_ok_and(|jwt| jwt.dpop_jkt.is_some() || jwt.hop_target.is_some());
+    if !confine {
+        return Ok(req_context);
+    }
+    let target = match proxy_kind {
+        ProxyKind::Agent => HopTarget::Agent(service_name.to_owned()),
+        ProxyKind::Mcp => HopTarget::McpServer(service_name.to_owned()),
+    };
+    let forwarded = JwtService::forward_token(&ForwardTokenParams {
+        token,
+        target: &target,
+        issuer: &ctx.config().jwt_issuer,
+    })
+    .map_err(|e| {
+        tracing::warn!(service = %service_name, error = %e, "Cannot reissue token for backend");
+        ProxyError::AuthenticationRequired {
+            service: service_name.to_owned(),
+        }
+    })?;
+    Ok(req_context.with_auth_token(forwarded.as_str()))
+}
+
 fn inject_forward_headers(
     headers: &mut HeaderMap,
     req_context: &RequestContext,
@@ -257,6 +304,7 @@ fn inject_forward_headers(
     let has_auth_before = headers.get("authorization").is_some();
     let ctx_has_token = !req_context.auth_token().as_str().is_empty();
 
+    headers.remove(DPOP_HEADER);
     HeaderInjector::inject_context(headers, req_context);
 
     let has_auth_after = headers.get("authorization").is_some();
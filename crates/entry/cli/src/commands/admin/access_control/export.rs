@@ -48,6 +48,7 @@ async fn load_grouped_rules(pool: &DbPool) -> Result<BTreeMap<GroupKey, GroupVal
             entity_id: row.entity_id,
             access: row.access,
             justification: row.justification.clone(),
+            enforce: row.enforce,
         };
         let entry = grouped.entry(key).or_default();
         if row.rule_type == "role" {
@@ -84,6 +85,11 @@ fn write_rule(out: &mut String, key: &GroupKey, value: &GroupValue) {
         out.push_str(&yaml_scalar(j));
         out.push('\n');
     }
+    if key.enforce != "enforce" {
+        out.push_str("    enforce: ");
+        out.push_str(&yaml_scalar(&key.enforce));
+        out.push('\n');
+    }
 }
 
 fn write_string_array(out: &mut String, key: &str, items: &[String]) {
@@ -108,6 +114,7 @@ struct GroupKey {
     entity_id: String,
     access: String,
     justification: Option<String>,
+    enforce: String,
 }
 
 #[derive(Debug, Default)]
@@ -9,25 +9,10 @@ use std::collections::BTreeSet;
 use anyhow::{Result, anyhow};
 use systemprompt_runtime::AppContext;
 use systemprompt_security::authz::repository::AccessControlRepository;
-use systemprompt_security::authz::types::EntityKind;
 
-use super::LintArgs;
+use super::{ALL_KINDS, LintArgs};
 use crate::CliConfig;
 
-const ALL_KINDS: &[EntityKind] = &[
-    EntityKind::GatewayRoute,
-    EntityKind::McpServer,
-    EntityKind::Plugin,
-    EntityKind::Agent,
-    EntityKind::Marketplace,
-    EntityKind::Skill,
-    EntityKind::Hook,
-    EntityKind::SlackWorkspace,
-    EntityKind::SlackChannel,
-    EntityKind::TeamsTenant,
-    EntityKind::TeamsConversation,
-];
-
 pub(super) async fn run(_args: LintArgs, _config: &CliConfig) -> Result<(String, bool)> {
     let ctx = AppContext::new().await?;
     let repo =
@@ -1,22 +1,41 @@
 //! `admin access-control` subcommand: inspect and promote live RBAC rules.
 //!
 //! Exposes [`AccessControlCommands`] for exporting the current role rules as a
-//! committable YAML baseline and linting the live access-control tables for
-//! unknown entities or unreachable rules.
+//! committable YAML baseline, linting the live access-control tables for
+//! unknown entities or unreachable rules, and replaying recorded decisions
+//! against a candidate rules file.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 pub mod export;
 mod lint;
+mod simulate;
+
+use std::path::PathBuf;
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
+use systemprompt_security::authz::types::EntityKind;
 
 use crate::context::CommandContext;
 use crate::shared::{CommandOutput, render_result};
 
-#[derive(Debug, Clone, Copy, Subcommand)]
+const ALL_KINDS: &[EntityKind] = &[
+    EntityKind::GatewayRoute,
+    EntityKind::McpServer,
+    EntityKind::Plugin,
+    EntityKind::Agent,
+    EntityKind::Marketplace,
+    EntityKind::Skill,
+    EntityKind::Hook,
+    EntityKind::SlackWorkspace,
+    EntityKind::SlackChannel,
+    EntityKind::TeamsTenant,
+    EntityKind::TeamsConversation,
+];
+
+#[derive(Debug, Clone, Subcommand)]
 pub enum AccessControlCommands {
     #[command(
         about = "Print current role rules as a YAML snippet for promotion to the committed \
@@ -29,6 +48,17 @@ pub enum AccessControlCommands {
                  exits non-zero on findings"
     )]
     Lint(LintArgs),
+
+    #[command(
+        about = "Replay recorded access-control decisions against a candidate rules file and \
+                 report which calls would flip between allow and deny",
+        long_about = "Replay recorded access-control decisions against a candidate rules file and \
+                      report which calls would flip between allow and deny.\n\nOnly the user, \
+                      their roles and the entity are replayed. Rules on extension subject \
+                      dimensions never match, and rows that recorded no roles for the caller \
+                      are skipped and counted rather than replayed."
+    )]
+    Simulate(SimulateArgs),
 }
 
 #[derive(Debug, Clone, Copy, Args)]
@@ -37,6 +67,42 @@ pub struct ExportYamlArgs;
 #[derive(Debug, Clone, Copy, Args)]
 pub struct LintArgs;
 
+#[derive(Debug, Clone, Args)]
+pub struct SimulateArgs {
+    #[arg(long, help = "Candidate access-control rules file (YAML)")]
+    pub rules: PathBuf,
+
+    #[arg(
+        long,
+        default_value = "24h",
+        help = "Replay decisions recorded since (e.g. '24h', '7d', '2026-01-13')"
+    )]
+    pub since: String,
+
+    #[arg(
+        long,
+        help = "Replay decisions recorded before (e.g. '1h', '2026-01-13')"
+    )]
+    pub until: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "10000",
+        help = "Maximum recorded decisions to replay"
+    )]
+    pub limit: i64,
+
+    #[arg(
+        long,
+        help = "Treat the file's role rules as replacing the live ones, as ingestion with \
+                delete_orphans does"
+    )]
+    pub delete_orphans: bool,
+
+    #[arg(long, default_value = "50", help = "Maximum flipped calls to list")]
+    pub show: usize,
+}
+
 pub async fn execute(cmd: AccessControlCommands, ctx: &CommandContext) -> Result<()> {
     match cmd {
         AccessControlCommands::ExportYaml(args) => {
@@ -53,5 +119,11 @@ pub async fn execute(cmd: AccessControlCommands, ctx: &CommandContext) -> Result
             }
             Ok(())
         },
+        AccessControlCommands::Simulate(args) => {
+            let text = simulate::run(args, &ctx.cli).await?;
+            let result = CommandOutput::text_titled("Access-control simulation", text);
+            render_result(&result, &ctx.cli);
+            Ok(())
+        },
     }
 }
@@ -0,0 +1,121 @@
+//! `admin access-control simulate` command: replays recorded decisions
+//! against a candidate rules file.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result, anyhow};
+use chrono::Utc;
+use systemprompt_runtime::AppContext;
+use systemprompt_security::authz::repository::AccessControlRepository;
+use systemprompt_security::authz::types::{Decision, DecisionTag};
+use systemprompt_security::authz::{
+    AccessControlConfig, AccessControlSimulation, GovernanceDecisionRepository, LiveAccessControl,
+    REPLAYABLE_POLICY, RecordedDecision, SimulationReport,
+};
+
+use super::{ALL_KINDS, SimulateArgs};
+use crate::CliConfig;
+use crate::commands::analytics::shared::{parse_since, parse_until};
+
+pub(super) async fn run(args: SimulateArgs, _config: &CliConfig) -> Result<String> {
+    let raw = std::fs::read_to_string(&args.rules)
+        .with_context(|| format!("read {}", args.rules.display()))?;
+    let candidate: AccessControlConfig = serde_yaml::from_str(&raw)
+        .with_context(|| format!("parse {} as access-control rules", args.rules.display()))?;
+
+    let since = parse_since(Some(&args.since))?.ok_or_else(|| anyhow!("--since is required"))?;
+    let until = parse_until(args.until.as_ref())?.unwrap_or_else(Utc::now);
+
+    let ctx = AppContext::new().await?;
+    let pool = ctx.db_pool().pool_arc()?;
+    let rows = GovernanceDecisionRepository::from_pool(pool)
+        .list_window(REPLAYABLE_POLICY, since, until, args.limit)
+        .await
+        .map_err(|e| anyhow!("list recorded decisions: {e}"))?;
+    let recorded: Vec<RecordedDecision> =
+        rows.iter().filter_map(RecordedDecision::from_row).collect();
+    let skipped = rows.len() - recorded.len();
+
+    let repo =
+        AccessControlRepository::new(ctx.db_pool()).map_err(|e| anyhow!("acquire repo: {e}"))?;
+    let mut live = LiveAccessControl::default();
+    for kind in ALL_KINDS {
+        live.entities.extend(
+            repo.list_entities(*kind)
+                .await
+                .map_err(|e| anyhow!("list_entities({kind}): {e}"))?,
+        );
+        let ids: Vec<String> = recorded
+            .iter()
+            .filter(|r| r.entity.kind() == *kind)
+            .map(|r| r.entity.id_str().to_owned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let rules = repo
+            .list_rules_bulk(*kind, &ids)
+            .await
+            .map_err(|e| anyhow!("list rules ({kind}): {e}"))?;
+        live.rules
+            .extend(rules.into_iter().map(|(id, rules)| ((*kind, id), rules)));
+    }
+
+    let simulation = AccessControlSimulation::new(live, &candidate, args.delete_orphans)
+        .map_err(|e| anyhow!("candidate rules: {e}"))?;
+    let report = simulation.replay(&recorded);
+
+    Ok(render(&report, skipped, args.show))
+}
+
+fn render(report: &SimulationReport, skipped: usize, show: usize) -> String {
+    let mut out = format!(
+        "{} replayed, {} unchanged, {} allow→deny, {} deny→allow\n",
+        report.replayed,
+        report.unchanged,
+        report.allow_to_deny(),
+        report.deny_to_allow(),
+    );
+    if skipped > 0 {
+        out.push_str(&format!(
+            "{skipped} recorded row(s) skipped: no entity or roles in the audit blob\n"
+        ));
+    }
+    if report.without_roles > 0 {
+        out.push_str(&format!(
+            "{} recorded row(s) skipped: no roles were recorded for the caller\n",
+            report.without_roles
+        ));
+    }
+    if report.drifted > 0 {
+        out.push_str(&format!(
+            "{} row(s) already decide differently under the live rules; their flips are not \
+             the candidate's alone\n",
+            report.drifted
+        ));
+    }
+
+    for flip in report.flips.iter().take(show) {
+        let arrow = match flip.recorded.decision {
+            DecisionTag::Allow => "allow→deny",
+            DecisionTag::Deny => "deny→allow",
+        };
+        let why = match &flip.candidate {
+            Decision::Deny { reason } => reason.to_string(),
+            Decision::Allow { matched_by } => format!("{matched_by:?}"),
+        };
+        out.push_str(&format!(
+            "  {arrow}  {}  {} on {}  ({why})  [{}]\n",
+            flip.recorded.created_at.format("%Y-%m-%d %H:%M:%S"),
+            flip.recorded.user_id,
+            flip.recorded.entity,
+            flip.recorded.id,
+        ));
+    }
+    if report.flips.len() > show {
+        out.push_str(&format!("  … {} more\n", report.flips.len() - show));
+    }
+    out
+}
@@ -29,6 +29,7 @@ pub mod shared;
 pub mod show;
 mod status;
 mod task;
+mod task_list;
 mod tools;
 mod tools_mcp;
 pub mod validate;
@@ -71,7 +72,7 @@ pub enum AgentsCommands {
     #[command(about = "Send a message to an agent via A2A protocol")]
     Message(message::MessageArgs),
 
-    #[command(about = "Get task details and response from an agent")]
+    #[command(about = "Get task details from an agent, or list its tasks")]
     Task(task::TaskArgs),
 
     #[command(about = "List MCP tools available to an agent")]
@@ -15,6 +15,7 @@ use systemprompt_loader::ConfigLoader;
 use systemprompt_mcp::McpToolProvider;
 use systemprompt_oauth::JwtValidationProviderImpl;
 use systemprompt_runtime::AppContext;
+use systemprompt_traits::AppContext as AppContextTrait;
 
 #[derive(Debug, Clone, Args)]
 pub struct RunArgs {
@@ -39,12 +40,16 @@ pub(super) async fn execute(args: RunArgs) -> Result<()> {
         JwtValidationProviderImpl::from_config().context("Failed to create JWT provider")?,
     );
 
-    let agent_state = Arc::new(AgentState::new(
+    let mut agent_state = AgentState::new(
         Arc::clone(&db_pool),
         Arc::new(ctx.config().clone()),
         jwt_provider,
         Arc::clone(ctx.a2a_repositories()),
-    ));
+    );
+    if let Some(users) = AppContextTrait::user_provider(&ctx) {
+        agent_state = agent_state.with_user_provider(users);
+    }
+    let agent_state = Arc::new(agent_state);
 
     let tool_provider = Arc::new(McpToolProvider::new(
         Arc::clone(&db_pool),
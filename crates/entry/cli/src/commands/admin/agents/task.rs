@@ -1,23 +1,29 @@
-//! `admin agents task` command inspecting a task.
+//! `admin agents task` command inspecting a task, or listing tasks with
+//! `admin agents task list`.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 use anyhow::{Result, anyhow};
-use clap::Args;
+use clap::{Args, Subcommand};
 use systemprompt_agent::models::a2a::jsonrpc::{JSON_RPC_VERSION_2_0, Request, RequestId};
 use systemprompt_agent::models::a2a::protocol::TaskQueryParams;
 use systemprompt_identifiers::TaskId;
 use systemprompt_models::a2a::{Task, methods};
 
 use super::client::{A2aCall, ensure_agent_exists, send_a2a_request};
+use super::task_list::{self, TaskListArgs};
 use crate::context::CommandContext;
 use crate::interactive::resolve_required;
 use crate::session::get_or_create_session;
 use crate::shared::CommandOutput;
 
 #[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct TaskArgs {
+    #[command(subcommand)]
+    pub command: Option<TaskCommands>,
+
     #[arg(help = "Agent name that processed the task")]
     pub agent: Option<String>,
 
@@ -40,7 +46,17 @@ pub struct TaskArgs {
     pub timeout: u64,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum TaskCommands {
+    #[command(about = "List an agent's tasks, newest first")]
+    List(TaskListArgs),
+}
+
 pub(super) async fn execute(args: TaskArgs, ctx: &CommandContext) -> Result<CommandOutput> {
+    if let Some(TaskCommands::List(list_args)) = args.command {
+        return task_list::execute(list_args, ctx).await;
+    }
+
     let config = &ctx.cli;
     let session_ctx = get_or_create_session(ctx).await?;
 
@@ -0,0 +1,138 @@
+//! `admin agents task list` command paging through an agent's tasks.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use anyhow::{Result, anyhow};
+use clap::Args;
+use systemprompt_agent::models::a2a::jsonrpc::{JSON_RPC_VERSION_2_0, Request, RequestId};
+use systemprompt_agent::models::a2a::protocol::{ListTasksParams, ListTasksResult};
+use systemprompt_agent::repository::task::task_state_to_db_string;
+use systemprompt_identifiers::{ContextId, TaskId};
+use systemprompt_models::a2a::{TaskState, methods};
+
+use super::client::{A2aCall, ensure_agent_exists, send_a2a_request};
+use super::types::TaskListRow;
+use crate::commands::analytics::shared::{parse_since, parse_until};
+use crate::context::CommandContext;
+use crate::interactive::resolve_required;
+use crate::session::get_or_create_session;
+use crate::shared::CommandOutput;
+
+#[derive(Debug, Args)]
+pub struct TaskListArgs {
+    #[arg(help = "Agent name whose tasks to list")]
+    pub agent: Option<String>,
+
+    #[arg(long, help = "Only tasks in this context")]
+    pub context_id: Option<String>,
+
+    #[arg(
+        long,
+        help = "Only tasks in this state (e.g. working, completed, input-required)"
+    )]
+    pub state: Option<TaskState>,
+
+    #[arg(
+        long,
+        help = "Only tasks created since (e.g. '24h', '7d', '2026-01-13')"
+    )]
+    pub since: Option<String>,
+
+    #[arg(long, help = "Only tasks created before (e.g. '1h', '2026-01-13')")]
+    pub until: Option<String>,
+
+    #[arg(long, help = "Tasks per page (server default 50, maximum 100)")]
+    pub limit: Option<u32>,
+
+    #[arg(
+        long,
+        help = "Continue after this task (the previous page's next token)"
+    )]
+    pub page_token: Option<String>,
+
+    #[arg(long, help = "Gateway URL (overrides profile's api_external_url)")]
+    pub url: Option<String>,
+
+    #[arg(
+        long,
+        help = "Bearer token override (defaults to the active CLI session token)"
+    )]
+    pub token: Option<String>,
+
+    #[arg(long, default_value = "30", help = "Timeout in seconds")]
+    pub timeout: u64,
+}
+
+pub(super) async fn execute(args: TaskListArgs, ctx: &CommandContext) -> Result<CommandOutput> {
+    let config = &ctx.cli;
+    let session_ctx = get_or_create_session(ctx).await?;
+
+    let agent = resolve_required(args.agent, "agent", config, || {
+        Err(anyhow!("Agent name is required"))
+    })?;
+
+    ensure_agent_exists(&agent)?;
+
+    let params = ListTasksParams {
+        context_id: args.context_id.map(ContextId::try_new).transpose()?,
+        status: args.state,
+        created_after: parse_since(args.since.as_ref())?,
+        created_before: parse_until(args.until.as_ref())?,
+        page_size: args.limit,
+        page_token: args.page_token.map(TaskId::new),
+    };
+
+    let base_url = args.url.as_deref().unwrap_or_else(|| session_ctx.api_url());
+    let agent_url = format!("{}/api/v1/agents/{}", base_url.trim_end_matches('/'), agent);
+
+    let auth_token = args
+        .token
+        .as_deref()
+        .unwrap_or_else(|| session_ctx.session_token().as_str());
+
+    let request = Request {
+        jsonrpc: JSON_RPC_VERSION_2_0.to_owned(),
+        method: methods::LIST_TASKS.to_owned(),
+        params,
+        id: RequestId::String(uuid::Uuid::new_v4().to_string()),
+    };
+
+    let page: ListTasksResult = send_a2a_request(A2aCall {
+        agent: &agent,
+        agent_url: &agent_url,
+        auth_token,
+        request: &request,
+        timeout: args.timeout,
+    })
+    .await?;
+
+    let rows: Vec<TaskListRow> = page
+        .tasks
+        .iter()
+        .map(|task| TaskListRow {
+            task_id: task.id.to_string(),
+            context_id: task.context_id.to_string(),
+            state: task_state_to_db_string(task.status.state).to_owned(),
+            created_at: task.created_at.map(|t| t.to_rfc3339()),
+            last_modified: task.last_modified.map(|t| t.to_rfc3339()),
+        })
+        .collect();
+
+    let title = page.next_page_token.map_or_else(
+        || format!("Tasks ({})", rows.len()),
+        |token| format!("Tasks ({}) — next page: --page-token {token}", rows.len()),
+    );
+
+    Ok(CommandOutput::table_of(
+        vec![
+            "task_id",
+            "context_id",
+            "state",
+            "created_at",
+            "last_modified",
+        ],
+        &rows,
+    )
+    .with_title(title))
+}
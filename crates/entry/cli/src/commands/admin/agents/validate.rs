@@ -172,7 +172,7 @@ pub fn check_provider(
                 .secrets
                 .and_then(|s| s.get(secret_name))
                 .is_some_and(|k| !k.is_empty());
-            if !key_present {
+            if !key_present && !entry.is_local() {
                 errors.push(ValidationIssue {
                     source: name.to_owned(),
                     message: format!(
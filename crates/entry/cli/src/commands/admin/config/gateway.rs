@@ -149,6 +149,9 @@ pub fn add_route(profile: &mut Profile, args: &RouteAddArgs) -> Result<String> {
         extra_headers: HashMap::new(),
         pricing: None,
         when: None,
+        weight: 1,
+        targets: Vec::new(),
+        fallback: None,
     };
     route.ensure_id();
     let spec = spec_mut(profile)?;
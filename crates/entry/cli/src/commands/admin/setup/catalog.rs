@@ -66,6 +66,9 @@ pub fn build_routes(secrets: &SecretsData) -> Vec<GatewayRoute> {
                 extra_headers: HashMap::new(),
                 pricing: None,
                 when: None,
+                weight: 1,
+                targets: Vec::new(),
+                fallback: None,
             };
             route.ensure_id();
             route
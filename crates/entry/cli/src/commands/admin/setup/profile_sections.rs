@@ -90,6 +90,7 @@ pub(super) fn security(api_external_url: &str) -> SecurityConfig {
         signing_key_path: std::path::PathBuf::from("signing_key.pem"),
         trusted_issuers: Vec::new(),
         id_jag_ttl_secs: systemprompt_models::profile::DEFAULT_ID_JAG_TTL_SECS,
+        oidc_providers: Vec::new(),
     }
 }
 
@@ -58,6 +58,7 @@ async fn execute_internal(
             name: row.name,
             task_count: row.task_count,
             message_count: row.message_count,
+            summary_count: row.summary_count,
             created_at: row.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
             updated_at: row.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
         })
@@ -73,7 +74,13 @@ async fn execute_internal(
         export_to_csv(&output.conversations, &resolved_path)?;
         CliService::success(&format!("Exported to {}", resolved_path.display()));
         return Ok(CommandOutput::table_of(
-            vec!["context_id", "name", "task_count", "message_count"],
+            vec![
+                "context_id",
+                "name",
+                "task_count",
+                "message_count",
+                "summary_count",
+            ],
             &output.conversations,
         )
         .with_skip_render());
@@ -82,14 +89,26 @@ async fn execute_internal(
     if output.conversations.is_empty() {
         CliService::warning("No conversations found");
         return Ok(CommandOutput::table_of(
-            vec!["context_id", "name", "task_count", "message_count"],
+            vec![
+                "context_id",
+                "name",
+                "task_count",
+                "message_count",
+                "summary_count",
+            ],
             &output.conversations,
         )
         .with_skip_render());
     }
 
     Ok(CommandOutput::table_of(
-        vec!["context_id", "name", "task_count", "message_count"],
+        vec![
+            "context_id",
+            "name",
+            "task_count",
+            "message_count",
+            "summary_count",
+        ],
         &output.conversations,
     )
     .with_title("Conversations"))
@@ -1,15 +1,17 @@
-//! Conversation analytics: aggregate stats, time-series trends, and listings.
+//! Conversation analytics: aggregate stats, time-series trends, listings, and
+//! the history summaries stored for a context.
 //!
 //! Defines the [`ConversationsCommands`] subcommand tree and the typed output
 //! shapes ([`ConversationStatsOutput`], [`ConversationTrendsOutput`],
-//! [`ConversationListOutput`]) rendered by the `analytics conversations`
-//! commands.
+//! [`ConversationListOutput`], [`ConversationSummariesOutput`]) rendered by
+//! the `analytics conversations` commands.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 mod list;
 mod stats;
+mod summaries;
 mod trends;
 
 use anyhow::Result;
@@ -30,6 +32,9 @@ pub enum ConversationsCommands {
 
     #[command(about = "List conversations")]
     List(list::ListArgs),
+
+    #[command(about = "Show the history summaries stored for a conversation")]
+    Summaries(summaries::SummariesArgs),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -64,6 +69,7 @@ pub struct ConversationListRow {
     pub name: Option<String>,
     pub task_count: i64,
     pub message_count: i64,
+    pub summary_count: i64,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -74,6 +80,22 @@ pub struct ConversationListOutput {
     pub total: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConversationSummaryRow {
+    pub agent_name: String,
+    pub covered_messages: i32,
+    pub token_estimate: i32,
+    pub summary: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConversationSummariesOutput {
+    #[serde(rename = "context_id")]
+    pub context: String,
+    pub summaries: Vec<ConversationSummaryRow>,
+}
+
 pub async fn execute(command: ConversationsCommands, ctx: &CommandContext) -> Result<()> {
     let db_ctx = ctx.database().await?;
     match command {
@@ -92,5 +114,10 @@ pub async fn execute(command: ConversationsCommands, ctx: &CommandContext) -> Re
             render_result(&result, &ctx.cli);
             Ok(())
         },
+        ConversationsCommands::Summaries(args) => {
+            let result = summaries::execute_with_pool(args, &db_ctx, &ctx.cli).await?;
+            render_result(&result, &ctx.cli);
+            Ok(())
+        },
     }
 }
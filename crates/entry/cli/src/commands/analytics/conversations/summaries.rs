@@ -0,0 +1,66 @@
+//! `analytics conversations summaries` command.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use anyhow::Result;
+use clap::Args;
+use systemprompt_analytics::ConversationAnalyticsRepository;
+use systemprompt_identifiers::ContextId;
+use systemprompt_logging::CliService;
+use systemprompt_runtime::DatabaseContext;
+
+use super::{ConversationSummariesOutput, ConversationSummaryRow};
+use crate::CliConfig;
+use crate::shared::CommandOutput;
+
+#[derive(Debug, Args)]
+pub struct SummariesArgs {
+    #[arg(help = "Context ID of the conversation")]
+    pub context_id: String,
+}
+
+pub(super) async fn execute_with_pool(
+    args: SummariesArgs,
+    db_ctx: &DatabaseContext,
+    _config: &CliConfig,
+) -> Result<CommandOutput> {
+    let repo = ConversationAnalyticsRepository::new(db_ctx.db_pool())?;
+    execute_internal(args, &repo).await
+}
+
+async fn execute_internal(
+    args: SummariesArgs,
+    repo: &ConversationAnalyticsRepository,
+) -> Result<CommandOutput> {
+    let context_id = ContextId::new(&args.context_id);
+    let rows = repo.list_context_summaries(&context_id).await?;
+
+    let output = ConversationSummariesOutput {
+        context: args.context_id,
+        summaries: rows
+            .into_iter()
+            .map(|row| ConversationSummaryRow {
+                agent_name: row.agent_name,
+                covered_messages: row.covered_messages,
+                token_estimate: row.token_estimate,
+                summary: row.summary,
+                created_at: row.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            })
+            .collect(),
+    };
+
+    let columns = vec![
+        "created_at",
+        "agent_name",
+        "covered_messages",
+        "token_estimate",
+        "summary",
+    ];
+    if output.summaries.is_empty() {
+        CliService::warning("No summaries stored for this conversation");
+        return Ok(CommandOutput::table_of(columns, &output.summaries).with_skip_render());
+    }
+
+    Ok(CommandOutput::table_of(columns, &output.summaries).with_title("Conversation Summaries"))
+}
@@ -0,0 +1,95 @@
+//! `analytics costs budgets` command: subjects approaching their gateway cost
+//! ceilings in the current quota windows.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use anyhow::Result;
+use chrono::Utc;
+use clap::Args;
+use systemprompt_ai::repository::{AiGatewayPolicyRepository, AiQuotaBucketRepository};
+use systemprompt_api::services::gateway::policy::PolicyResolver;
+use systemprompt_api::services::gateway::quota::align_window;
+use systemprompt_logging::CliService;
+use systemprompt_runtime::DatabaseContext;
+
+use super::{BudgetStatusItem, BudgetStatusOutput};
+use crate::CliConfig;
+use crate::shared::CommandOutput;
+
+#[derive(Debug, Clone, Copy, Args)]
+pub struct BudgetsArgs {
+    #[arg(
+        long,
+        default_value = "50",
+        help = "List subjects that have spent at least this percentage of their budget"
+    )]
+    pub min_percent: u8,
+}
+
+pub(super) async fn execute_with_pool(
+    args: BudgetsArgs,
+    db_ctx: &DatabaseContext,
+    _config: &CliConfig,
+) -> Result<CommandOutput> {
+    let policies =
+        PolicyResolver::from_repository(AiGatewayPolicyRepository::new(db_ctx.db_pool())?);
+    let buckets = AiQuotaBucketRepository::new(db_ctx.db_pool())?;
+    let spec = policies.resolve().await;
+
+    let now = Utc::now();
+    let mut items = Vec::new();
+    for window in &spec.quota_windows {
+        let Some(limit) = window.max_cost_microdollars.filter(|max| *max > 0) else {
+            continue;
+        };
+        let window_start = align_window(now, window.window_seconds);
+        let min_cost = (i128::from(limit) * i128::from(args.min_percent) + 99) / 100;
+        let rows = buckets
+            .list_by_spend(
+                &window.subject,
+                window.window_seconds,
+                window_start,
+                i64::try_from(min_cost).unwrap_or(i64::MAX),
+            )
+            .await?;
+        items.extend(rows.into_iter().map(|row| BudgetStatusItem {
+            subject_kind: window.subject.clone(),
+            subject_id: row.subject_id,
+            window_seconds: window.window_seconds,
+            window_start: window_start.format("%Y-%m-%d %H:%M").to_string(),
+            spent_microdollars: row.cost_microdollars,
+            limit_microdollars: limit,
+            percentage: (row.cost_microdollars as f64 / limit as f64) * 100.0,
+        }));
+    }
+    items.sort_by(|a, b| b.percentage.total_cmp(&a.percentage));
+
+    let output = BudgetStatusOutput {
+        min_percent: args.min_percent,
+        items,
+    };
+    if output.items.is_empty() {
+        CliService::warning(&format!(
+            "No subject has spent {}% of a cost budget in the current windows",
+            output.min_percent
+        ));
+        return Ok(budgets_table(&output.items).with_skip_render());
+    }
+    Ok(budgets_table(&output.items).with_title("Budgets"))
+}
+
+fn budgets_table(items: &[BudgetStatusItem]) -> CommandOutput {
+    CommandOutput::table_of(
+        vec![
+            "subject_kind",
+            "subject_id",
+            "window_seconds",
+            "window_start",
+            "spent_microdollars",
+            "limit_microdollars",
+            "percentage",
+        ],
+        items,
+    )
+}
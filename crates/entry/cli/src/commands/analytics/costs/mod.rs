@@ -1,14 +1,15 @@
-//! Cost analytics: spend summary, trends over time, and breakdown by model or
-//! agent.
+//! Cost analytics: spend summary, trends over time, breakdown by model or
+//! agent, and subjects approaching their gateway cost budgets.
 //!
 //! Defines the [`CostsCommands`] subcommand tree and the typed output shapes
-//! ([`CostSummaryOutput`], [`CostTrendsOutput`], [`CostBreakdownOutput`])
-//! rendered by the `analytics costs` commands.
+//! ([`CostSummaryOutput`], [`CostTrendsOutput`], [`CostBreakdownOutput`],
+//! [`BudgetStatusOutput`]) rendered by the `analytics costs` commands.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 mod breakdown;
+mod budgets;
 pub mod summary;
 mod trends;
 
@@ -44,6 +45,9 @@ pub enum CostsCommands {
 
     #[command(about = "Cost breakdown by model/agent")]
     Breakdown(breakdown::BreakdownArgs),
+
+    #[command(about = "Subjects approaching their gateway cost budgets")]
+    Budgets(budgets::BudgetsArgs),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -90,6 +94,23 @@ pub struct CostBreakdownOutput {
     pub total_cost_microdollars: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BudgetStatusItem {
+    pub subject_kind: String,
+    pub subject_id: String,
+    pub window_seconds: i32,
+    pub window_start: String,
+    pub spent_microdollars: i64,
+    pub limit_microdollars: i64,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BudgetStatusOutput {
+    pub min_percent: u8,
+    pub items: Vec<BudgetStatusItem>,
+}
+
 pub async fn execute(args: CostsArgs, ctx: &CommandContext) -> Result<()> {
     let db_ctx = ctx.database().await?;
     match args.cmd.unwrap_or(CostsCommands::Summary(args.summary)) {
@@ -108,5 +129,10 @@ pub async fn execute(args: CostsArgs, ctx: &CommandContext) -> Result<()> {
             render_result(&result, &ctx.cli);
             Ok(())
         },
+        CostsCommands::Budgets(args) => {
+            let result = budgets::execute_with_pool(args, &db_ctx, &ctx.cli).await?;
+            render_result(&result, &ctx.cli);
+            Ok(())
+        },
     }
 }
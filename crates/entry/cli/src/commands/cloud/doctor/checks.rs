@@ -121,7 +121,9 @@ pub fn check_provider_secrets<S: BuildHasher>(
         .providers
         .providers
         .iter()
-        .filter(|provider| !secret_present(secrets, provider.api_key_secret.as_str()))
+        .filter(|provider| {
+            !provider.is_local() && !secret_present(secrets, provider.api_key_secret.as_str())
+        })
         .map(|provider| {
             format!(
                 "{} (needs `{}`)",
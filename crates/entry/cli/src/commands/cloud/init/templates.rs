@@ -88,7 +88,7 @@ pub fn ai_config(default_provider: &str) -> String {
     let default_model = |provider: &str| -> String {
         seed.as_ref()
             .and_then(|registry| registry.find_provider(provider))
-            .and_then(|entry| entry.models.first())
+            .and_then(|entry| entry.all_models().next())
             .map(|model| model.id.as_str().to_owned())
             .unwrap_or_default()
     };
@@ -168,7 +168,7 @@ pub(super) async fn execute(
     let mcp_statuses = mcp_service_statuses(&ctx).await?;
     let mcp_health: HashMap<String, HealthStatus> = mcp_statuses
         .iter()
-        .filter(|s| s.server_type == McpServerType::Internal)
+        .filter(|s| s.server_type.is_managed())
         .map(|s| (s.name.clone(), s.health))
         .collect();
     let external: Vec<ServiceStatusRow> = mcp_statuses
@@ -0,0 +1,47 @@
+//! `plugins mcp bridge` command hosting one stdio MCP server over HTTP.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use std::sync::Arc;
+
+use systemprompt_mcp::services::stdio_bridge::{StdioBridgeParams, run_stdio_bridge};
+use systemprompt_runtime::AppContext;
+
+#[derive(Debug, Clone, Args)]
+pub struct BridgeArgs {
+    #[arg(long, help = "Stdio MCP server to bridge")]
+    pub server_name: String,
+
+    #[arg(long, help = "Port to listen on")]
+    pub port: u16,
+}
+
+pub(super) async fn execute(args: BridgeArgs) -> Result<()> {
+    let ctx = AppContext::new()
+        .await
+        .context("Failed to bootstrap AppContext for MCP bridge subprocess")?;
+
+    let mut config = ctx
+        .mcp_registry()
+        .get_server(&args.server_name)
+        .context("Failed to resolve MCP server")?;
+    if !config.is_stdio() {
+        bail!(
+            "MCP server '{}' is {}, not stdio",
+            config.name,
+            config.server_type.as_str()
+        );
+    }
+    config.port = args.port;
+
+    run_stdio_bridge(StdioBridgeParams {
+        config,
+        session_repository: Arc::clone(ctx.mcp_session_repository()),
+        authz_hook: Arc::clone(ctx.authz_hook()),
+    })
+    .await
+    .context("Failed to run stdio MCP bridge")
+}
@@ -98,6 +98,27 @@ pub fn summarize_server(
         };
     }
 
+    if server.server_type == McpServerType::Stdio {
+        return McpServerSummary {
+            name: name.to_owned(),
+            display_name: name.to_owned(),
+            server_type: McpServerType::Stdio.as_str().to_owned(),
+            port: server.port,
+            enabled: server.enabled,
+            status: Some(if server.enabled {
+                "bridged".to_owned()
+            } else {
+                "disabled".to_owned()
+            }),
+            endpoint: None,
+            binary_debug: None,
+            binary_release: None,
+            debug_created_at: None,
+            release_created_at: None,
+            created_at: None,
+        };
+    }
+
     let binary_name = if server.binary.is_empty() {
         name.to_owned()
     } else {
@@ -3,6 +3,7 @@
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
+mod bridge;
 pub mod call;
 pub mod call_client;
 pub mod list;
@@ -45,6 +46,9 @@ pub enum McpCommands {
 
     #[command(about = "Invoke a tool on an MCP server")]
     Call(call::CallArgs),
+
+    #[command(about = "Serve a stdio MCP server over HTTP (spawned by the orchestrator)")]
+    Bridge(bridge::BridgeArgs),
 }
 
 pub async fn execute(command: McpCommands, ctx: &CommandContext) -> Result<()> {
@@ -97,5 +101,8 @@ pub async fn execute(command: McpCommands, ctx: &CommandContext) -> Result<()> {
             render_result(&result, config);
             Ok(())
         },
+        McpCommands::Bridge(args) => bridge::execute(args)
+            .await
+            .context("Failed to bridge stdio MCP server"),
     }
 }
@@ -139,6 +139,23 @@ fn collect_status_entries(
                 ),
                 debug_binary: binary_display(bin_path, "debug", &deployment.binary, args.detailed),
             },
+            McpServerType::Stdio => McpStatusEntry {
+                name: name.clone(),
+                server_type: McpServerType::Stdio.as_str().to_owned(),
+                port: deployment.port,
+                enabled: deployment.enabled,
+                running,
+                health,
+                pid,
+                endpoint: None,
+                binary: deployment
+                    .stdio
+                    .as_ref()
+                    .map(|stdio| stdio.command.clone())
+                    .unwrap_or_default(),
+                release_binary: None,
+                debug_binary: None,
+            },
         };
 
         servers.push(entry);
@@ -69,6 +69,7 @@ fn security_config(issuer: &str, trusted_issuers: Vec<TrustedIssuer>) -> Securit
         signing_key_path: std::path::PathBuf::from("signing_key.pem"),
         trusted_issuers,
         id_jag_ttl_secs: systemprompt_models::profile::DEFAULT_ID_JAG_TTL_SECS,
+        oidc_providers: Vec::new(),
     }
 }
 
@@ -14,7 +14,7 @@ use std::collections::HashMap;
 
 use systemprompt_identifiers::{ModelId, ProviderId, SecretName};
 use systemprompt_models::profile::{
-    ApiSurface, ProviderEntry, ProviderModel, ProviderRegistry, WireProtocol,
+    ApiSurface, DiscoveredModels, ProviderEntry, ProviderModel, ProviderRegistry, WireProtocol,
 };
 use systemprompt_models::services::ai::{ModelCapabilities, ModelLimits, ModelPricing};
 
@@ -58,6 +58,8 @@ impl ProviderCatalogService {
             api_key_secret: spec.api_key_secret,
             extra_headers: spec.extra_headers,
             models,
+            local: None,
+            discovered: DiscoveredModels::default(),
         });
     }
 
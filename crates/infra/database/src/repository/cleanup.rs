@@ -96,6 +96,25 @@ impl CleanupRepository {
         Ok(result.rows_affected())
     }
 
+    pub async fn delete_expired_oauth_device_codes(&self) -> DatabaseResult<u64> {
+        let result = sqlx::query!(
+            "DELETE FROM oauth_device_codes WHERE expires_at < NOW() OR consumed_at IS NOT NULL"
+        )
+        .execute(&self.write_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_expired_oauth_federated_logins(&self) -> DatabaseResult<u64> {
+        let result = sqlx::query!(
+            "DELETE FROM oauth_federated_logins
+             WHERE expires_at < NOW() OR consumed_at IS NOT NULL"
+        )
+        .execute(&self.write_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn delete_expired_id_jag_replays(&self) -> DatabaseResult<u64> {
         let result = sqlx::query!("DELETE FROM id_jag_replay WHERE expires_at < NOW()")
             .execute(&self.write_pool)
@@ -99,6 +99,13 @@ impl CircuitBreaker {
         }
     }
 
+    /// Give back a slot taken by [`Self::acquire`] for a call that was never
+    /// made. Health is unchanged: nothing was learned about the dependency.
+    pub fn release(&self) {
+        let mut state = self.lock();
+        state.probes_in_flight = state.probes_in_flight.saturating_sub(1);
+    }
+
     #[must_use]
     pub fn is_open(&self) -> bool {
         self.lock().mode == Mode::Open
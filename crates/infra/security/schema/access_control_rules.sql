@@ -19,6 +19,12 @@ CREATE TABLE IF NOT EXISTS access_control_rules (
     -- access matrix tooltip and copied into governance_decisions.evaluated_rules
     -- when a rule decides. NULL is distinct from empty string.
     justification TEXT,
+    -- 'shadow' rules are evaluated beside the enforced set and their would-be
+    -- outcome is audited, but they never change a decision. Promoting a rule
+    -- is an UPDATE to 'enforce'.
+    enforce TEXT NOT NULL DEFAULT 'enforce'
+        CONSTRAINT access_control_rules_enforce_check
+        CHECK (enforce IN ('enforce','shadow')),
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     UNIQUE(entity_type, entity_id, rule_type, rule_value),
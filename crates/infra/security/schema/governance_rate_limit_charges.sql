@@ -0,0 +1,13 @@
+-- One row per governed call charged against a shared `rate_limit` window.
+-- Replicas serialise on a transaction-scoped advisory lock per bucket_key
+-- before counting, so the window holds across the whole deployment.
+CREATE TABLE IF NOT EXISTS governance_rate_limit_charges (
+    bucket_key TEXT NOT NULL,
+    call_id TEXT NOT NULL,
+    charged_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (bucket_key, call_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_governance_rate_limit_charges_bucket ON governance_rate_limit_charges(bucket_key, charged_at);
+CREATE INDEX IF NOT EXISTS idx_governance_rate_limit_charges_expires ON governance_rate_limit_charges(expires_at);
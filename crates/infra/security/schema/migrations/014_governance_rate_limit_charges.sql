@@ -0,0 +1,14 @@
+-- Shared sliding window for the governance `rate_limit` policy's
+-- `backend: postgres` mode. The in-memory window stays per process; this table
+-- lets every replica charge one budget.
+
+CREATE TABLE IF NOT EXISTS governance_rate_limit_charges (
+    bucket_key TEXT NOT NULL,
+    call_id TEXT NOT NULL,
+    charged_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (bucket_key, call_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_governance_rate_limit_charges_bucket ON governance_rate_limit_charges(bucket_key, charged_at);
+CREATE INDEX IF NOT EXISTS idx_governance_rate_limit_charges_expires ON governance_rate_limit_charges(expires_at);
@@ -0,0 +1,13 @@
+-- Shadow-mode access-control rules.
+--
+-- A rule was either live or absent, so tightening one meant finding out from
+-- the denials what it actually covered. A 'shadow' rule is resolved beside
+-- the enforced set and the audit row records where the two would disagree,
+-- without the caller ever seeing the shadow outcome. Existing rows keep
+-- deciding: the column defaults to 'enforce'.
+
+ALTER TABLE access_control_rules ADD COLUMN IF NOT EXISTS enforce TEXT NOT NULL DEFAULT 'enforce';
+
+ALTER TABLE access_control_rules DROP CONSTRAINT IF EXISTS access_control_rules_enforce_check;
+ALTER TABLE access_control_rules ADD CONSTRAINT access_control_rules_enforce_check
+    CHECK (enforce IN ('enforce','shadow'));
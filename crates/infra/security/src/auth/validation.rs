@@ -5,7 +5,7 @@
 
 use axum::http::HeaderMap;
 use systemprompt_identifiers::{Actor, ContextId, SessionId, UserId};
-use systemprompt_models::auth::{JwtAudience, MAX_ACT_CHAIN_DEPTH, Permission, UserType};
+use systemprompt_models::auth::{ActClaim, JwtAudience, MAX_ACT_CHAIN_DEPTH, Permission, UserType};
 use systemprompt_models::execution::context::RequestContext;
 
 use crate::error::{AuthError, AuthResult};
@@ -61,6 +61,11 @@ impl AuthValidationService {
             user_type,
             jti: claims.jti,
             exp: claims.exp,
+            act_chain: claims
+                .act
+                .as_ref()
+                .map(ActClaim::flatten_to_chain)
+                .unwrap_or_default(),
         })
     }
 
@@ -86,5 +91,6 @@ impl AuthValidationService {
         .with_user_type(claims.user_type)
         .with_jti(claims.jti.clone())
         .with_token_exp(claims.exp)
+        .with_act_chain(claims.act_chain.clone())
     }
 }
@@ -12,7 +12,7 @@ use systemprompt_identifiers::{Actor, ContextId, SessionId, TaskId};
 
 use super::repository::{GovernanceDecisionRecord, GovernanceDecisionRepository};
 use super::{AuthzAuditSink, AuthzSource};
-use crate::authz::types::{AuthzDecision, AuthzRequest, DecisionTag};
+use crate::authz::types::{AuthzDecision, AuthzRequest, Decision, DecisionTag};
 
 #[derive(Debug, Clone)]
 pub struct DbAuditSink {
@@ -23,11 +23,14 @@ impl DbAuditSink {
     pub const fn new(repo: GovernanceDecisionRepository) -> Self {
         Self { repo }
     }
-}
 
-#[async_trait]
-impl AuthzAuditSink for DbAuditSink {
-    async fn record(&self, req: &AuthzRequest, decision: &AuthzDecision, source: AuthzSource) {
+    async fn write(
+        &self,
+        req: &AuthzRequest,
+        decision: &AuthzDecision,
+        source: AuthzSource,
+        shadow: Option<&Decision>,
+    ) {
         let id = uuid::Uuid::new_v4().to_string();
         let decision_tag = DecisionTag::from(decision);
         let reason_str = match decision {
@@ -38,7 +41,7 @@ impl AuthzAuditSink for DbAuditSink {
         let entity_id = req.entity.id_str();
         // JSON: audit blob constructed for core-side (non-template) authz
         // denies — same payload shape as template's `DecisionAudit`.
-        let evaluated = serde_json::json!({
+        let mut evaluated = serde_json::json!({
             "entity_type": entity_type,
             "entity_id": entity_id,
             "trace_id": req.trace_id.as_str(),
@@ -47,6 +50,14 @@ impl AuthzAuditSink for DbAuditSink {
             "context": req.context,
             "source": format!("{:?}", source),
         });
+        // JSON: the would-be outcome under shadow rules; `diverges` is what an
+        // operator filters on before promoting them.
+        if let Some(shadow) = shadow {
+            evaluated["shadow"] = serde_json::json!({
+                "diverges": shadow.tag() != decision_tag,
+                "outcome": shadow,
+            });
+        }
         let actor = Actor::user(req.user_id.clone());
         let context_id = req.context_id.clone().unwrap_or_else(|| {
             req.session_id
@@ -81,3 +92,20 @@ impl AuthzAuditSink for DbAuditSink {
         }
     }
 }
+
+#[async_trait]
+impl AuthzAuditSink for DbAuditSink {
+    async fn record(&self, req: &AuthzRequest, decision: &AuthzDecision, source: AuthzSource) {
+        self.write(req, decision, source, None).await;
+    }
+
+    async fn record_with_shadow(
+        &self,
+        req: &AuthzRequest,
+        decision: &AuthzDecision,
+        source: AuthzSource,
+        shadow: &Decision,
+    ) {
+        self.write(req, decision, source, Some(shadow)).await;
+    }
+}
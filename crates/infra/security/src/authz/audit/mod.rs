@@ -19,12 +19,12 @@ mod repository;
 
 use async_trait::async_trait;
 
-use super::types::{AuthzDecision, AuthzRequest};
+use super::types::{AuthzDecision, AuthzRequest, Decision};
 
 pub use db_sink::DbAuditSink;
 pub use repository::{
     AUDIT_WRITE_FAILED_TOTAL, GovernanceDecisionRecord, GovernanceDecisionRepository,
-    insert_governance_decision,
+    RecordedDecisionRow, insert_governance_decision,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,6 +54,19 @@ impl AuthzSource {
 #[async_trait]
 pub trait AuthzAuditSink: Send + Sync + std::fmt::Debug {
     async fn record(&self, req: &AuthzRequest, decision: &AuthzDecision, source: AuthzSource);
+
+    /// Records `decision` alongside `shadow`, the outcome the request would
+    /// have had with shadow rules enforced. Sinks without an audit blob to
+    /// carry it record the real decision only.
+    async fn record_with_shadow(
+        &self,
+        req: &AuthzRequest,
+        decision: &AuthzDecision,
+        source: AuthzSource,
+        _shadow: &Decision,
+    ) {
+        self.record(req, decision, source).await;
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy)]
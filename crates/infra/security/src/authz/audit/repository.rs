@@ -40,6 +40,19 @@ pub struct GovernanceDecisionRecord<'a> {
     pub trace_id: Option<&'a str>,
 }
 
+/// One recorded decision as read back for replay: the flat columns plus the
+/// audit blob the writer stored beside them.
+#[derive(Debug, Clone)]
+pub struct RecordedDecisionRow {
+    pub id: String,
+    pub user_id: String,
+    pub decision: String,
+    // JSON: the `evaluated_rules` blob as written; its shape depends on which
+    // writer produced the row.
+    pub evaluated_rules: Option<serde_json::Value>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, Clone)]
 pub struct GovernanceDecisionRepository {
     pool: std::sync::Arc<PgPool>,
@@ -57,6 +70,33 @@ impl GovernanceDecisionRepository {
     pub async fn insert(&self, record: &GovernanceDecisionRecord<'_>) -> Result<(), sqlx::Error> {
         insert_governance_decision(&self.pool, record).await
     }
+
+    /// Rows written under `policy` in `[since, until)`, oldest first, at most
+    /// `limit` of them.
+    pub async fn list_window(
+        &self,
+        policy: &str,
+        since: chrono::DateTime<chrono::Utc>,
+        until: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<RecordedDecisionRow>, sqlx::Error> {
+        sqlx::query_as!(
+            RecordedDecisionRow,
+            r#"
+            SELECT id, user_id, decision, evaluated_rules, created_at
+            FROM governance_decisions
+            WHERE policy = $1 AND created_at >= $2 AND created_at < $3
+            ORDER BY created_at
+            LIMIT $4
+            "#,
+            policy,
+            since,
+            until,
+            limit,
+        )
+        .fetch_all(&*self.pool)
+        .await
+    }
 }
 
 pub async fn insert_governance_decision(
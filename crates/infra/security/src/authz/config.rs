@@ -15,6 +15,13 @@
 //!   in the catalog for that [`EntityKind`]; one rule per matched id. The glob
 //!   never creates entities — it only grants ones a prior pass materialised.
 //!
+//! A rule with `enforce: shadow` is ingested like any other but never decides:
+//! the resolver ignores it and the audit row records where it would have
+//! disagreed (see [`super::resolver::resolve_shadow`]). A role is granted at
+//! most once per entity, so shadow mode trials a rule that does not exist yet;
+//! the effect of changing an existing grant is previewed by replaying the
+//! audit log with `admin access-control simulate`.
+//!
 //! The contract is one-way (YAML → DB). Per-user overrides (`rule_type='user'`)
 //! are operational state and never appear here — the loader rejects any rule
 //! with no `roles:` set. Per-tenant attribute rules live in extension-owned
@@ -26,7 +33,7 @@
 use serde::{Deserialize, Serialize, Serializer};
 
 use super::error::AuthzError;
-use super::types::{Access, EntityKind};
+use super::types::{Access, EnforceMode, EntityKind};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -49,6 +56,7 @@ pub struct RuleEntry {
     pub default_included: bool,
     pub roles: Vec<String>,
     pub justification: Option<String>,
+    pub enforce: EnforceMode,
 }
 
 #[derive(Deserialize)]
@@ -67,6 +75,8 @@ struct RuleEntryWire {
     roles: Vec<String>,
     #[serde(default)]
     justification: Option<String>,
+    #[serde(default)]
+    enforce: EnforceMode,
 }
 
 const fn default_allow() -> Access {
@@ -85,6 +95,12 @@ struct RuleEntryOut<'a> {
     roles: &'a [String],
     #[serde(skip_serializing_if = "Option::is_none")]
     justification: Option<&'a str>,
+    #[serde(skip_serializing_if = "is_enforced")]
+    enforce: EnforceMode,
+}
+
+const fn is_enforced(mode: &EnforceMode) -> bool {
+    !mode.is_shadow()
 }
 
 impl Serialize for RuleEntry {
@@ -104,6 +120,7 @@ impl Serialize for RuleEntry {
             default_included: self.default_included,
             roles: &self.roles,
             justification: self.justification.as_deref(),
+            enforce: self.enforce,
         }
         .serialize(serializer)
     }
@@ -138,6 +155,7 @@ impl<'de> Deserialize<'de> for RuleEntry {
             default_included: wire.default_included,
             roles: wire.roles,
             justification: wire.justification,
+            enforce: wire.enforce,
         })
     }
 }
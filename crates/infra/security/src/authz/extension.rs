@@ -44,6 +44,7 @@ impl Extension for AuthzExtension {
                 "rule_type".into(),
                 "rule_value".into(),
                 "access".into(),
+                "enforce".into(),
             ]),
             SchemaDefinition::new(
                 "governance_decisions",
@@ -58,6 +59,16 @@ impl Extension for AuthzExtension {
                 "policy".into(),
                 "reason".into(),
             ]),
+            SchemaDefinition::new(
+                "governance_rate_limit_charges",
+                include_str!("../../schema/governance_rate_limit_charges.sql"),
+            )
+            .with_required_columns(vec![
+                "bucket_key".into(),
+                "call_id".into(),
+                "charged_at".into(),
+                "expires_at".into(),
+            ]),
         ]
     }
 
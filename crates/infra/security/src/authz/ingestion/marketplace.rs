@@ -11,7 +11,7 @@ use systemprompt_identifiers::MarketplaceId;
 use systemprompt_models::services::MarketplaceConfig;
 
 use super::super::error::AuthzResult;
-use super::super::types::{EntityKind, RuleType};
+use super::super::types::{EnforceMode, EntityKind, RuleType};
 use super::upsert::{Target, UpsertOutcome, upsert_marketplace_entity_row, upsert_target};
 use super::{AccessControlIngestionService, IngestOptions, IngestReport};
 
@@ -61,6 +61,7 @@ impl AccessControlIngestionService {
                     rule_value: role.as_str(),
                     access: "allow",
                     justification: cfg.access.justification.as_deref(),
+                    enforce: EnforceMode::Enforce,
                 };
                 let outcome = upsert_target(&mut tx, &target, options.override_existing).await?;
                 match outcome {
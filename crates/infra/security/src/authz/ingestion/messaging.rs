@@ -14,7 +14,7 @@ use std::collections::HashMap;
 use systemprompt_models::services::{SlackAppConfig, TeamsAppConfig};
 
 use super::super::error::AuthzResult;
-use super::super::types::{EntityKind, RuleType};
+use super::super::types::{EnforceMode, EntityKind, RuleType};
 use super::upsert::{Target, UpsertOutcome, upsert_entity_row, upsert_target};
 use super::{AccessControlIngestionService, IngestOptions, IngestReport};
 
@@ -97,6 +97,7 @@ impl AccessControlIngestionService {
                     rule_value: role.as_str(),
                     access: "allow",
                     justification: None,
+                    enforce: EnforceMode::Enforce,
                 };
                 match upsert_target(&mut tx, &target, options.override_existing).await? {
                     UpsertOutcome::Inserted => report.inserted += 1,
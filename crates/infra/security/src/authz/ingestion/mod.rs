@@ -28,7 +28,7 @@ use systemprompt_database::DbPool;
 
 use super::config::{AccessControlConfig, RuleEntry, RuleTarget};
 use super::error::{AuthzError, AuthzResult};
-use super::types::{Access, EntityKind, RuleType};
+use super::types::{Access, EnforceMode, EntityKind, RuleType};
 
 use glob::glob_matches;
 use upsert::{SOURCE_LABEL, Target, UpsertOutcome, upsert_entity_row, upsert_target};
@@ -59,6 +59,7 @@ struct ResolvedRule<'a> {
     default_included: bool,
     roles: &'a [String],
     justification: Option<&'a str>,
+    enforce: EnforceMode,
 }
 
 impl AccessControlIngestionService {
@@ -144,6 +145,7 @@ impl AccessControlIngestionService {
                         rule_value: role,
                         access: rule.access,
                         justification: rule.justification,
+                        enforce: rule.enforce,
                     };
                     match upsert_target(&mut tx, &target, options.override_existing).await? {
                         UpsertOutcome::Inserted => report.inserted += 1,
@@ -204,6 +206,7 @@ impl AccessControlIngestionService {
                 default_included: rule.default_included,
                 roles: &rule.roles,
                 justification: rule.justification.as_deref(),
+                enforce: rule.enforce,
             });
         }
 
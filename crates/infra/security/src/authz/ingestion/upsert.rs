@@ -13,7 +13,7 @@
 use systemprompt_identifiers::RuleId;
 
 use crate::authz::error::AuthzResult;
-use crate::authz::types::{EntityKind, RuleType};
+use crate::authz::types::{EnforceMode, EntityKind, RuleType};
 
 pub(super) const SOURCE_LABEL: &str = "ingestion:access_control_config";
 
@@ -25,6 +25,7 @@ pub(super) struct Target<'a> {
     pub(super) rule_value: &'a str,
     pub(super) access: &'static str,
     pub(super) justification: Option<&'a str>,
+    pub(super) enforce: EnforceMode,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -90,7 +91,7 @@ pub(super) async fn upsert_target(
 ) -> AuthzResult<UpsertOutcome> {
     let existing = sqlx::query!(
         r#"
-        SELECT id, access, justification
+        SELECT id, access, justification, enforce
         FROM access_control_rules
         WHERE entity_type = $1 AND entity_id = $2
           AND rule_type = $3 AND rule_value = $4
@@ -107,8 +108,9 @@ pub(super) async fn upsert_target(
         if !override_existing {
             return Ok(UpsertOutcome::Skipped);
         }
-        let unchanged =
-            row.access == target.access && row.justification.as_deref() == target.justification;
+        let unchanged = row.access == target.access
+            && row.justification.as_deref() == target.justification
+            && row.enforce == target.enforce.as_str();
         if unchanged {
             return Ok(UpsertOutcome::Skipped);
         }
@@ -117,12 +119,14 @@ pub(super) async fn upsert_target(
             UPDATE access_control_rules
             SET access = $2,
                 justification = $3,
+                enforce = $4,
                 updated_at = NOW()
             WHERE id = $1
             "#,
             row.id,
             target.access,
             target.justification,
+            target.enforce.as_str(),
         )
         .execute(&mut **tx)
         .await?;
@@ -132,8 +136,9 @@ pub(super) async fn upsert_target(
         sqlx::query!(
             r#"
             INSERT INTO access_control_rules
-                (id, entity_type, entity_id, rule_type, rule_value, access, justification)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+                (id, entity_type, entity_id, rule_type, rule_value, access, justification,
+                 enforce)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             "#,
             id.as_str(),
             target.entity_kind.as_str(),
@@ -142,6 +147,7 @@ pub(super) async fn upsert_target(
             target.rule_value,
             target.access,
             target.justification,
+            target.enforce.as_str(),
         )
         .execute(&mut **tx)
         .await?;
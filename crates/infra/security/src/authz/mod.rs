@@ -41,12 +41,13 @@ pub mod repository;
 pub mod resolver;
 pub mod rule_based;
 pub mod runtime;
+pub mod simulate;
 pub mod subject;
 pub mod types;
 
 pub use audit::{
     AUDIT_WRITE_FAILED_TOTAL, AuthzAuditSink, AuthzSource, DbAuditSink, GovernanceDecisionRecord,
-    GovernanceDecisionRepository, NullAuditSink, insert_governance_decision,
+    GovernanceDecisionRepository, NullAuditSink, RecordedDecisionRow, insert_governance_decision,
 };
 pub use composite::CompositeAuthzHook;
 pub use config::{AccessControlConfig, RuleEntry, RuleTarget};
@@ -58,9 +59,13 @@ pub use ingestion::{AccessControlIngestionService, IngestOptions, IngestReport};
 pub use marketplace_floor::member_attribute_floor;
 pub use registry::{AuthzHookContext, AuthzHookRegistration, discover_authz_hook};
 pub use repository::{AccessControlRepository, UpsertRuleParams};
-pub use resolver::{ResolveInput, ResolveParent, resolve};
+pub use resolver::{ResolveInput, ResolveParent, resolve, resolve_shadow};
 pub use rule_based::RuleBasedHook;
 pub use runtime::build_authz_hook;
+pub use simulate::{
+    AccessControlSimulation, Flip, LiveAccessControl, REPLAYABLE_POLICY, RecordedDecision,
+    SimulationReport,
+};
 pub use subject::{
     NO_SUBJECT_ATTRIBUTES, ROLE_PRECEDENCE, SharedSubjectAttributeProvider,
     SubjectAttributeProvider, SubjectAttributes, SubjectDimension, SubjectProviderRegistration,
@@ -68,5 +73,5 @@ pub use subject::{
 };
 pub use types::{
     Access, AccessRule, AuthzContext, AuthzDecision, AuthzRequest, Decision, DecisionTag,
-    DenyReason, EntityKind, EntityRef, EntityRow, MatchedBy, RuleType,
+    DenyReason, EnforceMode, EntityKind, EntityRef, EntityRow, MatchedBy, RuleType,
 };
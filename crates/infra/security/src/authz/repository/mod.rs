@@ -30,6 +30,7 @@ pub struct ExportRuleRow {
     pub rule_value: String,
     pub access: String,
     pub justification: Option<String>,
+    pub enforce: String,
 }
 
 #[derive(Debug, Clone)]
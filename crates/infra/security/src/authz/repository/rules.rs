@@ -10,14 +10,14 @@ use systemprompt_identifiers::RuleId;
 
 use super::{AccessControlRepository, ExportRuleRow, UpsertRuleParams};
 use crate::authz::error::AuthzResult;
-use crate::authz::types::{Access, AccessRule, EntityKind, RuleType};
+use crate::authz::types::{Access, AccessRule, EnforceMode, EntityKind, RuleType};
 
 impl AccessControlRepository {
     pub async fn list_role_rules_for_export(&self) -> AuthzResult<Vec<ExportRuleRow>> {
         let rows = sqlx::query_as!(
             ExportRuleRow,
             r#"
-            SELECT entity_type, entity_id, rule_type, rule_value, access, justification
+            SELECT entity_type, entity_id, rule_type, rule_value, access, justification, enforce
             FROM access_control_rules
             WHERE rule_type = 'role'
             ORDER BY entity_type, entity_id, access, rule_type, rule_value
@@ -35,7 +35,7 @@ impl AccessControlRepository {
     ) -> AuthzResult<Vec<AccessRule>> {
         let rows = sqlx::query!(
             r#"
-            SELECT id, rule_type, rule_value, access, justification
+            SELECT id, rule_type, rule_value, access, justification, enforce
             FROM access_control_rules
             WHERE entity_type = $1 AND entity_id = $2
             ORDER BY rule_type, rule_value
@@ -54,6 +54,7 @@ impl AccessControlRepository {
                 rule_value: row.rule_value,
                 access: Access::from_str(&row.access)?,
                 justification: row.justification,
+                enforce: EnforceMode::from_str(&row.enforce)?,
             });
         }
         Ok(out)
@@ -74,7 +75,7 @@ impl AccessControlRepository {
 
         let rows = sqlx::query!(
             r#"
-            SELECT entity_id, id, rule_type, rule_value, access, justification
+            SELECT entity_id, id, rule_type, rule_value, access, justification, enforce
             FROM access_control_rules
             WHERE entity_type = $1 AND entity_id = ANY($2)
             ORDER BY entity_id, rule_type, rule_value
@@ -92,6 +93,7 @@ impl AccessControlRepository {
                 rule_value: row.rule_value,
                 access: Access::from_str(&row.access)?,
                 justification: row.justification,
+                enforce: EnforceMode::from_str(&row.enforce)?,
             };
             out.entry(row.entity_id).or_default().push(rule);
         }
@@ -112,7 +114,7 @@ impl AccessControlRepository {
                 access = EXCLUDED.access,
                 justification = COALESCE(EXCLUDED.justification, access_control_rules.justification),
                 updated_at = NOW()
-            RETURNING id, rule_type, rule_value, access, justification
+            RETURNING id, rule_type, rule_value, access, justification, enforce
             "#,
             id.as_str(),
             params.entity_type.as_str(),
@@ -131,6 +133,7 @@ impl AccessControlRepository {
             rule_value: row.rule_value,
             access: Access::from_str(&row.access)?,
             justification: row.justification,
+            enforce: EnforceMode::from_str(&row.enforce)?,
         })
     }
 
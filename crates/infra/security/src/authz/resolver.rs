@@ -20,6 +20,11 @@
 //! `roles: [admin]` grant restrictive even when the entity belongs to a group
 //! that is granted to everyone.
 //!
+//! Rules marked [`EnforceMode::Shadow`] never decide: [`resolve`] skips them
+//! as if they were absent. [`resolve_shadow`] answers the what-if — the
+//! decision were they enforced — so the caller can audit it next to the real
+//! one.
+//!
 //! `default_included` is `Option<bool>` — `None` signals the entity is
 //! unknown to access control (no row in `access_control_entities`), which
 //! the resolver turns into [`DenyReason::UnknownEntity`] rather than the
@@ -32,7 +37,9 @@
 use systemprompt_identifiers::UserId;
 
 use super::subject::{ROLE_PRECEDENCE, SubjectAttributes, SubjectDimension, USER_PRECEDENCE};
-use super::types::{Access, AccessRule, Decision, DenyReason, EntityRef, MatchedBy, RuleType};
+use super::types::{
+    Access, AccessRule, Decision, DenyReason, EnforceMode, EntityRef, MatchedBy, RuleType,
+};
 
 /// A parent entity whose rules cascade onto the child being resolved.
 ///
@@ -62,6 +69,22 @@ pub struct ResolveInput<'a> {
 
 #[must_use]
 pub fn resolve(input: ResolveInput<'_>) -> Decision {
+    resolve_counting(input, false)
+}
+
+/// The decision [`resolve`] would return if every shadow rule in `input` were
+/// enforced, or `None` when neither the entity nor its parents carry one.
+#[must_use]
+pub fn resolve_shadow(input: ResolveInput<'_>) -> Option<Decision> {
+    let has_shadow = input
+        .rules
+        .iter()
+        .chain(input.parents.iter().flat_map(|p| p.rules))
+        .any(|r| r.enforce == EnforceMode::Shadow);
+    has_shadow.then(|| resolve_counting(input, true))
+}
+
+fn resolve_counting(input: ResolveInput<'_>, include_shadow: bool) -> Decision {
     let ResolveInput {
         entity,
         rules,
@@ -79,12 +102,14 @@ pub fn resolve(input: ResolveInput<'_>) -> Decision {
         user_roles,
         attributes,
         ladder: &ladder,
+        include_shadow,
     };
 
     if let Some(decision) = match_ruleset(entity, rules, &subject) {
         return decision;
     }
-    let parents = if rules.is_empty() { parents } else { &[] };
+    let has_own_rules = rules.iter().any(|r| subject.counts(r));
+    let parents = if has_own_rules { &[] } else { parents };
 
     for parent in parents {
         if let Some(decision) = match_ruleset(parent.entity, parent.rules, &subject) {
@@ -142,9 +167,14 @@ struct Subject<'a> {
     user_roles: &'a [String],
     attributes: &'a SubjectAttributes,
     ladder: &'a [(RuleType, u16)],
+    include_shadow: bool,
 }
 
 impl Subject<'_> {
+    fn counts(&self, rule: &AccessRule) -> bool {
+        self.include_shadow || rule.enforce == EnforceMode::Enforce
+    }
+
     fn matches(&self, rule: &AccessRule) -> bool {
         if rule.rule_type == RuleType::USER {
             return rule.rule_value == self.user_id.as_str();
@@ -164,7 +194,8 @@ fn match_ruleset(
     subject: &Subject<'_>,
 ) -> Option<Decision> {
     for (rule_type, _) in subject.ladder {
-        let in_band = |r: &&AccessRule| r.rule_type == *rule_type && subject.matches(r);
+        let in_band =
+            |r: &&AccessRule| subject.counts(r) && r.rule_type == *rule_type && subject.matches(r);
 
         if let Some(rule) = ruleset
             .iter()
@@ -16,6 +16,10 @@
 //! Put `RuleBasedHook` first so a coarse-grained RBAC reject short-circuits
 //! the chain before any per-attribute lookup runs.
 //!
+//! Shadow rules on the entity are resolved a second time with
+//! [`resolve_shadow`] and the would-be outcome rides on the audit row; the
+//! returned decision is always the enforced one.
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
@@ -28,7 +32,7 @@ use super::audit::{AuthzAuditSink, AuthzSource};
 use super::hook::AuthzDecisionHook;
 use super::registry::AuthzHookContext;
 use super::repository::AccessControlRepository;
-use super::resolver::{ResolveInput, resolve};
+use super::resolver::{ResolveInput, resolve, resolve_shadow};
 use super::subject::{
     SharedSubjectAttributeProvider, SubjectDimension, dimensions_of, discover_subject_providers,
     gather_subject_attributes,
@@ -95,7 +99,7 @@ impl AuthzDecisionHook for RuleBasedHook {
         };
 
         let attributes = gather_subject_attributes(&self.providers, &req.user_id).await;
-        let decision = resolve(ResolveInput {
+        let input = ResolveInput {
             entity: &req.entity,
             rules: &rules,
             user_id: &req.user_id,
@@ -104,16 +108,27 @@ impl AuthzDecisionHook for RuleBasedHook {
             parents: &[],
             attributes: &attributes,
             dimensions: &self.dimensions,
-        });
+        };
+        let decision = resolve(input);
+        let shadow = resolve_shadow(input);
 
         let policy = AuthzSource::RuleBased.policy().to_owned();
         let authz_decision = match decision {
             Decision::Allow { .. } => AuthzDecision::Allow,
             Decision::Deny { reason } => AuthzDecision::Deny { reason, policy },
         };
-        self.sink
-            .record(&req, &authz_decision, AuthzSource::RuleBased)
-            .await;
+        match &shadow {
+            Some(shadow) => {
+                self.sink
+                    .record_with_shadow(&req, &authz_decision, AuthzSource::RuleBased, shadow)
+                    .await;
+            },
+            None => {
+                self.sink
+                    .record(&req, &authz_decision, AuthzSource::RuleBased)
+                    .await;
+            },
+        }
         authz_decision
     }
 }
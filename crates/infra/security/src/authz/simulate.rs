@@ -0,0 +1,258 @@
+//! What-if replay of recorded access-control decisions.
+//!
+//! [`AccessControlSimulation`] applies a candidate [`AccessControlConfig`] to
+//! a snapshot of the live catalog exactly as ingestion would — role rules
+//! upserted over the live ones (optionally replacing them, as
+//! `delete_orphans` does), named entities taking the file's
+//! `default_included` — and re-resolves recorded decisions against the result.
+//! A [`SimulationReport`] lists every call whose outcome would flip.
+//!
+//! Only what the `authz_rule_based` audit row captured can be replayed: the
+//! user, their roles, and the entity. Extension subject dimensions are not
+//! recorded, so rules on them never match during a replay; a row whose blob
+//! lacks the entity or roles is not a [`RecordedDecision`] at all. A row that
+//! recorded no roles is skipped and counted rather than replayed, since it
+//! cannot tell a caller who held none from one whose roles went unrecorded.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use systemprompt_identifiers::{RuleId, UserId};
+
+use super::audit::RecordedDecisionRow;
+use super::config::{AccessControlConfig, RuleTarget};
+use super::error::AuthzResult;
+use super::ingestion::glob::glob_matches;
+use super::resolver::{ResolveInput, resolve};
+use super::subject::NO_SUBJECT_ATTRIBUTES;
+use super::types::{AccessRule, Decision, DecisionTag, EntityKind, EntityRef, EntityRow, RuleType};
+
+/// The `authz_rule_based` audit rows are the ones [`super::RuleBasedHook`]
+/// writes, and the only ones a rules file decides.
+pub const REPLAYABLE_POLICY: &str = "authz_rule_based";
+
+type EntityKey = (EntityKind, String);
+
+/// One replayable decision read back from `governance_decisions`.
+#[derive(Debug, Clone)]
+pub struct RecordedDecision {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub user_id: UserId,
+    pub roles: Vec<String>,
+    pub entity: EntityRef,
+    pub decision: DecisionTag,
+}
+
+impl RecordedDecision {
+    /// `None` when the row's blob does not name an entity and the caller's
+    /// roles, or its decision column is not `allow`/`deny`.
+    #[must_use]
+    pub fn from_row(row: &RecordedDecisionRow) -> Option<Self> {
+        let blob = row.evaluated_rules.as_ref()?;
+        // JSON: `EntityRef` is tagged `{kind, id}`, the same strings the sink
+        // flattened into `entity_type`/`entity_id`.
+        let entity = serde_json::from_value(serde_json::json!({
+            "kind": blob.get("entity_type")?,
+            "id": blob.get("entity_id")?,
+        }))
+        .ok()?;
+        let roles = serde_json::from_value(blob.get("roles")?.clone()).ok()?;
+        let decision = match row.decision.as_str() {
+            "allow" => DecisionTag::Allow,
+            "deny" => DecisionTag::Deny,
+            _ => return None,
+        };
+        Some(Self {
+            id: row.id.clone(),
+            created_at: row.created_at,
+            user_id: UserId::new(row.user_id.clone()),
+            roles,
+            entity,
+            decision,
+        })
+    }
+}
+
+/// The live catalog and rules the candidate is applied over.
+#[derive(Debug, Clone, Default)]
+pub struct LiveAccessControl {
+    pub entities: Vec<EntityRow>,
+    pub rules: HashMap<EntityKey, Vec<AccessRule>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccessControlSimulation {
+    live_default_included: HashMap<EntityKey, bool>,
+    live_rules: HashMap<EntityKey, Vec<AccessRule>>,
+    default_included: HashMap<EntityKey, bool>,
+    rules: HashMap<EntityKey, Vec<AccessRule>>,
+}
+
+/// A recorded call whose outcome the candidate changes.
+#[derive(Debug, Clone)]
+pub struct Flip {
+    pub recorded: RecordedDecision,
+    pub candidate: Decision,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SimulationReport {
+    pub replayed: usize,
+    /// Rows skipped because they recorded no roles for the caller.
+    pub without_roles: usize,
+    pub unchanged: usize,
+    /// Rows the live rules already decide differently from how they were
+    /// recorded: the rules changed since. A flip on such a row is not the
+    /// candidate's doing alone.
+    pub drifted: usize,
+    pub flips: Vec<Flip>,
+}
+
+impl SimulationReport {
+    pub fn allow_to_deny(&self) -> usize {
+        self.flips
+            .iter()
+            .filter(|f| f.recorded.decision == DecisionTag::Allow)
+            .count()
+    }
+
+    pub fn deny_to_allow(&self) -> usize {
+        self.flips.len() - self.allow_to_deny()
+    }
+}
+
+impl AccessControlSimulation {
+    pub fn new(
+        live: LiveAccessControl,
+        candidate: &AccessControlConfig,
+        delete_orphans: bool,
+    ) -> AuthzResult<Self> {
+        candidate.validate()?;
+
+        let live_default_included: HashMap<EntityKey, bool> = live
+            .entities
+            .iter()
+            .map(|e| ((e.kind, e.id.clone()), e.default_included))
+            .collect();
+        let mut default_included = live_default_included.clone();
+        let mut rules = live.rules.clone();
+
+        let resolved: Vec<(usize, EntityKey)> = candidate
+            .rules
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, rule)| {
+                expand(&live.entities, rule.entity_type, &rule.target)
+                    .into_iter()
+                    .map(move |id| (idx, (rule.entity_type, id)))
+            })
+            .collect();
+
+        if delete_orphans {
+            for (_, key) in &resolved {
+                if let Some(existing) = rules.get_mut(key) {
+                    existing.retain(|r| r.rule_type != RuleType::ROLE);
+                }
+            }
+        }
+
+        for (idx, key) in resolved {
+            let rule = &candidate.rules[idx];
+            default_included.insert(key.clone(), rule.default_included);
+            let ruleset = rules.entry(key).or_default();
+            for role in &rule.roles {
+                let replacement = AccessRule {
+                    id: RuleId::new(format!("candidate:rules[{idx}]:{role}")),
+                    rule_type: RuleType::ROLE,
+                    rule_value: role.clone(),
+                    access: rule.access,
+                    justification: rule.justification.clone(),
+                    enforce: rule.enforce,
+                };
+                match ruleset
+                    .iter_mut()
+                    .find(|r| r.rule_type == RuleType::ROLE && r.rule_value == *role)
+                {
+                    Some(existing) => *existing = replacement,
+                    None => ruleset.push(replacement),
+                }
+            }
+        }
+
+        Ok(Self {
+            live_default_included,
+            live_rules: live.rules,
+            default_included,
+            rules,
+        })
+    }
+
+    #[must_use]
+    pub fn replay(&self, recorded: &[RecordedDecision]) -> SimulationReport {
+        let mut report = SimulationReport::default();
+        for row in recorded {
+            if row.roles.is_empty() {
+                report.without_roles += 1;
+                continue;
+            }
+            report.replayed += 1;
+            let key = (row.entity.kind(), row.entity.id_str().to_owned());
+
+            let live = decide(
+                row,
+                self.live_rules.get(&key),
+                self.live_default_included.get(&key).copied(),
+            );
+            if live.tag() != row.decision {
+                report.drifted += 1;
+            }
+
+            let candidate = decide(
+                row,
+                self.rules.get(&key),
+                self.default_included.get(&key).copied(),
+            );
+            if candidate.tag() == row.decision {
+                report.unchanged += 1;
+            } else {
+                report.flips.push(Flip {
+                    recorded: row.clone(),
+                    candidate,
+                });
+            }
+        }
+        report
+    }
+}
+
+fn expand(catalog: &[EntityRow], kind: EntityKind, target: &RuleTarget) -> Vec<String> {
+    match target {
+        RuleTarget::Id(id) => vec![id.clone()],
+        RuleTarget::Match(pattern) => catalog
+            .iter()
+            .filter(|e| e.kind == kind && glob_matches(pattern, &e.id))
+            .map(|e| e.id.clone())
+            .collect(),
+    }
+}
+
+fn decide(
+    row: &RecordedDecision,
+    rules: Option<&Vec<AccessRule>>,
+    default_included: Option<bool>,
+) -> Decision {
+    resolve(ResolveInput {
+        entity: &row.entity,
+        rules: rules.map_or(&[], Vec::as_slice),
+        user_id: &row.user_id,
+        user_roles: &row.roles,
+        default_included,
+        parents: &[],
+        attributes: &NO_SUBJECT_ATTRIBUTES,
+        dimensions: &[],
+    })
+}
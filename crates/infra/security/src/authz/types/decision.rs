@@ -12,7 +12,7 @@ use thiserror::Error;
 
 use super::entity_ref::EntityRef;
 use super::kinds::RuleType;
-use crate::policy::types::{AccessScope, RateLimitWindow, SecretLocation};
+use crate::policy::types::{AccessScope, ConstraintViolation, RateLimitWindow, SecretLocation};
 
 /// Why an [`super::request::AuthzRequest`] was allowed. Carries enough
 /// structure for the audit row to attribute the decision without re-deriving
@@ -40,9 +40,9 @@ pub enum MatchedBy {
 /// Variants cover both the user→entity resolver
 /// (`UserDeny`, `RoleDeny`, `NotAssigned`, `UnknownEntity`),
 /// the hook plane (`HookUnavailable`), and the tool-use governance chain
-/// (`SecretLeak`, `ScopeViolation`, `ToolBlocked`, `RateLimitExceeded`). The
-/// human-readable `#[error]` strings double as the `reason` column in the
-/// `governance_decisions` audit row.
+/// (`SecretLeak`, `ScopeViolation`, `ToolBlocked`, `RateLimitExceeded`,
+/// `ArgumentViolation`). The human-readable `#[error]` strings double as the
+/// `reason` column in the `governance_decisions` audit row.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Error)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum DenyReason {
@@ -107,6 +107,13 @@ pub enum DenyReason {
         window: RateLimitWindow,
         retry_after_ms: u64,
     },
+    #[error("tool {tool} argument {pointer} violates rule {rule_id}: {violation}")]
+    ArgumentViolation {
+        tool: McpToolName,
+        rule_id: String,
+        pointer: String,
+        violation: ConstraintViolation,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -130,6 +130,67 @@ impl FromStr for Access {
     }
 }
 
+/// Whether a rule or governance policy decides calls or only observes them.
+///
+/// A `Shadow` rule is evaluated next to the enforced set and its would-be
+/// outcome is audited, but it never changes the decision a caller sees. This
+/// is how a tightened rule or a new policy is trialled against live traffic
+/// before it is promoted to `Enforce`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    sqlx::Type,
+)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum EnforceMode {
+    #[default]
+    Enforce,
+    Shadow,
+}
+
+impl EnforceMode {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Enforce => "enforce",
+            Self::Shadow => "shadow",
+        }
+    }
+
+    pub const fn is_shadow(self) -> bool {
+        matches!(self, Self::Shadow)
+    }
+}
+
+impl fmt::Display for EnforceMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EnforceMode {
+    type Err = AuthzError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "enforce" => Ok(Self::Enforce),
+            "shadow" => Ok(Self::Shadow),
+            other => Err(AuthzError::Validation(format!(
+                "unknown enforce mode: {other} (expected `enforce` or `shadow`)"
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EntityKind {
@@ -6,7 +6,7 @@
 use serde::{Deserialize, Serialize};
 use systemprompt_identifiers::RuleId;
 
-use super::kinds::{Access, EntityKind, RuleType};
+use super::kinds::{Access, EnforceMode, EntityKind, RuleType};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
 pub struct AccessRule {
@@ -16,6 +16,8 @@ pub struct AccessRule {
     pub access: Access,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub justification: Option<String>,
+    #[serde(default)]
+    pub enforce: EnforceMode,
 }
 
 /// One row from `access_control_entities`.
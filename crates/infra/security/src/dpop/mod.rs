@@ -0,0 +1,43 @@
+//! RFC 9449 `DPoP` (Demonstrating Proof of Possession) primitives.
+//!
+//! A `DPoP` proof is a short-lived JWT the client signs with its own key and
+//! sends in the `DPoP` header. The token endpoint binds the access token it
+//! issues to that key (`cnf.jkt`, the RFC 7638 thumbprint); a resource then
+//! only accepts the token alongside a fresh proof from the same key, so a
+//! stolen token is useless without the private key.
+//!
+//! - [`proof`] — stateless proof verification: `typ`, asymmetric `alg`,
+//!   embedded public `jwk`, signature, `htm`/`htu`, `iat` window and the `ath`
+//!   access-token hash.
+//! - [`nonce`] — server-issued nonces derived from the deployment
+//!   `oauth_at_rest_pepper` and a coarse time window, so every API instance
+//!   issues and accepts the same values without shared state.
+//!
+//! Single-use enforcement of proof `jti`s needs a shared store and lives with
+//! the OAuth repository; callers record [`VerifiedDpopProof::jti`] there after
+//! verification.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+pub mod nonce;
+pub mod proof;
+
+pub use nonce::{
+    DPOP_NONCE_WINDOW_SECONDS, issue_nonce, issue_nonce_with, verify_nonce, verify_nonce_with,
+};
+pub use proof::{
+    DPOP_SIGNING_ALG_VALUES, DpopProofCheck, VerifiedDpopProof, access_token_hash, verify_proof,
+};
+
+pub const DPOP_HEADER: &str = "dpop";
+
+pub const DPOP_NONCE_HEADER: &str = "dpop-nonce";
+
+pub const DPOP_SCHEME: &str = "DPoP";
+
+pub const DPOP_PROOF_TYP: &str = "dpop+jwt";
+
+pub const DPOP_PROOF_MAX_AGE_SECONDS: i64 = 300;
+
+pub const DPOP_CLOCK_SKEW_SECONDS: i64 = 60;
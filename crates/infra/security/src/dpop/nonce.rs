@@ -0,0 +1,54 @@
+//! Stateless `DPoP` nonces (RFC 9449 §8).
+//!
+//! A nonce is the HMAC-SHA-256 of a time-window index under the deployment
+//! `oauth_at_rest_pepper`, domain-separated from the at-rest hashes that use
+//! the same key. Any instance can issue and check it; a nonce from the current
+//! or previous window is accepted, so a nonce lives between one and two
+//! windows.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use systemprompt_config::SecretsBootstrap;
+
+use crate::at_rest::hmac_sha256;
+use crate::error::{DpopError, DpopResult};
+
+pub const DPOP_NONCE_WINDOW_SECONDS: i64 = 300;
+
+const NONCE_DOMAIN: &[u8] = b"systemprompt.dpop-nonce.v1:";
+
+pub fn issue_nonce(now: i64) -> DpopResult<String> {
+    Ok(issue_nonce_with(nonce_key()?, now))
+}
+
+pub fn verify_nonce(nonce: &str, now: i64) -> DpopResult<bool> {
+    Ok(verify_nonce_with(nonce_key()?, nonce, now))
+}
+
+#[must_use]
+pub fn issue_nonce_with(key: &[u8], now: i64) -> String {
+    nonce_for_window(key, now.div_euclid(DPOP_NONCE_WINDOW_SECONDS))
+}
+
+#[must_use]
+pub fn verify_nonce_with(key: &[u8], nonce: &str, now: i64) -> bool {
+    let window = now.div_euclid(DPOP_NONCE_WINDOW_SECONDS);
+    [window, window - 1]
+        .into_iter()
+        .any(|w| nonce_for_window(key, w) == nonce)
+}
+
+fn nonce_for_window(key: &[u8], window: i64) -> String {
+    let mut input = NONCE_DOMAIN.to_vec();
+    input.extend_from_slice(&window.to_be_bytes());
+    URL_SAFE_NO_PAD.encode(hmac_sha256(key, &input))
+}
+
+fn nonce_key() -> DpopResult<&'static [u8]> {
+    SecretsBootstrap::oauth_at_rest_pepper()
+        .map(str::as_bytes)
+        .map_err(|e| DpopError::NonceKey(e.to_string()))
+}
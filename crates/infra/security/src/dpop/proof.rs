@@ -0,0 +1,152 @@
+//! `DPoP` proof verification (RFC 9449 §4.3).
+//!
+//! [`verify_proof`] checks everything a single proof can prove on its own and
+//! returns the key thumbprint plus the `jti`/`nonce` the caller still has to
+//! check against shared state. Only keys whose type matches the header `alg`
+//! are accepted, which also keeps symmetric (`oct`) keys out.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::HashSet;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::jwk::{AlgorithmParameters, EllipticCurve, Jwk, ThumbprintHash};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use super::{DPOP_CLOCK_SKEW_SECONDS, DPOP_PROOF_MAX_AGE_SECONDS, DPOP_PROOF_TYP};
+use crate::error::{DpopError, DpopResult};
+
+pub const DPOP_SIGNING_ALG_VALUES: &[&str] = &["ES256", "ES384", "RS256", "PS256", "EdDSA"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct DpopProofCheck<'a> {
+    pub method: &'a str,
+    pub url: &'a str,
+    pub access_token: Option<&'a str>,
+    pub now: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedDpopProof {
+    pub jkt: String,
+    pub jti: String,
+    pub iat: i64,
+    pub nonce: Option<String>,
+}
+
+impl VerifiedDpopProof {
+    #[must_use]
+    pub const fn replay_window_end(&self) -> i64 {
+        self.iat + DPOP_PROOF_MAX_AGE_SECONDS + DPOP_CLOCK_SKEW_SECONDS
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DpopProofClaims {
+    jti: String,
+    htm: String,
+    htu: String,
+    iat: i64,
+    #[serde(default)]
+    ath: Option<String>,
+    #[serde(default)]
+    nonce: Option<String>,
+}
+
+pub fn verify_proof(proof: &str, check: &DpopProofCheck<'_>) -> DpopResult<VerifiedDpopProof> {
+    let header = decode_header(proof).map_err(|e| DpopError::Malformed(e.to_string()))?;
+    if !header
+        .typ
+        .as_deref()
+        .is_some_and(|typ| typ.eq_ignore_ascii_case(DPOP_PROOF_TYP))
+    {
+        return Err(DpopError::WrongType);
+    }
+
+    let alg_name = format!("{:?}", header.alg);
+    if !DPOP_SIGNING_ALG_VALUES.contains(&alg_name.as_str()) {
+        return Err(DpopError::UnsupportedAlgorithm(alg_name));
+    }
+
+    let jwk = header.jwk.ok_or(DpopError::MissingJwk)?;
+    if !key_matches_algorithm(&jwk, header.alg) {
+        return Err(DpopError::UnsupportedKey);
+    }
+    let key = DecodingKey::from_jwk(&jwk).map_err(|_e| DpopError::UnsupportedKey)?;
+
+    let mut validation = Validation::new(header.alg);
+    validation.required_spec_claims = HashSet::new();
+    validation.validate_exp = false;
+    validation.validate_aud = false;
+    let claims = decode::<DpopProofClaims>(proof, &key, &validation)
+        .map_err(DpopError::InvalidSignature)?
+        .claims;
+
+    if claims.jti.is_empty() {
+        return Err(DpopError::Malformed("empty `jti`".to_owned()));
+    }
+    if claims.htm != check.method {
+        return Err(DpopError::MethodMismatch {
+            expected: check.method.to_owned(),
+            got: claims.htm,
+        });
+    }
+    if normalize_htu(&claims.htu)? != normalize_htu(check.url)? {
+        return Err(DpopError::UriMismatch);
+    }
+    if claims.iat > check.now + DPOP_CLOCK_SKEW_SECONDS
+        || check.now - claims.iat > DPOP_PROOF_MAX_AGE_SECONDS
+    {
+        return Err(DpopError::Stale);
+    }
+    if let Some(token) = check.access_token {
+        let ath = claims
+            .ath
+            .as_deref()
+            .ok_or(DpopError::MissingAccessTokenHash)?;
+        if ath != access_token_hash(token) {
+            return Err(DpopError::AccessTokenHashMismatch);
+        }
+    }
+
+    Ok(VerifiedDpopProof {
+        jkt: jwk.thumbprint(ThumbprintHash::SHA256),
+        jti: claims.jti,
+        iat: claims.iat,
+        nonce: claims.nonce,
+    })
+}
+
+#[must_use]
+pub fn access_token_hash(access_token: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(access_token.as_bytes()))
+}
+
+// Why: `Jwk::thumbprint` panics on curve/key-type combinations it cannot
+// represent, so the key must be pinned to the header `alg` before it is used.
+fn key_matches_algorithm(jwk: &Jwk, alg: Algorithm) -> bool {
+    match (&jwk.algorithm, alg) {
+        (AlgorithmParameters::RSA(_), Algorithm::RS256 | Algorithm::PS256) => true,
+        (AlgorithmParameters::EllipticCurve(params), Algorithm::ES256) => {
+            params.curve == EllipticCurve::P256
+        },
+        (AlgorithmParameters::EllipticCurve(params), Algorithm::ES384) => {
+            params.curve == EllipticCurve::P384
+        },
+        (AlgorithmParameters::OctetKeyPair(params), Algorithm::EdDSA) => {
+            params.curve == EllipticCurve::Ed25519
+        },
+        _ => false,
+    }
+}
+
+fn normalize_htu(raw: &str) -> DpopResult<String> {
+    let mut url = url::Url::parse(raw).map_err(|_e| DpopError::UriMismatch)?;
+    url.set_query(None);
+    url.set_fragment(None);
+    Ok(url.to_string())
+}
@@ -3,10 +3,11 @@
 //! Public APIs in this crate return `thiserror`-derived error enums:
 //!
 //! - [`AuthError`] — request validation, JWT decoding, claim extraction.
-//! - [`JwtError`] — JWT minting (admin tokens, session tokens).
+//! - [`JwtError`] — JWT minting (admin tokens, session tokens, token exchange).
 //! - [`ManifestSigningError`] — Ed25519 signing of bridge manifests.
+//! - [`DpopError`] — RFC 9449 `DPoP` proof and nonce verification.
 //!
-//! All three implement `std::error::Error` and can be composed into larger
+//! All four implement `std::error::Error` and can be composed into larger
 //! `thiserror` enums via `#[from]`.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
@@ -95,6 +96,9 @@ pub enum JwtError {
 
     #[error("jwt signing key unavailable: {0}")]
     Signing(String),
+
+    #[error("token exchange rejected: {0}")]
+    Exchange(#[source] AuthError),
 }
 
 #[derive(Debug, Error)]
@@ -109,8 +113,49 @@ pub enum ManifestSigningError {
     KeyMissing,
 }
 
+#[derive(Debug, Error)]
+pub enum DpopError {
+    #[error("DPoP proof is malformed: {0}")]
+    Malformed(String),
+
+    #[error("DPoP proof `typ` must be `dpop+jwt`")]
+    WrongType,
+
+    #[error("DPoP proof algorithm `{0}` is not supported")]
+    UnsupportedAlgorithm(String),
+
+    #[error("DPoP proof header carries no `jwk`")]
+    MissingJwk,
+
+    #[error("DPoP proof `jwk` is not a public key of the type its `alg` requires")]
+    UnsupportedKey,
+
+    #[error("DPoP proof signature is invalid: {0}")]
+    InvalidSignature(#[source] jsonwebtoken::errors::Error),
+
+    #[error("DPoP proof `htm` `{got}` does not match request method `{expected}`")]
+    MethodMismatch { expected: String, got: String },
+
+    #[error("DPoP proof `htu` does not match the request URI")]
+    UriMismatch,
+
+    #[error("DPoP proof `iat` is outside the accepted window")]
+    Stale,
+
+    #[error("DPoP proof is missing the `ath` claim")]
+    MissingAccessTokenHash,
+
+    #[error("DPoP proof `ath` does not match the presented access token")]
+    AccessTokenHashMismatch,
+
+    #[error("DPoP nonce key unavailable: {0}")]
+    NonceKey(String),
+}
+
 pub type AuthResult<T> = Result<T, AuthError>;
 
 pub type JwtResult<T> = Result<T, JwtError>;
 
 pub type ManifestSigningResult<T> = Result<T, ManifestSigningError>;
+
+pub type DpopResult<T> = Result<T, DpopError>;
@@ -1,4 +1,4 @@
-//! Bearer/proxy-header token extraction from requests.
+//! Bearer/`DPoP`/proxy-header token extraction from requests.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -11,6 +11,7 @@ use super::cookie::{CookieExtractionError, CookieExtractor};
 
 const DEFAULT_MCP_HEADER_NAME: &str = "x-mcp-proxy-auth";
 const BEARER_PREFIX: &str = "Bearer ";
+const DPOP_PREFIX: &str = "DPoP ";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExtractionMethod {
@@ -124,7 +125,10 @@ impl TokenExtractor {
                 continue;
             };
 
-            if let Some(token) = auth_header.strip_prefix(BEARER_PREFIX) {
+            if let Some(token) = auth_header
+                .strip_prefix(BEARER_PREFIX)
+                .or_else(|| auth_header.strip_prefix(DPOP_PREFIX))
+            {
                 let token = token.trim();
                 if !token.is_empty() {
                     return Ok(token.to_owned());
@@ -135,6 +139,15 @@ impl TokenExtractor {
         Err(TokenExtractionError::InvalidAuthorizationFormat)
     }
 
+    #[must_use]
+    pub fn uses_dpop_scheme(headers: &HeaderMap) -> bool {
+        headers
+            .get_all("authorization")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .any(|value| value.starts_with(DPOP_PREFIX))
+    }
+
     pub fn extract_from_mcp_proxy(
         &self,
         headers: &HeaderMap,
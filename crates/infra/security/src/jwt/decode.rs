@@ -6,17 +6,19 @@
 //! `nbf` + leeway, first-party `aud`), then re-derives `user_type` from
 //! `scope` so a forged or mis-minted type claim cannot ride past the gate, and
 //! returns the subset of claims the request-context layer consumes
-//! ([`JwtUserContext`]). Issuer pinning is left to the stateful validators
-//! that hold deployment config ([`crate::AuthValidationService`]); this path
-//! instead binds the token to a live session and user row in the database
-//! after decode.
+//! ([`JwtUserContext`]), including the RFC 9449 `cnf.jkt` binding the caller
+//! must check a `DPoP` proof against, the `parent_jti` whose revocation also
+//! revokes this token, and the [`HopTarget`] a derived token is confined to.
+//! Issuer pinning is left to the stateful validators that hold deployment
+//! config ([`crate::AuthValidationService`]); this path instead binds the token
+//! to a live session and user row in the database after decode.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 use std::collections::BTreeMap;
 use systemprompt_identifiers::{Actor, ClientId, SessionId, UserId};
-use systemprompt_models::auth::{Permission, UserType};
+use systemprompt_models::auth::{HopTarget, Permission, UserType};
 
 use super::validate::{ValidationPolicy, decode_rs256_claims};
 use crate::error::{AuthError, AuthResult};
@@ -32,10 +34,14 @@ pub struct JwtUserContext {
     pub attributes: BTreeMap<String, serde_json::Value>,
     pub jti: String,
     pub exp: i64,
+    pub dpop_jkt: Option<String>,
+    pub parent_jti: Option<String>,
+    pub hop_target: Option<HopTarget>,
 }
 
 pub fn extract_user_context(token: &str) -> AuthResult<JwtUserContext> {
     let claims = decode_rs256_claims(token, &ValidationPolicy::session_context())?;
+    let hop_target = claims.hop_target();
 
     let session_id = claims.session_id.ok_or(AuthError::MissingSessionId)?;
     let role = *claims.scope.first().ok_or(AuthError::MissingScope)?;
@@ -62,5 +68,8 @@ pub fn extract_user_context(token: &str) -> AuthResult<JwtUserContext> {
         attributes: claims.attributes,
         jti: claims.jti,
         exp: claims.exp,
+        dpop_jkt: claims.cnf.map(|cnf| cnf.jkt),
+        parent_jti: claims.parent_jti,
+        hop_target,
     })
 }
@@ -0,0 +1,177 @@
+//! In-process RFC 8693 token exchange for agent-to-agent delegation.
+//!
+//! An agent handing work to a peer does not forward the caller's token as it
+//! is, which would let the peer act as the user with no record of who asked.
+//! It mints an actor token naming itself with
+//! [`JwtService::generate_actor_token`] and trades the caller's token for a
+//! delegated one with [`JwtService::exchange_token`]. Both tokens are verified
+//! against this deployment's signing authority; the issued token keeps the
+//! subject's identity, scope and session, and its `act` names the actor with
+//! the subject's own chain nested beneath it, so every hop adds one level. Its
+//! audience is the peer agent alone ([`HopTarget::Agent`]), and its
+//! `parent_jti` names the user-held token the chain started from, so revoking
+//! that token revokes every delegation made with it. Actor tokens carry only
+//! the `internal` audience, so they cannot authenticate a request themselves.
+//!
+//! A `DPoP`-bound token cannot travel past the proxy as it is: the agent and
+//! MCP servers behind it resend it as a bearer token and cannot sign proofs
+//! for the caller's key. Once the proxy has verified the caller's proof it
+//! swaps in [`JwtService::forward_token`]: the same identity without `cnf`,
+//! confined to the one service it is sent to, linked to the user-held token
+//! by `parent_jti`, and valid for minutes.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Utc};
+use systemprompt_identifiers::JwtToken;
+use systemprompt_models::auth::{
+    ActClaim, HopTarget, JwtAudience, JwtClaims, MAX_ACT_CHAIN_DEPTH, Permission, TokenType,
+    UserType,
+};
+
+use super::mint::{JwtService, sign};
+use super::validate::{ValidationPolicy, decode_rs256_claims};
+use crate::error::{AuthError, JwtError, JwtResult};
+
+const ACTOR_TOKEN_MINUTES: i64 = 5;
+const EXCHANGED_TOKEN_HOURS: i64 = 1;
+const FORWARDED_TOKEN_MINUTES: i64 = 15;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ActorTokenParams<'a> {
+    /// Who is acting, e.g. the delegating agent's name.
+    pub actor: &'a str,
+    pub issuer: &'a str,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TokenExchangeParams<'a> {
+    pub subject_token: &'a str,
+    pub actor_token: &'a str,
+    /// The agent the work is handed to; the only audience of the issued token.
+    pub peer: &'a str,
+    pub issuer: &'a str,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ForwardTokenParams<'a> {
+    /// The caller's token, already verified for this request.
+    pub token: &'a str,
+    /// The service the request is forwarded to.
+    pub target: &'a HopTarget,
+    pub issuer: &'a str,
+}
+
+impl JwtService {
+    pub fn generate_actor_token(params: &ActorTokenParams<'_>) -> JwtResult<JwtToken> {
+        let now = Utc::now();
+        let claims = JwtClaims {
+            sub: params.actor.to_owned(),
+            iat: now.timestamp(),
+            exp: (now + Duration::minutes(ACTOR_TOKEN_MINUTES)).timestamp(),
+            nbf: Some(now.timestamp()),
+            iss: params.issuer.to_owned(),
+            aud: vec![JwtAudience::Internal],
+            jti: uuid::Uuid::new_v4().to_string(),
+            scope: vec![Permission::A2a],
+            username: params.actor.to_owned(),
+            email: String::new(),
+            user_type: UserType::A2a,
+            roles: Vec::new(),
+            attributes: BTreeMap::new(),
+            client_id: None,
+            token_type: TokenType::Bearer,
+            auth_time: now.timestamp(),
+            session_id: None,
+            rate_limit_tier: None,
+            plugin_id: None,
+            act: None,
+            cnf: None,
+            parent_jti: None,
+        };
+        sign(&claims)
+    }
+
+    /// Issues a token for the subject of `subject_token` whose `act` names the
+    /// subject of `actor_token`, accepted only on the route to `peer`. Expires
+    /// with the subject token, or after an hour if that is sooner.
+    pub fn exchange_token(params: &TokenExchangeParams<'_>) -> JwtResult<JwtToken> {
+        let subject = decode_rs256_claims(
+            params.subject_token,
+            &ValidationPolicy::issuer_scoped(params.issuer, JwtAudience::FIRST_PARTY),
+        )
+        .map_err(JwtError::Exchange)?;
+        let actor = decode_rs256_claims(
+            params.actor_token,
+            &ValidationPolicy::issuer_scoped(params.issuer, &[JwtAudience::Internal]),
+        )
+        .map_err(JwtError::Exchange)?;
+
+        let parent_jti = subject.root_jti().to_owned();
+        let act = ActClaim {
+            iss: actor.iss,
+            sub: actor.sub,
+            act: Box::new(subject.act),
+        };
+        let depth = act.depth();
+        if depth > MAX_ACT_CHAIN_DEPTH {
+            return Err(JwtError::Exchange(AuthError::ActChainTooDeep {
+                depth,
+                max: MAX_ACT_CHAIN_DEPTH,
+            }));
+        }
+
+        let now = Utc::now();
+        let exp = subject
+            .exp
+            .min((now + Duration::hours(EXCHANGED_TOKEN_HOURS)).timestamp());
+        // Why: the proof that bound the subject token was checked where the
+        // caller presented it; the peer cannot produce one for that key, so
+        // the delegated token is a short-lived bearer token instead.
+        let claims = JwtClaims {
+            iat: now.timestamp(),
+            exp,
+            nbf: Some(now.timestamp()),
+            aud: HopTarget::Agent(params.peer.to_owned()).audiences(),
+            jti: uuid::Uuid::new_v4().to_string(),
+            parent_jti: Some(parent_jti),
+            act: Some(act),
+            cnf: None,
+            ..subject
+        };
+        sign(&claims)
+    }
+
+    /// Issues the caller's identity for the service behind the proxy: no
+    /// `cnf`, an audience naming only `target`, and the original `act`. Only
+    /// call it after the caller's token, and its proof if bound, have been
+    /// verified. Expires with that token, or after fifteen minutes if that is
+    /// sooner.
+    pub fn forward_token(params: &ForwardTokenParams<'_>) -> JwtResult<JwtToken> {
+        let caller = decode_rs256_claims(
+            params.token,
+            &ValidationPolicy::issuer_scoped(params.issuer, JwtAudience::FIRST_PARTY),
+        )
+        .map_err(JwtError::Exchange)?;
+
+        let now = Utc::now();
+        let exp = caller
+            .exp
+            .min((now + Duration::minutes(FORWARDED_TOKEN_MINUTES)).timestamp());
+        let parent_jti = caller.root_jti().to_owned();
+        let claims = JwtClaims {
+            iat: now.timestamp(),
+            exp,
+            nbf: Some(now.timestamp()),
+            aud: params.target.audiences(),
+            jti: uuid::Uuid::new_v4().to_string(),
+            parent_jti: Some(parent_jti),
+            cnf: None,
+            ..caller
+        };
+        sign(&claims)
+    }
+}
@@ -57,14 +57,21 @@ impl JwtService {
             rate_limit_tier: Some(RateLimitTier::Admin),
             plugin_id: None,
             act: None,
+            cnf: None,
+            parent_jti: None,
         };
 
-        let kid = authority::active_kid().map_err(|e| JwtError::Signing(e.to_string()))?;
-        let mut header = Header::new(Algorithm::RS256);
-        header.kid = Some(kid.to_owned());
-        let key = authority::encoding_key().map_err(|e| JwtError::Signing(e.to_string()))?;
-        let token = encode(&header, &claims, key).map_err(JwtError::from)?;
-
-        Ok(JwtToken::new(token))
+        sign(&claims)
     }
 }
+
+/// Signs `claims` with the active key of the signing authority.
+pub(super) fn sign(claims: &JwtClaims) -> JwtResult<JwtToken> {
+    let kid = authority::active_kid().map_err(|e| JwtError::Signing(e.to_string()))?;
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(kid.to_owned());
+    let key = authority::encoding_key().map_err(|e| JwtError::Signing(e.to_string()))?;
+    let token = encode(&header, claims, key).map_err(JwtError::from)?;
+
+    Ok(JwtToken::new(token))
+}
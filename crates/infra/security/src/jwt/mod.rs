@@ -1,11 +1,16 @@
 //! JWT plane.
 //!
-//! Two stateless surfaces, each a free function or unit-struct method that
+//! Three stateless surfaces, each a free function or unit-struct method that
 //! never holds JWT state of its own:
 //!
 //! - [`mint`] — issues administrator-scoped RS256 tokens via
 //!   [`JwtService::generate_admin_token`]. Session-scoped tokens are minted by
 //!   [`crate::session::SessionGenerator`] instead.
+//! - [`exchange`] — in-process RFC 8693 token exchange for agent-to-agent
+//!   delegation: [`JwtService::exchange_token`] re-issues a caller's token for
+//!   one peer agent with the delegating agent recorded in its `act` chain, and
+//!   [`JwtService::forward_token`] reissues a verified caller token for the one
+//!   service the proxy forwards it to, without its `DPoP` binding.
 //! - [`decode`] — turns a raw `Bearer …` string into a typed
 //!   [`JwtUserContext`], enforcing kid + RS256, re-deriving `user_type` from
 //!   `scope` (defence-in-depth against a forged claim), and surfacing every
@@ -20,9 +25,11 @@
 //! See <https://systemprompt.io> for licensing details.
 
 pub mod decode;
+pub mod exchange;
 pub mod mint;
 pub mod validate;
 
 pub use decode::{JwtUserContext, extract_user_context};
+pub use exchange::{ActorTokenParams, ForwardTokenParams, TokenExchangeParams};
 pub use mint::{AdminTokenParams, JwtService};
 pub use validate::{JWT_LEEWAY_SECONDS, ValidationPolicy, decode_rs256_claims};
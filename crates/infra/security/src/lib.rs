@@ -19,6 +19,8 @@
 //! - At-rest hashing ([`at_rest`]) — `hmac_sha256` / `hmac_sha256_hex` under
 //!   the deployment `oauth_at_rest_pepper`, used to store refresh-token ids and
 //!   authorisation codes as digests rather than plaintext.
+//! - `DPoP` ([`dpop`]) — RFC 9449 proof verification and stateless nonces for
+//!   sender-constrained tokens bound through the `cnf.jkt` claim.
 //! - Bridge manifest signing ([`manifest_signing`]) with Ed25519 keys.
 //! - Lightweight scanner / bot detection ([`services`]).
 //! - Authorization decision plane ([`authz`]) — deny-overrides resolver,
@@ -54,6 +56,7 @@
 pub mod at_rest;
 pub mod auth;
 pub mod authz;
+pub mod dpop;
 pub mod error;
 pub mod extraction;
 pub mod jwt;
@@ -68,7 +71,8 @@ pub use at_rest::{hmac_sha256, hmac_sha256_hex};
 pub use auth::{AuthValidationService, HookTokenValidator, ValidatedHookClaims};
 pub use authz::CompositeAuthzHook;
 pub use error::{
-    AuthError, AuthResult, JwtError, JwtResult, ManifestSigningError, ManifestSigningResult,
+    AuthError, AuthResult, DpopError, DpopResult, JwtError, JwtResult, ManifestSigningError,
+    ManifestSigningResult,
 };
 pub use extraction::{
     CookieExtractionError, CookieExtractor, ExtractionMethod, HeaderExtractor,
@@ -19,6 +19,8 @@ use super::types::AccessScope;
 use crate::authz::types::{Decision, DecisionTag};
 use crate::authz::{GovernanceDecisionRecord, insert_governance_decision};
 
+/// `Shadow` is a deny from a policy running with `enforce: shadow`: recorded,
+/// never acted on.
 #[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(tag = "result", rename_all = "lowercase")]
 pub enum ChainEntryResult {
@@ -26,6 +28,7 @@ pub enum ChainEntryResult {
     Fail,
     Disabled,
     Skip,
+    Shadow,
 }
 
 /// One traced chain entry: which policy, what it decided, and what it cost.
@@ -94,8 +97,12 @@ pub struct DecisionAudit {
 // Why: an allow because nothing ran and an allow because everything passed are
 // the same `Decision`, and the flat `policy` column is what operational queries
 // filter on. Collapsing both to `default_allow` would make an unguarded
-// installation indistinguishable from a healthy one.
+// installation indistinguishable from a healthy one. For the same reason an
+// allow that a shadow policy would have denied is labelled `shadow_deny`.
 fn allow_policy_label(chain: &[ChainEntryOutcome]) -> &'static str {
+    if chain.iter().any(|e| e.result == ChainEntryResult::Shadow) {
+        return "shadow_deny";
+    }
     if !chain.is_empty() && chain.iter().all(|e| e.result == ChainEntryResult::Disabled) {
         return "governance_disabled";
     }
@@ -111,7 +118,11 @@ pub async fn record_decision(pool: &PgPool, audit: &DecisionAudit) -> Result<(),
     let (decision_tag, reason_str, policy_str) = match &audit.decision {
         Decision::Allow { .. } => (
             DecisionTag::Allow,
-            String::new(),
+            audit
+                .chain
+                .iter()
+                .find(|e| e.result == ChainEntryResult::Shadow)
+                .map_or_else(String::new, |e| format!("{}: {}", e.policy_id, e.detail)),
             allow_policy_label(&audit.chain).to_owned(),
         ),
         Decision::Deny { reason } => {
@@ -0,0 +1,435 @@
+//! `argument_constraints`: declarative conditions over tool-call arguments.
+//!
+//! Each rule names the tools it governs (`*` globs; omitted means every tool),
+//! optionally the access scopes it applies to, a JSON pointer into the
+//! arguments, and the conditions the value there must meet:
+//!
+//! ```yaml
+//! - id: argument_constraints
+//!   rules:
+//!     - id: data_root
+//!       tools: ["filesystem_*"]
+//!       scopes: [user, unknown]
+//!       pointer: /path
+//!       under: ["/srv/data"]
+//!     - id: payment_cap
+//!       tools: ["create_payment"]
+//!       pointer: /amount
+//!       required: true
+//!       exclusive_maximum: 1000
+//!     - id: regions
+//!       pointer: /targets/*/region
+//!       one_of: ["eu-west-1", "eu-central-1"]
+//! ```
+//!
+//! A `*` pointer segment fans out over every element or member, so every
+//! resolved value must pass. An absent or `null` value passes unless the rule
+//! is `required`. `under` compares lexically normalised absolute paths — `..`
+//! cannot climb out of the prefix, but symlinks are not resolved. `pattern` is
+//! an unanchored regex. Bounds mirror JSON Schema (`minimum`, `maximum`,
+//! `exclusive_minimum`, `exclusive_maximum`).
+//!
+//! A rule that cannot be built — a bad regex, an unknown scope, no condition —
+//! is logged and denies every call it would have governed, so a typo never
+//! silently widens what a tool may do.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use serde_yaml::Value as YamlValue;
+use systemprompt_identifiers::{McpToolName, PolicyId};
+
+use super::super::registry::PolicyRegistration;
+use super::super::types::{AccessScope, ConstraintViolation, GovernancePolicy, PolicyContext};
+use crate::authz::ingestion::glob::glob_matches;
+use crate::authz::types::{Decision, DenyReason, MatchedBy};
+
+const ID: &str = "argument_constraints";
+
+#[derive(Debug)]
+struct ArgumentConstraints {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    id: String,
+    tools: Vec<String>,
+    scopes: Vec<AccessScope>,
+    pointer: String,
+    required: bool,
+    checks: Result<Vec<Check>, String>,
+}
+
+#[derive(Debug)]
+enum Check {
+    Under(Vec<String>),
+    Pattern(Regex),
+    Bound(Bound, f64),
+    OneOf(Vec<JsonValue>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Bound {
+    Minimum,
+    Maximum,
+    ExclusiveMinimum,
+    ExclusiveMaximum,
+}
+
+impl Bound {
+    const ALL: [(&'static str, Self); 4] = [
+        ("minimum", Self::Minimum),
+        ("maximum", Self::Maximum),
+        ("exclusive_minimum", Self::ExclusiveMinimum),
+        ("exclusive_maximum", Self::ExclusiveMaximum),
+    ];
+
+    const fn symbol(self) -> &'static str {
+        match self {
+            Self::Minimum => ">=",
+            Self::Maximum => "<=",
+            Self::ExclusiveMinimum => ">",
+            Self::ExclusiveMaximum => "<",
+        }
+    }
+
+    fn admits(self, value: f64, limit: f64) -> bool {
+        match self {
+            Self::Minimum => value >= limit,
+            Self::Maximum => value <= limit,
+            Self::ExclusiveMinimum => value > limit,
+            Self::ExclusiveMaximum => value < limit,
+        }
+    }
+}
+
+fn string_list(v: &YamlValue, key: &str) -> Option<Vec<String>> {
+    match v.get(key)? {
+        YamlValue::String(s) => Some(vec![s.clone()]),
+        YamlValue::Sequence(seq) => Some(
+            seq.iter()
+                .filter_map(|s| s.as_str().map(str::to_owned))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+impl ArgumentConstraints {
+    fn from_yaml(v: &YamlValue) -> Self {
+        let rules = v
+            .get("rules")
+            .and_then(YamlValue::as_sequence)
+            .map(|seq| {
+                seq.iter()
+                    .enumerate()
+                    .map(|(index, entry)| Rule::from_yaml(index, entry))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { rules }
+    }
+}
+
+impl Rule {
+    fn from_yaml(index: usize, v: &YamlValue) -> Self {
+        let id = v
+            .get("id")
+            .and_then(YamlValue::as_str)
+            .map_or_else(|| format!("rules[{index}]"), str::to_owned);
+        let pointer = v
+            .get("pointer")
+            .and_then(YamlValue::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        let scopes = string_list(v, "scopes")
+            .unwrap_or_default()
+            .iter()
+            .map(|s| AccessScope::from_str(s).map_err(|e| e.to_string()))
+            .collect::<Result<Vec<_>, _>>();
+        let checks = match &scopes {
+            Ok(_) if !pointer.is_empty() && !pointer.starts_with('/') => Err(format!(
+                "pointer {pointer:?} must be empty or start with '/'"
+            )),
+            Ok(_) => checks_from_yaml(v),
+            Err(e) => Err(e.clone()),
+        };
+        if let Err(detail) = &checks {
+            tracing::error!(
+                rule = %id,
+                %detail,
+                "argument_constraints: rule is misconfigured and will deny every call it governs"
+            );
+        }
+        Self {
+            id,
+            tools: string_list(v, "tools").unwrap_or_default(),
+            scopes: scopes.unwrap_or_default(),
+            pointer,
+            required: v
+                .get("required")
+                .and_then(YamlValue::as_bool)
+                .unwrap_or(false),
+            checks,
+        }
+    }
+
+    fn governs(&self, tool: &McpToolName, scope: AccessScope) -> bool {
+        (self.tools.is_empty() || self.tools.iter().any(|p| glob_matches(p, tool.as_str())))
+            && (self.scopes.is_empty() || self.scopes.contains(&scope))
+    }
+
+    fn check(&self, arguments: &JsonValue) -> Option<(String, ConstraintViolation)> {
+        let checks = match &self.checks {
+            Ok(checks) => checks,
+            Err(detail) => {
+                return Some((
+                    self.pointer.clone(),
+                    ConstraintViolation::Misconfigured {
+                        detail: detail.clone(),
+                    },
+                ));
+            },
+        };
+        let values: Vec<(String, &JsonValue)> = resolve(arguments, &self.pointer)
+            .into_iter()
+            .filter(|(_, value)| !value.is_null())
+            .collect();
+        if values.is_empty() && self.required {
+            return Some((self.pointer.clone(), ConstraintViolation::Missing));
+        }
+        values.into_iter().find_map(|(pointer, value)| {
+            checks
+                .iter()
+                .find_map(|check| check.violation(value))
+                .map(|violation| (pointer, violation))
+        })
+    }
+}
+
+fn checks_from_yaml(v: &YamlValue) -> Result<Vec<Check>, String> {
+    let mut checks = Vec::new();
+    if let Some(prefixes) = string_list(v, "under") {
+        let normalised = prefixes
+            .iter()
+            .map(|p| normalise_path(p).ok_or_else(|| format!("under entry {p:?} is not absolute")))
+            .collect::<Result<Vec<_>, _>>()?;
+        if normalised.is_empty() {
+            return Err("under lists no paths".to_owned());
+        }
+        checks.push(Check::Under(normalised));
+    }
+    if let Some(expr) = v.get("pattern").and_then(YamlValue::as_str) {
+        let re = Regex::new(expr).map_err(|e| format!("pattern {expr:?}: {e}"))?;
+        checks.push(Check::Pattern(re));
+    }
+    for (key, bound) in Bound::ALL {
+        if let Some(raw) = v.get(key) {
+            let limit = raw
+                .as_f64()
+                .ok_or_else(|| format!("{key} must be a number"))?;
+            checks.push(Check::Bound(bound, limit));
+        }
+    }
+    if let Some(raw) = v.get("one_of") {
+        let allowed = raw
+            .as_sequence()
+            .filter(|seq| !seq.is_empty())
+            .ok_or_else(|| "one_of must be a non-empty list".to_owned())?
+            .iter()
+            .map(|item| serde_json::to_value(item).map_err(|e| format!("one_of: {e}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        checks.push(Check::OneOf(allowed));
+    }
+    if checks.is_empty() && v.get("required").and_then(YamlValue::as_bool) != Some(true) {
+        return Err("rule declares no condition".to_owned());
+    }
+    Ok(checks)
+}
+
+impl Check {
+    fn violation(&self, value: &JsonValue) -> Option<ConstraintViolation> {
+        match self {
+            Self::Under(prefixes) => {
+                let Some(path) = value.as_str() else {
+                    return Some(wrong_type("a path string"));
+                };
+                let inside = normalise_path(path)
+                    .is_some_and(|path| prefixes.iter().any(|prefix| is_within(&path, prefix)));
+                (!inside).then(|| ConstraintViolation::OutsidePaths {
+                    allowed: prefixes.clone(),
+                })
+            },
+            Self::Pattern(re) => {
+                let Some(text) = value.as_str() else {
+                    return Some(wrong_type("a string"));
+                };
+                (!re.is_match(text)).then(|| ConstraintViolation::PatternMismatch {
+                    pattern: re.as_str().to_owned(),
+                })
+            },
+            Self::Bound(bound, limit) => {
+                let Some(number) = value.as_f64() else {
+                    return Some(wrong_type("a number"));
+                };
+                (!bound.admits(number, *limit)).then(|| ConstraintViolation::OutOfRange {
+                    bound: format!("{} {limit}", bound.symbol()),
+                })
+            },
+            Self::OneOf(allowed) => (!allowed.iter().any(|a| same_scalar(a, value))).then(|| {
+                ConstraintViolation::NotAllowed {
+                    allowed: allowed.iter().map(ToString::to_string).collect(),
+                }
+            }),
+        }
+    }
+}
+
+fn wrong_type(expected: &str) -> ConstraintViolation {
+    ConstraintViolation::WrongType {
+        expected: expected.to_owned(),
+    }
+}
+
+// Why: YAML `1000` and JSON `1000.0` are the same amount; comparing the
+// `Value`s directly would reject one of them.
+fn same_scalar(allowed: &JsonValue, value: &JsonValue) -> bool {
+    match (allowed.as_f64(), value.as_f64()) {
+        (Some(a), Some(b)) => (a - b).abs() < f64::EPSILON,
+        _ => allowed == value,
+    }
+}
+
+/// Resolves an RFC 6901 pointer, with `*` matching every element or member.
+/// Each hit carries its concrete pointer for the deny reason.
+fn resolve<'a>(root: &'a JsonValue, pointer: &str) -> Vec<(String, &'a JsonValue)> {
+    let mut found = vec![(String::new(), root)];
+    for raw in pointer.split('/').skip(1) {
+        let token = raw.replace("~1", "/").replace("~0", "~");
+        found = found
+            .into_iter()
+            .flat_map(|(at, value)| step(&at, value, &token))
+            .collect();
+    }
+    found
+}
+
+fn step<'a>(at: &str, value: &'a JsonValue, token: &str) -> Vec<(String, &'a JsonValue)> {
+    match value {
+        JsonValue::Array(items) if token == "*" => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (format!("{at}/{index}"), item))
+            .collect(),
+        JsonValue::Object(map) if token == "*" => map
+            .iter()
+            .map(|(key, item)| (format!("{at}/{}", escape(key)), item))
+            .collect(),
+        JsonValue::Array(items) => token
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| items.get(index))
+            .map(|item| (format!("{at}/{token}"), item))
+            .into_iter()
+            .collect(),
+        JsonValue::Object(map) => map
+            .get(token)
+            .map(|item| (format!("{at}/{}", escape(token)), item))
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Lexically normalises an absolute path, or `None` when it is relative or
+/// `..` climbs above the root.
+fn normalise_path(path: &str) -> Option<String> {
+    if !path.starts_with('/') {
+        return None;
+    }
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {},
+            ".." => {
+                parts.pop()?;
+            },
+            other => parts.push(other),
+        }
+    }
+    Some(format!("/{}", parts.join("/")))
+}
+
+fn is_within(path: &str, prefix: &str) -> bool {
+    prefix == "/"
+        || path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+impl GovernancePolicy for ArgumentConstraints {
+    fn id(&self) -> PolicyId {
+        PolicyId::new(ID)
+    }
+    fn name(&self) -> &'static str {
+        "Argument Constraints"
+    }
+    fn description(&self) -> &'static str {
+        "Check tool-call arguments against per-tool, per-scope rules: allowed path \
+         roots, regex patterns, numeric ranges, and value allowlists."
+    }
+    fn evaluate(&self, ctx: &PolicyContext<'_>) -> Decision {
+        let allow = |detail| Decision::Allow {
+            matched_by: MatchedBy::PolicyAllow {
+                policy_id: PolicyId::new(ID),
+                detail,
+            },
+        };
+        let (Some(tool), Some(arguments)) = (ctx.target.tool(), ctx.input.arguments()) else {
+            return allow(Cow::Borrowed("Not a tool call"));
+        };
+
+        let mut applied = 0usize;
+        for rule in self
+            .rules
+            .iter()
+            .filter(|rule| rule.governs(tool, ctx.access_scope))
+        {
+            applied += 1;
+            if let Some((pointer, violation)) = rule.check(arguments.as_value()) {
+                return Decision::Deny {
+                    reason: DenyReason::ArgumentViolation {
+                        tool: tool.clone(),
+                        rule_id: rule.id.clone(),
+                        pointer,
+                        violation,
+                    },
+                };
+            }
+        }
+
+        if applied == 0 {
+            allow(Cow::Borrowed("No argument rule governs this tool"))
+        } else {
+            allow(Cow::Owned(format!("{applied} argument rule(s) passed")))
+        }
+    }
+}
+
+inventory::submit! {
+    PolicyRegistration {
+        id: ID,
+        factory: |v| Box::new(ArgumentConstraints::from_yaml(v)),
+    }
+}
@@ -1,7 +1,9 @@
-//! The four built-in governance policies.
+//! The built-in governance policies.
 //!
-//! Each registers itself with the [`super::registry`] under a stable id and is
-//! enabled by [`super::GovernanceConfig::defaults`]:
+//! Each registers itself with the [`super::registry`] under a stable id. The
+//! first four are enabled by [`super::GovernanceConfig::defaults`];
+//! `argument_constraints` has nothing to check until a deployment declares
+//! rules, so it runs only when listed in `governance.policies`:
 //!
 //! | id | denies with |
 //! |----|-------------|
@@ -9,10 +11,12 @@
 //! | `scope_check` | [`DenyReason::ScopeViolation`][crate::authz::DenyReason::ScopeViolation] |
 //! | `tool_blocklist` | [`DenyReason::ToolBlocked`][crate::authz::DenyReason::ToolBlocked] |
 //! | `rate_limit` | [`DenyReason::RateLimitExceeded`][crate::authz::DenyReason::RateLimitExceeded] |
+//! | `argument_constraints` | [`DenyReason::ArgumentViolation`][crate::authz::DenyReason::ArgumentViolation] |
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
+mod argument_constraints;
 mod rate_limit;
 mod scope_check;
 mod secret_scan;
@@ -1,14 +1,30 @@
 //! `rate_limit`: per-`{session,user}` sliding-window limiter.
 //!
-//! State is instance-scoped: each engine built by
-//! [`super::super::GovernanceEngine::from_config`] gets its own window, so
-//! shared enforcement means holding one engine per process.
+//! With the default `backend: memory` state is instance-scoped: each engine
+//! built by [`super::super::GovernanceEngine::from_config`] gets its own
+//! window, so shared enforcement means holding one engine per process, and N
+//! replicas grant N times the budget. `backend: postgres` charges the window
+//! kept by [`super::super::rate_limit_store`] instead, so every replica counts
+//! against one budget. When that store is not installed, errors, or does not
+//! answer in time the call is refused; `on_store_error: memory` charges it to
+//! the in-memory window instead, keeping per-replica enforcement. Either way
+//! the miss is logged and counted in
+//! [`super::super::rate_limit_store::RATE_LIMIT_STORE_UNAVAILABLE_TOTAL`].
+//!
+//! Shared buckets are keyed by the limiter's `name` as well as the caller, so
+//! two configured limiters — an enforced one and a shadow one, say — each
+//! keep their own window in the table. Without a `name` the window and limit
+//! stand in for it; every replica derives the same key from the same config.
 //!
 //! Configurable via:
 //! ```yaml
 //! - id: rate_limit
 //!   requests_per_window: 300
 //!   window_secs: 60
+//!   name: per-caller         # default: <window_secs>s:<requests_per_window>
+//!   backend: postgres        # default: memory
+//!   store_timeout_ms: 500
+//!   on_store_error: deny     # default: deny; or memory
 //! ```
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
@@ -22,6 +38,9 @@ use std::time::{Duration, Instant};
 use serde_yaml::Value as YamlValue;
 use systemprompt_identifiers::{CallId, PolicyId, SessionId, UserId};
 
+use super::super::rate_limit_store::{
+    RATE_LIMIT_STORE_UNAVAILABLE_TOTAL, SharedCharge, charge_blocking,
+};
 use super::super::registry::PolicyRegistration;
 use super::super::types::{GovernancePolicy, PolicyContext, RateLimitWindow};
 use crate::authz::types::{Decision, DenyReason, MatchedBy};
@@ -29,11 +48,78 @@ use crate::authz::types::{Decision, DenyReason, MatchedBy};
 const ID: &str = "rate_limit";
 const DEFAULT_WINDOW_SECS: u64 = 60;
 const DEFAULT_LIMIT: usize = 300;
+const DEFAULT_STORE_TIMEOUT_MS: u64 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    Memory,
+    Postgres {
+        timeout: Duration,
+        on_error: OnStoreError,
+    },
+}
+
+/// What a `backend: postgres` limiter does with a call the shared window
+/// could not charge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OnStoreError {
+    Deny,
+    Memory,
+}
+
+impl OnStoreError {
+    fn from_yaml(v: &YamlValue) -> Self {
+        match v.get("on_store_error").and_then(YamlValue::as_str) {
+            None | Some("deny") => Self::Deny,
+            Some("memory") => Self::Memory,
+            Some(other) => {
+                tracing::warn!(
+                    on_store_error = other,
+                    "rate_limit: unknown on_store_error; refusing calls the shared window cannot \
+                     charge"
+                );
+                Self::Deny
+            },
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Deny => "deny",
+            Self::Memory => "memory",
+        }
+    }
+}
+
+impl Backend {
+    fn from_yaml(v: &YamlValue) -> Self {
+        match v.get("backend").and_then(YamlValue::as_str) {
+            None | Some("memory") => Self::Memory,
+            Some("postgres") => Self::Postgres {
+                timeout: Duration::from_millis(
+                    v.get("store_timeout_ms")
+                        .and_then(YamlValue::as_u64)
+                        .unwrap_or(DEFAULT_STORE_TIMEOUT_MS),
+                ),
+                on_error: OnStoreError::from_yaml(v),
+            },
+            Some(other) => {
+                tracing::warn!(
+                    backend = other,
+                    "rate_limit: unknown backend; using the in-memory window"
+                );
+                Self::Memory
+            },
+        }
+    }
+}
 
 #[derive(Debug)]
 struct RateLimit {
     window_secs: u64,
     limit: usize,
+    instance: String,
+    backend: Backend,
     counters: Mutex<SlidingWindow>,
 }
 
@@ -47,9 +133,15 @@ impl RateLimit {
             .get("requests_per_window")
             .and_then(YamlValue::as_u64)
             .map_or(DEFAULT_LIMIT, |n| n as usize);
+        let instance = v
+            .get("name")
+            .and_then(YamlValue::as_str)
+            .map_or_else(|| format!("{window_secs}s:{limit}"), str::to_owned);
         Self {
             window_secs,
             limit,
+            instance,
+            backend: Backend::from_yaml(v),
             counters: Mutex::new(SlidingWindow::default()),
         }
     }
@@ -116,6 +208,56 @@ fn key_for(session_id: &SessionId, user_id: &UserId) -> String {
     format!("{}:{}", session_id.as_str(), user_id.as_str())
 }
 
+impl RateLimit {
+    fn charge_local(&self, key: &str, call_id: &CallId) -> usize {
+        self.counters
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .check_and_record(
+                &ChargeRequest {
+                    key,
+                    call_id,
+                    window_secs: self.window_secs,
+                },
+                self.limit,
+            )
+    }
+
+    /// How many live charges preceded `call_id`, or `None` when the shared
+    /// window could not charge it and the limiter refuses such calls.
+    fn charge(&self, key: &str, call_id: &CallId) -> Option<usize> {
+        let Backend::Postgres { timeout, on_error } = self.backend else {
+            return Some(self.charge_local(key, call_id));
+        };
+        let shared = SharedCharge {
+            bucket_key: format!("{}:{key}", self.instance),
+            call_id: call_id.clone(),
+            window_secs: self.window_secs,
+            limit: self.limit as u64,
+        };
+        match charge_blocking(shared, timeout) {
+            Ok(count) => Some(count as usize),
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    on_store_error = on_error.as_str(),
+                    "rate_limit: shared window unavailable"
+                );
+                metrics::counter!(
+                    RATE_LIMIT_STORE_UNAVAILABLE_TOTAL,
+                    "reason" => e.label(),
+                    "on_store_error" => on_error.as_str(),
+                )
+                .increment(1);
+                match on_error {
+                    OnStoreError::Deny => None,
+                    OnStoreError::Memory => Some(self.charge_local(key, call_id)),
+                }
+            },
+        }
+    }
+}
+
 impl GovernancePolicy for RateLimit {
     fn id(&self) -> PolicyId {
         PolicyId::new(ID)
@@ -124,23 +266,20 @@ impl GovernancePolicy for RateLimit {
         "Rate Limit"
     }
     fn description(&self) -> &'static str {
-        "Sliding-window per-session per-user request limiter. Stops a single \
-         caller from monopolising the gateway or exfiltrating data via volume."
+        "Sliding-window per-session per-user request limiter, in memory or \
+         shared across replicas through PostgreSQL. Stops a single caller from \
+         monopolising the gateway or exfiltrating data via volume."
     }
     fn evaluate(&self, ctx: &PolicyContext<'_>) -> Decision {
         let key = key_for(ctx.session_id, ctx.user_id);
-        let count = self
-            .counters
-            .lock()
-            .unwrap_or_else(std::sync::PoisonError::into_inner)
-            .check_and_record(
-                &ChargeRequest {
-                    key: &key,
-                    call_id: ctx.call_id,
-                    window_secs: self.window_secs,
+        let Some(count) = self.charge(&key, ctx.call_id) else {
+            return Decision::Deny {
+                reason: DenyReason::PolicyViolation {
+                    policy: ID.to_owned(),
+                    detail: Cow::Borrowed("the shared rate-limit window is unavailable"),
                 },
-                self.limit,
-            );
+            };
+        };
 
         let window = RateLimitWindow {
             name: ID.to_owned(),
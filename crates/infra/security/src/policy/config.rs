@@ -1,8 +1,10 @@
 //! Governance-chain configuration.
 //!
 //! One YAML document (`governance.enabled` plus `governance.policies: [{id,
-//! enabled, ...params}]`) declares whether the chain runs at all, which
-//! policies it contains, in what order, and with what per-policy parameters.
+//! enabled, enforce, ...params}]`) declares whether the chain runs at all,
+//! which policies it contains, in what order, and with what per-policy
+//! parameters. `enforce: shadow` runs a policy without letting it deny; see
+//! [`super::engine`].
 //!
 //! Two loaders, because startup and the request path want opposite failure
 //! modes. [`GovernanceConfig::validate`] is for boot: it returns the error so
@@ -28,6 +30,8 @@ use std::path::Path;
 use serde_yaml::Value as YamlValue;
 use thiserror::Error;
 
+use crate::authz::types::EnforceMode;
+
 #[derive(Debug, Error)]
 pub enum GovernanceConfigError {
     #[error("governance config is not valid YAML: {0}")]
@@ -36,16 +40,23 @@ pub enum GovernanceConfigError {
     MissingPolicies,
     #[error("governance config policy entry {index} has no string `id`")]
     MissingPolicyId { index: usize },
+    #[error(
+        "governance config policy entry {index} has `enforce: {value}`; expected `enforce` or \
+         `shadow`"
+    )]
+    InvalidEnforce { index: usize, value: String },
     #[error("governance config exists but could not be read: {0}")]
     Unreadable(#[from] std::io::Error),
 }
 
-/// One entry of the configured chain: which policy, whether it runs, and the
-/// raw YAML mapping handed to the policy's factory as parameters.
+/// One entry of the configured chain: which policy, whether it runs, whether
+/// its denials count, and the raw YAML mapping handed to the policy's factory
+/// as parameters.
 #[derive(Debug, Clone)]
 pub struct PolicyConfig {
     pub id: String,
     pub enabled: bool,
+    pub enforce: EnforceMode,
     pub params: YamlValue,
 }
 
@@ -64,6 +75,7 @@ impl GovernanceConfig {
             .map(|id| PolicyConfig {
                 id: id.to_owned(),
                 enabled: true,
+                enforce: EnforceMode::Enforce,
                 params: YamlValue::Null,
             })
             .collect();
@@ -96,9 +108,20 @@ impl GovernanceConfig {
                 .get("enabled")
                 .and_then(YamlValue::as_bool)
                 .unwrap_or(true);
+            let enforce = match entry.get("enforce") {
+                None => EnforceMode::Enforce,
+                Some(value) => value.as_str().and_then(|s| s.parse().ok()).ok_or_else(|| {
+                    GovernanceConfigError::InvalidEnforce {
+                        index,
+                        value: serde_yaml::to_string(value)
+                            .map_or_else(|_| "?".to_owned(), |s| s.trim().to_owned()),
+                    }
+                })?,
+            };
             out.push(PolicyConfig {
                 id,
                 enabled,
+                enforce,
                 params: entry.clone(),
             });
         }
@@ -7,6 +7,13 @@
 //! so the audit row preserves the full evaluation order, not just the first
 //! deny.
 //!
+//! A policy configured with `enforce: shadow` is evaluated like any other, but
+//! its deny is traced as [`ChainEntryResult::Shadow`] and the chain carries
+//! on: the caller sees the decision of the enforced policies alone, and the
+//! audit row shows what the shadow policy would have done. Shadow policies
+//! still keep their state — a shadow rate limiter counts calls — so promoting
+//! one changes nothing but the outcome.
+//!
 //! Policies that accumulate state (the rate limiter) scope it to their
 //! instance, so two engines never share buckets — a second engine would
 //! silently double every budget. [`GovernanceEngine::global`] is therefore the
@@ -29,7 +36,7 @@ use super::audit::{ChainEntryOutcome, ChainEntryResult};
 use super::config::{GovernanceConfig, PolicyConfig};
 use super::registry::{PolicyFactory, PolicyRegistration};
 use super::types::{GovernancePolicy, PolicyContext};
-use crate::authz::types::{Decision, MatchedBy};
+use crate::authz::types::{Decision, EnforceMode, MatchedBy};
 
 /// The outcome of one traced chain run: the first-deny-wins [`Decision`] and
 /// the ordered per-entry trace destined for the audit row.
@@ -111,6 +118,7 @@ impl GovernanceEngine {
             let cfg = PolicyConfig {
                 id: r.id.to_owned(),
                 enabled: false,
+                enforce: EnforceMode::Enforce,
                 params: serde_yaml::Value::Null,
             };
             let instance = (r.factory)(&cfg.params);
@@ -183,6 +191,14 @@ impl GovernanceEngine {
                     detail: allow_detail(matched_by),
                     duration_ms,
                 }),
+                Decision::Deny { reason } if entry.config.enforce.is_shadow() => {
+                    chain.push(ChainEntryOutcome {
+                        policy_id: entry.instance.id(),
+                        result: ChainEntryResult::Shadow,
+                        detail: reason.to_string(),
+                        duration_ms,
+                    });
+                },
                 Decision::Deny { reason } => {
                     chain.push(ChainEntryOutcome {
                         policy_id: entry.instance.id(),
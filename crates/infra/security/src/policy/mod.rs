@@ -9,11 +9,15 @@
 //!   ([`GovernanceConfig`]).
 //! - [`registry`] — inventory registration
 //!   ([`crate::register_governance_policy!`]); the [`builtin`] policies (secret
-//!   scan, scope check, blocklist, rate limit) self-register here.
+//!   scan, scope check, blocklist, rate limit, argument constraints)
+//!   self-register here.
 //! - [`engine`] — [`GovernanceEngine`], the traced first-deny-wins evaluator.
 //! - [`audit`] — [`DecisionAudit`], the typed blob persisted through
 //!   [`record_decision`] into `governance_decisions`.
 //! - [`secrets`] — the shared credential scanner.
+//! - [`rate_limit_store`] — the `PostgreSQL` window behind `rate_limit`'s
+//!   `backend: postgres`, installed once per process by
+//!   [`install_shared_rate_limiter`].
 //!
 //! Decisions are the same typed [`crate::authz::types::Decision`] the
 //! user→entity resolver returns, so a single audit shape and a single CLI
@@ -29,6 +33,7 @@ pub mod builtin;
 pub mod config;
 pub mod engine;
 pub mod governed;
+pub mod rate_limit_store;
 pub mod registry;
 pub mod secrets;
 pub mod types;
@@ -43,6 +48,13 @@ pub use governed::{
     GovernedInput, GovernedString, GovernedTarget, McpToolInput, PROMPT_TARGET_NAME,
     UNKNOWN_TARGET_NAME,
 };
+pub use rate_limit_store::{
+    GovernanceRateLimitRepository, RATE_LIMIT_STORE_UNAVAILABLE_TOTAL, SharedCharge,
+    SharedChargeError, charge_blocking, install_shared_rate_limiter,
+};
 pub use registry::{PolicyFactory, PolicyRegistration};
 pub use secrets::{EntropyConfig, detect_secrets, detect_secrets_with, scan_str_for_secret};
-pub use types::{AgentScope, GovernancePolicy, PolicyContext, RateLimitWindow, SecretLocation};
+pub use types::{
+    AgentScope, ConstraintViolation, GovernancePolicy, PolicyContext, RateLimitWindow,
+    SecretLocation,
+};
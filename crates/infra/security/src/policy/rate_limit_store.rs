@@ -0,0 +1,213 @@
+//! PostgreSQL-backed sliding window for the `rate_limit` policy.
+//!
+//! The in-memory window counts only the calls one process saw, so N replicas
+//! each grant the full budget. With `backend: postgres` every replica charges
+//! the `governance_rate_limit_charges` table instead. A charge runs in one
+//! transaction that first takes `pg_advisory_xact_lock` on the bucket key —
+//! the same advisory-lock coordination the scheduler uses for job claims — so
+//! two replicas charging one bucket count one after the other.
+//!
+//! Policies evaluate synchronously, so [`install_shared_rate_limiter`] records
+//! the write pool together with the runtime that owns it at bootstrap, and
+//! [`charge_blocking`] runs the charge on that runtime while the caller waits
+//! with a bound. On any [`SharedChargeError`] the caller refuses the call, or
+//! with `on_store_error: memory` charges its in-memory window, so a slow or
+//! unreachable database never blocks a governed call or lets it through
+//! uncounted.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use sqlx::PgPool;
+use systemprompt_identifiers::CallId;
+use thiserror::Error;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// Counter of calls a `backend: postgres` limiter could not charge, labelled
+/// by `reason` and by the limiter's `on_store_error`.
+pub const RATE_LIMIT_STORE_UNAVAILABLE_TOTAL: &str =
+    "governance_rate_limit_store_unavailable_total";
+
+#[derive(Debug, Clone)]
+pub struct GovernanceRateLimitRepository {
+    pool: Arc<PgPool>,
+}
+
+/// One charge against a shared window.
+///
+/// `limit` and `window_secs` travel with the charge because each policy
+/// instance carries its own configuration, and `bucket_key` names that
+/// instance as well as the caller.
+#[derive(Debug, Clone)]
+pub struct SharedCharge {
+    pub bucket_key: String,
+    pub call_id: CallId,
+    pub window_secs: u64,
+    pub limit: u64,
+}
+
+impl GovernanceRateLimitRepository {
+    pub const fn from_pool(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Charges `charge` and returns how many live charges preceded it, with
+    /// the same contract as the in-memory window: a call already charged
+    /// returns its original position and is not counted again, and nothing
+    /// is recorded once the bucket holds `limit` charges.
+    pub async fn charge(&self, charge: &SharedCharge) -> Result<u64, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "SELECT pg_advisory_xact_lock(hashtext($1))",
+            charge.bucket_key
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM governance_rate_limit_charges WHERE bucket_key = $1 AND expires_at <= \
+             NOW()",
+            charge.bucket_key
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let window = sqlx::query!(
+            r#"
+            WITH mine AS (
+                SELECT charged_at FROM governance_rate_limit_charges
+                WHERE bucket_key = $1 AND call_id = $2
+            )
+            SELECT
+                (SELECT COUNT(*) FROM governance_rate_limit_charges WHERE bucket_key = $1) AS "live!",
+                (SELECT COUNT(*) FROM governance_rate_limit_charges c, mine
+                 WHERE c.bucket_key = $1 AND c.charged_at < mine.charged_at) AS "before!",
+                EXISTS (SELECT 1 FROM mine) AS "charged!"
+            "#,
+            charge.bucket_key,
+            charge.call_id.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let position = if window.charged {
+            window.before
+        } else {
+            if window.live < charge.limit as i64 {
+                sqlx::query!(
+                    "INSERT INTO governance_rate_limit_charges (bucket_key, call_id, charged_at, \
+                     expires_at) VALUES ($1, $2, NOW(), NOW() + make_interval(secs => $3)) ON \
+                     CONFLICT (bucket_key, call_id) DO NOTHING",
+                    charge.bucket_key,
+                    charge.call_id.as_str(),
+                    charge.window_secs as f64
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            window.live
+        };
+
+        tx.commit().await?;
+        Ok(position.max(0) as u64)
+    }
+
+    pub async fn delete_expired(&self) -> Result<u64, sqlx::Error> {
+        let result =
+            sqlx::query!("DELETE FROM governance_rate_limit_charges WHERE expires_at <= NOW()")
+                .execute(self.pool.as_ref())
+                .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[derive(Debug)]
+struct SharedLimiter {
+    repository: GovernanceRateLimitRepository,
+    runtime: Handle,
+}
+
+static SHARED: OnceLock<SharedLimiter> = OnceLock::new();
+
+/// Makes `pool` the store behind every `backend: postgres` rate limiter in
+/// this process. Call from inside the runtime that owns the pool; later calls
+/// are ignored.
+pub fn install_shared_rate_limiter(pool: Arc<PgPool>) {
+    let Ok(runtime) = Handle::try_current() else {
+        tracing::warn!(
+            "shared rate limiter not installed: no Tokio runtime is running; \
+             `backend: postgres` rate limits cannot charge their shared window"
+        );
+        return;
+    };
+    let installed = SHARED.set(SharedLimiter {
+        repository: GovernanceRateLimitRepository::from_pool(pool),
+        runtime,
+    });
+    if installed.is_err() {
+        tracing::debug!("shared rate limiter already installed; keeping the first pool");
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SharedChargeError {
+    #[error("no shared rate-limit store is installed in this process")]
+    NotInstalled,
+    #[error("cannot wait on the database from a current-thread runtime")]
+    CurrentThreadRuntime,
+    #[error("the database did not answer within {0:?}")]
+    Timeout(Duration),
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl SharedChargeError {
+    /// The metric label for this failure.
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::NotInstalled => "not_installed",
+            Self::CurrentThreadRuntime => "current_thread_runtime",
+            Self::Timeout(_) => "timeout",
+            Self::Database(_) => "database",
+        }
+    }
+}
+
+/// Runs [`GovernanceRateLimitRepository::charge`] on the installed runtime and
+/// waits up to `timeout` for it.
+///
+/// On a multi-thread runtime worker the wait uses `block_in_place`, so other
+/// tasks move off the blocked thread. A current-thread runtime cannot be
+/// blocked without starving the very task it waits on, so it is refused.
+pub fn charge_blocking(charge: SharedCharge, timeout: Duration) -> Result<u64, SharedChargeError> {
+    let shared = SHARED.get().ok_or(SharedChargeError::NotInstalled)?;
+    let on_worker = match Handle::try_current() {
+        Ok(current) if current.runtime_flavor() == RuntimeFlavor::CurrentThread => {
+            return Err(SharedChargeError::CurrentThreadRuntime);
+        },
+        Ok(_) => true,
+        Err(_) => false,
+    };
+
+    let (tx, rx) = std::sync::mpsc::sync_channel(1);
+    let repository = shared.repository.clone();
+    shared.runtime.spawn(async move {
+        // Why: a send fails only when the caller gave up waiting; the charge
+        // itself has committed either way.
+        tx.send(repository.charge(&charge).await).ok();
+    });
+
+    let wait = || rx.recv_timeout(timeout);
+    let received = if on_worker {
+        tokio::task::block_in_place(wait)
+    } else {
+        wait()
+    };
+    received
+        .map_err(|_e| SharedChargeError::Timeout(timeout))?
+        .map_err(SharedChargeError::from)
+}
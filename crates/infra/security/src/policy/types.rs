@@ -56,6 +56,38 @@ impl fmt::Display for SecretLocation {
     }
 }
 
+/// Which condition of an `argument_constraints` rule a tool argument failed.
+///
+/// Carries the rule's side of the comparison only — never the argument value,
+/// which may itself be sensitive and is rendered into the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConstraintViolation {
+    Missing,
+    WrongType { expected: String },
+    OutsidePaths { allowed: Vec<String> },
+    PatternMismatch { pattern: String },
+    OutOfRange { bound: String },
+    NotAllowed { allowed: Vec<String> },
+    Misconfigured { detail: String },
+}
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => f.write_str("required argument is missing"),
+            Self::WrongType { expected } => write!(f, "expected {expected}"),
+            Self::OutsidePaths { allowed } => {
+                write!(f, "path is outside {}", allowed.join(", "))
+            },
+            Self::PatternMismatch { pattern } => write!(f, "does not match {pattern}"),
+            Self::OutOfRange { bound } => write!(f, "must be {bound}"),
+            Self::NotAllowed { allowed } => write!(f, "not one of {}", allowed.join(", ")),
+            Self::Misconfigured { detail } => write!(f, "rule is misconfigured: {detail}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RateLimitWindow {
     pub name: String,
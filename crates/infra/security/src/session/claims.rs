@@ -3,7 +3,7 @@
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
-use systemprompt_identifiers::{SessionId, UserId};
+use systemprompt_identifiers::{Actor, SessionId, UserId};
 use systemprompt_models::auth::UserType;
 
 #[derive(Debug, Clone)]
@@ -13,4 +13,5 @@ pub struct ValidatedSessionClaims {
     pub user_type: UserType,
     pub jti: String,
     pub exp: i64,
+    pub act_chain: Vec<Actor>,
 }
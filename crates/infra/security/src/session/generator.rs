@@ -64,6 +64,8 @@ impl SessionGenerator {
             rate_limit_tier: Some(params.rate_limit_tier),
             plugin_id: None,
             act: None,
+            cnf: None,
+            parent_jti: None,
         };
 
         let kid = authority::active_kid().map_err(|e| JwtError::Signing(e.to_string()))?;
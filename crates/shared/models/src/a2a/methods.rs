@@ -6,6 +6,7 @@
 pub const SEND_MESSAGE: &str = "SendMessage";
 pub const SEND_STREAMING_MESSAGE: &str = "SendStreamingMessage";
 pub const GET_TASK: &str = "GetTask";
+pub const LIST_TASKS: &str = "ListTasks";
 pub const CANCEL_TASK: &str = "CancelTask";
 pub const SUBSCRIBE_TO_TASK: &str = "SubscribeToTask";
 pub const GET_EXTENDED_AGENT_CARD: &str = "GetExtendedAgentCard";
@@ -17,6 +17,8 @@ pub struct McpTool {
     pub service_id: McpServerId,
     #[serde(default)]
     pub terminal_on_success: bool,
+    #[serde(default)]
+    pub requires_approval: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model_config: Option<ToolModelConfig>,
 }
@@ -30,6 +32,7 @@ impl McpTool {
             output_schema: None,
             service_id,
             terminal_on_success: false,
+            requires_approval: false,
             model_config: None,
         }
     }
@@ -54,6 +57,11 @@ impl McpTool {
         self
     }
 
+    pub const fn with_requires_approval(mut self, requires_approval: bool) -> Self {
+        self.requires_approval = requires_approval;
+        self
+    }
+
     pub fn with_model_config(mut self, config: ToolModelConfig) -> Self {
         self.model_config = Some(config);
         self
@@ -3,7 +3,9 @@
 //! [`JwtClaims`] is the canonical token payload; its scope/roles/attributes
 //! fields are the transport for the platform's three authorization layers
 //! (PBAC, RBAC, ABAC). [`ActClaim`] models the recursive `act` delegation
-//! chain, capped at [`MAX_ACT_CHAIN_DEPTH`].
+//! chain, capped at [`MAX_ACT_CHAIN_DEPTH`]. [`ConfirmationClaim`] carries the
+//! RFC 9449 key binding of a DPoP-bound token. `parent_jti` links a derived
+//! token to the user-held token it came from, so revoking that one reaches it.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -14,7 +16,7 @@ use std::str::FromStr;
 use systemprompt_identifiers::{ClientId, SessionId, UserId};
 
 use super::{
-    JwtAudience, Permission, RateLimitTier, TokenType, UserType, parse_permissions,
+    HopTarget, JwtAudience, Permission, RateLimitTier, TokenType, UserType, parse_permissions,
     permissions_to_string,
 };
 use systemprompt_identifiers::Actor;
@@ -66,6 +68,17 @@ impl ActClaim {
     }
 }
 
+/// RFC 7800 confirmation (`cnf`) claim.
+///
+/// Only the RFC 9449 `jkt` member is modelled: the base64url SHA-256 JWK
+/// thumbprint of the key the client proved possession of when the token was
+/// issued. A token carrying `cnf` is only usable with a `DPoP` proof signed by
+/// that key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfirmationClaim {
+    pub jkt: String,
+}
+
 /// Self-issued JWT claim shape.
 ///
 /// Fields are grouped by who consumes them downstream. The platform runs
@@ -118,6 +131,12 @@ pub struct JwtClaims {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub act: Option<ActClaim>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cnf: Option<ConfirmationClaim>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_jti: Option<String>,
 }
 
 fn serialize_audiences<S>(auds: &[JwtAudience], s: S) -> Result<S::Ok, S::Error>
@@ -198,4 +217,17 @@ impl JwtClaims {
     pub fn roles(&self) -> &[String] {
         &self.roles
     }
+
+    pub fn dpop_jkt(&self) -> Option<&str> {
+        self.cnf.as_ref().map(|cnf| cnf.jkt.as_str())
+    }
+
+    /// The `jti` of the user-held token this one derives from, or its own.
+    pub fn root_jti(&self) -> &str {
+        self.parent_jti.as_deref().unwrap_or(&self.jti)
+    }
+
+    pub fn hop_target(&self) -> Option<HopTarget> {
+        HopTarget::from_audiences(&self.aud)
+    }
 }
@@ -1,5 +1,9 @@
 //! The `aud` claim domain: which surface a token is minted for.
 //!
+//! A token derived for one backend (a delegated or forwarded token) also
+//! carries a [`HopTarget`] resource audience naming that backend, so it can be
+//! refused on routes that do not lead there.
+//!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
@@ -48,6 +52,56 @@ impl JwtAudience {
     }
 }
 
+/// The one backend a delegated or forwarded token is confined to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HopTarget {
+    Agent(String),
+    McpServer(String),
+}
+
+impl HopTarget {
+    const AGENT_PREFIX: &'static str = "agent:";
+    const MCP_SERVER_PREFIX: &'static str = "mcp:";
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Agent(name) | Self::McpServer(name) => name,
+        }
+    }
+
+    /// The surface audiences the backend's routes require, then the resource
+    /// audience naming it.
+    pub fn audiences(&self) -> Vec<JwtAudience> {
+        match self {
+            // Why: an agent calls its MCP tools with the token it was sent, so
+            // the MCP proxy's `mcp` audience check must pass for it.
+            Self::Agent(name) => vec![
+                JwtAudience::A2a,
+                JwtAudience::Mcp,
+                JwtAudience::Resource(format!("{}{name}", Self::AGENT_PREFIX)),
+            ],
+            Self::McpServer(name) => vec![
+                JwtAudience::Mcp,
+                JwtAudience::Resource(format!("{}{name}", Self::MCP_SERVER_PREFIX)),
+            ],
+        }
+    }
+
+    pub fn from_audiences(audiences: &[JwtAudience]) -> Option<Self> {
+        audiences.iter().find_map(|aud| {
+            let JwtAudience::Resource(resource) = aud else {
+                return None;
+            };
+            if let Some(name) = resource.strip_prefix(Self::AGENT_PREFIX) {
+                return Some(Self::Agent(name.to_owned()));
+            }
+            resource
+                .strip_prefix(Self::MCP_SERVER_PREFIX)
+                .map(|name| Self::McpServer(name.to_owned()))
+        })
+    }
+}
+
 impl fmt::Display for JwtAudience {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
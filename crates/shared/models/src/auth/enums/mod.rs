@@ -1,6 +1,7 @@
 //! Authentication and authorization enumerations.
 //!
-//! Defines the closed sets the platform reasons over: [`JwtAudience`],
+//! Defines the closed sets the platform reasons over: [`JwtAudience`] (and
+//! the [`HopTarget`] it can name),
 //! [`UserType`], [`TokenType`], [`RateLimitTier`], [`UserRole`], and
 //! [`UserStatus`]. [`UserType::from_permissions`] is the single source of
 //! truth for the permission-to-type mapping.
@@ -12,6 +13,6 @@ mod audience;
 mod caller;
 mod user_state;
 
-pub use audience::JwtAudience;
+pub use audience::{HopTarget, JwtAudience};
 pub use caller::{RateLimitTier, TokenType, UserType};
 pub use user_state::{UserRole, UserStatus};
@@ -52,14 +52,13 @@ pub fn provider_health(
         .advertised_providers()
         .map(|entry| {
             let secret = entry.api_key_secret.as_str();
-            let configured = secret_present(secret);
+            let configured = entry.is_local() || secret_present(secret);
             ProviderHealth {
                 name: entry.name.as_str().to_owned(),
                 surface: entry.surface,
                 configured,
                 models: entry
-                    .models
-                    .iter()
+                    .all_models()
                     .flat_map(|m| {
                         std::iter::once(m.id.as_str().to_owned())
                             .chain(m.aliases.iter().map(|a| a.as_str().to_owned()))
@@ -14,7 +14,9 @@ use std::sync::OnceLock;
 use systemprompt_traits::ConfigProvider;
 
 use crate::auth::JwtAudience;
-use crate::profile::{ContentNegotiationConfig, SecurityHeadersConfig, TrustedIssuer};
+use crate::profile::{
+    ContentNegotiationConfig, OidcProvider, SecurityHeadersConfig, TrustedIssuer,
+};
 
 mod environment;
 mod paths;
@@ -82,6 +84,7 @@ pub struct Config {
     pub security_headers: SecurityHeadersConfig,
     pub allow_registration: bool,
     pub login_page_url: Option<String>,
+    pub oidc_providers: Vec<OidcProvider>,
     pub system_admin_username: String,
     pub system_admin_email: Option<systemprompt_identifiers::Email>,
 }
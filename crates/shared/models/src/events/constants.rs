@@ -25,4 +25,5 @@ pub mod system {
     pub const CONTEXTS_SNAPSHOT: &str = "CONTEXTS_SNAPSHOT";
     pub const CONNECTED: &str = "CONNECTED";
     pub const HEARTBEAT: &str = "HEARTBEAT";
+    pub const BUDGET_THRESHOLD_CROSSED: &str = "BUDGET_THRESHOLD_CROSSED";
 }
@@ -24,7 +24,7 @@ pub use analytics_event::{
     RealTimeStatsPayload, SessionEndedPayload, SessionStartedPayload,
 };
 pub use context_event::ContextEvent;
-pub use payloads::system::ContextSummary;
+pub use payloads::system::{BudgetThresholdCrossedPayload, ContextSummary};
 pub use system_event::{SystemEvent, SystemEventBuilder};
 pub use system_event_type::SystemEventType;
 
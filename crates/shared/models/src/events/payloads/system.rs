@@ -105,3 +105,16 @@ pub struct ContextsSnapshotPayload {
 pub struct ConnectedPayload {
     pub connection_id: systemprompt_identifiers::ConnectionId,
 }
+
+/// A gateway quota window's spend crossing one of its alert thresholds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetThresholdCrossedPayload {
+    pub subject_kind: String,
+    pub subject_id: String,
+    pub window_seconds: i32,
+    pub window_start: DateTime<Utc>,
+    pub threshold_percent: u8,
+    pub spent_microdollars: i64,
+    pub limit_microdollars: i64,
+}
@@ -8,8 +8,8 @@ use serde::{Deserialize, Serialize};
 use systemprompt_identifiers::ContextId;
 
 use super::payloads::system::{
-    ConnectedPayload, ContextCreatedPayload, ContextDeletedPayload, ContextSummary,
-    ContextUpdatedPayload, ContextsSnapshotPayload,
+    BudgetThresholdCrossedPayload, ConnectedPayload, ContextCreatedPayload, ContextDeletedPayload,
+    ContextSummary, ContextUpdatedPayload, ContextsSnapshotPayload,
 };
 use super::system_event_type::SystemEventType;
 
@@ -44,6 +44,11 @@ pub enum SystemEvent {
     Heartbeat {
         timestamp: DateTime<Utc>,
     },
+    BudgetThresholdCrossed {
+        timestamp: DateTime<Utc>,
+        #[serde(flatten)]
+        payload: BudgetThresholdCrossedPayload,
+    },
 }
 
 impl SystemEvent {
@@ -55,6 +60,7 @@ impl SystemEvent {
             Self::ContextsSnapshot { .. } => SystemEventType::ContextsSnapshot,
             Self::Connected { .. } => SystemEventType::Connected,
             Self::Heartbeat { .. } => SystemEventType::Heartbeat,
+            Self::BudgetThresholdCrossed { .. } => SystemEventType::BudgetThresholdCrossed,
         }
     }
 
@@ -65,6 +71,7 @@ impl SystemEvent {
             | Self::ContextDeleted { timestamp, .. }
             | Self::ContextsSnapshot { timestamp, .. }
             | Self::Connected { timestamp, .. }
+            | Self::BudgetThresholdCrossed { timestamp, .. }
             | Self::Heartbeat { timestamp } => *timestamp,
         }
     }
@@ -114,4 +121,11 @@ impl SystemEventBuilder {
             timestamp: Utc::now(),
         }
     }
+
+    pub fn budget_threshold_crossed(payload: BudgetThresholdCrossedPayload) -> SystemEvent {
+        SystemEvent::BudgetThresholdCrossed {
+            timestamp: Utc::now(),
+            payload,
+        }
+    }
 }
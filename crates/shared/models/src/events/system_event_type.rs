@@ -14,6 +14,7 @@ pub enum SystemEventType {
     ContextsSnapshot,
     Connected,
     Heartbeat,
+    BudgetThresholdCrossed,
 }
 
 impl SystemEventType {
@@ -25,6 +26,7 @@ impl SystemEventType {
             Self::ContextsSnapshot => super::constants::system::CONTEXTS_SNAPSHOT,
             Self::Connected => super::constants::system::CONNECTED,
             Self::Heartbeat => super::constants::system::HEARTBEAT,
+            Self::BudgetThresholdCrossed => super::constants::system::BUDGET_THRESHOLD_CROSSED,
         }
     }
 }
@@ -26,6 +26,9 @@ pub enum ContextExtractionError {
     #[error("Token revoked")]
     Revoked,
 
+    #[error("Invalid DPoP proof: {0}")]
+    InvalidDpopProof(String),
+
     #[error("JWT missing required 'session_id' claim")]
     MissingSessionId,
 
@@ -85,6 +85,9 @@ impl InjectContextHeaders for RequestContext {
         if auth_token.is_empty() {
             tracing::trace!(user_id = %self.auth.actor.user_id, "No auth_token to inject - Authorization header not added");
         } else {
+            // Why: the proxy swaps a `DPoP`-bound caller token for its unbound
+            // form before forwarding, so the context token is a bearer token on
+            // every hop behind it.
             let auth_value = format!("Bearer {}", auth_token);
             insert_header(hdrs, headers::AUTHORIZATION, &auth_value);
             tracing::trace!(user_id = %self.auth.actor.user_id, "Injected Authorization header for proxy");
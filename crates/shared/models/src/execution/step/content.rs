@@ -4,9 +4,10 @@
 //! See <https://systemprompt.io> for licensing details.
 
 use serde::{Deserialize, Serialize};
-use systemprompt_identifiers::SkillId;
+use systemprompt_identifiers::{SkillId, TaskId};
 
 use super::enums::StepType;
+use crate::a2a::TaskState;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PlannedTool {
@@ -34,6 +35,13 @@ pub enum StepContent {
         #[serde(skip_serializing_if = "Option::is_none")]
         tool_result: Option<serde_json::Value>,
     },
+    Delegation {
+        agent_name: String,
+        task_id: TaskId,
+        state: TaskState,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
     Completion,
 }
 
@@ -69,6 +77,20 @@ impl StepContent {
         }
     }
 
+    pub fn delegation(
+        agent_name: impl Into<String>,
+        task_id: TaskId,
+        state: TaskState,
+        message: Option<String>,
+    ) -> Self {
+        Self::Delegation {
+            agent_name: agent_name.into(),
+            task_id,
+            state,
+            message,
+        }
+    }
+
     #[must_use]
     pub const fn completion() -> Self {
         Self::Completion
@@ -81,6 +103,7 @@ impl StepContent {
             Self::Planning { .. } => StepType::Planning,
             Self::SkillUsage { .. } => StepType::SkillUsage,
             Self::ToolExecution { .. } => StepType::ToolExecution,
+            Self::Delegation { .. } => StepType::Delegation,
             Self::Completion => StepType::Completion,
         }
     }
@@ -92,6 +115,7 @@ impl StepContent {
             Self::Planning { .. } => "Planning response...".to_owned(),
             Self::SkillUsage { skill_name, .. } => format!("Using {skill_name} skill..."),
             Self::ToolExecution { tool_name, .. } => format!("Running {tool_name}..."),
+            Self::Delegation { agent_name, .. } => format!("Delegating to {agent_name}..."),
             Self::Completion => "Complete".to_owned(),
         }
     }
@@ -106,7 +130,10 @@ impl StepContent {
         match self {
             Self::ToolExecution { tool_name, .. } => Some(tool_name),
             Self::SkillUsage { skill_name, .. } => Some(skill_name),
-            Self::Understanding | Self::Planning { .. } | Self::Completion => None,
+            Self::Understanding
+            | Self::Planning { .. }
+            | Self::Delegation { .. }
+            | Self::Completion => None,
         }
     }
 
@@ -117,6 +144,7 @@ impl StepContent {
             Self::Understanding
             | Self::Planning { .. }
             | Self::SkillUsage { .. }
+            | Self::Delegation { .. }
             | Self::Completion => None,
         }
     }
@@ -128,6 +156,7 @@ impl StepContent {
             Self::Understanding
             | Self::Planning { .. }
             | Self::SkillUsage { .. }
+            | Self::Delegation { .. }
             | Self::Completion => None,
         }
     }
@@ -139,6 +168,7 @@ impl StepContent {
             Self::Understanding
             | Self::SkillUsage { .. }
             | Self::ToolExecution { .. }
+            | Self::Delegation { .. }
             | Self::Completion => None,
         }
     }
@@ -150,6 +180,7 @@ impl StepContent {
             Self::Understanding
             | Self::SkillUsage { .. }
             | Self::ToolExecution { .. }
+            | Self::Delegation { .. }
             | Self::Completion => None,
         }
     }
@@ -169,6 +200,7 @@ impl StepContent {
             other @ (Self::Understanding
             | Self::Planning { .. }
             | Self::SkillUsage { .. }
+            | Self::Delegation { .. }
             | Self::Completion) => other,
         }
     }
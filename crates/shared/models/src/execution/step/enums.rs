@@ -81,6 +81,7 @@ pub enum StepType {
     Planning,
     SkillUsage,
     ToolExecution,
+    Delegation,
     Completion,
 }
 
@@ -91,6 +92,7 @@ impl std::fmt::Display for StepType {
             Self::Planning => write!(f, "planning"),
             Self::SkillUsage => write!(f, "skill_usage"),
             Self::ToolExecution => write!(f, "tool_execution"),
+            Self::Delegation => write!(f, "delegation"),
             Self::Completion => write!(f, "completion"),
         }
     }
@@ -105,6 +107,7 @@ impl std::str::FromStr for StepType {
             "planning" => Ok(Self::Planning),
             "skill_usage" => Ok(Self::SkillUsage),
             "tool_execution" | "toolexecution" => Ok(Self::ToolExecution),
+            "delegation" => Ok(Self::Delegation),
             "completion" => Ok(Self::Completion),
             _ => Err(format!("Invalid step type: {s}")),
         }
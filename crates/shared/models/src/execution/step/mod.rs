@@ -14,6 +14,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use systemprompt_identifiers::{SkillId, TaskId};
 
+use crate::a2a::TaskState;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecutionStep {
@@ -88,6 +90,20 @@ impl ExecutionStep {
         )
     }
 
+    #[must_use]
+    pub fn delegation(
+        task_id: TaskId,
+        agent_name: impl Into<String>,
+        child_task_id: TaskId,
+        state: TaskState,
+        message: Option<String>,
+    ) -> Self {
+        Self::new(
+            task_id,
+            StepContent::delegation(agent_name, child_task_id, state, message),
+        )
+    }
+
     #[must_use]
     pub fn completion(task_id: TaskId) -> Self {
         Self::new(task_id, StepContent::completion())
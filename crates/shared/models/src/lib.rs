@@ -149,8 +149,9 @@ pub use content_config::{
 pub use env::{contains_placeholder, interpolate, none_if_blank, read_env_optional};
 pub use errors::{RepositoryError, ServiceError};
 pub use events::{
-    A2AEvent, A2AEventBuilder, A2AEventType, AnalyticsEvent, AnalyticsEventBuilder, ContextEvent,
-    ContextSummary, SystemEvent, SystemEventBuilder, SystemEventType,
+    A2AEvent, A2AEventBuilder, A2AEventType, AnalyticsEvent, AnalyticsEventBuilder,
+    BudgetThresholdCrossedPayload, ContextEvent, ContextSummary, SystemEvent, SystemEventBuilder,
+    SystemEventType,
 };
 pub use execution::{
     ExecutionStep, PlannedTool, RequestContext, StepContent, StepId, StepStatus, StepType,
@@ -182,17 +183,18 @@ pub use routing::{ApiCategory, AssetType, RouteClassifier, RouteType};
 pub use scope::RequestScope;
 pub use secrets::Secrets;
 pub use services::{
-    AGENT_CONFIG_FILENAME, AgentCardConfig, AgentConfig, AgentMetadataConfig, AgentProviderInfo,
-    AgentSummary, AiConfig, AiProviderConfig, CapabilitiesConfig, ComponentFilter, ComponentSource,
-    DEFAULT_AGENT_SYSTEM_PROMPT_FILE, DEFAULT_SKILL_CONTENT_FILE, DiskAgentConfig, DiskHookConfig,
-    DiskSkillConfig, Frontmatter, HOOK_CONFIG_FILENAME, HistoryConfig, HookAction, HookCategory,
-    HookEvent, HookEventsConfig, HookMatcher, HookType, IncludableString, JobConfig,
-    MarketplaceConfig, MarketplaceConfigFile, MarketplaceVisibility, McpConfig,
-    OAuthConfig as AgentOAuthConfig, PluginAuthor, PluginComponentRef, PluginConfig,
-    PluginConfigFile, PluginScript, PluginVariableDef, RuntimeStatus, SKILL_CONFIG_FILENAME,
-    SamplingConfig, SchedulerConfig, ServiceType, ServicesConfig, Settings as ServicesSettings,
-    SkillConfig, SkillsConfig, SystemAdmin, SystemAdminConfig, WebConfig, split_frontmatter,
-    strip_frontmatter,
+    AGENT_CONFIG_FILENAME, AgentCardConfig, AgentCompactionConfig, AgentConfig,
+    AgentExecutionConfig, AgentExecutionMode, AgentMetadataConfig, AgentProviderInfo,
+    AgentRecoveryConfig, AgentSummary, AiConfig, AiProviderConfig, CapabilitiesConfig,
+    CompactionStrategy, ComponentFilter, ComponentSource, DEFAULT_AGENT_SYSTEM_PROMPT_FILE,
+    DEFAULT_SKILL_CONTENT_FILE, DiskAgentConfig, DiskHookConfig, DiskSkillConfig, Frontmatter,
+    HOOK_CONFIG_FILENAME, HistoryConfig, HookAction, HookCategory, HookEvent, HookEventsConfig,
+    HookMatcher, HookType, IncludableString, JobConfig, MarketplaceConfig, MarketplaceConfigFile,
+    MarketplaceVisibility, McpConfig, OAuthConfig as AgentOAuthConfig, PluginAuthor,
+    PluginComponentRef, PluginConfig, PluginConfigFile, PluginScript, PluginVariableDef,
+    RuntimeStatus, SKILL_CONFIG_FILENAME, SamplingConfig, SchedulerConfig, ServiceType,
+    ServicesConfig, Settings as ServicesSettings, SkillConfig, SkillsConfig, SystemAdmin,
+    SystemAdminConfig, TaskRecoveryMode, WebConfig, split_frontmatter, strip_frontmatter,
 };
 pub use systemprompt_identifiers::{AgentId, ContextId, SessionId, TaskId, TraceId, UserId};
 pub use users::{SessionSummary, UserSummary};
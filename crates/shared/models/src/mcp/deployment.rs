@@ -4,7 +4,9 @@
 //! a map of named [`Deployment`]s plus global [`Settings`]. Each deployment
 //! declares its [`McpServerType`], OAuth requirement, schemas, and per-tool
 //! [`ToolMetadata`]. Internal-server endpoints are validated relative by
-//! [`Deployment::validate`].
+//! [`Deployment::validate`]; `stdio` servers additionally declare the
+//! [`StdioCommand`] the orchestrator bridges onto HTTP, and any spawned
+//! server may carry a [`SandboxConfig`].
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -13,6 +15,7 @@ use crate::ai::ToolModelConfig;
 use crate::auth::{JwtAudience, Permission};
 use crate::errors::ConfigValidationError;
 use crate::mcp::capabilities::ToolVisibility;
+use crate::mcp::sandbox::SandboxConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use systemprompt_identifiers::ClientId;
@@ -24,6 +27,8 @@ pub enum McpServerType {
     Internal,
     #[serde(rename = "external")]
     External,
+    #[serde(rename = "stdio")]
+    Stdio,
 }
 
 impl McpServerType {
@@ -31,8 +36,15 @@ impl McpServerType {
         match self {
             Self::Internal => "internal",
             Self::External => "external",
+            Self::Stdio => "stdio",
         }
     }
+
+    /// Whether the orchestrator spawns and supervises a local process for
+    /// this server (native binaries and bridged stdio commands).
+    pub const fn is_managed(&self) -> bool {
+        matches!(self, Self::Internal | Self::Stdio)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -55,6 +67,9 @@ fn default_visibility_enum() -> Vec<ToolVisibility> {
 pub struct ToolMetadata {
     #[serde(default)]
     pub terminal_on_success: bool,
+    /// Agents pause the task for a human decision before running this tool.
+    #[serde(default)]
+    pub requires_approval: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model_config: Option<ToolModelConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -71,6 +86,7 @@ pub struct DeploymentConfig {
 pub struct Deployment {
     #[serde(default, alias = "type")]
     pub server_type: McpServerType,
+    #[serde(default)]
     pub binary: String,
     pub package: Option<String>,
     pub port: u16,
@@ -93,6 +109,25 @@ pub struct Deployment {
     pub external_auth: Option<ExternalAuth>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub headers: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdio: Option<StdioCommand>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<SandboxConfig>,
+}
+
+/// Command line of a `stdio` MCP server.
+///
+/// The orchestrator runs a bridge process on the server's port that spawns
+/// `command` with `args`, speaks MCP over its stdin/stdout, and serves the
+/// result as streamable HTTP behind the `/api/v1/mcp/{name}` proxy. Only the
+/// variables listed in the deployment's `env_vars` reach the command.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StdioCommand {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
 }
 
 /// Per-user bearer resolution for an `external` MCP server.
@@ -132,7 +167,12 @@ impl ExternalAuth {
 
 impl Deployment {
     pub fn validate(&self, name: &str) -> Result<(), ConfigValidationError> {
-        if matches!(self.server_type, McpServerType::Internal) {
+        self.validate_stdio(name)?;
+        if let Some(sandbox) = self.sandbox.as_ref() {
+            sandbox.validate(name, self.server_type)?;
+        }
+
+        if self.server_type.is_managed() {
             if let Some(ep) = self.endpoint.as_deref()
                 && (ep.starts_with("http://") || ep.starts_with("https://"))
             {
@@ -145,8 +185,9 @@ impl Deployment {
             if self.external_auth.is_some() || !self.headers.is_empty() {
                 return Err(ConfigValidationError::invalid_field(format!(
                     "MCP server '{name}': external_auth and headers are only valid on \
-                         external servers; internal servers are reached through the gateway \
-                         with the systemprompt credential."
+                         external servers; {} servers are reached through the gateway \
+                         with the systemprompt credential.",
+                    self.server_type.as_str()
                 )));
             }
         }
@@ -176,6 +217,26 @@ impl Deployment {
 
         Ok(())
     }
+
+    fn validate_stdio(&self, name: &str) -> Result<(), ConfigValidationError> {
+        match (self.server_type, self.stdio.as_ref()) {
+            (McpServerType::Stdio, None) => Err(ConfigValidationError::invalid_field(format!(
+                "MCP server '{name}': stdio servers require a `stdio.command` to spawn."
+            ))),
+            (McpServerType::Stdio, Some(stdio)) if stdio.command.trim().is_empty() => {
+                Err(ConfigValidationError::invalid_field(format!(
+                    "MCP server '{name}': stdio.command must not be empty."
+                )))
+            },
+            (McpServerType::Internal | McpServerType::External, Some(_)) => {
+                Err(ConfigValidationError::invalid_field(format!(
+                    "MCP server '{name}': the stdio block is only valid on servers with \
+                         type: stdio."
+                )))
+            },
+            _ => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,9 +1,9 @@
 //! MCP protocol metadata helpers.
 //!
 //! Non-wire MCP support types: server capabilities and UI/CSP config,
-//! deployment descriptors, the registry and tool/deployment provider
-//! traits (with `dyn`-compatible aliases), server lifecycle state, and
-//! tool-result metadata extensions.
+//! deployment descriptors and their sandbox settings, the registry and
+//! tool/deployment provider traits (with `dyn`-compatible aliases), server
+//! lifecycle state, and tool-result metadata extensions.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -15,6 +15,7 @@ pub mod client_profile;
 pub mod deployment;
 pub mod registry;
 pub mod registry_trait;
+pub mod sandbox;
 pub mod server;
 mod tool_result_metadata;
 
@@ -32,11 +33,16 @@ pub use capabilities::{
 pub use client_profile::ClientProfile;
 pub use deployment::{
     Deployment, DeploymentConfig, ExternalAuth, McpServerType, OAuthRequirement, Settings,
+    StdioCommand,
 };
 pub use registry::RegistryConfig;
 pub use registry_trait::{
     DynMcpDeploymentProvider, DynMcpRegistry, DynMcpToolProvider, McpDeploymentProvider,
     McpProvider, McpRegistry, McpServerState, McpToolProvider,
 };
+pub use sandbox::{
+    DEFAULT_CGROUP_PARENT, FilesystemAllowlist, NetworkMode, ResourceLimits, SandboxConfig,
+    SeccompProfile,
+};
 pub use server::{ERROR, McpAuthState, McpServerConfig, RUNNING, STARTING, STOPPED};
 pub use tool_result_metadata::McpToolResultMetadata;
@@ -0,0 +1,192 @@
+//! Per-server sandbox settings for spawned MCP processes.
+//!
+//! A deployment's optional `sandbox` block confines the process the
+//! orchestrator launches for it: a Landlock filesystem allowlist, a seccomp
+//! syscall denylist, `no_new_privs`, a private network namespace, and cgroup
+//! v2 memory/CPU/pids caps. For a `stdio` server the settings apply to the
+//! bridged command rather than the bridge. Enforcement is Linux-only; an
+//! operator who asks for a sandbox on another platform gets a spawn error, not
+//! an unconfined server.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ConfigValidationError;
+use crate::mcp::deployment::McpServerType;
+
+pub const DEFAULT_CGROUP_PARENT: &str = "/sys/fs/cgroup/systemprompt-mcp";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SandboxConfig {
+    /// Set `no_new_privs` on the process. Always applied when a filesystem
+    /// allowlist or seccomp profile is configured, which both require it.
+    #[serde(default)]
+    pub no_new_privs: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filesystem: Option<FilesystemAllowlist>,
+    #[serde(default)]
+    pub seccomp: SeccompProfile,
+    #[serde(default)]
+    pub network: NetworkMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<ResourceLimits>,
+}
+
+/// Landlock filesystem allowlist.
+///
+/// Everything outside these paths is unreachable once the process starts,
+/// including the shared libraries and interpreters it loads, so the list must
+/// cover them (typically `/usr`, `/lib`, `/etc`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FilesystemAllowlist {
+    #[serde(default)]
+    pub read_only: Vec<String>,
+    #[serde(default)]
+    pub read_write: Vec<String>,
+}
+
+/// Seccomp denylist applied to the process.
+///
+/// Both profiles deny the same host-level syscalls: mounts, module loading,
+/// `ptrace`, `bpf`, namespace changes, keyrings, clock changes and similar.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SeccompProfile {
+    #[default]
+    None,
+    /// Denied syscalls fail with `EPERM`.
+    Default,
+    /// Denied syscalls kill the process with `SIGSYS`, which the kernel audit
+    /// log records and the health monitor sees as the server going down.
+    Strict,
+}
+
+impl SeccompProfile {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Default => "default",
+            Self::Strict => "strict",
+        }
+    }
+
+    pub const fn is_enabled(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkMode {
+    #[default]
+    Host,
+    /// A private network namespace with only a loopback interface.
+    Isolated,
+}
+
+/// cgroup v2 caps for the process.
+///
+/// The server gets its own cgroup under `cgroup_parent`, which must be
+/// writable by this process with the needed controllers available (for
+/// example through systemd `Delegate=yes`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ResourceLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_max_mb: Option<u64>,
+    /// Share of one CPU, so `150` allows one and a half cores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_max_percent: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pids_max: Option<u32>,
+    #[serde(default = "default_cgroup_parent")]
+    pub cgroup_parent: String,
+}
+
+fn default_cgroup_parent() -> String {
+    DEFAULT_CGROUP_PARENT.to_owned()
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            memory_max_mb: None,
+            cpu_max_percent: None,
+            pids_max: None,
+            cgroup_parent: default_cgroup_parent(),
+        }
+    }
+}
+
+impl ResourceLimits {
+    pub const fn is_empty(&self) -> bool {
+        self.memory_max_mb.is_none() && self.cpu_max_percent.is_none() && self.pids_max.is_none()
+    }
+
+    fn validate(&self, name: &str) -> Result<(), ConfigValidationError> {
+        if self.is_empty() {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "MCP server '{name}': sandbox.limits sets no memory, CPU or pids cap."
+            )));
+        }
+        let zero = [
+            ("memory_max_mb", self.memory_max_mb == Some(0)),
+            ("cpu_max_percent", self.cpu_max_percent == Some(0)),
+            ("pids_max", self.pids_max == Some(0)),
+        ]
+        .into_iter()
+        .find_map(|(field, is_zero)| is_zero.then_some(field));
+        if let Some(field) = zero {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "MCP server '{name}': sandbox.limits.{field} must be greater than zero."
+            )));
+        }
+        if !self.cgroup_parent.starts_with('/') {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "MCP server '{name}': sandbox.limits.cgroup_parent must be an absolute path."
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl SandboxConfig {
+    pub const fn requires_no_new_privs(&self) -> bool {
+        self.no_new_privs || self.filesystem.is_some() || self.seccomp.is_enabled()
+    }
+
+    pub fn validate(
+        &self,
+        name: &str,
+        server_type: McpServerType,
+    ) -> Result<(), ConfigValidationError> {
+        if server_type == McpServerType::External {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "MCP server '{name}': sandbox is only valid on servers the orchestrator \
+                     spawns; external servers run elsewhere."
+            )));
+        }
+        if self.network == NetworkMode::Isolated && server_type == McpServerType::Internal {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "MCP server '{name}': sandbox.network: isolated needs type: stdio; an \
+                     internal server must accept connections on its host port."
+            )));
+        }
+        if let Some(filesystem) = self.filesystem.as_ref()
+            && let Some(path) = filesystem
+                .read_only
+                .iter()
+                .chain(&filesystem.read_write)
+                .find(|path| !path.starts_with('/'))
+        {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "MCP server '{name}': sandbox.filesystem path '{path}' must be absolute."
+            )));
+        }
+        if let Some(limits) = self.limits.as_ref() {
+            limits.validate(name)?;
+        }
+        Ok(())
+    }
+}
@@ -53,6 +53,10 @@ pub struct McpServerConfig {
     pub external_auth: Option<super::deployment::ExternalAuth>,
     #[serde(default)]
     pub headers: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdio: Option<super::deployment::StdioCommand>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<super::sandbox::SandboxConfig>,
 }
 
 fn serialize_path<S>(path: &Path, serializer: S) -> Result<S::Ok, S::Error>
@@ -90,6 +94,14 @@ impl McpServerConfig {
     pub const fn is_external(&self) -> bool {
         matches!(self.server_type, McpServerType::External)
     }
+
+    pub const fn is_stdio(&self) -> bool {
+        matches!(self.server_type, McpServerType::Stdio)
+    }
+
+    pub const fn is_managed(&self) -> bool {
+        self.server_type.is_managed()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,11 +16,18 @@ impl CliPaths {
     pub const RUN: &'static str = "run";
     pub const MIGRATE: &'static str = "migrate";
     pub const SERVE: &'static str = "serve";
+    pub const PLUGINS: &'static str = "plugins";
+    pub const MCP: &'static str = "mcp";
+    pub const BRIDGE: &'static str = "bridge";
 
     pub const fn agent_run_args() -> [&'static str; 3] {
         [Self::ADMIN, Self::AGENTS, Self::RUN]
     }
 
+    pub const fn mcp_bridge_args() -> [&'static str; 3] {
+        [Self::PLUGINS, Self::MCP, Self::BRIDGE]
+    }
+
     pub const fn db_migrate_args() -> [&'static str; 3] {
         [Self::INFRA, Self::DB, Self::MIGRATE]
     }
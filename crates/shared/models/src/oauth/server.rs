@@ -73,6 +73,7 @@ impl OAuthServerConfig {
                 "refresh_token".to_owned(),
                 "urn:ietf:params:oauth:grant-type:token-exchange".to_owned(),
                 "urn:ietf:params:oauth:grant-type:jwt-bearer".to_owned(),
+                "urn:ietf:params:oauth:grant-type:device_code".to_owned(),
             ],
             supported_code_challenge_methods: vec!["S256".to_owned()],
             token_endpoint_auth_method: "client_secret_post".to_owned(),
@@ -168,6 +168,7 @@ fn security_config_from_env() -> ProfileResult<SecurityConfig> {
         signing_key_path: std::path::PathBuf::from("signing_key.pem"),
         trusted_issuers: Vec::new(),
         id_jag_ttl_secs: super::security::DEFAULT_ID_JAG_TTL_SECS,
+        oidc_providers: Vec::new(),
     })
 }
 
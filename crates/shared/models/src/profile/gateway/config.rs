@@ -18,6 +18,7 @@ use super::super::providers::ProviderRegistry;
 use super::error::{GatewayProfileError, GatewayResult};
 use super::override_rule::SystemPromptRule;
 use super::route::GatewayRoute;
+use super::target::DEFAULT_TARGET_WEIGHT;
 use crate::wire::canonical::CanonicalRequest;
 
 pub(crate) const DEFAULT_ROUTE_PATTERN: &str = "*";
@@ -200,6 +201,9 @@ impl GatewayConfig {
             extra_headers: HashMap::new(),
             pricing: None,
             when: None,
+            weight: DEFAULT_TARGET_WEIGHT,
+            targets: Vec::new(),
+            fallback: None,
         };
         route.ensure_id();
         Some(route)
@@ -248,7 +252,10 @@ impl GatewayConfig {
             if let Some(when) = route.when.as_ref() {
                 when.validate()?;
             }
-            self.validate_route_pricing(registry, route)?;
+            Self::validate_route_targets(registry, route)?;
+            for target in route.all_targets() {
+                self.validate_route_pricing(registry, &route.for_target(&target))?;
+            }
         }
         for rule in &self.system_prompt_overrides {
             rule.validate()?;
@@ -263,6 +270,29 @@ impl GatewayConfig {
         Ok(())
     }
 
+    fn validate_route_targets(
+        registry: &ProviderRegistry,
+        route: &GatewayRoute,
+    ) -> GatewayResult<()> {
+        for target in &route.targets {
+            if registry.find_provider(target.provider.as_str()).is_none() {
+                return Err(GatewayProfileError::RouteTargetProviderNotInRegistry {
+                    route: route.model_pattern.clone(),
+                    provider: target.provider.as_str().to_owned(),
+                });
+            }
+        }
+        if route.all_targets().all(|t| t.weight == 0) {
+            return Err(GatewayProfileError::RouteTargetsAllZeroWeight {
+                route: route.model_pattern.clone(),
+            });
+        }
+        if let Some(fallback) = route.fallback.as_ref() {
+            fallback.validate()?;
+        }
+        Ok(())
+    }
+
     fn validate_route_pricing(
         &self,
         registry: &ProviderRegistry,
@@ -285,6 +315,11 @@ impl GatewayConfig {
         let Some(entry) = route.resolve(registry) else {
             return Ok(());
         };
+        // Why: a local provider bills nothing and its catalog is only known once
+        // discovery has run after boot.
+        if entry.is_local() {
+            return Ok(());
+        }
         if let Some(upstream) = route.upstream_model.as_deref() {
             return match entry.find_model(upstream) {
                 Some(model) if model.pricing.is_billable() => Ok(()),
@@ -1,6 +1,7 @@
 //! Failure modes emitted while validating the gateway's references into the
-//! provider registry: duplicate route ids, and a route or `default_provider`
-//! naming a provider absent from `profile.providers`.
+//! provider registry: duplicate route ids, a route, route target, or
+//! `default_provider` naming a provider absent from `profile.providers`, and
+//! malformed route weights and fallback policies.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -33,6 +34,20 @@ pub enum GatewayProfileError {
     #[error("route `when` sets `requires_tools: false` but also a positive `min_tools`")]
     RouteMatchContradictoryTools,
 
+    #[error(
+        "gateway route '{route}' target provider '{provider}' is not declared in profile.providers"
+    )]
+    RouteTargetProviderNotInRegistry { route: String, provider: String },
+
+    #[error("gateway route '{route}' gives every target weight 0, so no hop can be drawn first")]
+    RouteTargetsAllZeroWeight { route: String },
+
+    #[error("route `fallback.on` must list at least one trigger")]
+    RouteFallbackNoTriggers,
+
+    #[error("route `fallback.max_attempts` must be at least 1")]
+    RouteFallbackZeroAttempts,
+
     #[error(
         "gateway route '{route}' (model_pattern '{pattern}') reaches no priced model: provider \
          '{provider}' declares no model matching the pattern and the route sets no `pricing:` \
@@ -10,6 +10,8 @@
 //!   resolves its provider against `profile.providers` at use time.
 //! - [`route`] / [`GatewayRoute`] — routing patterns and the stable id
 //!   synthesis used to address routes from `access_control_rules`.
+//! - [`target`] / [`RouteTarget`] / [`RouteFallback`] — the extra upstreams a
+//!   route can spread load over and fall back to.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -19,6 +21,7 @@ mod error;
 mod override_rule;
 mod route;
 mod state;
+mod target;
 
 pub use config::{BridgeReleasesSpec, GatewayConfig, GatewayConfigSpec};
 pub use error::{GatewayProfileError, GatewayResult};
@@ -27,3 +30,4 @@ pub use route::{
     GatewayRoute, ResponseFormatKind, RouteMatch, slugify_pattern, synthesize_route_id,
 };
 pub use state::GatewayState;
+pub use target::{FallbackTrigger, RouteFallback, RouteTarget, order_hops};
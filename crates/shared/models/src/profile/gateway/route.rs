@@ -6,7 +6,9 @@
 //! from `(model_pattern, provider)` so `access_control_rules` can address the
 //! route by a name that survives reordering. A model's connectivity is never
 //! embedded here — [`GatewayRoute::resolve`] looks the provider up in the
-//! registry at use time.
+//! registry at use time. A route may list further upstream [`RouteTarget`]s
+//! and a [`RouteFallback`] policy; [`GatewayRoute::hops`] expands them into the
+//! ordered attempts for one request.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
@@ -18,6 +20,9 @@ use systemprompt_identifiers::{ProviderId, RouteId};
 
 use super::super::providers::{ProviderEntry, ProviderRegistry};
 use super::error::{GatewayProfileError, GatewayResult};
+use super::target::{
+    RouteFallback, RouteTarget, default_target_weight, is_default_target_weight, order_hops,
+};
 use crate::gateway_hash::fnv1a_segments;
 use crate::services::ai::ModelPricing;
 use crate::wire::canonical::{CanonicalContent, CanonicalRequest, ReasoningEffort, ResponseFormat};
@@ -41,6 +46,15 @@ pub struct GatewayRoute {
     pub pricing: Option<ModelPricing>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub when: Option<RouteMatch>,
+    #[serde(
+        default = "default_target_weight",
+        skip_serializing_if = "is_default_target_weight"
+    )]
+    pub weight: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<RouteTarget>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<RouteFallback>,
 }
 
 impl GatewayRoute {
@@ -69,6 +83,52 @@ impl GatewayRoute {
     pub fn resolve<'a>(&self, registry: &'a ProviderRegistry) -> Option<&'a ProviderEntry> {
         registry.find_provider(self.provider.as_str())
     }
+
+    #[must_use]
+    pub fn primary_target(&self) -> RouteTarget {
+        RouteTarget {
+            provider: self.provider.clone(),
+            upstream_model: self.upstream_model.clone(),
+            weight: self.weight,
+            extra_headers: self.extra_headers.clone(),
+        }
+    }
+
+    pub fn all_targets(&self) -> impl Iterator<Item = RouteTarget> + '_ {
+        std::iter::once(self.primary_target()).chain(self.targets.iter().cloned())
+    }
+
+    /// The ordered hops one request may try, drawn with `roll` (see
+    /// [`order_hops`]). A route without a `fallback` block yields one hop.
+    #[must_use]
+    pub fn hops(&self, roll: u64) -> Vec<RouteTarget> {
+        let max_attempts = self.fallback.as_ref().map_or(Some(1), |f| f.max_attempts);
+        order_hops(self.all_targets().collect(), roll, max_attempts)
+    }
+
+    /// This route re-pointed at `target`: same id, pattern, and predicates,
+    /// with the target's connectivity. Outbound adapters read the provider and
+    /// headers off the route, so each hop is sent as one of these. The id is
+    /// pinned first, so a synthesized id still names the primary. `pricing`
+    /// is the operator's rate for the primary upstream, so any other target
+    /// drops it and is priced from its own provider's catalog.
+    #[must_use]
+    pub fn for_target(&self, target: &RouteTarget) -> Self {
+        let is_primary =
+            target.provider == self.provider && target.upstream_model == self.upstream_model;
+        let mut route = Self {
+            provider: target.provider.clone(),
+            upstream_model: target.upstream_model.clone(),
+            extra_headers: target.extra_headers.clone(),
+            weight: target.weight,
+            pricing: self.pricing.filter(|_| is_primary),
+            ..self.clone()
+        };
+        if route.id.as_str().trim().is_empty() {
+            route.id = synthesize_route_id(&self.model_pattern, self.provider.as_str());
+        }
+        route
+    }
 }
 
 /// Request-shape predicates a route can require beyond the model glob.
@@ -0,0 +1,151 @@
+//! Upstream targets and the fallback policy of a gateway route.
+//!
+//! A [`GatewayRoute`](super::route::GatewayRoute) always names one primary
+//! `provider`; `targets` appends further upstreams. Together they form the
+//! route's hop list, primary first. Each hop carries a `weight`: the first hop
+//! of a request is drawn from the hops with a non-zero weight in proportion to
+//! it, so a weight of `0` marks a fallback-only hop. When the drawn hop fails
+//! with a failure listed in [`RouteFallback::on`], the gateway moves on to the
+//! remaining hops in declared order, up to [`RouteFallback::max_attempts`].
+//! Without a `fallback` block a request gets exactly one hop.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use systemprompt_identifiers::ProviderId;
+
+use super::error::{GatewayProfileError, GatewayResult};
+
+pub(crate) const DEFAULT_TARGET_WEIGHT: u32 = 1;
+
+pub(crate) const fn default_target_weight() -> u32 {
+    DEFAULT_TARGET_WEIGHT
+}
+
+pub(crate) const fn is_default_target_weight(weight: &u32) -> bool {
+    *weight == DEFAULT_TARGET_WEIGHT
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RouteTarget {
+    pub provider: ProviderId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_model: Option<String>,
+    #[serde(
+        default = "default_target_weight",
+        skip_serializing_if = "is_default_target_weight"
+    )]
+    pub weight: u32,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra_headers: HashMap<String, String>,
+}
+
+/// Upstream failures that move a request on to the route's next hop.
+///
+/// Transport failures other than a timeout (refused connection, reset, DNS)
+/// count as `server_error`: either way the upstream could not serve the call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FallbackTrigger {
+    RateLimited,
+    ServerError,
+    Timeout,
+    CircuitOpen,
+}
+
+impl FallbackTrigger {
+    pub const ALL: [Self; 4] = [
+        Self::RateLimited,
+        Self::ServerError,
+        Self::Timeout,
+        Self::CircuitOpen,
+    ];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::RateLimited => "rate_limited",
+            Self::ServerError => "server_error",
+            Self::Timeout => "timeout",
+            Self::CircuitOpen => "circuit_open",
+        }
+    }
+}
+
+fn default_fallback_triggers() -> Vec<FallbackTrigger> {
+    FallbackTrigger::ALL.to_vec()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RouteFallback {
+    #[serde(default = "default_fallback_triggers")]
+    pub on: Vec<FallbackTrigger>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for RouteFallback {
+    fn default() -> Self {
+        Self {
+            on: default_fallback_triggers(),
+            max_attempts: None,
+        }
+    }
+}
+
+impl RouteFallback {
+    #[must_use]
+    pub fn triggers_on(&self, trigger: FallbackTrigger) -> bool {
+        self.on.contains(&trigger)
+    }
+
+    pub fn validate(&self) -> GatewayResult<()> {
+        if self.on.is_empty() {
+            return Err(GatewayProfileError::RouteFallbackNoTriggers);
+        }
+        if self.max_attempts == Some(0) {
+            return Err(GatewayProfileError::RouteFallbackZeroAttempts);
+        }
+        Ok(())
+    }
+}
+
+/// Order the hops for one request.
+///
+/// `roll` is any uniformly distributed value; it selects the first hop among
+/// the weighted ones. The remaining hops follow in declared order. The caller
+/// supplies the randomness so the ordering itself stays deterministic.
+#[must_use]
+pub fn order_hops(
+    hops: Vec<RouteTarget>,
+    roll: u64,
+    max_attempts: Option<u32>,
+) -> Vec<RouteTarget> {
+    let total: u64 = hops.iter().map(|h| u64::from(h.weight)).sum();
+    let mut ordered = hops;
+    if total > 0 {
+        let mut point = roll % total;
+        let first = ordered
+            .iter()
+            .position(|h| {
+                let w = u64::from(h.weight);
+                if point < w {
+                    return true;
+                }
+                point -= w;
+                false
+            })
+            .unwrap_or(0);
+        let chosen = ordered.remove(first);
+        ordered.insert(0, chosen);
+    }
+    if let Some(max) = max_attempts {
+        ordered.truncate(max as usize);
+    }
+    ordered
+}
@@ -15,6 +15,7 @@ mod from_env;
 mod gateway;
 mod governance;
 mod info;
+mod oidc_providers;
 mod paths;
 mod providers;
 mod rate_limits;
@@ -31,18 +32,20 @@ pub use cloud::{CloudConfig, CloudValidationMode};
 pub use database::{DatabaseConfig, PoolConfig};
 pub use error::{ProfileError, ProfileResult};
 pub use gateway::{
-    BridgeReleasesSpec, GatewayConfig, GatewayConfigSpec, GatewayProfileError, GatewayResult,
-    GatewayRoute, GatewayState, OverrideRuleAction, ResponseFormatKind, RouteMatch,
-    SystemPromptRule, slugify_pattern, synthesize_route_id,
+    BridgeReleasesSpec, FallbackTrigger, GatewayConfig, GatewayConfigSpec, GatewayProfileError,
+    GatewayResult, GatewayRoute, GatewayState, OverrideRuleAction, ResponseFormatKind,
+    RouteFallback, RouteMatch, RouteTarget, SystemPromptRule, order_hops, slugify_pattern,
+    synthesize_route_id,
 };
 pub use governance::{
     AuthzConfig, AuthzHookConfig, AuthzMode, GovernanceConfig, UNRESTRICTED_ACKNOWLEDGEMENT,
 };
 pub use info::ProfileInfo;
+pub use oidc_providers::{OidcProvider, OidcProviderKind};
 pub use paths::{PathsConfig, expand_home, resolve_path, resolve_with_home};
 pub use providers::{
-    ApiSurface, ProviderEntry, ProviderModel, ProviderRegistry, ProviderRegistryError,
-    ProviderRegistryResult, WireProtocol,
+    ApiSurface, DiscoveredModels, LocalProvider, ModelDiscovery, ProviderEntry, ProviderModel,
+    ProviderRegistry, ProviderRegistryError, ProviderRegistryResult, WireProtocol,
 };
 pub use rate_limits::{
     RateLimitsConfig, TierMultipliers, default_a2a_multiplier, default_admin_multiplier,
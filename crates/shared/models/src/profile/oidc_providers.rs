@@ -0,0 +1,172 @@
+//! Profile `security.oidc_providers` entries: upstream `OpenID` Connect
+//! providers offered on the browser sign-in page.
+//!
+//! Each entry names its `kind` so the well-known endpoint layout of Keycloak,
+//! Microsoft Entra ID and Google Workspace can be derived from the issuer;
+//! explicit endpoint fields override the derivation, and a `mock` provider
+//! must spell all of them out.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use systemprompt_identifiers::SecretName;
+
+const GOOGLE_AUTHORIZATION_ENDPOINT: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const GOOGLE_TOKEN_ENDPOINT: &str = "https://oauth2.googleapis.com/token";
+const GOOGLE_JWKS_URI: &str = "https://www.googleapis.com/oauth2/v3/certs";
+
+fn default_scopes() -> Vec<String> {
+    ["openid", "email", "profile"]
+        .iter()
+        .map(|s| (*s).to_owned())
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OidcProviderKind {
+    Keycloak,
+    Entra,
+    GoogleWorkspace,
+    Mock,
+}
+
+impl OidcProviderKind {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Keycloak => "Keycloak",
+            Self::Entra => "Microsoft",
+            Self::GoogleWorkspace => "Google",
+            Self::Mock => "Mock IdP",
+        }
+    }
+
+    /// Whether the provider's endpoints may use plain `http`. Only the mock
+    /// provider, which runs next to the server in development and tests; its
+    /// keys are still fetched under the JWKS client's own scheme policy.
+    #[must_use]
+    pub const fn allows_http(self) -> bool {
+        matches!(self, Self::Mock)
+    }
+}
+
+/// An upstream `OpenID` Connect provider users can sign in with.
+///
+/// `role_mappings` maps a value of the `role_claim` claim to local RBAC
+/// roles. Roles that appear as a mapping target are managed by this provider:
+/// every sign-in recomputes them from the ID token, while roles granted by
+/// other means are left alone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OidcProvider {
+    pub id: String,
+
+    pub kind: OidcProviderKind,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+
+    pub issuer: String,
+
+    pub client_id: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<SecretName>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorization_endpoint: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_endpoint: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwks_uri: Option<String>,
+
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role_claim: Option<String>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub role_mappings: BTreeMap<String, Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_domains: Vec<String>,
+}
+
+impl OidcProvider {
+    #[must_use]
+    pub fn display_name(&self) -> &str {
+        self.display_name
+            .as_deref()
+            .unwrap_or_else(|| self.kind.label())
+    }
+
+    #[must_use]
+    pub fn authorization_endpoint(&self) -> Option<String> {
+        self.authorization_endpoint
+            .clone()
+            .or_else(|| match self.kind {
+                OidcProviderKind::Keycloak => Some(self.keycloak_endpoint("auth")),
+                OidcProviderKind::Entra => Some(self.entra_endpoint("oauth2/v2.0/authorize")),
+                OidcProviderKind::GoogleWorkspace => Some(GOOGLE_AUTHORIZATION_ENDPOINT.to_owned()),
+                OidcProviderKind::Mock => None,
+            })
+    }
+
+    #[must_use]
+    pub fn token_endpoint(&self) -> Option<String> {
+        self.token_endpoint.clone().or_else(|| match self.kind {
+            OidcProviderKind::Keycloak => Some(self.keycloak_endpoint("token")),
+            OidcProviderKind::Entra => Some(self.entra_endpoint("oauth2/v2.0/token")),
+            OidcProviderKind::GoogleWorkspace => Some(GOOGLE_TOKEN_ENDPOINT.to_owned()),
+            OidcProviderKind::Mock => None,
+        })
+    }
+
+    #[must_use]
+    pub fn jwks_uri(&self) -> Option<String> {
+        self.jwks_uri.clone().or_else(|| match self.kind {
+            OidcProviderKind::Keycloak => Some(self.keycloak_endpoint("certs")),
+            OidcProviderKind::Entra => Some(self.entra_endpoint("discovery/v2.0/keys")),
+            OidcProviderKind::GoogleWorkspace => Some(GOOGLE_JWKS_URI.to_owned()),
+            OidcProviderKind::Mock => None,
+        })
+    }
+
+    /// The ID-token claim holding the upstream groups or roles. Dotted paths
+    /// reach into nested objects, as Keycloak's `realm_access.roles` needs.
+    #[must_use]
+    pub fn role_claim(&self) -> Option<&str> {
+        self.role_claim.as_deref().or(match self.kind {
+            OidcProviderKind::Keycloak => Some("realm_access.roles"),
+            OidcProviderKind::Entra | OidcProviderKind::Mock => Some("roles"),
+            OidcProviderKind::GoogleWorkspace => None,
+        })
+    }
+
+    /// Every local role some mapping can grant; these are the roles this
+    /// provider owns on the accounts it signs in.
+    pub fn managed_roles(&self) -> impl Iterator<Item = &str> {
+        self.role_mappings.values().flatten().map(String::as_str)
+    }
+
+    fn keycloak_endpoint(&self, leaf: &str) -> String {
+        format!(
+            "{}/protocol/openid-connect/{leaf}",
+            self.issuer.trim_end_matches('/')
+        )
+    }
+
+    // Why: the Entra v2 issuer is `https://login.microsoftonline.com/{tenant}/v2.0`
+    // while its endpoints hang off the tenant root.
+    fn entra_endpoint(&self, path: &str) -> String {
+        let tenant_root = self.issuer.trim_end_matches('/').trim_end_matches("/v2.0");
+        format!("{tenant_root}/{path}")
+    }
+}
@@ -0,0 +1,61 @@
+//! Self-hosted OpenAI-compatible upstreams (Ollama, llama.cpp, vLLM).
+//!
+//! A [`ProviderEntry`](super::ProviderEntry) with a `local` block is a server
+//! the deployment runs itself. Its API-key secret may be left unset, its
+//! models need no pricing, and its catalog can be discovered from the server
+//! at boot rather than maintained by hand: [`ModelDiscovery`] selects the
+//! listing endpoint, and the models found are held in [`DiscoveredModels`]
+//! alongside the statically declared ones.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use super::ProviderModel;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelDiscovery {
+    /// `GET {endpoint}/models`, the `OpenAI` listing llama.cpp and vLLM serve.
+    #[default]
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// `GET {host}/api/tags`, Ollama's native listing.
+    Ollama,
+    /// Only the models declared in the profile.
+    None,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LocalProvider {
+    #[serde(default)]
+    pub discovery: ModelDiscovery,
+}
+
+/// Models a local provider reported at boot. Set once, after the profile is
+/// loaded; an entry whose discovery failed or has not run reads as empty.
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveredModels(OnceLock<Vec<ProviderModel>>);
+
+impl DiscoveredModels {
+    /// Records the discovered catalog. Returns `false` if one was already set.
+    pub fn set(&self, models: Vec<ProviderModel>) -> bool {
+        self.0.set(models).is_ok()
+    }
+
+    #[must_use]
+    pub fn get(&self) -> &[ProviderModel] {
+        self.0.get().map_or(&[], Vec::as_slice)
+    }
+
+    #[must_use]
+    pub fn is_set(&self) -> bool {
+        self.0.get().is_some()
+    }
+}
@@ -9,6 +9,10 @@
 //! policy (`services/ai/config.yaml`) selects an agent default and per-provider
 //! overrides.
 //!
+//! An entry with a `local` block ([`LocalProvider`]) is a self-hosted
+//! OpenAI-compatible server whose catalog may be discovered at boot; lookups
+//! here see its [`DiscoveredModels`] alongside the declared ones.
+//!
 //! Validation here is the authority for connectivity: unique provider names,
 //! SSRF-guarded endpoints, and globally-unique model ids/aliases. The gateway
 //! and AI layers validate only their references *into* this registry.
@@ -17,6 +21,7 @@
 //! See <https://systemprompt.io> for licensing details.
 
 mod error;
+mod local;
 mod protocol;
 mod surface;
 
@@ -28,6 +33,7 @@ use systemprompt_identifiers::{ModelId, ProviderId, SecretName};
 use crate::services::ai::{ModelCapabilities, ModelLimits, ModelPricing};
 
 pub use error::{ProviderRegistryError, ProviderRegistryResult};
+pub use local::{DiscoveredModels, LocalProvider, ModelDiscovery};
 pub use protocol::WireProtocol;
 pub use surface::ApiSurface;
 
@@ -89,12 +95,28 @@ pub struct ProviderEntry {
 
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub models: Vec<ProviderModel>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local: Option<LocalProvider>,
+
+    #[serde(skip)]
+    pub discovered: DiscoveredModels,
 }
 
 impl ProviderEntry {
+    /// The declared models followed by any discovered at boot.
+    pub fn all_models(&self) -> impl Iterator<Item = &ProviderModel> {
+        self.models.iter().chain(self.discovered.get())
+    }
+
     #[must_use]
     pub fn find_model(&self, requested: &str) -> Option<&ProviderModel> {
-        self.models.iter().find(|m| m.matches(requested))
+        self.all_models().find(|m| m.matches(requested))
+    }
+
+    #[must_use]
+    pub const fn is_local(&self) -> bool {
+        self.local.is_some()
     }
 }
 
@@ -136,7 +158,7 @@ impl ProviderRegistry {
         self.advertised_providers()
             .filter(|entry| surfaces.is_empty() || surfaces.contains(&entry.surface))
             .flat_map(|entry| {
-                entry.models.iter().flat_map(|m| {
+                entry.all_models().flat_map(|m| {
                     std::iter::once(m.id.as_str().to_owned())
                         .chain(m.aliases.iter().map(|a| a.as_str().to_owned()))
                 })
@@ -1,11 +1,12 @@
 //! Profile `security:` block: signing keys, trusted issuers, resource
-//! audiences.
+//! audiences, upstream sign-in providers.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
 use std::path::PathBuf;
 
+use super::oidc_providers::OidcProvider;
 use crate::auth::JwtAudience;
 use serde::{Deserialize, Serialize};
 
@@ -68,6 +69,9 @@ pub struct SecurityConfig {
 
     #[serde(default = "default_id_jag_ttl_secs")]
     pub id_jag_ttl_secs: i64,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub oidc_providers: Vec<OidcProvider>,
 }
 
 /// A federated identity provider trusted for the RFC 8693 token-exchange and
@@ -1,11 +1,14 @@
 //! Profile validation logic.
 //!
 //! This module contains all validation logic for Profile configurations,
-//! including path validation, security settings, CORS, and rate limits.
+//! including path validation, security settings, upstream sign-in providers,
+//! CORS, and rate limits.
 //!
 //! Copyright (c) systemprompt.io — Business Source License 1.1.
 //! See <https://systemprompt.io> for licensing details.
 
+use std::collections::HashSet;
+
 use super::governance::{AuthzMode, UNRESTRICTED_ACKNOWLEDGEMENT};
 use super::security::GATEWAY_REQUIRED_RESOURCE_AUDIENCES;
 use super::{Profile, ProfileError, ProfileResult};
@@ -20,6 +23,7 @@ impl Profile {
         self.validate_urls(&mut errors);
         self.validate_paths(&mut errors, is_cloud);
         self.validate_security_settings(&mut errors);
+        self.validate_oidc_providers(&mut errors);
         self.validate_database_pool(&mut errors);
         self.validate_cors_origins(&mut errors);
         self.validate_rate_limits(&mut errors);
@@ -230,6 +234,66 @@ impl Profile {
         }
     }
 
+    pub(super) fn validate_oidc_providers(&self, errors: &mut Vec<String>) {
+        let mut seen: HashSet<&str> = HashSet::new();
+        for (idx, provider) in self.security.oidc_providers.iter().enumerate() {
+            let field = format!("security.oidc_providers[{idx}]");
+            let is_slug = !provider.id.is_empty()
+                && provider.id.bytes().all(|b| {
+                    b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_'
+                });
+            if !is_slug {
+                errors.push(format!(
+                    "{field}.id must be lowercase letters, digits, '-' or '_' (got: '{}')",
+                    provider.id
+                ));
+            } else if !seen.insert(provider.id.as_str()) {
+                errors.push(format!(
+                    "{field}.id '{}' is used by more than one provider",
+                    provider.id
+                ));
+            }
+
+            if provider.client_id.trim().is_empty() {
+                errors.push(format!("{field}.client_id is required"));
+            }
+            if provider.issuer.is_empty() {
+                errors.push(format!("{field}.issuer is required"));
+            }
+
+            let https_only = !provider.kind.allows_http();
+            Self::require_absolute_url(
+                errors,
+                &format!("{field}.issuer"),
+                &provider.issuer,
+                https_only,
+            );
+            for (name, endpoint) in [
+                ("authorization_endpoint", provider.authorization_endpoint()),
+                ("token_endpoint", provider.token_endpoint()),
+                ("jwks_uri", provider.jwks_uri()),
+            ] {
+                match endpoint {
+                    Some(url) => Self::require_absolute_url(
+                        errors,
+                        &format!("{field}.{name}"),
+                        &url,
+                        https_only,
+                    ),
+                    None => errors.push(format!("{field}.{name} is required for a mock provider")),
+                }
+            }
+
+            for (claim_value, roles) in &provider.role_mappings {
+                if roles.is_empty() || roles.iter().any(|role| role.trim().is_empty()) {
+                    errors.push(format!(
+                        "{field}.role_mappings['{claim_value}'] must list one or more role names"
+                    ));
+                }
+            }
+        }
+    }
+
     pub(super) fn validate_database_pool(&self, errors: &mut Vec<String>) {
         let Some(pool) = self.database.pool.as_ref() else {
             return;
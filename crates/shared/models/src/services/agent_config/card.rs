@@ -10,6 +10,8 @@ use crate::ai::ToolModelOverrides;
 use crate::auth::{JwtAudience, Permission};
 use crate::services::plugin::PluginComponentRef;
 
+use super::{AgentCompactionConfig, AgentExecutionConfig, AgentRecoveryConfig};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AgentCardConfig {
@@ -75,6 +77,16 @@ pub struct AgentMetadataConfig {
     pub max_output_tokens: Option<u32>,
     #[serde(default)]
     pub tool_model_overrides: ToolModelOverrides,
+    #[serde(default)]
+    pub execution: AgentExecutionConfig,
+    #[serde(default)]
+    pub compaction: AgentCompactionConfig,
+    #[serde(default)]
+    pub recovery: AgentRecoveryConfig,
+    /// Hosted agents this agent may hand work to; each is offered to the
+    /// model as a tool.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delegates: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
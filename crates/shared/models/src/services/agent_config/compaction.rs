@@ -0,0 +1,97 @@
+//! How an agent keeps a long-lived context's history inside its model
+//! window: replay everything, drop the oldest turns to a token budget, or
+//! fold them into a rolling summary. Recent turns are always kept verbatim,
+//! and artifacts can be pinned so they are never dropped or summarized.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ConfigValidationError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompactionStrategy {
+    /// Replay the whole history into every AI call.
+    #[default]
+    None,
+    /// Drop the oldest turns until the history fits the token budget.
+    Truncate,
+    /// Fold the oldest turns into a stored summary that is refreshed each time
+    /// the history outgrows the budget again.
+    Summarize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AgentCompactionConfig {
+    #[serde(default)]
+    pub strategy: CompactionStrategy,
+    /// Estimated tokens the replayed history may take up.
+    #[serde(default = "default_max_history_tokens")]
+    pub max_history_tokens: u32,
+    /// Most recent messages that are always replayed as written.
+    #[serde(default = "default_keep_recent_messages")]
+    pub keep_recent_messages: u32,
+    /// Replay every artifact regardless of the budget.
+    #[serde(default = "default_pin_artifacts")]
+    pub pin_artifacts: bool,
+    /// Output tokens a summarization call may use.
+    #[serde(default = "default_summary_max_tokens")]
+    pub summary_max_tokens: u32,
+}
+
+impl Default for AgentCompactionConfig {
+    fn default() -> Self {
+        Self {
+            strategy: CompactionStrategy::None,
+            max_history_tokens: default_max_history_tokens(),
+            keep_recent_messages: default_keep_recent_messages(),
+            pin_artifacts: default_pin_artifacts(),
+            summary_max_tokens: default_summary_max_tokens(),
+        }
+    }
+}
+
+impl AgentCompactionConfig {
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        !matches!(self.strategy, CompactionStrategy::None)
+    }
+
+    #[must_use]
+    pub const fn summarizes(&self) -> bool {
+        matches!(self.strategy, CompactionStrategy::Summarize)
+    }
+
+    pub fn validate(&self, agent: &str) -> Result<(), ConfigValidationError> {
+        if self.max_history_tokens == 0 {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "Agent '{agent}' compaction.maxHistoryTokens must be at least 1"
+            )));
+        }
+        if self.summarizes() && self.summary_max_tokens >= self.max_history_tokens {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "Agent '{agent}' compaction.summaryMaxTokens must be below maxHistoryTokens"
+            )));
+        }
+        Ok(())
+    }
+}
+
+const fn default_max_history_tokens() -> u32 {
+    32_000
+}
+
+const fn default_keep_recent_messages() -> u32 {
+    8
+}
+
+const fn default_pin_artifacts() -> bool {
+    true
+}
+
+const fn default_summary_max_tokens() -> u32 {
+    1_024
+}
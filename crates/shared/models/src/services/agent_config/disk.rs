@@ -8,7 +8,10 @@ use serde::Deserialize;
 use systemprompt_identifiers::AgentId;
 
 use super::card::{AgentCardConfig, AgentMetadataConfig, OAuthConfig, default_true};
-use super::{AgentConfig, DEFAULT_AGENT_SYSTEM_PROMPT_FILE};
+use super::{
+    AgentCompactionConfig, AgentConfig, AgentExecutionConfig, AgentRecoveryConfig,
+    DEFAULT_AGENT_SYSTEM_PROMPT_FILE, validate_delegates,
+};
 use crate::errors::ConfigValidationError;
 use crate::services::plugin::PluginComponentRef;
 
@@ -53,6 +56,14 @@ pub struct DiskAgentConfig {
     pub card: AgentCardConfig,
     #[serde(default)]
     pub oauth: OAuthConfig,
+    #[serde(default)]
+    pub execution: AgentExecutionConfig,
+    #[serde(default)]
+    pub compaction: AgentCompactionConfig,
+    #[serde(default)]
+    pub recovery: AgentRecoveryConfig,
+    #[serde(default)]
+    pub delegates: Vec<String>,
 }
 
 impl DiskAgentConfig {
@@ -99,6 +110,10 @@ impl DiskAgentConfig {
                 skills: self.skills.clone(),
                 provider: self.provider.clone(),
                 model: self.model.clone(),
+                execution: self.execution,
+                compaction: self.compaction,
+                recovery: self.recovery,
+                delegates: self.delegates.clone(),
                 ..Default::default()
             },
             oauth: self.oauth.clone(),
@@ -146,6 +161,11 @@ impl DiskAgentConfig {
             )));
         }
 
+        self.execution.validate(&self.name)?;
+        self.compaction.validate(&self.name)?;
+        self.recovery.validate(&self.name)?;
+        validate_delegates(&self.name, &self.delegates)?;
+
         Ok(())
     }
 }
@@ -0,0 +1,89 @@
+//! How an agent's planned strategy runs its tool calls: a single
+//! plan → execute → respond pass, or an iterative loop that observes each
+//! batch of tool results and plans again, bounded by the limits here.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ConfigValidationError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgentExecutionMode {
+    /// Plan once, run the planned tools, then respond.
+    #[default]
+    Single,
+    /// Plan, run the tools, feed their results back, and plan again until the
+    /// model answers or a limit is reached.
+    Iterative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AgentExecutionConfig {
+    #[serde(default)]
+    pub mode: AgentExecutionMode,
+    /// Planning rounds an iterative task may run, the first included.
+    #[serde(default = "default_max_iterations")]
+    pub max_iterations: u32,
+    /// Tokens the task's AI requests may use before the loop stops planning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<i64>,
+    /// Cost the task's AI requests may incur before the loop stops planning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost_microdollars: Option<i64>,
+    /// Times one tool may be called with identical arguments before the loop
+    /// is treated as stuck.
+    #[serde(default = "default_max_repeated_calls")]
+    pub max_repeated_calls: u32,
+}
+
+impl Default for AgentExecutionConfig {
+    fn default() -> Self {
+        Self {
+            mode: AgentExecutionMode::Single,
+            max_iterations: default_max_iterations(),
+            max_tokens: None,
+            max_cost_microdollars: None,
+            max_repeated_calls: default_max_repeated_calls(),
+        }
+    }
+}
+
+impl AgentExecutionConfig {
+    #[must_use]
+    pub const fn is_iterative(&self) -> bool {
+        matches!(self.mode, AgentExecutionMode::Iterative)
+    }
+
+    pub fn validate(&self, agent: &str) -> Result<(), ConfigValidationError> {
+        if self.max_iterations == 0 {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "Agent '{agent}' execution.maxIterations must be at least 1"
+            )));
+        }
+        if self.max_repeated_calls == 0 {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "Agent '{agent}' execution.maxRepeatedCalls must be at least 1"
+            )));
+        }
+        if self.max_tokens.is_some_and(|t| t <= 0)
+            || self.max_cost_microdollars.is_some_and(|c| c <= 0)
+        {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "Agent '{agent}' execution budgets must be positive when set"
+            )));
+        }
+        Ok(())
+    }
+}
+
+const fn default_max_iterations() -> u32 {
+    8
+}
+
+const fn default_max_repeated_calls() -> u32 {
+    2
+}
@@ -5,13 +5,19 @@
 //! See <https://systemprompt.io> for licensing details.
 
 mod card;
+mod compaction;
 mod disk;
+mod execution;
+mod recovery;
 mod summary;
 
 pub use card::{
     AgentCardConfig, AgentMetadataConfig, AgentProviderInfo, CapabilitiesConfig, OAuthConfig,
 };
+pub use compaction::{AgentCompactionConfig, CompactionStrategy};
 pub use disk::DiskAgentConfig;
+pub use execution::{AgentExecutionConfig, AgentExecutionMode};
+pub use recovery::{AgentRecoveryConfig, TaskRecoveryMode};
 pub use summary::AgentSummary;
 
 use crate::auth::Permission;
@@ -75,6 +81,11 @@ impl AgentConfig {
             )));
         }
 
+        self.metadata.execution.validate(&self.name)?;
+        self.metadata.compaction.validate(&self.name)?;
+        self.metadata.recovery.validate(&self.name)?;
+        validate_delegates(&self.name, &self.metadata.delegates)?;
+
         Ok(())
     }
 
@@ -114,3 +125,19 @@ impl AgentConfig {
         )
     }
 }
+
+fn validate_delegates(agent: &str, delegates: &[String]) -> Result<(), ConfigValidationError> {
+    for (index, delegate) in delegates.iter().enumerate() {
+        if delegate == agent {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "Agent '{agent}' cannot delegate to itself"
+            )));
+        }
+        if delegates[..index].contains(delegate) {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "Agent '{agent}' lists delegate '{delegate}' more than once"
+            )));
+        }
+    }
+    Ok(())
+}
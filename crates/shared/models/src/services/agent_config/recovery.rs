@@ -0,0 +1,60 @@
+//! What an agent does with tasks left working when its process stopped:
+//! fail them with a reason, or resume them from the message that started the
+//! run, replaying the tool calls that already completed instead of running
+//! them again.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ConfigValidationError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskRecoveryMode {
+    /// Mark interrupted tasks failed and notify their listeners.
+    #[default]
+    Fail,
+    /// Run interrupted tasks again, reusing recorded tool results.
+    Resume,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AgentRecoveryConfig {
+    #[serde(default)]
+    pub mode: TaskRecoveryMode,
+    /// Restarts a single task may be resumed across before it is failed.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+}
+
+impl Default for AgentRecoveryConfig {
+    fn default() -> Self {
+        Self {
+            mode: TaskRecoveryMode::Fail,
+            max_attempts: default_max_attempts(),
+        }
+    }
+}
+
+impl AgentRecoveryConfig {
+    #[must_use]
+    pub const fn resumes(&self) -> bool {
+        matches!(self.mode, TaskRecoveryMode::Resume)
+    }
+
+    pub fn validate(&self, agent: &str) -> Result<(), ConfigValidationError> {
+        if self.resumes() && self.max_attempts == 0 {
+            return Err(ConfigValidationError::invalid_field(format!(
+                "Agent '{agent}' recovery.maxAttempts must be at least 1 when mode is resume"
+            )));
+        }
+        Ok(())
+    }
+}
+
+const fn default_max_attempts() -> u32 {
+    1
+}
@@ -26,9 +26,10 @@ mod validation;
 pub use includable::IncludableString;
 
 pub use agent_config::{
-    AGENT_CONFIG_FILENAME, AgentCardConfig, AgentConfig, AgentMetadataConfig, AgentProviderInfo,
-    AgentSummary, CapabilitiesConfig, DEFAULT_AGENT_SYSTEM_PROMPT_FILE, DiskAgentConfig,
-    OAuthConfig,
+    AGENT_CONFIG_FILENAME, AgentCardConfig, AgentCompactionConfig, AgentConfig,
+    AgentExecutionConfig, AgentExecutionMode, AgentMetadataConfig, AgentProviderInfo,
+    AgentRecoveryConfig, AgentSummary, CapabilitiesConfig, CompactionStrategy,
+    DEFAULT_AGENT_SYSTEM_PROMPT_FILE, DiskAgentConfig, OAuthConfig, TaskRecoveryMode,
 };
 pub use ai::{
     AiConfig, AiProviderConfig, HistoryConfig, McpConfig, ModelCapabilities, ModelDefinition,
@@ -132,6 +132,18 @@ impl AgentConfigValidator {
         for mcp_server in &agent.metadata.mcp_servers.include {
             Self::validate_agent_mcp_ref(name, agent, mcp_server, config, report);
         }
+
+        for delegate in &agent.metadata.delegates {
+            if !config.agents.contains_key(delegate) {
+                report.add_error(
+                    ValidationError::new(
+                        format!("agents.{}.metadata.delegates", name),
+                        format!("Delegate agent '{}' is not defined", delegate),
+                    )
+                    .with_suggestion("Define the agent or remove it from the delegates"),
+                );
+            }
+        }
     }
 
     fn validate_agent_mcp_ref(
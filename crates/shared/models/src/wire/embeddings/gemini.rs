@@ -0,0 +1,73 @@
+//! Gemini `batchEmbedContents` request build and response parse.
+//!
+//! Gemini reports no token usage for embeddings, so the input-token count is
+//! estimated from the input length; quota and cost accounting need a number
+//! and an undercount of zero would make embedding traffic free.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+// JSON: protocol boundary — Gemini embeddings wire format is dynamic JSON.
+use serde_json::{Value, json};
+
+use super::{EmbeddingDecodeError, EmbeddingRequest, EmbeddingResponse, vector};
+
+#[must_use]
+pub fn upstream_path(model: &str) -> String {
+    format!("/models/{model}:batchEmbedContents")
+}
+
+#[must_use]
+pub fn build_request_body(request: &EmbeddingRequest, upstream_model: &str) -> Value {
+    let model = format!("models/{upstream_model}");
+    let requests: Vec<Value> = request
+        .inputs
+        .iter()
+        .map(|text| {
+            let mut item = json!({
+                "model": model,
+                "content": { "parts": [{ "text": text }] },
+            });
+            if let (Some(dimensions), Some(obj)) = (request.dimensions, item.as_object_mut()) {
+                obj.insert("outputDimensionality".into(), json!(dimensions));
+            }
+            item
+        })
+        .collect();
+    json!({ "requests": requests })
+}
+
+pub fn parse_response(
+    value: &Value,
+    request: &EmbeddingRequest,
+) -> Result<EmbeddingResponse, EmbeddingDecodeError> {
+    let embeddings = value
+        .get("embeddings")
+        .and_then(Value::as_array)
+        .ok_or(EmbeddingDecodeError::MissingField("embeddings"))?;
+    let vectors = embeddings
+        .iter()
+        .map(|e| {
+            e.get("values")
+                .and_then(vector)
+                .ok_or(EmbeddingDecodeError::MissingField("embeddings[].values"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if vectors.len() != request.inputs.len() {
+        return Err(EmbeddingDecodeError::CountMismatch {
+            expected: request.inputs.len(),
+            got: vectors.len(),
+        });
+    }
+    Ok(EmbeddingResponse {
+        model: request.model.clone(),
+        vectors,
+        input_tokens: estimate_input_tokens(&request.inputs),
+    })
+}
+
+#[must_use]
+pub fn estimate_input_tokens(inputs: &[String]) -> u32 {
+    let chars: usize = inputs.iter().map(String::len).sum();
+    u32::try_from(chars / 4 + inputs.len()).unwrap_or(u32::MAX)
+}
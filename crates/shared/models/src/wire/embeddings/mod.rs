@@ -0,0 +1,45 @@
+//! Embeddings wire codec for the `OpenAI` and Gemini protocols.
+//!
+//! An [`EmbeddingRequest`] is the provider-neutral form of "embed these
+//! strings with this model"; an [`EmbeddingResponse`] carries one vector per
+//! input, in input order, plus the input-token count used for quota and cost.
+//! [`openai`] renders and parses the `/embeddings` surface (also served by
+//! OpenAI-compatible providers); [`gemini`] renders and parses
+//! `batchEmbedContents`. Transport stays with the caller, as with the chat
+//! codecs.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+pub mod gemini;
+pub mod openai;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmbeddingRequest {
+    pub model: String,
+    pub inputs: Vec<String>,
+    pub dimensions: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EmbeddingResponse {
+    pub model: String,
+    pub vectors: Vec<Vec<f32>>,
+    pub input_tokens: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum EmbeddingDecodeError {
+    #[error("embeddings response is missing `{0}`")]
+    MissingField(&'static str),
+    #[error("embeddings response returned {got} vectors for {expected} inputs")]
+    CountMismatch { expected: usize, got: usize },
+}
+
+fn vector(value: &serde_json::Value) -> Option<Vec<f32>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|v| v.as_f64().map(|f| f as f32))
+        .collect()
+}
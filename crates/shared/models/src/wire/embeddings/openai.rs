@@ -0,0 +1,71 @@
+//! `OpenAI` `/embeddings` request build and response parse.
+//!
+//! Copyright (c) systemprompt.io — Business Source License 1.1.
+//! See <https://systemprompt.io> for licensing details.
+
+// JSON: protocol boundary — OpenAI embeddings wire format is dynamic JSON.
+use serde_json::{Value, json};
+
+use super::{EmbeddingDecodeError, EmbeddingRequest, EmbeddingResponse, vector};
+
+pub const UPSTREAM_PATH: &str = "/embeddings";
+
+#[must_use]
+pub fn build_request_body(request: &EmbeddingRequest, upstream_model: &str) -> Value {
+    let mut body = json!({
+        "model": upstream_model,
+        "input": request.inputs,
+        "encoding_format": "float",
+    });
+    if let (Some(dimensions), Some(obj)) = (request.dimensions, body.as_object_mut()) {
+        obj.insert("dimensions".into(), json!(dimensions));
+    }
+    body
+}
+
+pub fn parse_response(
+    value: &Value,
+    request: &EmbeddingRequest,
+) -> Result<EmbeddingResponse, EmbeddingDecodeError> {
+    let data = value
+        .get("data")
+        .and_then(Value::as_array)
+        .ok_or(EmbeddingDecodeError::MissingField("data"))?;
+    let mut indexed = data
+        .iter()
+        .enumerate()
+        .map(|(position, item)| {
+            let index = item
+                .get("index")
+                .and_then(Value::as_u64)
+                .map_or(position, |i| i as usize);
+            let embedding = item
+                .get("embedding")
+                .and_then(vector)
+                .ok_or(EmbeddingDecodeError::MissingField("data[].embedding"))?;
+            Ok((index, embedding))
+        })
+        .collect::<Result<Vec<_>, EmbeddingDecodeError>>()?;
+    if indexed.len() != request.inputs.len() {
+        return Err(EmbeddingDecodeError::CountMismatch {
+            expected: request.inputs.len(),
+            got: indexed.len(),
+        });
+    }
+    indexed.sort_by_key(|(index, _)| *index);
+
+    let input_tokens = value
+        .pointer("/usage/prompt_tokens")
+        .and_then(Value::as_u64)
+        .map_or(0, |t| u32::try_from(t).unwrap_or(u32::MAX));
+    let model = value
+        .get("model")
+        .and_then(Value::as_str)
+        .unwrap_or(&request.model)
+        .to_owned();
+    Ok(EmbeddingResponse {
+        model,
+        vectors: indexed.into_iter().map(|(_, v)| v).collect(),
+        input_tokens,
+    })
+}
@@ -12,6 +12,8 @@
 //!   [`openai_responses`], [`gemini`]) hold the codec for one wire dialect:
 //!   request build, response parse, stop-reason + usage mapping, SSE-to-event
 //!   translation, and auth-header construction.
+//! - [`embeddings`] holds the embeddings codec for the `OpenAI` and Gemini
+//!   dialects; embeddings do not pass through the canonical chat model.
 //!
 //! These types are defined ONCE here and re-exported by the gateway and the
 //! agent provider clients so both layers share a single wire vocabulary.
@@ -24,6 +26,7 @@ pub const BUFFERED_BODY_LIMIT_BYTES: usize = 8 * 1024 * 1024;
 pub mod canonical;
 
 pub mod anthropic;
+pub mod embeddings;
 pub mod gemini;
 pub mod inspect;
 pub mod openai_chat;
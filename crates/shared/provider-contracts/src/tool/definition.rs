@@ -15,6 +15,8 @@ pub struct ToolDefinition {
     pub service_id: String,
     #[serde(default)]
     pub terminal_on_success: bool,
+    #[serde(default)]
+    pub requires_approval: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model_config: Option<JsonValue>,
 }
@@ -29,6 +31,7 @@ impl ToolDefinition {
             output_schema: None,
             service_id: service_id.into(),
             terminal_on_success: false,
+            requires_approval: false,
             model_config: None,
         }
     }
@@ -57,6 +60,12 @@ impl ToolDefinition {
         self
     }
 
+    #[must_use]
+    pub const fn with_requires_approval(mut self, requires_approval: bool) -> Self {
+        self.requires_approval = requires_approval;
+        self
+    }
+
     #[must_use]
     pub fn with_model_config(mut self, config: JsonValue) -> Self {
         self.model_config = Some(config);
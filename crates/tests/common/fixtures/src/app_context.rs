@@ -74,6 +74,7 @@ pub fn fixture_config(database_url: &str) -> Config {
         security_headers: SecurityHeadersConfig::default(),
         allow_registration: false,
         login_page_url: None,
+        oidc_providers: Vec::new(),
     }
 }
 
@@ -11,7 +11,7 @@ use std::collections::BTreeMap;
 
 use anyhow::Result;
 use chrono::{Duration, Utc};
-use jsonwebtoken::{encode, Algorithm, Header};
+use jsonwebtoken::{Algorithm, Header, encode};
 use systemprompt_database::DbPool;
 use systemprompt_identifiers::{JwtToken, SessionId, UserId};
 use systemprompt_models::auth::{
@@ -129,6 +129,8 @@ fn mint_jwt_internal(
         rate_limit_tier: Some(rate_limit_tier),
         plugin_id: None,
         act: None,
+        cnf: None,
+        parent_jti: None,
     };
     let kid = active_kid().expect("active kid present");
     let mut header = Header::new(Algorithm::RS256);
@@ -10,14 +10,14 @@ use std::collections::BTreeMap;
 use std::sync::OnceLock;
 
 use chrono::{Duration, Utc};
-use jsonwebtoken::{encode, Algorithm, Header};
+use jsonwebtoken::{Algorithm, Header, encode};
 use systemprompt_identifiers::{JwtToken, SessionId, UserId};
 use systemprompt_models::auth::{
     JwtAudience, JwtClaims, Permission, RateLimitTier, TokenType, UserType,
 };
 use systemprompt_security::jwt::{AdminTokenParams, JwtService};
-use systemprompt_security::keys::authority::{active_kid, encoding_key, install_for_test};
 use systemprompt_security::keys::RsaSigningKey;
+use systemprompt_security::keys::authority::{active_kid, encoding_key, install_for_test};
 
 static SIGNING_KEY: OnceLock<RsaSigningKey> = OnceLock::new();
 
@@ -70,6 +70,8 @@ pub fn mint_bridge_jwt(user_id: &UserId, email: &str, issuer: &str) -> JwtToken
         rate_limit_tier: Some(RateLimitTier::User),
         plugin_id: None,
         act: None,
+        cnf: None,
+        parent_jti: None,
     };
 
     let kid = active_kid().expect("active kid present");
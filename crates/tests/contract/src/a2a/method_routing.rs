@@ -55,6 +55,19 @@ fn get_task_routes_correctly() {
     assert!(matches!(result, Ok(A2aRequestParams::GetTask(_))));
 }
 
+#[test]
+fn list_tasks_routes_correctly() {
+    let req = make_request(
+        "ListTasks",
+        serde_json::json!({"status": "TASK_STATE_WORKING", "pageSize": 10}),
+    );
+    let result = req.parse_request();
+    assert!(matches!(
+        result,
+        Ok(A2aRequestParams::ListTasks(ref params)) if params.page_size == Some(10)
+    ));
+}
+
 #[test]
 fn cancel_task_routes_correctly() {
     let req = make_request("CancelTask", task_id_params("task-1"));
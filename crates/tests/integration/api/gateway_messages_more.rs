@@ -233,6 +233,9 @@ fn gateway_route() -> systemprompt_models::profile::GatewayRoute {
         extra_headers: std::collections::HashMap::new(),
         pricing: None,
         when: None,
+        weight: 1,
+        targets: Vec::new(),
+        fallback: None,
     };
     route.ensure_id();
     route
@@ -184,6 +184,14 @@ mod routes_oauth_webauthn_complete;
 #[path = "routes_oauth_token_exchange.rs"]
 mod routes_oauth_token_exchange;
 
+#[cfg(test)]
+#[path = "routes_oauth_device.rs"]
+mod routes_oauth_device;
+
+#[cfg(test)]
+#[path = "routes_oauth_federated.rs"]
+mod routes_oauth_federated;
+
 #[cfg(test)]
 #[path = "routes_health_discovery.rs"]
 mod routes_health_discovery;
@@ -308,6 +316,10 @@ mod proxy_mcp_session_cache;
 #[path = "proxy_audit_tap.rs"]
 mod proxy_audit_tap;
 
+#[cfg(test)]
+#[path = "proxy_dpop_hops.rs"]
+mod proxy_dpop_hops;
+
 #[cfg(test)]
 #[path = "routes_oauth_token_grants_happy.rs"]
 mod routes_oauth_token_grants_happy;
@@ -0,0 +1,370 @@
+//! A `DPoP`-bound caller token across the agent and MCP hops.
+//!
+//! The caller presents a bound token and its proof for the agent proxy route.
+//! The real `JwtContextExtractor` verifies the proof and builds the request
+//! context, and the bare `proxy::agents` router forwards to a `wiremock` agent
+//! backend, which records the token the agent would hold: unbound, confined to
+//! that agent, and linked to the caller's token. That token is then sent as a
+//! bearer token for the MCP proxy route, as the agent's MCP client sends it:
+//! it must pass the same extractor without a proof and reach a `wiremock` MCP
+//! backend through `proxy::mcp`, which receives a token confined to itself.
+//! The agent's token is refused on other routes and once the caller's token is
+//! revoked, and the bound token itself sent as a bearer token is refused.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Once};
+
+use anyhow::Result;
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{HeaderMap, Method, Request, Uri, header};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use chrono::{Duration, Utc};
+use jsonwebtoken::jwk::{Jwk, ThumbprintHash};
+use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use serde_json::json;
+use systemprompt_api::routes::proxy::{agents, mcp};
+use systemprompt_api::services::middleware::{
+    ContextExtractor, JtiRevocationChecker, JwtContextExtractor,
+};
+use systemprompt_models::Config;
+use systemprompt_models::auth::{
+    ConfirmationClaim, HopTarget, JwtAudience, JwtClaims, Permission, RateLimitTier, TokenType,
+    UserType,
+};
+use systemprompt_models::execution::context::RequestContext;
+use systemprompt_security::dpop::{DPOP_HEADER, access_token_hash};
+use systemprompt_security::extract_user_context;
+use systemprompt_security::keys::authority::{active_kid, encoding_key};
+use systemprompt_test_fixtures::{
+    AuthedFixture, ensure_test_bootstrap, fixture_config, install_test_signing_key, next_test_key,
+    seed_admin_credential,
+};
+use systemprompt_traits::{AnalyticsProvider, UserProvider};
+use systemprompt_users::UserService;
+use tower::ServiceExt;
+use uuid::Uuid;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use super::common::{body_to_string, setup_ctx};
+
+static CONFIG_INSTALL: Once = Once::new();
+
+fn ensure_config() {
+    CONFIG_INSTALL.call_once(|| {
+        let b = ensure_test_bootstrap();
+        let _ = Config::install(fixture_config(&b.database_url));
+    });
+}
+
+struct ProofKey {
+    encoding: EncodingKey,
+    jwk: Jwk,
+}
+
+impl ProofKey {
+    fn new() -> Self {
+        let pem = next_test_key().to_pkcs8_pem().expect("pem");
+        let encoding = EncodingKey::from_rsa_pem(pem.as_bytes()).expect("encoding key");
+        let jwk = Jwk::from_encoding_key(&encoding, Algorithm::RS256).expect("public jwk");
+        Self { encoding, jwk }
+    }
+
+    fn jkt(&self) -> String {
+        self.jwk.thumbprint(ThumbprintHash::SHA256)
+    }
+
+    fn proof(&self, htm: &Method, htu: &str, access_token: &str) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.typ = Some("dpop+jwt".to_owned());
+        header.jwk = Some(self.jwk.clone());
+        let claims = json!({
+            "jti": Uuid::new_v4().to_string(),
+            "htm": htm.as_str(),
+            "htu": htu,
+            "iat": Utc::now().timestamp(),
+            "ath": access_token_hash(access_token),
+        });
+        encode(&header, &claims, &self.encoding).expect("sign proof")
+    }
+}
+
+/// The seeded admin's token, re-minted under the configured issuer and bound
+/// to `jkt`.
+fn bound_token(fixture: &AuthedFixture, jkt: &str) -> String {
+    let issuer = Config::get().expect("config installed").jwt_issuer.clone();
+    let now = Utc::now();
+    let claims = JwtClaims {
+        sub: fixture.user_id.to_string(),
+        iat: now.timestamp(),
+        exp: (now + Duration::hours(1)).timestamp(),
+        nbf: Some(now.timestamp()),
+        iss: issuer,
+        aud: JwtAudience::standard(),
+        jti: Uuid::new_v4().to_string(),
+        scope: vec![Permission::Admin],
+        username: fixture.email.clone(),
+        email: fixture.email.clone(),
+        user_type: UserType::Admin,
+        roles: vec!["admin".to_owned(), "user".to_owned()],
+        attributes: BTreeMap::new(),
+        client_id: None,
+        token_type: TokenType::Bearer,
+        auth_time: now.timestamp(),
+        session_id: Some(fixture.session_id.clone()),
+        rate_limit_tier: Some(RateLimitTier::Admin),
+        plugin_id: None,
+        act: None,
+        cnf: Some(ConfirmationClaim {
+            jkt: jkt.to_owned(),
+        }),
+        parent_jti: None,
+    };
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(active_kid().expect("active kid").to_owned());
+    encode(&header, &claims, encoding_key().expect("encoding key")).expect("encode jwt")
+}
+
+fn extractor(ctx: &systemprompt_runtime::AppContext) -> JwtContextExtractor {
+    let concrete = Arc::clone(ctx.analytics_service());
+    let analytics: Arc<dyn AnalyticsProvider> = concrete;
+    let user_provider: Arc<dyn UserProvider> =
+        Arc::new(UserService::new(Arc::clone(ctx.user_repository())));
+    let jti = JtiRevocationChecker::from_repository(ctx.oauth_repositories().oauth.clone());
+    JwtContextExtractor::new(analytics, user_provider, jti)
+}
+
+fn htu(uri: &Uri) -> String {
+    let base = Config::get()
+        .expect("config installed")
+        .api_external_url
+        .clone();
+    format!("{}{}", base.trim_end_matches('/'), uri.path())
+}
+
+async fn inject_ctx(
+    State(rc): State<RequestContext>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    req.extensions_mut().insert(rc);
+    next.run(req).await
+}
+
+fn forwarded_request(uri: &str, headers: &HeaderMap) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method(http::Method::POST)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json");
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+    builder
+        .body(Body::from(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#))
+        .expect("request build")
+}
+
+async fn mock_backend(at: &str) -> MockServer {
+    let backend = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(at))
+        .respond_with(ResponseTemplate::new(200).set_body_string("backend-ok"))
+        .mount(&backend)
+        .await;
+    backend
+}
+
+async fn received_authorization(backend: &MockServer) -> (String, bool) {
+    let requests = backend
+        .received_requests()
+        .await
+        .expect("recorded requests");
+    assert_eq!(requests.len(), 1, "the backend is called once");
+    let authorization = requests[0]
+        .headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .expect("forwarded authorization")
+        .to_owned();
+    (
+        authorization,
+        requests[0].headers.get(DPOP_HEADER).is_some(),
+    )
+}
+
+/// Sends `bound` with a fresh proof through the agent proxy route of a new
+/// `wiremock` agent and returns the agent's name and the token it received.
+async fn token_held_by_an_agent(
+    db: &systemprompt_database::DbPool,
+    ctx: &systemprompt_runtime::AppContext,
+    key: &ProofKey,
+    bound: &str,
+) -> Result<(String, String)> {
+    let agent = format!("dpop-agent-{}", Uuid::new_v4().simple());
+    let agent_backend = mock_backend("/").await;
+    systemprompt_test_fixtures::seed_running_service(
+        db,
+        &agent,
+        "custom",
+        agent_backend.address().port(),
+    )
+    .await?;
+
+    let agent_uri: Uri = format!("/api/v1/agents/{agent}").parse()?;
+    let mut caller = HeaderMap::new();
+    caller.insert(header::AUTHORIZATION, format!("DPoP {bound}").parse()?);
+    caller.insert(
+        DPOP_HEADER,
+        key.proof(&Method::POST, &htu(&agent_uri), bound).parse()?,
+    );
+    let agent_ctx = extractor(ctx)
+        .extract_from_parts(&Method::POST, &agent_uri, &caller)
+        .await?;
+
+    let app = agents::router(ctx).layer(middleware::from_fn_with_state(agent_ctx, inject_ctx));
+    let resp = app
+        .oneshot(forwarded_request(&format!("/{agent}"), &caller))
+        .await?;
+    let (status, body) = body_to_string(resp).await?;
+    assert_eq!(status.as_u16(), 200, "{body}");
+
+    let (authorization, proof_forwarded) = received_authorization(&agent_backend).await;
+    assert!(!proof_forwarded, "the caller's proof stays at the proxy");
+    let agent_token = authorization
+        .strip_prefix("Bearer ")
+        .expect("the agent receives a bearer token")
+        .to_owned();
+    assert_ne!(agent_token, bound, "the bound token is not forwarded");
+    Ok((agent, agent_token))
+}
+
+#[tokio::test]
+async fn bound_token_reaches_mcp_through_an_agent() -> Result<()> {
+    ensure_config();
+    install_test_signing_key();
+    let (db, ctx) = setup_ctx().await?;
+    let extractor = extractor(&ctx);
+    let fixture = seed_admin_credential(&db, "dpop-hops").await?;
+    let key = ProofKey::new();
+    let bound = bound_token(&fixture, &key.jkt());
+    let bound_jti = extract_user_context(&bound)?.jti;
+
+    let (agent, agent_token) = token_held_by_an_agent(&db, &ctx, &key, &bound).await?;
+    let claims = extract_user_context(&agent_token)?;
+    assert!(claims.dpop_jkt.is_none(), "the forwarded token is unbound");
+    assert_eq!(claims.user_id.as_str(), fixture.user_id.as_str());
+    assert_eq!(claims.session_id.as_str(), fixture.session_id.as_str());
+    assert_eq!(claims.hop_target, Some(HopTarget::Agent(agent)));
+    assert_eq!(claims.parent_jti.as_deref(), Some(bound_jti.as_str()));
+    assert!(claims.exp <= (Utc::now() + Duration::minutes(15)).timestamp());
+
+    let server = format!("dpop-mcp-{}", Uuid::new_v4().simple());
+    let mcp_backend = mock_backend("/mcp").await;
+    systemprompt_test_fixtures::seed_running_service(
+        &db,
+        &server,
+        "custom",
+        mcp_backend.address().port(),
+    )
+    .await?;
+
+    let mcp_uri: Uri = format!("/api/v1/mcp/{server}/mcp").parse()?;
+    let mut from_agent = HeaderMap::new();
+    from_agent.insert(
+        header::AUTHORIZATION,
+        format!("Bearer {agent_token}").parse()?,
+    );
+    let mcp_ctx = extractor
+        .extract_from_parts(&Method::POST, &mcp_uri, &from_agent)
+        .await?;
+
+    let app = mcp::router(&ctx).layer(middleware::from_fn_with_state(mcp_ctx, inject_ctx));
+    let resp = app
+        .oneshot(forwarded_request(&format!("/{server}/mcp"), &from_agent))
+        .await?;
+    let (status, body) = body_to_string(resp).await?;
+    assert_eq!(status.as_u16(), 200, "{body}");
+    let (authorization, _) = received_authorization(&mcp_backend).await;
+    let mcp_token = authorization
+        .strip_prefix("Bearer ")
+        .expect("the MCP server receives a bearer token");
+    assert_ne!(mcp_token, agent_token, "the agent's token is not forwarded");
+    let claims = extract_user_context(mcp_token)?;
+    assert_eq!(claims.hop_target, Some(HopTarget::McpServer(server)));
+    assert_eq!(claims.parent_jti.as_deref(), Some(bound_jti.as_str()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn agent_token_is_refused_on_other_routes_and_after_revocation() -> Result<()> {
+    ensure_config();
+    install_test_signing_key();
+    let (db, ctx) = setup_ctx().await?;
+    let fixture = seed_admin_credential(&db, "dpop-confined").await?;
+    let key = ProofKey::new();
+    let bound = bound_token(&fixture, &key.jkt());
+    let (agent, agent_token) = token_held_by_an_agent(&db, &ctx, &key, &bound).await?;
+
+    let mut from_agent = HeaderMap::new();
+    from_agent.insert(
+        header::AUTHORIZATION,
+        format!("Bearer {agent_token}").parse()?,
+    );
+    let own_route: Uri = format!("/api/v1/agents/{agent}").parse()?;
+    for uri in ["/api/v1/agents/another-agent", "/api/v1/core/users"] {
+        let result = extractor(&ctx)
+            .extract_from_parts(&Method::POST, &uri.parse::<Uri>()?, &from_agent)
+            .await;
+        assert!(result.is_err(), "the agent's token is refused on {uri}");
+    }
+    assert!(
+        extractor(&ctx).extract_standard(&from_agent).await.is_err(),
+        "a route that cannot name its target refuses it"
+    );
+    extractor(&ctx)
+        .extract_from_parts(&Method::POST, &own_route, &from_agent)
+        .await?;
+
+    let caller = extract_user_context(&bound)?;
+    ctx.oauth_repositories()
+        .oauth
+        .revoke_jti(
+            &caller.jti,
+            fixture.user_id.as_str().parse()?,
+            Utc::now() + Duration::hours(1),
+        )
+        .await?;
+    let result = extractor(&ctx)
+        .extract_from_parts(&Method::POST, &own_route, &from_agent)
+        .await;
+    assert!(
+        result.is_err(),
+        "revoking the caller's token revokes the agent's"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn bound_token_resent_as_bearer_is_refused() -> Result<()> {
+    ensure_config();
+    install_test_signing_key();
+    let (db, ctx) = setup_ctx().await?;
+    let extractor = extractor(&ctx);
+    let fixture = seed_admin_credential(&db, "dpop-resent").await?;
+    let key = ProofKey::new();
+    let bound = bound_token(&fixture, &key.jkt());
+
+    let mcp_uri: Uri = "/api/v1/mcp/any-server/mcp".parse()?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::AUTHORIZATION, format!("Bearer {bound}").parse()?);
+    let result = extractor
+        .extract_from_parts(&Method::POST, &mcp_uri, &headers)
+        .await;
+    assert!(
+        result.is_err(),
+        "a bound token needs the DPoP scheme and a proof"
+    );
+    Ok(())
+}
@@ -64,6 +64,7 @@ fn test_config() -> Config {
         security_headers: SecurityHeadersConfig::default(),
         allow_registration: false,
         login_page_url: None,
+        oidc_providers: Vec::new(),
     }
 }
 
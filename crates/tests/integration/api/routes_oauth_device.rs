@@ -0,0 +1,445 @@
+//! RFC 8628 device authorization grant over HTTP. A client registered for
+//! the device-code grant starts the flow at `/device_authorization` and polls
+//! `/token` while the user code is undecided. Once the signed-in user approves
+//! on the consent page the device code redeems exactly once; once they deny
+//! it the poll answers `access_denied`. The verification page is driven for
+//! its entry and unknown-code branches.
+
+use std::sync::Once;
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, Response, StatusCode, header};
+use axum::middleware::{self, Next};
+use systemprompt_api::routes::oauth::public_router;
+use systemprompt_identifiers::{AgentName, ContextId, SessionId, TraceId, UserId};
+use systemprompt_models::Config;
+use systemprompt_models::config::RateLimitConfig;
+use systemprompt_models::execution::context::RequestContext;
+use systemprompt_models::profile::{ContentNegotiationConfig, SecurityHeadersConfig};
+use systemprompt_oauth::OAuthState;
+use systemprompt_oauth::repository::OAuthRepository;
+use systemprompt_oauth::services::generate_secure_token;
+use systemprompt_test_fixtures::{
+    OAuthClientFixture, ensure_test_bootstrap, fixture_db_pool, install_test_signing_key,
+    seed_oauth_client,
+};
+use systemprompt_traits::AppContext as _;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use super::common::setup_ctx;
+
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+static CONFIG_INSTALL: Once = Once::new();
+
+fn ensure_config() {
+    CONFIG_INSTALL.call_once(|| {
+        let _ = Config::install(Config {
+            instance_id: "test".to_owned(),
+            max_concurrent_streams: 16,
+            sitename: "test".to_owned(),
+            database_type: "postgres".to_owned(),
+            database_url: "postgres://x".to_owned(),
+            database_write_url: None,
+            github_link: String::new(),
+            github_token: None,
+            system_path: "/tmp".to_owned(),
+            services_path: "/tmp".to_owned(),
+            bin_path: "/tmp".to_owned(),
+            skills_path: "/tmp".to_owned(),
+            settings_path: "/tmp".to_owned(),
+            content_config_path: "/tmp".to_owned(),
+            geoip_database_path: None,
+            web_path: "/tmp".to_owned(),
+            web_config_path: "/tmp".to_owned(),
+            web_metadata_path: "/tmp".to_owned(),
+            host: "127.0.0.1".to_owned(),
+            port: 0,
+            api_server_url: "http://127.0.0.1".to_owned(),
+            api_internal_url: "http://127.0.0.1".to_owned(),
+            api_external_url: "http://127.0.0.1".to_owned(),
+            jwt_issuer: "https://issuer.test".to_owned(),
+            jwt_access_token_expiration: 3600,
+            jwt_refresh_token_expiration: 86_400,
+            jwt_audiences: vec![],
+            allowed_resource_audiences: vec!["hook".to_owned()],
+            trusted_issuers: vec![],
+            id_jag_ttl_secs: 300,
+            signing_key_path: std::path::PathBuf::from("signing_key.pem"),
+            use_https: false,
+            rate_limits: RateLimitConfig::default(),
+            cors_allowed_origins: vec![],
+            trusted_proxies: vec![],
+            is_cloud: false,
+            system_admin_username: "admin".to_owned(),
+            system_admin_email: None,
+            content_negotiation: ContentNegotiationConfig::default(),
+            security_headers: SecurityHeadersConfig::default(),
+            allow_registration: false,
+            login_page_url: None,
+            oidc_providers: Vec::new(),
+        });
+    });
+}
+
+async fn inject_context(mut req: Request<Body>, next: Next) -> Response<Body> {
+    req.extensions_mut().insert(RequestContext::new(
+        SessionId::generate(),
+        TraceId::new("device-grant"),
+        ContextId::generate(),
+        AgentName::system(),
+    ));
+    next.run(req).await
+}
+
+async fn device_app() -> anyhow::Result<Router> {
+    ensure_config();
+    install_test_signing_key();
+    let (_pool, ctx) = setup_ctx().await?;
+    let state = OAuthState::new(
+        ctx.oauth_repositories().oauth.clone(),
+        ctx.analytics_provider().expect("analytics"),
+        ctx.user_provider().expect("user"),
+    );
+    Ok(public_router()
+        .layer(middleware::from_fn(inject_context))
+        .with_state(state))
+}
+
+struct SeededDeviceClient {
+    client: OAuthClientFixture,
+    user: UserId,
+    repo: OAuthRepository,
+}
+
+async fn seed_device_client(with_device_grant: bool) -> anyhow::Result<SeededDeviceClient> {
+    let b = ensure_test_bootstrap();
+    let pool = fixture_db_pool(&b.database_url).await?;
+    let user = UserId::new(Uuid::new_v4().to_string());
+    let p = pool.pool_arc().expect("read pool");
+    sqlx::query("INSERT INTO users (id, name, email) VALUES ($1, $1, $2) ON CONFLICT DO NOTHING")
+        .bind(user.as_str())
+        .bind(format!("{}@device.invalid", user.as_str()))
+        .execute(p.as_ref())
+        .await?;
+    let client = seed_oauth_client(&pool, &user).await?;
+    if with_device_grant {
+        sqlx::query("INSERT INTO oauth_client_grant_types (client_id, grant_type) VALUES ($1, $2)")
+            .bind(client.client_id.as_str())
+            .bind(DEVICE_CODE_GRANT)
+            .execute(p.as_ref())
+            .await?;
+    }
+    let repo = OAuthRepository::new(&pool).map_err(|e| anyhow::anyhow!("oauth repo: {e}"))?;
+    Ok(SeededDeviceClient { client, user, repo })
+}
+
+fn form_post(uri: &str, body: String) -> Request<Body> {
+    Request::builder()
+        .method(http::Method::POST)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from(body))
+        .expect("build")
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method(http::Method::GET)
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build")
+}
+
+async fn read_json(resp: Response<Body>) -> anyhow::Result<serde_json::Value> {
+    let bytes = to_bytes(resp.into_body(), 1024 * 1024).await?;
+    Ok(serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+async fn read_text(resp: Response<Body>) -> anyhow::Result<String> {
+    let bytes = to_bytes(resp.into_body(), 1024 * 1024).await?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn urlencode(pairs: &[(&str, &str)]) -> String {
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", enc(k), enc(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn enc(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char);
+            },
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{b:02X}")),
+        }
+    }
+    out
+}
+
+async fn start(
+    app: Router,
+    client: &OAuthClientFixture,
+) -> anyhow::Result<(StatusCode, serde_json::Value)> {
+    let body = urlencode(&[
+        ("client_id", client.client_id.as_str()),
+        ("client_secret", client.client_secret.as_str()),
+        ("scope", "user"),
+    ]);
+    let resp = app
+        .oneshot(form_post("/device_authorization", body))
+        .await?;
+    let status = resp.status();
+    Ok((status, read_json(resp).await?))
+}
+
+async fn poll(
+    app: Router,
+    client: &OAuthClientFixture,
+    device_code: &str,
+) -> anyhow::Result<(StatusCode, serde_json::Value)> {
+    let body = urlencode(&[
+        ("grant_type", DEVICE_CODE_GRANT),
+        ("device_code", device_code),
+        ("client_id", client.client_id.as_str()),
+        ("client_secret", client.client_secret.as_str()),
+    ]);
+    let resp = app.oneshot(form_post("/token", body)).await?;
+    let status = resp.status();
+    Ok((status, read_json(resp).await?))
+}
+
+/// Records the passkey sign-in `webauthn/complete` performs, returning the
+/// consent token the consent page would carry.
+async fn sign_in(seeded: &SeededDeviceClient, user_code: &str) -> anyhow::Result<String> {
+    let consent_token = generate_secure_token("device_consent");
+    let verified = seeded
+        .repo
+        .verify_device_code(user_code, &seeded.user, &consent_token)
+        .await
+        .map_err(|e| anyhow::anyhow!("verify: {e}"))?;
+    assert!(verified, "a pending code verifies");
+    Ok(consent_token)
+}
+
+async fn decide(
+    app: Router,
+    user_code: &str,
+    consent_token: &str,
+    decision: &str,
+) -> anyhow::Result<(StatusCode, String)> {
+    let body = urlencode(&[
+        ("user_code", user_code),
+        ("consent_token", consent_token),
+        ("decision", decision),
+    ]);
+    let resp = app.oneshot(form_post("/device/consent", body)).await?;
+    let status = resp.status();
+    Ok((status, read_text(resp).await?))
+}
+
+#[tokio::test]
+async fn device_authorization_returns_codes_and_verification_uri() -> anyhow::Result<()> {
+    let seeded = seed_device_client(true).await?;
+    let app = device_app().await?;
+    let (status, v) = start(app, &seeded.client).await?;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    assert!(
+        v["device_code"].as_str().is_some_and(|c| !c.is_empty()),
+        "{v}"
+    );
+    let user_code = v["user_code"].as_str().expect("user_code");
+    assert_eq!(user_code.len(), 9, "{v}");
+    assert_eq!(user_code.as_bytes()[4], b'-', "{v}");
+    assert!(
+        v["verification_uri"]
+            .as_str()
+            .is_some_and(|u| u.ends_with("/api/v1/core/oauth/device")),
+        "{v}"
+    );
+    assert!(
+        v["verification_uri_complete"]
+            .as_str()
+            .is_some_and(|u| u.contains("user_code=")),
+        "{v}"
+    );
+    assert_eq!(v["expires_in"].as_i64(), Some(600), "{v}");
+    assert_eq!(v["interval"].as_i64(), Some(5), "{v}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn device_authorization_rejects_client_without_device_grant() -> anyhow::Result<()> {
+    let seeded = seed_device_client(false).await?;
+    let app = device_app().await?;
+    let (status, v) = start(app, &seeded.client).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{v}");
+    assert_eq!(v["error"].as_str(), Some("unauthorized_client"), "{v}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn device_authorization_rejects_unknown_client() -> anyhow::Result<()> {
+    let app = device_app().await?;
+    let body = urlencode(&[("client_id", "no-such-device-client")]);
+    let resp = app
+        .oneshot(form_post("/device_authorization", body))
+        .await?;
+    let status = resp.status();
+    let v = read_json(resp).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{v}");
+    assert_eq!(v["error"].as_str(), Some("invalid_client"), "{v}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn device_code_polls_pending_then_slow_down() -> anyhow::Result<()> {
+    let seeded = seed_device_client(true).await?;
+    let app = device_app().await?;
+    let (_, started) = start(app.clone(), &seeded.client).await?;
+    let device_code = started["device_code"].as_str().expect("device_code");
+
+    let (status, v) = poll(app.clone(), &seeded.client, device_code).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{v}");
+    assert_eq!(v["error"].as_str(), Some("authorization_pending"), "{v}");
+
+    let (status, v) = poll(app, &seeded.client, device_code).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{v}");
+    assert_eq!(v["error"].as_str(), Some("slow_down"), "{v}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn approved_device_code_is_redeemed_once() -> anyhow::Result<()> {
+    let seeded = seed_device_client(true).await?;
+    let app = device_app().await?;
+    let (_, started) = start(app.clone(), &seeded.client).await?;
+    let device_code = started["device_code"].as_str().expect("device_code");
+    let user_code = started["user_code"].as_str().expect("user_code");
+
+    let consent_token = sign_in(&seeded, user_code).await?;
+    let (status, html) = decide(app.clone(), user_code, &consent_token, "approve").await?;
+    assert_eq!(status, StatusCode::OK, "{html}");
+    assert!(html.contains(seeded.client.client_id.as_str()), "{html}");
+
+    let (status, v) = poll(app.clone(), &seeded.client, device_code).await?;
+    assert!(status.is_success(), "expected 200, got {status} {v}");
+    assert!(
+        v["access_token"].as_str().is_some_and(|t| !t.is_empty()),
+        "{v}"
+    );
+    assert_eq!(v["token_type"].as_str(), Some("Bearer"), "{v}");
+
+    let (status, v) = poll(app, &seeded.client, device_code).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{v}");
+    assert_eq!(v["error"].as_str(), Some("invalid_grant"), "{v}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn denied_device_code_polls_access_denied() -> anyhow::Result<()> {
+    let seeded = seed_device_client(true).await?;
+    let app = device_app().await?;
+    let (_, started) = start(app.clone(), &seeded.client).await?;
+    let device_code = started["device_code"].as_str().expect("device_code");
+    let user_code = started["user_code"].as_str().expect("user_code");
+
+    let consent_token = sign_in(&seeded, user_code).await?;
+    let (status, html) = decide(app.clone(), user_code, &consent_token, "deny").await?;
+    assert_eq!(status, StatusCode::OK, "{html}");
+    assert!(html.contains("denied"), "{html}");
+
+    let (status, v) = poll(app.clone(), &seeded.client, device_code).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{v}");
+    assert_eq!(v["error"].as_str(), Some("access_denied"), "{v}");
+
+    let (status, _) = decide(app, user_code, &consent_token, "approve").await?;
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "a denied code cannot be approved afterwards"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn signing_in_without_consent_leaves_the_code_pending() -> anyhow::Result<()> {
+    let seeded = seed_device_client(true).await?;
+    let app = device_app().await?;
+    let (_, started) = start(app.clone(), &seeded.client).await?;
+    let device_code = started["device_code"].as_str().expect("device_code");
+    let user_code = started["user_code"].as_str().expect("user_code");
+
+    sign_in(&seeded, user_code).await?;
+    let (status, v) = decide(app.clone(), user_code, "device_consent_forged", "approve").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{v}");
+
+    let (status, v) = poll(app, &seeded.client, device_code).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{v}");
+    assert_eq!(v["error"].as_str(), Some("authorization_pending"), "{v}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn device_code_from_another_client_is_invalid() -> anyhow::Result<()> {
+    let owner = seed_device_client(true).await?;
+    let other = seed_device_client(true).await?;
+    let app = device_app().await?;
+    let (_, started) = start(app.clone(), &owner.client).await?;
+    let device_code = started["device_code"].as_str().expect("device_code");
+
+    let (status, v) = poll(app, &other.client, device_code).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{v}");
+    assert_eq!(v["error"].as_str(), Some("invalid_grant"), "{v}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn verification_page_without_code_asks_for_one() -> anyhow::Result<()> {
+    let app = device_app().await?;
+    let resp = app.oneshot(get("/device")).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let html = read_text(resp).await?;
+    assert!(html.contains("name=\"user_code\""), "{html}");
+    assert!(html.contains("error hidden"), "{html}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn verification_page_rejects_unknown_code() -> anyhow::Result<()> {
+    let app = device_app().await?;
+    let resp = app.oneshot(get("/device?user_code=BBBB-BBBB")).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let html = read_text(resp).await?;
+    assert!(html.contains("unknown, expired, or already used"), "{html}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn verification_page_renders_sign_in_for_pending_code() -> anyhow::Result<()> {
+    let seeded = seed_device_client(true).await?;
+    let app = device_app().await?;
+    let (_, started) = start(app.clone(), &seeded.client).await?;
+    let user_code = started["user_code"].as_str().expect("user_code");
+    let typed = user_code.replace('-', "").to_ascii_lowercase();
+
+    let resp = app
+        .oneshot(get(&format!("/device?user_code={typed}")))
+        .await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let html = read_text(resp).await?;
+    assert!(
+        html.contains(&format!("id=\"oauth-user-code\" value=\"{user_code}\"")),
+        "{html}"
+    );
+    assert!(html.contains(seeded.client.client_id.as_str()), "{html}");
+    Ok(())
+}
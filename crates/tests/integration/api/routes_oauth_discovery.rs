@@ -65,6 +65,7 @@ fn test_config() -> Config {
         security_headers: SecurityHeadersConfig::default(),
         allow_registration: false,
         login_page_url: None,
+        oidc_providers: Vec::new(),
     }
 }
 
@@ -212,3 +213,55 @@ async fn well_known_advertises_the_id_jag_grant_profile() -> anyhow::Result<()>
     );
     Ok(())
 }
+
+#[tokio::test]
+async fn well_known_advertises_device_authorization() -> anyhow::Result<()> {
+    let app = discovery_app().await?;
+    let resp = app
+        .oneshot(empty_get("/.well-known/openid-configuration"))
+        .await?;
+    let (status, body) = body_to_string(resp).await?;
+    assert!(status.is_success(), "{status}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+
+    assert!(
+        json["device_authorization_endpoint"]
+            .as_str()
+            .is_some_and(|e| e.ends_with("/api/v1/core/oauth/device_authorization")),
+        "RFC 8628 endpoint must be advertised: {body}"
+    );
+    let grants = json["grant_types_supported"]
+        .as_array()
+        .expect("grant_types_supported is an array");
+    assert!(
+        grants
+            .iter()
+            .any(|g| g == "urn:ietf:params:oauth:grant-type:device_code"),
+        "device_code grant must be advertised: {body}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn well_known_advertises_dpop_signing_algorithms() -> anyhow::Result<()> {
+    let app = discovery_app().await?;
+    let resp = app
+        .oneshot(empty_get("/.well-known/openid-configuration"))
+        .await?;
+    let (status, body) = body_to_string(resp).await?;
+    assert!(status.is_success(), "{status}");
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+
+    let algs = json["dpop_signing_alg_values_supported"]
+        .as_array()
+        .expect("dpop_signing_alg_values_supported is an array");
+    assert!(
+        algs.iter().any(|a| a == "ES256"),
+        "RFC 9449 §5.1 clients pick a proof algorithm from this list: {body}"
+    );
+    assert!(
+        algs.iter().all(|a| a != "HS256"),
+        "symmetric algorithms cannot prove key possession: {body}"
+    );
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//! Upstream `OpenID` Connect sign-in routes. Both legs look the provider up
+//! in `security.oidc_providers` before touching anything else, so a provider
+//! id the profile does not configure is a 404 whether the browser is starting
+//! a sign-in or returning from one. The upstream round trip itself needs a
+//! live `IdP` and is covered by the domain federation tests.
+
+use std::sync::Once;
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, Response, StatusCode};
+use axum::middleware::{self, Next};
+use systemprompt_api::routes::oauth::public_router;
+use systemprompt_identifiers::{AgentName, ContextId, SessionId, TraceId};
+use systemprompt_models::Config;
+use systemprompt_models::config::RateLimitConfig;
+use systemprompt_models::execution::context::RequestContext;
+use systemprompt_models::profile::{ContentNegotiationConfig, SecurityHeadersConfig};
+use systemprompt_oauth::OAuthState;
+use systemprompt_traits::AppContext as _;
+use tower::ServiceExt;
+
+use super::common::setup_ctx;
+
+static CONFIG_INSTALL: Once = Once::new();
+
+fn ensure_config() {
+    CONFIG_INSTALL.call_once(|| {
+        let _ = Config::install(Config {
+            instance_id: "test".to_owned(),
+            max_concurrent_streams: 16,
+            sitename: "test".to_owned(),
+            database_type: "postgres".to_owned(),
+            database_url: "postgres://x".to_owned(),
+            database_write_url: None,
+            github_link: String::new(),
+            github_token: None,
+            system_path: "/tmp".to_owned(),
+            services_path: "/tmp".to_owned(),
+            bin_path: "/tmp".to_owned(),
+            skills_path: "/tmp".to_owned(),
+            settings_path: "/tmp".to_owned(),
+            content_config_path: "/tmp".to_owned(),
+            geoip_database_path: None,
+            web_path: "/tmp".to_owned(),
+            web_config_path: "/tmp".to_owned(),
+            web_metadata_path: "/tmp".to_owned(),
+            host: "127.0.0.1".to_owned(),
+            port: 0,
+            api_server_url: "http://127.0.0.1".to_owned(),
+            api_internal_url: "http://127.0.0.1".to_owned(),
+            api_external_url: "http://127.0.0.1".to_owned(),
+            jwt_issuer: "https://issuer.test".to_owned(),
+            jwt_access_token_expiration: 3600,
+            jwt_refresh_token_expiration: 86_400,
+            jwt_audiences: vec![],
+            allowed_resource_audiences: vec!["hook".to_owned()],
+            trusted_issuers: vec![],
+            id_jag_ttl_secs: 300,
+            signing_key_path: std::path::PathBuf::from("signing_key.pem"),
+            use_https: false,
+            rate_limits: RateLimitConfig::default(),
+            cors_allowed_origins: vec![],
+            trusted_proxies: vec![],
+            is_cloud: false,
+            system_admin_username: "admin".to_owned(),
+            system_admin_email: None,
+            content_negotiation: ContentNegotiationConfig::default(),
+            security_headers: SecurityHeadersConfig::default(),
+            allow_registration: false,
+            login_page_url: None,
+            oidc_providers: Vec::new(),
+        });
+    });
+}
+
+async fn inject_context(mut req: Request<Body>, next: Next) -> Response<Body> {
+    req.extensions_mut().insert(RequestContext::new(
+        SessionId::generate(),
+        TraceId::new("federated-sign-in"),
+        ContextId::generate(),
+        AgentName::system(),
+    ));
+    next.run(req).await
+}
+
+async fn federated_app() -> anyhow::Result<Router> {
+    ensure_config();
+    let (_pool, ctx) = setup_ctx().await?;
+    let state = OAuthState::new(
+        ctx.oauth_repositories().oauth.clone(),
+        ctx.analytics_provider().expect("analytics"),
+        ctx.user_provider().expect("user"),
+    );
+    Ok(public_router()
+        .layer(middleware::from_fn(inject_context))
+        .with_state(state))
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method(http::Method::GET)
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build")
+}
+
+async fn read_json(resp: Response<Body>) -> anyhow::Result<serde_json::Value> {
+    let bytes = to_bytes(resp.into_body(), 1024 * 1024).await?;
+    Ok(serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+#[tokio::test]
+async fn start_with_an_unconfigured_provider_is_not_found() -> anyhow::Result<()> {
+    let app = federated_app().await?;
+    let resp = app
+        .oneshot(get(
+            "/federated/not-configured/start?response_type=code&client_id=c&state=s",
+        ))
+        .await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let v = read_json(resp).await?;
+    assert!(
+        v["error_description"]
+            .as_str()
+            .is_some_and(|d| d.contains("not-configured")),
+        "{v}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn callback_for_an_unconfigured_provider_is_not_found() -> anyhow::Result<()> {
+    let app = federated_app().await?;
+    let resp = app
+        .oneshot(get("/federated/not-configured/callback?code=c&state=s"))
+        .await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
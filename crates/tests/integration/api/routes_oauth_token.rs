@@ -78,6 +78,7 @@ fn ensure_config() {
             security_headers: SecurityHeadersConfig::default(),
             allow_registration: false,
             login_page_url: None,
+            oidc_providers: Vec::new(),
         });
     });
 }
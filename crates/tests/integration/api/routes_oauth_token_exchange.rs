@@ -81,6 +81,7 @@ fn ensure_config() {
             security_headers: SecurityHeadersConfig::default(),
             allow_registration: false,
             login_page_url: None,
+            oidc_providers: Vec::new(),
         });
     });
 }
@@ -182,6 +183,7 @@ fn mint_self_issued_subject(scopes: Vec<Permission>) -> String {
         expires_in_hours: Some(1),
         resource: None,
         plugin_id: None,
+        dpop_jkt: None,
     };
     let signing = JwtSigningParams { issuer: &issuer };
     generate_jwt(
@@ -5,7 +5,7 @@
 
 use systemprompt_api::services::gateway::policy::{PolicyResolver, QuotaWindow};
 use systemprompt_api::services::gateway::quota::{
-    PostUpdateParams, post_update_tokens, precheck_and_reserve,
+    PostUpdateParams, align_window, post_update_tokens, precheck_and_reserve,
 };
 use systemprompt_identifiers::UserId;
 
@@ -97,6 +97,8 @@ async fn precheck_denies_once_the_cost_ceiling_is_spent() {
         PostUpdateParams {
             user_id: &user,
             windows: &windows,
+            rate_limits: &[],
+            alerts: &[],
             input_tokens: 10,
             output_tokens: 20,
             cost_microdollars: 1_500,
@@ -143,6 +145,8 @@ async fn post_update_with_empty_windows_is_noop() {
         PostUpdateParams {
             user_id: &user,
             windows: &[],
+            rate_limits: &[],
+            alerts: &[],
             input_tokens: 100,
             output_tokens: 50,
             cost_microdollars: 10,
@@ -167,6 +171,8 @@ async fn post_update_increments_token_counts() {
         PostUpdateParams {
             user_id: &user,
             windows: &windows,
+            rate_limits: &[],
+            alerts: &[],
             input_tokens: 10,
             output_tokens: 20,
             cost_microdollars: 5,
@@ -175,6 +181,52 @@ async fn post_update_increments_token_counts() {
     .await;
 }
 
+#[tokio::test]
+async fn spend_past_an_alert_threshold_lists_the_subject_by_spend() {
+    let p = pool().await;
+    let user = UserId::new(format!("quota-budget-{}", uuid::Uuid::new_v4()));
+    let windows = vec![QuotaWindow {
+        max_cost_microdollars: Some(1_000),
+        alert_thresholds: vec![50, 80],
+        ..window(86_400)
+    }];
+    post_update_tokens(
+        &p,
+        &quota_repo(&p),
+        PostUpdateParams {
+            user_id: &user,
+            windows: &windows,
+            rate_limits: &[],
+            alerts: &[],
+            input_tokens: 10,
+            output_tokens: 20,
+            cost_microdollars: 850,
+        },
+    )
+    .await;
+
+    let window_start = align_window(chrono::Utc::now(), 86_400);
+    let rows = quota_repo(&p)
+        .list_by_spend("user", 86_400, window_start, 800)
+        .await
+        .expect("list");
+    let row = rows
+        .iter()
+        .find(|row| row.subject_id == user.as_str())
+        .expect("the subject past 80% is listed");
+    assert_eq!(row.cost_microdollars, 850);
+
+    let above_ceiling = quota_repo(&p)
+        .list_by_spend("user", 86_400, window_start, 1_000)
+        .await
+        .expect("list");
+    assert!(
+        above_ceiling
+            .iter()
+            .all(|row| row.subject_id != user.as_str())
+    );
+}
+
 #[tokio::test]
 async fn policy_resolver_falls_back_when_empty() {
     let p = pool().await;
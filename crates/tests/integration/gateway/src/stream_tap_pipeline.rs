@@ -110,6 +110,8 @@ fn tap_ctx(db: &DbPool, ai_request_id: &AiRequestId, policy: GatewayPolicySpec)
         repos: gateway_repos(db),
         policy,
         ai_request_id: ai_request_id.clone(),
+        cache: None,
+        rewriter: None,
     }
 }
 
@@ -373,3 +375,171 @@ async fn tap_completion_runs_response_safety_scan() {
     assert_eq!(phase, "response");
     assert_eq!(scanner, "heuristic");
 }
+
+#[tokio::test]
+async fn tap_cuts_off_a_stream_when_a_blocked_category_completes() {
+    let db = setup_db().await;
+    let user_id = seed_user(&db).await;
+    let (audit, ai_request_id) = open_audit(&db, user_id).await;
+
+    let upstream = events_stream(vec![
+        Ok(CanonicalEvent::MessageStart {
+            id: "resp-tap-5".to_owned(),
+            model: "claude-served".to_owned(),
+            usage: usage(4, 0),
+        }),
+        Ok(CanonicalEvent::ContentBlockStart {
+            index: 0,
+            block: ContentBlockKind::Text,
+        }),
+        Ok(CanonicalEvent::TextDelta {
+            index: 0,
+            text: "Your card is 4111 1111 ".to_owned(),
+        }),
+        Ok(CanonicalEvent::TextDelta {
+            index: 0,
+            text: "1111 1111, keep it safe.".to_owned(),
+        }),
+        Ok(CanonicalEvent::ContentBlockStop { index: 0 }),
+        Ok(CanonicalEvent::MessageStop {
+            id: "resp-tap-5".to_owned(),
+            stop_reason: Some(CanonicalStopReason::EndTurn),
+        }),
+    ]);
+    let inbound: Arc<dyn InboundAdapter> = Arc::new(AnthropicMessagesInbound);
+    let mut policy = GatewayPolicySpec::default();
+    policy.safety.scanners = vec!["pattern".to_owned()];
+    policy.safety.block_response_categories = vec!["pii_credit_card".to_owned()];
+    policy.safety.stream.enforce = true;
+    let body = tap(
+        upstream,
+        inbound,
+        "claude-test".to_owned(),
+        Arc::clone(&audit),
+        tap_ctx(&db, &ai_request_id, policy),
+    );
+    let collected = axum::body::to_bytes(body, 4 * 1024 * 1024)
+        .await
+        .expect("collect tapped body");
+    let text = String::from_utf8_lossy(&collected);
+    assert!(
+        !text.contains("4111"),
+        "the delta that started the match is still held back: {text}"
+    );
+    assert!(
+        !text.contains("keep it safe"),
+        "the delta completing the match must not reach the client: {text}"
+    );
+    assert!(text.contains("event: error"), "{text}");
+    assert!(!text.contains("message_stop"), "{text}");
+
+    let (status, error) = wait_for_terminal_status(&db, &ai_request_id).await;
+    assert_eq!(status, "truncated");
+    assert!(
+        error.as_deref().is_some_and(|e| e.contains("cut off")),
+        "{error:?}"
+    );
+    let pool = db.pool_arc().expect("read pool");
+    let (input_tokens, output_tokens): (Option<i32>, Option<i32>) =
+        sqlx::query_as("SELECT input_tokens, output_tokens FROM ai_requests WHERE id = $1")
+            .bind(ai_request_id.as_str())
+            .fetch_one(pool.as_ref())
+            .await
+            .expect("query ai_requests usage");
+    assert_eq!(input_tokens, Some(4));
+    assert!(
+        output_tokens.is_some_and(|tokens| tokens > 0),
+        "tokens generated before the cut must be charged: {output_tokens:?}"
+    );
+
+    let mut finding = None;
+    for _ in 0..200 {
+        finding = sqlx::query_as::<_, (String, String)>(
+            "SELECT phase, category FROM ai_safety_findings WHERE ai_request_id = $1",
+        )
+        .bind(ai_request_id.as_str())
+        .fetch_optional(pool.as_ref())
+        .await
+        .expect("query ai_safety_findings");
+        if finding.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+    let (phase, category) = finding.expect("the cut-off finding must be persisted");
+    assert_eq!(phase, "response");
+    assert_eq!(category, "pii_credit_card");
+}
+
+#[tokio::test]
+async fn tap_holds_back_every_delta_of_a_credential_split_across_the_stream() {
+    let db = setup_db().await;
+    let user_id = seed_user(&db).await;
+    let (audit, ai_request_id) = open_audit(&db, user_id).await;
+
+    let preamble = format!(
+        "Here is the summary you asked for. {}",
+        "All figures are final. ".repeat(16)
+    );
+    let mut events = vec![
+        Ok(CanonicalEvent::MessageStart {
+            id: "resp-tap-6".to_owned(),
+            model: "claude-served".to_owned(),
+            usage: usage(4, 0),
+        }),
+        Ok(CanonicalEvent::ContentBlockStart {
+            index: 0,
+            block: ContentBlockKind::Text,
+        }),
+        Ok(CanonicalEvent::TextDelta {
+            index: 0,
+            text: preamble,
+        }),
+    ];
+    events.extend(
+        ["Card: 4111", " 1111", " 1111", " 1111", ", expires soon."]
+            .into_iter()
+            .map(|text| {
+                Ok(CanonicalEvent::TextDelta {
+                    index: 0,
+                    text: text.to_owned(),
+                })
+            }),
+    );
+    events.extend([
+        Ok(CanonicalEvent::ContentBlockStop { index: 0 }),
+        Ok(CanonicalEvent::MessageStop {
+            id: "resp-tap-6".to_owned(),
+            stop_reason: Some(CanonicalStopReason::EndTurn),
+        }),
+    ]);
+    let inbound: Arc<dyn InboundAdapter> = Arc::new(AnthropicMessagesInbound);
+    let mut policy = GatewayPolicySpec::default();
+    policy.safety.scanners = vec!["pattern".to_owned()];
+    policy.safety.block_response_categories = vec!["pii_credit_card".to_owned()];
+    policy.safety.stream.enforce = true;
+    let body = tap(
+        events_stream(events),
+        inbound,
+        "claude-test".to_owned(),
+        Arc::clone(&audit),
+        tap_ctx(&db, &ai_request_id, policy),
+    );
+    let collected = axum::body::to_bytes(body, 4 * 1024 * 1024)
+        .await
+        .expect("collect tapped body");
+    let text = String::from_utf8_lossy(&collected);
+
+    assert!(
+        text.contains("Here is the summary"),
+        "text older than the hold-back is sent: {text}"
+    );
+    assert!(
+        !text.contains("Card:") && !text.contains("4111") && !text.contains(" 1111"),
+        "no delta of the credential reaches the client: {text}"
+    );
+    assert!(text.contains("event: error"), "{text}");
+
+    let (status, _) = wait_for_terminal_status(&db, &ai_request_id).await;
+    assert_eq!(status, "truncated");
+}
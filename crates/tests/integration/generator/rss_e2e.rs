@@ -62,6 +62,7 @@ fn install_test_config() {
             security_headers: Default::default(),
             allow_registration: false,
             login_page_url: None,
+            oidc_providers: Vec::new(),
             system_admin_username: "admin".to_owned(),
             system_admin_email: None,
         });
@@ -51,6 +51,8 @@ fn external_config(name: &str, remote_endpoint: &str) -> McpServerConfig {
         remote_endpoint: remote_endpoint.to_owned(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
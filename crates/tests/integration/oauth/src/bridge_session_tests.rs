@@ -78,6 +78,7 @@ fn test_config() -> Config {
         security_headers: Default::default(),
         allow_registration: false,
         login_page_url: None,
+        oidc_providers: Vec::new(),
         system_admin_username: "admin".to_string(),
         system_admin_email: None,
     }
@@ -58,6 +58,7 @@ fn minimal_config(
         security_headers: SecurityHeadersConfig::default(),
         allow_registration: false,
         login_page_url: None,
+        oidc_providers: Vec::new(),
         system_admin_username: "admin".to_string(),
         system_admin_email: None,
     }
@@ -20,13 +20,13 @@ use sqlx::PgPool;
 use systemprompt_database::DbPool;
 use systemprompt_identifiers::{ModelId, ProviderId, RouteId, SecretName, UserId};
 use systemprompt_models::profile::{
-    ApiSurface, GatewayConfig, ProviderEntry, ProviderModel, ProviderRegistry, WireProtocol,
-    synthesize_route_id,
+    ApiSurface, DiscoveredModels, GatewayConfig, ProviderEntry, ProviderModel, ProviderRegistry,
+    WireProtocol, synthesize_route_id,
 };
 use systemprompt_security::authz::{
     Access, AccessControlConfig, AccessControlIngestionService, AccessControlRepository, Decision,
-    DenyReason, EntityKind, EntityRef, IngestOptions, ResolveInput, RuleEntry, RuleTarget,
-    reconcile_gateway_entities, resolve,
+    DenyReason, EnforceMode, EntityKind, EntityRef, IngestOptions, ResolveInput, RuleEntry,
+    RuleTarget, reconcile_gateway_entities, resolve,
 };
 use systemprompt_test_fixtures::{fixture_database_url, fixture_db_pool};
 use uuid::Uuid;
@@ -89,6 +89,8 @@ fn registry(name: &str) -> ProviderRegistry {
                 capabilities: Default::default(),
                 limits: Default::default(),
             }],
+            local: None,
+            discovered: DiscoveredModels::default(),
         }],
     }
 }
@@ -110,6 +112,7 @@ fn wildcard_gateway_rule(roles: &[&str]) -> AccessControlConfig {
             default_included: true,
             roles: roles.iter().map(|r| (*r).to_owned()).collect(),
             justification: None,
+            enforce: EnforceMode::Enforce,
         }],
     }
 }
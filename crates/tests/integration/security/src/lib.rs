@@ -4,7 +4,8 @@
 //! algorithm enforcement, the unknown-kid DoS guard) stands up a real
 //! `wiremock` HTTP JWKS endpoint. The marketplace-access ingestion suite
 //! projects declarative `access` blocks into the two-table authz schema
-//! against a real Postgres instance via `DATABASE_URL`.
+//! against a real Postgres instance via `DATABASE_URL`, as does the shared
+//! `rate_limit` window.
 
 #[cfg(test)]
 mod support;
@@ -38,3 +39,6 @@ mod slack_teams_ingestion_tests;
 
 #[cfg(test)]
 mod gateway_reconcile_tests;
+
+#[cfg(test)]
+mod shared_rate_limit_tests;
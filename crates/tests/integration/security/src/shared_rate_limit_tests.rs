@@ -0,0 +1,105 @@
+//! DB-backed coverage for `rate_limit` with `backend: postgres`.
+//!
+//! Two limiters configured in one process charge the same calls into the
+//! shared table. Each must keep its own window: a short-window limiter
+//! pruning its expired charges must not empty the bucket a longer-window
+//! limiter is still counting.
+//!
+//! This is the only test in the binary that installs the shared store, which
+//! is process-global.
+
+use std::time::Duration;
+
+use serde_json::json;
+use systemprompt_identifiers::{CallId, McpToolName, SessionId, UserId};
+use systemprompt_security::authz::types::Decision;
+use systemprompt_security::policy::governed::{GovernedInput, GovernedTarget, McpToolInput};
+use systemprompt_security::policy::types::{AccessScope, AgentScope, PolicyContext};
+use systemprompt_security::policy::{
+    GovernanceConfig, GovernanceEngine, install_shared_rate_limiter,
+};
+use systemprompt_test_fixtures::{fixture_database_url, fixture_db_pool};
+use uuid::Uuid;
+
+fn postgres_limiter(window_secs: u64) -> GovernanceEngine {
+    let yaml = format!(
+        r#"
+governance:
+  enabled: true
+  policies:
+    - id: rate_limit
+      enabled: true
+      requests_per_window: 1
+      window_secs: {window_secs}
+      backend: postgres
+      store_timeout_ms: 5000
+"#
+    );
+    GovernanceEngine::from_config(&GovernanceConfig::parse(&yaml).expect("governance yaml"))
+}
+
+fn charge(
+    engine: &GovernanceEngine,
+    session: &SessionId,
+    user: &UserId,
+    call: &CallId,
+) -> Decision {
+    let input = GovernedInput::tool_arguments(McpToolInput::new(json!({})));
+    engine
+        .evaluate(&PolicyContext {
+            target: GovernedTarget::Tool {
+                tool: McpToolName::new("read_file"),
+            },
+            agent_scope: AgentScope::User {
+                user_id: user.clone(),
+            },
+            access_scope: AccessScope::User,
+            session_id: session,
+            user_id: user,
+            input: &input,
+            call_id: call,
+        })
+        .decision
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn differently_configured_postgres_limiters_keep_separate_windows() {
+    let url = fixture_database_url().expect("DATABASE_URL");
+    let db = fixture_db_pool(&url).await.expect("connect test database");
+    let pg = db.write_pool_arc().expect("write pool");
+    install_shared_rate_limiter(pg.clone());
+
+    let short = postgres_limiter(1);
+    let long = postgres_limiter(60);
+    let session = SessionId::generate();
+    let user = UserId::new(format!("rate-limit-{}", Uuid::new_v4().simple()));
+
+    let first = CallId::generate();
+    for engine in [&short, &long] {
+        let decision = charge(engine, &session, &user, &first);
+        assert!(
+            matches!(decision, Decision::Allow { .. }),
+            "the first call fits both budgets: {decision:?}"
+        );
+    }
+
+    tokio::time::sleep(Duration::from_millis(1_500)).await;
+
+    let second = CallId::generate();
+    let decision = charge(&short, &session, &user, &second);
+    assert!(
+        matches!(decision, Decision::Allow { .. }),
+        "the short window has expired the first call: {decision:?}"
+    );
+    let decision = charge(&long, &session, &user, &second);
+    assert!(
+        matches!(decision, Decision::Deny { .. }),
+        "the long window still holds the first call: {decision:?}"
+    );
+
+    sqlx::query("DELETE FROM governance_rate_limit_charges WHERE bucket_key LIKE $1")
+        .bind(format!("%:{}", user.as_str()))
+        .execute(pg.as_ref())
+        .await
+        .expect("cleanup charges");
+}
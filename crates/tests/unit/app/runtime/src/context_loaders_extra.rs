@@ -51,6 +51,7 @@ fn cfg_without_geoip() -> Config {
         security_headers: SecurityHeadersConfig::default(),
         allow_registration: false,
         login_page_url: None,
+        oidc_providers: Vec::new(),
         system_admin_username: "admin".to_string(),
         system_admin_email: None,
     }
@@ -40,6 +40,8 @@ fn deployment(
         env_vars: vec![],
         external_auth: None,
         headers: HashMap::new(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
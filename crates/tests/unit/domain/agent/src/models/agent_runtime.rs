@@ -1,6 +1,8 @@
 use systemprompt_agent::models::AgentRuntimeInfo;
 use systemprompt_models::ai::ToolModelOverrides;
-use systemprompt_models::services::PluginComponentRef;
+use systemprompt_models::services::{
+    AgentCompactionConfig, AgentExecutionConfig, AgentRecoveryConfig, PluginComponentRef,
+};
 
 fn minimal_runtime_info(name: &str, port: u16) -> AgentRuntimeInfo {
     AgentRuntimeInfo {
@@ -15,6 +17,10 @@ fn minimal_runtime_info(name: &str, port: u16) -> AgentRuntimeInfo {
         max_output_tokens: None,
         skills: PluginComponentRef::default(),
         tool_model_overrides: ToolModelOverrides::default(),
+        execution: AgentExecutionConfig::default(),
+        compaction: AgentCompactionConfig::default(),
+        recovery: AgentRecoveryConfig::default(),
+        delegates: Vec::new(),
     }
 }
 
@@ -45,6 +51,10 @@ fn agent_runtime_info_serde_roundtrip() {
         max_output_tokens: Some(4096),
         skills: PluginComponentRef::default(),
         tool_model_overrides: ToolModelOverrides::default(),
+        execution: AgentExecutionConfig::default(),
+        compaction: AgentCompactionConfig::default(),
+        recovery: AgentRecoveryConfig::default(),
+        delegates: Vec::new(),
     };
     let json = serde_json::to_string(&info).unwrap();
     let de: AgentRuntimeInfo = serde_json::from_str(&json).unwrap();
@@ -96,6 +106,10 @@ fn agent_runtime_info_with_system_prompt() {
         max_output_tokens: None,
         skills: PluginComponentRef::default(),
         tool_model_overrides: ToolModelOverrides::default(),
+        execution: AgentExecutionConfig::default(),
+        compaction: AgentCompactionConfig::default(),
+        recovery: AgentRecoveryConfig::default(),
+        delegates: Vec::new(),
     };
     let json = serde_json::to_string(&info).unwrap();
     assert!(json.contains("You are a coding assistant"));
@@ -120,6 +134,10 @@ fn agent_runtime_info_disabled_not_primary() {
         max_output_tokens: None,
         skills: PluginComponentRef::default(),
         tool_model_overrides: ToolModelOverrides::default(),
+        execution: AgentExecutionConfig::default(),
+        compaction: AgentCompactionConfig::default(),
+        recovery: AgentRecoveryConfig::default(),
+        delegates: Vec::new(),
     };
     assert!(!info.is_enabled);
     assert!(!info.is_primary);
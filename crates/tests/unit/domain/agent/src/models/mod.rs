@@ -8,6 +8,7 @@
 //! - Agent info (AgentInfo builder methods)
 //! - External integration models (TokenInfo, WebhookEndpoint, etc.)
 //! - Protocol event types (TaskStatusUpdateEvent, etc.)
+//! - Tool approval request and decision parts
 
 mod a2a;
 mod agent_info;
@@ -23,6 +24,7 @@ mod protocol_requests;
 mod push_notification_extended;
 mod runtime;
 mod service_status;
+mod tool_approval;
 mod validation;
 mod web;
 mod web_agent_requests;
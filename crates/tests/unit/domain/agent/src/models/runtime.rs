@@ -6,7 +6,9 @@
 
 use systemprompt_agent::models::runtime::AgentRuntimeInfo;
 use systemprompt_models::ai::ToolModelOverrides;
-use systemprompt_models::services::PluginComponentRef;
+use systemprompt_models::services::{
+    AgentCompactionConfig, AgentExecutionConfig, AgentRecoveryConfig, PluginComponentRef,
+};
 
 fn pcr<I: IntoIterator<Item = &'static str>>(items: I) -> PluginComponentRef {
     PluginComponentRef {
@@ -29,6 +31,10 @@ fn test_agent_runtime_info_serialize() {
         max_output_tokens: Some(4096),
         skills: pcr(["skill1"]),
         tool_model_overrides: ToolModelOverrides::default(),
+        execution: AgentExecutionConfig::default(),
+        compaction: AgentCompactionConfig::default(),
+        recovery: AgentRecoveryConfig::default(),
+        delegates: Vec::new(),
     };
 
     let json = serde_json::to_string(&info).unwrap();
@@ -104,6 +110,10 @@ fn test_agent_runtime_info_debug() {
         max_output_tokens: None,
         skills: PluginComponentRef::default(),
         tool_model_overrides: ToolModelOverrides::default(),
+        execution: AgentExecutionConfig::default(),
+        compaction: AgentCompactionConfig::default(),
+        recovery: AgentRecoveryConfig::default(),
+        delegates: Vec::new(),
     };
 
     let debug_str = format!("{:?}", info);
@@ -126,6 +136,10 @@ fn test_agent_runtime_info_clone() {
         max_output_tokens: Some(2048),
         skills: pcr(["skill"]),
         tool_model_overrides: ToolModelOverrides::default(),
+        execution: AgentExecutionConfig::default(),
+        compaction: AgentCompactionConfig::default(),
+        recovery: AgentRecoveryConfig::default(),
+        delegates: Vec::new(),
     };
 
     let cloned = info.clone();
@@ -150,6 +164,10 @@ fn test_agent_runtime_info_with_skills() {
         max_output_tokens: None,
         skills: pcr(["code-review", "documentation", "testing"]),
         tool_model_overrides: ToolModelOverrides::default(),
+        execution: AgentExecutionConfig::default(),
+        compaction: AgentCompactionConfig::default(),
+        recovery: AgentRecoveryConfig::default(),
+        delegates: Vec::new(),
     };
 
     assert_eq!(info.skills.include.len(), 3);
@@ -0,0 +1,146 @@
+use serde_json::json;
+use systemprompt_agent::models::a2a::{DataPart, Message, MessageRole, Part, TextPart};
+use systemprompt_agent::models::{
+    LoopProgress, PendingToolApproval, ToolApprovalCall, ToolApprovalDecision, ToolApprovalRequest,
+};
+use systemprompt_identifiers::{ContextId, MessageId, TaskId};
+use systemprompt_models::ai::PlannedToolCall;
+
+fn request() -> ToolApprovalRequest {
+    ToolApprovalRequest {
+        approval_id: "appr-1".to_string(),
+        reasoning: "ship it".to_string(),
+        calls: vec![
+            ToolApprovalCall {
+                tool_name: "deploy".to_string(),
+                arguments: json!({"env": "prod"}),
+            },
+            ToolApprovalCall {
+                tool_name: "notify".to_string(),
+                arguments: json!({}),
+            },
+        ],
+    }
+}
+
+fn user_message(parts: Vec<Part>) -> Message {
+    Message {
+        role: MessageRole::User,
+        parts,
+        message_id: MessageId::new("msg-decision"),
+        context_id: ContextId::new("00000000-0000-4000-8000-000000000001"),
+        task_id: Some(TaskId::new("task-1")),
+        reference_task_ids: None,
+        metadata: None,
+        extensions: None,
+    }
+}
+
+fn data(value: serde_json::Value) -> Part {
+    let serde_json::Value::Object(data) = value else {
+        panic!("data part must be an object");
+    };
+    Part::Data(DataPart { data })
+}
+
+#[test]
+fn request_serializes_with_kind_and_camel_case() {
+    let value = serde_json::to_value(request()).unwrap();
+    assert_eq!(value["kind"], "tool-approval-request");
+    assert_eq!(value["approvalId"], "appr-1");
+    assert_eq!(value["calls"][0]["toolName"], "deploy");
+    assert_eq!(value["calls"][0]["arguments"]["env"], "prod");
+}
+
+#[test]
+fn request_prompt_lists_the_tools() {
+    assert_eq!(
+        request().prompt(),
+        "Approval required to run: deploy, notify"
+    );
+}
+
+#[test]
+fn request_message_carries_prompt_and_data_part() {
+    let task_id = TaskId::new("task-1");
+    let context_id = ContextId::new("00000000-0000-4000-8000-000000000002");
+    let message = request().to_message(&task_id, &context_id);
+
+    assert_eq!(message.role, MessageRole::Agent);
+    assert_eq!(message.task_id, Some(task_id));
+    assert_eq!(message.context_id, context_id);
+    assert!(matches!(
+        &message.parts[0],
+        Part::Text(TextPart { text }) if text == "Approval required to run: deploy, notify"
+    ));
+    let Part::Data(DataPart { data }) = &message.parts[1] else {
+        panic!("second part must be data");
+    };
+    let parsed: ToolApprovalRequest =
+        serde_json::from_value(serde_json::Value::Object(data.clone())).unwrap();
+    assert_eq!(parsed, request());
+}
+
+#[test]
+fn decision_is_read_from_the_first_matching_data_part() {
+    let message = user_message(vec![
+        Part::Text(TextPart {
+            text: "go ahead".to_string(),
+        }),
+        data(json!({"kind": "other", "approvalId": "x", "approved": false})),
+        data(json!({"kind": "tool-approval-decision", "approvalId": "appr-1", "approved": true})),
+    ]);
+
+    let decision = ToolApprovalDecision::from_message(&message).expect("decision");
+    assert_eq!(decision.approval_id, "appr-1");
+    assert!(decision.approved);
+    assert_eq!(decision.reason, None);
+}
+
+#[test]
+fn decision_keeps_the_rejection_reason() {
+    let message = user_message(vec![data(json!({
+        "kind": "tool-approval-decision",
+        "approvalId": "appr-1",
+        "approved": false,
+        "reason": "freeze"
+    }))]);
+
+    let decision = ToolApprovalDecision::from_message(&message).expect("decision");
+    assert!(!decision.approved);
+    assert_eq!(decision.reason.as_deref(), Some("freeze"));
+}
+
+#[test]
+fn message_without_decision_yields_none() {
+    let text_only = user_message(vec![Part::Text(TextPart {
+        text: "yes".to_string(),
+    })]);
+    assert!(ToolApprovalDecision::from_message(&text_only).is_none());
+
+    let malformed = user_message(vec![data(json!({"kind": "tool-approval-decision"}))]);
+    assert!(ToolApprovalDecision::from_message(&malformed).is_none());
+}
+
+#[test]
+fn pending_approval_request_mirrors_the_stored_calls() {
+    let pending = PendingToolApproval {
+        approval_id: "appr-2".to_string(),
+        task_id: TaskId::new("task-2"),
+        reasoning: "needs prod".to_string(),
+        calls: vec![PlannedToolCall::new("deploy", json!({"env": "prod"}))],
+        messages: Vec::new(),
+        progress: LoopProgress::default(),
+    };
+
+    let request = pending.request();
+    assert_eq!(request.approval_id, "appr-2");
+    assert_eq!(request.reasoning, "needs prod");
+    assert_eq!(
+        request.calls,
+        vec![ToolApprovalCall {
+            tool_name: "deploy".to_string(),
+            arguments: json!({"env": "prod"}),
+        }]
+    );
+}
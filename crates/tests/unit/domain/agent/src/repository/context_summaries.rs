@@ -0,0 +1,88 @@
+// DB-backed tests for the rolling history summaries written by context
+// compaction: the newest summary per agent wins, and a context lists every
+// summary oldest first.
+
+use super::{repos, seed_context_and_task, seed_user_and_session, try_pool};
+use systemprompt_agent::models::NewContextSummary;
+use systemprompt_identifiers::ContextId;
+
+fn new_summary<'a>(
+    context_id: &'a ContextId,
+    agent_name: &'a str,
+    summary: &'a str,
+    covered_messages: i32,
+) -> NewContextSummary<'a> {
+    NewContextSummary {
+        context_id,
+        agent_name,
+        summary,
+        covered_messages,
+        token_estimate: 12,
+    }
+}
+
+#[tokio::test]
+async fn latest_summary_is_the_newest_for_the_agent() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let (user, session) = seed_user_and_session(&pool).await;
+    let (ctx, _task) = seed_context_and_task(&repos, &user, &session).await;
+
+    assert!(
+        repos
+            .contexts
+            .latest_summary(&ctx, "writer")
+            .await
+            .expect("latest")
+            .is_none()
+    );
+
+    let first = repos
+        .contexts
+        .create_summary(&new_summary(&ctx, "writer", "first pass", 6))
+        .await
+        .expect("first");
+    let second = repos
+        .contexts
+        .create_summary(&new_summary(&ctx, "writer", "second pass", 14))
+        .await
+        .expect("second");
+    repos
+        .contexts
+        .create_summary(&new_summary(&ctx, "reviewer", "other agent", 3))
+        .await
+        .expect("other agent");
+
+    let latest = repos
+        .contexts
+        .latest_summary(&ctx, "writer")
+        .await
+        .expect("latest")
+        .expect("a summary");
+    assert_eq!(latest, second);
+    assert_eq!(latest.covered_messages, 14);
+
+    let all = repos.contexts.list_summaries(&ctx).await.expect("list");
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0], first);
+    assert_eq!(all[2].agent_name, "reviewer");
+}
+
+#[tokio::test]
+async fn summaries_require_covered_messages() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let (user, session) = seed_user_and_session(&pool).await;
+    let (ctx, _task) = seed_context_and_task(&repos, &user, &session).await;
+
+    let result = repos
+        .contexts
+        .create_summary(&new_summary(&ctx, "writer", "nothing covered", 0))
+        .await;
+
+    assert!(result.is_err(), "covered_messages must be positive");
+}
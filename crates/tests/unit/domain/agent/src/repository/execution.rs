@@ -1,6 +1,9 @@
 use super::{repos, seed_context_and_task, seed_user_and_session, try_pool};
+use systemprompt_agent::models::{LoopProgress, PendingToolApproval, ToolApprovalDecision};
+use systemprompt_agent::repository::execution::TaskAiUsage;
 use systemprompt_identifiers::{McpExecutionId, TaskId};
-use systemprompt_models::{ExecutionStep, StepContent, StepId, StepStatus, StepType};
+use systemprompt_models::ai::PlannedToolCall;
+use systemprompt_models::{AiMessage, ExecutionStep, StepContent, StepId, StepStatus, StepType};
 
 #[tokio::test]
 async fn create_and_get_tool_execution_step() {
@@ -337,3 +340,161 @@ async fn get_step_with_corrupt_content_errors() {
 
     r.tasks.delete_task(&task_id).await.ok();
 }
+
+#[tokio::test]
+async fn ai_usage_sums_the_task_ai_requests() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (context_id, task_id) = seed_context_and_task(&r, &user_id, &session_id).await;
+
+    let empty = r.execution_steps.ai_usage(&task_id).await.expect("usage");
+    assert_eq!(empty, TaskAiUsage::default());
+
+    let pg = pool.pool_arc().expect("pg pool");
+    for (tokens, cost) in [(Some(120), 300_i64), (None, 50), (Some(80), 0)] {
+        let id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO ai_requests (id, request_id, user_id, task_id, context_id, provider, \
+             model, tokens_used, cost_microdollars, actor_kind, actor_id) VALUES ($1, $1, $2, \
+             $3, $4, 'anthropic', 'claude', $5, $6, 'user', $2)",
+        )
+        .bind(&id)
+        .bind(user_id.as_str())
+        .bind(task_id.as_str())
+        .bind(context_id.as_str())
+        .bind(tokens)
+        .bind(cost)
+        .execute(pg.as_ref())
+        .await
+        .expect("insert ai request");
+    }
+
+    let usage = r.execution_steps.ai_usage(&task_id).await.expect("usage");
+    assert_eq!(
+        usage,
+        TaskAiUsage {
+            tokens: 200,
+            cost_microdollars: 350,
+        }
+    );
+
+    sqlx::query("DELETE FROM ai_requests WHERE task_id = $1")
+        .bind(task_id.as_str())
+        .execute(pg.as_ref())
+        .await
+        .ok();
+    r.tasks.delete_task(&task_id).await.ok();
+}
+
+fn pending_approval(task_id: &TaskId) -> PendingToolApproval {
+    PendingToolApproval {
+        approval_id: uuid::Uuid::new_v4().to_string(),
+        task_id: task_id.clone(),
+        reasoning: "deploy the fix".to_owned(),
+        calls: vec![PlannedToolCall::new(
+            "deploy",
+            serde_json::json!({"env": "prod"}),
+        )],
+        messages: vec![AiMessage::user("ship it")],
+        progress: LoopProgress {
+            iteration: 3,
+            call_counts: std::collections::HashMap::from([("status:{}".to_owned(), 2)]),
+        },
+    }
+}
+
+#[tokio::test]
+async fn tool_approval_roundtrips_while_pending() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (_context_id, task_id) = seed_context_and_task(&r, &user_id, &session_id).await;
+    let approval = pending_approval(&task_id);
+
+    r.execution_steps
+        .create_tool_approval(&approval)
+        .await
+        .expect("create");
+    let fetched = r
+        .execution_steps
+        .pending_tool_approval(&task_id)
+        .await
+        .expect("get")
+        .expect("pending");
+
+    assert_eq!(fetched.approval_id, approval.approval_id);
+    assert_eq!(fetched.reasoning, "deploy the fix");
+    assert_eq!(fetched.calls[0].tool_name, "deploy");
+    assert_eq!(fetched.messages.len(), 1);
+    assert_eq!(fetched.progress, approval.progress);
+
+    r.tasks.delete_task(&task_id).await.ok();
+}
+
+#[tokio::test]
+async fn resolve_tool_approval_applies_once() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (_context_id, task_id) = seed_context_and_task(&r, &user_id, &session_id).await;
+    let approval = pending_approval(&task_id);
+    r.execution_steps
+        .create_tool_approval(&approval)
+        .await
+        .expect("create");
+    let decision = ToolApprovalDecision {
+        approval_id: approval.approval_id.clone(),
+        approved: false,
+        reason: Some("freeze".to_owned()),
+    };
+
+    assert!(
+        r.execution_steps
+            .resolve_tool_approval(&decision)
+            .await
+            .expect("resolve")
+    );
+    assert!(
+        !r.execution_steps
+            .resolve_tool_approval(&decision)
+            .await
+            .expect("resolve again")
+    );
+    let pending = r
+        .execution_steps
+        .pending_tool_approval(&task_id)
+        .await
+        .expect("get");
+    assert!(pending.is_none());
+
+    r.tasks.delete_task(&task_id).await.ok();
+}
+
+#[tokio::test]
+async fn second_pending_approval_for_a_task_is_refused() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (_context_id, task_id) = seed_context_and_task(&r, &user_id, &session_id).await;
+    r.execution_steps
+        .create_tool_approval(&pending_approval(&task_id))
+        .await
+        .expect("create");
+
+    let second = r
+        .execution_steps
+        .create_tool_approval(&pending_approval(&task_id))
+        .await;
+    assert!(second.is_err());
+
+    r.tasks.delete_task(&task_id).await.ok();
+}
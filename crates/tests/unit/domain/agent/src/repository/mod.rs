@@ -1,6 +1,8 @@
 // DB-backed tests for the agent repository layer. Each module covers one
-// sub-repository (agent_service, context, message, task, artifact, execution,
-// push_notification) plus the aggregate `A2ARepositories` facade.
+// sub-repository (agent_service, context, context summaries, message, task,
+// task checkpoints, task lineage, artifact, execution, push_notification) plus
+// the aggregate
+// `A2ARepositories` facade.
 //
 // Every test early-returns when DATABASE_URL is unset so the suite still
 // compiles and passes in environments without a migrated Postgres.
@@ -12,11 +14,14 @@ mod artifact_parts;
 mod batch_builders;
 mod context;
 mod context_notifications;
+mod context_summaries;
 mod execution;
 mod message;
 mod message_tx;
 mod push_notification;
 mod task;
+mod task_checkpoints;
+mod task_lineage;
 
 use systemprompt_agent::models::context::ContextKind;
 use systemprompt_agent::repository::A2ARepositories;
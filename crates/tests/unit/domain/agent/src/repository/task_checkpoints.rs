@@ -0,0 +1,304 @@
+// DB-backed tests for run checkpoints (`task_checkpoints`), their recovery
+// lease, and the scan for tasks a stopped agent process left submitted or
+// working.
+
+use std::time::Duration;
+
+use super::{make_task, repos, seed_context_and_task, seed_user_and_session, try_pool};
+use systemprompt_agent::models::a2a::{Message, MessageRole, Part, TaskState, TextPart};
+use systemprompt_agent::repository::task::{RepoCreateTaskParams, SaveCheckpointParams};
+use systemprompt_identifiers::{ContextId, MessageId, TaskId, TraceId};
+use systemprompt_models::auth::UserType;
+
+fn checkpoint_message(context_id: &ContextId, task_id: &TaskId) -> Message {
+    Message {
+        role: MessageRole::User,
+        parts: vec![Part::Text(TextPart {
+            text: "summarize the report".to_owned(),
+        })],
+        message_id: MessageId::generate(),
+        task_id: Some(task_id.clone()),
+        context_id: context_id.clone(),
+        metadata: None,
+        extensions: None,
+        reference_task_ids: None,
+    }
+}
+
+#[tokio::test]
+async fn checkpoint_roundtrips_leases_and_counts_resumes() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (context_id, task_id) = seed_context_and_task(&r, &user_id, &session_id).await;
+    let message = checkpoint_message(&context_id, &task_id);
+    let lease = Duration::from_secs(600);
+
+    assert!(r.tasks.get_checkpoint(&task_id).await.unwrap().is_none());
+    r.tasks
+        .save_checkpoint(SaveCheckpointParams {
+            task_id: &task_id,
+            message: &message,
+            user_type: UserType::Admin,
+            claimant: "runner",
+            lease,
+        })
+        .await
+        .unwrap();
+
+    let checkpoint = r.tasks.get_checkpoint(&task_id).await.unwrap().unwrap();
+    assert_eq!(checkpoint.message, message);
+    assert_eq!(checkpoint.user_type, UserType::Admin);
+    assert_eq!(checkpoint.resume_attempts, 0);
+
+    assert!(
+        r.tasks
+            .record_resume_attempt(&task_id, "replica-a")
+            .await
+            .is_err(),
+        "only the lease holder counts a resume"
+    );
+    assert!(
+        !r.tasks
+            .claim_checkpoint(&task_id, "replica-a", lease)
+            .await
+            .unwrap(),
+        "the running process's lease is not taken over"
+    );
+    assert!(
+        r.tasks
+            .renew_checkpoint_lease(&task_id, "runner", lease)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !r.tasks
+            .renew_checkpoint_lease(&task_id, "replica-a", lease)
+            .await
+            .unwrap(),
+        "only the lease holder renews it"
+    );
+
+    assert!(
+        r.tasks
+            .renew_checkpoint_lease(&task_id, "runner", Duration::ZERO)
+            .await
+            .unwrap()
+    );
+    assert!(
+        r.tasks
+            .claim_checkpoint(&task_id, "replica-a", lease)
+            .await
+            .unwrap(),
+        "a lapsed lease is taken over"
+    );
+    assert!(
+        !r.tasks
+            .claim_checkpoint(&task_id, "replica-b", lease)
+            .await
+            .unwrap(),
+        "a live lease is not taken over"
+    );
+    assert!(
+        !r.tasks
+            .renew_checkpoint_lease(&task_id, "runner", lease)
+            .await
+            .unwrap(),
+        "a process that lost the lease stops renewing it"
+    );
+    assert_eq!(
+        r.tasks
+            .record_resume_attempt(&task_id, "replica-a")
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        r.tasks
+            .record_resume_attempt(&task_id, "replica-a")
+            .await
+            .unwrap(),
+        2
+    );
+    assert!(
+        r.tasks
+            .record_resume_attempt(&task_id, "replica-b")
+            .await
+            .is_err()
+    );
+
+    r.tasks
+        .save_checkpoint(SaveCheckpointParams {
+            task_id: &task_id,
+            message: &message,
+            user_type: UserType::User,
+            claimant: "next-runner",
+            lease,
+        })
+        .await
+        .unwrap();
+    let checkpoint = r.tasks.get_checkpoint(&task_id).await.unwrap().unwrap();
+    assert_eq!(checkpoint.resume_attempts, 0, "a new run resets the count");
+    assert_eq!(checkpoint.user_type, UserType::User);
+    assert!(
+        !r.tasks
+            .claim_checkpoint(&task_id, "replica-b", lease)
+            .await
+            .unwrap(),
+        "a new run takes the lease"
+    );
+
+    r.tasks.delete_checkpoint(&task_id).await.unwrap();
+    assert!(r.tasks.get_checkpoint(&task_id).await.unwrap().is_none());
+    assert!(
+        !r.tasks
+            .claim_checkpoint(&task_id, "replica-b", lease)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !r.tasks
+            .renew_checkpoint_lease(&task_id, "next-runner", lease)
+            .await
+            .unwrap(),
+        "a cleared checkpoint ends the renewal"
+    );
+    assert!(
+        r.tasks
+            .record_resume_attempt(&task_id, "replica-b")
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn orphan_scan_lists_only_unfinished_tasks_of_the_agent_from_before_start() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (context_id, _) = seed_context_and_task(&r, &user_id, &session_id).await;
+    let agent = format!("orphan_agent_{}", uuid::Uuid::new_v4().simple());
+
+    let mut ids = Vec::new();
+    for agent_name in [
+        agent.as_str(),
+        agent.as_str(),
+        agent.as_str(),
+        "other-agent",
+    ] {
+        let task_id = TaskId::generate();
+        r.tasks
+            .create_task(RepoCreateTaskParams {
+                task: &make_task(&task_id, &context_id),
+                user_id: &user_id,
+                session_id: &session_id,
+                trace_id: &TraceId::generate(),
+                agent_name,
+            })
+            .await
+            .unwrap();
+        ids.push(task_id);
+    }
+    let now = chrono::Utc::now();
+    r.tasks
+        .update_task_state(&ids[1], TaskState::Working, &now)
+        .await
+        .unwrap();
+    r.tasks
+        .update_task_state(&ids[2], TaskState::Completed, &now)
+        .await
+        .unwrap();
+    let started_at = chrono::Utc::now();
+
+    let orphans = r
+        .tasks
+        .list_orphaned_tasks(&agent, started_at, started_at)
+        .await
+        .unwrap();
+    let orphan_ids: Vec<&TaskId> = orphans.iter().map(|o| &o.task_id).collect();
+    assert_eq!(orphan_ids, vec![&ids[0], &ids[1]]);
+    assert_eq!(orphans[0].state, TaskState::Submitted);
+    assert_eq!(orphans[1].state, TaskState::Working);
+    assert_eq!(orphans[1].user_id.as_ref(), Some(&user_id));
+    assert_eq!(orphans[1].session_id.as_ref(), Some(&session_id));
+    assert_eq!(orphans[1].context_id, context_id);
+
+    let before_creation = started_at - chrono::Duration::hours(1);
+    assert!(
+        r.tasks
+            .list_orphaned_tasks(&agent, before_creation, before_creation)
+            .await
+            .unwrap()
+            .is_empty(),
+        "tasks touched after the process started are not orphans"
+    );
+}
+
+#[tokio::test]
+async fn orphan_scan_skips_tasks_a_live_process_leases() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (context_id, _) = seed_context_and_task(&r, &user_id, &session_id).await;
+    let agent = format!("leased_agent_{}", uuid::Uuid::new_v4().simple());
+
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let task_id = TaskId::generate();
+        r.tasks
+            .create_task(RepoCreateTaskParams {
+                task: &make_task(&task_id, &context_id),
+                user_id: &user_id,
+                session_id: &session_id,
+                trace_id: &TraceId::generate(),
+                agent_name: &agent,
+            })
+            .await
+            .unwrap();
+        ids.push(task_id);
+    }
+    let (running, lapsed, unleased) = (&ids[0], &ids[1], &ids[2]);
+    for (task_id, lease) in [
+        (running, Duration::from_secs(600)),
+        (lapsed, Duration::ZERO),
+    ] {
+        r.tasks
+            .save_checkpoint(SaveCheckpointParams {
+                task_id,
+                message: &checkpoint_message(&context_id, task_id),
+                user_type: UserType::User,
+                claimant: "sibling-process",
+                lease,
+            })
+            .await
+            .unwrap();
+    }
+    let started_at = chrono::Utc::now();
+    let unleased_before = started_at - chrono::Duration::hours(1);
+
+    let orphans = r
+        .tasks
+        .list_orphaned_tasks(&agent, started_at, unleased_before)
+        .await
+        .unwrap();
+    let orphan_ids: Vec<&TaskId> = orphans.iter().map(|o| &o.task_id).collect();
+    assert_eq!(
+        orphan_ids,
+        vec![lapsed],
+        "a live lease and a recent task without a checkpoint are not orphans"
+    );
+
+    let orphans = r
+        .tasks
+        .list_orphaned_tasks(&agent, started_at, started_at)
+        .await
+        .unwrap();
+    let orphan_ids: Vec<&TaskId> = orphans.iter().map(|o| &o.task_id).collect();
+    assert_eq!(orphan_ids, vec![lapsed, unleased]);
+}
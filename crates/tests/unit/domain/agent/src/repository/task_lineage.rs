@@ -0,0 +1,135 @@
+// DB-backed tests for parent/child task links written by agent-to-agent
+// delegation, and for the ownership and depth checks applied before a
+// delegated task is linked.
+
+use super::{make_task, repos, seed_context_and_task, seed_user_and_session, try_pool};
+use systemprompt_agent::repository::A2ARepositories;
+use systemprompt_agent::repository::task::RepoCreateTaskParams;
+use systemprompt_agent::services::a2a_server::processing::strategies::delegation::{
+    MAX_DELEGATION_DEPTH, link_delegated_task,
+};
+use systemprompt_agent::services::shared::error::AgentServiceError;
+use systemprompt_identifiers::{ContextId, SessionId, TaskId, TraceId, UserId};
+use systemprompt_traits::RepositoryError;
+
+async fn create_task_in(
+    repos: &A2ARepositories,
+    context_id: &ContextId,
+    user_id: &UserId,
+    session_id: &SessionId,
+) -> TaskId {
+    let task_id = TaskId::generate();
+    repos
+        .tasks
+        .create_task(RepoCreateTaskParams {
+            task: &make_task(&task_id, context_id),
+            user_id,
+            session_id,
+            trace_id: &TraceId::generate(),
+            agent_name: "delegate-agent",
+        })
+        .await
+        .expect("create task");
+    task_id
+}
+
+#[tokio::test]
+async fn parent_link_roundtrips_and_lists_children() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (context_id, parent) = seed_context_and_task(&r, &user_id, &session_id).await;
+    let first = create_task_in(&r, &context_id, &user_id, &session_id).await;
+    let second = create_task_in(&r, &context_id, &user_id, &session_id).await;
+
+    assert_eq!(r.tasks.get_parent_task_id(&first).await.unwrap(), None);
+    r.tasks.set_parent_task(&first, &parent).await.unwrap();
+    r.tasks.set_parent_task(&second, &parent).await.unwrap();
+
+    assert_eq!(
+        r.tasks.get_parent_task_id(&first).await.unwrap(),
+        Some(parent.clone())
+    );
+    assert_eq!(
+        r.tasks.list_child_task_ids(&parent).await.unwrap(),
+        vec![first.clone(), second]
+    );
+    assert_eq!(r.tasks.delegation_depth(&parent).await.unwrap(), 0);
+    assert_eq!(r.tasks.delegation_depth(&first).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn a_task_cannot_be_its_own_parent() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (_, task_id) = seed_context_and_task(&r, &user_id, &session_id).await;
+
+    let err = r
+        .tasks
+        .set_parent_task(&task_id, &task_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RepositoryError::InvalidData(_)));
+
+    let err = r
+        .tasks
+        .set_parent_task(&TaskId::generate(), &task_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RepositoryError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn delegated_task_links_below_the_depth_limit_only() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (context_id, root) = seed_context_and_task(&r, &user_id, &session_id).await;
+
+    let mut parent = root;
+    for _ in 0..MAX_DELEGATION_DEPTH {
+        let child = create_task_in(&r, &context_id, &user_id, &session_id).await;
+        link_delegated_task(&r.tasks, &child, &parent, &user_id)
+            .await
+            .expect("within the depth limit");
+        parent = child;
+    }
+    assert_eq!(
+        r.tasks.delegation_depth(&parent).await.unwrap(),
+        MAX_DELEGATION_DEPTH
+    );
+
+    let too_deep = create_task_in(&r, &context_id, &user_id, &session_id).await;
+    let err = link_delegated_task(&r.tasks, &too_deep, &parent, &user_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AgentServiceError::Validation(..)));
+    assert_eq!(r.tasks.get_parent_task_id(&too_deep).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn delegated_task_cannot_link_to_another_users_task() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let r = repos(&pool);
+    let (owner, owner_session) = seed_user_and_session(&pool).await;
+    let (_, parent) = seed_context_and_task(&r, &owner, &owner_session).await;
+    let (intruder, intruder_session) = seed_user_and_session(&pool).await;
+    let (context_id, _) = seed_context_and_task(&r, &intruder, &intruder_session).await;
+    let child = create_task_in(&r, &context_id, &intruder, &intruder_session).await;
+
+    assert!(
+        link_delegated_task(&r.tasks, &child, &parent, &intruder)
+            .await
+            .is_err()
+    );
+    assert_eq!(r.tasks.get_parent_task_id(&child).await.unwrap(), None);
+}
@@ -24,9 +24,12 @@ use systemprompt_models::ai::{
 };
 use systemprompt_models::errors::ProviderResult;
 use systemprompt_models::execution::context::RequestContext;
-use systemprompt_models::services::PluginComponentRef;
+use systemprompt_models::services::{
+    AgentCompactionConfig, AgentExecutionConfig, AgentRecoveryConfig, PluginComponentRef,
+};
 use systemprompt_traits::{
-    AgentJwtClaims, GenerateTokenParams, JwtProviderError, JwtResult, JwtValidationProvider,
+    AgentJwtClaims, AuthResult, AuthUser, FederatedIdentityClaims, GenerateTokenParams,
+    JwtProviderError, JwtResult, JwtValidationProvider, UserProvider,
 };
 use tokio::sync::{RwLock, Semaphore};
 use uuid::Uuid;
@@ -50,6 +53,63 @@ pub(crate) fn stub_jwt() -> systemprompt_traits::DynJwtValidationProvider {
     Arc::new(RejectingJwtProvider)
 }
 
+// A user directory in which every id resolves to a `user`-role account that
+// is active or not as configured.
+struct StubUsers {
+    active: bool,
+}
+
+#[async_trait]
+impl UserProvider for StubUsers {
+    async fn find_by_id(&self, id: &UserId) -> AuthResult<Option<AuthUser>> {
+        Ok(Some(AuthUser {
+            id: id.clone(),
+            name: "owner".to_owned(),
+            email: format!("{}@example.com", id.as_str()),
+            roles: vec!["user".to_owned()],
+            is_active: self.active,
+        }))
+    }
+
+    async fn find_by_email(&self, _email: &str) -> AuthResult<Option<AuthUser>> {
+        Ok(None)
+    }
+
+    async fn find_by_name(&self, _name: &str) -> AuthResult<Option<AuthUser>> {
+        Ok(None)
+    }
+
+    async fn create_user(
+        &self,
+        _name: &str,
+        _email: &str,
+        _full_name: Option<&str>,
+    ) -> AuthResult<AuthUser> {
+        unimplemented!("not used by agent tests")
+    }
+
+    async fn create_anonymous(&self, _fingerprint: &str) -> AuthResult<AuthUser> {
+        unimplemented!("not used by agent tests")
+    }
+
+    async fn assign_roles(&self, _user_id: &UserId, _roles: &[String]) -> AuthResult<()> {
+        Ok(())
+    }
+
+    async fn find_or_create_federated(
+        &self,
+        _issuer: &str,
+        _external_sub: &str,
+        _claims: &FederatedIdentityClaims,
+    ) -> AuthResult<UserId> {
+        unimplemented!("not used by agent tests")
+    }
+
+    async fn promote_anonymous(&self, _source: &UserId, _target: &UserId) -> AuthResult<u64> {
+        Ok(0)
+    }
+}
+
 // A configurable in-test Ai provider. Streams are queued as chunk batches;
 // `generate` returns a queued response or a canned default.
 pub(crate) struct StubAiProvider {
@@ -58,6 +118,7 @@ pub(crate) struct StubAiProvider {
     plans: Mutex<Vec<ProviderResult<PlanningResult>>>,
     responses: Mutex<Vec<ProviderResult<String>>>,
     tool_results: Mutex<HashMap<String, CallToolResult>>,
+    tools: Vec<McpTool>,
     fail_stream: bool,
     provider: String,
     model: String,
@@ -78,6 +139,7 @@ impl StubAiProvider {
             plans: Mutex::new(Vec::new()),
             responses: Mutex::new(Vec::new()),
             tool_results: Mutex::new(HashMap::new()),
+            tools: Vec::new(),
             fail_stream: false,
             provider: "mock-provider".to_owned(),
             model: "mock-model".to_owned(),
@@ -149,6 +211,11 @@ impl StubAiProvider {
         self
     }
 
+    pub(crate) fn with_tools(mut self, tools: Vec<McpTool>) -> Self {
+        self.tools = tools;
+        self
+    }
+
     fn next_generate(&self) -> ProviderResult<AiResponse> {
         self.generate_responses
             .lock()
@@ -238,7 +305,7 @@ impl AiProvider for StubAiProvider {
         _agent_name: &AgentName,
         _context: &RequestContext,
     ) -> ProviderResult<Vec<McpTool>> {
-        Ok(Vec::new())
+        Ok(self.tools.clone())
     }
 
     async fn generate_with_google_search(
@@ -303,6 +370,13 @@ pub(crate) fn make_agent_state(pool: &DbPool) -> Arc<AgentState> {
     ))
 }
 
+// An agent state whose user directory reports every task owner as active or
+// inactive.
+pub(crate) fn make_agent_state_with_owners(pool: &DbPool, active: bool) -> Arc<AgentState> {
+    let state = Arc::unwrap_or_clone(make_agent_state(pool));
+    Arc::new(state.with_user_provider(Arc::new(StubUsers { active })))
+}
+
 pub(crate) fn make_oauth_state(pool: &DbPool) -> Arc<AgentOAuthState> {
     Arc::new(AgentOAuthState::new(
         Arc::clone(pool),
@@ -377,6 +451,10 @@ pub(crate) fn runtime_info(name: &str) -> AgentRuntimeInfo {
         max_output_tokens: Some(1024),
         skills: PluginComponentRef::default(),
         tool_model_overrides: ToolModelOverrides::default(),
+        execution: AgentExecutionConfig::default(),
+        compaction: AgentCompactionConfig::default(),
+        recovery: AgentRecoveryConfig::default(),
+        delegates: Vec::new(),
     }
 }
 
@@ -10,7 +10,7 @@ use systemprompt_agent::repository::execution::ExecutionStepRepository;
 use systemprompt_agent::services::SkillService;
 use systemprompt_agent::services::a2a_server::processing::message::StreamEvent;
 use systemprompt_agent::services::a2a_server::processing::strategies::{
-    ContextToolExecutor, ExecutionContext, ToolExecutorTrait,
+    ContextToolExecutor, ExecutionContext, ToolExecutorTrait, ToolReplay,
 };
 use systemprompt_identifiers::AgentName;
 use tokio::sync::mpsc;
@@ -44,6 +44,7 @@ async fn executor(provider: StubAiProvider) -> Option<ContextToolExecutor> {
             tx,
             request_ctx,
             execution_step_repo: Arc::new(ExecutionStepRepository::new(&pool).expect("exec repo")),
+            tool_replay: ToolReplay::default(),
         },
     })
 }
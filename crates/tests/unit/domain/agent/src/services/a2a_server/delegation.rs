@@ -0,0 +1,211 @@
+// Agent-to-agent delegation: delegates become tools described from the
+// registry, the peer's SSE status frames are parsed whatever state spelling
+// they use, a delegated message names its parent task, and the tool executor
+// routes delegation tools away from the MCP client.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::json;
+use systemprompt_agent::models::a2a::{Message, MessageRole, Part, TaskState, TextPart};
+use systemprompt_agent::repository::execution::ExecutionStepRepository;
+use systemprompt_agent::services::SkillService;
+use systemprompt_agent::services::a2a_server::processing::message::StreamEvent;
+use systemprompt_agent::services::a2a_server::processing::strategies::delegation::{
+    DELEGATION_SERVICE_ID, DelegationUpdate, delegate_of, delegating_task_id, delegation_tool_name,
+    delegation_tools, parse_status_frame,
+};
+use systemprompt_agent::services::a2a_server::processing::strategies::{
+    ContextToolExecutor, ExecutionContext, ToolExecutorTrait, ToolReplay,
+};
+use systemprompt_agent::services::registry::AgentRegistry;
+use systemprompt_agent::services::shared::error::AgentServiceError;
+use systemprompt_identifiers::{AgentName, ContextId, McpServerId, MessageId, TaskId};
+use systemprompt_models::{McpTool, ServicesConfig};
+use tokio::sync::mpsc;
+
+use super::a2a_helpers::{StubAiProvider, agent_config, request_context, runtime_info};
+use crate::repository::{repos, seed_context_and_task, seed_user_and_session, try_pool};
+
+const AGENT: &str = "delegating_agent";
+
+fn registry_with(names: &[&str]) -> AgentRegistry {
+    let agents: HashMap<_, _> = names
+        .iter()
+        .map(|name| ((*name).to_owned(), agent_config(name)))
+        .collect();
+    AgentRegistry::from_config(ServicesConfig {
+        agents,
+        ..ServicesConfig::default()
+    })
+}
+
+fn message_with_metadata(metadata: Option<serde_json::Value>) -> Message {
+    Message {
+        role: MessageRole::User,
+        parts: vec![Part::Text(TextPart {
+            text: "research this".to_owned(),
+        })],
+        message_id: MessageId::new("msg-delegated"),
+        task_id: None,
+        context_id: ContextId::new("00000000-0000-4000-8000-000000000003"),
+        metadata,
+        extensions: None,
+        reference_task_ids: None,
+    }
+}
+
+#[tokio::test]
+async fn known_delegates_become_tools_described_from_their_cards() {
+    let registry = registry_with(&["researcher"]);
+    let delegates = vec!["researcher".to_owned(), "ghost".to_owned()];
+
+    let tools = delegation_tools(&delegates, &registry).await;
+
+    assert_eq!(tools.len(), 1, "unknown delegates are skipped");
+    let tool = &tools[0];
+    assert_eq!(tool.name, "delegate_to_researcher");
+    assert_eq!(tool.service_id.as_str(), DELEGATION_SERVICE_ID);
+    let description = tool.description.as_deref().unwrap_or_default();
+    assert!(description.contains("Test agent"), "got {description}");
+    assert!(description.contains("test agent"), "got {description}");
+    let schema = tool.input_schema.as_ref().expect("input schema");
+    assert_eq!(schema["required"], json!(["message"]));
+    assert_eq!(delegate_of(tool), Some("researcher"));
+}
+
+#[test]
+fn only_delegation_tools_name_a_delegate() {
+    let mcp_tool = McpTool::new(delegation_tool_name("researcher"), McpServerId::new("ops"));
+    assert_eq!(delegate_of(&mcp_tool), None);
+}
+
+#[test]
+fn lifecycle_frames_parse_with_legacy_state_names() {
+    let frame = json!({
+        "jsonrpc": "2.0",
+        "id": "req-1",
+        "result": {
+            "kind": "status-update",
+            "taskId": "child-1",
+            "contextId": "ctx-1",
+            "status": {"state": "working", "timestamp": "2026-01-01T00:00:00Z"},
+            "final": false
+        }
+    });
+
+    let update = parse_status_frame(&frame.to_string())
+        .expect("parses")
+        .expect("status update");
+
+    assert_eq!(
+        update,
+        DelegationUpdate {
+            task_id: TaskId::new("child-1"),
+            state: TaskState::Working,
+            text: None,
+            is_final: false,
+        }
+    );
+}
+
+#[test]
+fn completion_frames_carry_the_peer_answer() {
+    let frame = json!({
+        "jsonrpc": "2.0",
+        "id": "req-1",
+        "result": {
+            "kind": "status-update",
+            "taskId": "child-1",
+            "contextId": "ctx-1",
+            "status": {
+                "state": "TASK_STATE_COMPLETED",
+                "message": {"parts": [{"text": "The answer "}, {"text": "is 42."}]}
+            },
+            "final": true
+        }
+    });
+
+    let update = parse_status_frame(&frame.to_string())
+        .expect("parses")
+        .expect("status update");
+
+    assert_eq!(update.state, TaskState::Completed);
+    assert_eq!(update.text.as_deref(), Some("The answer is 42."));
+    assert!(update.is_final);
+}
+
+#[test]
+fn error_frames_fail_and_other_frames_are_skipped() {
+    let error = json!({
+        "jsonrpc": "2.0",
+        "id": "req-1",
+        "error": {"code": -32603, "message": "Delegation refused: too deep"}
+    });
+    let err = parse_status_frame(&error.to_string()).expect_err("error frame");
+    assert!(err.to_string().contains("Delegation refused"), "got {err}");
+
+    assert!(parse_status_frame("not json").expect("skipped").is_none());
+    let artifact = json!({"jsonrpc": "2.0", "id": "req-1", "result": {"kind": "artifact-update"}});
+    assert!(
+        parse_status_frame(&artifact.to_string())
+            .expect("skipped")
+            .is_none()
+    );
+}
+
+#[test]
+fn delegated_messages_name_their_parent_task() {
+    let delegated = message_with_metadata(Some(json!({"parentTaskId": "parent-1"})));
+    assert_eq!(
+        delegating_task_id(&delegated),
+        Some(TaskId::new("parent-1"))
+    );
+
+    assert_eq!(delegating_task_id(&message_with_metadata(None)), None);
+    let other = message_with_metadata(Some(json!({"source": "web"})));
+    assert_eq!(delegating_task_id(&other), None);
+}
+
+#[tokio::test]
+async fn executor_routes_delegation_tools_to_the_peer() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    systemprompt_test_fixtures::ensure_test_bootstrap();
+    let repos_handle = repos(&pool);
+    let (user, session) = seed_user_and_session(&pool).await;
+    let (ctx, task_id) = seed_context_and_task(&repos_handle, &user, &session).await;
+    let (tx, _rx) = mpsc::channel::<StreamEvent>(64);
+    let executor = ContextToolExecutor {
+        context: ExecutionContext {
+            ai_service: Arc::new(StubAiProvider::new()),
+            skill_service: Arc::new(SkillService::new().expect("skill service")),
+            agent_runtime: runtime_info(AGENT),
+            agent_name: AgentName::new(AGENT),
+            task_id,
+            context_id: ctx.clone(),
+            tx,
+            request_ctx: request_context(&ctx, &session, &user, AGENT),
+            execution_step_repo: Arc::new(ExecutionStepRepository::new(&pool).expect("exec repo")),
+            tool_replay: ToolReplay::default(),
+        },
+    };
+    let tools = vec![McpTool::new(
+        delegation_tool_name("researcher"),
+        McpServerId::new(DELEGATION_SERVICE_ID),
+    )];
+    let request_ctx = executor.context.request_ctx.clone();
+
+    // Why: a delegation call without a message is refused before any network
+    // hop, which shows the call never reached the stub's MCP path.
+    let err = executor
+        .execute_tool(&tools[0].name, json!({}), &tools, &request_ctx)
+        .await
+        .expect_err("empty delegation is refused");
+
+    assert!(
+        matches!(err, AgentServiceError::Validation(..)),
+        "got {err}"
+    );
+}
@@ -0,0 +1,247 @@
+// ListTasks over JSON-RPC and the HTTP+JSON binding: filtering by context,
+// state, and creation time, newest-first cursor pagination, and the ownership
+// rule that limits non-admin callers to their own tasks.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::response::IntoResponse;
+use serde_json::{Value, json};
+use systemprompt_agent::models::a2a::TaskState;
+use systemprompt_agent::repository::A2ARepositories;
+use systemprompt_agent::repository::task::RepoCreateTaskParams;
+use systemprompt_agent::services::a2a_server::handlers::request::handle_agent_request;
+use systemprompt_agent::services::a2a_server::handlers::rest_routes;
+use systemprompt_identifiers::{ContextId, SessionId, TaskId, TraceId, UserId};
+use systemprompt_models::RequestContext;
+use systemprompt_models::auth::UserType;
+use tower::ServiceExt;
+
+use super::a2a_helpers::{StubAiProvider, make_handler_state, request_context};
+use crate::repository::{make_task, repos, seed_context_and_task, seed_user_and_session, try_pool};
+
+const AGENT: &str = "test_agent";
+
+struct Owner {
+    user_id: UserId,
+    session_id: SessionId,
+    context_id: ContextId,
+}
+
+async fn seed_owner(pool: &systemprompt_database::DbPool, repos: &A2ARepositories) -> Owner {
+    let (user_id, session_id) = seed_user_and_session(pool).await;
+    let (context_id, seeded) = seed_context_and_task(repos, &user_id, &session_id).await;
+    repos.tasks.delete_task(&seeded).await.ok();
+    Owner {
+        user_id,
+        session_id,
+        context_id,
+    }
+}
+
+async fn seed_task(repos: &A2ARepositories, owner: &Owner, state: TaskState) -> TaskId {
+    let task_id = TaskId::generate();
+    let mut task = make_task(&task_id, &owner.context_id);
+    task.status.state = state;
+    repos
+        .tasks
+        .create_task(RepoCreateTaskParams {
+            task: &task,
+            user_id: &owner.user_id,
+            session_id: &owner.session_id,
+            trace_id: &TraceId::generate(),
+            agent_name: AGENT,
+        })
+        .await
+        .expect("create task");
+    task_id
+}
+
+fn caller(owner: &Owner) -> RequestContext {
+    request_context(&owner.context_id, &owner.session_id, &owner.user_id, AGENT)
+}
+
+async fn list(
+    pool: &systemprompt_database::DbPool,
+    context: RequestContext,
+    params: Value,
+) -> Value {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({"jsonrpc": "2.0", "method": "ListTasks", "params": params, "id": 1}).to_string(),
+        ))
+        .expect("request");
+    request.extensions_mut().insert(context);
+    let state = make_handler_state(pool, Arc::new(StubAiProvider::new()), 1);
+    let response = handle_agent_request(State(state), request)
+        .await
+        .into_response();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    serde_json::from_slice(&bytes).expect("json body")
+}
+
+fn ids(page: &Value) -> Vec<String> {
+    page["result"]["tasks"]
+        .as_array()
+        .expect("tasks array")
+        .iter()
+        .map(|task| task["id"].as_str().expect("task id").to_owned())
+        .collect()
+}
+
+#[tokio::test]
+async fn tasks_are_paged_newest_first_with_a_cursor() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let owner = seed_owner(&pool, &repos).await;
+    let mut seeded = Vec::new();
+    for _ in 0..3 {
+        seeded.push(seed_task(&repos, &owner, TaskState::Completed).await);
+    }
+    let filter = json!({"contextId": owner.context_id.as_str(), "pageSize": 2});
+
+    let first = list(&pool, caller(&owner), filter.clone()).await;
+    assert_eq!(ids(&first), [seeded[2].as_str(), seeded[1].as_str()]);
+    assert_eq!(first["result"]["pageSize"], json!(2));
+    let token = first["result"]["nextPageToken"].clone();
+    assert_eq!(token, json!(seeded[1].as_str()));
+
+    let mut next = filter;
+    next["pageToken"] = token;
+    let second = list(&pool, caller(&owner), next).await;
+    assert_eq!(ids(&second), [seeded[0].as_str()]);
+    assert!(
+        second["result"].get("nextPageToken").is_none(),
+        "the last page carries no token: {second}"
+    );
+
+    for task_id in &seeded {
+        repos.tasks.delete_task(task_id).await.ok();
+    }
+}
+
+#[tokio::test]
+async fn state_and_time_filters_narrow_the_listing() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let owner = seed_owner(&pool, &repos).await;
+    let working = seed_task(&repos, &owner, TaskState::Working).await;
+    let completed = seed_task(&repos, &owner, TaskState::Completed).await;
+    let context = owner.context_id.as_str();
+
+    let page = list(
+        &pool,
+        caller(&owner),
+        json!({"contextId": context, "status": "TASK_STATE_WORKING"}),
+    )
+    .await;
+    assert_eq!(ids(&page), [working.as_str()]);
+
+    let future = (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339();
+    let page = list(
+        &pool,
+        caller(&owner),
+        json!({"contextId": context, "createdAfter": future}),
+    )
+    .await;
+    assert!(ids(&page).is_empty(), "nothing was created after {future}");
+
+    let page = list(
+        &pool,
+        caller(&owner),
+        json!({"contextId": context, "createdBefore": future}),
+    )
+    .await;
+    assert_eq!(ids(&page).len(), 2);
+
+    repos.tasks.delete_task(&working).await.ok();
+    repos.tasks.delete_task(&completed).await.ok();
+}
+
+#[tokio::test]
+async fn only_admins_see_tasks_owned_by_other_users() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let owner = seed_owner(&pool, &repos).await;
+    let other = seed_owner(&pool, &repos).await;
+    let task_id = seed_task(&repos, &owner, TaskState::Completed).await;
+    let filter = json!({"contextId": owner.context_id.as_str()});
+
+    let as_owner = list(&pool, caller(&owner), filter.clone()).await;
+    assert_eq!(ids(&as_owner), [task_id.as_str()]);
+
+    let as_other = list(&pool, caller(&other), filter.clone()).await;
+    assert!(
+        ids(&as_other).is_empty(),
+        "another user's tasks are hidden: {as_other}"
+    );
+
+    let as_admin = list(
+        &pool,
+        caller(&other).with_user_type(UserType::Admin),
+        filter,
+    )
+    .await;
+    assert_eq!(ids(&as_admin), [task_id.as_str()]);
+
+    repos.tasks.delete_task(&task_id).await.ok();
+}
+
+#[tokio::test]
+async fn the_rest_binding_lists_tasks_from_query_parameters() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let owner = seed_owner(&pool, &repos).await;
+    let task_id = seed_task(&repos, &owner, TaskState::Completed).await;
+    let router = rest_routes().with_state(make_handler_state(
+        &pool,
+        Arc::new(StubAiProvider::new()),
+        1,
+    ));
+
+    let mut request = Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tasks?contextId={}&status=TASK_STATE_COMPLETED",
+            owner.context_id
+        ))
+        .body(Body::empty())
+        .expect("request");
+    request.extensions_mut().insert(caller(&owner));
+    let response = router
+        .clone()
+        .oneshot(request)
+        .await
+        .expect("router responds");
+    assert!(response.status().is_success());
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    let page: Value = serde_json::from_slice(&bytes).expect("json body");
+    assert_eq!(page["tasks"][0]["id"], json!(task_id.as_str()));
+
+    let mut bad = Request::builder()
+        .method("GET")
+        .uri("/tasks?pageSize=many")
+        .body(Body::empty())
+        .expect("request");
+    bad.extensions_mut().insert(caller(&owner));
+    let response = router.oneshot(bad).await.expect("router responds");
+    assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    repos.tasks.delete_task(&task_id).await.ok();
+}
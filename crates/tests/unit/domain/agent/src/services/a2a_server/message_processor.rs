@@ -12,6 +12,7 @@ use systemprompt_agent::services::a2a_server::processing::message::{
     MessageProcessor, PersistCompletedTaskOnProcessorParams, ProcessMessageStreamParams,
     StreamEvent,
 };
+use systemprompt_agent::services::a2a_server::processing::strategies::ToolReplay;
 use systemprompt_identifiers::{ContextId, MessageId, TaskId};
 
 use super::a2a_helpers::{StubAiProvider, request_context, runtime_info};
@@ -70,6 +71,7 @@ async fn process_message_stream_emits_text_and_complete() {
             agent_name: "stream-agent",
             context: &request,
             task_id: task_id.clone(),
+            tool_replay: ToolReplay::default(),
         })
         .await
         .expect("stream");
@@ -161,6 +163,7 @@ async fn process_message_stream_provider_failure_emits_error() {
             agent_name: "fail-agent",
             context: &request,
             task_id,
+            tool_replay: ToolReplay::default(),
         })
         .await
         .expect("stream");
@@ -6,20 +6,26 @@ mod card_and_middleware;
 mod card_response;
 mod context_tool_executor;
 mod conversation_service;
+mod delegation;
 mod errors_jsonrpc;
 mod event_loop;
 mod event_loop_lifecycle;
+mod list_tasks;
 mod message_handler;
 mod message_processor;
 mod multiturn_task;
 mod persistence_service;
+mod planned_approval;
+mod planned_iterative;
 mod planned_tool_execution;
 mod push_notification_config;
 mod push_notification_config_faults;
+mod recovery;
 mod request_dispatch;
 mod request_handler;
 mod request_non_streaming;
 mod request_validation;
+mod rest_binding;
 mod server;
 mod server_router;
 mod stream_processor;
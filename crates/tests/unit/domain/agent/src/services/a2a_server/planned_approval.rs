@@ -0,0 +1,208 @@
+// DB-backed tests for the planned strategy's approval gate: a plan that calls
+// a tool marked `requires_approval` suspends with a persisted pending
+// approval instead of running, an approved decision runs the stored calls,
+// a rejected one never runs them, and a decision is applied only once.
+
+use std::sync::Arc;
+
+use rmcp::model::{CallToolResult, ContentBlock};
+use serde_json::json;
+use systemprompt_agent::models::{ToolApprovalDecision, ToolApprovalRequest};
+use systemprompt_agent::repository::execution::ExecutionStepRepository;
+use systemprompt_agent::services::a2a_server::processing::strategies::{
+    ExecutionContext, ExecutionStrategy, PlannedAgenticStrategy, ToolReplay,
+};
+use systemprompt_agent::services::shared::error::AgentServiceError;
+use systemprompt_agent::services::skills::SkillService;
+use systemprompt_identifiers::{AgentName, McpServerId};
+use systemprompt_models::McpTool;
+use systemprompt_models::ai::{PlannedToolCall, PlanningResult};
+use tokio::sync::mpsc;
+
+use super::a2a_helpers::{StubAiProvider, request_context, runtime_info};
+use crate::repository::{repos, seed_context_and_task, seed_user_and_session, try_pool};
+
+const AGENT: &str = "planned_approval_agent";
+
+async fn context(provider: StubAiProvider) -> Option<ExecutionContext> {
+    let pool = try_pool().await?;
+    systemprompt_test_fixtures::ensure_test_bootstrap();
+    let repos_handle = repos(&pool);
+    let (user, session) = seed_user_and_session(&pool).await;
+    let (ctx, task_id) = seed_context_and_task(&repos_handle, &user, &session).await;
+
+    let (tx, _rx) = mpsc::channel(64);
+    let request_ctx = request_context(&ctx, &session, &user, AGENT);
+    Some(ExecutionContext {
+        ai_service: Arc::new(provider),
+        skill_service: Arc::new(SkillService::new().expect("skill service")),
+        agent_runtime: runtime_info(AGENT),
+        agent_name: AgentName::new(AGENT),
+        task_id,
+        context_id: ctx,
+        tx,
+        request_ctx,
+        execution_step_repo: Arc::new(ExecutionStepRepository::new(&pool).expect("exec repo")),
+        tool_replay: ToolReplay::default(),
+    })
+}
+
+fn tools() -> Vec<McpTool> {
+    let server = McpServerId::new("ops");
+    vec![
+        McpTool::new("deploy", server.clone()).with_requires_approval(true),
+        McpTool::new("status", server),
+    ]
+}
+
+fn provider_planning(tool: &str) -> StubAiProvider {
+    let mut result = CallToolResult::success(vec![ContentBlock::text("ok".to_owned())]);
+    result.structured_content = Some(json!({"ok": true}));
+    StubAiProvider::new()
+        .with_tools(tools())
+        .with_plan(PlanningResult::tool_calls(
+            format!("run {tool}"),
+            vec![PlannedToolCall::new(tool, json!({"env": "prod"}))],
+        ))
+        .with_tool_result(tool, result)
+}
+
+async fn suspend(context: &ExecutionContext) -> ToolApprovalRequest {
+    let result = PlannedAgenticStrategy::new()
+        .execute(context.clone(), Vec::new())
+        .await
+        .expect("planned execution succeeds");
+    assert!(result.tool_calls.is_empty(), "gated calls must not run");
+    result.pending_approval.expect("approval requested")
+}
+
+fn decision(request: &ToolApprovalRequest, approved: bool) -> ToolApprovalDecision {
+    ToolApprovalDecision {
+        approval_id: request.approval_id.clone(),
+        approved,
+        reason: (!approved).then(|| "not during the freeze".to_owned()),
+    }
+}
+
+#[tokio::test]
+async fn ungated_tool_runs_without_approval() {
+    let Some(context) = context(provider_planning("status")).await else {
+        return;
+    };
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+
+    let result = PlannedAgenticStrategy::new()
+        .execute(context, Vec::new())
+        .await
+        .expect("planned execution succeeds");
+
+    assert!(result.pending_approval.is_none());
+    assert_eq!(result.tool_calls.len(), 1);
+}
+
+#[tokio::test]
+async fn gated_tool_suspends_with_a_persisted_pending_approval() {
+    let Some(context) = context(provider_planning("deploy")).await else {
+        return;
+    };
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+
+    let request = suspend(&context).await;
+
+    assert_eq!(request.calls.len(), 1);
+    assert_eq!(request.calls[0].tool_name, "deploy");
+    assert_eq!(request.calls[0].arguments, json!({"env": "prod"}));
+    let pending = context
+        .execution_step_repo
+        .pending_tool_approval(&context.task_id)
+        .await
+        .expect("query")
+        .expect("pending approval stored");
+    assert_eq!(pending.approval_id, request.approval_id);
+    assert_eq!(pending.calls[0].tool_name, "deploy");
+}
+
+#[tokio::test]
+async fn approved_decision_runs_the_stored_calls() {
+    let Some(context) = context(provider_planning("deploy")).await else {
+        return;
+    };
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+    let request = suspend(&context).await;
+    let pending = context
+        .execution_step_repo
+        .pending_tool_approval(&context.task_id)
+        .await
+        .expect("query")
+        .expect("pending approval stored");
+
+    let result = PlannedAgenticStrategy::new()
+        .resume(context.clone(), pending, &decision(&request, true))
+        .await
+        .expect("approved resume succeeds");
+
+    assert!(result.pending_approval.is_none());
+    let names: Vec<&str> = result.tool_calls.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["deploy"]);
+    assert_eq!(result.tool_results.len(), 1);
+    let remaining = context
+        .execution_step_repo
+        .pending_tool_approval(&context.task_id)
+        .await
+        .expect("query");
+    assert!(
+        remaining.is_none(),
+        "a decided approval is no longer pending"
+    );
+}
+
+#[tokio::test]
+async fn rejected_decision_never_runs_the_calls() {
+    let provider = provider_planning("deploy").with_response("I did not deploy.");
+    let Some(context) = context(provider).await else {
+        return;
+    };
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+    let request = suspend(&context).await;
+    let pending = context
+        .execution_step_repo
+        .pending_tool_approval(&context.task_id)
+        .await
+        .expect("query")
+        .expect("pending approval stored");
+
+    let result = PlannedAgenticStrategy::new()
+        .resume(context, pending, &decision(&request, false))
+        .await
+        .expect("rejected resume succeeds");
+
+    assert_eq!(result.accumulated_text, "I did not deploy.");
+    assert!(result.tool_calls.is_empty());
+    assert!(result.tool_results.is_empty());
+}
+
+#[tokio::test]
+async fn decision_is_applied_only_once() {
+    let Some(context) = context(provider_planning("deploy")).await else {
+        return;
+    };
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+    let request = suspend(&context).await;
+    let pending = context
+        .execution_step_repo
+        .pending_tool_approval(&context.task_id)
+        .await
+        .expect("query")
+        .expect("pending approval stored");
+    PlannedAgenticStrategy::new()
+        .resume(context.clone(), pending.clone(), &decision(&request, false))
+        .await
+        .expect("first decision applies");
+
+    let err = PlannedAgenticStrategy::new()
+        .resume(context, pending, &decision(&request, true))
+        .await
+        .expect_err("second decision is refused");
+
+    assert!(matches!(err, AgentServiceError::Conflict(_)));
+}
@@ -0,0 +1,263 @@
+// DB-backed tests for the planned strategy's iterative mode: tool results are
+// fed back until the model answers, the loop stops at the iteration limit and
+// on a repeated call with a synthesized response, and an invalid plan is
+// observed and re-planned rather than ending the task. An approval resume
+// carries the iteration count and call counts forward. The stub provider pops
+// plans last-in first-out, so each test pushes them in reverse order.
+
+use std::sync::Arc;
+
+use rmcp::model::{CallToolResult, ContentBlock};
+use serde_json::json;
+use systemprompt_agent::models::ToolApprovalDecision;
+use systemprompt_agent::repository::execution::ExecutionStepRepository;
+use systemprompt_agent::services::a2a_server::processing::message::StreamEvent;
+use systemprompt_agent::services::a2a_server::processing::strategies::{
+    ExecutionContext, ExecutionStrategy, PlannedAgenticStrategy, ToolReplay,
+};
+use systemprompt_agent::services::skills::SkillService;
+use systemprompt_identifiers::{AgentName, McpServerId};
+use systemprompt_models::McpTool;
+use systemprompt_models::ai::{PlannedToolCall, PlanningResult};
+use systemprompt_models::services::{AgentExecutionConfig, AgentExecutionMode};
+use tokio::sync::mpsc;
+
+use super::a2a_helpers::{StubAiProvider, request_context, runtime_info};
+use crate::repository::{repos, seed_context_and_task, seed_user_and_session, try_pool};
+
+const AGENT: &str = "planned_iterative_agent";
+
+struct Harness {
+    context: ExecutionContext,
+    rx: mpsc::Receiver<StreamEvent>,
+}
+
+async fn harness(provider: StubAiProvider, execution: AgentExecutionConfig) -> Option<Harness> {
+    let pool = try_pool().await?;
+    systemprompt_test_fixtures::ensure_test_bootstrap();
+    let repos_handle = repos(&pool);
+    let (user, session) = seed_user_and_session(&pool).await;
+    let (ctx, task_id) = seed_context_and_task(&repos_handle, &user, &session).await;
+
+    let (tx, rx) = mpsc::channel(64);
+    let request_ctx = request_context(&ctx, &session, &user, AGENT);
+    let mut agent_runtime = runtime_info(AGENT);
+    agent_runtime.execution = execution;
+    let context = ExecutionContext {
+        ai_service: Arc::new(provider),
+        skill_service: Arc::new(SkillService::new().expect("skill service")),
+        agent_runtime,
+        agent_name: AgentName::new(AGENT),
+        task_id,
+        context_id: ctx,
+        tx,
+        request_ctx,
+        execution_step_repo: Arc::new(ExecutionStepRepository::new(&pool).expect("exec repo")),
+        tool_replay: ToolReplay::default(),
+    };
+    Some(Harness { context, rx })
+}
+
+fn iterative() -> AgentExecutionConfig {
+    AgentExecutionConfig {
+        mode: AgentExecutionMode::Iterative,
+        ..AgentExecutionConfig::default()
+    }
+}
+
+fn call(tool: &str, arguments: serde_json::Value) -> PlanningResult {
+    PlanningResult::tool_calls(
+        format!("call {tool}"),
+        vec![PlannedToolCall::new(tool, arguments)],
+    )
+}
+
+fn success_result(payload: serde_json::Value) -> CallToolResult {
+    let mut result = CallToolResult::success(vec![ContentBlock::text("ok".to_owned())]);
+    result.structured_content = Some(payload);
+    result
+}
+
+fn drain(rx: &mut mpsc::Receiver<StreamEvent>) -> Vec<StreamEvent> {
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    events
+}
+
+#[tokio::test]
+async fn tool_results_feed_back_until_the_model_answers() {
+    let provider = StubAiProvider::new()
+        .with_plan(PlanningResult::direct_response("done"))
+        .with_plan(call("beta", json!({"from": "alpha"})))
+        .with_plan(call("alpha", json!({"q": 1})))
+        .with_tool_result("alpha", success_result(json!({"answer": 42})))
+        .with_tool_result("beta", success_result(json!({"answer": 43})));
+    let Some(Harness { context, mut rx }) = harness(provider, iterative()).await else {
+        return;
+    };
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+
+    let result = PlannedAgenticStrategy::new()
+        .execute(context, Vec::new())
+        .await
+        .expect("iterative execution succeeds");
+
+    assert_eq!(result.accumulated_text, "done");
+    assert_eq!(result.iterations, 3);
+    let names: Vec<&str> = result.tool_calls.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["alpha", "beta"]);
+    let ids: Vec<&str> = result
+        .tool_calls
+        .iter()
+        .map(|c| c.ai_tool_call_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["plan_call_0", "plan_call_1"]);
+    assert_eq!(result.tool_results.len(), 2);
+
+    let events = drain(&mut rx);
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(e, StreamEvent::Text(t) if t == "done")),
+        "final answer must be streamed"
+    );
+}
+
+#[tokio::test]
+async fn iteration_limit_stops_the_loop_with_a_synthesized_response() {
+    let provider = StubAiProvider::new()
+        .with_plan(call("alpha", json!({"q": 3})))
+        .with_plan(call("alpha", json!({"q": 2})))
+        .with_plan(call("alpha", json!({"q": 1})))
+        .with_tool_result("alpha", success_result(json!({"answer": 42})))
+        .with_response("best effort");
+    let execution = AgentExecutionConfig {
+        max_iterations: 2,
+        ..iterative()
+    };
+    let Some(Harness { context, rx: _rx }) = harness(provider, execution).await else {
+        return;
+    };
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+
+    let result = PlannedAgenticStrategy::new()
+        .execute(context, Vec::new())
+        .await
+        .expect("a stopped loop still responds");
+
+    assert_eq!(result.accumulated_text, "best effort");
+    assert_eq!(result.iterations, 2);
+    assert_eq!(result.tool_calls.len(), 2);
+}
+
+#[tokio::test]
+async fn repeated_identical_call_stops_the_loop() {
+    let provider = StubAiProvider::new()
+        .with_plan(call("alpha", json!({"q": 1})))
+        .with_plan(call("alpha", json!({"q": 1})))
+        .with_tool_result("alpha", success_result(json!({"answer": 42})))
+        .with_response("giving up on the loop");
+    let execution = AgentExecutionConfig {
+        max_repeated_calls: 1,
+        ..iterative()
+    };
+    let Some(Harness { context, rx: _rx }) = harness(provider, execution).await else {
+        return;
+    };
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+
+    let result = PlannedAgenticStrategy::new()
+        .execute(context, Vec::new())
+        .await
+        .expect("a stuck loop still responds");
+
+    assert_eq!(result.accumulated_text, "giving up on the loop");
+    assert_eq!(result.iterations, 2);
+    assert_eq!(result.tool_calls.len(), 1, "the repeated call is not run");
+}
+
+#[tokio::test]
+async fn invalid_plan_is_observed_and_replanned() {
+    let provider = StubAiProvider::new()
+        .with_plan(PlanningResult::direct_response("fixed"))
+        .with_plan(call("alpha", json!({"x": "$missing.output.value"})));
+    let Some(Harness { context, rx: _rx }) = harness(provider, iterative()).await else {
+        return;
+    };
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+
+    let result = PlannedAgenticStrategy::new()
+        .execute(context, Vec::new())
+        .await
+        .expect("the model recovers from an invalid plan");
+
+    assert_eq!(result.accumulated_text, "fixed");
+    assert_eq!(result.iterations, 2);
+    assert!(result.tool_calls.is_empty());
+    assert!(result.tool_results.is_empty());
+}
+
+#[tokio::test]
+async fn approval_resume_keeps_the_loop_counting() {
+    let server = McpServerId::new("ops");
+    let provider = StubAiProvider::new()
+        .with_tools(vec![
+            McpTool::new("alpha", server.clone()),
+            McpTool::new("deploy", server).with_requires_approval(true),
+        ])
+        .with_plan(call("alpha", json!({"q": 3})))
+        .with_plan(call("deploy", json!({"env": "prod"})))
+        .with_plan(call("alpha", json!({"q": 1})))
+        .with_tool_result("alpha", success_result(json!({"answer": 42})))
+        .with_tool_result("deploy", success_result(json!({"ok": true})))
+        .with_response("stopped after deploying");
+    let execution = AgentExecutionConfig {
+        max_iterations: 2,
+        ..iterative()
+    };
+    let Some(Harness { context, rx: _rx }) = harness(provider, execution).await else {
+        return;
+    };
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+
+    let suspended = PlannedAgenticStrategy::new()
+        .execute(context.clone(), Vec::new())
+        .await
+        .expect("iterative execution suspends");
+    let request = suspended.pending_approval.expect("approval requested");
+    let pending = context
+        .execution_step_repo
+        .pending_tool_approval(&context.task_id)
+        .await
+        .expect("query")
+        .expect("pending approval stored");
+    assert_eq!(pending.progress.iteration, 2);
+    assert_eq!(
+        pending.progress.call_counts.get(r#"alpha:{"q":1}"#),
+        Some(&1)
+    );
+    assert!(
+        !pending
+            .progress
+            .call_counts
+            .contains_key(r#"deploy:{"env":"prod"}"#),
+        "the suspended round is counted when it runs"
+    );
+
+    let decision = ToolApprovalDecision {
+        approval_id: request.approval_id,
+        approved: true,
+        reason: None,
+    };
+    let result = PlannedAgenticStrategy::new()
+        .resume(context, pending, &decision)
+        .await
+        .expect("approved resume succeeds");
+
+    assert_eq!(result.accumulated_text, "stopped after deploying");
+    assert_eq!(result.iterations, 2, "the resumed round is still round two");
+    let names: Vec<&str> = result.tool_calls.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["deploy"]);
+}
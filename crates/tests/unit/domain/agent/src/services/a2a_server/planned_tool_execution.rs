@@ -11,7 +11,7 @@ use serde_json::json;
 use systemprompt_agent::repository::execution::ExecutionStepRepository;
 use systemprompt_agent::services::a2a_server::processing::message::StreamEvent;
 use systemprompt_agent::services::a2a_server::processing::strategies::{
-    ExecutionContext, ExecutionStrategy, PlannedAgenticStrategy,
+    ExecutionContext, ExecutionStrategy, PlannedAgenticStrategy, ToolReplay,
 };
 use systemprompt_agent::services::skills::SkillService;
 use systemprompt_identifiers::AgentName;
@@ -47,6 +47,7 @@ async fn harness(provider: StubAiProvider) -> Option<Harness> {
         tx,
         request_ctx,
         execution_step_repo: Arc::new(ExecutionStepRepository::new(&pool).expect("exec repo")),
+        tool_replay: ToolReplay::default(),
     };
     Some(Harness { context, rx })
 }
@@ -0,0 +1,335 @@
+// DB-backed tests for `TaskRecoveryService`: tasks a previous agent process
+// left working are failed with a reason when the agent does not resume, when
+// no attempt remains, or when their owner is inactive, are run again to
+// completion from their checkpoint when it does, and are left alone while
+// a live process holds their lease. Each test owns a uniquely named agent so
+// the orphan scan never touches another test's tasks.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use systemprompt_agent::models::AgentRuntimeInfo;
+use systemprompt_agent::models::a2a::{Message, MessageRole, Part, TaskState, TextPart};
+use systemprompt_agent::repository::A2ARepositories;
+use systemprompt_agent::repository::task::{RepoCreateTaskParams, SaveCheckpointParams};
+use systemprompt_agent::services::a2a_server::{RecoveryReport, TaskRecoveryService};
+use systemprompt_database::DbPool;
+use systemprompt_identifiers::{ContextId, MessageId, SessionId, TaskId, TraceId, UserId};
+use systemprompt_models::auth::UserType;
+use systemprompt_models::{AgentRecoveryConfig, TaskRecoveryMode};
+
+use super::a2a_helpers::{
+    StubAiProvider, make_agent_state, make_agent_state_with_owners, runtime_info,
+};
+use crate::repository::{make_task, repos, try_pool};
+
+struct Orphan {
+    repos: A2ARepositories,
+    runtime: AgentRuntimeInfo,
+    task_id: TaskId,
+    context_id: ContextId,
+}
+
+// Seeds a UUID-owned user, session, context and a working task of a fresh
+// agent whose recovery config is `recovery`.
+async fn seed_orphan(pool: &DbPool, recovery: AgentRecoveryConfig) -> Orphan {
+    let repos = repos(pool);
+    let user_id = UserId::new(uuid::Uuid::new_v4().to_string());
+    let session_id = SessionId::generate();
+    let email = format!("{}@recovery.invalid", user_id.as_str());
+    systemprompt_test_fixtures::seed_user_row(pool, &user_id, &email)
+        .await
+        .expect("seed user");
+    systemprompt_test_fixtures::seed_user_session(pool, &user_id, &session_id)
+        .await
+        .expect("seed session");
+    let context_id = systemprompt_agent::repository::ContextRepository::new(repos.db_pool())
+        .expect("context repo")
+        .create_context(
+            &user_id,
+            Some(&session_id),
+            "recovery-context",
+            systemprompt_agent::models::context::ContextKind::User,
+        )
+        .await
+        .expect("create context");
+
+    let mut runtime = runtime_info(&format!("recovery_{}", uuid::Uuid::new_v4().simple()));
+    runtime.recovery = recovery;
+    let task_id = TaskId::generate();
+    repos
+        .tasks
+        .create_task(RepoCreateTaskParams {
+            task: &make_task(&task_id, &context_id),
+            user_id: &user_id,
+            session_id: &session_id,
+            trace_id: &TraceId::generate(),
+            agent_name: &runtime.name,
+        })
+        .await
+        .expect("create task");
+    repos
+        .tasks
+        .update_task_state(&task_id, TaskState::Working, &chrono::Utc::now())
+        .await
+        .expect("mark working");
+
+    Orphan {
+        repos,
+        runtime,
+        task_id,
+        context_id,
+    }
+}
+
+// Checkpoints the task as a process that has since stopped would have: its
+// lease has already run out.
+async fn save_checkpoint(orphan: &Orphan) {
+    save_checkpoint_leased(orphan, "earlier-process", Duration::ZERO).await;
+}
+
+async fn save_checkpoint_leased(orphan: &Orphan, claimant: &str, lease: Duration) {
+    let message = Message {
+        role: MessageRole::User,
+        parts: vec![Part::Text(TextPart {
+            text: "what is the answer?".to_owned(),
+        })],
+        message_id: MessageId::generate(),
+        task_id: Some(orphan.task_id.clone()),
+        context_id: orphan.context_id.clone(),
+        metadata: None,
+        extensions: None,
+        reference_task_ids: None,
+    };
+    orphan
+        .repos
+        .tasks
+        .save_checkpoint(SaveCheckpointParams {
+            task_id: &orphan.task_id,
+            message: &message,
+            user_type: UserType::User,
+            claimant,
+            lease,
+        })
+        .await
+        .expect("save checkpoint");
+}
+
+const fn resume(max_attempts: u32) -> AgentRecoveryConfig {
+    AgentRecoveryConfig {
+        mode: TaskRecoveryMode::Resume,
+        max_attempts,
+    }
+}
+
+async fn task_state(orphan: &Orphan) -> TaskState {
+    orphan
+        .repos
+        .tasks
+        .get_task(&orphan.task_id)
+        .await
+        .expect("get task")
+        .expect("task exists")
+        .status
+        .state
+}
+
+async fn failure_reason(pool: &DbPool, task_id: &TaskId) -> Option<String> {
+    let pg = pool.pool_arc().expect("pg pool");
+    let (reason,): (Option<String>,) =
+        sqlx::query_as("SELECT error_message FROM agent_tasks WHERE task_id = $1")
+            .bind(task_id.as_str())
+            .fetch_one(pg.as_ref())
+            .await
+            .expect("fetch task row");
+    reason
+}
+
+#[tokio::test]
+async fn fail_mode_fails_interrupted_tasks_with_a_reason() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let orphan = seed_orphan(&pool, AgentRecoveryConfig::default()).await;
+    save_checkpoint(&orphan).await;
+    let service =
+        TaskRecoveryService::new(make_agent_state(&pool), Arc::new(StubAiProvider::new()));
+
+    let report = service
+        .recover(&orphan.runtime, chrono::Utc::now())
+        .await
+        .expect("recover");
+
+    assert_eq!(report.failed, vec![orphan.task_id.clone()]);
+    assert!(report.resumed.is_empty());
+    assert_eq!(task_state(&orphan).await, TaskState::Failed);
+    assert_eq!(
+        failure_reason(&pool, &orphan.task_id).await.as_deref(),
+        Some("Interrupted by an agent restart")
+    );
+    assert!(
+        orphan
+            .repos
+            .tasks
+            .get_checkpoint(&orphan.task_id)
+            .await
+            .unwrap()
+            .is_none(),
+        "a failed task keeps no checkpoint"
+    );
+
+    let again = service
+        .recover(&orphan.runtime, chrono::Utc::now())
+        .await
+        .expect("recover");
+    assert!(again.failed.is_empty(), "failed tasks are not orphans");
+}
+
+#[tokio::test]
+async fn resume_mode_fails_tasks_without_a_checkpoint_or_attempts_left() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let no_checkpoint = seed_orphan(&pool, resume(1)).await;
+    let exhausted = seed_orphan(&pool, resume(1)).await;
+    save_checkpoint(&exhausted).await;
+    let tasks = &exhausted.repos.tasks;
+    tasks
+        .claim_checkpoint(&exhausted.task_id, "earlier-process", Duration::ZERO)
+        .await
+        .expect("claim");
+    tasks
+        .record_resume_attempt(&exhausted.task_id, "earlier-process")
+        .await
+        .expect("attempt");
+    let service =
+        TaskRecoveryService::new(make_agent_state(&pool), Arc::new(StubAiProvider::new()));
+
+    // Why: a task without a checkpoint counts only once it has gone
+    // untouched for an hour, so recover as a process started well after.
+    let started_at = chrono::Utc::now() + chrono::Duration::hours(2);
+    for orphan in [&no_checkpoint, &exhausted] {
+        let report = service
+            .recover(&orphan.runtime, started_at)
+            .await
+            .expect("recover");
+        assert_eq!(report.failed, vec![orphan.task_id.clone()]);
+        assert_eq!(task_state(orphan).await, TaskState::Failed);
+    }
+    let reason = failure_reason(&pool, &exhausted.task_id)
+        .await
+        .unwrap_or_default();
+    assert!(
+        reason.contains("gave up after 1 resume attempts"),
+        "{reason}"
+    );
+}
+
+#[tokio::test]
+async fn resume_mode_runs_the_checkpointed_message_to_completion() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    systemprompt_test_fixtures::ensure_test_bootstrap();
+    let _lock = crate::SKILLS_FIXTURE_LOCK.read().await;
+    let orphan = seed_orphan(&pool, resume(2)).await;
+    save_checkpoint(&orphan).await;
+    let provider = Arc::new(StubAiProvider::new().with_text_stream(&["It is ", "42."]));
+    let service = TaskRecoveryService::new(make_agent_state_with_owners(&pool, true), provider);
+
+    let report = service
+        .recover(&orphan.runtime, chrono::Utc::now())
+        .await
+        .expect("recover");
+    assert_eq!(report.resumed, vec![orphan.task_id.clone()]);
+
+    let mut state = TaskState::Working;
+    for _ in 0..100 {
+        state = task_state(&orphan).await;
+        if state != TaskState::Working {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(state, TaskState::Completed);
+    assert!(
+        orphan
+            .repos
+            .tasks
+            .get_checkpoint(&orphan.task_id)
+            .await
+            .unwrap()
+            .is_none(),
+        "a finished run clears its checkpoint"
+    );
+}
+
+#[tokio::test]
+async fn tasks_of_an_inactive_owner_are_failed_not_resumed() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let orphan = seed_orphan(&pool, resume(2)).await;
+    save_checkpoint(&orphan).await;
+    let service = TaskRecoveryService::new(
+        make_agent_state_with_owners(&pool, false),
+        Arc::new(StubAiProvider::new()),
+    );
+
+    let report = service
+        .recover(&orphan.runtime, chrono::Utc::now())
+        .await
+        .expect("recover");
+
+    assert_eq!(report.failed, vec![orphan.task_id.clone()]);
+    assert!(report.resumed.is_empty());
+    assert_eq!(task_state(&orphan).await, TaskState::Failed);
+    let reason = failure_reason(&pool, &orphan.task_id)
+        .await
+        .unwrap_or_default();
+    assert!(reason.contains("the task owner is inactive"), "{reason}");
+}
+
+#[tokio::test]
+async fn tasks_a_live_process_leases_are_left_alone() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let running = seed_orphan(&pool, resume(2)).await;
+    save_checkpoint_leased(&running, "sibling-process", Duration::from_secs(600)).await;
+    let claimed = seed_orphan(&pool, resume(2)).await;
+    save_checkpoint(&claimed).await;
+    assert!(
+        claimed
+            .repos
+            .tasks
+            .claim_checkpoint(&claimed.task_id, "other-replica", Duration::from_secs(600))
+            .await
+            .expect("claim")
+    );
+    let unleased = seed_orphan(&pool, resume(2)).await;
+    let service = TaskRecoveryService::new(
+        make_agent_state_with_owners(&pool, true),
+        Arc::new(StubAiProvider::new()),
+    );
+
+    for orphan in [&running, &claimed, &unleased] {
+        let report = service
+            .recover(&orphan.runtime, chrono::Utc::now())
+            .await
+            .expect("recover");
+
+        assert_eq!(report, RecoveryReport::default());
+        assert_eq!(task_state(orphan).await, TaskState::Working);
+    }
+    for orphan in [&running, &claimed] {
+        let checkpoint = orphan
+            .repos
+            .tasks
+            .get_checkpoint(&orphan.task_id)
+            .await
+            .unwrap()
+            .expect("checkpoint kept");
+        assert_eq!(checkpoint.resume_attempts, 0);
+    }
+}
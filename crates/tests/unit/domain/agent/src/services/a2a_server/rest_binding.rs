@@ -0,0 +1,189 @@
+// The HTTP+JSON binding served by rest_routes: bare task bodies in place of
+// the JSON-RPC envelope, error codes mapped onto HTTP statuses, the `:cancel`
+// and `:subscribe` task methods, and the push-notification config resource.
+
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::{Value, json};
+use systemprompt_agent::models::a2a::TaskState;
+use systemprompt_agent::services::a2a_server::handlers::rest_routes;
+use systemprompt_models::RequestContext;
+use tower::ServiceExt;
+
+use super::a2a_helpers::{StubAiProvider, make_handler_state, request_context};
+use crate::repository::{repos, seed_context_and_task, seed_user_and_session, try_pool};
+
+fn router(pool: &systemprompt_database::DbPool) -> Router {
+    rest_routes().with_state(make_handler_state(pool, Arc::new(StubAiProvider::new()), 1))
+}
+
+fn rest_request(
+    context: &RequestContext,
+    method: &str,
+    uri: &str,
+    body: Option<&Value>,
+) -> Request<Body> {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .expect("request");
+    request.extensions_mut().insert(context.clone());
+    request
+}
+
+async fn send(router: &Router, request: Request<Body>) -> (StatusCode, String) {
+    let response = router
+        .clone()
+        .oneshot(request)
+        .await
+        .expect("router responds");
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    (
+        status,
+        String::from_utf8(bytes.to_vec()).expect("utf8 body"),
+    )
+}
+
+fn json_body(body: &str) -> Value {
+    serde_json::from_str(body).expect("json body")
+}
+
+#[tokio::test]
+async fn get_task_returns_the_task_without_an_envelope() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (context_id, task_id) = seed_context_and_task(&repos, &user_id, &session_id).await;
+    let ctx = request_context(&context_id, &session_id, &user_id, "test_agent");
+
+    let uri = format!("/tasks/{task_id}?historyLength=1");
+    let (status, body) = send(&router(&pool), rest_request(&ctx, "GET", &uri, None)).await;
+    repos.tasks.delete_task(&task_id).await.ok();
+
+    assert_eq!(status, StatusCode::OK);
+    let body = json_body(&body);
+    assert_eq!(body["id"], json!(task_id.as_str()));
+    assert_eq!(body["contextId"], json!(context_id.as_str()));
+    assert!(
+        body.get("jsonrpc").is_none(),
+        "no JSON-RPC envelope: {body}"
+    );
+}
+
+#[tokio::test]
+async fn handler_errors_carry_the_status_their_code_maps_to() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (context_id, task_id) = seed_context_and_task(&repos, &user_id, &session_id).await;
+    let ctx = request_context(&context_id, &session_id, &user_id, "test_agent");
+    let router = router(&pool);
+
+    let (status, body) = send(
+        &router,
+        rest_request(&ctx, "POST", "/tasks/no-such-task:cancel", None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(json_body(&body)["error"]["code"], json!(-32603));
+
+    let uri = format!("/tasks/{task_id}:archive");
+    let (status, body) = send(&router, rest_request(&ctx, "POST", &uri, None)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(json_body(&body)["error"]["code"], json!(-32601));
+
+    let (status, body) = send(
+        &router,
+        rest_request(&ctx, "POST", "/message:send", Some(&json!({"message": 7}))),
+    )
+    .await;
+    repos.tasks.delete_task(&task_id).await.ok();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json_body(&body)["error"]["code"], json!(-32602));
+}
+
+#[tokio::test]
+async fn cancel_and_subscribe_are_routed_by_the_method_suffix() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (context_id, task_id) = seed_context_and_task(&repos, &user_id, &session_id).await;
+    let ctx = request_context(&context_id, &session_id, &user_id, "test_agent");
+    let router = router(&pool);
+
+    let uri = format!("/tasks/{task_id}:cancel");
+    let (status, body) = send(&router, rest_request(&ctx, "POST", &uri, None)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        json_body(&body)["status"]["state"],
+        json!("TASK_STATE_CANCELED")
+    );
+
+    repos
+        .tasks
+        .update_task_state(&task_id, TaskState::Completed, &chrono::Utc::now())
+        .await
+        .expect("complete task");
+    let uri = format!("/tasks/{task_id}:subscribe");
+    let (status, body) = send(&router, rest_request(&ctx, "GET", &uri, None)).await;
+    repos.tasks.delete_task(&task_id).await.ok();
+
+    assert_eq!(status, StatusCode::OK);
+    let frames: Vec<Value> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .map(json_body)
+        .collect();
+    assert_eq!(frames.len(), 1, "a terminal task ends the stream: {body}");
+    assert_eq!(frames[0]["result"]["id"], json!(task_id.as_str()));
+    assert_eq!(
+        frames[0]["result"]["status"]["state"],
+        json!("TASK_STATE_COMPLETED")
+    );
+}
+
+#[tokio::test]
+async fn push_notification_configs_are_created_listed_and_deleted() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let (user_id, session_id) = seed_user_and_session(&pool).await;
+    let (context_id, task_id) = seed_context_and_task(&repos, &user_id, &session_id).await;
+    let ctx = request_context(&context_id, &session_id, &user_id, "test_agent");
+    let router = router(&pool);
+    let uri = format!("/tasks/{task_id}/pushNotificationConfigs");
+
+    let config = json!({"url": "https://hooks.example.invalid/a2a", "token": "t-1"});
+    let (status, body) = send(&router, rest_request(&ctx, "POST", &uri, Some(&config))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json_body(&body)["success"], json!(true));
+
+    let (status, body) = send(&router, rest_request(&ctx, "GET", &uri, None)).await;
+    assert_eq!(status, StatusCode::OK);
+    let listed = json_body(&body);
+    assert_eq!(listed["configs"].as_array().map(Vec::len), Some(1));
+    assert_eq!(
+        listed["configs"][0]["url"],
+        json!("https://hooks.example.invalid/a2a")
+    );
+
+    let (status, body) = send(&router, rest_request(&ctx, "DELETE", &uri, None)).await;
+    repos.tasks.delete_task(&task_id).await.ok();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json_body(&body)["deleted"], json!(1));
+}
@@ -0,0 +1,297 @@
+// Context history compaction: token estimates, truncation to the budget with
+// pinned artifacts and a protected recent tail, summaries standing in for the
+// messages they cover, and the DB-backed summarize path that stores a summary
+// once and falls back to truncation when the model fails.
+
+use systemprompt_agent::models::{ContextSummary, NewContextSummary};
+use systemprompt_agent::services::HistoryEntry;
+use systemprompt_agent::services::compaction::{
+    CompactHistoryParams, ContextCompactionService, estimate_tokens, fit_history, history_tokens,
+    pending_summary, summary_prompt,
+};
+use systemprompt_identifiers::ContextId;
+use systemprompt_models::{
+    AgentCompactionConfig, AiContentPart, AiMessage, CompactionStrategy, MessageRole,
+};
+
+use super::a2a_server::a2a_helpers::{StubAiProvider, request_context, runtime_info};
+use crate::repository::{repos, seed_context_and_task, seed_user_and_session, try_pool};
+
+const AGENT: &str = "compacting_agent";
+
+// Each turn is 400 characters: 100 tokens of text plus the per-message
+// overhead.
+fn turn(index: usize) -> HistoryEntry {
+    let role = if index.is_multiple_of(2) {
+        AiMessage::user
+    } else {
+        AiMessage::assistant
+    };
+    HistoryEntry {
+        message: role(format!("{index:03}{}", "x".repeat(397))),
+        is_artifact: false,
+    }
+}
+
+fn artifact(name: &str) -> HistoryEntry {
+    HistoryEntry {
+        message: AiMessage::assistant(format!("[Artifact: {name} (type: document)]")),
+        is_artifact: true,
+    }
+}
+
+fn config(strategy: CompactionStrategy, max_history_tokens: u32) -> AgentCompactionConfig {
+    AgentCompactionConfig {
+        strategy,
+        max_history_tokens,
+        keep_recent_messages: 2,
+        summary_max_tokens: 100,
+        ..AgentCompactionConfig::default()
+    }
+}
+
+fn summary(covered_messages: i32) -> ContextSummary {
+    ContextSummary {
+        id: 1,
+        context_id: ContextId::generate(),
+        agent_name: AGENT.to_owned(),
+        summary: "The user asked about invoices.".to_owned(),
+        covered_messages,
+        token_estimate: 8,
+        created_at: chrono::Utc::now(),
+    }
+}
+
+fn prefixes(messages: &[AiMessage]) -> Vec<String> {
+    messages
+        .iter()
+        .map(|m| m.content.chars().take(3).collect())
+        .collect()
+}
+
+#[test]
+fn tokens_are_estimated_from_text_with_a_flat_cost_for_media() {
+    let text = AiMessage::user("x".repeat(400));
+    assert_eq!(estimate_tokens(&text), 104);
+
+    let mut with_image = AiMessage::user("look");
+    with_image.parts = vec![
+        AiContentPart::text("look"),
+        AiContentPart::image("image/png", "A".repeat(40_000)),
+    ];
+    assert_eq!(estimate_tokens(&with_image), 1 + 256 + 4);
+}
+
+#[test]
+fn history_within_budget_is_replayed_unchanged() {
+    let entries: Vec<_> = (0..4).map(turn).collect();
+    let messages = fit_history(
+        &entries,
+        &config(CompactionStrategy::Truncate, 10_000),
+        None,
+    );
+    assert_eq!(prefixes(&messages), ["000", "001", "002", "003"]);
+}
+
+#[test]
+fn truncation_drops_the_oldest_turns_but_keeps_pinned_artifacts_and_recent_turns() {
+    let mut entries: Vec<_> = (0..3).map(turn).collect();
+    entries.push(artifact("report"));
+    entries.extend((3..6).map(turn));
+
+    let messages = fit_history(&entries, &config(CompactionStrategy::Truncate, 300), None);
+
+    assert!(history_tokens(&messages) <= 300);
+    assert_eq!(prefixes(&messages), ["[Ar", "004", "005"]);
+}
+
+#[test]
+fn recent_turns_survive_even_over_budget() {
+    let entries: Vec<_> = (0..4).map(turn).collect();
+    let messages = fit_history(&entries, &config(CompactionStrategy::Truncate, 10), None);
+    assert_eq!(prefixes(&messages), ["002", "003"]);
+}
+
+#[test]
+fn unpinned_artifacts_are_dropped_like_any_other_message() {
+    let mut entries = vec![artifact("report")];
+    entries.extend((0..3).map(turn));
+    let config = AgentCompactionConfig {
+        pin_artifacts: false,
+        ..config(CompactionStrategy::Truncate, 250)
+    };
+
+    let messages = fit_history(&entries, &config, None);
+
+    assert_eq!(prefixes(&messages), ["001", "002"]);
+}
+
+#[test]
+fn a_summary_stands_in_for_the_messages_it_covers() {
+    let entries: Vec<_> = (0..6).map(turn).collect();
+
+    let messages = fit_history(
+        &entries,
+        &config(CompactionStrategy::Summarize, 10_000),
+        Some(&summary(3)),
+    );
+
+    assert_eq!(messages[0].role, MessageRole::System);
+    assert!(messages[0].content.contains("3 earliest messages"));
+    assert!(messages[0].content.contains("invoices"));
+    assert_eq!(prefixes(&messages[1..]), ["003", "004", "005"]);
+}
+
+#[test]
+fn a_summary_is_pending_only_when_the_history_outgrows_the_budget() {
+    let entries: Vec<_> = (0..6).map(turn).collect();
+
+    assert!(
+        pending_summary(
+            &entries,
+            &config(CompactionStrategy::Summarize, 10_000),
+            None
+        )
+        .is_none()
+    );
+    assert!(
+        pending_summary(&entries, &config(CompactionStrategy::Truncate, 300), None).is_none(),
+        "truncation never summarizes"
+    );
+
+    let batch = pending_summary(
+        &entries,
+        &config(CompactionStrategy::Summarize, 300),
+        Some(&summary(2)),
+    )
+    .expect("over budget");
+    assert_eq!(batch.covered_messages, 4);
+    let batch_prefixes: Vec<String> = batch
+        .messages
+        .iter()
+        .map(|m| m.content.chars().take(3).collect())
+        .collect();
+    assert_eq!(batch_prefixes, ["002", "003"]);
+
+    let prompt = summary_prompt(Some(&summary(2)), &batch);
+    assert_eq!(prompt[0].role, MessageRole::System);
+    assert!(
+        prompt[1]
+            .content
+            .starts_with("Existing summary:\nThe user asked")
+    );
+    assert!(prompt[1].content.contains("User: 002"));
+    assert!(prompt[1].content.contains("Assistant: 003"));
+}
+
+#[test]
+fn nothing_is_pending_once_everything_outside_the_recent_tail_is_summarized() {
+    let entries: Vec<_> = (0..6).map(turn).collect();
+    assert!(
+        pending_summary(
+            &entries,
+            &config(CompactionStrategy::Summarize, 10),
+            Some(&summary(4))
+        )
+        .is_none()
+    );
+}
+
+#[tokio::test]
+async fn summarizing_stores_a_summary_and_replays_it_on_later_turns() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let (user, session) = seed_user_and_session(&pool).await;
+    let (ctx, _task) = seed_context_and_task(&repos, &user, &session).await;
+    let request_ctx = request_context(&ctx, &session, &user, AGENT);
+    let mut runtime = runtime_info(AGENT);
+    runtime.compaction = config(CompactionStrategy::Summarize, 400);
+    let service = ContextCompactionService::new(repos.contexts.clone());
+    let provider = StubAiProvider::new().with_generate("Invoices were discussed.");
+
+    let messages = service
+        .compact(CompactHistoryParams {
+            ai_service: &provider,
+            agent_runtime: &runtime,
+            context_id: &ctx,
+            request_ctx: &request_ctx,
+            entries: (0..6).map(turn).collect(),
+        })
+        .await;
+
+    assert!(messages[0].content.contains("Invoices were discussed."));
+    assert_eq!(prefixes(&messages[1..]), ["004", "005"]);
+    let stored = repos.contexts.list_summaries(&ctx).await.expect("list");
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].covered_messages, 4);
+
+    let again = service
+        .compact(CompactHistoryParams {
+            ai_service: &provider,
+            agent_runtime: &runtime,
+            context_id: &ctx,
+            request_ctx: &request_ctx,
+            entries: (0..6).map(turn).collect(),
+        })
+        .await;
+
+    assert_eq!(prefixes(&again), prefixes(&messages));
+    assert_eq!(
+        repos
+            .contexts
+            .list_summaries(&ctx)
+            .await
+            .expect("list")
+            .len(),
+        1,
+        "a history that fits after the stored summary is not summarized again"
+    );
+}
+
+#[tokio::test]
+async fn a_failed_summarization_falls_back_to_truncation() {
+    let Some(pool) = try_pool().await else {
+        return;
+    };
+    let repos = repos(&pool);
+    let (user, session) = seed_user_and_session(&pool).await;
+    let (ctx, _task) = seed_context_and_task(&repos, &user, &session).await;
+    repos
+        .contexts
+        .create_summary(&NewContextSummary {
+            context_id: &ctx,
+            agent_name: AGENT,
+            summary: "Earlier: greetings.",
+            covered_messages: 1,
+            token_estimate: 5,
+        })
+        .await
+        .expect("seed summary");
+    let mut runtime = runtime_info(AGENT);
+    runtime.compaction = config(CompactionStrategy::Summarize, 300);
+    let provider = StubAiProvider::new().failing_generate();
+
+    let messages = ContextCompactionService::new(repos.contexts.clone())
+        .compact(CompactHistoryParams {
+            ai_service: &provider,
+            agent_runtime: &runtime,
+            context_id: &ctx,
+            request_ctx: &request_context(&ctx, &session, &user, AGENT),
+            entries: (0..6).map(turn).collect(),
+        })
+        .await;
+
+    assert!(messages[0].content.contains("Earlier: greetings."));
+    assert_eq!(prefixes(&messages[1..]), ["004", "005"]);
+    assert_eq!(
+        repos
+            .contexts
+            .list_summaries(&ctx)
+            .await
+            .expect("list")
+            .len(),
+        1
+    );
+}
@@ -64,6 +64,8 @@ fn services_config_with_server(name: &str) -> ServicesConfig {
             env_vars: vec![],
             external_auth: None,
             headers: Default::default(),
+            stdio: None,
+            sandbox: None,
         },
     );
     config
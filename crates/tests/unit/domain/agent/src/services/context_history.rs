@@ -1,7 +1,8 @@
 // ContextService::load_conversation_history over persisted tasks: text and
 // file parts (image and text attachments decoded, unsupported and byteless
 // files dropped), role mapping, empty-message skipping, and artifact
-// serialization including the long-description truncation.
+// serialization including the long-description truncation, with artifact
+// entries flagged for compaction.
 
 use std::sync::Arc;
 
@@ -153,12 +154,13 @@ async fn history_decodes_parts_and_serializes_artifacts() {
         .await
         .expect("persist");
 
-    let history = ContextService::new(
+    let service = ContextService::new(
         TaskRepository::new(&pool, crate::session_usage(&pool)).expect("task repo"),
-    )
-    .load_conversation_history(&ctx)
-    .await
-    .expect("history");
+    );
+    let history = service
+        .load_conversation_history(&ctx)
+        .await
+        .expect("history");
 
     let user_entry = history
         .iter()
@@ -180,6 +182,14 @@ async fn history_decodes_parts_and_serializes_artifacts() {
     assert_eq!(artifact_entries.len(), 3);
     assert!(artifact_entries.iter().any(|m| m.content.contains("...")));
     assert!(artifact_entries.iter().any(|m| m.content.contains("short")));
+
+    let entries = service.load_history_entries(&ctx).await.expect("entries");
+    assert_eq!(entries.len(), history.len());
+    assert!(
+        entries
+            .iter()
+            .all(|e| e.is_artifact == e.message.content.starts_with("[Artifact: report"))
+    );
 }
 
 #[tokio::test]
@@ -9,6 +9,7 @@ mod agent_orchestration;
 mod agent_token_validation;
 mod artifact_publishing;
 mod auth_validation;
+mod compaction;
 mod config_authoring;
 mod context_history;
 mod context_provider;
@@ -91,7 +91,7 @@ async fn to_agent_card_builds_interfaces_and_extensions() {
         .expect("card");
 
     assert_eq!(card.name, "card_agent");
-    assert_eq!(card.supported_interfaces.len(), 1);
+    assert_eq!(card.supported_interfaces.len(), 2, "JSON-RPC and HTTP+JSON");
     assert!(
         card.supported_interfaces[0]
             .url
@@ -110,7 +110,7 @@ async fn to_agent_card_transport_variants() {
     systemprompt_test_fixtures::ensure_test_bootstrap();
     let _skills = crate::SKILLS_FIXTURE_LOCK.read().await;
 
-    for transport in ["GRPC", "HTTP+JSON", "JSONRPC"] {
+    for (transport, served) in [("GRPC", 3), ("HTTP+JSON", 2), ("JSONRPC", 2)] {
         let mut a = agent_config("transport_agent");
         a.card.preferred_transport = transport.to_owned();
         let registry = registry_with(vec![a]);
@@ -118,7 +118,12 @@ async fn to_agent_card_transport_variants() {
             .to_agent_card("transport_agent", "http://localhost:8080", Vec::new(), None)
             .await
             .expect("card");
-        assert_eq!(card.supported_interfaces.len(), 1);
+        assert_eq!(card.supported_interfaces.len(), served);
+        assert_eq!(
+            String::from(card.supported_interfaces[0].protocol_binding),
+            transport,
+            "the preferred binding is listed first"
+        );
     }
 }
 
@@ -6,11 +6,15 @@
 //! - crates/domain/agent/src/services/a2a_server/processing/strategies/mod.rs
 //! - crates/domain/agent/src/services/a2a_server/processing/strategies/
 //!   standard.rs
+//! - crates/domain/agent/src/services/a2a_server/processing/strategies/
+//!   replay.rs
 
 use systemprompt_agent::services::a2a_server::processing::strategies::{
     ExecutionResult, ExecutionStrategy, ExecutionStrategySelector, PlannedAgenticStrategy,
-    StandardExecutionStrategy,
+    StandardExecutionStrategy, ToolReplay,
 };
+use systemprompt_identifiers::TaskId;
+use systemprompt_models::ExecutionStep;
 
 #[test]
 fn execution_result_default() {
@@ -85,3 +89,65 @@ fn execution_result_with_values() {
     assert_eq!(r.accumulated_text, "hello");
     assert_eq!(r.iterations, 5);
 }
+
+fn completed_tool_step(tool_name: &str, result: serde_json::Value) -> ExecutionStep {
+    let mut step =
+        ExecutionStep::tool_execution(TaskId::generate(), tool_name, serde_json::json!({}));
+    step.complete(Some(result));
+    step
+}
+
+#[test]
+fn tool_replay_collects_single_and_batch_results_of_completed_steps() {
+    let single = completed_tool_step(
+        "search",
+        serde_json::json!({"tool": "search", "arguments": {"q": "rust"}, "output": {"hits": 3}}),
+    );
+    let batch = completed_tool_step(
+        "batch",
+        serde_json::json!({"results": [
+            {"tool": "fetch", "arguments": {"url": "a"}, "output": "A"},
+            {"tool": "fetch", "arguments": {"url": "b"}, "output": "B"},
+        ]}),
+    );
+    let mut cut_off = ExecutionStep::tool_execution(
+        TaskId::generate(),
+        "write",
+        serde_json::json!({"path": "x"}),
+    );
+    cut_off.fail("interrupted".to_owned());
+
+    let replay = ToolReplay::from_steps(&[single, batch, cut_off]);
+    assert_eq!(replay.len(), 3);
+    assert_eq!(
+        replay.take("fetch", &serde_json::json!({"url": "b"})),
+        Some(serde_json::json!("B"))
+    );
+    assert_eq!(
+        replay.take("search", &serde_json::json!({"q": "rust"})),
+        Some(serde_json::json!({"hits": 3}))
+    );
+    assert_eq!(
+        replay.take("write", &serde_json::json!({"path": "x"})),
+        None
+    );
+}
+
+#[test]
+fn tool_replay_answers_each_recorded_call_once() {
+    let replay = ToolReplay::from_steps(&[completed_tool_step(
+        "search",
+        serde_json::json!({"tool": "search", "arguments": {"q": "rust"}, "output": 1}),
+    )]);
+    assert_eq!(replay.take("search", &serde_json::json!({"q": "go"})), None);
+    assert_eq!(
+        replay.take("search", &serde_json::json!({"q": "rust"})),
+        Some(serde_json::json!(1))
+    );
+    assert_eq!(
+        replay.take("search", &serde_json::json!({"q": "rust"})),
+        None
+    );
+    assert!(replay.is_empty());
+    assert!(ToolReplay::default().is_empty());
+}
@@ -135,6 +135,8 @@ async fn update_completion_sets_tokens_and_status() {
             cache_hit: true,
             cache_read_tokens: 128,
             cache_creation_tokens: 0,
+            status: RequestStatus::Completed,
+            error_message: None,
         })
         .await
         .expect("update");
@@ -21,6 +21,7 @@ async fn insert_returns_generated_id() {
             category: "prompt_injection",
             scanner: "heuristic",
             excerpt: Some("ignore previous instructions"),
+            redaction_count: 0,
         })
         .await
         .expect("insert");
@@ -44,6 +45,31 @@ async fn insert_allows_null_excerpt() {
             category: "pii",
             scanner: "null",
             excerpt: None,
+            redaction_count: 0,
+        })
+        .await
+        .expect("insert");
+    assert!(!id.as_str().is_empty());
+}
+
+#[tokio::test]
+async fn insert_records_redaction_count() {
+    let Some(pool) = pool().await else {
+        return;
+    };
+    let uid = user();
+    let request_id = seed_request(&pool, &uid).await;
+    let repo = AiSafetyFindingRepository::new(&pool).expect("repo");
+
+    let id = repo
+        .insert(InsertSafetyFinding {
+            ai_request_id: &request_id,
+            phase: "request",
+            severity: "low",
+            category: "pii_email",
+            scanner: "pattern",
+            excerpt: None,
+            redaction_count: 3,
         })
         .await
         .expect("insert");
@@ -1,4 +1,7 @@
-use systemprompt_ai::{GatewayPolicyConfig, GatewayPolicyEntry, GatewayPolicySpec};
+use systemprompt_ai::{
+    BudgetAlertTarget, GatewayPolicyConfig, GatewayPolicyEntry, GatewayPolicySpec, QuotaWindow,
+    RateLimit,
+};
 
 #[test]
 fn empty_config_validates() {
@@ -149,3 +152,345 @@ policies:
     );
     cfg.validate().expect("validates");
 }
+
+#[test]
+fn enabled_response_cache_with_zero_ttl_is_rejected() {
+    let mut spec = GatewayPolicySpec::default();
+    spec.cache.enabled = true;
+    spec.cache.ttl_seconds = 0;
+    let cfg = GatewayPolicyConfig {
+        policies: vec![GatewayPolicyEntry {
+            name: "cached".to_owned(),
+            enabled: true,
+            spec,
+        }],
+    };
+    let err = cfg.validate().expect_err("a zero TTL must be rejected");
+    assert!(err.to_string().contains("response cache"), "{err}");
+}
+
+#[test]
+fn pattern_scanner_with_invalid_rule_regex_is_rejected() {
+    use systemprompt_ai::{PatternRule, Severity};
+    let mut spec = GatewayPolicySpec::default();
+    spec.safety.scanners = vec!["pattern".to_owned()];
+    spec.safety.pattern.rules = vec![PatternRule {
+        category: "codename".to_owned(),
+        pattern: "(unclosed".to_owned(),
+        severity: Severity::Medium,
+        case_insensitive: false,
+    }];
+    let cfg = GatewayPolicyConfig {
+        policies: vec![GatewayPolicyEntry {
+            name: "strict".to_owned(),
+            enabled: true,
+            spec,
+        }],
+    };
+    let err = cfg
+        .validate()
+        .expect_err("a rule that does not compile must be rejected");
+    assert!(err.to_string().contains("rules[0]"), "{err}");
+}
+
+#[test]
+fn pattern_scanner_with_nothing_selected_is_rejected() {
+    let mut spec = GatewayPolicySpec::default();
+    spec.safety.scanners = vec!["pattern".to_owned()];
+    spec.safety.pattern.detectors = Some(Vec::new());
+    let cfg = GatewayPolicyConfig {
+        policies: vec![GatewayPolicyEntry {
+            name: "strict".to_owned(),
+            enabled: true,
+            spec,
+        }],
+    };
+    let err = cfg
+        .validate()
+        .expect_err("an empty pattern scanner must be rejected");
+    assert!(err.to_string().contains("pattern"), "{err}");
+}
+
+#[test]
+fn yaml_parses_pattern_block() {
+    let yaml = r#"
+policies:
+  - name: guarded
+    spec:
+      safety:
+        scanners: [pattern]
+        pattern:
+          detectors: [credit_card, cloud_credential]
+          rules:
+            - category: codename
+              pattern: "(?i)project falcon"
+              severity: high
+        block_categories: [pii_credit_card, codename]
+"#;
+    let cfg: GatewayPolicyConfig = serde_yaml::from_str(yaml).expect("yaml parses");
+    let pattern = &cfg.policies[0].spec.safety.pattern;
+    assert_eq!(pattern.effective_detectors().len(), 2);
+    assert_eq!(pattern.rules[0].severity, systemprompt_ai::Severity::High);
+    cfg.validate().expect("validates");
+}
+
+#[test]
+fn redaction_category_without_a_locator_is_rejected() {
+    let mut spec = GatewayPolicySpec::default();
+    spec.safety.redaction.categories = vec!["prompt_injection".to_owned()];
+    let cfg = GatewayPolicyConfig {
+        policies: vec![GatewayPolicyEntry {
+            name: "strict".to_owned(),
+            enabled: true,
+            spec,
+        }],
+    };
+    let err = cfg
+        .validate()
+        .expect_err("a category no detector produces cannot be redacted");
+    assert!(err.to_string().contains("prompt_injection"), "{err}");
+}
+
+#[test]
+fn redaction_category_that_is_also_blocked_is_rejected() {
+    let mut spec = GatewayPolicySpec::default();
+    spec.safety.redaction.categories = vec!["pii_email".to_owned()];
+    spec.safety.block_categories = vec!["pii_email".to_owned()];
+    let cfg = GatewayPolicyConfig {
+        policies: vec![GatewayPolicyEntry {
+            name: "strict".to_owned(),
+            enabled: true,
+            spec,
+        }],
+    };
+    let err = cfg
+        .validate()
+        .expect_err("a category cannot be both redacted and blocked");
+    assert!(
+        err.to_string().contains("both redacted and blocked"),
+        "{err}"
+    );
+}
+
+#[test]
+fn yaml_parses_redaction_block() {
+    let yaml = r#"
+policies:
+  - name: masked
+    spec:
+      safety:
+        redaction:
+          categories: [pii_email, pii_phone]
+          response_categories: [secret_cloud_credential]
+          rehydrate: true
+"#;
+    let cfg: GatewayPolicyConfig = serde_yaml::from_str(yaml).expect("yaml parses");
+    let redaction = &cfg.policies[0].spec.safety.redaction;
+    assert!(redaction.is_active());
+    assert!(redaction.rehydrate);
+    assert_eq!(redaction.categories.len(), 2);
+    cfg.validate().expect("validates");
+}
+
+#[test]
+fn stream_enforcement_without_blocked_response_categories_is_rejected() {
+    let mut spec = GatewayPolicySpec::default();
+    spec.safety.scanners = vec!["pattern".to_owned()];
+    spec.safety.stream.enforce = true;
+    let cfg = GatewayPolicyConfig {
+        policies: vec![GatewayPolicyEntry {
+            name: "strict".to_owned(),
+            enabled: true,
+            spec,
+        }],
+    };
+    let err = cfg
+        .validate()
+        .expect_err("enforcement with nothing to block must be rejected");
+    assert!(
+        err.to_string().contains("block_response_categories"),
+        "{err}"
+    );
+}
+
+#[test]
+fn stream_window_outside_bounds_is_rejected() {
+    let mut spec = GatewayPolicySpec::default();
+    spec.safety.scanners = vec!["pattern".to_owned()];
+    spec.safety.block_response_categories = vec!["pii_credit_card".to_owned()];
+    spec.safety.stream.enforce = true;
+    spec.safety.stream.window_bytes = 16;
+    let cfg = GatewayPolicyConfig {
+        policies: vec![GatewayPolicyEntry {
+            name: "strict".to_owned(),
+            enabled: true,
+            spec,
+        }],
+    };
+    let err = cfg
+        .validate()
+        .expect_err("a window too small to hold a match must be rejected");
+    assert!(err.to_string().contains("window_bytes"), "{err}");
+}
+
+#[test]
+fn yaml_parses_stream_block() {
+    let yaml = r#"
+policies:
+  - name: enforced
+    spec:
+      safety:
+        scanners: [pattern]
+        block_response_categories: [secret_cloud_credential]
+        stream:
+          enforce: true
+"#;
+    let cfg: GatewayPolicyConfig = serde_yaml::from_str(yaml).expect("yaml parses");
+    let stream = cfg.policies[0].spec.safety.stream;
+    assert!(stream.enforce);
+    assert_eq!(stream.window_bytes, 4096);
+    cfg.validate().expect("validates");
+}
+
+#[test]
+fn rate_limit_with_no_limit_set_is_rejected() {
+    let spec = GatewayPolicySpec {
+        rate_limits: vec![RateLimit::default()],
+        ..GatewayPolicySpec::default()
+    };
+    let cfg = GatewayPolicyConfig {
+        policies: vec![GatewayPolicyEntry {
+            name: "limits".to_owned(),
+            enabled: true,
+            spec,
+        }],
+    };
+    let err = cfg
+        .validate()
+        .expect_err("a rate limit that limits nothing must be rejected");
+    assert!(err.to_string().contains("rate_limits[0]"), "{err}");
+}
+
+#[test]
+fn zero_rate_limit_is_rejected() {
+    let spec = GatewayPolicySpec {
+        rate_limits: vec![RateLimit {
+            requests_per_minute: Some(60),
+            max_concurrent: Some(0),
+            ..RateLimit::default()
+        }],
+        ..GatewayPolicySpec::default()
+    };
+    let cfg = GatewayPolicyConfig {
+        policies: vec![GatewayPolicyEntry {
+            name: "limits".to_owned(),
+            enabled: true,
+            spec,
+        }],
+    };
+    let err = cfg.validate().expect_err("a zero limit must be rejected");
+    assert!(err.to_string().contains("positive"), "{err}");
+}
+
+#[test]
+fn yaml_parses_rate_limits_block() {
+    let yaml = r#"
+policies:
+  - name: limits
+    spec:
+      rate_limits:
+        - max_concurrent: 4
+          tokens_per_minute: 200000
+        - subject: organization
+          requests_per_minute: 600
+"#;
+    let cfg: GatewayPolicyConfig = serde_yaml::from_str(yaml).expect("yaml parses");
+    let limits = &cfg.policies[0].spec.rate_limits;
+    assert_eq!(limits.len(), 2);
+    assert_eq!(limits[0].subject, "user");
+    assert_eq!(limits[0].max_concurrent, Some(4));
+    assert_eq!(limits[0].requests_per_minute, None);
+    assert_eq!(limits[1].subject, "organization");
+    assert_eq!(limits[1].requests_per_minute, Some(600));
+    cfg.validate().expect("validates");
+}
+
+fn single_policy(spec: GatewayPolicySpec) -> GatewayPolicyConfig {
+    GatewayPolicyConfig {
+        policies: vec![GatewayPolicyEntry {
+            name: "budgets".to_owned(),
+            enabled: true,
+            spec,
+        }],
+    }
+}
+
+#[test]
+fn alert_thresholds_without_a_cost_ceiling_are_rejected() {
+    let cfg = single_policy(GatewayPolicySpec {
+        quota_windows: vec![QuotaWindow {
+            max_requests: Some(100),
+            alert_thresholds: vec![80],
+            ..QuotaWindow::default()
+        }],
+        ..GatewayPolicySpec::default()
+    });
+    let err = cfg
+        .validate()
+        .expect_err("thresholds need a cost ceiling to be a percentage of");
+    assert!(
+        err.to_string()
+            .contains("quota_windows[0].alert_thresholds"),
+        "{err}"
+    );
+}
+
+#[test]
+fn alert_threshold_above_one_hundred_is_rejected() {
+    let cfg = single_policy(GatewayPolicySpec {
+        quota_windows: vec![QuotaWindow {
+            max_cost_microdollars: Some(1_000_000),
+            alert_thresholds: vec![50, 120],
+            ..QuotaWindow::default()
+        }],
+        ..GatewayPolicySpec::default()
+    });
+    let err = cfg.validate().expect_err("120% is not a threshold");
+    assert!(err.to_string().contains("120"), "{err}");
+}
+
+#[test]
+fn alert_target_with_an_empty_field_is_rejected() {
+    let cfg = single_policy(GatewayPolicySpec {
+        alerts: vec![BudgetAlertTarget::Slack {
+            app: "ops".to_owned(),
+            channel: " ".to_owned(),
+        }],
+        ..GatewayPolicySpec::default()
+    });
+    let err = cfg
+        .validate()
+        .expect_err("a blank channel must be rejected");
+    assert!(err.to_string().contains("alerts[0].channel"), "{err}");
+}
+
+#[test]
+fn yaml_parses_budget_alerts() {
+    let yaml = r#"
+policies:
+  - name: budgets
+    spec:
+      quota_windows:
+        - window_seconds: 86400
+          max_cost_microdollars: 25000000
+          alert_thresholds: [50, 80, 100]
+      alerts:
+        - kind: webhook
+          url: https://hooks.example.com/budget
+"#;
+    let cfg: GatewayPolicyConfig = serde_yaml::from_str(yaml).expect("yaml parses");
+    let spec = &cfg.policies[0].spec;
+    assert_eq!(spec.quota_windows[0].alert_thresholds, vec![50, 80, 100]);
+    assert_eq!(spec.alerts.len(), 1);
+    cfg.validate().expect("validates");
+}
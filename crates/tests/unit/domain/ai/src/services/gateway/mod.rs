@@ -2,6 +2,8 @@ pub mod config;
 pub mod ingestion;
 pub mod loader;
 pub mod overrides;
+pub mod pattern_scanner;
+pub mod redaction;
 pub mod route_selector;
 pub mod safety;
 pub mod spec;
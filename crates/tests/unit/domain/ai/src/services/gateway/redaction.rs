@@ -0,0 +1,195 @@
+// Behaviour tests for placeholder redaction: stable placeholders, request
+// rewriting, response rehydration, and the streaming hold-back buffer.
+
+use systemprompt_ai::{PatternConfig, RedactionVault, Redactor, ResponseRewriter, Severity};
+use systemprompt_models::wire::canonical::{
+    CanonicalContent, CanonicalMessage, CanonicalRequest, CanonicalResponse, Role,
+};
+
+const EMAIL: &str = "jane.doe@example.com";
+
+fn redactor(categories: &[&str]) -> Redactor {
+    let categories: Vec<String> = categories.iter().map(|c| (*c).to_owned()).collect();
+    Redactor::new(&PatternConfig::default(), &categories)
+        .expect("builtin detectors compile")
+        .expect("category is located by a detector")
+}
+
+fn request(text: &str) -> CanonicalRequest {
+    CanonicalRequest {
+        model: "test-model".to_owned(),
+        system: Some(format!("Reply to {EMAIL}.")),
+        messages: vec![
+            CanonicalMessage {
+                role: Role::User,
+                content: vec![CanonicalContent::Text(text.to_owned())],
+            },
+            CanonicalMessage {
+                role: Role::User,
+                content: vec![CanonicalContent::ToolResult {
+                    tool_use_id: "toolu_1".to_owned(),
+                    content: vec![CanonicalContent::Text(format!("owner: {EMAIL}"))],
+                    is_error: false,
+                    structured_content: None,
+                    meta: None,
+                }],
+            },
+        ],
+        max_tokens: 16,
+        ..CanonicalRequest::default()
+    }
+}
+
+fn text_of(content: &[CanonicalContent]) -> String {
+    content
+        .iter()
+        .map(|part| match part {
+            CanonicalContent::Text(t) => t.clone(),
+            CanonicalContent::ToolResult { content, .. } => text_of(content),
+            _ => String::new(),
+        })
+        .collect()
+}
+
+#[test]
+fn unlocatable_categories_build_no_redactor() {
+    let categories = vec!["prompt_injection".to_owned()];
+    let built = Redactor::new(&PatternConfig::default(), &categories).expect("compiles");
+    assert!(built.is_none());
+    assert!(
+        Redactor::new(&PatternConfig::default(), &[])
+            .expect("compiles")
+            .is_none()
+    );
+}
+
+#[test]
+fn same_original_maps_to_the_same_placeholder() {
+    let redactor = redactor(&["pii_email"]);
+    let mut vault = RedactionVault::default();
+    let out = redactor
+        .redact(
+            &mut vault,
+            &format!("{EMAIL} and again {EMAIL}, then bob@example.org"),
+        )
+        .expect("spans redacted");
+    assert_eq!(
+        out,
+        "[PII_EMAIL_1] and again [PII_EMAIL_1], then [PII_EMAIL_2]"
+    );
+    let tallies = vault.take_tallies();
+    assert_eq!(tallies.len(), 1);
+    assert_eq!(tallies[0].category, "pii_email");
+    assert_eq!(tallies[0].severity, Severity::Low);
+    assert_eq!(tallies[0].count, 3);
+    assert!(vault.take_tallies().is_empty(), "tallies drain once");
+}
+
+#[test]
+fn unselected_categories_pass_through() {
+    let redactor = redactor(&["pii_email"]);
+    let mut vault = RedactionVault::default();
+    assert!(
+        redactor
+            .redact(&mut vault, "card 4111 1111 1111 1111")
+            .is_none()
+    );
+    assert!(vault.is_empty());
+}
+
+#[test]
+fn request_rewrite_covers_system_messages_and_tool_results() {
+    let redactor = redactor(&["pii_email"]);
+    let mut vault = RedactionVault::default();
+    let mut req = request(&format!("mail {EMAIL}"));
+    assert!(redactor.redact_request(&mut vault, &mut req));
+    assert_eq!(req.system.as_deref(), Some("Reply to [PII_EMAIL_1]."));
+    assert_eq!(text_of(&req.messages[0].content), "mail [PII_EMAIL_1]");
+    assert_eq!(text_of(&req.messages[1].content), "owner: [PII_EMAIL_1]");
+    assert_eq!(vault.take_tallies()[0].count, 3);
+
+    let mut clean = CanonicalRequest {
+        messages: vec![CanonicalMessage {
+            role: Role::User,
+            content: vec![CanonicalContent::Text("nothing here".to_owned())],
+        }],
+        ..CanonicalRequest::default()
+    };
+    assert!(!redactor.redact_request(&mut vault, &mut clean));
+}
+
+#[test]
+fn rewriter_is_none_when_nothing_would_change() {
+    assert!(ResponseRewriter::new(None, RedactionVault::default(), true).is_none());
+    let redactor = redactor(&["pii_email"]);
+    let mut vault = RedactionVault::default();
+    redactor.redact(&mut vault, EMAIL);
+    assert!(ResponseRewriter::new(None, vault, false).is_none());
+}
+
+#[test]
+fn rehydration_restores_request_placeholders_in_the_response() {
+    let redactor = redactor(&["pii_email"]);
+    let mut vault = RedactionVault::default();
+    redactor.redact(&mut vault, EMAIL);
+    let mut rewriter = ResponseRewriter::new(None, vault, true).expect("rehydrates");
+    let mut response = CanonicalResponse {
+        content: vec![CanonicalContent::Text(
+            "I will write to [PII_EMAIL_1] now.".to_owned(),
+        )],
+        ..CanonicalResponse::default()
+    };
+    rewriter.rewrite_response(&mut response);
+    assert_eq!(
+        text_of(&response.content),
+        format!("I will write to {EMAIL} now.")
+    );
+}
+
+#[test]
+fn response_redaction_is_not_undone_by_rehydration() {
+    let mut vault = RedactionVault::default();
+    redactor(&["pii_email"]).redact(&mut vault, EMAIL);
+    vault.take_tallies();
+    let mut rewriter =
+        ResponseRewriter::new(Some(redactor(&["pii_email"])), vault, true).expect("rewrites");
+    let out = rewriter.rewrite("[PII_EMAIL_1] or other@example.net");
+    assert_eq!(out, format!("{EMAIL} or [PII_EMAIL_2]"));
+    let tallies = rewriter.take_tallies();
+    assert_eq!(tallies.len(), 1);
+    assert_eq!(tallies[0].count, 1, "only the response span is tallied");
+}
+
+#[test]
+fn streamed_span_split_across_deltas_is_redacted() {
+    let mut rewriter = ResponseRewriter::new(
+        Some(redactor(&["pii_email"])),
+        RedactionVault::default(),
+        false,
+    )
+    .expect("rewrites");
+    let lead = "x".repeat(300);
+    let mut out = rewriter.push(0, &format!("{lead} contact jane.d"));
+    out.push_str(&rewriter.push(0, "oe@example.com please"));
+    assert!(
+        !out.contains("jane.d"),
+        "held-back text must not leak early"
+    );
+    out.push_str(&rewriter.flush(0));
+    assert_eq!(out, format!("{lead} contact [PII_EMAIL_1] please"));
+    assert!(rewriter.pending_blocks().is_empty());
+}
+
+#[test]
+fn streamed_placeholder_split_across_deltas_is_rehydrated() {
+    let redactor = redactor(&["pii_email"]);
+    let mut vault = RedactionVault::default();
+    redactor.redact(&mut vault, EMAIL);
+    let mut rewriter = ResponseRewriter::new(None, vault, true).expect("rehydrates");
+    let lead = "y".repeat(400);
+    let mut out = rewriter.push(3, &format!("{lead} [PII_EM"));
+    out.push_str(&rewriter.push(3, "AIL_1]"));
+    assert_eq!(rewriter.pending_blocks(), vec![3]);
+    out.push_str(&rewriter.flush(3));
+    assert_eq!(out, format!("{lead} {EMAIL}"));
+}
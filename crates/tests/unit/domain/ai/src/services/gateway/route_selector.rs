@@ -17,6 +17,9 @@ fn route(pattern: &str, provider: &str) -> GatewayRoute {
         extra_headers: HashMap::new(),
         pricing: None,
         when: None,
+        weight: 1,
+        targets: Vec::new(),
+        fallback: None,
     };
     r.ensure_id();
     r
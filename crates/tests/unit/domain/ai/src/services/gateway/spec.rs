@@ -1,4 +1,7 @@
-use systemprompt_ai::{GatewayPolicySpec, QuotaWindow, SafetyConfig, SafetyHistoryMode};
+use systemprompt_ai::{
+    BudgetAlertTarget, GatewayPolicySpec, QuotaWindow, ResponseCacheScope, SafetyConfig,
+    SafetyHistoryMode,
+};
 
 #[test]
 fn permissive_is_default() {
@@ -39,6 +42,66 @@ fn a_quota_window_can_key_on_an_extension_subject_with_a_cost_ceiling() {
     assert_eq!(qw.max_requests, None);
 }
 
+fn budget_window(thresholds: Vec<u8>) -> QuotaWindow {
+    QuotaWindow {
+        window_seconds: 86_400,
+        max_cost_microdollars: Some(10_000_000),
+        alert_thresholds: thresholds,
+        ..QuotaWindow::default()
+    }
+}
+
+#[test]
+fn crossed_thresholds_reports_each_line_once_in_ascending_order() {
+    let window = budget_window(vec![100, 50, 80, 80]);
+    assert_eq!(
+        window.crossed_thresholds(4_000_000, 9_000_000),
+        vec![50, 80]
+    );
+    assert!(window.crossed_thresholds(5_000_000, 7_000_000).is_empty());
+    assert_eq!(
+        window.crossed_thresholds(4_999_999, 5_000_000),
+        vec![50],
+        "landing exactly on a threshold crosses it"
+    );
+    assert_eq!(window.crossed_thresholds(9_500_000, 12_000_000), vec![100]);
+}
+
+#[test]
+fn crossed_thresholds_is_empty_without_a_cost_ceiling() {
+    let window = QuotaWindow {
+        max_cost_microdollars: None,
+        ..budget_window(vec![50])
+    };
+    assert!(window.crossed_thresholds(0, i64::MAX).is_empty());
+}
+
+#[test]
+fn budget_alert_targets_parse_by_kind() {
+    let yaml = r#"
+- kind: webhook
+  url: https://hooks.example.com/budget
+  secret_ref: budget_webhook_secret
+- kind: slack
+  app: ops
+  channel: C0123
+- kind: teams
+  app: finance
+  service_url: https://smba.trafficmanager.net/emea/
+  conversation_id: "19:abc@thread.tacv2"
+"#;
+    let targets: Vec<BudgetAlertTarget> = serde_yaml::from_str(yaml).expect("de");
+    assert_eq!(
+        targets[0],
+        BudgetAlertTarget::Webhook {
+            url: "https://hooks.example.com/budget".to_owned(),
+            secret_ref: Some("budget_webhook_secret".to_owned()),
+        }
+    );
+    assert!(matches!(&targets[1], BudgetAlertTarget::Slack { channel, .. } if channel == "C0123"));
+    assert!(matches!(&targets[2], BudgetAlertTarget::Teams { app, .. } if app == "finance"));
+}
+
 #[test]
 fn safety_config_defaults_are_empty() {
     let s = SafetyConfig::default();
@@ -78,3 +141,34 @@ fn safety_history_modes_round_trip_as_lowercase() {
         assert_eq!(s.history, mode);
     }
 }
+
+#[test]
+fn response_cache_is_off_by_default() {
+    let p = GatewayPolicySpec::permissive();
+    assert!(!p.cache.enabled);
+    assert_eq!(p.cache.scope, ResponseCacheScope::User);
+}
+
+#[test]
+fn a_policy_written_before_the_response_cache_existed_still_deserializes() {
+    let yaml = "quota_windows: []\nsafety:\n  scanners: [heuristic]";
+    let p: GatewayPolicySpec = serde_yaml::from_str(yaml).expect("de");
+    assert!(!p.cache.enabled);
+}
+
+#[test]
+fn response_cache_block_parses_with_defaults_for_omitted_limits() {
+    let yaml = "cache:\n  enabled: true\n  scope: tenant";
+    let p: GatewayPolicySpec = serde_yaml::from_str(yaml).expect("de");
+    assert!(p.cache.enabled);
+    assert_eq!(p.cache.scope, ResponseCacheScope::Tenant);
+    assert_eq!(p.cache.ttl_seconds, 3600);
+    assert_eq!(p.cache.max_entry_bytes, 256 * 1024);
+}
+
+#[test]
+fn response_cache_unknown_field_rejected() {
+    let yaml = "cache:\n  enabled: true\n  ttl: 60";
+    let r: Result<GatewayPolicySpec, _> = serde_yaml::from_str(yaml);
+    assert!(r.is_err());
+}
@@ -0,0 +1,74 @@
+use serde_json::json;
+use systemprompt_ai::services::providers::discovery::{
+    discovery_url, parse_ollama_tags, parse_openai_models,
+};
+use systemprompt_models::profile::ModelDiscovery;
+
+mod discovery_url_tests {
+    use super::*;
+
+    #[test]
+    fn openai_listing_sits_under_the_endpoint() {
+        assert_eq!(
+            discovery_url("http://localhost:8000/v1/", ModelDiscovery::OpenAi).as_deref(),
+            Some("http://localhost:8000/v1/models")
+        );
+    }
+
+    #[test]
+    fn ollama_listing_sits_beside_the_v1_prefix() {
+        assert_eq!(
+            discovery_url("http://localhost:11434/v1", ModelDiscovery::Ollama).as_deref(),
+            Some("http://localhost:11434/api/tags")
+        );
+        assert_eq!(
+            discovery_url("http://localhost:11434", ModelDiscovery::Ollama).as_deref(),
+            Some("http://localhost:11434/api/tags")
+        );
+    }
+
+    #[test]
+    fn none_skips_discovery() {
+        assert!(discovery_url("http://localhost:8080/v1", ModelDiscovery::None).is_none());
+    }
+}
+
+mod parse_tests {
+    use super::*;
+
+    #[test]
+    fn openai_models_reads_data_ids() {
+        let body = json!({
+            "object": "list",
+            "data": [
+                {"id": "meta-llama/Llama-3.1-8B-Instruct", "object": "model"},
+                {"id": "", "object": "model"},
+                {"object": "model"}
+            ]
+        });
+        assert_eq!(
+            parse_openai_models(&body),
+            vec!["meta-llama/Llama-3.1-8B-Instruct".to_owned()]
+        );
+    }
+
+    #[test]
+    fn ollama_tags_reads_model_names() {
+        let body = json!({
+            "models": [
+                {"name": "llama3.2:latest", "model": "llama3.2:latest"},
+                {"name": "qwen2.5-coder:7b"}
+            ]
+        });
+        assert_eq!(
+            parse_ollama_tags(&body),
+            vec!["llama3.2:latest".to_owned(), "qwen2.5-coder:7b".to_owned()]
+        );
+    }
+
+    #[test]
+    fn unexpected_shapes_yield_no_models() {
+        assert!(parse_openai_models(&json!({"models": []})).is_empty());
+        assert!(parse_ollama_tags(&json!([])).is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+// `embed` on the OpenAI and Gemini providers against a mock upstream: the
+// request each wire expects, vector ordering, and token accounting.
+
+use serde_json::json;
+use systemprompt_ai::error::AiError;
+use systemprompt_ai::services::providers::gemini::GeminiProvider;
+use systemprompt_ai::services::providers::openai::OpenAiProvider;
+use systemprompt_ai::services::providers::provider_trait::{AiProvider, EmbeddingParams};
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn inputs() -> Vec<String> {
+    vec!["first".to_owned(), "second".to_owned()]
+}
+
+#[tokio::test]
+async fn openai_embed_posts_inputs_and_orders_vectors_by_index() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .and(header("authorization", "Bearer k"))
+        .and(body_partial_json(json!({
+            "model": "text-embedding-3-small",
+            "input": ["first", "second"],
+            "dimensions": 2
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "list",
+            "data": [
+                { "object": "embedding", "index": 1, "embedding": [0.3, 0.4] },
+                { "object": "embedding", "index": 0, "embedding": [0.1, 0.2] }
+            ],
+            "model": "text-embedding-3-small",
+            "usage": { "prompt_tokens": 5, "total_tokens": 5 }
+        })))
+        .mount(&server)
+        .await;
+    let provider = OpenAiProvider::with_endpoint("k".to_owned(), server.uri());
+    let inputs = inputs();
+
+    let response = provider
+        .embed(EmbeddingParams::new(&inputs, "text-embedding-3-small").with_dimensions(2))
+        .await
+        .expect("embed");
+
+    assert!(provider.supports_embeddings());
+    assert_eq!(response.vectors, vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+    assert_eq!(response.input_tokens, 5);
+    assert_eq!(response.model, "text-embedding-3-small");
+}
+
+#[tokio::test]
+async fn openai_embed_surfaces_upstream_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .respond_with(
+            ResponseTemplate::new(400)
+                .set_body_json(json!({ "error": { "message": "bad model" } })),
+        )
+        .mount(&server)
+        .await;
+    let provider = OpenAiProvider::with_endpoint("k".to_owned(), server.uri());
+    let inputs = inputs();
+
+    let err = provider
+        .embed(EmbeddingParams::new(&inputs, "nope"))
+        .await
+        .expect_err("upstream 400");
+    assert!(!matches!(err, AiError::Internal(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn gemini_embed_uses_batch_endpoint_and_estimates_tokens() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/models/text-embedding-004:batchEmbedContents"))
+        .and(header("x-goog-api-key", "test-key"))
+        .and(body_partial_json(json!({
+            "requests": [
+                { "model": "models/text-embedding-004", "content": { "parts": [{ "text": "first" }] } },
+                { "model": "models/text-embedding-004", "content": { "parts": [{ "text": "second" }] } }
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "embeddings": [ { "values": [1.0, 0.0] }, { "values": [0.0, 1.0] } ]
+        })))
+        .mount(&server)
+        .await;
+    let provider = GeminiProvider::with_endpoint("test-key".to_owned(), server.uri())
+        .expect("gemini provider");
+    let inputs = inputs();
+
+    let response = provider
+        .embed(EmbeddingParams::new(&inputs, "text-embedding-004"))
+        .await
+        .expect("embed");
+
+    assert!(provider.supports_embeddings());
+    assert_eq!(response.vectors.len(), 2);
+    assert_eq!(response.vectors[1], vec![0.0, 1.0]);
+    assert!(response.input_tokens > 0);
+}
+
+#[tokio::test]
+async fn gemini_embed_rejects_a_short_batch() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/models/text-embedding-004:batchEmbedContents"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "embeddings": [ { "values": [1.0] } ] })),
+        )
+        .mount(&server)
+        .await;
+    let provider = GeminiProvider::with_endpoint("test-key".to_owned(), server.uri())
+        .expect("gemini provider");
+    let inputs = inputs();
+
+    let err = provider
+        .embed(EmbeddingParams::new(&inputs, "text-embedding-004"))
+        .await
+        .expect_err("count mismatch");
+    assert!(matches!(err, AiError::ProviderError { .. }), "{err:?}");
+}
@@ -41,6 +41,7 @@ fn weather_tool() -> McpTool {
         output_schema: None,
         service_id: McpServerId::new("weather-service"),
         terminal_on_success: false,
+        requires_approval: false,
         model_config: None,
     }
 }
@@ -2,6 +2,8 @@
 mod anthropic;
 mod canonical_bridge;
 mod cost_estimation;
+mod discovery;
+mod embeddings_http;
 mod gemini;
 mod gemini_images_http;
 mod gemini_params;
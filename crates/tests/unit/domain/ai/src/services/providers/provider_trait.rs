@@ -82,6 +82,7 @@ mod tool_generation_params_tests {
             output_schema: None,
             service_id: McpServerId::new("math-service"),
             terminal_on_success: false,
+            requires_approval: false,
             model_config: None,
         }]
     }
@@ -13,8 +13,8 @@ use systemprompt_ai::models::ai::{
 };
 use systemprompt_ai::models::tools::{CallToolResult, ToolCall};
 use systemprompt_ai::services::providers::{
-    AiProvider, GenerationParams, ModelPricing, SchemaGenerationParams, SearchGenerationParams,
-    StructuredGenerationParams, ToolGenerationParams, ToolResultsParams,
+    AiProvider, EmbeddingParams, GenerationParams, ModelPricing, SchemaGenerationParams,
+    SearchGenerationParams, StructuredGenerationParams, ToolGenerationParams, ToolResultsParams,
 };
 use systemprompt_ai::services::schema::ProviderCapabilities;
 use systemprompt_ai::services::structured_output::StructuredOutputProcessor;
@@ -165,6 +165,7 @@ async fn default_capability_flags_and_unsupported_operations() {
     assert!(provider.supports_structured_output());
     assert!(!provider.supports_streaming());
     assert!(!provider.supports_google_search());
+    assert!(!provider.supports_embeddings());
 
     let msgs = messages();
     let stream = provider
@@ -186,6 +187,10 @@ async fn default_capability_flags_and_unsupported_operations() {
         )))
         .await;
     assert!(matches!(search, Err(AiError::Internal(msg)) if msg.contains("Google Search")));
+
+    let inputs = vec!["hello".to_owned()];
+    let embed = provider.embed(EmbeddingParams::new(&inputs, "m")).await;
+    assert!(matches!(embed, Err(AiError::Internal(msg)) if msg.contains("Embeddings")));
 }
 
 fn options() -> StructuredOutputOptions {
@@ -13,6 +13,7 @@ fn create_test_tool(name: &str, description: &str, schema: serde_json::Value) ->
         output_schema: None,
         service_id: McpServerId::new("test-service"),
         terminal_on_success: false,
+        requires_approval: false,
         model_config: None,
     }
 }
@@ -82,6 +83,7 @@ mod error_handling_tests {
             output_schema: None,
             service_id: McpServerId::new("test"),
             terminal_on_success: false,
+            requires_approval: false,
             model_config: None,
         };
 
@@ -100,6 +102,7 @@ mod error_handling_tests {
             output_schema: None,
             service_id: McpServerId::new("test"),
             terminal_on_success: false,
+            requires_approval: false,
             model_config: None,
         };
 
@@ -118,6 +121,7 @@ mod error_handling_tests {
             output_schema: None,
             service_id: McpServerId::new("test"),
             terminal_on_success: false,
+            requires_approval: false,
             model_config: None,
         };
 
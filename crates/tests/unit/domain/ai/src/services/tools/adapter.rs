@@ -26,6 +26,7 @@ mod mcp_tool_to_definition_tests {
             output_schema: Some(json!({"type": "string"})),
             service_id: McpServerId::new("test-service"),
             terminal_on_success: true,
+            requires_approval: false,
             model_config: None,
         }
     }
@@ -76,6 +77,7 @@ mod mcp_tool_to_definition_tests {
             output_schema: None,
             service_id: McpServerId::new("service"),
             terminal_on_success: false,
+            requires_approval: false,
             model_config: None,
         };
 
@@ -98,6 +100,7 @@ mod definition_to_mcp_tool_tests {
             output_schema: None,
             service_id: "def-service".to_string(),
             terminal_on_success: false,
+            requires_approval: false,
             model_config: None,
         }
     }
@@ -134,6 +137,7 @@ mod definition_to_mcp_tool_tests {
             output_schema: Some(json!({"type": "array"})),
             service_id: McpServerId::new("roundtrip-service"),
             terminal_on_success: true,
+            requires_approval: true,
             model_config: None,
         };
 
@@ -143,6 +147,7 @@ mod definition_to_mcp_tool_tests {
         assert_eq!(original.name, converted.name);
         assert_eq!(original.description, converted.description);
         assert_eq!(original.terminal_on_success, converted.terminal_on_success);
+        assert!(converted.requires_approval);
     }
 }
 
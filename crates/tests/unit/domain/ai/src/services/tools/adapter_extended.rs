@@ -107,6 +107,7 @@ mod mcp_tool_with_model_config_tests {
             output_schema: None,
             service_id: McpServerId::new("config-service"),
             terminal_on_success: false,
+            requires_approval: false,
             model_config: Some(ToolModelConfig::new("anthropic", "claude-3")),
         };
 
@@ -127,6 +128,7 @@ mod mcp_tool_with_model_config_tests {
             output_schema: None,
             service_id: McpServerId::new("svc"),
             terminal_on_success: true,
+            requires_approval: false,
             model_config: Some(
                 ToolModelConfig::new("openai", "gpt-4").with_max_output_tokens(2048),
             ),
@@ -139,6 +139,7 @@ mod agent_row_tests {
             name: Some("Support Chat".to_string()),
             task_count: 5,
             message_count: 25,
+            summary_count: 0,
             created_at: now,
             updated_at: now,
         };
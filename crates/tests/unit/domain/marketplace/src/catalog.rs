@@ -70,6 +70,8 @@ fn make_deployment(_name: &str, enabled: bool, endpoint: Option<&str>) -> Deploy
         env_vars: vec![],
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -212,6 +212,8 @@ fn enabled_deployment(endpoint: Option<&str>) -> systemprompt_models::mcp::Deplo
         env_vars: vec![],
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
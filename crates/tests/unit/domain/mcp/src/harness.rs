@@ -256,6 +256,8 @@ pub fn internal_mcp_config(name: &str, port: u16) -> systemprompt_models::mcp::M
         remote_endpoint: String::new(),
         external_auth: None,
         headers: std::collections::HashMap::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -38,6 +38,8 @@ fn claims_with(roles: Vec<String>, attributes: BTreeMap<String, serde_json::Valu
         rate_limit_tier: Some(RateLimitTier::User),
         plugin_id: None,
         act: None,
+        cnf: None,
+        parent_jti: None,
     }
 }
 
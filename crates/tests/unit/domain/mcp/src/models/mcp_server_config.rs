@@ -38,6 +38,8 @@ fn create_test_config() -> McpServerConfig {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: HashMap::new(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
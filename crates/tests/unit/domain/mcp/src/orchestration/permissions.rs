@@ -29,6 +29,8 @@ fn deployment(required: bool, scopes: Vec<Permission>) -> Deployment {
         env_vars: vec![],
         external_auth: None,
         headers: HashMap::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -44,6 +44,8 @@ fn external_config(external_auth: bool) -> McpServerConfig {
             scheme: "Bearer".to_string(),
         }),
         headers: HashMap::new(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
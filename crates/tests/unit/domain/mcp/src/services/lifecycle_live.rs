@@ -85,6 +85,8 @@ fn make_config(name: &str, port: u16) -> McpServerConfig {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
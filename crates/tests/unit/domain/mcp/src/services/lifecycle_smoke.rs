@@ -74,6 +74,8 @@ async fn make_orchestrator() -> Option<(LifecycleOrchestrator, McpServerConfig)>
         remote_endpoint: String::new(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     };
 
     Some((lifecycle, config))
@@ -27,6 +27,7 @@ mod orchestrator_start_live;
 mod process;
 mod process_monitor_live;
 mod process_pid_live;
+mod process_sandbox;
 mod process_spawn_live;
 mod process_spawner;
 mod proxy_health;
@@ -44,6 +45,7 @@ mod schema_validator;
 mod schema_validator_scripted;
 mod spawn_env;
 mod startup_delay;
+mod stdio_bridge;
 mod tool_context;
 mod tool_conversions;
 mod tool_provider;
@@ -40,6 +40,8 @@ fn make_config(name: &str) -> McpServerConfig {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -55,6 +55,8 @@ fn make_config(
         remote_endpoint: endpoint.to_owned(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -43,6 +43,8 @@ fn config(name: &str, oauth_required: bool) -> McpServerConfig {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -13,6 +13,7 @@ fn test_health_check_details_new() {
         validation_type: "mcp_validated".to_string(),
         error_message: None,
         server_version: Some("1.0.0".to_string()),
+        sandbox: None,
     };
 
     assert_eq!(details.service_name, "test-service");
@@ -32,6 +33,7 @@ fn test_health_check_details_with_error() {
         validation_type: "connection_failed".to_string(),
         error_message: Some("Connection refused".to_string()),
         server_version: None,
+        sandbox: None,
     };
 
     assert_eq!(details.service_name, "failing-service");
@@ -54,6 +56,7 @@ fn test_health_check_details_auth_required() {
         validation_type: "auth_required".to_string(),
         error_message: None,
         server_version: Some("2.0.0".to_string()),
+        sandbox: None,
     };
 
     assert!(details.requires_auth);
@@ -69,6 +72,7 @@ fn test_health_check_details_clone() {
         validation_type: "success".to_string(),
         error_message: None,
         server_version: Some("1.0.0".to_string()),
+        sandbox: None,
     };
 
     let cloned = details.clone();
@@ -86,6 +90,7 @@ fn test_health_check_details_debug() {
         validation_type: "success".to_string(),
         error_message: None,
         server_version: None,
+        sandbox: None,
     };
 
     let debug_str = format!("{:?}", details);
@@ -102,6 +107,7 @@ fn test_health_check_result_healthy() {
         validation_type: "mcp_validated".to_string(),
         error_message: None,
         server_version: Some("1.0.0".to_string()),
+        sandbox: None,
     };
 
     let result = HealthCheckResult {
@@ -125,6 +131,7 @@ fn test_health_check_result_degraded() {
         validation_type: "mcp_validated".to_string(),
         error_message: None,
         server_version: Some("1.0.0".to_string()),
+        sandbox: None,
     };
 
     let result = HealthCheckResult {
@@ -147,6 +154,7 @@ fn test_health_check_result_unhealthy() {
         validation_type: "connection_failed".to_string(),
         error_message: Some("Connection refused".to_string()),
         server_version: None,
+        sandbox: None,
     };
 
     let result = HealthCheckResult {
@@ -169,6 +177,7 @@ fn test_health_check_result_unknown() {
         validation_type: "error".to_string(),
         error_message: Some("Unknown error".to_string()),
         server_version: None,
+        sandbox: None,
     };
 
     let result = HealthCheckResult {
@@ -190,6 +199,7 @@ fn test_health_check_result_clone() {
         validation_type: "auth_required".to_string(),
         error_message: None,
         server_version: Some("1.5.0".to_string()),
+        sandbox: None,
     };
 
     let result = HealthCheckResult {
@@ -214,6 +224,7 @@ fn test_health_check_result_debug() {
         validation_type: "success".to_string(),
         error_message: None,
         server_version: None,
+        sandbox: None,
     };
 
     let result = HealthCheckResult {
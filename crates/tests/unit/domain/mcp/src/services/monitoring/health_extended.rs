@@ -42,6 +42,8 @@ fn config(name: &str) -> McpServerConfig {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -171,6 +173,7 @@ fn health_check_details_clone_and_debug() {
         validation_type: "mcp_validated".to_owned(),
         error_message: None,
         server_version: Some("1.0".to_owned()),
+        sandbox: None,
     };
     let cloned = d.clone();
     assert_eq!(cloned.service_name, d.service_name);
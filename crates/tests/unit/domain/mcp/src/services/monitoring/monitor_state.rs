@@ -9,6 +9,7 @@ use systemprompt_mcp::services::monitoring::health::{
 use systemprompt_mcp::services::monitoring::health_monitor::{
     HealthMonitorState, monitor_health_continuously,
 };
+use systemprompt_mcp::services::process::sandbox::SandboxEventCounts;
 
 use crate::harness::external_mcp_config;
 
@@ -24,6 +25,7 @@ fn result_with(status: HealthStatus, error: Option<&str>) -> HealthCheckResult {
             validation_type: "test".to_owned(),
             error_message: error.map(ToOwned::to_owned),
             server_version: None,
+            sandbox: None,
         },
     }
 }
@@ -76,6 +78,35 @@ fn degraded_after_healthy_keeps_failure_count() {
     assert_eq!(state.previous_status(), Some(HealthStatus::Unknown));
 }
 
+fn result_with_sandbox(oom_kills: u64) -> HealthCheckResult {
+    let mut result = result_with(HealthStatus::Healthy, None);
+    result.details.sandbox = Some(SandboxEventCounts {
+        oom_kills,
+        ..SandboxEventCounts::ZERO
+    });
+    result
+}
+
+#[test]
+fn sandbox_events_advance_the_seen_baseline() {
+    let config = external_mcp_config("mon", "http://127.0.0.1:1/mcp");
+    let mut state = HealthMonitorState::new();
+    assert_eq!(state.sandbox_seen(), SandboxEventCounts::ZERO);
+
+    state.observe(&config, &result_with_sandbox(2));
+    assert_eq!(state.sandbox_seen().oom_kills, 2);
+
+    state.observe(&config, &result_with(HealthStatus::Healthy, None));
+    assert_eq!(
+        state.sandbox_seen().oom_kills,
+        2,
+        "a result without sandbox data keeps the baseline"
+    );
+
+    state.observe(&config, &result_with_sandbox(3));
+    assert_eq!(state.sandbox_seen().oom_kills, 3);
+}
+
 #[tokio::test]
 async fn continuous_monitor_polls_until_cancelled() {
     let config = external_mcp_config("mon-loop", "http://127.0.0.1:1/mcp");
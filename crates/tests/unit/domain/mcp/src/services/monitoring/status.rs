@@ -238,6 +238,8 @@ async fn test_get_all_service_status_unreachable() {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     };
     let map = get_all_service_status(&[config]).await.unwrap();
     assert_eq!(map.len(), 1);
@@ -47,6 +47,8 @@ fn external_config(name: &str, endpoint: &str) -> McpServerConfig {
         remote_endpoint: endpoint.to_owned(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -68,6 +68,7 @@ fn health_check_details_construction() {
         validation_type: "full".to_string(),
         error_message: None,
         server_version: Some("1.0.0".to_string()),
+        sandbox: None,
     };
     assert_eq!(details.service_name, "my-service");
     assert_eq!(details.tools_available, Some(5));
@@ -85,6 +86,7 @@ fn health_check_details_with_error() {
         validation_type: "port_unavailable".to_string(),
         error_message: Some("Connection refused".to_string()),
         server_version: None,
+        sandbox: None,
     };
     assert_eq!(details.error_message.as_deref(), Some("Connection refused"));
     assert!(details.server_version.is_none());
@@ -99,6 +101,7 @@ fn health_check_details_clone() {
         validation_type: "test".to_string(),
         error_message: None,
         server_version: None,
+        sandbox: None,
     };
     let cloned = details.clone();
     assert_eq!(cloned.service_name, "clone-test");
@@ -347,5 +350,7 @@ fn test_mcp_config(name: &str) -> systemprompt_models::mcp::McpServerConfig {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
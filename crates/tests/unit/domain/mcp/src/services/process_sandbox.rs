@@ -0,0 +1,164 @@
+//! Tests for the parent-side pieces of `services::process::sandbox`: cgroup
+//! limit values, event-counter parsing and deltas, and reading a server's
+//! counters back from its cgroup directory.
+
+use std::fs;
+
+use systemprompt_mcp::services::process::sandbox::{
+    SandboxEventCounts, cgroup_dir, limit_values, prepare, read_events,
+};
+use systemprompt_models::mcp::{ResourceLimits, SandboxConfig};
+
+use crate::harness::internal_mcp_config;
+
+const MEMORY_EVENTS: &str = "low 0\nhigh 0\nmax 7\noom 2\noom_kill 1\noom_group_kill 0\n";
+const PIDS_EVENTS: &str = "max 3\n";
+const CPU_STAT: &str = "usage_usec 912\nnr_periods 40\nnr_throttled 12\nthrottled_usec 55\n";
+
+fn limits(parent: &str) -> ResourceLimits {
+    ResourceLimits {
+        memory_max_mb: Some(256),
+        cpu_max_percent: Some(150),
+        pids_max: Some(64),
+        cgroup_parent: parent.to_owned(),
+    }
+}
+
+#[test]
+fn limit_values_use_cgroup_v2_units() {
+    let values = limit_values(&limits("/sys/fs/cgroup/sp"));
+
+    assert_eq!(
+        values,
+        vec![
+            ("memory.max", "268435456".to_owned()),
+            ("cpu.max", "150000 100000".to_owned()),
+            ("pids.max", "64".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn limit_values_skip_unset_caps() {
+    let only_pids = ResourceLimits {
+        pids_max: Some(16),
+        ..ResourceLimits::default()
+    };
+
+    assert_eq!(
+        limit_values(&only_pids),
+        vec![("pids.max", "16".to_owned())]
+    );
+}
+
+#[test]
+fn each_server_gets_its_own_cgroup_under_the_parent() {
+    let dir = cgroup_dir(&limits("/sys/fs/cgroup/sp"), "github");
+
+    assert_eq!(dir.to_str(), Some("/sys/fs/cgroup/sp/github"));
+}
+
+#[test]
+fn counters_parse_from_cgroup_flat_keyed_files() {
+    let counts = SandboxEventCounts::parse(MEMORY_EVENTS, PIDS_EVENTS, CPU_STAT);
+
+    assert_eq!(
+        counts,
+        SandboxEventCounts {
+            oom_kills: 1,
+            memory_max_hits: 7,
+            pids_max_hits: 3,
+            cpu_throttled_periods: 12,
+        }
+    );
+    assert!(counts.has_violations());
+}
+
+#[test]
+fn missing_or_malformed_counters_read_as_zero() {
+    let counts = SandboxEventCounts::parse("", "max garbage\n", "nr_throttled\n");
+
+    assert_eq!(counts, SandboxEventCounts::ZERO);
+    assert!(!counts.has_violations());
+}
+
+#[test]
+fn throttling_alone_is_not_a_violation() {
+    let counts = SandboxEventCounts {
+        cpu_throttled_periods: 90,
+        ..SandboxEventCounts::ZERO
+    };
+
+    assert!(!counts.has_violations());
+}
+
+#[test]
+fn since_reports_only_new_events() {
+    let earlier = SandboxEventCounts::parse(MEMORY_EVENTS, PIDS_EVENTS, CPU_STAT);
+    let later = SandboxEventCounts {
+        oom_kills: 2,
+        ..earlier
+    };
+
+    let new_events = later.since(&earlier);
+
+    assert_eq!(
+        new_events,
+        SandboxEventCounts {
+            oom_kills: 1,
+            ..SandboxEventCounts::ZERO
+        }
+    );
+    assert_eq!(
+        earlier.since(&later),
+        SandboxEventCounts::ZERO,
+        "a reset cgroup never reports negative events"
+    );
+}
+
+#[test]
+fn read_events_reads_the_server_cgroup() {
+    let root = tempfile::tempdir().unwrap();
+    let parent = root.path().to_str().unwrap();
+    let cgroup = root.path().join("fixture");
+    fs::create_dir(&cgroup).unwrap();
+    fs::write(cgroup.join("memory.events"), MEMORY_EVENTS).unwrap();
+    fs::write(cgroup.join("pids.events"), PIDS_EVENTS).unwrap();
+    fs::write(cgroup.join("cpu.stat"), CPU_STAT).unwrap();
+
+    let mut config = internal_mcp_config("fixture", 5099);
+    config.sandbox = Some(SandboxConfig {
+        limits: Some(limits(parent)),
+        ..SandboxConfig::default()
+    });
+
+    let counts = read_events(&config).expect("cgroup exists");
+    assert_eq!(counts.oom_kills, 1);
+    assert_eq!(counts.pids_max_hits, 3);
+}
+
+#[test]
+fn read_events_is_none_without_limits_or_cgroup() {
+    let root = tempfile::tempdir().unwrap();
+    let mut config = internal_mcp_config("fixture", 5099);
+    assert!(read_events(&config).is_none());
+
+    config.sandbox = Some(SandboxConfig::default());
+    assert!(read_events(&config).is_none());
+
+    config.sandbox = Some(SandboxConfig {
+        limits: Some(limits(root.path().to_str().unwrap())),
+        ..SandboxConfig::default()
+    });
+    assert!(
+        read_events(&config).is_none(),
+        "the server has not been spawned, so it has no cgroup yet"
+    );
+}
+
+#[test]
+fn prepare_without_a_sandbox_is_a_no_op() {
+    let config = internal_mcp_config("fixture", 5099);
+
+    assert!(prepare(&config, None).unwrap().is_none());
+}
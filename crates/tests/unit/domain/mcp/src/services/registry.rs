@@ -37,6 +37,8 @@ fn make_internal_server(name: &str, port: u16) -> McpServerConfig {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -71,6 +73,8 @@ fn make_external_server(name: &str, endpoint: &str) -> McpServerConfig {
         remote_endpoint: endpoint.to_string(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -37,6 +37,8 @@ fn make_internal(name: &str, port: u16) -> McpServerConfig {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -71,6 +73,8 @@ fn make_external(name: &str, endpoint: &str) -> McpServerConfig {
         remote_endpoint: endpoint.to_owned(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -1,14 +1,14 @@
 //! Pre-flight validation of the MCP server registry. `validate_registry` is
 //! the only public entry point and nothing calls it from a test, so none of its
 //! four check suites — port conflicts, per-server fields, OAuth coherence, and
-//! internal-vs-external constraints — has ever run.
+//! internal/external/stdio constraints — has ever run.
 
 use std::path::PathBuf;
 
 use systemprompt_mcp::services::registry::validator::validate_registry;
 use systemprompt_models::auth::{JwtAudience, Permission};
 use systemprompt_models::mcp::RegistryConfig;
-use systemprompt_models::mcp::deployment::{McpServerType, OAuthRequirement};
+use systemprompt_models::mcp::deployment::{McpServerType, OAuthRequirement, StdioCommand};
 use systemprompt_models::mcp::server::McpServerConfig;
 use systemprompt_test_fixtures::fixture_user_id;
 
@@ -45,6 +45,8 @@ fn internal_server(name: &str, port: u16) -> McpServerConfig {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: Default::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -56,6 +58,19 @@ fn external_server(name: &str, endpoint: &str) -> McpServerConfig {
     server
 }
 
+fn stdio_server(name: &str, port: u16) -> McpServerConfig {
+    let mut server = internal_server(name, port);
+    server.server_type = McpServerType::Stdio;
+    server.binary = String::new();
+    server.crate_path = PathBuf::new();
+    server.stdio = Some(StdioCommand {
+        command: "npx".to_owned(),
+        args: vec!["-y".to_owned(), format!("{name}-mcp")],
+        working_dir: None,
+    });
+    server
+}
+
 fn registry(servers: Vec<McpServerConfig>) -> RegistryConfig {
     RegistryConfig {
         servers,
@@ -213,3 +228,46 @@ fn disabled_servers_are_exempt_from_every_field_check() {
     validate_registry(&registry(vec![broken]))
         .expect("a disabled server is never brought up, so it is never validated");
 }
+
+#[test]
+fn a_stdio_server_with_a_command_passes_every_check() {
+    validate_registry(&registry(vec![stdio_server("github", 5150)]))
+        .expect("a stdio server needs no crate path or binary");
+}
+
+#[test]
+fn a_stdio_server_contends_for_its_bridge_port() {
+    let config = registry(vec![
+        internal_server("alpha", 5150),
+        stdio_server("github", 5150),
+    ]);
+
+    let err = validate_registry(&config).expect_err("the bridge binds the server's port");
+    assert!(err.to_string().contains("github:5150"), "got: {err}");
+}
+
+#[test]
+fn a_stdio_server_without_a_command_is_rejected() {
+    let mut server = stdio_server("github", 5150);
+    server.stdio = None;
+
+    let err = validate_registry(&registry(vec![server]))
+        .expect_err("a stdio server with nothing to spawn cannot run");
+    assert!(
+        err.to_string().contains("stdio server has no command"),
+        "got: {err}"
+    );
+}
+
+#[test]
+fn a_stdio_server_carrying_a_binary_is_rejected() {
+    let mut server = stdio_server("github", 5150);
+    server.binary = "github-bin".to_owned();
+
+    let err = validate_registry(&registry(vec![server]))
+        .expect_err("the bridge, not a binary, runs a stdio server");
+    assert!(
+        err.to_string().contains("should not have a binary"),
+        "got: {err}"
+    );
+}
@@ -1,5 +1,6 @@
 //! Tests for the pure spawn-environment assembly and log-file helpers in
-//! `services::process::spawner`.
+//! `services::process::spawner`, and the environment a stdio server's
+//! command receives from `services::stdio_bridge`.
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -8,6 +9,7 @@ use systemprompt_mcp::services::process::spawner::{
     SpawnEnvSpec, build_environment, open_server_log, rotate_log_if_needed,
     serialize_server_configs,
 };
+use systemprompt_mcp::services::stdio_bridge::stdio_environment;
 use systemprompt_models::AppPaths;
 use systemprompt_models::auth::JwtAudience;
 use systemprompt_models::mcp::deployment::{McpServerType, OAuthRequirement};
@@ -46,6 +48,8 @@ fn make_config(name: &str, env_vars: Vec<String>) -> McpServerConfig {
         remote_endpoint: String::new(),
         external_auth: None,
         headers: HashMap::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -182,3 +186,31 @@ fn open_server_log_creates_log_directory_and_file() {
 
     open_server_log(&paths, &config).expect("log file should open");
 }
+
+#[test]
+fn stdio_environment_carries_only_path_home_and_declared_vars() {
+    let config = make_config(
+        "svc-stdio",
+        vec!["GITHUB_TOKEN".to_owned(), "MISSING_VAR".to_owned()],
+    );
+    let inherited: HashMap<&str, &str> = [
+        ("PATH", "/usr/bin"),
+        ("HOME", "/home/tester"),
+        ("GITHUB_TOKEN", "ghp_test"),
+        ("DATABASE_URL", "postgres://secret"),
+        ("SYSTEMPROMPT_PROFILE", "/profiles/local"),
+    ]
+    .into_iter()
+    .collect();
+
+    let env = env_map(stdio_environment(&config, |name| {
+        inherited.get(name).map(|v| (*v).to_owned())
+    }));
+
+    assert_eq!(env.len(), 3, "got: {env:?}");
+    assert_eq!(env.get("PATH").unwrap(), "/usr/bin");
+    assert_eq!(env.get("HOME").unwrap(), "/home/tester");
+    assert_eq!(env.get("GITHUB_TOKEN").unwrap(), "ghp_test");
+    assert!(!env.contains_key("DATABASE_URL"));
+    assert!(!env.contains_key("SYSTEMPROMPT_PROFILE"));
+}
@@ -0,0 +1,71 @@
+//! The stdio bridge's MCP handshake with the spawned command. An in-process
+//! server stands in for the stdio child on one end of a duplex pipe; the
+//! bridge must adopt its identity and capabilities, minus the surfaces it does
+//! not relay, and must fail cleanly when the command never answers.
+
+use std::sync::Arc;
+
+use rmcp::model::{Implementation, ServerCapabilities, ServerInfo};
+use rmcp::{ServerHandler, ServiceExt};
+use systemprompt_mcp::McpDomainError;
+use systemprompt_mcp::services::stdio_bridge::StdioBridge;
+use systemprompt_security::authz::{AllowAllHook, NullAuditSink, SharedAuthzHook};
+
+#[derive(Clone, Debug)]
+struct ChildServer;
+
+impl ServerHandler for ChildServer {
+    fn get_info(&self) -> ServerInfo {
+        let mut capabilities = ServerCapabilities::builder().enable_tools().build();
+        capabilities.logging = Some(serde_json::Map::new());
+        ServerInfo::new(capabilities)
+            .with_server_info(Implementation::new("github-stdio", "1.2.3"))
+            .with_instructions("use the github tools")
+    }
+}
+
+fn hook() -> SharedAuthzHook {
+    Arc::new(AllowAllHook::new(Arc::new(NullAuditSink)))
+}
+
+#[tokio::test]
+async fn bridge_adopts_the_child_identity_without_unrelayed_capabilities() {
+    let (bridge_io, child_io) = tokio::io::duplex(64 * 1024);
+    let child = tokio::spawn(async move {
+        let running = ChildServer.serve(child_io).await.expect("child serves");
+        let _ = running.waiting().await;
+    });
+
+    let bridge = StdioBridge::connect("github", tokio::io::split(bridge_io), hook())
+        .await
+        .expect("handshake with the child");
+    let info = bridge.get_info();
+
+    assert_eq!(bridge.server_name(), "github");
+    assert_eq!(info.server_info.name, "github-stdio");
+    assert_eq!(info.server_info.version, "1.2.3");
+    assert_eq!(info.instructions.as_deref(), Some("use the github tools"));
+    assert!(info.capabilities.tools.is_some());
+    assert!(
+        info.capabilities.logging.is_none(),
+        "log notifications are not relayed, so logging is not advertised"
+    );
+
+    drop(bridge);
+    child.abort();
+}
+
+#[tokio::test]
+async fn bridge_reports_a_child_that_closes_before_the_handshake() {
+    let (bridge_io, child_io) = tokio::io::duplex(64 * 1024);
+    drop(child_io);
+
+    let err = StdioBridge::connect("silent", tokio::io::split(bridge_io), hook())
+        .await
+        .expect_err("nothing answered the initialize request");
+
+    assert!(
+        matches!(err, McpDomainError::ConnectionFailed { ref server, .. } if server == "silent"),
+        "got: {err}"
+    );
+}
@@ -15,6 +15,7 @@ fn to_tool_definition_maps_all_fields() {
         output_schema: Some(serde_json::json!({"type": "string"})),
         service_id: McpServerId::new("tasks"),
         terminal_on_success: true,
+        requires_approval: false,
         model_config: None,
     };
 
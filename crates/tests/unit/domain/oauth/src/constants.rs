@@ -3,7 +3,7 @@
 //! Every public constant module has at least one test asserting the stored
 //! value so accidental config-drift fails loudly in CI.
 
-use systemprompt_oauth::constants::{pkce, token, validation, webauthn};
+use systemprompt_oauth::constants::{device, pkce, token, validation, webauthn};
 
 #[test]
 fn pkce_challenge_min_length_is_43() {
@@ -70,3 +70,33 @@ fn validation_diversity_threshold_is_half() {
 fn validation_min_unique_chars_is_20() {
     assert_eq!(validation::MIN_UNIQUE_CHARS, 20);
 }
+
+#[test]
+fn device_code_expiry_is_600_seconds() {
+    assert_eq!(device::DEVICE_CODE_EXPIRY_SECONDS, 600);
+}
+
+#[test]
+fn device_poll_interval_is_5_seconds() {
+    assert_eq!(device::POLL_INTERVAL_SECONDS, 5);
+}
+
+#[test]
+fn device_slow_down_adds_5_seconds() {
+    assert_eq!(device::SLOW_DOWN_INCREMENT_SECONDS, 5);
+}
+
+#[test]
+fn device_user_code_alphabet_has_no_vowels() {
+    assert_eq!(device::USER_CODE_ALPHABET.len(), 20);
+    assert!(
+        device::USER_CODE_ALPHABET
+            .iter()
+            .all(|b| b.is_ascii_uppercase() && !b"AEIOUY".contains(b))
+    );
+}
+
+#[test]
+fn device_user_code_length_is_8() {
+    assert_eq!(device::USER_CODE_LENGTH, 8);
+}
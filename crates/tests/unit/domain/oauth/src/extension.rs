@@ -25,6 +25,8 @@ fn schemas_cover_client_token_webauthn_and_bridge_tables() {
         "oauth_client_scopes",
         "webauthn_credentials",
         "webauthn_setup_tokens",
+        "oauth_device_codes",
+        "oauth_federated_logins",
     ] {
         assert!(names.contains(&expected), "missing schema {expected}");
     }
@@ -36,6 +36,13 @@ fn test_grant_type_from_str_client_credentials() {
     assert_eq!(grant_type, GrantType::ClientCredentials);
 }
 
+#[test]
+fn test_grant_type_device_code_round_trips_its_urn() {
+    let urn = "urn:ietf:params:oauth:grant-type:device_code";
+    assert_eq!(GrantType::DeviceCode.as_str(), urn);
+    assert_eq!(GrantType::from_str(urn).unwrap(), GrantType::DeviceCode);
+}
+
 #[test]
 fn test_grant_type_from_str_invalid() {
     let result = GrantType::from_str("invalid_grant");
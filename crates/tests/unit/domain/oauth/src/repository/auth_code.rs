@@ -307,6 +307,7 @@ async fn replayed_code_with_linked_refresh_token_revokes_the_family() {
             scope: "openid",
             expires_at: (chrono::Utc::now() + chrono::Duration::hours(1)).timestamp(),
             family_id: None,
+            dpop_jkt: None,
         })
         .await
         .expect("store refresh token");
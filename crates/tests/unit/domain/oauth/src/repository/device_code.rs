@@ -0,0 +1,328 @@
+// DB-backed RFC 8628 device-code persistence: pending lookup by user code,
+// verification and the consent decision, the poll state machine, and single
+// redemption.
+
+use chrono::{Duration, Utc};
+use systemprompt_database::DbPool;
+use systemprompt_identifiers::{ClientId, UserId};
+use systemprompt_oauth::repository::{DeviceCodeParams, DeviceCodePoll, OAuthRepository};
+use systemprompt_oauth::services::{generate_secure_token, generate_user_code};
+use systemprompt_test_fixtures::{
+    ensure_test_bootstrap, fixture_database_url, fixture_db_pool, seed_oauth_client, seed_user_row,
+    unique_user_id,
+};
+
+struct Ctx {
+    pool: DbPool,
+    repo: OAuthRepository,
+    client_id: ClientId,
+    user_id: UserId,
+}
+
+struct Issued {
+    device_code: String,
+    user_code: String,
+}
+
+async fn setup() -> Option<Ctx> {
+    let url = fixture_database_url().ok()?;
+    ensure_test_bootstrap();
+    let pool = fixture_db_pool(&url).await.expect("pool");
+    let repo = OAuthRepository::new(&pool).expect("repo");
+    let user_id = unique_user_id("dc");
+    seed_user_row(&pool, &user_id, &format!("{}@dc.invalid", user_id.as_str()))
+        .await
+        .expect("seed user");
+    let client_id = seed_oauth_client(&pool, &user_id)
+        .await
+        .expect("seed client")
+        .client_id;
+    Some(Ctx {
+        pool,
+        repo,
+        client_id,
+        user_id,
+    })
+}
+
+async fn issue(ctx: &Ctx, expires_in: Duration, interval_seconds: i32) -> Issued {
+    let device_code = generate_secure_token("device_code");
+    let user_code = generate_user_code();
+    let stored = ctx
+        .repo
+        .store_device_code(DeviceCodeParams {
+            device_code: &device_code,
+            user_code: &user_code,
+            client_id: &ctx.client_id,
+            scope: "user",
+            resource: Some("https://api.invalid"),
+            interval_seconds,
+            expires_at: Utc::now() + expires_in,
+        })
+        .await
+        .expect("store");
+    assert!(stored, "fresh user code must store");
+    Issued {
+        device_code,
+        user_code,
+    }
+}
+
+async fn verify(ctx: &Ctx, issued: &Issued) -> String {
+    let consent_token = generate_secure_token("device_consent");
+    assert!(
+        ctx.repo
+            .verify_device_code(&issued.user_code, &ctx.user_id, &consent_token)
+            .await
+            .expect("verify"),
+        "a pending code verifies"
+    );
+    consent_token
+}
+
+async fn approve(ctx: &Ctx, issued: &Issued) {
+    let consent_token = verify(ctx, issued).await;
+    ctx.repo
+        .approve_device_code(&issued.user_code, &consent_token)
+        .await
+        .expect("approve")
+        .expect("verified code approves");
+}
+
+#[tokio::test]
+async fn a_stored_code_is_pending_until_approved() {
+    let Some(ctx) = setup().await else { return };
+    let issued = issue(&ctx, Duration::minutes(10), 5).await;
+
+    let pending = ctx
+        .repo
+        .find_pending_device_code(&issued.user_code)
+        .await
+        .expect("find")
+        .expect("pending");
+    assert_eq!(pending.client_id, ctx.client_id);
+    assert_eq!(pending.scope, "user");
+    assert_eq!(pending.resource.as_deref(), Some("https://api.invalid"));
+
+    let consent_token = verify(&ctx, &issued).await;
+    assert!(
+        ctx.repo
+            .find_pending_device_code(&issued.user_code)
+            .await
+            .expect("find")
+            .is_some(),
+        "signing in alone approves nothing"
+    );
+
+    let decided = ctx
+        .repo
+        .approve_device_code(&issued.user_code, &consent_token)
+        .await
+        .expect("approve")
+        .expect("approved");
+    assert_eq!(decided.client_id, ctx.client_id);
+    assert_eq!(decided.user_id, ctx.user_id);
+    assert!(
+        ctx.repo
+            .find_pending_device_code(&issued.user_code)
+            .await
+            .expect("find")
+            .is_none(),
+        "an approved code is no longer pending"
+    );
+    assert!(
+        ctx.repo
+            .approve_device_code(&issued.user_code, &consent_token)
+            .await
+            .expect("approve again")
+            .is_none(),
+        "a code is approved once"
+    );
+}
+
+#[tokio::test]
+async fn approval_requires_the_consent_token_issued_at_sign_in() {
+    let Some(ctx) = setup().await else { return };
+    let issued = issue(&ctx, Duration::minutes(10), 5).await;
+
+    assert!(
+        ctx.repo
+            .approve_device_code(&issued.user_code, "device_consent_unissued")
+            .await
+            .expect("approve")
+            .is_none(),
+        "an unverified code cannot be approved"
+    );
+
+    verify(&ctx, &issued).await;
+    assert!(
+        ctx.repo
+            .approve_device_code(&issued.user_code, "device_consent_forged")
+            .await
+            .expect("approve")
+            .is_none(),
+        "a token other than the issued one decides nothing"
+    );
+}
+
+#[tokio::test]
+async fn a_denied_code_polls_as_denied_and_cannot_be_approved() {
+    let Some(ctx) = setup().await else { return };
+    let issued = issue(&ctx, Duration::minutes(10), 0).await;
+    let consent_token = verify(&ctx, &issued).await;
+
+    let decided = ctx
+        .repo
+        .deny_device_code(&issued.user_code, &consent_token)
+        .await
+        .expect("deny")
+        .expect("denied");
+    assert_eq!(decided.user_id, ctx.user_id);
+    assert!(
+        ctx.repo
+            .find_pending_device_code(&issued.user_code)
+            .await
+            .expect("find")
+            .is_none(),
+        "a denied code is no longer pending"
+    );
+    assert!(
+        ctx.repo
+            .approve_device_code(&issued.user_code, &consent_token)
+            .await
+            .expect("approve")
+            .is_none(),
+        "the spent consent token cannot approve a denied code"
+    );
+
+    let poll = ctx
+        .repo
+        .poll_device_code(&issued.device_code, &ctx.client_id)
+        .await
+        .expect("poll");
+    assert!(matches!(poll, DeviceCodePoll::Denied), "{poll:?}");
+}
+
+#[tokio::test]
+async fn a_duplicate_user_code_is_not_stored() {
+    let Some(ctx) = setup().await else { return };
+    let issued = issue(&ctx, Duration::minutes(10), 5).await;
+    let stored = ctx
+        .repo
+        .store_device_code(DeviceCodeParams {
+            device_code: &generate_secure_token("device_code"),
+            user_code: &issued.user_code,
+            client_id: &ctx.client_id,
+            scope: "user",
+            resource: None,
+            interval_seconds: 5,
+            expires_at: Utc::now() + Duration::minutes(10),
+        })
+        .await
+        .expect("store");
+    assert!(!stored);
+}
+
+#[tokio::test]
+async fn polling_faster_than_the_interval_slows_down() {
+    let Some(ctx) = setup().await else { return };
+    let issued = issue(&ctx, Duration::minutes(10), 5).await;
+
+    let first = ctx
+        .repo
+        .poll_device_code(&issued.device_code, &ctx.client_id)
+        .await
+        .expect("poll");
+    assert!(matches!(first, DeviceCodePoll::Pending), "{first:?}");
+
+    let second = ctx
+        .repo
+        .poll_device_code(&issued.device_code, &ctx.client_id)
+        .await
+        .expect("poll");
+    assert!(matches!(second, DeviceCodePoll::SlowDown), "{second:?}");
+}
+
+#[tokio::test]
+async fn an_approved_code_is_redeemed_exactly_once() {
+    let Some(ctx) = setup().await else { return };
+    let issued = issue(&ctx, Duration::minutes(10), 5).await;
+    approve(&ctx, &issued).await;
+
+    let DeviceCodePoll::Approved(grant) = ctx
+        .repo
+        .poll_device_code(&issued.device_code, &ctx.client_id)
+        .await
+        .expect("poll")
+    else {
+        panic!("approved code must redeem");
+    };
+    assert_eq!(grant.user_id, ctx.user_id);
+    assert_eq!(grant.scope, "user");
+    assert_eq!(grant.resource.as_deref(), Some("https://api.invalid"));
+
+    let replay = ctx
+        .repo
+        .poll_device_code(&issued.device_code, &ctx.client_id)
+        .await
+        .expect("poll");
+    assert!(matches!(replay, DeviceCodePoll::Invalid), "{replay:?}");
+}
+
+#[tokio::test]
+async fn another_clients_poll_is_invalid() {
+    let Some(ctx) = setup().await else { return };
+    let issued = issue(&ctx, Duration::minutes(10), 5).await;
+    let poll = ctx
+        .repo
+        .poll_device_code(
+            &issued.device_code,
+            &ClientId::new("not-the-issuing-client"),
+        )
+        .await
+        .expect("poll");
+    assert!(matches!(poll, DeviceCodePoll::Invalid), "{poll:?}");
+}
+
+#[tokio::test]
+async fn an_expired_code_reports_expired_and_cannot_be_approved() {
+    let Some(ctx) = setup().await else { return };
+    let issued = issue(&ctx, Duration::minutes(10), 5).await;
+    let consent_token = verify(&ctx, &issued).await;
+    let pool = ctx.pool.write_pool_arc().expect("write pool");
+    sqlx::query(
+        "UPDATE oauth_device_codes
+            SET created_at = now() - interval '20 minutes',
+                expires_at = now() - interval '1 second'
+          WHERE user_code = $1",
+    )
+    .bind(&issued.user_code)
+    .execute(pool.as_ref())
+    .await
+    .expect("age code");
+
+    let poll = ctx
+        .repo
+        .poll_device_code(&issued.device_code, &ctx.client_id)
+        .await
+        .expect("poll");
+    assert!(matches!(poll, DeviceCodePoll::Expired), "{poll:?}");
+    assert!(
+        ctx.repo
+            .approve_device_code(&issued.user_code, &consent_token)
+            .await
+            .expect("approve")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn an_unknown_device_code_is_invalid() {
+    let Some(ctx) = setup().await else { return };
+    let poll = ctx
+        .repo
+        .poll_device_code("device_code_unknown", &ctx.client_id)
+        .await
+        .expect("poll");
+    assert!(matches!(poll, DeviceCodePoll::Invalid), "{poll:?}");
+}
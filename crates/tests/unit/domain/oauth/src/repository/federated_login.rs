@@ -0,0 +1,119 @@
+// DB-backed pending upstream sign-ins: the stored authorization request comes
+// back intact, and a `state` is consumed once, for its own provider only.
+
+use chrono::{Duration, Utc};
+use systemprompt_identifiers::ClientId;
+use systemprompt_oauth::repository::{FederatedLoginParams, OAuthRepository, PendingAuthorization};
+use systemprompt_oauth::services::generate_secure_token;
+use systemprompt_test_fixtures::{
+    ensure_test_bootstrap, fixture_database_url, fixture_db_pool, seed_oauth_client, seed_user_row,
+    unique_user_id,
+};
+
+struct Ctx {
+    repo: OAuthRepository,
+    client_id: ClientId,
+}
+
+async fn setup() -> Option<Ctx> {
+    let url = fixture_database_url().ok()?;
+    ensure_test_bootstrap();
+    let pool = fixture_db_pool(&url).await.expect("pool");
+    let repo = OAuthRepository::new(&pool).expect("repo");
+    let user_id = unique_user_id("fl");
+    seed_user_row(&pool, &user_id, &format!("{}@fl.invalid", user_id.as_str()))
+        .await
+        .expect("seed user");
+    let client_id = seed_oauth_client(&pool, &user_id)
+        .await
+        .expect("seed client")
+        .client_id;
+    Some(Ctx { repo, client_id })
+}
+
+fn authorization(client_id: &ClientId) -> PendingAuthorization {
+    PendingAuthorization {
+        client_id: client_id.clone(),
+        redirect_uri: "https://app.invalid/callback".to_string(),
+        scope: "user".to_string(),
+        state: Some("client-state".to_string()),
+        code_challenge: Some("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM".to_string()),
+        code_challenge_method: Some("S256".to_string()),
+        resource: None,
+    }
+}
+
+async fn start(ctx: &Ctx, provider_id: &str) -> String {
+    let state_token = generate_secure_token("federated_state");
+    ctx.repo
+        .store_federated_login(FederatedLoginParams {
+            state_token: &state_token,
+            provider_id,
+            nonce: "nonce-value",
+            code_verifier: "verifier-value",
+            authorization: &authorization(&ctx.client_id),
+            expires_at: Utc::now() + Duration::minutes(10),
+        })
+        .await
+        .expect("store");
+    state_token
+}
+
+#[tokio::test]
+async fn a_state_is_consumed_once_with_its_request() {
+    let Some(ctx) = setup().await else { return };
+    let state_token = start(&ctx, "corp").await;
+
+    let login = ctx
+        .repo
+        .consume_federated_login(&state_token, "corp")
+        .await
+        .expect("consume")
+        .expect("pending login");
+    assert_eq!(login.nonce, "nonce-value");
+    assert_eq!(login.code_verifier, "verifier-value");
+    assert_eq!(login.authorization, authorization(&ctx.client_id));
+
+    assert!(
+        ctx.repo
+            .consume_federated_login(&state_token, "corp")
+            .await
+            .expect("consume again")
+            .is_none(),
+        "a state works once"
+    );
+}
+
+#[tokio::test]
+async fn a_state_is_bound_to_its_provider() {
+    let Some(ctx) = setup().await else { return };
+    let state_token = start(&ctx, "corp").await;
+
+    assert!(
+        ctx.repo
+            .consume_federated_login(&state_token, "other")
+            .await
+            .expect("consume")
+            .is_none()
+    );
+    assert!(
+        ctx.repo
+            .consume_federated_login(&state_token, "corp")
+            .await
+            .expect("consume")
+            .is_some(),
+        "a refused attempt does not burn the state"
+    );
+}
+
+#[tokio::test]
+async fn an_unknown_state_is_none() {
+    let Some(ctx) = setup().await else { return };
+    assert!(
+        ctx.repo
+            .consume_federated_login("never-issued", "corp")
+            .await
+            .expect("consume")
+            .is_none()
+    );
+}
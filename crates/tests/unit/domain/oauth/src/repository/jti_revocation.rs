@@ -150,3 +150,39 @@ fn cache_with_zero_capacity_is_clamped_and_usable() {
     );
     assert!(format!("{cache:?}").contains("JtiRevocationCache"));
 }
+
+#[tokio::test]
+async fn dpop_proof_is_recorded_once() {
+    let Ok(url) = fixture_database_url() else {
+        return;
+    };
+    ensure_test_bootstrap();
+    let pool = fixture_db_pool(&url).await.expect("pool");
+    let repo = OAuthRepository::new(&pool).expect("repo");
+
+    let jkt = format!("jkt-{}", Uuid::new_v4());
+    let proof_jti = format!("proof-{}", Uuid::new_v4());
+    let exp = Utc::now() + Duration::minutes(5);
+
+    assert!(
+        repo.record_dpop_proof(&jkt, &proof_jti, exp)
+            .await
+            .expect("first")
+    );
+    assert!(
+        !repo
+            .record_dpop_proof(&jkt, &proof_jti, exp)
+            .await
+            .expect("replay")
+    );
+    assert!(
+        repo.record_dpop_proof(&format!("{jkt}-other"), &proof_jti, exp)
+            .await
+            .expect("other key"),
+        "proof jti is scoped to the key that signed it"
+    );
+    assert!(
+        !repo.is_jti_revoked(&proof_jti).await.expect("check"),
+        "a recorded proof must not revoke an access token with the same jti"
+    );
+}
@@ -11,7 +11,9 @@ mod cleanup;
 mod client_cleanup;
 mod client_crud;
 mod client_relations;
+mod device_code;
 mod exchange_code;
+mod federated_login;
 mod id_jag_replay;
 mod jti_revocation;
 mod oauth_facade;
@@ -48,6 +48,7 @@ async fn store(ctx: &Ctx, token: &RefreshTokenId, exp: i64) {
             scope: "openid",
             expires_at: exp,
             family_id: None,
+            dpop_jkt: None,
         })
         .await
         .expect("store refresh token");
@@ -141,6 +142,7 @@ async fn consume_then_replay_revokes_family() {
             scope: "openid",
             expires_at: exp,
             family_id: Some(&family),
+            dpop_jkt: None,
         })
         .await
         .expect("store child");
@@ -225,6 +227,7 @@ async fn revoke_refresh_token_family_removes_all() {
             scope: "openid",
             expires_at: exp,
             family_id: Some(&family),
+            dpop_jkt: None,
         })
         .await
         .expect("store b");
@@ -268,3 +271,41 @@ async fn cleanup_expired_refresh_tokens_removes_past() {
         .expect("cleanup");
     assert!(removed >= 1);
 }
+
+#[tokio::test]
+async fn dpop_bound_token_only_redeems_with_the_same_key() {
+    let Some(ctx) = setup().await else { return };
+    let token = RefreshTokenId::new(format!("rt-{}", Uuid::new_v4()));
+    ctx.repo
+        .store_refresh_token(
+            RefreshTokenParams::builder(
+                &token,
+                &ctx.client_id,
+                &ctx.user_id,
+                "openid",
+                future_exp(),
+            )
+            .with_dpop_jkt("jkt-a")
+            .build(),
+        )
+        .await
+        .expect("store bound token");
+
+    for presented in [None, Some("jkt-b")] {
+        assert!(
+            ctx.repo
+                .consume_bound_refresh_token(&token, &ctx.client_id, presented)
+                .await
+                .is_err(),
+            "{presented:?} must not redeem a token bound to jkt-a"
+        );
+    }
+
+    // A failed binding check is not a replay: the token is still redeemable.
+    let consumed = ctx
+        .repo
+        .consume_bound_refresh_token(&token, &ctx.client_id, Some("jkt-a"))
+        .await
+        .expect("redeem with the bound key");
+    assert_eq!(consumed.dpop_jkt.as_deref(), Some("jkt-a"));
+}
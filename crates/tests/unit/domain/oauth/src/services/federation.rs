@@ -0,0 +1,675 @@
+//! Tests for upstream `OpenID` Connect sign-in helpers: claim-to-role mapping
+//! and reconciliation, dotted claim lookup, the `allowed_domains` gate, the
+//! upstream authorization URL, and ID-token validation. RS256 tokens are
+//! signed with a fixture key whose JWKS a `wiremock` mock `IdP` serves, and
+//! the same `IdP` drives the callback steps end to end: `state` consumption,
+//! code exchange, ID-token validation, and account linking with role mapping.
+
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use serde_json::{Map, Value, json};
+use systemprompt_models::profile::OidcProvider;
+use systemprompt_oauth::OauthError;
+use systemprompt_oauth::services::federation::{
+    UpstreamAuthorizationRequest, UpstreamIdentity, UpstreamPkce, claim_values,
+    ensure_allowed_domain, map_upstream_roles, reconcile_roles, upstream_authorization_url,
+    validate_upstream_id_token,
+};
+use systemprompt_security::keys::{JwksClient, RsaSigningKey};
+use systemprompt_test_fixtures::next_test_key;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn provider(extra: Value) -> OidcProvider {
+    let mut value = json!({
+        "id": "corp",
+        "kind": "keycloak",
+        "issuer": "https://sso.example.com/realms/corp",
+        "client_id": "systemprompt",
+    });
+    if let (Some(base), Some(extra)) = (value.as_object_mut(), extra.as_object()) {
+        base.extend(extra.clone());
+    }
+    serde_json::from_value(value).expect("provider parses")
+}
+
+fn mapped_provider() -> OidcProvider {
+    provider(json!({
+        "role_mappings": {
+            "platform-admins": ["admin"],
+            "staff": ["user", "analyst"],
+            "contractors": ["user"],
+        }
+    }))
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| (*v).to_owned()).collect()
+}
+
+fn claims(value: Value) -> Map<String, Value> {
+    value.as_object().cloned().expect("object")
+}
+
+/// A local `IdP` publishing one fixture signing key at `/jwks`.
+struct MockIdp {
+    server: MockServer,
+    key: RsaSigningKey,
+}
+
+impl MockIdp {
+    async fn start() -> Self {
+        let server = MockServer::start().await;
+        let key = next_test_key();
+        Mock::given(method("GET"))
+            .and(path("/jwks"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(key.jwks()))
+            .mount(&server)
+            .await;
+        Self { server, key }
+    }
+
+    fn issuer(&self) -> String {
+        self.server.uri()
+    }
+
+    fn provider(&self, extra: Value) -> OidcProvider {
+        let base = self.issuer();
+        let mut value = json!({
+            "id": "mock-idp",
+            "kind": "mock",
+            "issuer": base,
+            "authorization_endpoint": format!("{base}/authorize"),
+            "token_endpoint": format!("{base}/token"),
+            "jwks_uri": format!("{base}/jwks"),
+        });
+        if let (Some(base), Some(extra)) = (value.as_object_mut(), extra.as_object()) {
+            base.extend(extra.clone());
+        }
+        provider(value)
+    }
+
+    fn jwks() -> JwksClient {
+        JwksClient::new(vec!["127.0.0.1".to_owned()])
+    }
+
+    fn id_claims(&self, nonce: &str) -> Map<String, Value> {
+        let now = Utc::now().timestamp();
+        claims(json!({
+            "iss": self.issuer(),
+            "aud": "systemprompt",
+            "sub": "upstream-subject",
+            "iat": now,
+            "exp": now + 300,
+            "nonce": nonce,
+            "email": "ada@example.com",
+            "email_verified": true,
+            "name": "Ada Lovelace",
+            "roles": ["staff"],
+        }))
+    }
+
+    fn sign(&self, claims: &Map<String, Value>) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(self.key.kid().to_owned());
+        let pem = self.key.to_pkcs8_pem().expect("pem");
+        let encoding = EncodingKey::from_rsa_pem(pem.as_bytes()).expect("encoding key");
+        encode(&header, claims, &encoding).expect("sign id token")
+    }
+}
+
+mod roles {
+    use super::*;
+
+    #[test]
+    fn mapped_values_grant_their_roles_once() {
+        let granted = map_upstream_roles(
+            &mapped_provider(),
+            &strings(&["staff", "contractors", "unmapped"]),
+        );
+        assert_eq!(granted, strings(&["user", "analyst"]));
+    }
+
+    #[test]
+    fn no_mapped_values_grant_nothing() {
+        assert!(map_upstream_roles(&mapped_provider(), &strings(&["guests"])).is_empty());
+    }
+
+    #[test]
+    fn reconcile_replaces_managed_roles_and_keeps_the_rest() {
+        let roles = reconcile_roles(
+            &mapped_provider(),
+            &strings(&["admin", "billing"]),
+            &strings(&["user"]),
+        );
+        assert_eq!(roles, strings(&["billing", "user"]));
+    }
+
+    #[test]
+    fn reconcile_falls_back_to_user() {
+        let roles = reconcile_roles(&mapped_provider(), &strings(&["admin"]), &[]);
+        assert_eq!(roles, strings(&["user"]));
+    }
+}
+
+mod claim_lookup {
+    use super::*;
+
+    #[test]
+    fn dotted_path_reaches_nested_arrays() {
+        let c = claims(json!({ "realm_access": { "roles": ["staff", 7, "admins"] } }));
+        assert_eq!(
+            claim_values(&c, "realm_access.roles"),
+            strings(&["staff", "admins"])
+        );
+    }
+
+    #[test]
+    fn single_string_is_one_value() {
+        let c = claims(json!({ "groups": "staff" }));
+        assert_eq!(claim_values(&c, "groups"), strings(&["staff"]));
+    }
+
+    #[test]
+    fn missing_or_non_string_claims_are_empty() {
+        let c = claims(json!({ "roles": { "nested": true } }));
+        assert!(claim_values(&c, "roles").is_empty());
+        assert!(claim_values(&c, "absent.path").is_empty());
+    }
+}
+
+mod allowed_domains {
+    use super::*;
+
+    fn identity(email: &str, verified: bool, hd: Option<&str>) -> UpstreamIdentity {
+        UpstreamIdentity {
+            issuer: "https://accounts.google.com".to_owned(),
+            subject: "sub-1".to_owned(),
+            email: Some(email.to_owned()),
+            email_verified: verified,
+            hosted_domain: hd.map(str::to_owned),
+            ..UpstreamIdentity::default()
+        }
+    }
+
+    #[test]
+    fn no_allowlist_admits_everyone() {
+        assert!(
+            ensure_allowed_domain(&provider(json!({})), &identity("a@b.c", false, None)).is_ok()
+        );
+    }
+
+    #[test]
+    fn verified_email_domain_matches_case_insensitively() {
+        let p = provider(json!({ "allowed_domains": ["example.com"] }));
+        assert!(ensure_allowed_domain(&p, &identity("ada@Example.COM", true, None)).is_ok());
+    }
+
+    #[test]
+    fn unverified_email_does_not_count() {
+        let p = provider(json!({ "allowed_domains": ["example.com"] }));
+        let result = ensure_allowed_domain(&p, &identity("ada@example.com", false, None));
+        assert!(matches!(result, Err(OauthError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn hosted_domain_claim_is_ignored_outside_google() {
+        let p = provider(json!({ "allowed_domains": ["example.com"] }));
+        let result =
+            ensure_allowed_domain(&p, &identity("ada@other.io", false, Some("example.com")));
+        assert!(matches!(result, Err(OauthError::Unauthorized(_))));
+        assert!(
+            ensure_allowed_domain(&p, &identity("ada@example.com", true, Some("other.io"))).is_ok()
+        );
+    }
+
+    #[test]
+    fn google_workspace_goes_by_the_hosted_domain() {
+        let p = provider(json!({
+            "kind": "google_workspace",
+            "issuer": "https://accounts.google.com",
+            "allowed_domains": ["example.com"],
+        }));
+        assert!(
+            ensure_allowed_domain(&p, &identity("ada@other.io", true, Some("example.com"))).is_ok()
+        );
+        let personal = ensure_allowed_domain(&p, &identity("ada@example.com", true, None));
+        assert!(matches!(personal, Err(OauthError::Unauthorized(_))));
+    }
+}
+
+mod authorization_url {
+    use super::*;
+
+    #[test]
+    fn carries_pkce_nonce_and_state() {
+        let pkce = UpstreamPkce::generate();
+        let url = upstream_authorization_url(
+            &provider(json!({})),
+            UpstreamAuthorizationRequest {
+                redirect_uri: "https://api.invalid/api/v1/core/oauth/federated/corp/callback",
+                state: "state-1",
+                nonce: "nonce-1",
+                code_challenge: &pkce.challenge,
+            },
+        )
+        .expect("url");
+        let parsed = url::Url::parse(&url).expect("absolute");
+        assert_eq!(parsed.path(), "/realms/corp/protocol/openid-connect/auth");
+        let query: Map<String, Value> = parsed
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), Value::String(v.into_owned())))
+            .collect();
+        assert_eq!(query["response_type"], "code");
+        assert_eq!(query["client_id"], "systemprompt");
+        assert_eq!(query["scope"], "openid email profile");
+        assert_eq!(query["state"], "state-1");
+        assert_eq!(query["nonce"], "nonce-1");
+        assert_eq!(query["code_challenge_method"], "S256");
+        assert_eq!(query["code_challenge"], pkce.challenge.as_str());
+    }
+
+    #[test]
+    fn mock_without_endpoint_is_a_config_error() {
+        let result = upstream_authorization_url(
+            &provider(json!({ "kind": "mock", "issuer": "http://127.0.0.1:9000" })),
+            UpstreamAuthorizationRequest {
+                redirect_uri: "https://api.invalid/cb",
+                state: "s",
+                nonce: "n",
+                code_challenge: "c",
+            },
+        );
+        assert!(matches!(result, Err(OauthError::Config(_))));
+    }
+
+    #[test]
+    fn pkce_pairs_are_fresh_and_well_formed() {
+        let first = UpstreamPkce::generate();
+        let second = UpstreamPkce::generate();
+        assert_eq!(first.verifier.len(), 64);
+        assert_eq!(first.challenge.len(), 43);
+        assert_ne!(first.verifier, second.verifier);
+    }
+}
+
+mod id_token {
+    use super::*;
+
+    fn hs256_token(kid: Option<&str>) -> String {
+        let mut header = Header::default();
+        header.kid = kid.map(str::to_owned);
+        encode(
+            &header,
+            &json!({ "sub": "u", "iss": "https://sso.example.com/realms/corp" }),
+            &EncodingKey::from_secret(b"not-an-upstream-key"),
+        )
+        .expect("encode")
+    }
+
+    #[tokio::test]
+    async fn non_rs256_token_is_a_federation_failure() {
+        let jwks = JwksClient::new(Vec::new());
+        let result =
+            validate_upstream_id_token(&provider(json!({})), &jwks, &hs256_token(Some("k1")), "n")
+                .await;
+        assert!(
+            matches!(result, Err(OauthError::Federation(_))),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn malformed_token_is_a_federation_failure() {
+        let jwks = JwksClient::new(Vec::new());
+        let result =
+            validate_upstream_id_token(&provider(json!({})), &jwks, "not-a-jwt", "n").await;
+        assert!(
+            matches!(result, Err(OauthError::Federation(_))),
+            "{result:?}"
+        );
+    }
+}
+
+mod rs256_id_token {
+    use super::*;
+
+    async fn validate(
+        idp: &MockIdp,
+        claims: &Map<String, Value>,
+    ) -> Result<UpstreamIdentity, OauthError> {
+        let provider = idp.provider(json!({}));
+        validate_upstream_id_token(&provider, &MockIdp::jwks(), &idp.sign(claims), "nonce-1").await
+    }
+
+    async fn assert_rejected(edit: impl FnOnce(&mut Map<String, Value>)) {
+        let idp = MockIdp::start().await;
+        let mut claims = idp.id_claims("nonce-1");
+        edit(&mut claims);
+        let result = validate(&idp, &claims).await;
+        assert!(
+            matches!(result, Err(OauthError::Federation(_))),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn valid_token_yields_the_upstream_identity() {
+        let idp = MockIdp::start().await;
+        let identity = validate(&idp, &idp.id_claims("nonce-1"))
+            .await
+            .expect("valid id token");
+        assert_eq!(identity.issuer, idp.issuer());
+        assert_eq!(identity.subject, "upstream-subject");
+        assert_eq!(identity.email.as_deref(), Some("ada@example.com"));
+        assert!(identity.email_verified);
+        assert_eq!(identity.name.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(identity.upstream_roles, strings(&["staff"]));
+    }
+
+    #[tokio::test]
+    async fn wrong_audience_is_rejected() {
+        assert_rejected(|c| {
+            c.insert("aud".to_owned(), json!("another-client"));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn wrong_issuer_is_rejected() {
+        assert_rejected(|c| {
+            c.insert("iss".to_owned(), json!("https://elsewhere.example"));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn wrong_nonce_is_rejected() {
+        assert_rejected(|c| {
+            c.insert("nonce".to_owned(), json!("nonce-2"));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn missing_nonce_is_rejected() {
+        assert_rejected(|c| {
+            c.remove("nonce");
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn expired_token_is_rejected() {
+        let expired = Utc::now().timestamp() - 600;
+        assert_rejected(|c| {
+            c.insert("exp".to_owned(), json!(expired));
+        })
+        .await;
+    }
+}
+
+mod callback {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use http::HeaderMap;
+    use systemprompt_identifiers::{SessionId, UserId};
+    use systemprompt_oauth::OAuthState;
+    use systemprompt_oauth::repository::{
+        FederatedLoginParams, OAuthRepository, PendingAuthorization,
+    };
+    use systemprompt_oauth::services::federation::{
+        UpstreamCodeExchange, exchange_upstream_code, sign_in_federated,
+    };
+    use systemprompt_oauth::services::generate_secure_token;
+    use systemprompt_test_fixtures::{
+        ensure_test_bootstrap, fixture_database_url, fixture_db_pool, seed_oauth_client,
+        seed_user_row, unique_user_id,
+    };
+    use systemprompt_traits::{
+        AnalyticsProvider, AnalyticsResult, AnalyticsSession, AuthResult, AuthUser,
+        CreateSessionInput, ExtractSignals, FederatedIdentityClaims, SessionAnalytics,
+        UserProvider,
+    };
+    use wiremock::matchers::body_string_contains;
+
+    use super::*;
+
+    const REDIRECT_URI: &str = "https://api.invalid/api/v1/core/oauth/federated/mock-idp/callback";
+
+    struct NullAnalytics;
+
+    #[async_trait]
+    impl AnalyticsProvider for NullAnalytics {
+        fn extract_analytics(
+            &self,
+            _headers: &HeaderMap,
+            _signals: ExtractSignals<'_>,
+        ) -> SessionAnalytics {
+            SessionAnalytics::default()
+        }
+        async fn create_session(&self, _input: CreateSessionInput<'_>) -> AnalyticsResult<()> {
+            Ok(())
+        }
+        async fn find_recent_session_by_fingerprint(
+            &self,
+            _fingerprint: &str,
+            _max_age_seconds: i64,
+        ) -> AnalyticsResult<Option<AnalyticsSession>> {
+            Ok(None)
+        }
+        async fn find_session_by_id(
+            &self,
+            _session_id: &SessionId,
+        ) -> AnalyticsResult<Option<AnalyticsSession>> {
+            Ok(None)
+        }
+        async fn find_active_session_by_id(
+            &self,
+            _session_id: &SessionId,
+        ) -> AnalyticsResult<Option<systemprompt_traits::ActiveSession>> {
+            Ok(None)
+        }
+        async fn revoke_session(&self, _session_id: &SessionId) -> AnalyticsResult<()> {
+            Ok(())
+        }
+        async fn revoke_all_sessions_for_user(&self, _user_id: &UserId) -> AnalyticsResult<u64> {
+            Ok(0)
+        }
+        async fn migrate_user_sessions(
+            &self,
+            _from_user_id: &UserId,
+            _to_user_id: &UserId,
+        ) -> AnalyticsResult<u64> {
+            Ok(0)
+        }
+        async fn mark_session_converted(&self, _session_id: &SessionId) -> AnalyticsResult<()> {
+            Ok(())
+        }
+    }
+
+    /// An existing account holding one provider-managed role (`admin`) and
+    /// one granted elsewhere (`billing`); records what the sign-in asks of it.
+    #[derive(Default)]
+    struct RecordingUsers {
+        linked: Mutex<Option<(String, String, FederatedIdentityClaims)>>,
+        assigned: Mutex<Option<Vec<String>>>,
+    }
+
+    const LINKED_USER: &str = "user_federated_linked";
+
+    #[async_trait]
+    impl UserProvider for RecordingUsers {
+        async fn find_by_id(&self, id: &UserId) -> AuthResult<Option<AuthUser>> {
+            Ok(Some(AuthUser {
+                id: id.clone(),
+                name: "ada".to_owned(),
+                email: "ada@example.com".to_owned(),
+                roles: strings(&["admin", "billing"]),
+                is_active: true,
+            }))
+        }
+        async fn find_by_email(&self, _email: &str) -> AuthResult<Option<AuthUser>> {
+            Ok(None)
+        }
+        async fn find_by_name(&self, _name: &str) -> AuthResult<Option<AuthUser>> {
+            Ok(None)
+        }
+        async fn create_user(
+            &self,
+            _name: &str,
+            _email: &str,
+            _full_name: Option<&str>,
+        ) -> AuthResult<AuthUser> {
+            Err(systemprompt_traits::AuthProviderError::Internal(
+                "not used".to_owned(),
+            ))
+        }
+        async fn create_anonymous(&self, _fingerprint: &str) -> AuthResult<AuthUser> {
+            Err(systemprompt_traits::AuthProviderError::Internal(
+                "not used".to_owned(),
+            ))
+        }
+        async fn assign_roles(&self, _user_id: &UserId, roles: &[String]) -> AuthResult<()> {
+            *self.assigned.lock().expect("lock") = Some(roles.to_vec());
+            Ok(())
+        }
+        async fn find_or_create_federated(
+            &self,
+            issuer: &str,
+            external_sub: &str,
+            claims: &FederatedIdentityClaims,
+        ) -> AuthResult<UserId> {
+            *self.linked.lock().expect("lock") =
+                Some((issuer.to_owned(), external_sub.to_owned(), claims.clone()));
+            Ok(UserId::new(LINKED_USER))
+        }
+        async fn promote_anonymous(&self, _source: &UserId, _target: &UserId) -> AuthResult<u64> {
+            Ok(0)
+        }
+    }
+
+    async fn repo_with_authorization() -> Option<(OAuthRepository, PendingAuthorization)> {
+        let url = fixture_database_url().ok()?;
+        ensure_test_bootstrap();
+        let pool = fixture_db_pool(&url).await.expect("pool");
+        let repo = OAuthRepository::new(&pool).expect("repo");
+        let user_id = unique_user_id("fed");
+        seed_user_row(
+            &pool,
+            &user_id,
+            &format!("{}@fed.invalid", user_id.as_str()),
+        )
+        .await
+        .expect("seed user");
+        let client_id = seed_oauth_client(&pool, &user_id)
+            .await
+            .expect("seed client")
+            .client_id;
+        let authorization = PendingAuthorization {
+            client_id,
+            redirect_uri: "https://app.invalid/callback".to_owned(),
+            scope: "user".to_owned(),
+            state: Some("client-state".to_owned()),
+            code_challenge: Some("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM".to_owned()),
+            code_challenge_method: Some("S256".to_owned()),
+            resource: None,
+        };
+        Some((repo, authorization))
+    }
+
+    #[tokio::test]
+    async fn mock_idp_sign_in_links_the_account_and_maps_its_roles() {
+        let Some((repo, authorization)) = repo_with_authorization().await else {
+            return;
+        };
+        let idp = MockIdp::start().await;
+        let provider = idp.provider(json!({
+            "role_mappings": { "staff": ["user", "analyst"], "platform-admins": ["admin"] },
+        }));
+
+        let upstream_state = generate_secure_token("federated_state");
+        let nonce = generate_secure_token("nonce");
+        let pkce = UpstreamPkce::generate();
+        repo.store_federated_login(FederatedLoginParams {
+            state_token: &upstream_state,
+            provider_id: &provider.id,
+            nonce: &nonce,
+            code_verifier: &pkce.verifier,
+            authorization: &authorization,
+            expires_at: Utc::now() + Duration::minutes(10),
+        })
+        .await
+        .expect("store");
+
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("code=upstream-code"))
+            .and(body_string_contains(format!(
+                "code_verifier={}",
+                pkce.verifier
+            )))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "id_token": idp.sign(&idp.id_claims(&nonce)) })),
+            )
+            .expect(1)
+            .mount(&idp.server)
+            .await;
+
+        let login = repo
+            .consume_federated_login(&upstream_state, &provider.id)
+            .await
+            .expect("consume")
+            .expect("pending login");
+        assert_eq!(login.authorization, authorization);
+        assert!(
+            repo.consume_federated_login(&upstream_state, &provider.id)
+                .await
+                .expect("consume again")
+                .is_none(),
+            "the state is single-use"
+        );
+
+        let id_token = exchange_upstream_code(
+            &provider,
+            UpstreamCodeExchange {
+                code: "upstream-code",
+                redirect_uri: REDIRECT_URI,
+                code_verifier: &login.code_verifier,
+            },
+        )
+        .await
+        .expect("code exchange");
+        let identity =
+            validate_upstream_id_token(&provider, &MockIdp::jwks(), &id_token, &login.nonce)
+                .await
+                .expect("id token");
+
+        let users = Arc::new(RecordingUsers::default());
+        let state = OAuthState::new(repo, Arc::new(NullAnalytics), users.clone());
+        let user_id = sign_in_federated(&state, &provider, &identity)
+            .await
+            .expect("sign in");
+        assert_eq!(user_id.as_str(), LINKED_USER);
+
+        let (issuer, subject, claims) = users
+            .linked
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("identity linked");
+        assert_eq!(issuer, idp.issuer());
+        assert_eq!(subject, "upstream-subject");
+        assert_eq!(claims.email.as_deref(), Some("ada@example.com"));
+        assert!(claims.email_verified);
+        assert_eq!(claims.roles, strings(&["user", "analyst"]));
+        assert_eq!(
+            users.assigned.lock().expect("lock").clone(),
+            Some(strings(&["billing", "user", "analyst"])),
+            "the managed admin role is replaced and billing is kept"
+        );
+    }
+}
@@ -8,7 +8,7 @@ use systemprompt_oauth::services::generation::{IdJagGrant, mint_id_jag};
 use systemprompt_oauth::services::validation::id_jag::{ID_JAG_TYP, IdJagClaims};
 use systemprompt_oauth::services::{
     JwtConfig, generate_access_token_jti, generate_client_secret, generate_secure_token,
-    hash_client_secret, verify_client_secret,
+    generate_user_code, hash_client_secret, normalize_user_code, verify_client_secret,
 };
 use systemprompt_test_fixtures::install_test_signing_key;
 
@@ -215,6 +215,7 @@ fn test_jwt_config_custom() {
         expires_in_hours: Some(48),
         resource: None,
         plugin_id: None,
+        dpop_jkt: None,
     };
 
     assert_eq!(config.permissions.len(), 2);
@@ -231,6 +232,7 @@ fn test_jwt_config_no_expiry() {
         expires_in_hours: None,
         resource: None,
         plugin_id: None,
+        dpop_jkt: None,
     };
 
     assert!(config.expires_in_hours.is_none());
@@ -525,3 +527,41 @@ mod jwt_minting {
         assert!((7195..=7205).contains(&lifetime), "lifetime {lifetime}");
     }
 }
+
+#[test]
+fn test_generate_user_code_is_two_dashed_halves_of_consonants() {
+    let code = generate_user_code();
+    assert_eq!(code.len(), 9);
+    let (head, tail) = code.split_once('-').unwrap();
+    assert_eq!(head.len(), 4);
+    assert_eq!(tail.len(), 4);
+    assert!(
+        head.chars()
+            .chain(tail.chars())
+            .all(|c| c.is_ascii_uppercase() && !"AEIOUY".contains(c))
+    );
+}
+
+#[test]
+fn test_generate_user_code_normalizes_to_itself() {
+    let code = generate_user_code();
+    assert_eq!(normalize_user_code(&code).as_deref(), Some(code.as_str()));
+}
+
+#[test]
+fn test_normalize_user_code_accepts_what_people_type() {
+    for typed in ["bcdf-ghjk", "BCDFGHJK", " bcdf ghjk ", "BC-DF-GH-JK"] {
+        assert_eq!(
+            normalize_user_code(typed).as_deref(),
+            Some("BCDF-GHJK"),
+            "{typed}"
+        );
+    }
+}
+
+#[test]
+fn test_normalize_user_code_rejects_wrong_length_or_alphabet() {
+    for typed in ["", "BCDF-GHJ", "BCDF-GHJKL", "ABCD-EFGH", "BCDF-GHJ1"] {
+        assert!(normalize_user_code(typed).is_none(), "{typed}");
+    }
+}
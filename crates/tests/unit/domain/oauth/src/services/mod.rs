@@ -3,6 +3,7 @@
 mod bridge;
 mod cimd;
 mod cimd_validator;
+mod federation;
 mod generation;
 mod http;
 mod jwt;
@@ -164,6 +164,45 @@ fn test_load_link_passkey_template() {
     assert!(template.contains("<!DOCTYPE html>") || template.contains("<html"));
 }
 
+#[test]
+fn test_load_device_verification_template() {
+    let template = TemplateEngine::load_device_verification_template();
+    assert!(template.contains("<!DOCTYPE html>"));
+    assert!(template.contains("name=\"user_code\""));
+    assert!(template.contains("{error}"));
+}
+
+#[test]
+fn test_load_device_consent_template() {
+    let template = TemplateEngine::load_device_consent_template();
+    assert!(template.contains("<!DOCTYPE html>"));
+    assert!(template.contains("action=\"/api/v1/core/oauth/device/consent\""));
+    assert!(template.contains("name=\"consent_token\" value=\"{consent_token}\""));
+    assert!(template.contains("name=\"decision\" value=\"approve\""));
+    assert!(template.contains("name=\"decision\" value=\"deny\""));
+}
+
+#[test]
+fn test_load_device_denied_template() {
+    let template = TemplateEngine::load_device_denied_template();
+    assert!(template.contains("<!DOCTYPE html>"));
+    assert!(template.contains("{client_id}"));
+}
+
+#[test]
+fn test_load_device_approved_template() {
+    let template = TemplateEngine::load_device_approved_template();
+    assert!(template.contains("<!DOCTYPE html>"));
+    assert!(template.contains("{client_id}"));
+}
+
+#[test]
+fn test_webauthn_oauth_template_carries_the_user_code() {
+    let template = TemplateEngine::load_webauthn_oauth_template();
+    assert!(template.contains("id=\"oauth-user-code\" value=\"{user_code}\""));
+    assert!(template.contains("{device_class}"));
+}
+
 #[test]
 fn test_template_engine_debug() {
     let engine = TemplateEngine;
@@ -29,6 +29,8 @@ fn sample_claims() -> JwtClaims {
         rate_limit_tier: Some(RateLimitTier::User),
         plugin_id: None,
         act: None,
+        cnf: None,
+        parent_jti: None,
     }
 }
 
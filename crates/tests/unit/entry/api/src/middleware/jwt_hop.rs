@@ -0,0 +1,77 @@
+//! Unit tests for the routes a token derived for one backend is accepted on.
+
+use systemprompt_api::services::middleware::jwt::test_api::hop_route_allowed;
+use systemprompt_models::auth::HopTarget;
+
+fn agent(name: &str) -> HopTarget {
+    HopTarget::Agent(name.to_owned())
+}
+
+fn mcp_server(name: &str) -> HopTarget {
+    HopTarget::McpServer(name.to_owned())
+}
+
+#[test]
+fn agent_token_reaches_its_own_agent() {
+    assert!(hop_route_allowed(
+        &agent("planner"),
+        "/api/v1/agents/planner"
+    ));
+    assert!(hop_route_allowed(
+        &agent("planner"),
+        "/api/v1/agents/planner/message"
+    ));
+}
+
+#[test]
+fn agent_token_is_refused_by_another_agent() {
+    assert!(!hop_route_allowed(
+        &agent("planner"),
+        "/api/v1/agents/researcher"
+    ));
+    assert!(!hop_route_allowed(
+        &agent("planner"),
+        "/api/v1/agents/planner-admin"
+    ));
+}
+
+#[test]
+fn agent_token_reaches_mcp_tools_and_webhooks() {
+    assert!(hop_route_allowed(
+        &agent("planner"),
+        "/api/v1/mcp/files/mcp"
+    ));
+    assert!(hop_route_allowed(
+        &agent("planner"),
+        "/api/v1/webhook/broadcast"
+    ));
+}
+
+#[test]
+fn mcp_token_reaches_only_its_own_server() {
+    assert!(hop_route_allowed(
+        &mcp_server("files"),
+        "/api/v1/mcp/files/mcp"
+    ));
+    assert!(!hop_route_allowed(
+        &mcp_server("files"),
+        "/api/v1/mcp/mail/mcp"
+    ));
+    assert!(!hop_route_allowed(
+        &mcp_server("files"),
+        "/api/v1/agents/planner"
+    ));
+    assert!(hop_route_allowed(
+        &mcp_server("files"),
+        "/api/v1/webhook/agui"
+    ));
+}
+
+#[test]
+fn derived_tokens_are_refused_on_other_api_routes() {
+    for hop in [agent("planner"), mcp_server("files")] {
+        assert!(!hop_route_allowed(&hop, "/api/v1/core/users"));
+        assert!(!hop_route_allowed(&hop, "/api/v1/admin/logs"));
+        assert!(!hop_route_allowed(&hop, "/api/v1/webhooks"));
+    }
+}
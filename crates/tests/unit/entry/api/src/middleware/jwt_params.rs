@@ -24,6 +24,9 @@ fn jwt_context(user_id: &UserId, session_id: &SessionId) -> JwtUserContext {
         attributes: BTreeMap::new(),
         jti: "jti-123".to_owned(),
         exp: 1_900_000_000,
+        dpop_jkt: None,
+        parent_jti: None,
+        hop_target: None,
     }
 }
 
@@ -9,6 +9,7 @@
 //! - Rate limit config construction and tier multipliers
 //! - Session tracking skip logic
 //! - Per-flavour context middleware admission contracts
+//! - Route confinement of tokens derived for one backend
 //! - CORS error variants
 
 mod authz_policy;
@@ -20,6 +21,7 @@ mod content_negotiation;
 mod context_flavours;
 mod cors_config;
 mod header_source;
+mod jwt_hop;
 mod jwt_params;
 mod negotiation_middleware;
 mod rate_limit_config;
@@ -9,7 +9,7 @@ use systemprompt_api::routes::gateway::bridge::{canonicalize_org_uuid, provider_
 use systemprompt_api::routes::gateway::models::model_entries;
 use systemprompt_identifiers::{ModelId, ProviderId, SecretName, TenantId};
 use systemprompt_models::profile::{
-    ApiSurface, ProviderEntry, ProviderModel, ProviderRegistry, WireProtocol,
+    ApiSurface, DiscoveredModels, ProviderEntry, ProviderModel, ProviderRegistry, WireProtocol,
 };
 
 fn model(id: &str, aliases: &[&str]) -> ProviderModel {
@@ -51,6 +51,8 @@ fn provider_with_surface(
         api_key_secret: SecretName::new(secret),
         extra_headers: Default::default(),
         models,
+        local: None,
+        discovered: DiscoveredModels::default(),
     }
 }
 
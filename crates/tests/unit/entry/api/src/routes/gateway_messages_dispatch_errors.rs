@@ -11,7 +11,9 @@ use axum::http::StatusCode;
 use systemprompt_api::routes::gateway::messages::test_api::{
     classify_dispatch_error, map_dispatch_error,
 };
-use systemprompt_api::services::gateway::protocol::outbound::UpstreamError;
+use systemprompt_api::services::gateway::protocol::outbound::{
+    EmbeddingsUnsupported, UpstreamError,
+};
 use systemprompt_api::services::gateway::service::{
     DispatchError, GovernanceDenied, GuardForbidden, PolicyDenied, QuotaExceeded, SafetyBlocked,
 };
@@ -36,6 +38,16 @@ fn a_policy_denial_is_a_400_carrying_the_policy_reason() {
     );
 }
 
+#[test]
+fn embeddings_on_a_non_embedding_provider_is_a_400() {
+    let (status, message) = classify_dispatch_error(&anyhow::Error::new(EmbeddingsUnsupported {
+        provider: "anthropic".to_owned(),
+    }));
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(message.contains("anthropic"), "{message}");
+}
+
 #[test]
 fn a_safety_block_is_a_400_carrying_the_scanner_message() {
     let (status, message) = classify_dispatch_error(&anyhow::Error::new(SafetyBlocked {
@@ -39,6 +39,14 @@ fn as_str_emits_canonical_rfc_wire_strings() {
         (OAuthErrorCode::LinkFailed, "link_failed"),
         (OAuthErrorCode::InvalidTarget, "invalid_target"),
         (OAuthErrorCode::NotFound, "not_found"),
+        (
+            OAuthErrorCode::AuthorizationPending,
+            "authorization_pending",
+        ),
+        (OAuthErrorCode::SlowDown, "slow_down"),
+        (OAuthErrorCode::ExpiredToken, "expired_token"),
+        (OAuthErrorCode::InvalidDpopProof, "invalid_dpop_proof"),
+        (OAuthErrorCode::UseDpopNonce, "use_dpop_nonce"),
     ];
     for (code, wire) in pairs {
         assert_eq!(code.as_str(), wire);
@@ -59,6 +67,11 @@ fn default_status_400_for_request_shape_errors() {
         OAuthErrorCode::LinkFailed,
         OAuthErrorCode::InvalidTarget,
         OAuthErrorCode::RegistrationFailed,
+        OAuthErrorCode::AuthorizationPending,
+        OAuthErrorCode::SlowDown,
+        OAuthErrorCode::ExpiredToken,
+        OAuthErrorCode::InvalidDpopProof,
+        OAuthErrorCode::UseDpopNonce,
     ];
     for c in codes {
         assert_eq!(c.default_status(), StatusCode::BAD_REQUEST, "{c:?}");
@@ -112,6 +112,23 @@ fn each_constructor_carries_its_own_code_and_default_status() {
             OAuthErrorCode::InvalidTarget,
         ),
         (OAuthHttpError::not_found("d"), OAuthErrorCode::NotFound),
+        (
+            OAuthHttpError::authorization_pending("d"),
+            OAuthErrorCode::AuthorizationPending,
+        ),
+        (OAuthHttpError::slow_down("d"), OAuthErrorCode::SlowDown),
+        (
+            OAuthHttpError::expired_token("d"),
+            OAuthErrorCode::ExpiredToken,
+        ),
+        (
+            OAuthHttpError::invalid_dpop_proof("d"),
+            OAuthErrorCode::InvalidDpopProof,
+        ),
+        (
+            OAuthHttpError::use_dpop_nonce("d"),
+            OAuthErrorCode::UseDpopNonce,
+        ),
     ];
 
     for (err, expected) in cases {
@@ -146,6 +163,29 @@ async fn a_non_401_carries_no_authenticate_challenge() {
     );
 }
 
+#[tokio::test]
+async fn a_nonce_challenge_hands_the_client_the_nonce_to_retry_with() {
+    let resp = OAuthHttpError::use_dpop_nonce("nonce required")
+        .with_dpop_nonce("n-123")
+        .into_response();
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        resp.headers()
+            .get("dpop-nonce")
+            .and_then(|v| v.to_str().ok()),
+        Some("n-123"),
+        "RFC 9449 §8: the client retries with the nonce from this header"
+    );
+}
+
+#[tokio::test]
+async fn an_error_without_a_nonce_sets_no_nonce_header() {
+    let resp = OAuthHttpError::invalid_dpop_proof("bad proof").into_response();
+
+    assert!(resp.headers().get("dpop-nonce").is_none());
+}
+
 #[test]
 fn an_explicit_status_overrides_the_code_default() {
     let err =
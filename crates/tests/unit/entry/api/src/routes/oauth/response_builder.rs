@@ -1,11 +1,13 @@
 //! Unit tests for OAuth authorize response_builder module
 //!
 //! Tests the convert_form_to_query and is_user_consent_granted functions
-//! that support the authorize POST handler.
+//! that support the authorize POST handler, and the WebAuthn page shared with
+//! the device verification flow.
 
 use systemprompt_api::routes::oauth::endpoints::authorize::AuthorizeRequest;
 use systemprompt_api::routes::oauth::endpoints::authorize::response_builder::{
-    convert_form_to_query, is_user_consent_granted,
+    convert_form_to_query, generate_device_webauthn_form, generate_webauthn_form,
+    is_user_consent_granted,
 };
 use systemprompt_identifiers::ClientId;
 
@@ -157,3 +159,21 @@ fn test_is_user_consent_granted_returns_false_for_arbitrary_string() {
 
     assert!(!is_user_consent_granted(&form));
 }
+
+#[test]
+fn test_generate_webauthn_form_hides_the_device_code_line() {
+    let query = convert_form_to_query(&create_full_authorize_request());
+    let html = generate_webauthn_form(&query, "user");
+    assert!(html.contains("id=\"oauth-user-code\" value=\"\""));
+    assert!(html.contains("<p class=\"hidden\"><strong>Device code:</strong>"));
+}
+
+#[test]
+fn test_generate_device_webauthn_form_shows_and_carries_the_user_code() {
+    let mut query = convert_form_to_query(&create_minimal_authorize_request());
+    query.response_type = "device_code".to_string();
+    let html = generate_device_webauthn_form(&query, "user", "BCDF-GHJK");
+    assert!(html.contains("id=\"oauth-user-code\" value=\"BCDF-GHJK\""));
+    assert!(html.contains("<p class=\"\"><strong>Device code:</strong> BCDF-GHJK</p>"));
+    assert!(html.contains("id=\"oauth-client-id\" value=\"sp_minimal_client\""));
+}
@@ -14,6 +14,7 @@ fn test_well_known_response_serialization() {
         issuer: "https://example.com".to_string(),
         authorization_endpoint: "https://example.com/authorize".to_string(),
         token_endpoint: "https://example.com/token".to_string(),
+        device_authorization_endpoint: "https://example.com/device_authorization".to_string(),
         userinfo_endpoint: "https://example.com/userinfo".to_string(),
         introspection_endpoint: "https://example.com/introspect".to_string(),
         revocation_endpoint: "https://example.com/revoke".to_string(),
@@ -35,6 +36,7 @@ fn test_well_known_response_serialization() {
         authorization_grant_profiles_supported: vec![
             "urn:ietf:params:oauth:grant-profile:id-jag".to_owned(),
         ],
+        dpop_signing_alg_values_supported: vec!["ES256".to_owned()],
     };
 
     let json = serde_json::to_value(&response).unwrap();
@@ -52,6 +54,7 @@ fn test_well_known_response_optional_registration_endpoint() {
         issuer: "https://example.com".to_string(),
         authorization_endpoint: String::new(),
         token_endpoint: String::new(),
+        device_authorization_endpoint: String::new(),
         userinfo_endpoint: String::new(),
         introspection_endpoint: String::new(),
         revocation_endpoint: String::new(),
@@ -71,6 +74,7 @@ fn test_well_known_response_optional_registration_endpoint() {
         authorization_grant_profiles_supported: vec![
             "urn:ietf:params:oauth:grant-profile:id-jag".to_owned(),
         ],
+        dpop_signing_alg_values_supported: vec!["ES256".to_owned()],
     };
 
     let json = serde_json::to_value(&response).unwrap();
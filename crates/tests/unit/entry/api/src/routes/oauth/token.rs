@@ -195,6 +195,22 @@ async fn token_error_invalid_target_maps_to_invalid_target() {
     assert_eq!(json["error"], "invalid_target");
 }
 
+#[tokio::test]
+async fn token_error_device_poll_outcomes_map_to_rfc8628_codes() {
+    let cases = [
+        (TokenError::AuthorizationPending, "authorization_pending"),
+        (TokenError::SlowDown, "slow_down"),
+        (TokenError::ExpiredDeviceCode, "expired_token"),
+        (TokenError::AccessDenied, "access_denied"),
+    ];
+    for (error, wire) in cases {
+        let resp = OAuthHttpError::from(error).into_response();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let json = body_to_json(resp).await;
+        assert_eq!(json["error"], wire);
+    }
+}
+
 #[test]
 fn test_token_request_deserialize_authorization_code() {
     let json = serde_json::json!({
@@ -318,6 +334,7 @@ fn test_well_known_response_serialize() {
         issuer: "https://auth.example.com".to_string(),
         authorization_endpoint: "https://auth.example.com/authorize".to_string(),
         token_endpoint: "https://auth.example.com/token".to_string(),
+        device_authorization_endpoint: "https://auth.example.com/device_authorization".to_string(),
         userinfo_endpoint: "https://auth.example.com/userinfo".to_string(),
         introspection_endpoint: "https://auth.example.com/introspect".to_string(),
         revocation_endpoint: "https://auth.example.com/revoke".to_string(),
@@ -339,6 +356,7 @@ fn test_well_known_response_serialize() {
         authorization_grant_profiles_supported: vec![
             "urn:ietf:params:oauth:grant-profile:id-jag".to_owned(),
         ],
+        dpop_signing_alg_values_supported: vec!["ES256".to_owned()],
     };
 
     let json = serde_json::to_value(&response).unwrap();
@@ -0,0 +1,60 @@
+//! Unit tests for gateway budget alerts: which thresholds a cost increment
+//! crosses and the text chat-platform targets receive.
+
+use chrono::{TimeZone, Utc};
+use systemprompt_ai::QuotaWindow;
+use systemprompt_api::services::gateway::budget_alert::{BucketSpend, alert_text, crossings};
+
+fn window() -> QuotaWindow {
+    QuotaWindow {
+        window_seconds: 86_400,
+        max_cost_microdollars: Some(20_000_000),
+        alert_thresholds: vec![50, 80, 100],
+        ..QuotaWindow::default()
+    }
+}
+
+fn spend(before: i64, after: i64) -> BucketSpend<'static> {
+    BucketSpend {
+        subject_kind: "user",
+        subject_id: "user-1",
+        window_start: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+        spent_before: before,
+        spent_after: after,
+    }
+}
+
+#[test]
+fn one_increment_can_cross_several_thresholds() {
+    let crossed = crossings(&window(), spend(9_000_000, 17_000_000));
+    let percents: Vec<u8> = crossed.iter().map(|c| c.threshold_percent).collect();
+    assert_eq!(percents, vec![50, 80]);
+    assert!(crossed.iter().all(|c| c.spent_microdollars == 17_000_000));
+    assert!(crossed.iter().all(|c| c.limit_microdollars == 20_000_000));
+    assert_eq!(crossed[0].window_seconds, 86_400);
+}
+
+#[test]
+fn an_increment_within_a_band_crosses_nothing() {
+    assert!(crossings(&window(), spend(10_000_000, 15_000_000)).is_empty());
+}
+
+#[test]
+fn a_window_without_thresholds_never_alerts() {
+    let quiet = QuotaWindow {
+        alert_thresholds: Vec::new(),
+        ..window()
+    };
+    assert!(crossings(&quiet, spend(0, 30_000_000)).is_empty());
+}
+
+#[test]
+fn alert_text_names_the_subject_share_and_dollars() {
+    let crossed = crossings(&window(), spend(17_000_000, 20_500_000));
+    assert_eq!(crossed.len(), 1);
+    assert_eq!(
+        alert_text(&crossed[0]),
+        "Budget alert: user `user-1` has reached 100% of its 86400s cost budget ($20.50 of \
+         $20.00)."
+    );
+}
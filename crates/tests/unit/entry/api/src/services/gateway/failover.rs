@@ -0,0 +1,53 @@
+//! `classify` decides which upstream failures move a multi-target route on to
+//! its next hop: rate limits, server errors, and timeouts do; a request the
+//! upstream refused as malformed does not, because the next provider would
+//! refuse it too.
+
+use systemprompt_api::services::gateway::protocol::outbound::UpstreamError;
+use systemprompt_api::services::gateway::service::test_api::classify;
+use systemprompt_models::profile::FallbackTrigger;
+
+fn status(code: u16) -> anyhow::Error {
+    anyhow::Error::new(UpstreamError::Status {
+        provider: "anthropic".to_owned(),
+        status: code,
+        message: "upstream said no".to_owned(),
+        body: bytes::Bytes::new(),
+        retry_after: None,
+        request_id: None,
+    })
+}
+
+#[test]
+fn rate_limit_classifies_as_rate_limited() {
+    assert_eq!(classify(&status(429)), Some(FallbackTrigger::RateLimited));
+}
+
+#[test]
+fn gateway_timeouts_classify_as_timeout() {
+    assert_eq!(classify(&status(408)), Some(FallbackTrigger::Timeout));
+    assert_eq!(classify(&status(504)), Some(FallbackTrigger::Timeout));
+}
+
+#[test]
+fn other_5xx_classify_as_server_error() {
+    for code in [500, 502, 503, 529] {
+        assert_eq!(
+            classify(&status(code)),
+            Some(FallbackTrigger::ServerError),
+            "{code}"
+        );
+    }
+}
+
+#[test]
+fn client_errors_do_not_fall_back() {
+    for code in [400, 401, 403, 404, 422] {
+        assert_eq!(classify(&status(code)), None, "{code}");
+    }
+}
+
+#[test]
+fn non_upstream_errors_do_not_fall_back() {
+    assert_eq!(classify(&anyhow::anyhow!("body build failed")), None);
+}
@@ -0,0 +1,241 @@
+//! Unit tests for the OpenAI Chat Completions inbound adapter — request
+//! parsing, system folding, tool calls, images, and `response_format`.
+
+use bytes::Bytes;
+use systemprompt_api::services::gateway::protocol::canonical::{
+    CanonicalContent, CanonicalRequest, CanonicalToolChoice, ImageSource, ReasoningEffort,
+    ResponseFormat, Role,
+};
+use systemprompt_api::services::gateway::protocol::inbound::openai_chat::OpenAiChatInbound;
+use systemprompt_api::services::gateway::protocol::inbound::{InboundAdapter, InboundParseError};
+
+fn parse(body: &'static [u8]) -> Result<CanonicalRequest, InboundParseError> {
+    OpenAiChatInbound::default().parse_request(&Bytes::from_static(body))
+}
+
+#[test]
+fn wire_name_is_openai_chat() {
+    assert_eq!(OpenAiChatInbound::default().wire_name(), "openai.chat");
+}
+
+#[test]
+fn parse_request_invalid_json() {
+    let err = parse(b"not json").expect_err("should fail");
+    assert!(matches!(err, InboundParseError::InvalidJson(_)));
+}
+
+#[test]
+fn parse_request_requires_model_and_messages() {
+    match parse(br#"{"messages":[]}"#).expect_err("no model") {
+        InboundParseError::MissingField("model") => {},
+        other => panic!("expected MissingField(model), got {other:?}"),
+    }
+    match parse(br#"{"model":"gpt-4o"}"#).expect_err("no messages") {
+        InboundParseError::MissingField("messages") => {},
+        other => panic!("expected MissingField(messages), got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_request_minimal_uses_defaults() {
+    let req =
+        parse(br#"{"model":"gpt-4o","messages":[{"role":"user","content":"hi"}]}"#).expect("parse");
+    assert_eq!(req.model, "gpt-4o");
+    assert_eq!(req.max_tokens, 4096);
+    assert!(req.system.is_none());
+    assert!(!req.stream);
+    assert_eq!(req.messages.len(), 1);
+    assert!(matches!(req.messages[0].role, Role::User));
+    assert!(matches!(&req.messages[0].content[0], CanonicalContent::Text(t) if t == "hi"));
+}
+
+#[test]
+fn system_and_developer_messages_fold_into_system_prompt() {
+    let req = parse(
+        br#"{"model":"m","messages":[
+            {"role":"system","content":"be brief"},
+            {"role":"developer","content":[{"type":"text","text":"use json"}]},
+            {"role":"user","content":"hi"}
+        ]}"#,
+    )
+    .expect("parse");
+    assert_eq!(req.system.as_deref(), Some("be brief\n\nuse json"));
+    assert_eq!(
+        req.messages.len(),
+        1,
+        "system turns must not stay in messages"
+    );
+}
+
+#[test]
+fn max_completion_tokens_wins_over_legacy_max_tokens() {
+    let req = parse(br#"{"model":"m","messages":[],"max_tokens":10,"max_completion_tokens":20}"#)
+        .expect("parse");
+    assert_eq!(req.max_tokens, 20);
+    let legacy = parse(br#"{"model":"m","messages":[],"max_tokens":10}"#).expect("parse");
+    assert_eq!(legacy.max_tokens, 10);
+}
+
+#[test]
+fn multiple_choices_are_rejected() {
+    match parse(br#"{"model":"m","messages":[],"n":2}"#).expect_err("n>1") {
+        InboundParseError::Unsupported { field: "n", .. } => {},
+        other => panic!("expected Unsupported(n), got {other:?}"),
+    }
+    parse(br#"{"model":"m","messages":[],"n":1}"#).expect("n=1 is the default");
+}
+
+#[test]
+fn assistant_tool_calls_and_tool_results_round_into_canonical() {
+    let req = parse(
+        br#"{"model":"m","messages":[
+            {"role":"user","content":"weather?"},
+            {"role":"assistant","content":null,"tool_calls":[
+                {"id":"call_1","type":"function","function":{"name":"weather","arguments":"{\"city\":\"Paris\"}"}}
+            ]},
+            {"role":"tool","tool_call_id":"call_1","content":"sunny"}
+        ]}"#,
+    )
+    .expect("parse");
+    assert_eq!(req.messages.len(), 3);
+    match &req.messages[1].content[..] {
+        [
+            CanonicalContent::ToolUse {
+                id, name, input, ..
+            },
+        ] => {
+            assert_eq!(id, "call_1");
+            assert_eq!(name, "weather");
+            assert_eq!(input["city"], "Paris");
+        },
+        other => panic!("expected one tool use, got {other:?}"),
+    }
+    assert!(matches!(req.messages[2].role, Role::Tool));
+    match &req.messages[2].content[..] {
+        [
+            CanonicalContent::ToolResult {
+                tool_use_id,
+                content,
+                ..
+            },
+        ] => {
+            assert_eq!(tool_use_id, "call_1");
+            assert!(matches!(&content[0], CanonicalContent::Text(t) if t == "sunny"));
+        },
+        other => panic!("expected one tool result, got {other:?}"),
+    }
+}
+
+#[test]
+fn tool_message_without_call_id_is_rejected() {
+    match parse(br#"{"model":"m","messages":[{"role":"tool","content":"x"}]}"#)
+        .expect_err("missing tool_call_id")
+    {
+        InboundParseError::MissingField("messages[].tool_call_id") => {},
+        other => panic!("expected MissingField(tool_call_id), got {other:?}"),
+    }
+}
+
+#[test]
+fn unknown_role_is_unsupported() {
+    let err = parse(br#"{"model":"m","messages":[{"role":"function","content":"x"}]}"#)
+        .expect_err("function role");
+    assert!(matches!(
+        err,
+        InboundParseError::Unsupported {
+            field: "messages[].role",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn image_parts_keep_urls_and_inline_data_urls() {
+    let req = parse(
+        br#"{"model":"m","messages":[{"role":"user","content":[
+            {"type":"image_url","image_url":{"url":"https://x/cat.png","detail":"low"}},
+            {"type":"image_url","image_url":{"url":"data:image/png;base64,AAAA"}}
+        ]}]}"#,
+    )
+    .expect("parse");
+    let content = &req.messages[0].content;
+    assert!(matches!(
+        &content[0],
+        CanonicalContent::Image(ImageSource::Url { url, detail: Some(_) }) if url == "https://x/cat.png"
+    ));
+    assert!(matches!(
+        &content[1],
+        CanonicalContent::Image(ImageSource::Base64 { media_type, data, .. })
+            if media_type == "image/png" && data == "AAAA"
+    ));
+}
+
+#[test]
+fn tools_and_tool_choice_map_to_canonical() {
+    let req = parse(
+        br#"{"model":"m","messages":[],
+            "tools":[{"type":"function","function":{"name":"weather","description":"d","parameters":{"type":"object"}}}],
+            "tool_choice":{"type":"function","function":{"name":"weather"}}}"#,
+    )
+    .expect("parse");
+    assert_eq!(req.tools.len(), 1);
+    assert_eq!(req.tools[0].name, "weather");
+    assert_eq!(req.tools[0].input_schema["type"], "object");
+    assert!(matches!(req.tool_choice, Some(CanonicalToolChoice::Tool(ref n)) if n == "weather"));
+
+    let required =
+        parse(br#"{"model":"m","messages":[],"tool_choice":"required"}"#).expect("parse");
+    assert!(matches!(
+        required.tool_choice,
+        Some(CanonicalToolChoice::Required)
+    ));
+}
+
+#[test]
+fn response_format_json_schema_is_carried() {
+    let req = parse(
+        br#"{"model":"m","messages":[],"response_format":{"type":"json_schema",
+            "json_schema":{"name":"out","schema":{"type":"object"},"strict":true}}}"#,
+    )
+    .expect("parse");
+    match req.response_format {
+        Some(ResponseFormat::JsonSchema {
+            name,
+            schema,
+            strict,
+        }) => {
+            assert_eq!(name, "out");
+            assert_eq!(schema["type"], "object");
+            assert!(strict);
+        },
+        other => panic!("expected JsonSchema, got {other:?}"),
+    }
+    let text =
+        parse(br#"{"model":"m","messages":[],"response_format":{"type":"text"}}"#).expect("parse");
+    assert!(text.response_format.is_none());
+}
+
+#[test]
+fn stop_sampling_and_reasoning_fields_are_parsed() {
+    let req = parse(
+        br#"{"model":"m","messages":[],"stop":"END","temperature":0.5,"top_p":0.9,
+            "presence_penalty":0.1,"frequency_penalty":0.2,"reasoning_effort":"minimal","stream":true}"#,
+    )
+    .expect("parse");
+    assert_eq!(req.stop_sequences, vec!["END".to_owned()]);
+    assert_eq!(req.temperature, Some(0.5));
+    assert_eq!(req.top_p, Some(0.9));
+    assert_eq!(req.presence_penalty, Some(0.1));
+    assert_eq!(req.frequency_penalty, Some(0.2));
+    assert!(matches!(req.reasoning_effort, Some(ReasoningEffort::Low)));
+    assert!(req.stream);
+}
+
+#[test]
+fn render_error_uses_openai_error_shape() {
+    let bytes =
+        OpenAiChatInbound::default().render_error(http::StatusCode::BAD_REQUEST, "bad \"input\"");
+    let v: serde_json::Value = serde_json::from_slice(&bytes).expect("json");
+    assert_eq!(v["error"]["type"], "api_error");
+    assert_eq!(v["error"]["message"], "bad \"input\"");
+}
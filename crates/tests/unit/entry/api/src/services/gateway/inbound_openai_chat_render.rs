@@ -0,0 +1,243 @@
+//! Tests for the OpenAI Chat Completions inbound renderer — the buffered
+//! `chat.completion` object and the `chat.completion.chunk` SSE stream.
+
+use bytes::Bytes;
+use serde_json::{Value, json};
+use systemprompt_api::services::gateway::protocol::canonical::CanonicalContent;
+use systemprompt_api::services::gateway::protocol::canonical_response::{
+    CanonicalEvent, CanonicalResponse, CanonicalStopReason, CanonicalUsage, ContentBlockKind,
+};
+use systemprompt_api::services::gateway::protocol::inbound::InboundAdapter;
+use systemprompt_api::services::gateway::protocol::inbound::openai_chat::{
+    ChatStream, OpenAiChatInbound,
+};
+
+fn sample_response() -> CanonicalResponse {
+    CanonicalResponse {
+        id: "msg_1".into(),
+        model: "gpt-x".into(),
+        content: vec![
+            CanonicalContent::Text("answer".into()),
+            CanonicalContent::ToolUse {
+                id: "call_1".into(),
+                name: "weather".into(),
+                input: json!({"city": "Paris"}),
+                signature: None,
+            },
+        ],
+        stop_reason: Some(CanonicalStopReason::ToolUse),
+        usage: CanonicalUsage {
+            input_tokens: 3,
+            output_tokens: 7,
+            cache_read_tokens: 2,
+            ..CanonicalUsage::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn frames(bytes: &Bytes) -> Vec<String> {
+    std::str::from_utf8(bytes)
+        .expect("utf8")
+        .split("\n\n")
+        .filter(|f| !f.is_empty())
+        .map(|f| f.strip_prefix("data: ").expect("data frame").to_owned())
+        .collect()
+}
+
+fn chunk(bytes: &Bytes) -> Value {
+    let frames = frames(bytes);
+    assert_eq!(frames.len(), 1);
+    serde_json::from_str(&frames[0]).expect("json")
+}
+
+fn bound(include_usage: bool) -> std::sync::Arc<dyn InboundAdapter> {
+    let body = if include_usage {
+        Bytes::from_static(
+            br#"{"model":"m","messages":[],"stream_options":{"include_usage":true}}"#,
+        )
+    } else {
+        Bytes::from_static(br#"{"model":"m","messages":[]}"#)
+    };
+    OpenAiChatInbound::default()
+        .bind_request(&body)
+        .expect("chat adapter binds per request")
+}
+
+#[test]
+fn render_response_emits_chat_completion_object() {
+    let bytes = OpenAiChatInbound::default().render_response(&sample_response());
+    let v: Value = serde_json::from_slice(&bytes).expect("json");
+    assert_eq!(v["object"], "chat.completion");
+    assert_eq!(v["model"], "gpt-x");
+    let choice = &v["choices"][0];
+    assert_eq!(choice["finish_reason"], "tool_calls");
+    assert_eq!(choice["message"]["role"], "assistant");
+    assert_eq!(choice["message"]["content"], "answer");
+    let call = &choice["message"]["tool_calls"][0];
+    assert_eq!(call["id"], "call_1");
+    assert_eq!(call["function"]["name"], "weather");
+    let args: Value = serde_json::from_str(call["function"]["arguments"].as_str().expect("string"))
+        .expect("arguments are a JSON string");
+    assert_eq!(args["city"], "Paris");
+    assert_eq!(v["usage"]["prompt_tokens"], 3);
+    assert_eq!(v["usage"]["completion_tokens"], 7);
+    assert_eq!(v["usage"]["total_tokens"], 10);
+    assert_eq!(v["usage"]["prompt_tokens_details"]["cached_tokens"], 2);
+}
+
+#[test]
+fn render_response_without_text_sets_null_content() {
+    let resp = CanonicalResponse {
+        content: Vec::new(),
+        stop_reason: Some(CanonicalStopReason::MaxTokens),
+        ..sample_response()
+    };
+    let bytes = OpenAiChatInbound::default().render_response(&resp);
+    let v: Value = serde_json::from_slice(&bytes).expect("json");
+    assert!(v["choices"][0]["message"]["content"].is_null());
+    assert!(v["choices"][0]["message"].get("tool_calls").is_none());
+    assert_eq!(v["choices"][0]["finish_reason"], "length");
+}
+
+#[test]
+fn bound_stream_repeats_id_and_created_on_every_chunk() {
+    let adapter = bound(false);
+    let start = adapter
+        .render_event(
+            &CanonicalEvent::MessageStart {
+                id: "msg_1".into(),
+                model: "gpt-x".into(),
+                usage: CanonicalUsage::default(),
+            },
+            "gpt-x",
+        )
+        .expect("role chunk");
+    let text = adapter
+        .render_event(
+            &CanonicalEvent::TextDelta {
+                index: 0,
+                text: "hel".into(),
+            },
+            "gpt-x",
+        )
+        .expect("text chunk");
+    let (start, text) = (chunk(&start), chunk(&text));
+    assert_eq!(start["object"], "chat.completion.chunk");
+    assert!(start["id"].as_str().expect("id").starts_with("chatcmpl-"));
+    assert_eq!(start["id"], text["id"]);
+    assert_eq!(start["created"], text["created"]);
+    assert_eq!(start["choices"][0]["delta"]["role"], "assistant");
+    assert_eq!(text["choices"][0]["delta"]["content"], "hel");
+}
+
+#[test]
+fn tool_calls_are_numbered_by_their_own_ordinal() {
+    let adapter = bound(false);
+    let open = adapter
+        .render_event(
+            &CanonicalEvent::ContentBlockStart {
+                index: 1,
+                block: ContentBlockKind::ToolUse {
+                    id: "call_1".into(),
+                    name: "weather".into(),
+                    signature: None,
+                },
+            },
+            "m",
+        )
+        .expect("tool call chunk");
+    let delta = adapter
+        .render_event(
+            &CanonicalEvent::ToolUseDelta {
+                index: 1,
+                partial_json: "{\"city\"".into(),
+            },
+            "m",
+        )
+        .expect("arguments chunk");
+    let open = chunk(&open);
+    let call = &open["choices"][0]["delta"]["tool_calls"][0];
+    assert_eq!(call["index"], 0, "block 1 is the first tool call");
+    assert_eq!(call["id"], "call_1");
+    assert_eq!(call["function"]["name"], "weather");
+    let delta = chunk(&delta);
+    let call = &delta["choices"][0]["delta"]["tool_calls"][0];
+    assert_eq!(call["index"], 0);
+    assert_eq!(call["function"]["arguments"], "{\"city\"");
+}
+
+#[test]
+fn non_content_events_render_nothing() {
+    let adapter = bound(false);
+    for event in [
+        CanonicalEvent::ContentBlockStart {
+            index: 0,
+            block: ContentBlockKind::Text,
+        },
+        CanonicalEvent::ThinkingDelta {
+            index: 0,
+            text: "t".into(),
+        },
+        CanonicalEvent::ContentBlockStop { index: 0 },
+    ] {
+        assert!(adapter.render_event(&event, "m").is_none(), "{event:?}");
+    }
+}
+
+#[test]
+fn message_stop_emits_finish_chunk_then_done() {
+    let adapter = bound(false);
+    let stop = CanonicalEvent::MessageStop {
+        id: "msg_1".into(),
+        stop_reason: Some(CanonicalStopReason::EndTurn),
+    };
+    let bytes = adapter
+        .render_terminal_event(&stop, &sample_response(), "gpt-x")
+        .expect("terminal frames");
+    let frames = frames(&bytes);
+    assert_eq!(frames.len(), 2);
+    let finish: Value = serde_json::from_str(&frames[0]).expect("json");
+    assert_eq!(finish["choices"][0]["finish_reason"], "stop");
+    assert_eq!(frames[1], "[DONE]");
+}
+
+#[test]
+fn include_usage_adds_a_usage_chunk_before_done() {
+    let adapter = bound(true);
+    let stop = CanonicalEvent::MessageStop {
+        id: "msg_1".into(),
+        stop_reason: Some(CanonicalStopReason::ToolUse),
+    };
+    let bytes = adapter
+        .render_terminal_event(&stop, &sample_response(), "gpt-x")
+        .expect("terminal frames");
+    let frames = frames(&bytes);
+    assert_eq!(frames.len(), 3);
+    let usage: Value = serde_json::from_str(&frames[1]).expect("json");
+    assert_eq!(usage["choices"], json!([]));
+    assert_eq!(usage["usage"]["total_tokens"], 10);
+    assert_eq!(frames[2], "[DONE]");
+}
+
+#[test]
+fn content_block_stop_is_not_a_terminal_frame() {
+    let adapter = bound(false);
+    assert!(
+        adapter
+            .render_terminal_event(
+                &CanonicalEvent::ContentBlockStop { index: 0 },
+                &sample_response(),
+                "m",
+            )
+            .is_none()
+    );
+}
+
+#[test]
+fn chat_stream_new_stamps_created() {
+    let stream = ChatStream::new("chatcmpl-x".into(), true);
+    assert_eq!(stream.id, "chatcmpl-x");
+    assert!(stream.include_usage);
+    assert!(stream.created > 0);
+}
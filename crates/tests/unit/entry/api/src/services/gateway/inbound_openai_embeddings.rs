@@ -0,0 +1,131 @@
+//! Unit tests for the OpenAI Embeddings inbound adapter — request parsing into
+//! the canonical model and the embeddings lane, and the `list` response shape.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use bytes::Bytes;
+use serde_json::Value;
+use systemprompt_api::services::gateway::protocol::canonical::{CanonicalContent, Role};
+use systemprompt_api::services::gateway::protocol::inbound::openai_embeddings::{
+    EmbeddingEncoding, OpenAiEmbeddingsInbound, parse_embeddings, render_embeddings,
+};
+use systemprompt_api::services::gateway::protocol::inbound::{InboundAdapter, InboundParseError};
+use systemprompt_models::wire::embeddings::EmbeddingResponse;
+
+fn parse(body: &'static [u8]) -> Result<Vec<String>, InboundParseError> {
+    parse_embeddings(&Bytes::from_static(body)).map(|p| p.request.inputs)
+}
+
+#[test]
+fn wire_name_is_openai_embeddings() {
+    assert_eq!(OpenAiEmbeddingsInbound.wire_name(), "openai.embeddings");
+}
+
+#[test]
+fn string_and_array_inputs_are_accepted() {
+    assert_eq!(
+        parse(br#"{"model":"m","input":"one"}"#).expect("string"),
+        vec!["one".to_owned()]
+    );
+    assert_eq!(
+        parse(br#"{"model":"m","input":["a","b"]}"#).expect("array"),
+        vec!["a".to_owned(), "b".to_owned()]
+    );
+}
+
+#[test]
+fn model_and_input_are_required() {
+    match parse(br#"{"input":"x"}"#).expect_err("no model") {
+        InboundParseError::MissingField("model") => {},
+        other => panic!("expected MissingField(model), got {other:?}"),
+    }
+    match parse(br#"{"model":"m","input":[]}"#).expect_err("empty input") {
+        InboundParseError::MissingField("input") => {},
+        other => panic!("expected MissingField(input), got {other:?}"),
+    }
+}
+
+#[test]
+fn token_id_inputs_are_unsupported() {
+    let err = parse(br#"{"model":"m","input":[[1,2,3]]}"#).expect_err("token ids");
+    assert!(matches!(
+        err,
+        InboundParseError::Unsupported { field: "input", .. }
+    ));
+}
+
+#[test]
+fn dimensions_and_encoding_format_are_parsed() {
+    let parsed = parse_embeddings(&Bytes::from_static(
+        br#"{"model":"m","input":"x","dimensions":128,"encoding_format":"base64"}"#,
+    ))
+    .expect("parse");
+    assert_eq!(parsed.request.dimensions, Some(128));
+    assert_eq!(parsed.encoding, EmbeddingEncoding::Base64);
+
+    let err = parse(br#"{"model":"m","input":"x","encoding_format":"int8"}"#)
+        .expect_err("unknown encoding");
+    assert!(matches!(
+        err,
+        InboundParseError::Unsupported {
+            field: "encoding_format",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn parse_request_maps_each_input_to_a_user_message() {
+    let req = OpenAiEmbeddingsInbound
+        .parse_request(&Bytes::from_static(
+            br#"{"model":"text-embedding-3-small","input":["a","b"]}"#,
+        ))
+        .expect("parse");
+    assert_eq!(req.model, "text-embedding-3-small");
+    assert!(!req.stream);
+    assert_eq!(req.messages.len(), 2);
+    assert!(req.messages.iter().all(|m| matches!(m.role, Role::User)));
+    assert!(matches!(&req.messages[1].content[0], CanonicalContent::Text(t) if t == "b"));
+    assert_eq!(req.flatten_text(), "a\nb");
+}
+
+fn response() -> EmbeddingResponse {
+    EmbeddingResponse {
+        model: "text-embedding-3-small".to_owned(),
+        vectors: vec![vec![1.0, -0.5], vec![0.25, 0.0]],
+        input_tokens: 6,
+    }
+}
+
+#[test]
+fn render_float_embeddings_as_an_openai_list() {
+    let bytes = render_embeddings(&response(), EmbeddingEncoding::Float);
+    let v: Value = serde_json::from_slice(&bytes).expect("json");
+    assert_eq!(v["object"], "list");
+    assert_eq!(v["model"], "text-embedding-3-small");
+    assert_eq!(v["data"][1]["object"], "embedding");
+    assert_eq!(v["data"][1]["index"], 1);
+    assert_eq!(v["data"][0]["embedding"][1], -0.5);
+    assert_eq!(v["usage"]["prompt_tokens"], 6);
+    assert_eq!(v["usage"]["total_tokens"], 6);
+}
+
+#[test]
+fn render_base64_packs_little_endian_f32() {
+    let bytes = render_embeddings(&response(), EmbeddingEncoding::Base64);
+    let v: Value = serde_json::from_slice(&bytes).expect("json");
+    let encoded = v["data"][0]["embedding"].as_str().expect("base64 string");
+    let raw = STANDARD.decode(encoded).expect("decode");
+    let floats: Vec<f32> = raw
+        .chunks_exact(4)
+        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+    assert_eq!(floats, vec![1.0, -0.5]);
+}
+
+#[test]
+fn render_error_uses_openai_error_shape() {
+    let bytes = OpenAiEmbeddingsInbound.render_error(http::StatusCode::BAD_REQUEST, "bad \"x\"");
+    let v: Value = serde_json::from_slice(&bytes).expect("json");
+    assert_eq!(v["error"]["message"], "bad \"x\"");
+}
@@ -11,6 +11,8 @@ use systemprompt_api::services::gateway::protocol::canonical_response::{
 };
 use systemprompt_api::services::gateway::protocol::inbound::InboundAdapter;
 use systemprompt_api::services::gateway::protocol::inbound::anthropic_messages::AnthropicMessagesInbound;
+use systemprompt_api::services::gateway::protocol::inbound::openai_chat::OpenAiChatInbound;
+use systemprompt_api::services::gateway::protocol::inbound::openai_embeddings::OpenAiEmbeddingsInbound;
 use systemprompt_api::services::gateway::protocol::inbound::openai_responses::OpenAiResponsesInbound;
 
 fn snapshot() -> CanonicalResponse {
@@ -76,3 +78,25 @@ fn each_adapter_names_its_own_wire() {
         "the wire name is recorded on the audit row; two wires must not share one"
     );
 }
+
+#[test]
+fn only_stateful_stream_renderers_bind_per_request() {
+    let body = bytes::Bytes::from_static(br#"{"model":"m","messages":[]}"#);
+    assert!(
+        AnthropicMessagesInbound.bind_request(&body).is_none(),
+        "a stateless renderer serves every request from the shared instance"
+    );
+    assert!(OpenAiResponsesInbound.bind_request(&body).is_none());
+    let bound = OpenAiChatInbound::default()
+        .bind_request(&body)
+        .expect("the Chat renderer carries a completion id across chunks");
+    assert_eq!(bound.wire_name(), "openai.chat");
+}
+
+#[test]
+fn only_the_embeddings_surface_takes_the_embeddings_lane() {
+    assert!(!AnthropicMessagesInbound.serves_embeddings());
+    assert!(!OpenAiResponsesInbound.serves_embeddings());
+    assert!(!OpenAiChatInbound::default().serves_embeddings());
+    assert!(OpenAiEmbeddingsInbound.serves_embeddings());
+}
@@ -37,6 +37,9 @@ fn route() -> GatewayRoute {
         extra_headers: HashMap::new(),
         pricing: None,
         when: None,
+        weight: 1,
+        targets: Vec::new(),
+        fallback: None,
     }
 }
 
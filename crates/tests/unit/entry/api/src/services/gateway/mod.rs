@@ -1,12 +1,17 @@
 mod audit_payload;
+mod budget_alert;
 mod canonical_request;
 mod canonical_response;
 mod captures;
+mod failover;
 mod inbound_anthropic;
 mod inbound_anthropic_deep;
 mod inbound_anthropic_render;
 mod inbound_openai;
+mod inbound_openai_chat;
+mod inbound_openai_chat_render;
 mod inbound_openai_deep;
+mod inbound_openai_embeddings;
 mod inbound_openai_render;
 mod inbound_trait_defaults;
 mod inspect_equals_send;
@@ -15,7 +20,9 @@ mod outbound_deep;
 mod outbound_passthrough;
 mod parse;
 mod pricing;
+mod rate_limit;
 mod registry;
+mod response_cache;
 mod safety;
 mod signature_cache;
 mod stream_tap;
@@ -43,6 +43,9 @@ fn route(provider: &str) -> GatewayRoute {
         extra_headers: HashMap::new(),
         pricing: None,
         when: None,
+        weight: 1,
+        targets: Vec::new(),
+        fallback: None,
     }
 }
 
@@ -53,6 +53,9 @@ fn route(provider: &str) -> GatewayRoute {
         extra_headers: extra,
         pricing: None,
         when: None,
+        weight: 1,
+        targets: Vec::new(),
+        fallback: None,
     }
 }
 
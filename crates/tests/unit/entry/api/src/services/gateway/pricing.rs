@@ -1,8 +1,8 @@
 use systemprompt_api::services::gateway::pricing::{CostTokens, cost_microdollars, resolve};
 use systemprompt_identifiers::{ModelId, ProviderId, RouteId, SecretName};
 use systemprompt_models::profile::{
-    ApiSurface, GatewayConfig, GatewayRoute, ProviderEntry, ProviderModel, ProviderRegistry,
-    WireProtocol,
+    ApiSurface, DiscoveredModels, GatewayConfig, GatewayRoute, ProviderEntry, ProviderModel,
+    ProviderRegistry, WireProtocol,
 };
 use systemprompt_models::services::ModelPricing;
 
@@ -15,6 +15,9 @@ fn route(pattern: &str, provider: &str, pricing: Option<ModelPricing>) -> Gatewa
         extra_headers: Default::default(),
         pricing,
         when: None,
+        weight: 1,
+        targets: Vec::new(),
+        fallback: None,
     }
 }
 
@@ -73,6 +76,8 @@ fn registry_pricing_used_when_no_route_override() {
                 capabilities: Default::default(),
                 limits: Default::default(),
             }],
+            local: None,
+            discovered: DiscoveredModels::default(),
         }],
     };
     let gw = gateway_with(vec![]);
@@ -104,6 +109,8 @@ fn resolve_falls_back_to_configured_model_when_served_alias_unknown() {
                 capabilities: Default::default(),
                 limits: Default::default(),
             }],
+            local: None,
+            discovered: DiscoveredModels::default(),
         }],
     };
     // First candidate is the dated alias the provider echoes (no catalog entry);
@@ -118,6 +125,58 @@ fn resolve_falls_back_to_configured_model_when_served_alias_unknown() {
     assert!((p.output_per_million - 2.0).abs() < f64::EPSILON);
 }
 
+fn provider(name: &str, model: &str, pricing: ModelPricing) -> ProviderEntry {
+    ProviderEntry {
+        name: ProviderId::new(name),
+        wire: WireProtocol::Anthropic,
+        surface: ApiSurface::Anthropic,
+        endpoint: format!("https://{name}.example.com/v1"),
+        api_key_secret: SecretName::new(name),
+        extra_headers: Default::default(),
+        models: vec![ProviderModel {
+            id: ModelId::new(model),
+            aliases: Vec::new(),
+            upstream_model: None,
+            pricing,
+            capabilities: Default::default(),
+            limits: Default::default(),
+        }],
+        local: None,
+        discovered: DiscoveredModels::default(),
+    }
+}
+
+#[test]
+fn fallback_hop_is_priced_by_the_provider_that_served_it() {
+    let rates = |input_per_million, output_per_million| ModelPricing {
+        input_per_million,
+        output_per_million,
+        ..ModelPricing::default()
+    };
+    let model = "claude-sonnet-4-20250514";
+    let registry = ProviderRegistry {
+        providers: vec![
+            provider("anthropic", model, rates(3.0, 15.0)),
+            provider("bedrock", model, rates(6.0, 30.0)),
+        ],
+    };
+
+    for route_pricing in [None, Some(rates(1.0, 2.0))] {
+        let gw = gateway_with(vec![route("claude-*", "anthropic", route_pricing)]);
+
+        let fallback = resolve("bedrock", &[model], Some(&gw), &registry);
+        assert!(
+            (fallback.input_per_million - 6.0).abs() < f64::EPSILON,
+            "{route_pricing:?}: {fallback:?}"
+        );
+        assert!((fallback.output_per_million - 30.0).abs() < f64::EPSILON);
+
+        let primary = resolve("anthropic", &[model], Some(&gw), &registry);
+        let expected = route_pricing.unwrap_or(rates(3.0, 15.0));
+        assert!((primary.input_per_million - expected.input_per_million).abs() < f64::EPSILON);
+    }
+}
+
 #[test]
 fn resolve_reads_pricing_from_seeded_registry() {
     let registry = ProviderRegistry::default_seed().expect("embedded default catalog parses");
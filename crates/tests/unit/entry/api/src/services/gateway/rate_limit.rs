@@ -0,0 +1,220 @@
+//! Unit tests for the gateway's in-process rate limiter: concurrency slots,
+//! the per-minute request and token buckets, and the provider-style headers a
+//! refusal is reported with.
+
+use std::time::{Duration, Instant};
+
+use axum::body::Body;
+use axum::response::Response;
+use systemprompt_ai::RateLimit;
+use systemprompt_api::services::gateway::protocol::inbound::InboundAdapter;
+use systemprompt_api::services::gateway::protocol::inbound::anthropic_messages::AnthropicMessagesInbound;
+use systemprompt_api::services::gateway::protocol::inbound::openai_chat::OpenAiChatInbound;
+use systemprompt_api::services::gateway::rate_limit::{
+    LimitStatus, LimitSubject, RateLimitStatus, RateLimiter,
+};
+
+fn subject(limit: &RateLimit) -> [LimitSubject<'_>; 1] {
+    [LimitSubject {
+        index: 0,
+        kind: "user",
+        id: "user-1",
+        limit,
+    }]
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_str())
+}
+
+#[test]
+fn concurrency_slot_is_released_when_the_permit_drops() {
+    let limiter = RateLimiter::default();
+    let limit = RateLimit {
+        max_concurrent: Some(1),
+        ..RateLimit::default()
+    };
+    let now = Instant::now();
+    let first = limiter
+        .try_admit(&subject(&limit), now)
+        .expect("first request fits");
+    let refused = limiter
+        .try_admit(&subject(&limit), now)
+        .expect_err("second concurrent request is refused");
+    assert!(refused.message.contains("1/1"), "{}", refused.message);
+    assert_eq!(refused.retry_after_seconds, 1);
+
+    drop(first);
+    limiter
+        .try_admit(&subject(&limit), now)
+        .expect("the slot is free again");
+}
+
+#[tokio::test]
+async fn a_held_response_keeps_its_slot_until_the_body_drops() {
+    let limiter = RateLimiter::default();
+    let limit = RateLimit {
+        max_concurrent: Some(1),
+        ..RateLimit::default()
+    };
+    let now = Instant::now();
+    let permit = limiter
+        .try_admit(&subject(&limit), now)
+        .expect("first request fits");
+    let response = permit.hold(Response::new(Body::from("streamed")));
+    assert!(limiter.try_admit(&subject(&limit), now).is_err());
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body reads");
+    assert_eq!(&body[..], b"streamed");
+    limiter
+        .try_admit(&subject(&limit), now)
+        .expect("reading the body to the end frees the slot");
+}
+
+#[test]
+fn requests_per_minute_refuse_the_burst_and_refill_continuously() {
+    let limiter = RateLimiter::default();
+    let limit = RateLimit {
+        requests_per_minute: Some(2),
+        ..RateLimit::default()
+    };
+    let now = Instant::now();
+    for _ in 0..2 {
+        limiter
+            .try_admit(&subject(&limit), now)
+            .expect("within the burst");
+    }
+    let refused = limiter
+        .try_admit(&subject(&limit), now)
+        .expect_err("the bucket is empty");
+    assert_eq!(refused.retry_after_seconds, 30);
+    let requests = refused.status.requests.expect("request bucket reported");
+    assert_eq!(requests.limit, 2);
+    assert_eq!(requests.remaining, 0);
+    assert_eq!(requests.reset, Duration::from_secs(60));
+    assert!(refused.status.tokens.is_none());
+
+    limiter
+        .try_admit(&subject(&limit), now + Duration::from_secs(30))
+        .expect("half a minute refills one request");
+}
+
+#[test]
+fn tokens_per_minute_hold_off_a_subject_in_debt() {
+    let limiter = RateLimiter::default();
+    let limit = RateLimit {
+        tokens_per_minute: Some(1_000),
+        ..RateLimit::default()
+    };
+    let subjects = subject(&limit);
+    let now = Instant::now();
+    drop(
+        limiter
+            .try_admit(&subjects, now)
+            .expect("a full bucket admits"),
+    );
+    limiter.debit_tokens(&subjects, 1_500, now);
+
+    let refused = limiter
+        .try_admit(&subjects, now)
+        .expect_err("a bucket in debt refuses");
+    assert_eq!(refused.retry_after_seconds, 31);
+    assert_eq!(
+        refused
+            .status
+            .tokens
+            .expect("token bucket reported")
+            .remaining,
+        0
+    );
+    limiter
+        .try_admit(&subjects, now + Duration::from_secs(31))
+        .expect("the debt has been paid down");
+}
+
+#[test]
+fn a_refusal_charges_no_other_limit() {
+    let limiter = RateLimiter::default();
+    let roomy = RateLimit {
+        requests_per_minute: Some(1),
+        ..RateLimit::default()
+    };
+    let tight = RateLimit {
+        max_concurrent: Some(1),
+        ..RateLimit::default()
+    };
+    let subjects = [
+        LimitSubject {
+            index: 0,
+            kind: "user",
+            id: "user-1",
+            limit: &roomy,
+        },
+        LimitSubject {
+            index: 1,
+            kind: "user",
+            id: "user-1",
+            limit: &tight,
+        },
+    ];
+    let now = Instant::now();
+    let held = limiter
+        .try_admit(&subjects[1..], now)
+        .expect("the concurrency slot is taken first");
+    assert!(limiter.try_admit(&subjects, now).is_err());
+    drop(held);
+    limiter
+        .try_admit(&subjects, now)
+        .expect("the refused request spent none of the per-minute budget");
+}
+
+fn status() -> RateLimitStatus {
+    RateLimitStatus {
+        requests: Some(LimitStatus {
+            limit: 60,
+            remaining: 0,
+            reset: Duration::from_millis(90_500),
+        }),
+        tokens: Some(LimitStatus {
+            limit: 10_000,
+            remaining: 2_500,
+            reset: Duration::from_millis(250),
+        }),
+    }
+}
+
+#[test]
+fn anthropic_surface_reports_anthropic_ratelimit_headers() {
+    let headers = AnthropicMessagesInbound.rate_limit_headers(&status());
+    assert_eq!(
+        header(&headers, "anthropic-ratelimit-requests-limit"),
+        Some("60")
+    );
+    assert_eq!(
+        header(&headers, "anthropic-ratelimit-tokens-remaining"),
+        Some("2500")
+    );
+    let reset = header(&headers, "anthropic-ratelimit-requests-reset").expect("reset present");
+    chrono::DateTime::parse_from_rfc3339(reset).expect("reset is an RFC 3339 timestamp");
+    assert!(header(&headers, "x-ratelimit-limit-requests").is_none());
+}
+
+#[test]
+fn openai_surface_reports_x_ratelimit_headers() {
+    let headers = OpenAiChatInbound::default().rate_limit_headers(&status());
+    assert_eq!(header(&headers, "x-ratelimit-limit-requests"), Some("60"));
+    assert_eq!(
+        header(&headers, "x-ratelimit-remaining-tokens"),
+        Some("2500")
+    );
+    assert_eq!(
+        header(&headers, "x-ratelimit-reset-requests"),
+        Some("1m31s")
+    );
+    assert_eq!(header(&headers, "x-ratelimit-reset-tokens"), Some("250ms"));
+}
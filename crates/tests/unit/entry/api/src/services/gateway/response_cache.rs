@@ -0,0 +1,215 @@
+//! Unit tests for the gateway response cache: eligibility, key normalization
+//! and partitioning, the stored-entry round trip, and synthetic stream replay.
+
+use serde_json::json;
+use systemprompt_ai::ResponseCacheScope;
+use systemprompt_api::services::gateway::protocol::canonical::{
+    CanonicalContent, CanonicalMessage, CanonicalRequest, CanonicalTool, ImageSource, Role,
+    SearchConfig,
+};
+use systemprompt_api::services::gateway::protocol::canonical_response::{
+    CanonicalResponse, CanonicalStopReason, CanonicalUsage,
+};
+use systemprompt_api::services::gateway::response_cache::{
+    KeyPartition, StoredResponse, cache_key, is_cacheable, replay_events,
+};
+use systemprompt_api::services::gateway::stream_tap::test_api::{
+    TapState, accumulate_event, extract_summary,
+};
+
+fn request(text: &str) -> CanonicalRequest {
+    CanonicalRequest {
+        model: "claude-test".into(),
+        system: Some("be terse".into()),
+        messages: vec![CanonicalMessage {
+            role: Role::User,
+            content: vec![CanonicalContent::Text(text.into())],
+        }],
+        max_tokens: 256,
+        temperature: Some(0.0),
+        ..CanonicalRequest::default()
+    }
+}
+
+fn partition(scope: ResponseCacheScope, user_id: &str) -> KeyPartition<'_> {
+    KeyPartition {
+        scope,
+        user_id,
+        provider: "anthropic",
+        upstream_model: "claude-upstream",
+    }
+}
+
+fn user_key(request: &CanonicalRequest) -> String {
+    cache_key(request, partition(ResponseCacheScope::User, "user-a"))
+}
+
+fn response() -> CanonicalResponse {
+    CanonicalResponse {
+        id: "msg_cached".into(),
+        model: "claude-upstream".into(),
+        content: vec![
+            CanonicalContent::Thinking {
+                text: "consider".into(),
+                signature: Some("sig".into()),
+                id: None,
+                encrypted_content: None,
+            },
+            CanonicalContent::Text("hello".into()),
+            CanonicalContent::ToolUse {
+                id: "toolu_1".into(),
+                name: "lookup".into(),
+                input: json!({"q": "x"}),
+                signature: None,
+            },
+        ],
+        stop_reason: Some(CanonicalStopReason::ToolUse),
+        usage: CanonicalUsage {
+            input_tokens: 12,
+            output_tokens: 7,
+            cache_read_tokens: 3,
+            cache_creation_tokens: 0,
+            total_tokens: 19,
+        },
+        ..CanonicalResponse::default()
+    }
+}
+
+#[test]
+fn only_temperature_zero_requests_are_cacheable() {
+    assert!(is_cacheable(&request("hi")));
+    let mut warm = request("hi");
+    warm.temperature = Some(0.7);
+    assert!(!is_cacheable(&warm));
+    let mut unset = request("hi");
+    unset.temperature = None;
+    assert!(!is_cacheable(&unset));
+}
+
+#[test]
+fn requests_with_outside_inputs_are_not_cacheable() {
+    let mut searched = request("hi");
+    searched.search = Some(SearchConfig::default());
+    assert!(!is_cacheable(&searched));
+
+    let mut remote_image = request("hi");
+    remote_image.messages[0]
+        .content
+        .push(CanonicalContent::Image(ImageSource::Url {
+            url: "https://example.com/a.png".into(),
+            detail: None,
+        }));
+    assert!(!is_cacheable(&remote_image));
+}
+
+#[test]
+fn key_ignores_stream_and_metadata() {
+    let buffered = request("hi");
+    let mut streamed = request("hi");
+    streamed.stream = true;
+    streamed.metadata = Some(json!({"trace": "abc"}));
+    assert_eq!(user_key(&buffered), user_key(&streamed));
+}
+
+#[test]
+fn key_ignores_tool_schema_key_order() {
+    let mut a = request("hi");
+    a.tools = vec![CanonicalTool {
+        name: "lookup".into(),
+        description: None,
+        input_schema: json!({"type": "object", "properties": {"q": {"type": "string"}}}),
+    }];
+    let mut b = request("hi");
+    b.tools = vec![CanonicalTool {
+        name: "lookup".into(),
+        description: None,
+        input_schema: json!({"properties": {"q": {"type": "string"}}, "type": "object"}),
+    }];
+    assert_eq!(user_key(&a), user_key(&b));
+}
+
+#[test]
+fn key_changes_with_prompt_system_or_sampling() {
+    let base = user_key(&request("hi"));
+    assert_ne!(base, user_key(&request("hello")));
+
+    let mut other_system = request("hi");
+    other_system.system = Some("be verbose".into());
+    assert_ne!(base, user_key(&other_system));
+
+    let mut other_max = request("hi");
+    other_max.max_tokens = 512;
+    assert_ne!(base, user_key(&other_max));
+}
+
+#[test]
+fn user_scope_separates_callers_and_tenant_scope_shares() {
+    let req = request("hi");
+    let a = cache_key(&req, partition(ResponseCacheScope::User, "user-a"));
+    let b = cache_key(&req, partition(ResponseCacheScope::User, "user-b"));
+    assert_ne!(a, b);
+
+    let ta = cache_key(&req, partition(ResponseCacheScope::Tenant, "user-a"));
+    let tb = cache_key(&req, partition(ResponseCacheScope::Tenant, "user-b"));
+    assert_eq!(ta, tb);
+    assert_ne!(a, ta);
+}
+
+#[test]
+fn key_is_partitioned_by_upstream_model() {
+    let req = request("hi");
+    let a = cache_key(&req, partition(ResponseCacheScope::User, "user-a"));
+    let b = cache_key(
+        &req,
+        KeyPartition {
+            upstream_model: "claude-other",
+            ..partition(ResponseCacheScope::User, "user-a")
+        },
+    );
+    assert_ne!(a, b);
+}
+
+#[test]
+fn stored_entry_round_trips_content_stop_reason_and_usage() {
+    let stored = StoredResponse::from_canonical(&response()).expect("storable");
+    let value = serde_json::to_value(&stored).expect("ser");
+    let back: StoredResponse = serde_json::from_value(value).expect("de");
+    let restored = back.into_canonical();
+
+    assert_eq!(restored.id, "msg_cached");
+    assert_eq!(restored.stop_reason, Some(CanonicalStopReason::ToolUse));
+    assert_eq!(restored.usage.input_tokens, 12);
+    assert_eq!(restored.usage.output_tokens, 7);
+    assert_eq!(restored.usage.cache_read_tokens, 3);
+    assert_eq!(restored.content.len(), 3);
+    assert!(matches!(
+        &restored.content[0],
+        CanonicalContent::Thinking { signature: Some(s), .. } if s == "sig"
+    ));
+    assert!(matches!(&restored.content[1], CanonicalContent::Text(t) if t == "hello"));
+    assert!(matches!(
+        &restored.content[2],
+        CanonicalContent::ToolUse { name, input, .. } if name == "lookup" && input["q"] == "x"
+    ));
+}
+
+#[test]
+fn replayed_stream_accumulates_back_to_the_cached_response() {
+    let mut state = TapState::default();
+    for event in replay_events(&response()) {
+        accumulate_event(&mut state, &event);
+    }
+    let summary = extract_summary(&mut state);
+
+    assert!(summary.saw_stop);
+    assert_eq!(summary.usage.input_tokens, 12);
+    assert_eq!(summary.usage.output_tokens, 7);
+    assert_eq!(summary.tool_calls.len(), 1);
+    assert_eq!(summary.tool_calls[0].tool_name, "lookup");
+    assert_eq!(
+        summary.response.stop_reason,
+        Some(CanonicalStopReason::ToolUse)
+    );
+    assert_eq!(summary.response.content.len(), 3);
+    assert!(matches!(&summary.response.content[1], CanonicalContent::Text(t) if t == "hello"));
+}
@@ -7,12 +7,13 @@
 #![allow(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 
 use clap::Parser;
+use systemprompt_agent::models::NewContextSummary;
 use systemprompt_agent::models::context::ContextKind;
 use systemprompt_agent::repository::ContextRepository;
 use systemprompt_cli::analytics::{self, AnalyticsCommands};
 use systemprompt_cli::{CliConfig, CommandContext, EnvOverrides, OutputFormat};
 use systemprompt_database::DbPool;
-use systemprompt_identifiers::SessionId;
+use systemprompt_identifiers::{ContextId, SessionId};
 use systemprompt_runtime::DatabaseContext;
 use systemprompt_test_fixtures::{
     fixture_database_url, fixture_db_pool, seed_user_row, seed_user_session, unique_user_id,
@@ -155,3 +156,43 @@ async fn a_limit_of_one_still_renders_a_row() {
     let rows = csv.lines().skip(1).filter(|l| !l.trim().is_empty()).count();
     assert_eq!(rows, 1, "{csv}");
 }
+
+#[tokio::test]
+async fn stored_summaries_are_counted_and_listed() {
+    let pool = pool().await;
+    let context_id = seed_conversation(&pool).await;
+    ContextRepository::new(&pool)
+        .unwrap()
+        .create_summary(&NewContextSummary {
+            context_id: &ContextId::new(&context_id),
+            agent_name: "covconvo_summarizer",
+            summary: "The user asked about invoices.",
+            covered_messages: 6,
+            token_estimate: 12,
+        })
+        .await
+        .unwrap();
+    let ctx = ctx(&pool);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("summarized.csv");
+    analytics::execute(
+        parse(&[
+            "conversations",
+            "list",
+            "--limit",
+            "200",
+            "--export",
+            path.to_str().unwrap(),
+        ]),
+        &ctx,
+    )
+    .await
+    .unwrap();
+    let csv = std::fs::read_to_string(&path).unwrap();
+    assert!(csv.contains("summary_count"), "{csv}");
+
+    analytics::execute(parse(&["conversations", "summaries", &context_id]), &ctx)
+        .await
+        .unwrap();
+}
@@ -57,6 +57,7 @@ fn profile(cloud: Option<CloudConfig>) -> Profile {
             allowed_resource_audiences: vec![],
             allow_registration: true,
             login_page_url: None,
+            oidc_providers: Vec::new(),
             signing_key_path: PathBuf::from("/tmp/test-signing-key.pem"),
             trusted_issuers: vec![],
             id_jag_ttl_secs: systemprompt_models::profile::DEFAULT_ID_JAG_TTL_SECS,
@@ -39,6 +39,8 @@ fn deployment(port: u16) -> Deployment {
         env_vars: vec![],
         external_auth: None,
         headers: HashMap::default(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -51,6 +51,8 @@ fn deployment(binary: &str, dev_only: bool) -> Deployment {
         env_vars: vec![],
         external_auth: None,
         headers: HashMap::new(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
@@ -101,6 +101,9 @@ fn route(id: &str) -> systemprompt_models::profile::GatewayRoute {
         extra_headers: std::collections::HashMap::new(),
         pricing: None,
         when: None,
+        weight: 1,
+        targets: Vec::new(),
+        fallback: None,
     }
 }
 
@@ -14,6 +14,7 @@ fn security() -> SecurityConfig {
         allowed_resource_audiences: default_resource_audiences(),
         allow_registration: true,
         login_page_url: None,
+        oidc_providers: Vec::new(),
         signing_key_path: PathBuf::from("signing_key.pem"),
         trusted_issuers: Vec::new(),
         id_jag_ttl_secs: systemprompt_models::profile::DEFAULT_ID_JAG_TTL_SECS,
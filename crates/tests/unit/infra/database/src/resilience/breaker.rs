@@ -73,3 +73,19 @@ async fn half_open_probe_failure_reopens_the_breaker() {
     breaker.record_failure();
     assert!(breaker.is_open());
 }
+
+#[tokio::test]
+async fn released_probe_leaves_the_breaker_half_open() {
+    let breaker = CircuitBreaker::new("dep", config());
+    for _ in 0..3 {
+        breaker.record_failure();
+    }
+
+    tokio::time::sleep(Duration::from_millis(35)).await;
+    assert!(breaker.acquire().is_ok());
+
+    // The probe was never sent, so its slot goes back and the next call probes.
+    breaker.release();
+    assert!(breaker.acquire().is_ok());
+    assert!(breaker.acquire().is_err());
+}
@@ -134,6 +134,8 @@ fn get_production_mcp_binary_names_excludes_dev_only() {
             env_vars: vec![],
             external_auth: None,
             headers: Default::default(),
+            stdio: None,
+            sandbox: None,
         },
     );
 
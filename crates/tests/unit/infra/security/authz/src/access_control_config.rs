@@ -3,7 +3,7 @@
 //! These cover the pure CPU/serde paths only — DB-backed ingestion is
 //! exercised separately under `crates/tests/integration/`.
 
-use systemprompt_security::authz::AccessControlConfig;
+use systemprompt_security::authz::{AccessControlConfig, EnforceMode};
 
 const VALID_YAML: &str = r#"
 rules:
@@ -161,3 +161,44 @@ rules:
         serde_yaml::from_str(&serialized).expect("glob rule round-trips");
     assert_eq!(reparsed.rules.len(), 1);
 }
+
+#[test]
+fn enforce_defaults_to_enforce_and_round_trips_shadow() {
+    let yaml = r#"
+rules:
+  - entity_type: agent
+    entity_id: planner
+    access: deny
+    roles: [contractor]
+    enforce: shadow
+  - entity_type: agent
+    entity_id: writer
+    access: allow
+    roles: [user]
+"#;
+    let cfg: AccessControlConfig = serde_yaml::from_str(yaml).expect("enforce parses");
+    assert_eq!(cfg.rules[0].enforce, EnforceMode::Shadow);
+    assert_eq!(cfg.rules[1].enforce, EnforceMode::Enforce);
+
+    let serialized = serde_yaml::to_string(&cfg).unwrap();
+    assert_eq!(
+        serialized.matches("enforce").count(),
+        1,
+        "only the shadow rule spells out its mode, got: {serialized}"
+    );
+    let reparsed: AccessControlConfig = serde_yaml::from_str(&serialized).unwrap();
+    assert_eq!(reparsed.rules[0].enforce, EnforceMode::Shadow);
+}
+
+#[test]
+fn rejects_an_unknown_enforce_mode() {
+    let bad = r#"
+rules:
+  - entity_type: agent
+    entity_id: foo
+    access: allow
+    roles: [user]
+    enforce: audit
+"#;
+    assert!(serde_yaml::from_str::<AccessControlConfig>(bad).is_err());
+}
@@ -1,5 +1,5 @@
 use systemprompt_security::authz::{
-    Access, AccessControlConfig, EntityKind, RuleEntry, RuleTarget,
+    Access, AccessControlConfig, EnforceMode, EntityKind, RuleEntry, RuleTarget,
 };
 
 fn make_rule(
@@ -15,6 +15,7 @@ fn make_rule(
         default_included: false,
         roles: roles.iter().map(|s| s.to_string()).collect(),
         justification: None,
+        enforce: EnforceMode::Enforce,
     }
 }
 
@@ -128,6 +129,7 @@ fn rule_entry_with_justification() {
         default_included: false,
         roles: vec!["external".to_owned()],
         justification: Some("ITAR restriction".to_owned()),
+        enforce: EnforceMode::Enforce,
     };
     let cfg = AccessControlConfig { rules: vec![rule] };
     assert!(cfg.validate().is_ok());
@@ -143,6 +145,7 @@ fn entity_match_rule_validates() {
             default_included: true,
             roles: vec!["user".to_owned()],
             justification: None,
+            enforce: EnforceMode::Enforce,
         }],
     };
     assert!(cfg.validate().is_ok());
@@ -158,6 +161,7 @@ fn empty_entity_match_fails() {
             default_included: false,
             roles: vec!["user".to_owned()],
             justification: None,
+            enforce: EnforceMode::Enforce,
         }],
     };
     let err = cfg.validate().unwrap_err();
@@ -45,6 +45,8 @@ mod resolver_parents;
 #[cfg(test)]
 mod rule_based_hook;
 #[cfg(test)]
+mod simulate;
+#[cfg(test)]
 mod subject_attributes;
 #[cfg(test)]
 mod webhook_hook;
@@ -1,8 +1,8 @@
 use systemprompt_identifiers::RouteId;
-use systemprompt_security::authz::resolver::{ResolveInput, resolve};
+use systemprompt_security::authz::resolver::{ResolveInput, resolve, resolve_shadow};
 use systemprompt_security::authz::subject::{SubjectAttributes, SubjectDimension};
 use systemprompt_security::authz::types::{
-    Access, AccessRule, Decision, DenyReason, EntityRef, MatchedBy, RuleType,
+    Access, AccessRule, Decision, DenyReason, EnforceMode, EntityRef, MatchedBy, RuleType,
 };
 use systemprompt_test_fixtures::fixture_user_id;
 
@@ -13,6 +13,7 @@ fn rule(rule_type: RuleType, value: &str, access: Access) -> AccessRule {
         rule_value: value.into(),
         access,
         justification: None,
+        enforce: EnforceMode::Enforce,
     }
 }
 
@@ -379,3 +380,93 @@ fn extension_slug_rejects_core_builtins_and_malformed_input() {
     assert!(RuleType::extension("").is_err());
     assert!(RuleType::extension("cost_centre").is_ok());
 }
+
+fn shadow(rule_type: RuleType, value: &str, access: Access) -> AccessRule {
+    AccessRule {
+        enforce: EnforceMode::Shadow,
+        ..rule(rule_type, value, access)
+    }
+}
+
+#[test]
+fn shadow_rules_do_not_decide() {
+    let e = entity();
+    let u = fixture_user_id();
+    let roles: Vec<String> = vec!["eng".into()];
+    let rules = vec![
+        rule(RuleType::ROLE, "eng", Access::Allow),
+        shadow(RuleType::ROLE, "eng", Access::Deny),
+    ];
+    let d = resolve(input(&e, &rules, &u, &roles, Some(false)));
+    assert!(
+        matches!(
+            d,
+            Decision::Allow {
+                matched_by: MatchedBy::RoleAllow { .. }
+            }
+        ),
+        "a shadow deny must not outrank an enforced allow: {d:?}"
+    );
+}
+
+#[test]
+fn resolve_shadow_is_none_without_shadow_rules() {
+    let e = entity();
+    let u = fixture_user_id();
+    let roles: Vec<String> = vec!["eng".into()];
+    let rules = vec![rule(RuleType::ROLE, "eng", Access::Allow)];
+    assert_eq!(
+        resolve_shadow(input(&e, &rules, &u, &roles, Some(false))),
+        None
+    );
+}
+
+#[test]
+fn resolve_shadow_decides_as_if_shadow_rules_were_enforced() {
+    let e = entity();
+    let u = fixture_user_id();
+    let roles: Vec<String> = vec!["eng".into()];
+    let rules = vec![
+        rule(RuleType::ROLE, "eng", Access::Allow),
+        shadow(RuleType::ROLE, "eng", Access::Deny),
+    ];
+    let d = resolve_shadow(input(&e, &rules, &u, &roles, Some(false)));
+    assert!(
+        matches!(
+            d,
+            Some(Decision::Deny {
+                reason: DenyReason::RoleDeny { .. }
+            })
+        ),
+        "got {d:?}"
+    );
+}
+
+#[test]
+fn a_shadow_grant_is_reported_without_granting() {
+    let e = entity();
+    let u = fixture_user_id();
+    let roles: Vec<String> = vec!["eng".into()];
+    let rules = vec![shadow(RuleType::ROLE, "eng", Access::Allow)];
+
+    let live = resolve(input(&e, &rules, &u, &roles, Some(false)));
+    assert!(
+        matches!(
+            live,
+            Decision::Deny {
+                reason: DenyReason::NotAssigned { .. }
+            }
+        ),
+        "got {live:?}"
+    );
+    let would = resolve_shadow(input(&e, &rules, &u, &roles, Some(false)));
+    assert!(
+        matches!(
+            would,
+            Some(Decision::Allow {
+                matched_by: MatchedBy::RoleAllow { .. }
+            })
+        ),
+        "got {would:?}"
+    );
+}
@@ -1,6 +1,6 @@
 use systemprompt_identifiers::{MarketplaceId, SkillId};
 use systemprompt_security::authz::types::{
-    Access, AccessRule, Decision, DenyReason, EntityRef, MatchedBy, RuleType,
+    Access, AccessRule, Decision, DenyReason, EnforceMode, EntityRef, MatchedBy, RuleType,
 };
 use systemprompt_security::authz::{ResolveInput, ResolveParent, resolve};
 use systemprompt_test_fixtures::fixture_user_id;
@@ -12,6 +12,7 @@ fn rule(rule_type: RuleType, value: &str, access: Access) -> AccessRule {
         rule_value: value.into(),
         access,
         justification: None,
+        enforce: EnforceMode::Enforce,
     }
 }
 
@@ -0,0 +1,219 @@
+//! Unit tests for replaying recorded decisions against a candidate rules file.
+
+use std::collections::HashMap;
+
+use serde_json::json;
+use systemprompt_identifiers::{AgentId, RuleId, UserId};
+use systemprompt_security::authz::types::{
+    Access, AccessRule, DecisionTag, EnforceMode, EntityKind, EntityRef, EntityRow, RuleType,
+};
+use systemprompt_security::authz::{
+    AccessControlConfig, AccessControlSimulation, LiveAccessControl, RecordedDecision,
+    RecordedDecisionRow,
+};
+
+fn row(id: &str, entity_id: &str, roles: &[&str], decision: &str) -> RecordedDecisionRow {
+    RecordedDecisionRow {
+        id: id.to_owned(),
+        user_id: "u1".to_owned(),
+        decision: decision.to_owned(),
+        evaluated_rules: Some(json!({
+            "entity_type": "agent",
+            "entity_id": entity_id,
+            "roles": roles,
+            "source": "rule",
+        })),
+        created_at: chrono::Utc::now(),
+    }
+}
+
+fn recorded(id: &str, entity_id: &str, roles: &[&str], decision: DecisionTag) -> RecordedDecision {
+    RecordedDecision {
+        id: id.to_owned(),
+        created_at: chrono::Utc::now(),
+        user_id: UserId::new("u1"),
+        roles: roles.iter().map(|r| (*r).to_owned()).collect(),
+        entity: EntityRef::Agent(AgentId::new(entity_id)),
+        decision,
+    }
+}
+
+fn agent(id: &str) -> EntityRow {
+    EntityRow {
+        kind: EntityKind::Agent,
+        id: id.to_owned(),
+        default_included: false,
+        source: "test".to_owned(),
+    }
+}
+
+fn role_rule(role: &str, access: Access) -> AccessRule {
+    AccessRule {
+        id: RuleId::new(format!("live-{role}-{access}")),
+        rule_type: RuleType::ROLE,
+        rule_value: role.to_owned(),
+        access,
+        justification: None,
+        enforce: EnforceMode::Enforce,
+    }
+}
+
+/// Two agents; `planner` is granted to `eng` and `writer` to `user`.
+fn live() -> LiveAccessControl {
+    LiveAccessControl {
+        entities: vec![agent("planner"), agent("writer")],
+        rules: HashMap::from([
+            (
+                (EntityKind::Agent, "planner".to_owned()),
+                vec![role_rule("eng", Access::Allow)],
+            ),
+            (
+                (EntityKind::Agent, "writer".to_owned()),
+                vec![role_rule("user", Access::Allow)],
+            ),
+        ]),
+    }
+}
+
+fn candidate(yaml: &str) -> AccessControlConfig {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+#[test]
+fn a_row_from_the_rule_based_sink_is_replayable() {
+    let parsed = RecordedDecision::from_row(&row("d1", "planner", &["eng"], "allow")).unwrap();
+    assert_eq!(parsed.entity, EntityRef::Agent(AgentId::new("planner")));
+    assert_eq!(parsed.roles, ["eng"]);
+    assert_eq!(parsed.decision, DecisionTag::Allow);
+    assert_eq!(parsed.user_id.as_str(), "u1");
+}
+
+#[test]
+fn rows_without_an_entity_or_a_decision_are_not_replayable() {
+    let mut no_blob = row("d1", "planner", &["eng"], "allow");
+    no_blob.evaluated_rules = None;
+    assert!(RecordedDecision::from_row(&no_blob).is_none());
+
+    let mut no_entity = row("d2", "planner", &["eng"], "allow");
+    no_entity.evaluated_rules = Some(json!({ "roles": ["eng"] }));
+    assert!(RecordedDecision::from_row(&no_entity).is_none());
+
+    assert!(RecordedDecision::from_row(&row("d3", "planner", &["eng"], "pending")).is_none());
+}
+
+#[test]
+fn a_candidate_deny_flips_a_recorded_allow() {
+    let sim = AccessControlSimulation::new(
+        live(),
+        &candidate(
+            r#"
+rules:
+  - entity_type: agent
+    entity_id: planner
+    access: deny
+    roles: [eng]
+"#,
+        ),
+        false,
+    )
+    .unwrap();
+
+    let report = sim.replay(&[
+        recorded("d1", "planner", &["eng"], DecisionTag::Allow),
+        recorded("d2", "writer", &["user"], DecisionTag::Allow),
+    ]);
+
+    assert_eq!(report.replayed, 2);
+    assert_eq!(report.unchanged, 1);
+    assert_eq!(report.drifted, 0);
+    assert_eq!(report.allow_to_deny(), 1);
+    assert_eq!(report.deny_to_allow(), 0);
+    assert_eq!(report.flips[0].recorded.id, "d1");
+    assert_eq!(report.flips[0].candidate.tag(), DecisionTag::Deny);
+}
+
+#[test]
+fn a_glob_grant_flips_recorded_denies_across_the_catalog() {
+    let sim = AccessControlSimulation::new(
+        live(),
+        &candidate(
+            r#"
+rules:
+  - entity_type: agent
+    entity_match: "*"
+    access: allow
+    roles: [ops]
+"#,
+        ),
+        false,
+    )
+    .unwrap();
+
+    let report = sim.replay(&[
+        recorded("d1", "planner", &["ops"], DecisionTag::Deny),
+        recorded("d2", "writer", &["ops"], DecisionTag::Deny),
+    ]);
+
+    assert_eq!(report.deny_to_allow(), 2);
+    assert_eq!(report.unchanged, 0);
+}
+
+#[test]
+fn delete_orphans_drops_live_role_rules_the_file_no_longer_lists() {
+    let yaml = r#"
+rules:
+  - entity_type: agent
+    entity_id: planner
+    access: allow
+    roles: [ops]
+"#;
+    let rows = [recorded("d1", "planner", &["eng"], DecisionTag::Allow)];
+
+    let merged = AccessControlSimulation::new(live(), &candidate(yaml), false).unwrap();
+    assert!(
+        merged.replay(&rows).flips.is_empty(),
+        "upserting an ops grant leaves the live eng grant in place"
+    );
+
+    let replaced = AccessControlSimulation::new(live(), &candidate(yaml), true).unwrap();
+    assert_eq!(replaced.replay(&rows).allow_to_deny(), 1);
+}
+
+#[test]
+fn rows_the_live_rules_already_decide_differently_count_as_drifted() {
+    let sim = AccessControlSimulation::new(live(), &AccessControlConfig::default(), false).unwrap();
+
+    let report = sim.replay(&[recorded("d1", "writer", &["eng"], DecisionTag::Allow)]);
+
+    assert_eq!(report.drifted, 1);
+    assert_eq!(report.allow_to_deny(), 1);
+}
+
+#[test]
+fn rows_without_recorded_roles_are_skipped_and_counted() {
+    let sim = AccessControlSimulation::new(live(), &AccessControlConfig::default(), false).unwrap();
+
+    let report = sim.replay(&[
+        recorded("d1", "planner", &[], DecisionTag::Allow),
+        recorded("d2", "planner", &["eng"], DecisionTag::Allow),
+    ]);
+
+    assert_eq!(report.without_roles, 1);
+    assert_eq!(report.replayed, 1);
+    assert_eq!(report.unchanged, 1);
+    assert!(report.flips.is_empty());
+}
+
+#[test]
+fn an_invalid_candidate_is_rejected() {
+    let bad = candidate(
+        r#"
+rules:
+  - entity_type: agent
+    entity_id: planner
+    access: allow
+    roles: []
+"#,
+    );
+    assert!(AccessControlSimulation::new(live(), &bad, false).is_err());
+}
@@ -58,6 +58,8 @@ fn base_claims() -> JwtClaims {
         rate_limit_tier: Some(RateLimitTier::User),
         plugin_id: None,
         act: None,
+        cnf: None,
+        parent_jti: None,
     }
 }
 
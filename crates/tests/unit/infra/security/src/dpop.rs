@@ -0,0 +1,207 @@
+//! RFC 9449 `DPoP` proof verification and stateless nonces.
+//!
+//! Proofs are signed with a committed RSA test key and carry its public `jwk`
+//! in the header, as a client would send them. Each rejection test changes one
+//! thing about an otherwise valid proof.
+
+use jsonwebtoken::jwk::{Jwk, ThumbprintHash};
+use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use serde_json::json;
+use systemprompt_security::DpopError;
+use systemprompt_security::dpop::{
+    DPOP_NONCE_WINDOW_SECONDS, DpopProofCheck, access_token_hash, issue_nonce_with,
+    verify_nonce_with, verify_proof,
+};
+
+const URL: &str = "https://api.example.com/api/v1/core/oauth/token";
+const NOW: i64 = 1_800_000_000;
+
+struct ProofKey {
+    encoding: EncodingKey,
+    jwk: Jwk,
+}
+
+fn proof_key() -> ProofKey {
+    let pem = systemprompt_test_fixtures::next_test_key()
+        .to_pkcs8_pem()
+        .expect("pem");
+    let encoding = EncodingKey::from_rsa_pem(pem.as_bytes()).expect("encoding key");
+    let jwk = Jwk::from_encoding_key(&encoding, Algorithm::RS256).expect("public jwk");
+    ProofKey { encoding, jwk }
+}
+
+fn dpop_header(key: &ProofKey) -> Header {
+    let mut header = Header::new(Algorithm::RS256);
+    header.typ = Some("dpop+jwt".to_owned());
+    header.jwk = Some(key.jwk.clone());
+    header
+}
+
+fn sign(key: &ProofKey, header: &Header, claims: &serde_json::Value) -> String {
+    encode(header, claims, &key.encoding).expect("sign proof")
+}
+
+fn claims() -> serde_json::Value {
+    json!({
+        "jti": "proof-1",
+        "htm": "POST",
+        "htu": URL,
+        "iat": NOW,
+    })
+}
+
+fn check(access_token: Option<&str>) -> DpopProofCheck<'_> {
+    DpopProofCheck {
+        method: "POST",
+        url: URL,
+        access_token,
+        now: NOW,
+    }
+}
+
+#[test]
+fn a_valid_proof_yields_the_key_thumbprint() {
+    let key = proof_key();
+    let proof = sign(&key, &dpop_header(&key), &claims());
+
+    let verified = verify_proof(&proof, &check(None)).expect("valid proof");
+
+    assert_eq!(verified.jkt, key.jwk.thumbprint(ThumbprintHash::SHA256));
+    assert_eq!(verified.jti, "proof-1");
+    assert_eq!(verified.iat, NOW);
+    assert!(verified.replay_window_end() > NOW);
+}
+
+#[test]
+fn query_and_fragment_are_ignored_when_matching_htu() {
+    let key = proof_key();
+    let mut body = claims();
+    body["htu"] = json!(format!("{URL}?ignored=1#frag"));
+    let proof = sign(&key, &dpop_header(&key), &body);
+
+    assert!(verify_proof(&proof, &check(None)).is_ok());
+}
+
+#[test]
+fn a_proof_for_another_method_is_rejected() {
+    let key = proof_key();
+    let mut body = claims();
+    body["htm"] = json!("GET");
+    let proof = sign(&key, &dpop_header(&key), &body);
+
+    let err = verify_proof(&proof, &check(None)).expect_err("wrong htm");
+    assert!(matches!(err, DpopError::MethodMismatch { .. }), "{err}");
+}
+
+#[test]
+fn a_proof_for_another_url_is_rejected() {
+    let key = proof_key();
+    let mut body = claims();
+    body["htu"] = json!("https://api.example.com/api/v1/core/oauth/revoke");
+    let proof = sign(&key, &dpop_header(&key), &body);
+
+    let err = verify_proof(&proof, &check(None)).expect_err("wrong htu");
+    assert!(matches!(err, DpopError::UriMismatch), "{err}");
+}
+
+#[test]
+fn an_old_or_future_proof_is_stale() {
+    let key = proof_key();
+    for iat in [NOW - 3600, NOW + 3600] {
+        let mut body = claims();
+        body["iat"] = json!(iat);
+        let proof = sign(&key, &dpop_header(&key), &body);
+
+        let err = verify_proof(&proof, &check(None)).expect_err("iat outside window");
+        assert!(matches!(err, DpopError::Stale), "iat {iat}: {err}");
+    }
+}
+
+#[test]
+fn a_proof_must_hash_the_token_it_accompanies() {
+    let key = proof_key();
+    let mut body = claims();
+    body["ath"] = json!(access_token_hash("the-token"));
+    let proof = sign(&key, &dpop_header(&key), &body);
+
+    assert!(verify_proof(&proof, &check(Some("the-token"))).is_ok());
+    let err = verify_proof(&proof, &check(Some("another-token"))).expect_err("ath mismatch");
+    assert!(matches!(err, DpopError::AccessTokenHashMismatch), "{err}");
+}
+
+#[test]
+fn a_resource_request_proof_without_ath_is_rejected() {
+    let key = proof_key();
+    let proof = sign(&key, &dpop_header(&key), &claims());
+
+    let err = verify_proof(&proof, &check(Some("the-token"))).expect_err("missing ath");
+    assert!(matches!(err, DpopError::MissingAccessTokenHash), "{err}");
+}
+
+#[test]
+fn a_plain_jwt_is_not_a_proof() {
+    let key = proof_key();
+    let mut header = dpop_header(&key);
+    header.typ = Some("JWT".to_owned());
+    let proof = sign(&key, &header, &claims());
+
+    let err = verify_proof(&proof, &check(None)).expect_err("wrong typ");
+    assert!(matches!(err, DpopError::WrongType), "{err}");
+}
+
+#[test]
+fn a_proof_without_an_embedded_key_is_rejected() {
+    let key = proof_key();
+    let mut header = dpop_header(&key);
+    header.jwk = None;
+    let proof = sign(&key, &header, &claims());
+
+    let err = verify_proof(&proof, &check(None)).expect_err("no jwk");
+    assert!(matches!(err, DpopError::MissingJwk), "{err}");
+}
+
+#[test]
+fn a_symmetric_proof_is_rejected() {
+    let mut header = Header::new(Algorithm::HS256);
+    header.typ = Some("dpop+jwt".to_owned());
+    let proof = encode(&header, &claims(), &EncodingKey::from_secret(b"shared")).expect("sign");
+
+    let err = verify_proof(&proof, &check(None)).expect_err("HS256");
+    assert!(matches!(err, DpopError::UnsupportedAlgorithm(_)), "{err}");
+}
+
+#[test]
+fn a_proof_signed_by_a_different_key_than_it_embeds_is_rejected() {
+    let embedded = proof_key();
+    let signer = proof_key();
+    let proof = sign(&signer, &dpop_header(&embedded), &claims());
+
+    let err = verify_proof(&proof, &check(None)).expect_err("signature");
+    assert!(matches!(err, DpopError::InvalidSignature(_)), "{err}");
+}
+
+#[test]
+fn a_nonce_is_accepted_in_its_own_and_the_next_window() {
+    let key = b"nonce-key";
+    let nonce = issue_nonce_with(key, NOW);
+
+    assert!(verify_nonce_with(key, &nonce, NOW));
+    assert!(verify_nonce_with(
+        key,
+        &nonce,
+        NOW + DPOP_NONCE_WINDOW_SECONDS
+    ));
+    assert!(!verify_nonce_with(
+        key,
+        &nonce,
+        NOW + 2 * DPOP_NONCE_WINDOW_SECONDS
+    ));
+}
+
+#[test]
+fn a_nonce_from_another_key_is_rejected() {
+    let nonce = issue_nonce_with(b"one-key", NOW);
+
+    assert!(!verify_nonce_with(b"other-key", &nonce, NOW));
+    assert!(!verify_nonce_with(b"one-key", "made-up", NOW));
+}
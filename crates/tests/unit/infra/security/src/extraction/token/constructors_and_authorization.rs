@@ -216,3 +216,29 @@ fn test_extract_from_authorization_only_non_ascii_header_is_invalid_format() {
         TokenExtractionError::InvalidAuthorizationFormat
     ));
 }
+
+#[test]
+fn test_extract_from_authorization_dpop_scheme() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "authorization",
+        HeaderValue::from_static("DPoP bound_token"),
+    );
+
+    let token = TokenExtractor::extract_from_authorization(&headers)
+        .expect("RFC 9449 §7.1: the DPoP scheme carries the token like Bearer");
+    assert_eq!(token, "bound_token");
+    assert!(TokenExtractor::uses_dpop_scheme(&headers));
+}
+
+#[test]
+fn test_bearer_scheme_is_not_dpop() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "authorization",
+        HeaderValue::from_static("Bearer test_token"),
+    );
+
+    assert!(!TokenExtractor::uses_dpop_scheme(&headers));
+    assert!(!TokenExtractor::uses_dpop_scheme(&HeaderMap::new()));
+}
@@ -51,6 +51,8 @@ fn hook_claims(plugin_id: &str, subject: &str) -> JwtClaims {
         rate_limit_tier: Some(RateLimitTier::User),
         plugin_id: Some(plugin_id.to_string()),
         act: None,
+        cnf: None,
+        parent_jti: None,
     }
 }
 
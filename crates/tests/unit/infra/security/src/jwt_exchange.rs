@@ -0,0 +1,238 @@
+// Tests for the in-process RFC 8693 exchange used by agent-to-agent
+// delegation: each hop nests the previous actor under the new one, the
+// subject's identity and session survive, the token is confined to the peer
+// and linked to the user-held token, and actor tokens cannot stand in for a
+// subject token. Also covers reissuing a verified caller token for the one
+// service behind the proxy it is forwarded to.
+
+use chrono::Duration;
+use jsonwebtoken::{Algorithm, Header, encode};
+use systemprompt_identifiers::{SessionId, UserId};
+use systemprompt_models::auth::{
+    ConfirmationClaim, HopTarget, JwtAudience, JwtClaims, Permission, RateLimitTier, UserType,
+};
+use systemprompt_security::jwt::{
+    ActorTokenParams, ForwardTokenParams, JwtService, TokenExchangeParams, ValidationPolicy,
+    decode_rs256_claims,
+};
+use systemprompt_security::keys::authority::{active_kid, encoding_key};
+use systemprompt_security::session::{SessionGenerator, SessionParams};
+use systemprompt_test_fixtures::install_test_signing_key;
+
+const ISSUER: &str = "exchange-issuer";
+
+fn user_token(user_id: &UserId, session_id: &SessionId) -> String {
+    SessionGenerator::new(ISSUER)
+        .generate(&SessionParams {
+            user_id,
+            session_id,
+            email: "delegator@example.com",
+            duration: Duration::hours(1),
+            user_type: UserType::User,
+            permissions: vec![Permission::User],
+            roles: vec!["user".to_owned()],
+            attributes: Default::default(),
+            rate_limit_tier: RateLimitTier::User,
+        })
+        .expect("mint user token")
+        .as_str()
+        .to_owned()
+}
+
+fn delegate(subject_token: &str, agent: &str, peer: &str) -> String {
+    let actor_token = JwtService::generate_actor_token(&ActorTokenParams {
+        actor: agent,
+        issuer: ISSUER,
+    })
+    .expect("mint actor token");
+    JwtService::exchange_token(&TokenExchangeParams {
+        subject_token,
+        actor_token: actor_token.as_str(),
+        peer,
+        issuer: ISSUER,
+    })
+    .expect("exchange")
+    .as_str()
+    .to_owned()
+}
+
+fn decode(token: &str) -> JwtClaims {
+    decode_rs256_claims(
+        token,
+        &ValidationPolicy::issuer_scoped(ISSUER, JwtAudience::FIRST_PARTY),
+    )
+    .expect("decode exchanged token")
+}
+
+#[test]
+fn two_delegation_hops_nest_the_act_chain() {
+    install_test_signing_key();
+    let user_id = UserId::new("exchange-user");
+    let session_id = SessionId::generate();
+    let token = user_token(&user_id, &session_id);
+
+    let first_hop = delegate(&token, "planner", "researcher");
+    let second_hop = delegate(&first_hop, "researcher", "writer");
+
+    let claims = decode(&second_hop);
+    assert_eq!(claims.sub, user_id.as_str());
+    assert_eq!(claims.session_id, Some(session_id));
+    assert_eq!(claims.scope, vec![Permission::User]);
+    let act = claims.act.expect("act claim");
+    assert_eq!(act.iss, ISSUER);
+    assert_eq!(act.sub, "researcher");
+    let inner = act.act.as_ref().as_ref().expect("nested act claim");
+    assert_eq!(inner.sub, "planner");
+    assert!(inner.act.is_none(), "the user is the subject, not an actor");
+    assert_eq!(act.depth(), 2);
+}
+
+#[test]
+fn delegated_token_is_confined_to_the_peer_and_linked_to_the_user_token() {
+    install_test_signing_key();
+    let token = user_token(&UserId::new("exchange-user"), &SessionId::generate());
+    let user_jti = decode(&token).jti;
+
+    let first_hop = decode(&delegate(&token, "planner", "researcher"));
+    assert_eq!(
+        first_hop.hop_target(),
+        Some(HopTarget::Agent("researcher".to_owned()))
+    );
+    assert!(!first_hop.has_audience(&JwtAudience::Api));
+    assert!(!first_hop.has_audience(&JwtAudience::Web));
+    assert_eq!(first_hop.parent_jti.as_deref(), Some(user_jti.as_str()));
+    assert_ne!(first_hop.jti, user_jti);
+
+    let token = delegate(&token, "planner", "researcher");
+    let second_hop = decode(&delegate(&token, "researcher", "writer"));
+    assert_eq!(
+        second_hop.hop_target(),
+        Some(HopTarget::Agent("writer".to_owned()))
+    );
+    assert_eq!(
+        second_hop.parent_jti.as_deref(),
+        Some(user_jti.as_str()),
+        "every hop links back to the user-held token"
+    );
+}
+
+#[test]
+fn actor_token_is_not_accepted_as_a_subject() {
+    install_test_signing_key();
+    let actor_token = JwtService::generate_actor_token(&ActorTokenParams {
+        actor: "planner",
+        issuer: ISSUER,
+    })
+    .expect("mint actor token");
+
+    let result = JwtService::exchange_token(&TokenExchangeParams {
+        subject_token: actor_token.as_str(),
+        actor_token: actor_token.as_str(),
+        peer: "researcher",
+        issuer: ISSUER,
+    });
+
+    assert!(
+        result.is_err(),
+        "an internal-audience token has no subject to delegate"
+    );
+}
+
+#[test]
+fn subject_token_is_not_accepted_as_an_actor() {
+    install_test_signing_key();
+    let token = user_token(&UserId::new("exchange-user"), &SessionId::generate());
+
+    let result = JwtService::exchange_token(&TokenExchangeParams {
+        subject_token: &token,
+        actor_token: &token,
+        peer: "researcher",
+        issuer: ISSUER,
+    });
+
+    assert!(result.is_err(), "only an actor token may name the actor");
+}
+
+fn bound(token: &str, jkt: &str) -> String {
+    let claims = JwtClaims {
+        cnf: Some(ConfirmationClaim {
+            jkt: jkt.to_owned(),
+        }),
+        ..decode(token)
+    };
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(active_kid().expect("active kid").to_owned());
+    encode(&header, &claims, encoding_key().expect("encoding key")).expect("encode bound token")
+}
+
+fn forward(token: &str, target: &HopTarget) -> JwtClaims {
+    let forwarded = JwtService::forward_token(&ForwardTokenParams {
+        token,
+        target,
+        issuer: ISSUER,
+    })
+    .expect("forward");
+    decode(forwarded.as_str())
+}
+
+#[test]
+fn forwarding_drops_the_binding_and_confines_the_token_to_the_target() {
+    install_test_signing_key();
+    let user_id = UserId::new("exchange-user");
+    let session_id = SessionId::generate();
+    let token = bound(&user_token(&user_id, &session_id), "bound-key-thumbprint");
+    let original = decode(&token);
+    let target = HopTarget::McpServer("files".to_owned());
+
+    let claims = forward(&token, &target);
+    assert!(claims.cnf.is_none(), "the binding is dropped");
+    assert_eq!(claims.sub, user_id.as_str());
+    assert_eq!(claims.session_id, Some(session_id));
+    assert_eq!(claims.scope, original.scope);
+    assert_eq!(claims.aud, target.audiences());
+    assert_eq!(claims.hop_target(), Some(target));
+    assert!(claims.act.is_none(), "forwarding is not a delegation hop");
+    assert_ne!(claims.jti, original.jti);
+    assert_eq!(claims.parent_jti.as_deref(), Some(original.jti.as_str()));
+    assert!(claims.exp <= original.exp);
+    assert!(
+        claims.exp - claims.iat <= 15 * 60,
+        "a forwarded token lives for minutes"
+    );
+}
+
+#[test]
+fn forwarding_a_derived_token_keeps_the_user_token_as_parent() {
+    install_test_signing_key();
+    let token = user_token(&UserId::new("exchange-user"), &SessionId::generate());
+    let user_jti = decode(&token).jti;
+    let delegated = delegate(&token, "planner", "researcher");
+
+    let claims = forward(&delegated, &HopTarget::McpServer("files".to_owned()));
+    assert_eq!(claims.parent_jti.as_deref(), Some(user_jti.as_str()));
+    assert_eq!(
+        claims.act.map(|act| act.sub).as_deref(),
+        Some("planner"),
+        "the delegation chain survives forwarding"
+    );
+}
+
+#[test]
+fn forwarding_rejects_a_foreign_issuer() {
+    install_test_signing_key();
+    let token = bound(
+        &user_token(&UserId::new("exchange-user"), &SessionId::generate()),
+        "bound-key-thumbprint",
+    );
+
+    let result = JwtService::forward_token(&ForwardTokenParams {
+        token: &token,
+        target: &HopTarget::Agent("planner".to_owned()),
+        issuer: "another-issuer",
+    });
+
+    assert!(
+        result.is_err(),
+        "only this deployment's tokens are reissued"
+    );
+}
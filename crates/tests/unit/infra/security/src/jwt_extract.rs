@@ -37,6 +37,8 @@ fn mint_custom(
         rate_limit_tier: Some(RateLimitTier::User),
         plugin_id: None,
         act: None,
+        cnf: None,
+        parent_jti: None,
     };
 
     let kid = authority::active_kid().expect("kid");
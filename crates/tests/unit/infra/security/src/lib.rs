@@ -11,6 +11,8 @@ mod auth_validation;
 #[cfg(test)]
 mod authz_repository;
 #[cfg(test)]
+mod dpop;
+#[cfg(test)]
 mod error_display;
 #[cfg(test)]
 mod extraction;
@@ -19,12 +21,16 @@ mod hook_token_typed_ids;
 #[cfg(test)]
 mod jwks_fetch;
 #[cfg(test)]
+mod jwt_exchange;
+#[cfg(test)]
 mod jwt_extract;
 #[cfg(test)]
 mod jwt_validate;
 #[cfg(test)]
 mod manifest_signing_jcs;
 #[cfg(test)]
+mod policy_argument_constraints;
+#[cfg(test)]
 mod policy_audit;
 #[cfg(test)]
 mod policy_builtin_config;
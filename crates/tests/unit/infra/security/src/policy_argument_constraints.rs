@@ -0,0 +1,271 @@
+//! Tests for the `argument_constraints` builtin: per-tool, per-scope rules over
+//! JSON-pointer paths in tool arguments.
+
+use serde_json::json;
+use systemprompt_identifiers::{CallId, McpToolName, SessionId, UserId};
+use systemprompt_security::authz::types::{Decision, DenyReason};
+use systemprompt_security::policy::governed::{GovernedInput, GovernedTarget, McpToolInput};
+use systemprompt_security::policy::types::{
+    AccessScope, AgentScope, ConstraintViolation, PolicyContext,
+};
+use systemprompt_security::policy::{GovernanceConfig, GovernanceEngine};
+
+const RULES: &str = r#"
+governance:
+  enabled: true
+  policies:
+    - id: argument_constraints
+      rules:
+        - id: data_root
+          tools: ["filesystem_*"]
+          scopes: [user, unknown]
+          pointer: /path
+          under: ["/srv/data"]
+        - id: payment_cap
+          tools: create_payment
+          pointer: /amount
+          required: true
+          minimum: 0
+          exclusive_maximum: 1000
+        - id: regions
+          tools: ["deploy"]
+          pointer: /targets/*/region
+          one_of: ["eu-west-1", "eu-central-1"]
+        - id: ticket_ref
+          tools: ["create_ticket"]
+          pointer: /ref
+          pattern: "^[A-Z]+-[0-9]+$"
+"#;
+
+fn engine(yaml: &str) -> GovernanceEngine {
+    GovernanceEngine::from_config(&GovernanceConfig::parse(yaml).unwrap())
+}
+
+fn evaluate(
+    eng: &GovernanceEngine,
+    tool: &str,
+    scope: AccessScope,
+    arguments: serde_json::Value,
+) -> Decision {
+    let session = SessionId::generate();
+    let user = UserId::new("user-argument-constraints");
+    let call = CallId::generate();
+    let input = GovernedInput::tool_arguments(McpToolInput::new(arguments));
+    eng.evaluate(&PolicyContext {
+        target: GovernedTarget::Tool {
+            tool: McpToolName::new(tool),
+        },
+        agent_scope: AgentScope::User {
+            user_id: user.clone(),
+        },
+        access_scope: scope,
+        session_id: &session,
+        user_id: &user,
+        input: &input,
+        call_id: &call,
+    })
+    .decision
+}
+
+fn violation(decision: Decision) -> (String, String, ConstraintViolation) {
+    match decision {
+        Decision::Deny {
+            reason:
+                DenyReason::ArgumentViolation {
+                    rule_id,
+                    pointer,
+                    violation,
+                    ..
+                },
+        } => (rule_id, pointer, violation),
+        other => panic!("expected an argument violation, got {other:?}"),
+    }
+}
+
+#[test]
+fn paths_must_stay_under_the_allowed_root() {
+    let eng = engine(RULES);
+
+    assert!(matches!(
+        evaluate(
+            &eng,
+            "filesystem_read",
+            AccessScope::User,
+            json!({"path": "/srv/data/a.csv"})
+        ),
+        Decision::Allow { .. }
+    ));
+    for escape in [
+        "/srv/data/../../etc/passwd",
+        "/srv/database",
+        "relative/path",
+    ] {
+        let (rule, pointer, violation) = violation(evaluate(
+            &eng,
+            "filesystem_read",
+            AccessScope::User,
+            json!({ "path": escape }),
+        ));
+        assert_eq!(rule, "data_root");
+        assert_eq!(pointer, "/path");
+        assert_eq!(
+            violation,
+            ConstraintViolation::OutsidePaths {
+                allowed: vec!["/srv/data".to_owned()]
+            },
+            "{escape} must be refused"
+        );
+    }
+}
+
+#[test]
+fn a_rule_scoped_to_user_tiers_does_not_bind_admins() {
+    let eng = engine(RULES);
+
+    let decision = evaluate(
+        &eng,
+        "filesystem_read",
+        AccessScope::Admin,
+        json!({"path": "/etc/hosts"}),
+    );
+    assert!(matches!(decision, Decision::Allow { .. }), "{decision:?}");
+}
+
+#[test]
+fn numeric_bounds_and_required_arguments_are_enforced() {
+    let eng = engine(RULES);
+
+    assert!(matches!(
+        evaluate(
+            &eng,
+            "create_payment",
+            AccessScope::User,
+            json!({"amount": 999.99})
+        ),
+        Decision::Allow { .. }
+    ));
+
+    let (_, _, at_cap) = violation(evaluate(
+        &eng,
+        "create_payment",
+        AccessScope::User,
+        json!({"amount": 1000}),
+    ));
+    assert_eq!(
+        at_cap,
+        ConstraintViolation::OutOfRange {
+            bound: "< 1000".to_owned()
+        }
+    );
+
+    let (_, _, missing) = violation(evaluate(
+        &eng,
+        "create_payment",
+        AccessScope::User,
+        json!({"amount": null}),
+    ));
+    assert_eq!(missing, ConstraintViolation::Missing);
+
+    let (_, _, stringly) = violation(evaluate(
+        &eng,
+        "create_payment",
+        AccessScope::User,
+        json!({"amount": "12"}),
+    ));
+    assert!(matches!(stringly, ConstraintViolation::WrongType { .. }));
+}
+
+#[test]
+fn wildcard_pointers_check_every_element_and_report_the_offending_one() {
+    let eng = engine(RULES);
+
+    let (rule, pointer, violation) = violation(evaluate(
+        &eng,
+        "deploy",
+        AccessScope::User,
+        json!({"targets": [{"region": "eu-west-1"}, {"region": "us-east-1"}]}),
+    ));
+    assert_eq!(rule, "regions");
+    assert_eq!(pointer, "/targets/1/region");
+    assert!(matches!(violation, ConstraintViolation::NotAllowed { .. }));
+
+    assert!(matches!(
+        evaluate(&eng, "deploy", AccessScope::User, json!({"targets": []})),
+        Decision::Allow { .. }
+    ));
+}
+
+#[test]
+fn patterns_match_string_arguments() {
+    let eng = engine(RULES);
+
+    assert!(matches!(
+        evaluate(
+            &eng,
+            "create_ticket",
+            AccessScope::User,
+            json!({"ref": "OPS-42"})
+        ),
+        Decision::Allow { .. }
+    ));
+    let (_, _, violation) = violation(evaluate(
+        &eng,
+        "create_ticket",
+        AccessScope::User,
+        json!({"ref": "ops 42; drop table"}),
+    ));
+    assert!(matches!(
+        violation,
+        ConstraintViolation::PatternMismatch { .. }
+    ));
+}
+
+#[test]
+fn a_misconfigured_rule_denies_the_tools_it_governs_and_nothing_else() {
+    let eng = engine(
+        r#"
+governance:
+  policies:
+    - id: argument_constraints
+      rules:
+        - id: broken
+          tools: ["shell_*"]
+          pointer: /cmd
+          pattern: "("
+"#,
+    );
+
+    let (rule, _, violation) = violation(evaluate(
+        &eng,
+        "shell_exec",
+        AccessScope::Admin,
+        json!({"cmd": "ls"}),
+    ));
+    assert_eq!(rule, "broken");
+    assert!(matches!(
+        violation,
+        ConstraintViolation::Misconfigured { .. }
+    ));
+
+    assert!(matches!(
+        evaluate(&eng, "read_file", AccessScope::User, json!({})),
+        Decision::Allow { .. }
+    ));
+}
+
+#[test]
+fn the_deny_reason_names_the_rule_but_not_the_argument_value() {
+    let eng = engine(RULES);
+
+    let Decision::Deny { reason } = evaluate(
+        &eng,
+        "filesystem_write",
+        AccessScope::User,
+        json!({"path": "/home/alice/.ssh/id_ed25519"}),
+    ) else {
+        panic!("expected a deny");
+    };
+    let rendered = reason.to_string();
+    assert!(rendered.contains("data_root"), "{rendered}");
+    assert!(!rendered.contains("id_ed25519"), "{rendered}");
+}
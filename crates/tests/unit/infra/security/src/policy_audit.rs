@@ -81,6 +81,7 @@ fn chain_entry_result_serializes_as_a_flattened_tag() {
         (ChainEntryResult::Fail, "fail"),
         (ChainEntryResult::Skip, "skip"),
         (ChainEntryResult::Disabled, "disabled"),
+        (ChainEntryResult::Shadow, "shadow"),
     ] {
         let entry = ChainEntryOutcome {
             policy_id: PolicyId::new("p"),
@@ -183,6 +184,32 @@ async fn allow_decision_records_default_allow_policy_and_empty_reason() {
     );
 }
 
+#[tokio::test]
+async fn allow_with_a_shadow_deny_is_labelled_and_keeps_the_would_be_reason() {
+    let Some(pool) = pool().await else {
+        return;
+    };
+    let mut audit = unique_audit();
+    audit.chain.push(ChainEntryOutcome {
+        policy_id: PolicyId::new("tool_blocklist"),
+        result: ChainEntryResult::Shadow,
+        detail: "tool read_file blocked by list delete".to_owned(),
+        duration_ms: 0.2,
+    });
+
+    record_decision(&pg(&pool), &audit)
+        .await
+        .expect("insert should succeed");
+
+    let row = fetch(&pool, &audit.id).await;
+    assert_eq!(row.decision, "allow", "a shadow policy never denies");
+    assert_eq!(row.policy, "shadow_deny");
+    assert_eq!(
+        row.reason, "tool_blocklist: tool read_file blocked by list delete",
+        "the reason names the shadow policy and what it would have refused"
+    );
+}
+
 #[tokio::test]
 async fn deny_decision_records_the_first_failing_policy() {
     let Some(pool) = pool().await else {
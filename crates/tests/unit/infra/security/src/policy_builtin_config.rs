@@ -1,9 +1,13 @@
+use std::time::Duration;
+
 use serde_json::json;
 use systemprompt_identifiers::{CallId, McpToolName, SessionId, UserId};
 use systemprompt_security::authz::types::{Decision, DenyReason};
 use systemprompt_security::policy::governed::{GovernedInput, GovernedTarget, McpToolInput};
 use systemprompt_security::policy::types::{AccessScope, AgentScope, PolicyContext};
-use systemprompt_security::policy::{GovernanceConfig, GovernanceEngine};
+use systemprompt_security::policy::{
+    GovernanceConfig, GovernanceEngine, SharedCharge, SharedChargeError, charge_blocking,
+};
 
 fn engine(yaml: &str) -> GovernanceEngine {
     GovernanceEngine::from_config(&GovernanceConfig::parse(yaml).unwrap())
@@ -173,7 +177,13 @@ governance:
 fn every_instantiated_policy_exposes_a_distinct_operator_facing_name_and_description() {
     let eng = engine("governance:\n  enabled: true\n  policies: []\n");
 
-    const BUILTINS: [&str; 4] = ["secret_scan", "scope_check", "tool_blocklist", "rate_limit"];
+    const BUILTINS: [&str; 5] = [
+        "secret_scan",
+        "scope_check",
+        "tool_blocklist",
+        "rate_limit",
+        "argument_constraints",
+    ];
 
     let mut seen: Vec<(String, &'static str, &'static str)> = Vec::new();
     for (cfg, policy) in eng.policies() {
@@ -251,3 +261,102 @@ governance:
         );
     }
 }
+
+fn rate_limit_engine(backend_lines: &str) -> GovernanceEngine {
+    engine(&format!(
+        r#"
+governance:
+  enabled: true
+  policies:
+    - id: rate_limit
+      enabled: true
+      requests_per_window: 2
+      window_secs: 60
+      store_timeout_ms: 50
+{backend_lines}
+"#
+    ))
+}
+
+fn three_calls(eng: &GovernanceEngine, user: &str) -> Vec<Decision> {
+    let target = tool("read_file");
+    let input = args(json!({}));
+    let first = Call::new(user);
+    (0..3)
+        .map(|_| {
+            let call = Call {
+                session: first.session.clone(),
+                user: first.user.clone(),
+                call: CallId::generate(),
+            };
+            eng.evaluate(&call.ctx(&target, AccessScope::User, &input))
+                .decision
+        })
+        .collect()
+}
+
+#[test]
+fn an_in_memory_window_and_a_postgres_fallback_enforce_the_budget_locally() {
+    for backend_lines in [
+        "      backend: memory",
+        "      backend: redis",
+        "      backend: postgres\n      on_store_error: memory",
+    ] {
+        let decisions = three_calls(&rate_limit_engine(backend_lines), "user-rate-limit-backend");
+
+        assert!(
+            matches!(decisions[0], Decision::Allow { .. })
+                && matches!(decisions[1], Decision::Allow { .. }),
+            "{backend_lines}: calls within the budget must pass: {decisions:?}"
+        );
+        assert!(
+            matches!(
+                decisions[2],
+                Decision::Deny {
+                    reason: DenyReason::RateLimitExceeded { .. }
+                }
+            ),
+            "{backend_lines}: the call past the budget must be refused: {decisions:?}"
+        );
+    }
+}
+
+#[test]
+fn a_postgres_backend_without_an_installed_store_refuses_calls() {
+    for backend_lines in [
+        "      backend: postgres",
+        "      backend: postgres\n      on_store_error: deny",
+        "      backend: postgres\n      on_store_error: sometimes",
+    ] {
+        let decisions = three_calls(&rate_limit_engine(backend_lines), "user-rate-limit-closed");
+
+        for decision in &decisions {
+            assert!(
+                matches!(
+                    decision,
+                    Decision::Deny {
+                        reason: DenyReason::PolicyViolation { policy, .. }
+                    } if policy == "rate_limit"
+                ),
+                "{backend_lines}: an uncharged call must be refused: {decisions:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn charging_the_shared_window_before_it_is_installed_reports_not_installed() {
+    let charge = SharedCharge {
+        bucket_key: "session:user".to_owned(),
+        call_id: CallId::generate(),
+        window_secs: 60,
+        limit: 1,
+    };
+
+    let result = charge_blocking(charge, Duration::from_millis(50));
+
+    assert!(
+        matches!(result, Err(SharedChargeError::NotInstalled)),
+        "{result:?}"
+    );
+}
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use systemprompt_security::authz::types::EnforceMode;
 use systemprompt_security::policy::{GovernanceConfig, GovernanceConfigError};
 
 #[test]
@@ -51,6 +52,37 @@ fn parse_reads_ids_enabled_flags_and_params() {
     assert!(cfg.policies[1].enabled);
 }
 
+#[test]
+fn enforce_parses_per_policy_and_defaults_to_enforcing() {
+    let cfg = GovernanceConfig::parse(
+        "governance:\n  policies:\n    - id: tool_blocklist\n      enforce: shadow\n    - id: secret_scan\n",
+    )
+    .unwrap();
+    assert_eq!(cfg.policies[0].enforce, EnforceMode::Shadow);
+    assert_eq!(cfg.policies[1].enforce, EnforceMode::Enforce);
+    assert!(
+        GovernanceConfig::defaults()
+            .policies
+            .iter()
+            .all(|p| p.enforce == EnforceMode::Enforce)
+    );
+}
+
+#[test]
+fn parse_rejects_an_unknown_enforce_mode() {
+    let err = GovernanceConfig::parse(
+        "governance:\n  policies:\n    - id: secret_scan\n    - id: rate_limit\n      enforce: audit\n",
+    )
+    .unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            GovernanceConfigError::InvalidEnforce { index: 1, value } if value == "audit"
+        ),
+        "{err:?}"
+    );
+}
+
 #[test]
 fn parse_rejects_documents_without_a_policies_sequence() {
     assert!(matches!(
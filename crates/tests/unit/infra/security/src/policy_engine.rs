@@ -108,6 +108,34 @@ fn deny_short_circuits_and_later_entries_record_skip() {
     assert_eq!(entry_result(&evaluation, "t_allow"), ChainEntryResult::Skip);
 }
 
+#[test]
+fn a_shadow_deny_is_traced_but_the_chain_continues_and_allows() {
+    let e = engine(
+        "governance:\n  policies:\n    - id: t_deny\n      enforce: shadow\n    - id: t_allow\n",
+    );
+    let sid = SessionId::generate();
+    let uid = UserId::new("u-shadow");
+    let input = GovernedInput::tool_arguments(McpToolInput::new(json!({})));
+    let call = CallId::generate();
+
+    let evaluation = e.evaluate(&ctx(&sid, &uid, &input, &call));
+    assert!(
+        matches!(evaluation.decision, Decision::Allow { .. }),
+        "a shadow policy must never deny: {:?}",
+        evaluation.decision
+    );
+    assert_eq!(
+        entry_result(&evaluation, "t_deny"),
+        ChainEntryResult::Shadow
+    );
+    assert!(evaluation.chain[0].detail.contains("blocked"));
+    assert_eq!(
+        entry_result(&evaluation, "t_allow"),
+        ChainEntryResult::Pass,
+        "entries after a shadow deny still run"
+    );
+}
+
 #[test]
 fn trace_preserves_declaration_order_and_pass_entries() {
     let e = engine("governance:\n  policies:\n    - id: t_allow\n    - id: t_deny\n");
@@ -5,7 +5,8 @@ use systemprompt_identifiers::{PolicyId, UserId};
 use systemprompt_security::authz::types::{Decision, MatchedBy};
 use systemprompt_security::policy::governed::McpToolInput;
 use systemprompt_security::policy::types::{
-    AccessScope, AgentScope, GovernancePolicy, PolicyContext, RateLimitWindow, SecretLocation,
+    AccessScope, AgentScope, ConstraintViolation, GovernancePolicy, PolicyContext, RateLimitWindow,
+    SecretLocation,
 };
 
 #[test]
@@ -36,6 +37,18 @@ fn rate_limit_window_serde_roundtrip() {
     assert_eq!(back, w);
 }
 
+#[test]
+fn constraint_violation_serde_is_tagged_by_kind() {
+    let v = ConstraintViolation::OutOfRange {
+        bound: "< 1000".to_owned(),
+    };
+    let value = serde_json::to_value(&v).unwrap();
+    assert_eq!(value, json!({"kind": "out_of_range", "bound": "< 1000"}));
+    let back: ConstraintViolation = serde_json::from_value(value).unwrap();
+    assert_eq!(back, v);
+    assert_eq!(v.to_string(), "must be < 1000");
+}
+
 #[test]
 fn agent_scope_user_id() {
     let uid = UserId::new("user-123");
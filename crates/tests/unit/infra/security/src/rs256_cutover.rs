@@ -52,6 +52,8 @@ fn sample_claims(iss: &str) -> JwtClaims {
         rate_limit_tier: Some(RateLimitTier::User),
         plugin_id: None,
         act: None,
+        cnf: None,
+        parent_jti: None,
     }
 }
 
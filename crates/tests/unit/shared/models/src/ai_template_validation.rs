@@ -10,6 +10,7 @@ fn tool(name: &str, output_schema: Option<serde_json::Value>) -> McpTool {
         output_schema,
         service_id: McpServerId::new("svc"),
         terminal_on_success: false,
+        requires_approval: false,
         model_config: None,
     }
 }
@@ -1,8 +1,8 @@
 use std::collections::BTreeMap;
 
 use systemprompt_models::auth::{
-    ActClaim, JwtAudience, JwtClaims, MAX_ACT_CHAIN_DEPTH, Permission, RateLimitTier, TokenType,
-    UserType,
+    ActClaim, HopTarget, JwtAudience, JwtClaims, MAX_ACT_CHAIN_DEPTH, Permission, RateLimitTier,
+    TokenType, UserType,
 };
 
 fn claims(scope: Vec<Permission>, aud: Vec<JwtAudience>) -> JwtClaims {
@@ -27,6 +27,8 @@ fn claims(scope: Vec<Permission>, aud: Vec<JwtAudience>) -> JwtClaims {
         rate_limit_tier: Some(RateLimitTier::User),
         plugin_id: None,
         act: None,
+        cnf: None,
+        parent_jti: None,
     }
 }
 
@@ -203,3 +205,51 @@ mod act_claim_depth {
         assert_eq!(back.depth(), 2);
     }
 }
+
+mod derived_token_links {
+    use super::*;
+
+    #[test]
+    fn root_jti_prefers_the_parent() {
+        let mut c = claims(vec![Permission::User], vec![]);
+        assert_eq!(c.root_jti(), "jti-1");
+        c.parent_jti = Some("user-held".to_owned());
+        assert_eq!(c.root_jti(), "user-held");
+    }
+
+    #[test]
+    fn parent_jti_round_trips_and_is_omitted_when_absent() {
+        let mut c = claims(vec![Permission::User], vec![JwtAudience::Api]);
+        let json = serde_json::to_value(&c).expect("serialize");
+        assert!(json.get("parent_jti").is_none());
+        c.parent_jti = Some("user-held".to_owned());
+        let json = serde_json::to_string(&c).expect("serialize");
+        let back: JwtClaims = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(back.parent_jti.as_deref(), Some("user-held"));
+    }
+
+    #[test]
+    fn hop_target_round_trips_through_the_audience() {
+        for target in [
+            HopTarget::Agent("planner".to_owned()),
+            HopTarget::McpServer("files".to_owned()),
+        ] {
+            let c = claims(vec![Permission::User], target.audiences());
+            let json = serde_json::to_string(&c).expect("serialize");
+            let back: JwtClaims = serde_json::from_str(&json).expect("deserialize");
+            assert_eq!(back.hop_target(), Some(target));
+        }
+    }
+
+    #[test]
+    fn first_party_and_url_resources_name_no_hop() {
+        let c = claims(
+            vec![Permission::User],
+            vec![
+                JwtAudience::Api,
+                JwtAudience::Resource("https://example.com/api/v1/mcp/files/mcp".to_owned()),
+            ],
+        );
+        assert_eq!(c.hop_target(), None);
+    }
+}
@@ -1,6 +1,8 @@
 use systemprompt_identifiers::AgentId;
 use systemprompt_models::services::{
-    AgentCardConfig, CapabilitiesConfig, DiskAgentConfig, OAuthConfig, PluginComponentRef,
+    AgentCardConfig, AgentCompactionConfig, AgentExecutionConfig, AgentExecutionMode,
+    AgentRecoveryConfig, CapabilitiesConfig, CompactionStrategy, DiskAgentConfig, OAuthConfig,
+    PluginComponentRef, TaskRecoveryMode,
 };
 
 fn pcr<I: IntoIterator<Item = &'static str>>(items: I) -> PluginComponentRef {
@@ -52,6 +54,10 @@ fn valid_disk(name: &str) -> DiskAgentConfig {
         model: Some("claude".to_owned()),
         card: empty_card(),
         oauth: OAuthConfig::default(),
+        execution: AgentExecutionConfig::default(),
+        compaction: AgentCompactionConfig::default(),
+        recovery: AgentRecoveryConfig::default(),
+        delegates: Vec::new(),
     }
 }
 
@@ -196,3 +202,202 @@ card:
     assert_eq!(cfg.version, "1.0.0");
     assert_eq!(cfg.mcp_servers.include, vec!["fs".to_owned()]);
 }
+
+#[test]
+fn execution_defaults_to_a_single_pass() {
+    let cfg = valid_disk("agent_one");
+    assert!(!cfg.execution.is_iterative());
+    assert_eq!(cfg.execution.max_iterations, 8);
+    assert_eq!(cfg.execution.max_repeated_calls, 2);
+}
+
+#[test]
+fn execution_block_parses_and_reaches_the_runtime_config() {
+    let yaml = r#"
+name: loop_agent
+display_name: Loop Agent
+description: An agent
+port: 9002
+execution:
+  mode: iterative
+  maxIterations: 4
+  maxCostMicrodollars: 250000
+card:
+  protocolVersion: '1.0'
+  displayName: Loop Agent
+  description: An agent
+  version: '1.0.0'
+  preferredTransport: JSONRPC
+  defaultInputModes: ['text/plain']
+  defaultOutputModes: ['text/plain']
+  capabilities: {}
+"#;
+    let cfg: DiskAgentConfig = serde_yaml::from_str(yaml).unwrap();
+    assert!(cfg.validate("loop_agent").is_ok());
+    let execution = cfg
+        .to_agent_config("https://api.example.com", None)
+        .metadata
+        .execution;
+    assert_eq!(execution.mode, AgentExecutionMode::Iterative);
+    assert_eq!(execution.max_iterations, 4);
+    assert_eq!(execution.max_cost_microdollars, Some(250_000));
+    assert_eq!(execution.max_tokens, None);
+    assert_eq!(execution.max_repeated_calls, 2);
+}
+
+#[test]
+fn validate_rejects_zero_iterations_and_non_positive_budgets() {
+    let mut cfg = valid_disk("agent_one");
+    cfg.execution.max_iterations = 0;
+    let err = cfg.validate("agent_one").unwrap_err();
+    assert!(format!("{err}").contains("maxIterations"));
+
+    let mut cfg = valid_disk("agent_one");
+    cfg.execution.max_tokens = Some(0);
+    let err = cfg.validate("agent_one").unwrap_err();
+    assert!(format!("{err}").contains("budgets"));
+}
+
+#[test]
+fn delegates_reach_the_runtime_config() {
+    let cfg = DiskAgentConfig {
+        delegates: vec!["researcher".to_owned(), "writer".to_owned()],
+        ..valid_disk("agent_one")
+    };
+    assert!(cfg.validate("agent_one").is_ok());
+    let delegates = cfg
+        .to_agent_config("https://api.example.com", None)
+        .metadata
+        .delegates;
+    assert_eq!(
+        delegates,
+        vec!["researcher".to_owned(), "writer".to_owned()]
+    );
+}
+
+#[test]
+fn validate_rejects_self_and_duplicate_delegates() {
+    let cfg = DiskAgentConfig {
+        delegates: vec!["agent_one".to_owned()],
+        ..valid_disk("agent_one")
+    };
+    let err = cfg.validate("agent_one").unwrap_err();
+    assert!(format!("{err}").contains("cannot delegate to itself"));
+
+    let cfg = DiskAgentConfig {
+        delegates: vec!["researcher".to_owned(), "researcher".to_owned()],
+        ..valid_disk("agent_one")
+    };
+    let err = cfg.validate("agent_one").unwrap_err();
+    assert!(format!("{err}").contains("more than once"));
+}
+
+#[test]
+fn compaction_is_off_by_default() {
+    let cfg = valid_disk("agent_one");
+    assert!(!cfg.compaction.is_enabled());
+    assert!(cfg.compaction.pin_artifacts);
+    assert_eq!(cfg.compaction.keep_recent_messages, 8);
+}
+
+#[test]
+fn compaction_block_parses_and_reaches_the_runtime_config() {
+    let yaml = r#"
+name: long_agent
+display_name: Long Agent
+description: An agent
+port: 9003
+compaction:
+  strategy: summarize
+  maxHistoryTokens: 8000
+  keepRecentMessages: 4
+  pinArtifacts: false
+card:
+  protocolVersion: '1.0'
+  displayName: Long Agent
+  description: An agent
+  version: '1.0.0'
+  preferredTransport: JSONRPC
+  defaultInputModes: ['text/plain']
+  defaultOutputModes: ['text/plain']
+  capabilities: {}
+"#;
+    let cfg: DiskAgentConfig = serde_yaml::from_str(yaml).unwrap();
+    assert!(cfg.validate("long_agent").is_ok());
+    let compaction = cfg
+        .to_agent_config("https://api.example.com", None)
+        .metadata
+        .compaction;
+    assert_eq!(compaction.strategy, CompactionStrategy::Summarize);
+    assert_eq!(compaction.max_history_tokens, 8000);
+    assert_eq!(compaction.keep_recent_messages, 4);
+    assert!(!compaction.pin_artifacts);
+    assert_eq!(compaction.summary_max_tokens, 1024);
+}
+
+#[test]
+fn validate_rejects_an_empty_budget_and_an_oversized_summary() {
+    let mut cfg = valid_disk("agent_one");
+    cfg.compaction.max_history_tokens = 0;
+    let err = cfg.validate("agent_one").unwrap_err();
+    assert!(format!("{err}").contains("maxHistoryTokens"));
+
+    let mut cfg = valid_disk("agent_one");
+    cfg.compaction = AgentCompactionConfig {
+        strategy: CompactionStrategy::Summarize,
+        max_history_tokens: 1000,
+        summary_max_tokens: 1000,
+        ..AgentCompactionConfig::default()
+    };
+    let err = cfg.validate("agent_one").unwrap_err();
+    assert!(format!("{err}").contains("summaryMaxTokens"));
+}
+
+#[test]
+fn recovery_fails_interrupted_tasks_by_default() {
+    let cfg = valid_disk("agent_one");
+    assert_eq!(cfg.recovery.mode, TaskRecoveryMode::Fail);
+    assert!(!cfg.recovery.resumes());
+    assert_eq!(cfg.recovery.max_attempts, 1);
+}
+
+#[test]
+fn recovery_block_parses_and_reaches_the_runtime_config() {
+    let yaml = r#"
+name: durable_agent
+display_name: Durable Agent
+description: An agent
+port: 9004
+recovery:
+  mode: resume
+  maxAttempts: 3
+card:
+  protocolVersion: '1.0'
+  displayName: Durable Agent
+  description: An agent
+  version: '1.0.0'
+  preferredTransport: JSONRPC
+  defaultInputModes: ['text/plain']
+  defaultOutputModes: ['text/plain']
+  capabilities: {}
+"#;
+    let cfg: DiskAgentConfig = serde_yaml::from_str(yaml).unwrap();
+    assert!(cfg.validate("durable_agent").is_ok());
+    let recovery = cfg
+        .to_agent_config("https://api.example.com", None)
+        .metadata
+        .recovery;
+    assert!(recovery.resumes());
+    assert_eq!(recovery.max_attempts, 3);
+}
+
+#[test]
+fn validate_rejects_resume_without_attempts() {
+    let mut cfg = valid_disk("agent_one");
+    cfg.recovery = AgentRecoveryConfig {
+        mode: TaskRecoveryMode::Resume,
+        max_attempts: 0,
+    };
+    let err = cfg.validate("agent_one").unwrap_err();
+    assert!(format!("{err}").contains("maxAttempts"));
+}
@@ -3,7 +3,9 @@
 
 use chrono::Utc;
 use systemprompt_identifiers::{ConnectionId, ContextId};
-use systemprompt_models::events::payloads::system::ContextSummary;
+use systemprompt_models::events::payloads::system::{
+    BudgetThresholdCrossedPayload, ContextSummary,
+};
 use systemprompt_models::events::{A2AEventType, ContextEvent, SystemEvent, SystemEventBuilder};
 use systemprompt_traits::ContextWithStats;
 
@@ -58,6 +60,32 @@ fn connected_event_carries_connection_id() {
     assert_eq!(json["connectionId"], "conn-1");
 }
 
+#[test]
+fn budget_threshold_crossed_serializes_camel_case_payload() {
+    let event = SystemEventBuilder::budget_threshold_crossed(BudgetThresholdCrossedPayload {
+        subject_kind: "user".to_owned(),
+        subject_id: "user-1".to_owned(),
+        window_seconds: 86_400,
+        window_start: Utc::now(),
+        threshold_percent: 80,
+        spent_microdollars: 8_100_000,
+        limit_microdollars: 10_000_000,
+    });
+
+    assert_eq!(
+        event.event_type(),
+        systemprompt_models::SystemEventType::BudgetThresholdCrossed
+    );
+    assert_eq!(event.event_type().as_str(), "BUDGET_THRESHOLD_CROSSED");
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["type"], "BUDGET_THRESHOLD_CROSSED");
+    assert_eq!(json["subjectId"], "user-1");
+    assert_eq!(json["thresholdPercent"], 80);
+    assert_eq!(json["limitMicrodollars"], 10_000_000);
+    let back: SystemEvent = serde_json::from_value(json).unwrap();
+    assert!(matches!(back, SystemEvent::BudgetThresholdCrossed { .. }));
+}
+
 #[test]
 fn contexts_snapshot_serializes_summaries() {
     let stats = ContextWithStats {
@@ -1,4 +1,5 @@
 use systemprompt_identifiers::{SkillId, TaskId};
+use systemprompt_models::a2a::TaskState;
 use systemprompt_models::execution::{
     ExecutionStep, PlannedTool, StepContent, StepId, StepStatus, StepType,
 };
@@ -166,6 +167,13 @@ fn step_type_display_tool_execution() {
     assert_eq!(format!("{}", StepType::ToolExecution), "tool_execution");
 }
 
+#[test]
+fn step_type_display_delegation() {
+    assert_eq!(format!("{}", StepType::Delegation), "delegation");
+    let st: StepType = "delegation".parse().unwrap();
+    assert_eq!(st, StepType::Delegation);
+}
+
 #[test]
 fn step_type_display_completion() {
     assert_eq!(format!("{}", StepType::Completion), "completion");
@@ -509,3 +517,39 @@ fn execution_step_started_at_is_set() {
     let diff = (now - step.started_at).num_seconds().abs();
     assert!(diff < 2);
 }
+
+#[test]
+fn step_content_delegation_is_an_instant_step_naming_the_peer() {
+    let content = StepContent::delegation(
+        "researcher",
+        TaskId::new("child-task"),
+        TaskState::Working,
+        None,
+    );
+    assert_eq!(content.step_type(), StepType::Delegation);
+    assert!(content.is_instant());
+    assert!(content.title().contains("researcher"));
+    assert!(content.tool_name().is_none());
+}
+
+#[test]
+fn execution_step_delegation_serializes_the_child_task_and_state() {
+    let step = ExecutionStep::delegation(
+        test_task_id(),
+        "researcher",
+        TaskId::new("child-task"),
+        TaskState::Completed,
+        Some("found it".to_string()),
+    );
+    assert_eq!(step.status, StepStatus::Completed);
+
+    let value = serde_json::to_value(&step.content).unwrap();
+    assert_eq!(value["type"], "delegation");
+    assert_eq!(value["agent_name"], "researcher");
+    assert_eq!(value["task_id"], "child-task");
+    assert_eq!(value["state"], "TASK_STATE_COMPLETED");
+    assert_eq!(value["message"], "found it");
+
+    let parsed: StepContent = serde_json::from_value(value).unwrap();
+    assert_eq!(parsed, step.content);
+}
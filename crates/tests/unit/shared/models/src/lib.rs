@@ -64,9 +64,18 @@ mod profile;
 #[cfg(test)]
 mod profile_gateway;
 
+#[cfg(test)]
+mod profile_oidc_providers;
+
+#[cfg(test)]
+mod profile_gateway_targets;
+
 #[cfg(test)]
 mod provider_catalog_parity;
 
+#[cfg(test)]
+mod provider_local;
+
 #[cfg(test)]
 mod provider_protocol_filter;
 
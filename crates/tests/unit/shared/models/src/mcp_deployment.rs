@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use systemprompt_models::auth::JwtAudience;
-use systemprompt_models::mcp::{Deployment, ExternalAuth, McpServerType, OAuthRequirement};
+use systemprompt_models::mcp::{
+    DEFAULT_CGROUP_PARENT, Deployment, ExternalAuth, FilesystemAllowlist, McpServerType,
+    NetworkMode, OAuthRequirement, ResourceLimits, SandboxConfig, SeccompProfile, StdioCommand,
+};
 
 fn deployment(server_type: McpServerType, endpoint: Option<&str>) -> Deployment {
     Deployment {
@@ -26,9 +29,25 @@ fn deployment(server_type: McpServerType, endpoint: Option<&str>) -> Deployment
         env_vars: vec![],
         external_auth: None,
         headers: HashMap::new(),
+        stdio: None,
+        sandbox: None,
     }
 }
 
+fn stdio_deployment(command: &str) -> Deployment {
+    let mut d = deployment(McpServerType::Stdio, None);
+    d.binary = String::new();
+    d.stdio = Some(StdioCommand {
+        command: command.to_owned(),
+        args: vec![
+            "-y".to_owned(),
+            "@modelcontextprotocol/server-github".to_owned(),
+        ],
+        working_dir: None,
+    });
+    d
+}
+
 fn external_auth(token_endpoint: &str) -> ExternalAuth {
     ExternalAuth {
         token_endpoint: token_endpoint.to_owned(),
@@ -157,3 +176,171 @@ fn static_headers_on_internal_server_are_rejected() {
         "static headers on an internal server must be rejected: {msg}"
     );
 }
+
+#[test]
+fn stdio_server_parses_from_yaml_without_a_binary() {
+    let yaml = r#"
+type: stdio
+port: 5150
+enabled: true
+display_in_web: false
+stdio:
+  command: npx
+  args: ["-y", "@modelcontextprotocol/server-github"]
+env_vars: [GITHUB_TOKEN]
+oauth:
+  required: true
+  scopes: [admin]
+  audience: mcp
+  client_id: null
+"#;
+    let d: Deployment = serde_yaml::from_str(yaml).expect("stdio deployment parses");
+    assert_eq!(d.server_type, McpServerType::Stdio);
+    assert!(d.binary.is_empty());
+    let stdio = d.stdio.as_ref().expect("stdio block");
+    assert_eq!(stdio.command, "npx");
+    assert_eq!(stdio.args.len(), 2);
+    assert_eq!(d.env_vars, vec!["GITHUB_TOKEN".to_owned()]);
+    d.validate("github").expect("valid stdio deployment");
+}
+
+#[test]
+fn stdio_server_type_is_managed_like_internal() {
+    assert_eq!(McpServerType::Stdio.as_str(), "stdio");
+    assert!(McpServerType::Stdio.is_managed());
+    assert!(McpServerType::Internal.is_managed());
+    assert!(!McpServerType::External.is_managed());
+}
+
+#[test]
+fn stdio_server_without_a_command_is_rejected() {
+    let mut d = deployment(McpServerType::Stdio, None);
+    let msg = d.validate("github").unwrap_err().to_string();
+    assert!(msg.contains("stdio.command"), "{msg}");
+
+    d = stdio_deployment("  ");
+    let msg = d.validate("github").unwrap_err().to_string();
+    assert!(msg.contains("must not be empty"), "{msg}");
+}
+
+#[test]
+fn stdio_block_on_a_non_stdio_server_is_rejected() {
+    let mut d = deployment(McpServerType::Internal, None);
+    d.stdio = stdio_deployment("npx").stdio;
+    let msg = d.validate("fixture").unwrap_err().to_string();
+    assert!(msg.contains("type: stdio"), "{msg}");
+}
+
+#[test]
+fn stdio_server_rejects_absolute_endpoints_and_external_credentials() {
+    let mut d = stdio_deployment("npx");
+    d.endpoint = Some("https://example.com/mcp".to_owned());
+    let msg = d.validate("github").unwrap_err().to_string();
+    assert!(msg.contains("relative path"), "{msg}");
+
+    let mut d = stdio_deployment("npx");
+    d.external_auth = Some(external_auth("/api/public/github/token"));
+    let msg = d.validate("github").unwrap_err().to_string();
+    assert!(msg.contains("stdio servers are reached"), "{msg}");
+}
+
+#[test]
+fn sandbox_block_parses_with_defaults() {
+    let yaml = r#"
+type: stdio
+port: 5151
+enabled: true
+display_in_web: false
+stdio:
+  command: /usr/bin/npx
+sandbox:
+  filesystem:
+    read_only: [/usr, /lib]
+    read_write: [/tmp/github-mcp]
+  seccomp: strict
+  network: isolated
+  limits:
+    memory_max_mb: 512
+    pids_max: 64
+oauth:
+  required: true
+  scopes: [admin]
+  audience: mcp
+  client_id: null
+"#;
+    let d: Deployment = serde_yaml::from_str(yaml).expect("sandboxed deployment parses");
+    let sandbox = d.sandbox.as_ref().expect("sandbox block");
+    assert_eq!(sandbox.seccomp, SeccompProfile::Strict);
+    assert_eq!(sandbox.network, NetworkMode::Isolated);
+    assert!(!sandbox.no_new_privs);
+    assert!(
+        sandbox.requires_no_new_privs(),
+        "landlock and seccomp force no_new_privs"
+    );
+    let limits = sandbox.limits.as_ref().expect("limits");
+    assert_eq!(limits.memory_max_mb, Some(512));
+    assert_eq!(limits.cpu_max_percent, None);
+    assert_eq!(limits.cgroup_parent, DEFAULT_CGROUP_PARENT);
+    d.validate("github").expect("valid sandbox");
+}
+
+#[test]
+fn empty_sandbox_leaves_the_process_unconfined() {
+    let sandbox = SandboxConfig::default();
+
+    assert_eq!(sandbox.seccomp, SeccompProfile::None);
+    assert_eq!(sandbox.network, NetworkMode::Host);
+    assert!(!sandbox.requires_no_new_privs());
+}
+
+#[test]
+fn isolated_network_is_rejected_on_internal_servers() {
+    let mut d = deployment(McpServerType::Internal, None);
+    d.sandbox = Some(SandboxConfig {
+        network: NetworkMode::Isolated,
+        ..SandboxConfig::default()
+    });
+
+    let msg = d.validate("fixture").unwrap_err().to_string();
+    assert!(msg.contains("network: isolated"), "{msg}");
+}
+
+#[test]
+fn sandbox_on_external_server_is_rejected() {
+    let mut d = deployment(McpServerType::External, Some("https://example.com/mcp"));
+    d.sandbox = Some(SandboxConfig::default());
+
+    let msg = d.validate("remote").unwrap_err().to_string();
+    assert!(msg.contains("sandbox is only valid"), "{msg}");
+}
+
+#[test]
+fn sandbox_rejects_relative_paths_and_empty_or_zero_limits() {
+    let mut d = deployment(McpServerType::Internal, None);
+    d.sandbox = Some(SandboxConfig {
+        filesystem: Some(FilesystemAllowlist {
+            read_only: vec!["usr/lib".to_owned()],
+            read_write: vec![],
+        }),
+        ..SandboxConfig::default()
+    });
+    let msg = d.validate("fixture").unwrap_err().to_string();
+    assert!(msg.contains("must be absolute"), "{msg}");
+
+    d.sandbox = Some(SandboxConfig {
+        limits: Some(ResourceLimits::default()),
+        ..SandboxConfig::default()
+    });
+    let msg = d.validate("fixture").unwrap_err().to_string();
+    assert!(msg.contains("sets no memory"), "{msg}");
+
+    d.sandbox = Some(SandboxConfig {
+        limits: Some(ResourceLimits {
+            pids_max: Some(0),
+            ..ResourceLimits::default()
+        }),
+        ..SandboxConfig::default()
+    });
+    let msg = d.validate("fixture").unwrap_err().to_string();
+    assert!(msg.contains("pids_max must be greater than zero"), "{msg}");
+}
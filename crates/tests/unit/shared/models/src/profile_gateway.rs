@@ -3,10 +3,10 @@ use std::collections::HashMap;
 
 use systemprompt_identifiers::{ModelId, ProviderId, RouteId, SecretName};
 use systemprompt_models::profile::{
-    ApiSurface, GatewayConfig, GatewayConfigSpec, GatewayProfileError, GatewayRoute, GatewayState,
-    OverrideRuleAction, ProviderEntry, ProviderModel, ProviderRegistry, ResponseFormatKind,
-    RouteMatch, SystemPromptRule, WireProtocol, default_resource_audiences, slugify_pattern,
-    synthesize_route_id,
+    ApiSurface, DiscoveredModels, GatewayConfig, GatewayConfigSpec, GatewayProfileError,
+    GatewayRoute, GatewayState, LocalProvider, OverrideRuleAction, ProviderEntry, ProviderModel,
+    ProviderRegistry, ResponseFormatKind, RouteMatch, SystemPromptRule, WireProtocol,
+    default_resource_audiences, slugify_pattern, synthesize_route_id,
 };
 use systemprompt_models::services::ModelPricing;
 use systemprompt_models::wire::canonical::{
@@ -48,6 +48,9 @@ fn route(pattern: &str) -> GatewayRoute {
         extra_headers: HashMap::new(),
         pricing: None,
         when: None,
+        weight: 1,
+        targets: Vec::new(),
+        fallback: None,
     }
 }
 
@@ -80,6 +83,9 @@ fn route_finds_matching_model() {
             extra_headers: HashMap::new(),
             pricing: None,
             when: None,
+            weight: 1,
+            targets: Vec::new(),
+            fallback: None,
         }],
         ..GatewayConfig::default()
     };
@@ -164,6 +170,8 @@ fn registry_with_endpoint(endpoint: &str) -> ProviderRegistry {
                 capabilities: Default::default(),
                 limits: Default::default(),
             }],
+            local: None,
+            discovered: DiscoveredModels::default(),
         }],
     }
 }
@@ -256,6 +264,8 @@ fn provider_entry(name: &str, endpoint: &str, models: Vec<ProviderModel>) -> Pro
         api_key_secret: SecretName::new(name),
         extra_headers: HashMap::new(),
         models,
+        local: None,
+        discovered: DiscoveredModels::default(),
     }
 }
 
@@ -315,6 +325,9 @@ fn route_to(pattern: &str, provider: &str) -> GatewayRoute {
         extra_headers: HashMap::new(),
         pricing: None,
         when: None,
+        weight: 1,
+        targets: Vec::new(),
+        fallback: None,
     };
     r.ensure_id();
     r
@@ -944,6 +957,8 @@ fn priced_registry(models: Vec<ProviderModel>) -> ProviderRegistry {
             api_key_secret: SecretName::new("test"),
             extra_headers: HashMap::new(),
             models,
+            local: None,
+            discovered: DiscoveredModels::default(),
         }],
     }
 }
@@ -1092,6 +1107,19 @@ fn validate_rejects_a_rewrite_route_whose_upstream_model_is_not_in_the_catalog()
     );
 }
 
+/// A local provider bills nothing and its catalog is discovered after boot, so
+/// a route to it reaching no priced model is not a misconfiguration.
+#[test]
+fn validate_accepts_a_route_to_a_local_provider_without_pricing() {
+    let mut registry = priced_registry(Vec::new());
+    registry.providers[0].local = Some(LocalProvider::default());
+    assert!(
+        enabled_gateway(vec![route("llama-*")])
+            .validate(&registry)
+            .is_ok()
+    );
+}
+
 #[test]
 fn validate_skips_pricing_checks_when_the_gateway_is_disabled() {
     let registry = priced_registry(vec![priced_model("claude-opus-5", ModelPricing::default())]);
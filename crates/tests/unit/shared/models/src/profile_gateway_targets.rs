@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use systemprompt_identifiers::{ModelId, ProviderId, RouteId, SecretName};
+use systemprompt_models::profile::{
+    ApiSurface, DiscoveredModels, FallbackTrigger, GatewayConfig, GatewayProfileError,
+    GatewayRoute, ProviderEntry, ProviderModel, ProviderRegistry, RouteFallback, RouteTarget,
+    WireProtocol, order_hops,
+};
+use systemprompt_models::services::ModelPricing;
+
+fn target(provider: &str, weight: u32) -> RouteTarget {
+    RouteTarget {
+        provider: ProviderId::new(provider),
+        upstream_model: None,
+        weight,
+        extra_headers: HashMap::new(),
+    }
+}
+
+fn providers(hops: &[RouteTarget]) -> Vec<&str> {
+    hops.iter().map(|h| h.provider.as_str()).collect()
+}
+
+fn multi_target_route() -> GatewayRoute {
+    GatewayRoute {
+        id: RouteId::new(""),
+        model_pattern: "claude-*".to_owned(),
+        provider: ProviderId::new("anthropic"),
+        upstream_model: None,
+        extra_headers: HashMap::new(),
+        pricing: None,
+        when: None,
+        weight: 1,
+        targets: vec![RouteTarget {
+            upstream_model: Some("claude-sonnet-4-20250514".to_owned()),
+            ..target("bedrock", 1)
+        }],
+        fallback: Some(RouteFallback::default()),
+    }
+}
+
+fn provider_entry(name: &str, model_id: &str) -> ProviderEntry {
+    ProviderEntry {
+        name: ProviderId::new(name),
+        wire: WireProtocol::Anthropic,
+        surface: ApiSurface::Anthropic,
+        endpoint: format!("https://{name}.example.com/v1"),
+        api_key_secret: SecretName::new(name),
+        extra_headers: HashMap::new(),
+        models: vec![ProviderModel {
+            id: ModelId::new(model_id),
+            aliases: Vec::new(),
+            upstream_model: None,
+            pricing: ModelPricing {
+                input_per_million: 3.0,
+                output_per_million: 15.0,
+                ..ModelPricing::default()
+            },
+            capabilities: Default::default(),
+            limits: Default::default(),
+        }],
+        local: None,
+        discovered: DiscoveredModels::default(),
+    }
+}
+
+fn registry() -> ProviderRegistry {
+    ProviderRegistry {
+        providers: vec![
+            provider_entry("anthropic", "claude-sonnet-4-20250514"),
+            provider_entry("bedrock", "claude-sonnet-4-20250514"),
+        ],
+    }
+}
+
+fn config(route: GatewayRoute) -> GatewayConfig {
+    GatewayConfig {
+        enabled: true,
+        routes: vec![route],
+        ..GatewayConfig::default()
+    }
+}
+
+#[test]
+fn order_hops_draws_first_hop_by_weight_and_keeps_declared_order() {
+    let hops = vec![target("a", 1), target("b", 3), target("c", 0)];
+    assert_eq!(
+        providers(&order_hops(hops.clone(), 0, None)),
+        ["a", "b", "c"]
+    );
+    for roll in 1..4 {
+        assert_eq!(
+            providers(&order_hops(hops.clone(), roll, None)),
+            ["b", "a", "c"]
+        );
+    }
+    assert_eq!(providers(&order_hops(hops, 4, None)), ["a", "b", "c"]);
+}
+
+#[test]
+fn order_hops_never_draws_zero_weight_first() {
+    let hops = vec![target("standby", 0), target("main", 2)];
+    for roll in 0..16 {
+        assert_eq!(
+            order_hops(hops.clone(), roll, None)[0].provider.as_str(),
+            "main"
+        );
+    }
+}
+
+#[test]
+fn order_hops_truncates_to_max_attempts() {
+    let hops = vec![target("a", 1), target("b", 1), target("c", 1)];
+    assert_eq!(providers(&order_hops(hops, 0, Some(2))), ["a", "b"]);
+}
+
+#[test]
+fn route_without_fallback_yields_one_hop() {
+    let mut route = multi_target_route();
+    route.fallback = None;
+    assert_eq!(route.hops(0).len(), 1);
+    assert_eq!(providers(&route.hops(1)), ["bedrock"]);
+}
+
+#[test]
+fn route_with_fallback_yields_every_target() {
+    let route = multi_target_route();
+    assert_eq!(providers(&route.hops(0)), ["anthropic", "bedrock"]);
+    assert_eq!(providers(&route.hops(1)), ["bedrock", "anthropic"]);
+}
+
+#[test]
+fn for_target_repoints_connectivity_and_pins_primary_id() {
+    let route = multi_target_route();
+    let primary_id = {
+        let mut r = route.clone();
+        r.ensure_id();
+        r.id
+    };
+    let hop = route.for_target(&route.targets[0]);
+    assert_eq!(hop.provider.as_str(), "bedrock");
+    assert_eq!(
+        hop.upstream_model.as_deref(),
+        Some("claude-sonnet-4-20250514")
+    );
+    assert_eq!(hop.model_pattern, "claude-*");
+    assert_eq!(hop.id, primary_id);
+}
+
+#[test]
+fn for_target_keeps_route_pricing_on_the_primary_only() {
+    let mut route = multi_target_route();
+    route.pricing = Some(ModelPricing {
+        input_per_million: 1.0,
+        output_per_million: 2.0,
+        ..ModelPricing::default()
+    });
+
+    let primary = route.for_target(&route.primary_target());
+    let fallback = route.for_target(&route.targets[0]);
+
+    assert!(primary.pricing.is_some());
+    assert!(
+        fallback.pricing.is_none(),
+        "a fallback hop is priced from its own provider: {:?}",
+        fallback.pricing
+    );
+}
+
+#[test]
+fn validate_prices_a_fallback_hop_from_its_own_provider() {
+    let mut route = multi_target_route();
+    route.pricing = Some(ModelPricing {
+        input_per_million: 1.0,
+        output_per_million: 2.0,
+        ..ModelPricing::default()
+    });
+    route.targets[0].upstream_model = Some("claude-unlisted".to_owned());
+
+    match config(route).validate(&registry()) {
+        Err(GatewayProfileError::RouteReachesNoPricedModel { provider, .. }) => {
+            assert_eq!(provider, "bedrock");
+        },
+        other => panic!("expected RouteReachesNoPricedModel, got {other:?}"),
+    }
+}
+
+#[test]
+fn validate_accepts_multi_target_route() {
+    assert!(config(multi_target_route()).validate(&registry()).is_ok());
+}
+
+#[test]
+fn validate_rejects_target_provider_absent_from_registry() {
+    let mut route = multi_target_route();
+    route.targets.push(target("vertex", 1));
+    match config(route).validate(&registry()) {
+        Err(GatewayProfileError::RouteTargetProviderNotInRegistry { provider, .. }) => {
+            assert_eq!(provider, "vertex");
+        },
+        other => panic!("expected RouteTargetProviderNotInRegistry, got {other:?}"),
+    }
+}
+
+#[test]
+fn validate_rejects_all_zero_weights() {
+    let mut route = multi_target_route();
+    route.weight = 0;
+    route.targets[0].weight = 0;
+    assert!(matches!(
+        config(route).validate(&registry()),
+        Err(GatewayProfileError::RouteTargetsAllZeroWeight { .. })
+    ));
+}
+
+#[test]
+fn validate_rejects_empty_triggers_and_zero_attempts() {
+    let mut route = multi_target_route();
+    route.fallback = Some(RouteFallback {
+        on: Vec::new(),
+        max_attempts: None,
+    });
+    assert!(matches!(
+        config(route.clone()).validate(&registry()),
+        Err(GatewayProfileError::RouteFallbackNoTriggers)
+    ));
+    route.fallback = Some(RouteFallback {
+        on: vec![FallbackTrigger::RateLimited],
+        max_attempts: Some(0),
+    });
+    assert!(matches!(
+        config(route).validate(&registry()),
+        Err(GatewayProfileError::RouteFallbackZeroAttempts)
+    ));
+}
+
+#[test]
+fn route_targets_deserialize_with_defaults() {
+    let yaml = r"
+model_pattern: claude-*
+provider: anthropic
+weight: 3
+targets:
+  - provider: bedrock
+    upstream_model: claude-sonnet-4-20250514
+fallback:
+  on: [rate_limited, server_error]
+  max_attempts: 2
+";
+    let route: GatewayRoute = serde_yaml::from_str(yaml).expect("route parses");
+    assert_eq!(route.weight, 3);
+    assert_eq!(route.targets[0].weight, 1);
+    let fallback = route.fallback.expect("fallback parsed");
+    assert!(fallback.triggers_on(FallbackTrigger::ServerError));
+    assert!(!fallback.triggers_on(FallbackTrigger::Timeout));
+    assert_eq!(fallback.max_attempts, Some(2));
+}
+
+#[test]
+fn fallback_block_defaults_to_every_trigger() {
+    let fallback: RouteFallback = serde_yaml::from_str("{}").expect("empty block parses");
+    for trigger in FallbackTrigger::ALL {
+        assert!(fallback.triggers_on(trigger), "{}", trigger.as_str());
+    }
+}
+
+#[test]
+fn single_target_route_serializes_without_new_keys() {
+    let mut route = multi_target_route();
+    route.targets.clear();
+    route.fallback = None;
+    let yaml = serde_yaml::to_string(&route).expect("route serializes");
+    assert!(!yaml.contains("weight"));
+    assert!(!yaml.contains("targets"));
+    assert!(!yaml.contains("fallback"));
+}
@@ -0,0 +1,193 @@
+use systemprompt_models::profile::{OidcProvider, OidcProviderKind};
+
+fn provider(kind: OidcProviderKind, issuer: &str) -> OidcProvider {
+    serde_yaml::from_str(&format!(
+        "id: corp\nkind: {}\nissuer: {issuer}\nclient_id: systemprompt\n",
+        serde_yaml::to_string(&kind)
+            .expect("kind serializes")
+            .trim()
+    ))
+    .expect("minimal provider parses")
+}
+
+mod parsing {
+    use super::*;
+
+    #[test]
+    fn minimal_entry_defaults_scopes_and_mappings() {
+        let p = provider(
+            OidcProviderKind::Keycloak,
+            "https://sso.example.com/realms/corp",
+        );
+        assert_eq!(p.scopes, vec!["openid", "email", "profile"]);
+        assert!(p.role_mappings.is_empty());
+        assert!(p.allowed_domains.is_empty());
+        assert!(p.client_secret.is_none());
+    }
+
+    #[test]
+    fn kinds_use_snake_case() {
+        let p = provider(
+            OidcProviderKind::GoogleWorkspace,
+            "https://accounts.google.com",
+        );
+        assert_eq!(p.kind, OidcProviderKind::GoogleWorkspace);
+        assert_eq!(
+            serde_yaml::to_string(&OidcProviderKind::GoogleWorkspace)
+                .expect("serializes")
+                .trim(),
+            "google_workspace"
+        );
+    }
+
+    #[test]
+    fn unknown_field_is_rejected() {
+        let result = serde_yaml::from_str::<OidcProvider>(
+            "id: corp\nkind: mock\nissuer: http://localhost:9000\nclient_id: x\nclient_sercet: y\n",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn role_mappings_parse_to_role_lists() {
+        let p: OidcProvider = serde_yaml::from_str(
+            "id: corp\nkind: entra\nissuer: https://login.microsoftonline.com/t/v2.0\n\
+             client_id: x\nrole_mappings:\n  Platform.Admins: [admin]\n  Staff: [user, \
+             analyst]\n",
+        )
+        .expect("parses");
+        assert_eq!(p.role_mappings["Staff"], vec!["user", "analyst"]);
+        let managed: Vec<&str> = p.managed_roles().collect();
+        assert_eq!(managed, vec!["admin", "user", "analyst"]);
+    }
+}
+
+mod endpoints {
+    use super::*;
+
+    #[test]
+    fn keycloak_endpoints_hang_off_the_realm() {
+        let p = provider(
+            OidcProviderKind::Keycloak,
+            "https://sso.example.com/realms/corp/",
+        );
+        assert_eq!(
+            p.authorization_endpoint().as_deref(),
+            Some("https://sso.example.com/realms/corp/protocol/openid-connect/auth")
+        );
+        assert_eq!(
+            p.token_endpoint().as_deref(),
+            Some("https://sso.example.com/realms/corp/protocol/openid-connect/token")
+        );
+        assert_eq!(
+            p.jwks_uri().as_deref(),
+            Some("https://sso.example.com/realms/corp/protocol/openid-connect/certs")
+        );
+    }
+
+    #[test]
+    fn entra_endpoints_drop_the_v2_issuer_suffix() {
+        let p = provider(
+            OidcProviderKind::Entra,
+            "https://login.microsoftonline.com/tenant-id/v2.0",
+        );
+        assert_eq!(
+            p.authorization_endpoint().as_deref(),
+            Some("https://login.microsoftonline.com/tenant-id/oauth2/v2.0/authorize")
+        );
+        assert_eq!(
+            p.token_endpoint().as_deref(),
+            Some("https://login.microsoftonline.com/tenant-id/oauth2/v2.0/token")
+        );
+        assert_eq!(
+            p.jwks_uri().as_deref(),
+            Some("https://login.microsoftonline.com/tenant-id/discovery/v2.0/keys")
+        );
+    }
+
+    #[test]
+    fn google_endpoints_are_fixed() {
+        let p = provider(
+            OidcProviderKind::GoogleWorkspace,
+            "https://accounts.google.com",
+        );
+        assert_eq!(
+            p.jwks_uri().as_deref(),
+            Some("https://www.googleapis.com/oauth2/v3/certs")
+        );
+        assert!(p.token_endpoint().is_some());
+    }
+
+    #[test]
+    fn mock_has_no_derived_endpoints() {
+        let p = provider(OidcProviderKind::Mock, "http://localhost:9000");
+        assert!(p.authorization_endpoint().is_none());
+        assert!(p.token_endpoint().is_none());
+        assert!(p.jwks_uri().is_none());
+    }
+
+    #[test]
+    fn explicit_endpoint_overrides_derivation() {
+        let mut p = provider(
+            OidcProviderKind::Keycloak,
+            "https://sso.example.com/realms/corp",
+        );
+        p.jwks_uri = Some("https://keys.example.com/jwks".to_string());
+        assert_eq!(
+            p.jwks_uri().as_deref(),
+            Some("https://keys.example.com/jwks")
+        );
+    }
+}
+
+mod defaults {
+    use super::*;
+
+    #[test]
+    fn role_claim_follows_the_provider_kind() {
+        let keycloak = provider(
+            OidcProviderKind::Keycloak,
+            "https://sso.example.com/realms/c",
+        );
+        let entra = provider(
+            OidcProviderKind::Entra,
+            "https://login.microsoftonline.com/t/v2.0",
+        );
+        let google = provider(
+            OidcProviderKind::GoogleWorkspace,
+            "https://accounts.google.com",
+        );
+        assert_eq!(keycloak.role_claim(), Some("realm_access.roles"));
+        assert_eq!(entra.role_claim(), Some("roles"));
+        assert_eq!(google.role_claim(), None);
+    }
+
+    #[test]
+    fn configured_role_claim_wins() {
+        let mut p = provider(
+            OidcProviderKind::GoogleWorkspace,
+            "https://accounts.google.com",
+        );
+        p.role_claim = Some("groups".to_string());
+        assert_eq!(p.role_claim(), Some("groups"));
+    }
+
+    #[test]
+    fn display_name_falls_back_to_the_kind_label() {
+        let mut p = provider(
+            OidcProviderKind::Entra,
+            "https://login.microsoftonline.com/t/v2.0",
+        );
+        assert_eq!(p.display_name(), "Microsoft");
+        p.display_name = Some("Contoso".to_string());
+        assert_eq!(p.display_name(), "Contoso");
+    }
+
+    #[test]
+    fn only_the_mock_kind_allows_http() {
+        assert!(OidcProviderKind::Mock.allows_http());
+        assert!(!OidcProviderKind::Keycloak.allows_http());
+        assert!(!OidcProviderKind::Entra.allows_http());
+        assert!(!OidcProviderKind::GoogleWorkspace.allows_http());
+    }
+}
@@ -1,7 +1,7 @@
 use systemprompt_models::auth::JwtAudience;
 use systemprompt_models::profile::{
-    AuthzConfig, AuthzHookConfig, AuthzMode, GovernanceConfig, UNRESTRICTED_ACKNOWLEDGEMENT,
-    default_resource_audiences,
+    AuthzConfig, AuthzHookConfig, AuthzMode, GovernanceConfig, OidcProvider, OidcProviderKind,
+    UNRESTRICTED_ACKNOWLEDGEMENT, default_resource_audiences,
 };
 use systemprompt_models::services::SystemAdminConfig;
 use systemprompt_models::{
@@ -49,6 +49,7 @@ fn security_config() -> SecurityConfig {
         allowed_resource_audiences: default_resource_audiences(),
         allow_registration: true,
         login_page_url: None,
+        oidc_providers: Vec::new(),
         signing_key_path: std::path::PathBuf::from("/tmp/test-signing-key.pem"),
         trusted_issuers: vec![],
         id_jag_ttl_secs: systemprompt_models::profile::DEFAULT_ID_JAG_TTL_SECS,
@@ -472,3 +473,75 @@ mod governance_validation {
         assert!(p.validate().is_ok());
     }
 }
+
+mod oidc_providers {
+    use super::*;
+
+    fn keycloak(id: &str) -> OidcProvider {
+        serde_yaml::from_str(&format!(
+            "id: {id}\nkind: keycloak\nissuer: https://sso.example.com/realms/corp\nclient_id: \
+             systemprompt\n"
+        ))
+        .expect("provider parses")
+    }
+
+    fn with_providers(providers: Vec<OidcProvider>) -> Profile {
+        let mut p = valid_profile();
+        p.security.oidc_providers = providers;
+        p
+    }
+
+    #[test]
+    fn well_formed_provider_passes() {
+        assert!(with_providers(vec![keycloak("corp")]).validate().is_ok());
+    }
+
+    #[test]
+    fn duplicate_ids_rejected() {
+        let p = with_providers(vec![keycloak("corp"), keycloak("corp")]);
+        assert!(errors_of(&p).contains("used by more than one provider"));
+    }
+
+    #[test]
+    fn non_slug_id_rejected() {
+        let p = with_providers(vec![keycloak("Corp SSO")]);
+        assert!(errors_of(&p).contains("security.oidc_providers[0].id"));
+    }
+
+    #[test]
+    fn empty_client_id_rejected() {
+        let mut provider = keycloak("corp");
+        provider.client_id = String::new();
+        let p = with_providers(vec![provider]);
+        assert!(errors_of(&p).contains("client_id is required"));
+    }
+
+    #[test]
+    fn http_issuer_rejected_for_real_providers() {
+        let mut provider = keycloak("corp");
+        provider.issuer = "http://sso.example.com/realms/corp".to_string();
+        let p = with_providers(vec![provider]);
+        assert!(errors_of(&p).contains("security.oidc_providers[0].issuer must be an https URL"));
+    }
+
+    #[test]
+    fn mock_must_spell_out_its_endpoints() {
+        let mut provider = keycloak("mock");
+        provider.kind = OidcProviderKind::Mock;
+        provider.issuer = "http://127.0.0.1:9000".to_string();
+        let msg = errors_of(&with_providers(vec![provider]));
+        assert!(msg.contains("authorization_endpoint is required for a mock provider"));
+        assert!(msg.contains("jwks_uri is required for a mock provider"));
+        assert!(!msg.contains("issuer must be"));
+    }
+
+    #[test]
+    fn mapping_to_no_roles_rejected() {
+        let mut provider = keycloak("corp");
+        provider
+            .role_mappings
+            .insert("staff".to_string(), Vec::new());
+        let p = with_providers(vec![provider]);
+        assert!(errors_of(&p).contains("role_mappings['staff']"));
+    }
+}
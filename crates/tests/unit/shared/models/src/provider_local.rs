@@ -0,0 +1,84 @@
+//! Unit tests for local OpenAI-compatible providers: the `local:` profile
+//! block, and catalog lookups seeing the models discovered at boot alongside
+//! the declared ones.
+
+use systemprompt_identifiers::ModelId;
+use systemprompt_models::bridge::profile;
+use systemprompt_models::profile::{ModelDiscovery, ProviderModel, ProviderRegistry};
+
+const LOCAL_YAML: &str = r"
+- name: ollama
+  wire: openai-chat
+  surface: openai
+  endpoint: http://localhost:11434/v1
+  api_key_secret: ollama
+  local:
+    discovery: ollama
+  models:
+    - id: llama3.2
+";
+
+fn registry() -> ProviderRegistry {
+    serde_yaml::from_str(LOCAL_YAML).expect("local provider parses")
+}
+
+fn discovered(id: &str) -> ProviderModel {
+    ProviderModel {
+        id: ModelId::new(id),
+        aliases: Vec::new(),
+        upstream_model: None,
+        pricing: Default::default(),
+        capabilities: Default::default(),
+        limits: Default::default(),
+    }
+}
+
+#[test]
+fn local_block_parses_with_its_discovery_mode() {
+    let registry = registry();
+    let entry = registry.find_provider("ollama").expect("provider present");
+    assert!(entry.is_local());
+    assert_eq!(
+        entry.local.expect("local block").discovery,
+        ModelDiscovery::Ollama
+    );
+    assert!(!entry.discovered.is_set());
+}
+
+#[test]
+fn local_block_defaults_to_openai_discovery_and_rejects_unknown_fields() {
+    let yaml = LOCAL_YAML.replace("    discovery: ollama\n", "    {}\n");
+    let registry: ProviderRegistry = serde_yaml::from_str(&yaml).expect("empty local block");
+    let entry = registry.find_provider("ollama").expect("provider present");
+    assert_eq!(
+        entry.local.expect("local block").discovery,
+        ModelDiscovery::OpenAi
+    );
+
+    let bogus = LOCAL_YAML.replace("discovery: ollama", "bogus: 1");
+    assert!(serde_yaml::from_str::<ProviderRegistry>(&bogus).is_err());
+}
+
+#[test]
+fn discovered_models_are_found_and_advertised_alongside_declared_ones() {
+    let registry = registry();
+    let entry = registry.find_provider("ollama").expect("provider present");
+    assert!(entry.find_model("qwen2.5-coder:7b").is_none());
+
+    assert!(entry.discovered.set(vec![discovered("qwen2.5-coder:7b")]));
+    assert!(!entry.discovered.set(Vec::new()), "discovery records once");
+
+    assert!(entry.find_model("llama3.2").is_some());
+    assert!(entry.find_model("qwen2.5-coder:7b").is_some());
+    let ids: Vec<&str> = entry.all_models().map(|m| m.id.as_str()).collect();
+    assert_eq!(ids, vec!["llama3.2", "qwen2.5-coder:7b"]);
+}
+
+#[test]
+fn local_provider_reports_configured_without_an_api_key() {
+    let registry = registry();
+    let health = profile::provider_health(&registry, |_| false);
+    assert_eq!(health.len(), 1);
+    assert!(health[0].configured);
+    assert!(health[0].config_issue.is_none());
+}
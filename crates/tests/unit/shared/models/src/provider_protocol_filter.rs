@@ -7,7 +7,7 @@
 
 use systemprompt_identifiers::{ModelId, ProviderId, SecretName};
 use systemprompt_models::profile::{
-    ApiSurface, ProviderEntry, ProviderModel, ProviderRegistry, WireProtocol,
+    ApiSurface, DiscoveredModels, ProviderEntry, ProviderModel, ProviderRegistry, WireProtocol,
 };
 
 const ALL_SURFACES: &[ApiSurface] = &[
@@ -42,6 +42,8 @@ fn provider(
         api_key_secret: SecretName::new(name),
         extra_headers: Default::default(),
         models,
+        local: None,
+        discovered: DiscoveredModels::default(),
     }
 }
 
@@ -625,6 +625,24 @@ mod agents_validator {
         let report = validated(dev_services);
         assert!(!report.has_errors(), "report: {report:?}");
     }
+
+    #[test]
+    fn undefined_delegate_is_reported() {
+        let services = services_with(
+            serde_json::json!({
+                "one": agent_json("one", 5001, serde_json::json!({
+                    "metadata": { "delegates": ["ghost"] }
+                })),
+                "two": agent_json("two", 5002, serde_json::json!({
+                    "metadata": { "delegates": ["one"] }
+                }))
+            }),
+            serde_json::json!({}),
+        );
+        let report = validated(services);
+        assert_eq!(report.errors.len(), 1, "report: {report:?}");
+        assert!(report.errors[0].message.contains("ghost"));
+    }
 }
 
 mod web_validator_paths {
@@ -0,0 +1,104 @@
+//! Embeddings wire-codec tests for the `OpenAI` and Gemini dialects.
+
+use serde_json::json;
+use systemprompt_models::wire::embeddings::{
+    EmbeddingDecodeError, EmbeddingRequest, gemini, openai,
+};
+
+fn request(dimensions: Option<u32>) -> EmbeddingRequest {
+    EmbeddingRequest {
+        model: "client-model".to_owned(),
+        inputs: vec!["alpha".to_owned(), "beta gamma".to_owned()],
+        dimensions,
+    }
+}
+
+#[test]
+fn openai_request_uses_upstream_model_and_float_encoding() {
+    let body = openai::build_request_body(&request(None), "text-embedding-3-small");
+    assert_eq!(body["model"], "text-embedding-3-small");
+    assert_eq!(body["input"], json!(["alpha", "beta gamma"]));
+    assert_eq!(body["encoding_format"], "float");
+    assert!(body.get("dimensions").is_none());
+
+    let sized = openai::build_request_body(&request(Some(256)), "m");
+    assert_eq!(sized["dimensions"], 256);
+}
+
+#[test]
+fn openai_response_orders_by_index_and_reads_prompt_tokens() {
+    let value = json!({
+        "data": [
+            { "index": 1, "embedding": [0.5, 0.25] },
+            { "index": 0, "embedding": [1.0, 2.0] }
+        ],
+        "model": "text-embedding-3-small",
+        "usage": { "prompt_tokens": 4 }
+    });
+    let parsed = openai::parse_response(&value, &request(None)).expect("parse");
+    assert_eq!(parsed.vectors, vec![vec![1.0, 2.0], vec![0.5, 0.25]]);
+    assert_eq!(parsed.input_tokens, 4);
+    assert_eq!(parsed.model, "text-embedding-3-small");
+}
+
+#[test]
+fn openai_response_falls_back_to_request_model() {
+    let value = json!({
+        "data": [ { "embedding": [0.0] }, { "embedding": [1.0] } ]
+    });
+    let parsed = openai::parse_response(&value, &request(None)).expect("parse");
+    assert_eq!(parsed.model, "client-model");
+    assert_eq!(parsed.input_tokens, 0);
+}
+
+#[test]
+fn openai_response_rejects_missing_data_and_count_mismatch() {
+    assert_eq!(
+        openai::parse_response(&json!({}), &request(None)).expect_err("no data"),
+        EmbeddingDecodeError::MissingField("data")
+    );
+    let short = json!({ "data": [ { "index": 0, "embedding": [0.0] } ] });
+    assert_eq!(
+        openai::parse_response(&short, &request(None)).expect_err("short"),
+        EmbeddingDecodeError::CountMismatch {
+            expected: 2,
+            got: 1
+        }
+    );
+}
+
+#[test]
+fn gemini_request_renders_one_entry_per_input() {
+    assert_eq!(
+        gemini::upstream_path("text-embedding-004"),
+        "/models/text-embedding-004:batchEmbedContents"
+    );
+    let body = gemini::build_request_body(&request(Some(64)), "text-embedding-004");
+    let requests = body["requests"].as_array().expect("requests");
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0]["model"], "models/text-embedding-004");
+    assert_eq!(requests[1]["content"]["parts"][0]["text"], "beta gamma");
+    assert_eq!(requests[0]["outputDimensionality"], 64);
+}
+
+#[test]
+fn gemini_response_estimates_input_tokens() {
+    let value = json!({ "embeddings": [ { "values": [0.1] }, { "values": [0.2] } ] });
+    let parsed = gemini::parse_response(&value, &request(None)).expect("parse");
+    assert_eq!(parsed.vectors.len(), 2);
+    assert_eq!(parsed.model, "client-model");
+    assert_eq!(
+        parsed.input_tokens,
+        gemini::estimate_input_tokens(&request(None).inputs)
+    );
+    assert!(parsed.input_tokens > 0);
+}
+
+#[test]
+fn gemini_response_rejects_non_numeric_values() {
+    let value = json!({ "embeddings": [ { "values": ["x"] }, { "values": [0.2] } ] });
+    assert_eq!(
+        gemini::parse_response(&value, &request(None)).expect_err("bad values"),
+        EmbeddingDecodeError::MissingField("embeddings[].values")
+    );
+}